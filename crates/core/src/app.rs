@@ -33,9 +33,31 @@
 //! returns, skipping the present on [`Flow::Idle`](crate::app::Flow::Idle) or when `update` already presented itself. The
 //! low-level [`poll`](crate::terminal::Terminal::poll) / [`present`](crate::terminal::Terminal::present) API remains
 //! available for turn-based games and headless tests.
+//!
+//! A game whose simulation has to tick at a stable rate regardless of render speed implements
+//! [`FixedApp`](crate::app::FixedApp) instead and wraps it in [`FixedStep`](crate::app::FixedStep),
+//! which is itself an [`App`](crate::app::App): every driver above (and
+//! [`TestHarness`](crate::testing::TestHarness)) runs it unchanged, with no fixed-step code of its
+//! own.
+//!
+//! An app that lives inside an async runtime (a networked client on tokio, say) drives the same
+//! [`App`](crate::app::App) with [`run_async`](crate::app::run_async)/
+//! [`run_on_async`](crate::app::run_on_async) instead: a future that awaits input rather than
+//! blocking the thread on it, with channel-fed [`EventSource`](crate::terminal::EventSource)s
+//! merged into the terminal's own events. See [`Terminal::events`](crate::terminal::Terminal::events)
+//! for the stream underneath.
 
 use crate::backend::Backend;
+use crate::frames::FrameClock;
+#[cfg(feature = "std")]
+use crate::terminal::EventSource;
 use crate::terminal::Terminal;
+#[cfg(feature = "std")]
+use core::future::Future;
+#[cfg(feature = "std")]
+use core::pin::Pin;
+#[cfg(feature = "std")]
+use core::task::{Context, Poll};
 use core::time::Duration;
 
 /// Whether the game loop should continue or stop after a frame, and whether that frame renders.
@@ -184,6 +206,8 @@ pub enum Idle {
 pub struct RunOptions {
     target_fps: Option<u32>,
     idle: Idle,
+    fixed_rate: u32,
+    max_catch_up: u32,
 }
 
 impl RunOptions {
@@ -202,6 +226,8 @@ impl RunOptions {
         Self {
             target_fps: Some(target_fps),
             idle: Idle::Spin,
+            fixed_rate: DEFAULT_FIXED_RATE,
+            max_catch_up: DEFAULT_MAX_CATCH_UP,
         }
     }
 
@@ -279,18 +305,235 @@ impl RunOptions {
     pub const fn idle(&self) -> Idle {
         self.idle
     }
+
+    /// Sets how many [`FixedApp::fixed_update`](crate::app::FixedApp::fixed_update) steps per
+    /// second a [`FixedStep`](crate::app::FixedStep) built from these options simulates.
+    ///
+    /// Independent of [`target_fps`](Self::target_fps), which caps how often frames render: a
+    /// 30 Hz simulation rendered at 144 fps interpolates between steps with
+    /// [`FixedApp::render`](crate::app::FixedApp::render)'s `alpha`, and a 120 Hz one rendered at
+    /// 30 fps runs four steps per frame. Defaults to 60. Only [`FixedStep`](crate::app::FixedStep)
+    /// reads this; a plain [`App`](crate::app::App) never sees it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero.
+    #[must_use]
+    pub const fn with_fixed_rate(mut self, hz: u32) -> Self {
+        assert!(hz > 0, "fixed rate must be non-zero");
+        self.fixed_rate = hz;
+        self
+    }
+
+    /// Returns the configured [`fixed_rate`](Self::with_fixed_rate), in steps per second.
+    #[must_use]
+    pub const fn fixed_rate(&self) -> u32 {
+        self.fixed_rate
+    }
+
+    /// Sets the most [`FixedApp::fixed_update`](crate::app::FixedApp::fixed_update) steps a
+    /// [`FixedStep`](crate::app::FixedStep) runs in one frame to catch up after a slow one.
+    ///
+    /// Time beyond the cap is dropped rather than simulated later, so a long stall (a debugger
+    /// pause, a dragged window) costs at most this many steps instead of a "spiral of death" where
+    /// each frame falls further behind. Defaults to 5, matching
+    /// [`FrameClock`](crate::frames::FrameClock)'s own default; `0` is treated as `1`.
+    #[must_use]
+    pub const fn with_max_catch_up(mut self, steps: u32) -> Self {
+        self.max_catch_up = steps;
+        self
+    }
+
+    /// Returns the configured [`max_catch_up`](Self::with_max_catch_up) step count.
+    #[must_use]
+    pub const fn max_catch_up(&self) -> u32 {
+        self.max_catch_up
+    }
 }
 
+/// [`RunOptions::fixed_rate`](crate::app::RunOptions::fixed_rate)'s default.
+const DEFAULT_FIXED_RATE: u32 = 60;
+
+/// [`RunOptions::max_catch_up`](crate::app::RunOptions::max_catch_up)'s default.
+const DEFAULT_MAX_CATCH_UP: u32 = 5;
+
 impl Default for RunOptions {
     /// Event-driven, uncapped, blocks indefinitely on [`Flow::Idle`](crate::app::Flow::Idle): see [`run_on`](crate::app::run_on).
     fn default() -> Self {
         Self {
             target_fps: None,
             idle: Idle::Block { wake: None },
+            fixed_rate: DEFAULT_FIXED_RATE,
+            max_catch_up: DEFAULT_MAX_CATCH_UP,
         }
     }
 }
 
+/// A game split into a fixed-rate simulation step and a per-frame render, driven through
+/// [`FixedStep`](crate::app::FixedStep).
+///
+/// [`App::update`](crate::app::App::update) runs once per rendered frame, so anything it simulates
+/// from [`Frame::delta`](crate::app::Frame::delta) speeds up, slows down, or jitters with the frame
+/// rate. `FixedApp` separates the two: [`fixed_update`](Self::fixed_update) always advances the
+/// simulation by the same `dt`, zero or more times per frame, and [`render`](Self::render) draws
+/// once per frame with how far the simulation has got toward its next step.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use retroglyph_core::app::{App, FixedApp, FixedStep, Flow, Frame, RunOptions};
+/// use retroglyph_core::backend::{Backend, Headless};
+/// use retroglyph_core::color::Style;
+/// use retroglyph_core::terminal::Terminal;
+///
+/// struct Ball {
+///     x: f64,
+///     prev_x: f64,
+/// }
+///
+/// impl<B: Backend> FixedApp<B> for Ball {
+///     fn fixed_update(&mut self, _term: &mut Terminal<B>, dt: Duration) -> Flow {
+///         self.prev_x = self.x;
+///         self.x += 10.0 * dt.as_secs_f64(); // 10 cells per second
+///         Flow::Continue
+///     }
+///
+///     fn render(&mut self, term: &mut Terminal<B>, _frame: &Frame, alpha: f64) -> Flow {
+///         let x = self.prev_x + (self.x - self.prev_x) * alpha;
+///         term.surface().put((x as u16, 0), 'o', Style::default());
+///         Flow::Continue
+///     }
+/// }
+///
+/// let options = RunOptions::animated(60).with_fixed_rate(100);
+/// let mut app = FixedStep::new(Ball { x: 0.0, prev_x: 0.0 }, options);
+/// let mut term = Terminal::new(Headless::new(20, 1));
+/// // 25ms frames against a 100 Hz simulation: two or three steps per frame, ten per 100ms.
+/// for n in 0..20 {
+///     app.update(&mut term, &Frame::new(Duration::from_millis(25), n));
+/// }
+/// assert!((app.app().x - 5.0).abs() < 1e-9);
+/// ```
+pub trait FixedApp<B: Backend> {
+    /// Runs once, before the first step or render; see [`App::init`](crate::app::App::init).
+    fn init(&mut self, _term: &mut Terminal<B>) {}
+
+    /// Advances the simulation by exactly `dt`, the step length
+    /// [`RunOptions::fixed_rate`](crate::app::RunOptions::fixed_rate) implies.
+    ///
+    /// Called as many times per frame as whole steps have accumulated (zero on a frame shorter
+    /// than one step, up to [`RunOptions::max_catch_up`](crate::app::RunOptions::max_catch_up)
+    /// after a long one), before that frame's [`render`](Self::render). Input read here through
+    /// `term` is whatever has arrived since the previous step; events left undrained on a frame
+    /// with no step stay buffered for the next one.
+    ///
+    /// Returning [`Flow::Exit`](crate::app::Flow::Exit) stops the loop immediately, skipping any
+    /// remaining steps and the frame's render. Every other [`Flow`](crate::app::Flow) keeps
+    /// stepping; only [`render`](Self::render)'s return decides whether the frame presents.
+    fn fixed_update(&mut self, term: &mut Terminal<B>, dt: Duration) -> Flow;
+
+    /// Draws one frame, after this frame's [`fixed_update`](Self::fixed_update) steps.
+    ///
+    /// `alpha`, in `0.0..1.0`, is how much of the next step has already elapsed (see
+    /// [`FrameClock::alpha`](crate::frames::FrameClock::alpha)): blend each moving thing's
+    /// previous and current simulated state by it for motion that stays smooth when the render
+    /// rate and the simulation rate differ. The returned [`Flow`](crate::app::Flow) means what
+    /// [`App::update`](crate::app::App::update)'s does.
+    ///
+    /// Returning [`Flow::Idle`](crate::app::Flow::Idle) under an event-driven
+    /// [`RunOptions`](crate::app::RunOptions) blocks the loop on input, and the simulation with
+    /// it; the first frame after waking then catches up at most
+    /// [`RunOptions::max_catch_up`](crate::app::RunOptions::max_catch_up) steps. Drive a
+    /// simulation that must keep running with [`RunOptions::animated`](crate::app::RunOptions::animated).
+    fn render(&mut self, term: &mut Terminal<B>, frame: &Frame, alpha: f64) -> Flow;
+}
+
+/// Runs a [`FixedApp`](crate::app::FixedApp) as an [`App`](crate::app::App), splitting each frame's
+/// [`Frame::delta`](crate::app::Frame::delta) into fixed-length simulation steps.
+///
+/// Because it is an ordinary [`App`](crate::app::App), every driver runs it:
+/// [`run_on_with`](crate::app::run_on_with), `retroglyph-window`'s `run_app`, the wasm entry points,
+/// and [`TestHarness`](crate::testing::TestHarness), whose
+/// [`with_step_delta`](crate::testing::TestHarness::with_step_delta) makes the step count per frame
+/// fully deterministic. The simulation rate and catch-up cap come from the
+/// [`RunOptions`](crate::app::RunOptions) passed to [`new`](Self::new); pass the same options to the
+/// driver so its render pacing matches.
+///
+/// See [`FixedApp`](crate::app::FixedApp) for an example.
+#[derive(Debug, Clone)]
+pub struct FixedStep<A> {
+    app: A,
+    clock: FrameClock,
+}
+
+impl<A> FixedStep<A> {
+    /// Wraps `app`, stepping it at `options`'
+    /// [`fixed_rate`](crate::app::RunOptions::fixed_rate) with at most
+    /// [`max_catch_up`](crate::app::RunOptions::max_catch_up) steps per frame.
+    #[must_use]
+    pub fn new(app: A, options: RunOptions) -> Self {
+        Self {
+            app,
+            clock: FrameClock::new(options.fixed_rate()).with_max_catch_up(options.max_catch_up()),
+        }
+    }
+
+    /// The length of one simulation step, the `dt` every
+    /// [`fixed_update`](crate::app::FixedApp::fixed_update) call receives.
+    #[must_use]
+    pub const fn step(&self) -> Duration {
+        self.clock.step()
+    }
+
+    /// Returns the wrapped app.
+    #[must_use]
+    pub const fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// Returns the wrapped app, mutably.
+    #[must_use]
+    pub const fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// Unwraps the app, discarding any partially accumulated step.
+    #[must_use]
+    pub fn into_inner(self) -> A {
+        self.app
+    }
+
+    /// Drops any partially accumulated step, so the next frame starts a fresh one.
+    ///
+    /// Call after resuming from a pause the app manages itself, where the time spent paused
+    /// should not be simulated at all.
+    pub const fn reset(&mut self) {
+        self.clock.reset();
+    }
+}
+
+impl<B, A> App<B> for FixedStep<A>
+where
+    B: Backend,
+    A: FixedApp<B>,
+{
+    fn init(&mut self, term: &mut Terminal<B>) {
+        self.app.init(term);
+    }
+
+    fn update(&mut self, term: &mut Terminal<B>, frame: &Frame) -> Flow {
+        self.clock.advance(frame.delta);
+        let dt = self.clock.step();
+        while self.clock.tick() {
+            if self.app.fixed_update(term, dt) == Flow::Exit {
+                return Flow::Exit;
+            }
+        }
+        self.app.render(term, frame, self.clock.alpha())
+    }
+}
+
 /// Drive an [`App`](crate::app::App) with a blocking loop until it returns [`Flow::Exit`](crate::app::Flow::Exit), paced by `options`.
 ///
 /// The zero-config [`run_on`](crate::app::run_on) is equivalent to `run_on_with(term, app,
@@ -537,6 +780,190 @@ where
     run_on_with(Terminal::new(backend), app, options)
 }
 
+/// Builds a [`Terminal`](crate::terminal::Terminal) over `backend` and drives `app` from a
+/// future: the async counterpart to [`run`](crate::app::run), with no extra event sources.
+///
+/// See [`run_on_async`](crate::app::run_on_async) for the loop itself.
+///
+/// # Errors
+///
+/// Returns the backend's error if the automatic `present()` call fails while `app` is running.
+#[cfg(feature = "std")]
+// The future is exactly as `Send` as `B` and `A` are: nothing else is held across an `.await`.
+// A `Send` bound here would only lock out `!Send` backends (and every single-threaded executor)
+// for no gain.
+#[allow(clippy::future_not_send)]
+pub async fn run_async<B, A>(backend: B, app: A) -> Result<(), B::Error>
+where
+    B: Backend,
+    A: App<B>,
+{
+    run_on_async(Terminal::new(backend), app, ()).await
+}
+
+/// Drives an [`App`](crate::app::App) from an executor-agnostic future until it returns
+/// [`Flow::Exit`](crate::app::Flow::Exit), merging `source`'s events into the terminal's own
+/// input.
+///
+/// The same contract as [`run_on`](crate::app::run_on) (see
+/// [`App::update`](crate::app::App::update)), except that nothing blocks the thread:
+///
+/// - on [`Flow::Idle`](crate::app::Flow::Idle), the future awaits the next event from the
+///   terminal ([`Terminal::poll_input`](crate::terminal::Terminal::poll_input)) or from `source`,
+///   instead of blocking in [`wait_for_input`](crate::terminal::Terminal::wait_for_input);
+/// - after any other frame, it yields to the executor once before the next `update`, so other
+///   tasks (the network client feeding `source`, say) keep running alongside a continuously
+///   rendering app.
+///
+/// Events from `source` are handed to the terminal's queue (see
+/// [`requeue_events`](crate::terminal::Terminal::requeue_events)) as they become ready, so
+/// `update` reads them with the usual [`poll`](crate::terminal::Terminal::poll)/
+/// [`drain_events`](crate::terminal::Terminal::drain_events) calls. A source that closes is simply
+/// no longer polled; pass `()` for none.
+///
+/// There is no [`RunOptions`](crate::app::RunOptions) here: frame pacing and an idle wake-up
+/// interval both need a timer, which only the executor has. Use the executor's own interval as
+/// an [`EventSource`](crate::terminal::EventSource) instead, returning `Idle` between ticks.
+///
+/// # Examples
+///
+/// ```
+/// use core::pin::pin;
+/// use core::task::{Context, Poll, Waker};
+/// use retroglyph_core::app::{App, Flow, Frame, run_on_async};
+/// use retroglyph_core::backend::Backend;
+/// use retroglyph_core::event::Event;
+/// use retroglyph_core::terminal::{Terminal, from_fn};
+/// use retroglyph_core::backend::Headless;
+///
+/// struct UntilCustom;
+///
+/// impl<B: Backend> App<B> for UntilCustom {
+///     fn update(&mut self, term: &mut Terminal<B>, _frame: &Frame) -> Flow {
+///         if term.drain_events().any(|e| e == Event::Custom(42)) {
+///             Flow::Exit
+///         } else {
+///             Flow::Idle
+///         }
+///     }
+/// }
+///
+/// let mut sent = false;
+/// let source = from_fn(move |_cx| {
+///     let ready = !sent;
+///     sent = true;
+///     if ready { Poll::Ready(Some(Event::Custom(42))) } else { Poll::Pending }
+/// });
+/// let run = pin!(run_on_async(Terminal::new(Headless::new(4, 1)), UntilCustom, source));
+///
+/// // Any executor works; this one just polls until done.
+/// let mut run = run;
+/// let mut cx = Context::from_waker(Waker::noop());
+/// while run.as_mut().poll(&mut cx).is_pending() {}
+/// ```
+///
+/// # Errors
+///
+/// Returns the backend's error if the automatic `present()` call fails. The loop stops and the
+/// terminal is dropped (running backend teardown) before the error is returned.
+#[cfg(feature = "std")]
+// See `run_async` above.
+#[allow(clippy::future_not_send)]
+pub async fn run_on_async<B, A, S>(
+    mut term: Terminal<B>,
+    mut app: A,
+    mut source: S,
+) -> Result<(), B::Error>
+where
+    B: Backend,
+    A: App<B>,
+    S: EventSource,
+{
+    app.init(&mut term);
+    let mut frame_count = 0u64;
+    let mut last = std::time::Instant::now();
+    let mut source_open = true;
+    loop {
+        // Hand over everything the source already has ready, without waiting on it.
+        if source_open {
+            source_open =
+                core::future::poll_fn(|cx| Poll::Ready(admit_ready(&mut term, &mut source, cx)))
+                    .await;
+        }
+        let now = std::time::Instant::now();
+        let delta = now.duration_since(last);
+        last = now;
+        let frame = Frame::new(delta, frame_count);
+        frame_count = frame_count.wrapping_add(1);
+        let present_count_before = term.present_count();
+        let flow = app.update(&mut term, &frame);
+        if flow == Flow::Exit {
+            return Ok(());
+        }
+        // See `run_on_with` for why this checks `present_count` rather than presenting twice.
+        if flow != Flow::Idle && term.present_count() == present_count_before {
+            term.present()?;
+        }
+        if flow == Flow::Idle {
+            core::future::poll_fn(|cx| {
+                if term.poll_input(cx).is_ready() {
+                    return Poll::Ready(());
+                }
+                if source_open {
+                    match source.poll_event(cx) {
+                        Poll::Ready(Some(event)) => {
+                            term.requeue_events([event]);
+                            return Poll::Ready(());
+                        }
+                        Poll::Ready(None) => source_open = false,
+                        Poll::Pending => {}
+                    }
+                }
+                Poll::Pending
+            })
+            .await;
+        } else {
+            YieldNow(false).await;
+        }
+    }
+}
+
+/// Moves every event `source` has ready into `term`'s queue; returns whether `source` is still
+/// open.
+#[cfg(feature = "std")]
+fn admit_ready<B: Backend>(
+    term: &mut Terminal<B>,
+    source: &mut impl EventSource,
+    cx: &mut Context<'_>,
+) -> bool {
+    loop {
+        match source.poll_event(cx) {
+            Poll::Ready(Some(event)) => term.requeue_events([event]),
+            Poll::Ready(None) => return false,
+            Poll::Pending => return true,
+        }
+    }
+}
+
+/// Pending exactly once (waking itself first), so the executor gets a turn between two
+/// continuously-rendered frames.
+#[cfg(feature = "std")]
+struct YieldNow(bool);
+
+#[cfg(feature = "std")]
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -763,6 +1190,222 @@ mod tests {
         run_with(backend, app, RunOptions::animated(1000)).expect("run_with");
     }
 
+    /// Counts fixed steps and renders, recording the `dt` and `alpha` each last saw.
+    #[derive(Default)]
+    struct StepCounter {
+        steps: u32,
+        renders: u32,
+        last_dt: Duration,
+        last_alpha: f64,
+        exit_after_steps: Option<u32>,
+    }
+
+    impl FixedApp<Headless> for StepCounter {
+        fn fixed_update(&mut self, _term: &mut Terminal<Headless>, dt: Duration) -> Flow {
+            self.steps += 1;
+            self.last_dt = dt;
+            if self.exit_after_steps == Some(self.steps) {
+                Flow::Exit
+            } else {
+                Flow::Continue
+            }
+        }
+
+        fn render(&mut self, _term: &mut Terminal<Headless>, _frame: &Frame, alpha: f64) -> Flow {
+            self.renders += 1;
+            self.last_alpha = alpha;
+            Flow::Continue
+        }
+    }
+
+    fn update_fixed(app: &mut FixedStep<StepCounter>, term: &mut Terminal<Headless>, ms: u64) {
+        let frame = Frame::new(Duration::from_millis(ms), 0);
+        assert_eq!(app.update(term, &frame), Flow::Continue);
+    }
+
+    #[test]
+    fn fixed_step_runs_whole_steps_and_carries_the_remainder_as_alpha() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let options = RunOptions::default().with_fixed_rate(100);
+        let mut app = FixedStep::new(StepCounter::default(), options);
+
+        update_fixed(&mut app, &mut term, 25);
+        assert_eq!(app.app().steps, 2);
+        assert_eq!(app.app().renders, 1);
+        assert_eq!(app.app().last_dt, Duration::from_millis(10));
+        assert!((app.app().last_alpha - 0.5).abs() < 1e-6);
+
+        // The carried 5ms plus this frame's 5ms make one more whole step.
+        update_fixed(&mut app, &mut term, 5);
+        assert_eq!(app.app().steps, 3);
+        assert_eq!(app.app().renders, 2);
+        assert!(app.app().last_alpha.abs() < 1e-6);
+    }
+
+    #[test]
+    fn fixed_step_renders_without_stepping_on_a_short_frame() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let mut app = FixedStep::new(StepCounter::default(), RunOptions::default());
+        update_fixed(&mut app, &mut term, 1);
+        assert_eq!(app.app().steps, 0);
+        assert_eq!(app.app().renders, 1);
+    }
+
+    #[test]
+    fn fixed_step_caps_catch_up_at_max_catch_up() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let options = RunOptions::default()
+            .with_fixed_rate(100)
+            .with_max_catch_up(3);
+        let mut app = FixedStep::new(StepCounter::default(), options);
+        update_fixed(&mut app, &mut term, 10_000);
+        assert_eq!(app.app().steps, 3);
+    }
+
+    #[test]
+    fn fixed_step_exit_stops_stepping_and_skips_render() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let options = RunOptions::default().with_fixed_rate(100);
+        let mut app = FixedStep::new(
+            StepCounter {
+                exit_after_steps: Some(2),
+                ..StepCounter::default()
+            },
+            options,
+        );
+        let frame = Frame::new(Duration::from_millis(45), 0);
+        assert_eq!(app.update(&mut term, &frame), Flow::Exit);
+        assert_eq!(app.app().steps, 2);
+        assert_eq!(app.app().renders, 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn run_on_with_drives_a_fixed_step_app_to_exit() {
+        let term = Terminal::new(Headless::new(2, 1));
+        let options = RunOptions::animated(1000).with_fixed_rate(1000);
+        let app = FixedStep::new(
+            StepCounter {
+                exit_after_steps: Some(3),
+                ..StepCounter::default()
+            },
+            options,
+        );
+        run_on_with(term, app, options).expect("run_on_with");
+    }
+
+    #[test]
+    fn run_options_fixed_rate_defaults_and_setters() {
+        let default = RunOptions::default();
+        assert_eq!(default.fixed_rate(), 60);
+        assert_eq!(default.max_catch_up(), 5);
+        assert_eq!(RunOptions::animated(30).fixed_rate(), 60);
+
+        let options = RunOptions::animated(144)
+            .with_fixed_rate(30)
+            .with_max_catch_up(8);
+        assert_eq!(options.fixed_rate(), 30);
+        assert_eq!(options.max_catch_up(), 8);
+        assert_eq!(options.target_fps(), Some(144));
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed rate must be non-zero")]
+    fn run_options_with_fixed_rate_zero_panics() {
+        let _ = RunOptions::default().with_fixed_rate(0);
+    }
+
+    /// Polls `future` to completion on the current thread, counting how many polls it took.
+    #[cfg(feature = "std")]
+    fn block_on<F: Future>(future: F) -> (F::Output, u32) {
+        let mut future = core::pin::pin!(future);
+        let mut cx = Context::from_waker(core::task::Waker::noop());
+        let mut polls = 0;
+        loop {
+            polls += 1;
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return (output, polls);
+            }
+        }
+    }
+
+    /// Idles until it has seen `Custom(1)` from a source and then a key from the terminal, then
+    /// exits, recording how many frames that took.
+    #[cfg(feature = "std")]
+    #[derive(Default)]
+    struct AwaitsCustomThenKey {
+        custom: bool,
+        frames: std::rc::Rc<core::cell::Cell<u32>>,
+    }
+
+    #[cfg(feature = "std")]
+    impl App<Headless> for AwaitsCustomThenKey {
+        fn update(&mut self, term: &mut Terminal<Headless>, _frame: &Frame) -> Flow {
+            self.frames.set(self.frames.get() + 1);
+            for event in term.drain_events() {
+                match event {
+                    Event::Custom(1) => self.custom = true,
+                    Event::Key(_) if self.custom => return Flow::Exit,
+                    _ => {}
+                }
+            }
+            if self.custom {
+                // Only the source's event has arrived: queue the key a user would type next.
+                term.backend_mut().push_event(Event::Key(KeyEvent::new(
+                    KeyCode::Enter,
+                    KeyModifiers::NONE,
+                )));
+            }
+            Flow::Idle
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn run_on_async_idles_until_a_source_or_terminal_event_arrives() {
+        let term = Terminal::new(Headless::new(2, 1));
+        let mut polls_until_ready = 3;
+        let source = crate::terminal::from_fn(move |_cx| {
+            if polls_until_ready == 0 {
+                return Poll::Ready(None);
+            }
+            polls_until_ready -= 1;
+            if polls_until_ready == 0 {
+                Poll::Ready(Some(Event::Custom(1)))
+            } else {
+                Poll::Pending
+            }
+        });
+        let app = AwaitsCustomThenKey::default();
+        let frames = std::rc::Rc::clone(&app.frames);
+        let (result, _) = block_on(run_on_async(term, app, source));
+        result.expect("run_on_async");
+        // Idle frames only ran when something arrived: the first frame, the custom event's,
+        // and the key's.
+        assert_eq!(frames.get(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn run_on_async_yields_to_the_executor_between_continue_frames() {
+        struct ExitsOnThirdFrame(u32);
+        impl App<Headless> for ExitsOnThirdFrame {
+            fn update(&mut self, _term: &mut Terminal<Headless>, _frame: &Frame) -> Flow {
+                self.0 += 1;
+                if self.0 == 3 {
+                    Flow::Exit
+                } else {
+                    Flow::Continue
+                }
+            }
+        }
+
+        let (result, polls) = block_on(run_async(Headless::new(2, 1), ExitsOnThirdFrame(0)));
+        result.expect("run_async");
+        // One pending yield after each of the two `Continue` frames, then ready.
+        assert_eq!(polls, 3);
+    }
+
     #[test]
     fn run_options_animated_sets_fields() {
         let animated = RunOptions::animated(30);
@@ -834,8 +1477,8 @@ mod tests {
         let term = Terminal::new(Headless::new(2, 1));
         let app = IdleThenExit { frames: 0 };
         let options = RunOptions {
-            target_fps: None,
             idle: Idle::Spin,
+            ..RunOptions::default()
         };
         run_on_with(term, app, options).expect("run_on_with");
     }
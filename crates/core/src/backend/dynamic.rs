@@ -0,0 +1,463 @@
+//! Object-safe backends: [`DynBackend`](crate::backend::DynBackend), so a backend picked at
+//! runtime can sit behind one type, `Box<dyn DynBackend>`.
+//!
+//! [`Backend`](crate::backend::Backend) itself can't be a trait object:
+//! [`Output::draw_layers`](crate::backend::Output::draw_layers) is generic over its iterator, and
+//! [`Output::Error`](crate::backend::Output::Error) differs per backend. `DynBackend` mirrors the
+//! four facets with the iterator taken as `&mut dyn Iterator` and every error erased into a
+//! [`DynBackendError`](crate::backend::DynBackendError); every `'static` backend implements it
+//! for free, and `Box<dyn DynBackend>` implements [`Output`], [`Input`], [`Cursor`], and
+//! [`Clipboard`] in turn, so `Terminal<Box<dyn DynBackend>>` is an ordinary terminal and any
+//! `impl<B: Backend> App<B>` runs on it unchanged.
+//!
+//! # Examples
+//!
+//! ```
+//! use retroglyph_core::backend::{DynBackend, Headless};
+//! use retroglyph_core::color::Style;
+//! use retroglyph_core::terminal::Terminal;
+//!
+//! // Chosen at runtime in a real program; any backend boxes the same way.
+//! let backend: Box<dyn DynBackend> = Box::new(Headless::new(8, 2));
+//! let mut term = Terminal::new(backend);
+//! term.draw(|s| s.put((0, 0), '@', Style::default()))?;
+//!
+//! let headless = term.backend().downcast_ref::<Headless>().expect("boxed a Headless");
+//! assert!(headless.format_view().starts_with('@'));
+//! # Ok::<(), retroglyph_core::backend::DynBackendError>(())
+//! ```
+
+use crate::backend::{Backend, BackendError, Clipboard, ClipboardError, Compositing, Cursor};
+use crate::backend::{CursorStyle, DrawCell};
+use crate::backend::{Input, Output};
+use crate::event::Event;
+use crate::grid::{Pos, Size};
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::any::Any;
+use core::fmt;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+/// An object-safe [`Backend`](crate::backend::Backend): what `Box<dyn DynBackend>` erases a
+/// concrete backend to.
+///
+/// Implemented for every `'static` backend whose error is `Send + Sync`, so there is nothing to
+/// implement by hand; box the backend and use `Box<dyn DynBackend>` wherever a [`Backend`] is
+/// expected. Each method forwards to the facet method of the same name without the `dyn_`
+/// prefix (the prefix keeps calls on a concrete backend unambiguous with both traits in scope).
+/// Use `downcast_ref`/`downcast_mut` on a `dyn DynBackend` to reach the
+/// concrete backend's own API again.
+pub trait DynBackend: Any {
+    /// [`Output::draw`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own error, erased.
+    fn dyn_draw(
+        &mut self,
+        content: &mut dyn Iterator<Item = DrawCell<'_>>,
+    ) -> Result<(), DynBackendError>;
+
+    /// [`Output::draw_layers`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own error, erased.
+    fn dyn_draw_layers(
+        &mut self,
+        content: &mut dyn Iterator<Item = DrawCell<'_>>,
+    ) -> Result<(), DynBackendError>;
+
+    /// [`Output::compositing`].
+    fn dyn_compositing(&self) -> Compositing;
+
+    /// [`Output::flush`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own error, erased.
+    fn dyn_flush(&mut self) -> Result<(), DynBackendError>;
+
+    /// [`Output::size`].
+    fn dyn_size(&self) -> Size;
+
+    /// [`Output::clear`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own error, erased.
+    fn dyn_clear(&mut self) -> Result<(), DynBackendError>;
+
+    /// [`Output::resize`].
+    fn dyn_resize(&mut self, size: Size);
+
+    /// [`Input::poll_event`].
+    fn dyn_poll_event(&mut self, timeout: Duration) -> Option<Event>;
+
+    /// [`Input::push_event`].
+    fn dyn_push_event(&mut self, event: Event);
+
+    /// [`Input::poll_next_event`].
+    fn dyn_poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event>;
+
+    /// [`Input::event_sender`].
+    #[cfg(feature = "std")]
+    fn dyn_event_sender(&mut self) -> Option<crate::backend::EventSender>;
+
+    /// [`Cursor::set_cursor_visible`].
+    fn dyn_set_cursor_visible(&mut self, visible: bool);
+
+    /// [`Cursor::set_cursor_position`].
+    fn dyn_set_cursor_position(&mut self, position: Pos);
+
+    /// [`Cursor::set_cursor_style`].
+    fn dyn_set_cursor_style(&mut self, style: CursorStyle);
+
+    /// [`Clipboard::clipboard_text`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own [`ClipboardError`].
+    fn dyn_clipboard_text(&mut self) -> Result<String, ClipboardError>;
+
+    /// [`Clipboard::set_clipboard_text`].
+    ///
+    /// # Errors
+    ///
+    /// The backend's own [`ClipboardError`].
+    fn dyn_set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+}
+
+impl<B> DynBackend for B
+where
+    B: Backend + 'static,
+    B::Error: Send + Sync + 'static,
+{
+    fn dyn_draw(
+        &mut self,
+        content: &mut dyn Iterator<Item = DrawCell<'_>>,
+    ) -> Result<(), DynBackendError> {
+        Output::draw(self, content).map_err(DynBackendError::new)
+    }
+
+    fn dyn_draw_layers(
+        &mut self,
+        content: &mut dyn Iterator<Item = DrawCell<'_>>,
+    ) -> Result<(), DynBackendError> {
+        Output::draw_layers(self, content).map_err(DynBackendError::new)
+    }
+
+    fn dyn_compositing(&self) -> Compositing {
+        Output::compositing(self)
+    }
+
+    fn dyn_flush(&mut self) -> Result<(), DynBackendError> {
+        Output::flush(self).map_err(DynBackendError::new)
+    }
+
+    fn dyn_size(&self) -> Size {
+        Output::size(self)
+    }
+
+    fn dyn_clear(&mut self) -> Result<(), DynBackendError> {
+        Output::clear(self).map_err(DynBackendError::new)
+    }
+
+    fn dyn_resize(&mut self, size: Size) {
+        Output::resize(self, size);
+    }
+
+    fn dyn_poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        Input::poll_event(self, timeout)
+    }
+
+    fn dyn_push_event(&mut self, event: Event) {
+        Input::push_event(self, event);
+    }
+
+    fn dyn_poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        Input::poll_next_event(self, cx)
+    }
+
+    #[cfg(feature = "std")]
+    fn dyn_event_sender(&mut self) -> Option<crate::backend::EventSender> {
+        Input::event_sender(self)
+    }
+
+    fn dyn_set_cursor_visible(&mut self, visible: bool) {
+        Cursor::set_cursor_visible(self, visible);
+    }
+
+    fn dyn_set_cursor_position(&mut self, position: Pos) {
+        Cursor::set_cursor_position(self, position);
+    }
+
+    fn dyn_set_cursor_style(&mut self, style: CursorStyle) {
+        Cursor::set_cursor_style(self, style);
+    }
+
+    fn dyn_clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        Clipboard::clipboard_text(self)
+    }
+
+    fn dyn_set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        Clipboard::set_clipboard_text(self, text)
+    }
+}
+
+impl dyn DynBackend {
+    /// Whether the boxed backend is a `B`.
+    #[must_use]
+    pub fn is<B: DynBackend>(&self) -> bool {
+        (self as &dyn Any).is::<B>()
+    }
+
+    /// The boxed backend as a `B`, or `None` if it is some other backend.
+    #[must_use]
+    pub fn downcast_ref<B: DynBackend>(&self) -> Option<&B> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// The boxed backend as a mutable `B`, or `None` if it is some other backend.
+    #[must_use]
+    pub fn downcast_mut<B: DynBackend>(&mut self) -> Option<&mut B> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+impl fmt::Debug for dyn DynBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynBackend")
+            .field("size", &self.dyn_size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Output for Box<dyn DynBackend> {
+    type Error = DynBackendError;
+
+    fn draw<'a, I>(&mut self, mut content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = DrawCell<'a>>,
+    {
+        (**self).dyn_draw(&mut content)
+    }
+
+    fn draw_layers<'a, I>(&mut self, mut content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = DrawCell<'a>>,
+    {
+        (**self).dyn_draw_layers(&mut content)
+    }
+
+    fn compositing(&self) -> Compositing {
+        (**self).dyn_compositing()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        (**self).dyn_flush()
+    }
+
+    fn size(&self) -> Size {
+        (**self).dyn_size()
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        (**self).dyn_clear()
+    }
+
+    fn resize(&mut self, size: Size) {
+        (**self).dyn_resize(size);
+    }
+}
+
+impl Input for Box<dyn DynBackend> {
+    fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        (**self).dyn_poll_event(timeout)
+    }
+
+    fn push_event(&mut self, event: Event) {
+        (**self).dyn_push_event(event);
+    }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        (**self).dyn_poll_next_event(cx)
+    }
+
+    #[cfg(feature = "std")]
+    fn event_sender(&mut self) -> Option<crate::backend::EventSender> {
+        (**self).dyn_event_sender()
+    }
+}
+
+impl Cursor for Box<dyn DynBackend> {
+    fn set_cursor_visible(&mut self, visible: bool) {
+        (**self).dyn_set_cursor_visible(visible);
+    }
+
+    fn set_cursor_position(&mut self, position: Pos) {
+        (**self).dyn_set_cursor_position(position);
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) {
+        (**self).dyn_set_cursor_style(style);
+    }
+}
+
+impl Clipboard for Box<dyn DynBackend> {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        (**self).dyn_clipboard_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        (**self).dyn_set_clipboard_text(text)
+    }
+}
+
+/// The error type of `Box<dyn DynBackend>`: whichever error the boxed backend returned, erased.
+///
+/// [`Display`](fmt::Display) and [`source`](core::error::Error::source) forward to the backend's
+/// own error; [`downcast_ref`](Self::downcast_ref) recovers it by type.
+pub struct DynBackendError(Box<dyn core::error::Error + Send + Sync>);
+
+impl DynBackendError {
+    /// Erases `error`.
+    pub fn new(error: impl core::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(error))
+    }
+
+    /// The backend's own error as an `E`, or `None` if it is some other type.
+    #[must_use]
+    pub fn downcast_ref<E: core::error::Error + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+
+    /// The backend's own error.
+    #[must_use]
+    pub fn into_inner(self) -> Box<dyn core::error::Error + Send + Sync> {
+        self.0
+    }
+}
+
+impl fmt::Debug for DynBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for DynBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl core::error::Error for DynBackendError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl BackendError for DynBackendError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Headless;
+    use crate::color::Style;
+    use crate::terminal::Terminal;
+
+    /// Fails every fallible call, to check errors survive erasure.
+    struct Broken;
+
+    #[derive(Debug, PartialEq)]
+    struct BrokenError;
+
+    impl fmt::Display for BrokenError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("broken")
+        }
+    }
+
+    impl core::error::Error for BrokenError {}
+    impl BackendError for BrokenError {}
+
+    impl Output for Broken {
+        type Error = BrokenError;
+
+        fn draw_layers<'a, I>(&mut self, _content: I) -> Result<(), Self::Error>
+        where
+            I: Iterator<Item = DrawCell<'a>>,
+        {
+            Err(BrokenError)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Err(BrokenError)
+        }
+
+        fn size(&self) -> Size {
+            Size::new(2, 1)
+        }
+
+        fn clear(&mut self) -> Result<(), Self::Error> {
+            Err(BrokenError)
+        }
+    }
+
+    impl Input for Broken {
+        fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
+            None
+        }
+
+        fn poll_next_event(&mut self, _cx: &mut Context<'_>) -> Poll<Event> {
+            Poll::Pending
+        }
+    }
+
+    impl Cursor for Broken {}
+
+    impl Clipboard for Broken {}
+
+    #[test]
+    fn boxed_headless_renders_and_receives_input_like_the_real_one() {
+        let mut term = Terminal::new(Box::new(Headless::new(4, 2)) as Box<dyn DynBackend>);
+        term.backend_mut().push_event(Event::Close);
+        assert_eq!(term.poll(Duration::ZERO), Some(Event::Close));
+
+        term.draw(|s| s.put((1, 0), 'x', Style::default())).unwrap();
+        term.set_cursor_visible(true);
+        term.backend_mut().set_clipboard_text("copied").unwrap();
+        let headless = term.backend().downcast_ref::<Headless>().unwrap();
+        assert_eq!(headless.grid()[Pos::new(1, 0)].glyph(), 'x');
+        assert!(headless.cursor_visible());
+        assert_eq!(headless.clipboard_contents(), Some("copied"));
+    }
+
+    #[test]
+    fn downcasting_to_the_wrong_backend_is_none() {
+        let mut backend: Box<dyn DynBackend> = Box::new(Headless::new(4, 2));
+        assert!(backend.is::<Headless>());
+        assert!(!backend.is::<Broken>());
+        assert!(backend.downcast_mut::<Broken>().is_none());
+    }
+
+    #[test]
+    fn downcasting_a_terminal_to_the_wrong_backend_hands_it_back() {
+        let mut term = Terminal::new(Headless::new(4, 2)).into_dyn();
+        term.requeue_events([Event::Close]);
+        let Err(mut term) = term.downcast::<Broken>() else {
+            panic!("backend is a Headless");
+        };
+        assert_eq!(term.poll(Duration::ZERO), Some(Event::Close));
+    }
+
+    #[test]
+    fn errors_keep_their_message_and_type_through_erasure() {
+        let mut term = Terminal::new(Box::new(Broken) as Box<dyn DynBackend>);
+        term.surface().put((0, 0), 'x', Style::default());
+        let err = term.present().unwrap_err();
+        assert_eq!(err.to_string(), "broken");
+        assert_eq!(err.downcast_ref::<BrokenError>(), Some(&BrokenError));
+    }
+}
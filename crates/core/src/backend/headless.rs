@@ -8,14 +8,19 @@
 //! [`Headless::format_styled`] emits Select Graphic Rendition (SGR) parameters via
 //! [`crate::color::sgr`], shared with `retroglyph-recorder`'s asciicast export so the two
 //! encoders can't drift apart.
+//!
+//! Its [`Clipboard`] is an in-memory fake: text set through it reads back until the next set,
+//! and a test can preload it with [`Headless::set_clipboard_contents`] to stand in for whatever
+//! the user copied elsewhere.
 
-use crate::backend::{Cursor, CursorStyle, DrawCell, Input, Output};
+use crate::backend::{Clipboard, ClipboardError, Cursor, CursorStyle, DrawCell, Input, Output};
 use crate::color::Style;
 use crate::event::{Event, push_coalesced};
 use crate::grid::{Grid, HasSize, Pos, Size};
 use crate::tile::Tile;
 use alloc::collections::VecDeque;
 use alloc::string::String;
+use core::task::{Context, Poll, Waker};
 use core::time::Duration;
 
 /// In-memory backend for testing.
@@ -27,7 +32,16 @@ pub struct Headless {
     cursor_visible: bool,
     cursor_pos: Pos,
     cursor_style: CursorStyle,
+    /// The fake clipboard's text; `None` until something is copied.
+    clipboard: Option<String>,
     event_queue: VecDeque<Event>,
+    /// Registered by [`Input::poll_next_event`] while the queue is empty, woken by the next
+    /// [`push_event`](Self::push_event).
+    waker: Option<Waker>,
+    /// Created by the first [`Input::event_sender`] call; drained ahead of `event_queue`'s tail
+    /// by every poll.
+    #[cfg(feature = "std")]
+    inbox: Option<crate::backend::EventInbox>,
 }
 
 impl Headless {
@@ -50,7 +64,11 @@ impl Headless {
             cursor_visible: false,
             cursor_pos: Pos::default(),
             cursor_style: CursorStyle::default(),
+            clipboard: None,
             event_queue: VecDeque::new(),
+            waker: None,
+            #[cfg(feature = "std")]
+            inbox: None,
         }
     }
 
@@ -121,28 +139,57 @@ impl Headless {
     /// `retroglyph-terminal-wasm` backends this stands in for during tests (retroglyph#768): a
     /// caller pushing a burst of pointer positions before draining the queue sees only the latest
     /// one, the same as it would against a real backend.
+    ///
+    /// Also wakes a task parked in [`Input::poll_next_event`] on an empty queue, so an async
+    /// driver over this backend resumes as soon as input arrives.
     pub fn push_event(&mut self, event: Event) {
         push_coalesced(&mut self.event_queue, event);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
     }
 
     /// Converts the current grid state into a readable string for snapshot testing.
     ///
     /// Space cells are rendered as `·` so layout is visible in text diffs.
+    ///
+    /// A double-size row (see [`LineSize`](crate::grid::LineSize)) is spread out to the width it
+    /// takes on screen: each visible cell is followed by a plain space, and the cells past the
+    /// row's visible half are left out, so `TITLE` on a 10-column double-width row reads as
+    /// `T I T L E `. Both halves of a double-height line render the same way.
     #[must_use]
     pub fn format_view(&self) -> String {
         let grid = self.grid();
         let mut out = String::new();
         for row in grid.size().to_rect().rows() {
+            let mut columns = 0;
             for pos in row {
+                if !grid.cell_is_visible(pos.x, pos.y) {
+                    break;
+                }
                 let cell = &grid[pos];
                 let (glyph, is_spacer) = Self::display_glyph(cell);
                 out.push(if is_spacer { ' ' } else { glyph });
+                columns += 1;
+                if grid.line_size(pos.y).is_double_width() {
+                    out.push(' ');
+                    columns += 1;
+                }
             }
+            Self::pad_row(&mut out, columns, grid.width());
             out.push('\n');
         }
         out
     }
 
+    /// Pads a double-size row of an odd-width grid out to the full width with plain spaces: the
+    /// column its halved cells could not fill. A no-op for any other row.
+    fn pad_row(out: &mut String, columns: u16, width: u16) {
+        for _ in columns..width {
+            out.push(' ');
+        }
+    }
+
     /// `format_view`, with each cell's colors emitted as SGR (ANSI) escape sequences.
     ///
     /// Suitable for `insta::assert_snapshot!`, which renders ANSI in its terminal diff output:
@@ -159,13 +206,20 @@ impl Headless {
     /// `38;5;n`/`48;5;n`); [`Color::Rgb`](crate::color::Color::Rgb) maps to 24-bit SGR (`38;2;r;g;b`/`48;2;r;g;b`) rather
     /// than being downgraded, so this reflects the style as authored, not as a particular
     /// terminal would render it.
+    ///
+    /// Double-size rows are spread out the same way [`format_view`](Self::format_view) spreads
+    /// them, each cell's padding column taking that cell's style.
     #[must_use]
     pub fn format_styled(&self) -> String {
         let grid = self.grid();
         let mut out = String::new();
         for row in grid.size().to_rect().rows() {
             let mut current: Option<Style> = None;
+            let mut columns = 0;
             for pos in row {
+                if !grid.cell_is_visible(pos.x, pos.y) {
+                    break;
+                }
                 let cell = &grid[pos];
                 let (glyph, is_spacer) = Self::display_glyph(cell);
                 let style = if is_spacer {
@@ -179,10 +233,16 @@ impl Headless {
                     current = Some(style);
                 }
                 out.push(if is_spacer { ' ' } else { glyph });
+                columns += 1;
+                if grid.line_size(pos.y).is_double_width() {
+                    out.push(' ');
+                    columns += 1;
+                }
             }
             if current.is_some_and(|s| s != Style::default()) {
                 out.push_str("\x1b[0m");
             }
+            Self::pad_row(&mut out, columns, grid.width());
             out.push('\n');
         }
         out
@@ -229,11 +289,14 @@ impl Output for Headless {
             if let Some(t) = self.layers.tile_mut_or_alloc(cell.layer, pos) {
                 *t = *cell.tile;
             }
+            self.layers.set_line_size(pos.y, cell.line);
             // Rebuild the side-table entry from the parts that arrived, so a headless capture
-            // round-trips both members rather than only the grapheme.
+            // round-trips every member rather than only the grapheme.
             let extra = crate::grid::TileExtra {
                 grapheme: cell.grapheme.map(alloc::sync::Arc::from),
                 tint: cell.tint,
+                transform: cell.transform,
+                remap: cell.remap.copied().map(alloc::sync::Arc::new),
             };
             self.layers.set_extra(cell.layer, pos.x, pos.y, extra);
         }
@@ -268,14 +331,83 @@ impl Output for Headless {
     }
 }
 
+impl Headless {
+    /// Returns the fake clipboard's text, or `None` if nothing has been copied yet.
+    ///
+    /// Reads without going through [`Clipboard::clipboard_text`], so an assertion on what the
+    /// app copied doesn't need to handle an error.
+    #[must_use]
+    pub fn clipboard_contents(&self) -> Option<&str> {
+        self.clipboard.as_deref()
+    }
+
+    /// Replaces the fake clipboard's text, as if the user had copied `text` in another program;
+    /// `None` empties it, so the next [`Clipboard::clipboard_text`] fails.
+    pub fn set_clipboard_contents(&mut self, text: Option<String>) {
+        self.clipboard = text;
+    }
+}
+
+#[cfg(feature = "std")]
+impl Headless {
+    /// Moves everything sent through an [`EventSender`](crate::backend::EventSender) onto the
+    /// local queue, so sent and pushed events come out in one order.
+    fn drain_inbox(&mut self) {
+        if let Some(inbox) = &self.inbox {
+            while let Some(event) = inbox.try_recv() {
+                push_coalesced(&mut self.event_queue, event);
+            }
+        }
+    }
+}
+
 impl Input for Headless {
-    fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
-        self.event_queue.pop_front()
+    /// Pops the next queued event.
+    ///
+    /// Never waits for pushed input: `timeout` only matters once an
+    /// [`event_sender`](Input::event_sender) has been handed out, in which case an empty queue
+    /// blocks up to `timeout` for another thread to send something (returning at once if every
+    /// sender is gone).
+    fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        #[cfg(feature = "std")]
+        self.drain_inbox();
+        if let Some(event) = self.event_queue.pop_front() {
+            return Some(event);
+        }
+        #[cfg(feature = "std")]
+        if let Some(inbox) = &self.inbox {
+            return inbox.recv_timeout(timeout);
+        }
+        let _ = timeout;
+        None
     }
 
     fn push_event(&mut self, event: Event) {
         Self::push_event(self, event);
     }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        #[cfg(feature = "std")]
+        self.drain_inbox();
+        if let Some(event) = self.event_queue.pop_front() {
+            return Poll::Ready(event);
+        }
+        #[cfg(feature = "std")]
+        if let Some(event) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.poll_recv(cx.waker()))
+        {
+            return Poll::Ready(event);
+        }
+        self.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    #[cfg(feature = "std")]
+    fn event_sender(&mut self) -> Option<crate::backend::EventSender> {
+        Some(self.inbox.get_or_insert_with(Default::default).sender())
+    }
 }
 
 impl Cursor for Headless {
@@ -292,6 +424,19 @@ impl Cursor for Headless {
     }
 }
 
+impl Clipboard for Headless {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.clipboard
+            .clone()
+            .ok_or_else(|| ClipboardError::new("clipboard is empty"))
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.clipboard = Some(text.into());
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,6 +459,22 @@ mod tests {
         assert_eq!(backend.cursor_style(), CursorStyle::SteadyBar);
     }
 
+    #[test]
+    fn test_headless_clipboard_is_an_in_memory_fake() {
+        let mut backend = Headless::new(4, 2);
+        assert!(backend.clipboard_text().is_err());
+
+        backend.set_clipboard_text("first").unwrap();
+        backend.set_clipboard_text("second").unwrap();
+        assert_eq!(backend.clipboard_text().unwrap(), "second");
+        assert_eq!(backend.clipboard_contents(), Some("second"));
+
+        backend.set_clipboard_contents(Some("pasted elsewhere".into()));
+        assert_eq!(backend.clipboard_text().unwrap(), "pasted elsewhere");
+        backend.set_clipboard_contents(None);
+        assert!(backend.clipboard_text().is_err());
+    }
+
     #[test]
     fn test_headless_events() {
         let mut backend = Headless::new(10, 10);
@@ -323,6 +484,53 @@ mod tests {
         assert_eq!(backend.poll_event(Duration::ZERO), None);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn a_send_wakes_a_task_parked_in_poll_next_event() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicBool, Ordering};
+        use std::task::Wake;
+
+        struct Flag(AtomicBool);
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let mut backend = Headless::new(4, 2);
+        let sender = backend.event_sender().expect("Headless supports senders");
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(Arc::clone(&flag));
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(backend.poll_next_event(&mut cx), Poll::Pending);
+        std::thread::spawn(move || sender.send(Event::Custom(4)))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(
+            backend.poll_next_event(&mut cx),
+            Poll::Ready(Event::Custom(4))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn poll_event_only_waits_while_a_sender_is_live() {
+        let mut backend = Headless::new(4, 2);
+        let sender = backend.event_sender().expect("Headless supports senders");
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            sender.send(Event::Custom(8))
+        });
+        assert_eq!(backend.poll_event(Duration::MAX), Some(Event::Custom(8)));
+        worker.join().unwrap().unwrap();
+        // The only sender is gone: an empty queue returns at once again.
+        assert_eq!(backend.poll_event(Duration::MAX), None);
+    }
+
     fn moved(x: u16) -> Event {
         use crate::event::{KeyModifiers, MouseEvent, MouseEventKind};
         Event::Mouse(MouseEvent {
@@ -601,4 +809,21 @@ mod tests {
             assert!(!styled.contains("\x1b[32m "));
         }
     }
+
+    #[test]
+    fn format_view_spreads_a_double_size_row_to_its_screen_width() {
+        use crate::grid::LineSize;
+        use crate::terminal::Terminal;
+
+        let mut term = Terminal::new(Headless::new(5, 2));
+        {
+            let mut s = term.surface();
+            s.set_line_size(0, LineSize::DoubleWidth);
+            s.print((0, 0), "HEY", Style::default());
+            s.print((0, 1), "x", Style::default());
+        }
+        term.present().unwrap();
+        // Only two of row 0's five columns fit once doubled; the odd column left over is padding.
+        assert_eq!(term.backend().format_view(), "H E  \nx····\n");
+    }
 }
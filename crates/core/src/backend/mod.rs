@@ -1,18 +1,29 @@
 //! Pluggable rendering backends.
 //!
-//! The [`Output`](crate::backend::Output), [`Input`](crate::backend::Input), and [`Cursor`](crate::backend::Cursor) traits (plus the [`Backend`](crate::backend::Backend) bundle that ties them
-//! together) and the dependency-free [`Headless`](crate::backend::Headless) test backend live here. Platform backends
+//! The [`Output`](crate::backend::Output), [`Input`](crate::backend::Input), [`Cursor`](crate::backend::Cursor), and [`Clipboard`](crate::backend::Clipboard) traits (plus the [`Backend`](crate::backend::Backend) bundle that ties them
+//! together), their object-safe mirror [`DynBackend`](crate::backend::DynBackend), and the dependency-free [`Headless`](crate::backend::Headless) test backend live here. Platform backends
 //! (crossterm, software/winit) are separate crates (`retroglyph-crossterm`, `retroglyph-software`)
 //! that depend on this one and implement these traits.
 
+pub mod dynamic;
 pub mod headless;
+#[cfg(feature = "std")]
+pub mod sender;
 
+pub use dynamic::{DynBackend, DynBackendError};
 pub use headless::Headless;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use sender::Timer;
+#[cfg(feature = "std")]
+pub use sender::{EventInbox, EventSender, SendError};
 
-use crate::color::Tint;
+use crate::color::{Remap, Tint};
 use crate::event::Event;
-use crate::grid::{Pos, Size};
-use crate::tile::Tile;
+use crate::grid::{LineSize, Pos, Size};
+use crate::tile::{Tile, Transform};
+use alloc::string::String;
+use core::fmt;
+use core::task::{Context, Poll};
 use core::time::Duration;
 
 /// Associated error type used by all fallible backend methods.
@@ -68,16 +79,37 @@ pub struct DrawCell<'a> {
     /// side table, so a backend that does not support `egc` can ignore this entirely and render
     /// from [`Tile::glyph`](crate::tile::Tile::glyph).
     pub grapheme: Option<&'a str>,
-    /// How a pixel backend recolours this cell's sprite.
+    /// How a pixel backend recolors this cell's sprite.
     ///
     /// [`Tint::None`](crate::color::Tint::None) for the overwhelming majority of cells. Cell backends have no sprite to
-    /// recolour and ignore it; see [`Tint`](crate::color::Tint).
+    /// recolor and ignore it; see [`Tint`](crate::color::Tint).
     pub tint: Tint,
+    /// How a pixel backend flips, rotates, and scales this cell's sprite.
+    ///
+    /// [`Transform::IDENTITY`](crate::tile::Transform::IDENTITY) for the overwhelming majority of cells. Cell backends ignore
+    /// it, or substitute a glyph through a [`GlyphTransforms`](crate::tile::GlyphTransforms) table
+    /// when they were given one.
+    pub transform: Transform,
+    /// The palette swap a pixel backend applies to this cell's sprite, or `None` to draw its
+    /// colors as authored.
+    ///
+    /// Borrowed from the grid's side table like [`grapheme`](Self::grapheme), since a
+    /// [`Remap`](crate::color::Remap) is a table rather than a word. `None` for the overwhelming
+    /// majority of cells, and never `Some` holding an identity remap. Cell backends ignore it.
+    pub remap: Option<&'a Remap>,
+    /// The size of the row this cell sits on.
+    ///
+    /// [`LineSize::Normal`](crate::grid::LineSize::Normal) unless the app set a double-size line
+    /// with [`Grid::set_line_size`](crate::grid::Grid::set_line_size), in which case the cell is
+    /// drawn two columns wide (see [`LineSize`](crate::grid::LineSize)). Cells past such a row's
+    /// visible half never arrive at all, and every cell of a row whose size changed is resent, so
+    /// a backend can redraw the row from this field alone.
+    pub line: LineSize,
 }
 
 impl<'a> DrawCell<'a> {
-    /// A cell on layer 0 with no grapheme text and no tint: the shape almost every test and
-    /// cell backend wants.
+    /// A cell on layer 0 with no grapheme text, no tint, no transform, and no remap, on a normal-size row: the shape almost
+    /// every test and cell backend wants.
     #[must_use]
     pub const fn new(pos: Pos, tile: &'a Tile) -> Self {
         Self {
@@ -86,6 +118,9 @@ impl<'a> DrawCell<'a> {
             tile,
             grapheme: None,
             tint: Tint::None,
+            transform: Transform::IDENTITY,
+            remap: None,
+            line: LineSize::Normal,
         }
     }
 
@@ -98,6 +133,9 @@ impl<'a> DrawCell<'a> {
             tile,
             grapheme: None,
             tint: Tint::None,
+            transform: Transform::IDENTITY,
+            remap: None,
+            line: LineSize::Normal,
         }
     }
 
@@ -114,6 +152,27 @@ impl<'a> DrawCell<'a> {
         self.tint = tint;
         self
     }
+
+    /// This cell with `transform` applied to its sprite.
+    #[must_use]
+    pub const fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// This cell with `remap` swapping its sprite's colors.
+    #[must_use]
+    pub const fn with_remap(mut self, remap: Option<&'a Remap>) -> Self {
+        self.remap = remap;
+        self
+    }
+
+    /// This cell on a row drawn at `line` size.
+    #[must_use]
+    pub const fn with_line(mut self, line: LineSize) -> Self {
+        self.line = line;
+        self
+    }
 }
 
 /// How a backend wants layers handed to [`Output::draw_layers`].
@@ -307,26 +366,44 @@ pub trait Output {
 /// Polls for and accepts input events.
 ///
 /// Backends that never receive events from outside their own [`poll_event`](Self::poll_event)
-/// implementation (e.g. `Crossterm`, which reads its own event stream) can use the default
-/// no-op [`push_event`](Self::push_event) via an empty `impl Input for X {}`.
+/// implementation (e.g. `Crossterm`, which reads its own event stream) can leave out
+/// [`push_event`](Self::push_event) and use its default no-op.
 ///
 /// # Examples
 ///
 /// ```
+/// use core::task::{Context, Poll, Waker};
 /// use core::time::Duration;
 /// use retroglyph_core::backend::Input;
 /// use retroglyph_core::event::Event;
 /// use std::collections::VecDeque;
 ///
-/// struct QueuedInput(VecDeque<Event>);
+/// #[derive(Default)]
+/// struct QueuedInput {
+///     queue: VecDeque<Event>,
+///     waker: Option<Waker>,
+/// }
 ///
 /// impl Input for QueuedInput {
 ///     fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
-///         self.0.pop_front()
+///         self.queue.pop_front()
 ///     }
 ///
 ///     fn push_event(&mut self, event: Event) {
-///         self.0.push_back(event);
+///         self.queue.push_back(event);
+///         if let Some(waker) = self.waker.take() {
+///             waker.wake();
+///         }
+///     }
+///
+///     fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+///         match self.queue.pop_front() {
+///             Some(event) => Poll::Ready(event),
+///             None => {
+///                 self.waker = Some(cx.waker().clone());
+///                 Poll::Pending
+///             }
+///         }
 ///     }
 /// }
 /// ```
@@ -352,6 +429,39 @@ pub trait Input {
     /// for example); see `retroglyph_core::testing::conformance::assert_input_contract` for the
     /// harness that pins it, and that module's doc for which backends it binds.
     fn push_event(&mut self, _event: Event) {}
+
+    /// Polls for an input event without blocking, arranging for `cx`'s waker to be woken once one
+    /// may be ready: the non-blocking, executor-agnostic counterpart to
+    /// [`poll_event`](Self::poll_event) that
+    /// [`Terminal::events`](crate::terminal::Terminal::events) and
+    /// [`run_on_async`](crate::app::run_on_async) are built on.
+    ///
+    /// Only the backend knows what its input waits on, so there is no default: returning
+    /// [`Poll::Pending`] without arranging a wake parks the task for good, and waking `cx`
+    /// straight away turns every idle wait into a busy loop. A backend whose events are pushed
+    /// in keeps the waker and wakes it from [`push_event`](Self::push_event)
+    /// ([`Headless`](crate::backend::Headless) does); one that reads a file descriptor or socket
+    /// has something wait on it while the task is parked (`retroglyph-terminal`'s
+    /// `InputWatcher`); a wrapper forwards to the backend it wraps. A backend with an
+    /// `EventInbox` registers the waker for sent events with `EventInbox::poll_recv`.
+    ///
+    /// A backend that can never produce another event (it has none to give) may return
+    /// `Poll::Pending` without registering anything.
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event>;
+
+    /// A handle other threads can use to inject events into this backend, or `None` if it has
+    /// no way to receive them.
+    ///
+    /// Events sent through it come out of [`poll_event`](Self::poll_event) like any other input,
+    /// and a send wakes a blocked `poll_event` (and so
+    /// [`Terminal::wait_for_input`](crate::terminal::Terminal::wait_for_input)) or a parked
+    /// [`poll_next_event`](Self::poll_next_event) without waiting out its timeout. Backends that
+    /// support it create their [`EventInbox`](crate::backend::EventInbox) on the first call;
+    /// every later call hands out another sender for the same inbox.
+    #[cfg(feature = "std")]
+    fn event_sender(&mut self) -> Option<EventSender> {
+        None
+    }
 }
 
 /// Shows, hides, and moves a text cursor.
@@ -420,21 +530,84 @@ pub enum CursorStyle {
     SteadyBar,
 }
 
+/// Reads and writes the system clipboard's text.
+///
+/// Both methods default to failing without doing anything, so backends with no clipboard to
+/// reach can use an empty `impl Clipboard for X {}`, the way [`Cursor`] works for backends with
+/// no text cursor. Where a clipboard exists, how it is reached is up to the backend: the native
+/// OS clipboard in a window, an OSC 52 escape sequence on a terminal (which also works over
+/// SSH), or an in-memory fake in [`Headless`](crate::backend::Headless) for tests.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::backend::{Clipboard, ClipboardError, Headless};
+///
+/// let mut backend = Headless::new(8, 2);
+/// backend.set_clipboard_text("seed 1234")?;
+/// assert_eq!(backend.clipboard_text()?, "seed 1234");
+/// # Ok::<(), ClipboardError>(())
+/// ```
+pub trait Clipboard {
+    /// Returns the clipboard's current text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipboardError`] if the backend has no clipboard, the clipboard is unavailable
+    /// or refuses to be read, or it does not currently hold text (e.g. it is empty, or holds an
+    /// image).
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        Err(ClipboardError::new("this backend has no clipboard"))
+    }
+
+    /// Replaces the clipboard's contents with `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipboardError`] if the backend has no clipboard, or the clipboard is
+    /// unavailable.
+    fn set_clipboard_text(&mut self, _text: &str) -> Result<(), ClipboardError> {
+        Err(ClipboardError::new("this backend has no clipboard"))
+    }
+}
+
+/// Error returned by [`Clipboard::clipboard_text`]/[`Clipboard::set_clipboard_text`], holding a
+/// message that says why the clipboard couldn't be read or written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardError(String);
+
+impl ClipboardError {
+    /// Wraps `message` as a [`ClipboardError`].
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "clipboard error: {}", self.0)
+    }
+}
+
+impl core::error::Error for ClipboardError {}
+
 /// A rendering backend that presents grid content to a display and provides input events.
 ///
-/// This is a pure ergonomic bundle over [`Output`](crate::backend::Output), [`Input`](crate::backend::Input), and [`Cursor`](crate::backend::Cursor), with no members
-/// of its own: every type implementing all three gets `Backend` for free, and every generic
+/// This is a pure ergonomic bundle over [`Output`](crate::backend::Output), [`Input`](crate::backend::Input), [`Cursor`](crate::backend::Cursor), and [`Clipboard`](crate::backend::Clipboard), with no members
+/// of its own: every type implementing all four gets `Backend` for free, and every generic
 /// call site that only needs one or two facets should bound on those directly instead of
-/// requiring all three through this trait.
+/// requiring all four through this trait.
 ///
 /// # Examples
 ///
 /// There is nothing to implement directly: a type gets `Backend` for free the moment it
-/// implements all three facet traits.
+/// implements all four facet traits.
 ///
 /// ```
+/// use core::task::{Context, Poll};
 /// use core::time::Duration;
-/// use retroglyph_core::backend::{Backend, Cursor, DrawCell, Input, Output};
+/// use retroglyph_core::backend::{Backend, Clipboard, Cursor, DrawCell, Input, Output};
 /// use retroglyph_core::event::Event;
 /// use retroglyph_core::grid::Size;
 ///
@@ -467,13 +640,47 @@ pub enum CursorStyle {
 ///     fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
 ///         None
 ///     }
+///
+///     fn poll_next_event(&mut self, _cx: &mut Context<'_>) -> Poll<Event> {
+///         // Nothing will ever arrive, so there is nothing to wake the task for.
+///         Poll::Pending
+///     }
 /// }
 ///
 /// impl Cursor for NullBackend {}
 ///
+/// impl Clipboard for NullBackend {}
+///
 /// fn assert_is_backend<B: Backend>(_backend: &B) {}
 /// assert_is_backend(&NullBackend);
 /// ```
-pub trait Backend: Output + Input + Cursor {}
+pub trait Backend: Output + Input + Cursor + Clipboard {}
+
+impl<T: Output + Input + Cursor + Clipboard> Backend for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl<T: Output + Input + Cursor> Backend for T {}
+    struct NoClipboard;
+
+    impl Clipboard for NoClipboard {}
+
+    #[test]
+    fn the_default_clipboard_fails_both_ways() {
+        assert!(NoClipboard.clipboard_text().is_err());
+        assert!(NoClipboard.set_clipboard_text("text").is_err());
+    }
+
+    #[test]
+    fn clipboard_error_display_includes_message() {
+        let err = ClipboardError::new("boom");
+        assert_eq!(err.to_string(), "clipboard error: boom");
+    }
+
+    #[test]
+    fn clipboard_error_equality() {
+        assert_eq!(ClipboardError::new("a"), ClipboardError::new("a"));
+        assert_ne!(ClipboardError::new("a"), ClipboardError::new("b"));
+    }
+}
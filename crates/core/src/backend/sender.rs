@@ -0,0 +1,620 @@
+//! Cross-thread event injection: [`EventSender`](crate::backend::sender::EventSender), the
+//! [`EventInbox`](crate::backend::sender::EventInbox) a backend drains it from, and the
+//! [`Timer`](crate::backend::sender::Timer) helper built on top.
+//!
+//! A backend that supports injection owns one `EventInbox` and hands out senders from
+//! [`Input::event_sender`](crate::backend::Input::event_sender). Every sent event lands in the
+//! inbox, then wakes whatever is waiting on it: a thread blocked in
+//! [`recv_timeout`](crate::backend::sender::EventInbox::recv_timeout), a task parked in
+//! [`Input::poll_next_event`](crate::backend::Input::poll_next_event), and the backend's own
+//! notify hook (a windowed backend uses this to wake a sleeping event loop).
+//!
+//! # Examples
+//!
+//! A worker thread waking a blocked [`wait_for_input`](crate::terminal::Terminal::wait_for_input)
+//! on [`Headless`](crate::backend::Headless):
+//!
+//! ```
+//! use retroglyph_core::backend::Headless;
+//! use retroglyph_core::event::Event;
+//! use retroglyph_core::terminal::Terminal;
+//! use std::time::Duration;
+//!
+//! let mut term = Terminal::new(Headless::new(10, 2));
+//! let sender = term.event_sender().expect("Headless supports senders");
+//!
+//! let worker = std::thread::spawn(move || sender.send(Event::Custom(7)));
+//! assert!(term.wait_for_input(Duration::from_secs(5)));
+//! assert_eq!(term.poll(Duration::ZERO), Some(Event::Custom(7)));
+//! worker.join().unwrap().unwrap();
+//! ```
+
+use crate::event::Event;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::fmt;
+use core::task::Waker;
+use core::time::Duration;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+/// The longest single condition-variable wait: longer requested timeouts (including
+/// [`Duration::MAX`]) are waited out in chunks of this size so no platform timeout overflows.
+const MAX_WAIT_CHUNK: Duration = Duration::from_secs(3600);
+
+type Notify = Arc<dyn Fn() + Send + Sync>;
+
+/// State shared between an [`EventInbox`] and every [`EventSender`] cloned from it.
+struct Shared {
+    state: Mutex<State>,
+    ready: Condvar,
+    notify: Mutex<Option<Notify>>,
+}
+
+struct State {
+    queue: VecDeque<Event>,
+    waker: Option<Waker>,
+    closed: bool,
+    /// Live [`EventSender`]s. Kept under the lock (rather than read off the `Arc`'s strong
+    /// count) so a sender's drop and a `recv_timeout` checking for one can't miss each other.
+    senders: usize,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, State> {
+        // A panic while holding the lock cannot leave `State` half-updated (every critical
+        // section is a single push, pop, or assignment), so a poisoned lock is still usable.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn notify(&self) -> Option<Notify> {
+        self.notify
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// A cloneable, [`Send`] handle that injects [`Event`]s into a backend from any thread.
+///
+/// Obtained from [`Terminal::event_sender`](crate::terminal::Terminal::event_sender) (or
+/// [`Input::event_sender`](crate::backend::Input::event_sender) on the backend directly). Each
+/// [`send`](Self::send) queues the event for the backend's next poll and wakes a blocked
+/// [`wait_for_input`](crate::terminal::Terminal::wait_for_input) or a parked async driver right
+/// away, so a worker thread (network, audio, file watcher) never has to wait for the next input
+/// event or a polling timeout to be noticed. Clone it freely to hand a copy to each worker.
+///
+/// [`schedule`](Self::schedule) and [`schedule_repeating`](Self::schedule_repeating) build
+/// one-shot and repeating timers on top, e.g. a blinking cursor that sleeps in
+/// [`Flow::Idle`](crate::app::Flow::Idle) between blinks instead of polling.
+pub struct EventSender {
+    shared: Arc<Shared>,
+}
+
+impl Clone for EventSender {
+    fn clone(&self) -> Self {
+        Self::attach(&self.shared)
+    }
+}
+
+impl fmt::Debug for EventSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSender")
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventSender {
+    fn attach(shared: &Arc<Shared>) -> Self {
+        shared.state().senders += 1;
+        Self {
+            shared: Arc::clone(shared),
+        }
+    }
+
+    /// Queues `event` for the backend and wakes anything waiting on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendError`] (carrying `event` back) if the backend, and with it the
+    /// [`EventInbox`], has been dropped.
+    pub fn send(&self, event: Event) -> Result<(), SendError> {
+        let waker = {
+            let mut state = self.shared.state();
+            if state.closed {
+                return Err(SendError(event));
+            }
+            state.queue.push_back(event);
+            state.waker.take()
+        };
+        self.shared.ready.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        if let Some(notify) = self.shared.notify() {
+            notify();
+        }
+        Ok(())
+    }
+
+    /// Whether the receiving backend has been dropped, so every further
+    /// [`send`](Self::send) would fail.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.shared.state().closed
+    }
+
+    /// Sends `event` once, after `delay`, from a background thread.
+    ///
+    /// Dropping (or [`cancel`](Timer::cancel)ling) the returned [`Timer`] before it fires
+    /// cancels it; [`detach`](Timer::detach) it to let it fire regardless.
+    ///
+    /// Not available on `wasm32`, which has no threads to sleep on: schedule a JS `setTimeout`
+    /// that calls [`send`](Self::send) instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the timer thread could not be spawned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn schedule(&self, delay: Duration, event: Event) -> std::io::Result<Timer> {
+        Timer::spawn(self.clone(), delay, None, event)
+    }
+
+    /// Sends `event` every `interval`, starting one `interval` from now, until the returned
+    /// [`Timer`] is dropped or cancelled, or the backend goes away.
+    ///
+    /// Ticks missed while the process was stalled are skipped rather than sent in a burst. An
+    /// `interval` below one millisecond is raised to one millisecond.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::backend::Headless;
+    /// use retroglyph_core::event::Event;
+    /// use retroglyph_core::terminal::Terminal;
+    /// use std::time::Duration;
+    ///
+    /// const BLINK: u64 = 1;
+    ///
+    /// let mut term = Terminal::new(Headless::new(10, 2));
+    /// let sender = term.event_sender().expect("Headless supports senders");
+    /// let blink = sender.schedule_repeating(Duration::from_millis(5), Event::Custom(BLINK))?;
+    ///
+    /// for _ in 0..2 {
+    ///     assert!(term.wait_for_input(Duration::from_secs(5)));
+    ///     assert_eq!(term.poll(Duration::ZERO), Some(Event::Custom(BLINK)));
+    /// }
+    /// blink.cancel();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the timer thread could not be spawned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn schedule_repeating(&self, interval: Duration, event: Event) -> std::io::Result<Timer> {
+        let interval = interval.max(Duration::from_millis(1));
+        Timer::spawn(self.clone(), interval, Some(interval), event)
+    }
+}
+
+impl Drop for EventSender {
+    fn drop(&mut self) {
+        // Once the last sender goes, a thread blocked in `recv_timeout` has nothing left that
+        // could ever wake it, so let it re-check and give up.
+        let last = {
+            let mut state = self.shared.state();
+            state.senders -= 1;
+            state.senders == 0
+        };
+        if last {
+            self.shared.ready.notify_all();
+        }
+    }
+}
+
+/// Error returned by [`EventSender::send`] when the receiving backend has been dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendError(Event);
+
+impl SendError {
+    /// The event that could not be delivered.
+    #[must_use]
+    pub fn into_inner(self) -> Event {
+        self.0
+    }
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "event receiver closed")
+    }
+}
+
+impl core::error::Error for SendError {}
+
+/// The receiving end of [`EventSender`]: owned by a backend, drained by its
+/// [`poll_event`](crate::backend::Input::poll_event).
+///
+/// Dropping the inbox closes it: every sender's [`send`](EventSender::send) fails from then on,
+/// and repeating [`Timer`]s stop.
+pub struct EventInbox {
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for EventInbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventInbox")
+            .field("pending", &self.shared.state().queue.len())
+            .field("has_senders", &self.has_senders())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for EventInbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventInbox {
+    /// Creates an empty inbox with no senders.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    queue: VecDeque::new(),
+                    waker: None,
+                    closed: false,
+                    senders: 0,
+                }),
+                ready: Condvar::new(),
+                notify: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// A new sender feeding this inbox.
+    #[must_use]
+    pub fn sender(&self) -> EventSender {
+        EventSender::attach(&self.shared)
+    }
+
+    /// Whether any [`EventSender`] (including one held by a live [`Timer`]) still feeds this
+    /// inbox.
+    #[must_use]
+    pub fn has_senders(&self) -> bool {
+        self.shared.state().senders > 0
+    }
+
+    /// Installs `notify`, called on the sending thread after every successful
+    /// [`send`](EventSender::send), replacing any previous hook.
+    ///
+    /// A backend whose wait happens somewhere the inbox cannot reach (a windowed event loop
+    /// parked inside the OS) uses this to wake it.
+    pub fn set_notify(&self, notify: impl Fn() + Send + Sync + 'static) {
+        *self
+            .shared
+            .notify
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(notify));
+    }
+
+    /// Takes the oldest sent event without blocking.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<Event> {
+        self.shared.state().queue.pop_front()
+    }
+
+    /// Takes the oldest sent event, blocking up to `timeout` for one to arrive.
+    ///
+    /// Returns `None` straight away, without waiting, when nothing is queued and no sender is
+    /// left that could send one.
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Event> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut state = self.shared.state();
+        loop {
+            if let Some(event) = state.queue.pop_front() {
+                return Some(event);
+            }
+            if state.senders == 0 {
+                return None;
+            }
+            let remaining = deadline.map_or(MAX_WAIT_CHUNK, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            });
+            if remaining.is_zero() {
+                return None;
+            }
+            state = self
+                .shared
+                .ready
+                .wait_timeout(state, remaining.min(MAX_WAIT_CHUNK))
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Takes the oldest sent event, or registers `waker` to be woken by the next
+    /// [`send`](EventSender::send) when there is none.
+    #[must_use]
+    pub fn poll_recv(&self, waker: &Waker) -> Option<Event> {
+        let mut state = self.shared.state();
+        let event = state.queue.pop_front();
+        if event.is_none() {
+            state.waker = Some(waker.clone());
+        }
+        event
+    }
+}
+
+impl Drop for EventInbox {
+    fn drop(&mut self) {
+        let mut state = self.shared.state();
+        state.closed = true;
+        state.queue.clear();
+    }
+}
+
+/// A running [`EventSender::schedule`]/[`schedule_repeating`](EventSender::schedule_repeating)
+/// timer.
+///
+/// Dropping it cancels the timer (and waits for its thread to notice, so nothing is sent
+/// afterwards); [`detach`](Self::detach) lets it run on its own instead.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use = "dropping a Timer cancels it; call `detach` to let it run"]
+pub struct Timer {
+    cancel: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timer")
+            .field("finished", &self.is_finished())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Timer {
+    fn spawn(
+        sender: EventSender,
+        delay: Duration,
+        interval: Option<Duration>,
+        event: Event,
+    ) -> std::io::Result<Self> {
+        let cancel = Arc::new((Mutex::new(false), Condvar::new()));
+        let flag = Arc::clone(&cancel);
+        let thread = std::thread::Builder::new()
+            .name("retroglyph-timer".into())
+            .spawn(move || {
+                let (cancelled, wake) = &*flag;
+                let mut deadline = Instant::now() + delay;
+                loop {
+                    let mut cancelled = cancelled.lock().unwrap_or_else(PoisonError::into_inner);
+                    loop {
+                        if *cancelled {
+                            return;
+                        }
+                        let remaining = deadline.saturating_duration_since(Instant::now());
+                        if remaining.is_zero() {
+                            break;
+                        }
+                        cancelled = wake
+                            .wait_timeout(cancelled, remaining.min(MAX_WAIT_CHUNK))
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .0;
+                    }
+                    drop(cancelled);
+                    let Some(interval) = interval else {
+                        let _ = sender.send(event);
+                        return;
+                    };
+                    if sender.send(event.clone()).is_err() {
+                        return;
+                    }
+                    let now = Instant::now();
+                    while deadline <= now {
+                        deadline += interval;
+                    }
+                }
+            })?;
+        Ok(Self {
+            cancel,
+            thread: Some(thread),
+        })
+    }
+
+    /// Stops the timer; a one-shot timer that has not fired yet never will.
+    pub fn cancel(self) {
+        drop(self);
+    }
+
+    /// Lets the timer keep running after this handle is gone: a one-shot timer still fires, a
+    /// repeating one runs until its backend is dropped.
+    pub fn detach(mut self) {
+        self.thread = None;
+    }
+
+    /// Whether the timer has stopped on its own: a one-shot timer has fired, or the backend it
+    /// sends to has been dropped.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_none_or(std::thread::JoinHandle::is_finished)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+        let (cancelled, wake) = &*self.cancel;
+        *cancelled.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wake.notify_all();
+        let _ = thread.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_queues_in_order_and_reports_senders() {
+        let inbox = EventInbox::new();
+        assert!(!inbox.has_senders());
+        let sender = inbox.sender();
+        assert!(inbox.has_senders());
+
+        let clone = sender.clone();
+        sender.send(Event::Custom(1)).unwrap();
+        clone.send(Event::Custom(2)).unwrap();
+        drop(clone);
+        assert_eq!(inbox.try_recv(), Some(Event::Custom(1)));
+        assert_eq!(inbox.try_recv(), Some(Event::Custom(2)));
+        assert_eq!(inbox.try_recv(), None);
+
+        drop(sender);
+        assert!(!inbox.has_senders());
+    }
+
+    #[test]
+    fn send_after_the_inbox_is_dropped_hands_the_event_back() {
+        let inbox = EventInbox::new();
+        let sender = inbox.sender();
+        drop(inbox);
+        assert!(sender.is_closed());
+        assert_eq!(
+            sender.send(Event::Close).map_err(SendError::into_inner),
+            Err(Event::Close)
+        );
+    }
+
+    #[test]
+    fn recv_timeout_returns_immediately_without_senders() {
+        let inbox = EventInbox::new();
+        let start = Instant::now();
+        assert_eq!(inbox.recv_timeout(Duration::MAX), None);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn recv_timeout_wakes_on_a_send_from_another_thread() {
+        let inbox = EventInbox::new();
+        let sender = inbox.sender();
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            sender.send(Event::Custom(9))
+        });
+        assert_eq!(inbox.recv_timeout(Duration::MAX), Some(Event::Custom(9)));
+        worker.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn recv_timeout_gives_up_once_the_last_sender_drops() {
+        let inbox = EventInbox::new();
+        let sender = inbox.sender();
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            drop(sender);
+        });
+        assert_eq!(inbox.recv_timeout(Duration::MAX), None);
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn send_runs_the_notify_hook_and_wakes_a_registered_waker() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::task::Wake;
+
+        struct Count(AtomicUsize);
+        impl Wake for Count {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let inbox = EventInbox::new();
+        let notified = Arc::new(AtomicUsize::new(0));
+        let hook = Arc::clone(&notified);
+        inbox.set_notify(move || {
+            hook.fetch_add(1, Ordering::SeqCst);
+        });
+        let woken = Arc::new(Count(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&woken));
+
+        assert_eq!(inbox.poll_recv(&waker), None);
+        inbox.sender().send(Event::FocusGained).unwrap();
+        assert_eq!(woken.0.load(Ordering::SeqCst), 1);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert_eq!(inbox.poll_recv(&waker), Some(Event::FocusGained));
+    }
+
+    #[test]
+    fn one_shot_timer_fires_once() {
+        let inbox = EventInbox::new();
+        let timer = inbox
+            .sender()
+            .schedule(Duration::from_millis(5), Event::Custom(3))
+            .unwrap();
+        assert_eq!(inbox.recv_timeout(Duration::MAX), Some(Event::Custom(3)));
+        assert_eq!(inbox.recv_timeout(Duration::MAX), None);
+        assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn dropping_a_timer_cancels_it() {
+        let inbox = EventInbox::new();
+        let timer = inbox
+            .sender()
+            .schedule(Duration::from_secs(3600), Event::Custom(3))
+            .unwrap();
+        let start = Instant::now();
+        drop(timer);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!inbox.has_senders());
+        assert_eq!(inbox.try_recv(), None);
+    }
+
+    #[test]
+    fn repeating_timer_ticks_until_cancelled() {
+        let inbox = EventInbox::new();
+        let timer = inbox
+            .sender()
+            .schedule_repeating(Duration::from_millis(2), Event::Custom(5))
+            .unwrap();
+        for _ in 0..3 {
+            assert_eq!(inbox.recv_timeout(Duration::MAX), Some(Event::Custom(5)));
+        }
+        timer.cancel();
+        while inbox.try_recv().is_some() {}
+        assert_eq!(inbox.recv_timeout(Duration::MAX), None);
+    }
+
+    #[test]
+    fn repeating_timer_stops_when_the_inbox_is_dropped() {
+        let inbox = EventInbox::new();
+        let timer = inbox
+            .sender()
+            .schedule_repeating(Duration::from_millis(1), Event::Custom(5))
+            .unwrap();
+        drop(inbox);
+        let start = Instant::now();
+        while !timer.is_finished() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "timer never stopped"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
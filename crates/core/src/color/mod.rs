@@ -6,21 +6,24 @@
 //! `Color`'s inherent methods (constants, RGB resolution, `gem` color-space conversions),
 //! `named` is `Color`'s string-name/hex constructors (`from_named`, `from_hex`), `palette_oklab`
 //! is the generated Oklab table `ansi` quantizes against, `parse` is `Color`'s
-//! `Display`/`FromStr`/serde impls, `sgr` (public: shared with `retroglyph-recorder`) is the SGR
-//! ANSI encoding [`Style`] resolves to, `style` is [`Style`](crate::color::Style) itself, and
-//! `tint` is [`Tint`](crate::color::Tint), sprite colour modulation.
+//! `Display`/`FromStr`/serde impls, `remap` is [`Remap`](crate::color::Remap), sprite palette
+//! swaps, `sgr` (public: shared with `retroglyph-recorder`) is the SGR ANSI encoding [`Style`]
+//! resolves to, `style` is [`Style`](crate::color::Style) itself, and `tint` is
+//! [`Tint`](crate::color::Tint), sprite color modulation.
 
 mod ansi;
 mod convert;
 mod named;
 mod palette_oklab;
 mod parse;
+mod remap;
 pub mod sgr;
 mod style;
 mod tint;
 
 pub use ansi::{AnsiColor, InvalidAnsiIndex, Quantize};
 pub use parse::ParseColorError;
+pub use remap::Remap;
 pub use style::Style;
 pub use tint::Tint;
 
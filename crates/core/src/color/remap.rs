@@ -0,0 +1,273 @@
+//! Sprite palette swaps: recoloring a sprite by looking its pixels up in a small table.
+
+/// A palette swap for a sprite: a small lookup table from the artwork's own colors to the
+/// colors to draw instead.
+///
+/// [`Tint`](crate::color::Tint) can only scale a sprite toward black or blend all of it toward one
+/// color. Neither can turn a green goblin's skin red while leaving its brown leather alone, give
+/// one unit blue trim and another orange, or repaint a four-shade grayscale sheet in a warm ramp.
+/// A remap can, because it decides per source color rather than per sprite.
+///
+/// There are two kinds of table, picked by constructor:
+///
+/// - [`colors`](Self::colors) swaps exact colors: a pixel whose RGB equals an entry's source
+///   takes that entry's target, and every other pixel is left alone. This is the classic
+///   palette swap for full-color art, where the artist used a handful of known key colors.
+/// - [`ramp`](Self::ramp) recolors by brightness: each pixel's gray level picks the nearest of
+///   the ramp's evenly spaced shades, darkest first. Every pixel is mapped. This is the natural
+///   fit for a `retroglyph_window::tileset::SheetColor::Mask` sheet, whose grayscale pixels are
+///   already ramp indices in all but name.
+///
+/// A pixel the remap maps takes its target color as drawn: a mask sheet's multiply by the cell's
+/// foreground is skipped for it, since the remap already said what color it is. The cell's
+/// [`Tint`](crate::color::Tint) still applies on top, so a remapped goblin can still flash white
+/// when hit. See `retroglyph_window::sprite_cache::SpriteTint` for the order both pixel backends
+/// follow.
+///
+/// Like a tint, alpha is never touched, and cell backends ignore a remap entirely.
+///
+/// The table holds at most [`MAX_ENTRIES`](Self::MAX_ENTRIES) entries. That bound is what lets a
+/// remap be `Copy` and travel to the GPU backends' shaders as a fixed-size per-sprite payload
+/// rather than through a separate table indexed per draw.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::color::Remap;
+///
+/// // A goblin in blue team colors: green skin stays, the red tabard turns blue.
+/// let team = Remap::colors(&[((200, 30, 30), (30, 60, 200)), ((120, 10, 10), (10, 20, 120))]);
+/// assert_eq!(team.apply((200, 30, 30)), (30, 60, 200));
+/// assert_eq!(team.apply((60, 160, 40)), (60, 160, 40));
+///
+/// // A four-shade grayscale sheet repainted in a sepia ramp, darkest shade first.
+/// let sepia = Remap::ramp(&[(40, 20, 10), (110, 70, 40), (180, 140, 90), (240, 220, 180)]);
+/// assert_eq!(sepia.apply((0, 0, 0)), (40, 20, 10));
+/// assert_eq!(sepia.apply((170, 170, 170)), (180, 140, 90));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Remap {
+    kind: RemapKind,
+    len: u8,
+    sources: [(u8, u8, u8); Self::MAX_ENTRIES],
+    targets: [(u8, u8, u8); Self::MAX_ENTRIES],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+enum RemapKind {
+    #[default]
+    Colors,
+    Ramp,
+}
+
+impl Remap {
+    /// The most entries one remap can hold.
+    pub const MAX_ENTRIES: usize = 8;
+
+    /// The empty remap, which leaves every pixel as authored. Also the [`Default`].
+    pub const NONE: Self = Self {
+        kind: RemapKind::Colors,
+        len: 0,
+        sources: [(0, 0, 0); Self::MAX_ENTRIES],
+        targets: [(0, 0, 0); Self::MAX_ENTRIES],
+    };
+
+    /// A remap swapping each `(source, target)` pair's exact source color for its target.
+    ///
+    /// Where two pairs share a source, the first wins. An empty slice is [`NONE`](Self::NONE).
+    ///
+    /// # Panics
+    ///
+    /// If `pairs` holds more than [`MAX_ENTRIES`](Self::MAX_ENTRIES) entries.
+    #[must_use]
+    #[allow(clippy::type_complexity)] // A pair of RGB triples reads plainest spelled out.
+    pub const fn colors(pairs: &[((u8, u8, u8), (u8, u8, u8))]) -> Self {
+        assert!(
+            pairs.len() <= Self::MAX_ENTRIES,
+            "a Remap holds at most Remap::MAX_ENTRIES entries"
+        );
+        let mut out = Self::NONE;
+        let mut i = 0;
+        while i < pairs.len() {
+            out.sources[i] = pairs[i].0;
+            out.targets[i] = pairs[i].1;
+            i += 1;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let len = pairs.len() as u8;
+        out.len = len;
+        out
+    }
+
+    /// A remap repainting every pixel from `shades` by its gray level, darkest shade first.
+    ///
+    /// The shades stand for evenly spaced gray levels from black to white, and a pixel takes the
+    /// shade whose level is nearest its own: with four shades, `0`, `85`, `170`, and `255` land
+    /// exactly on the first through fourth. A colored pixel's gray level is its integer luma,
+    /// `(77r + 150g + 29b + 128) / 256`, which is exact for a gray pixel. A single shade paints
+    /// the whole sprite that color, a silhouette. An empty slice is [`NONE`](Self::NONE).
+    ///
+    /// # Panics
+    ///
+    /// If `shades` holds more than [`MAX_ENTRIES`](Self::MAX_ENTRIES) entries.
+    #[must_use]
+    pub const fn ramp(shades: &[(u8, u8, u8)]) -> Self {
+        assert!(
+            shades.len() <= Self::MAX_ENTRIES,
+            "a Remap holds at most Remap::MAX_ENTRIES entries"
+        );
+        let mut out = Self::NONE;
+        let mut i = 0;
+        while i < shades.len() {
+            out.targets[i] = shades[i];
+            i += 1;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let len = shades.len() as u8;
+        out.len = len;
+        if len > 0 {
+            out.kind = RemapKind::Ramp;
+        }
+        out
+    }
+
+    /// Whether this remap leaves every pixel exactly as authored, so a renderer can skip it.
+    #[must_use]
+    pub const fn is_identity(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether this is a [`ramp`](Self::ramp) rather than a [`colors`](Self::colors) table.
+    #[must_use]
+    pub const fn is_ramp(&self) -> bool {
+        matches!(self.kind, RemapKind::Ramp)
+    }
+
+    /// The colors a [`colors`](Self::colors) table swaps out, in entry order. Empty for a ramp.
+    #[must_use]
+    pub const fn sources(&self) -> &[(u8, u8, u8)] {
+        if self.is_ramp() {
+            &[]
+        } else {
+            self.sources.split_at(self.len as usize).0
+        }
+    }
+
+    /// The colors this remap draws: each [`colors`](Self::colors) entry's target, or a
+    /// [`ramp`](Self::ramp)'s shades darkest first.
+    #[must_use]
+    pub const fn targets(&self) -> &[(u8, u8, u8)] {
+        self.targets.split_at(self.len as usize).0
+    }
+
+    /// The color this remap maps one straight-alpha RGB triple to, or `None` if it leaves that
+    /// pixel alone.
+    ///
+    /// The reference implementation of the lookup. The software renderer calls it per pixel and
+    /// the GPU renderers match its arithmetic in their sprite fragment shaders, the same
+    /// arrangement as [`Tint::apply`](crate::color::Tint::apply).
+    #[must_use]
+    pub const fn lookup(&self, rgb: (u8, u8, u8)) -> Option<(u8, u8, u8)> {
+        let len = self.len as usize;
+        if len == 0 {
+            return None;
+        }
+        match self.kind {
+            RemapKind::Colors => {
+                let mut i = 0;
+                while i < len {
+                    let (r, g, b) = self.sources[i];
+                    if r == rgb.0 && g == rgb.1 && b == rgb.2 {
+                        return Some(self.targets[i]);
+                    }
+                    i += 1;
+                }
+                None
+            }
+            RemapKind::Ramp => {
+                let (r, g, b) = (rgb.0 as u32, rgb.1 as u32, rgb.2 as u32);
+                let gray = (77 * r + 150 * g + 29 * b + 128) >> 8;
+                let index = (gray * (self.len as u32 - 1) + 127) / 255;
+                Some(self.targets[index as usize])
+            }
+        }
+    }
+
+    /// Applies this remap to one straight-alpha RGB triple: its mapped color, or the pixel
+    /// unchanged.
+    #[must_use]
+    pub const fn apply(&self, rgb: (u8, u8, u8)) -> (u8, u8, u8) {
+        match self.lookup(rgb) {
+            Some(mapped) => mapped,
+            None => rgb,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Remap;
+
+    #[test]
+    fn none_is_the_identity_and_the_default() {
+        assert!(Remap::NONE.is_identity());
+        assert_eq!(Remap::default(), Remap::NONE);
+        assert_eq!(Remap::NONE.lookup((1, 2, 3)), None);
+        assert_eq!(Remap::colors(&[]), Remap::NONE);
+        assert_eq!(Remap::ramp(&[]), Remap::NONE);
+    }
+
+    #[test]
+    fn colors_swaps_exact_matches_only_and_the_first_entry_wins() {
+        let r = Remap::colors(&[((10, 20, 30), (1, 1, 1)), ((10, 20, 30), (2, 2, 2))]);
+        assert_eq!(r.lookup((10, 20, 30)), Some((1, 1, 1)));
+        // One channel off is a different color, not a near match.
+        assert_eq!(r.lookup((10, 20, 31)), None);
+        assert_eq!(r.apply((10, 20, 31)), (10, 20, 31));
+        assert_eq!(r.sources(), &[(10, 20, 30), (10, 20, 30)]);
+    }
+
+    #[test]
+    fn a_ramp_maps_evenly_spaced_grays_exactly_onto_its_shades() {
+        let shades = [(1, 0, 0), (2, 0, 0), (3, 0, 0), (4, 0, 0)];
+        let r = Remap::ramp(&shades);
+        for (gray, shade) in [0u8, 85, 170, 255].into_iter().zip(shades) {
+            assert_eq!(r.lookup((gray, gray, gray)), Some(shade), "gray {gray}");
+        }
+        // Every pixel is mapped, to the nearest shade: 42 rounds down, 43 up.
+        assert_eq!(r.lookup((42, 42, 42)), Some((1, 0, 0)));
+        assert_eq!(r.lookup((43, 43, 43)), Some((2, 0, 0)));
+        assert!(r.sources().is_empty());
+        assert_eq!(r.targets(), &shades);
+    }
+
+    #[test]
+    fn a_single_shade_ramp_is_a_silhouette() {
+        let r = Remap::ramp(&[(255, 255, 255)]);
+        for px in [(0, 0, 0), (200, 10, 90), (255, 255, 255)] {
+            assert_eq!(r.apply(px), (255, 255, 255));
+        }
+    }
+
+    #[test]
+    fn a_ramp_reads_a_colored_pixel_by_its_luma() {
+        // Pure green is bright (150/256) and pure blue dark (29/256).
+        let r = Remap::ramp(&[(0, 0, 0), (255, 255, 255)]);
+        assert_eq!(r.apply((0, 255, 0)), (255, 255, 255));
+        assert_eq!(r.apply((0, 0, 255)), (0, 0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most")]
+    fn too_many_entries_panics() {
+        let _ = Remap::ramp(&[(0, 0, 0); Remap::MAX_ENTRIES + 1]);
+    }
+
+    // Pin the doc example's worked values, as `Tint`'s tests do.
+    #[test]
+    fn doc_example_values_are_what_apply_actually_returns() {
+        let sepia = Remap::ramp(&[(40, 20, 10), (110, 70, 40), (180, 140, 90), (240, 220, 180)]);
+        assert_eq!(sepia.apply((0, 0, 0)), (40, 20, 10));
+        assert_eq!(sepia.apply((170, 170, 170)), (180, 140, 90));
+    }
+}
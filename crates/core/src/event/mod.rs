@@ -17,11 +17,12 @@ use alloc::collections::VecDeque;
 use alloc::string::String;
 
 /// The system's light/dark color-scheme preference, as reported by the
-/// windowing/browser layer.
+/// windowing/browser layer or read off the terminal's background color.
 ///
 /// Currently just these two variants: every source that can report this
-/// (winit's `Theme`, the browser's `prefers-color-scheme` media query) only
-/// ever resolves to one of exactly these two, and a backend that can't
+/// (winit's `Theme`, the browser's `prefers-color-scheme` media query, a
+/// terminal's OSC 11 background color) only ever resolves to one of exactly
+/// these two, and a backend that can't
 /// determine a preference simply never emits [`Event::ThemeChanged`] rather
 /// than emitting a third "unknown" case for callers to handle. Marked
 /// `#[non_exhaustive]` for consistency with sibling public enums, in case a
@@ -65,11 +66,12 @@ pub enum Event {
     /// Only backends with a real source of truth for this emit it: the
     /// windowed (winit) backend, on both native and wasm (winit's web
     /// target derives it from the browser's `prefers-color-scheme` media
-    /// query, including live updates). Character-mode backends (crossterm)
-    /// have no equivalent free API (see the windowed backend's own docs
-    /// for why) and never emit this; an app that wants a default should
-    /// pick one itself rather than waiting for an event that may never
-    /// arrive.
+    /// query, including live updates), and the crossterm backend, once at
+    /// startup, from the luminance of the background color the terminal
+    /// reports in reply to an OSC 11 query. A terminal has no way to announce
+    /// a later change, and one that doesn't answer the query produces no
+    /// event at all, so an app that wants a default should pick one itself
+    /// rather than waiting for an event that may never arrive.
     ThemeChanged(SystemTheme),
     /// Pasted text, delivered as a single event rather than individual key
     /// presses.
@@ -104,6 +106,32 @@ pub enum Event {
     /// the real payload in whatever shared state or channel the sending
     /// thread already placed it in.
     Custom(u64),
+    /// The process was asked to interrupt what it is doing (`SIGINT`).
+    ///
+    /// A terminal in raw mode delivers `Ctrl-C` as an ordinary [`Event::Key`], so this comes
+    /// from `kill -INT`, or from `Ctrl-C` with raw mode off. Like the other signal events, only
+    /// emitted by a backend the app opted into signal handling on (`retroglyph-crossterm`'s
+    /// `CrosstermOptions::signals`); the signal's default action (ending the process) does not
+    /// run, so an app that should stop has to return
+    /// [`Flow::Exit`](crate::app::Flow::Exit) itself.
+    Interrupt,
+    /// The process was asked to terminate (`SIGTERM`), typically by a process manager or a
+    /// shutdown. The app should save what it needs to and exit.
+    Terminate,
+    /// The controlling terminal went away (`SIGHUP`): the SSH session dropped or the terminal
+    /// window closed. Nothing drawn from here on reaches anyone, so the app should exit.
+    HangUp,
+    /// The process is about to be suspended (`SIGTSTP`, `Ctrl-Z` with raw mode off): the
+    /// terminal is handed back to the shell before the process actually stops, the next time the
+    /// backend is polled. Save anything that must survive the process never being resumed.
+    Suspended,
+    /// The process was resumed after being suspended (`SIGCONT`, `fg` in the shell).
+    ///
+    /// Whatever ran in the meantime may have drawn over the screen. When this event comes from
+    /// [`Terminal::poll`](crate::terminal::Terminal::poll), the terminal has already forgotten
+    /// what it last presented, so the next present redraws every cell; as after a resize, a
+    /// [retained layer](crate::terminal::Terminal::retain_layer) has to be drawn again too.
+    Resumed,
 }
 
 /// Whether `new` should replace the queue's current tail event instead of being pushed alongside
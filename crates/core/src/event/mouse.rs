@@ -67,7 +67,11 @@ pub struct MouseEvent {
     /// Physical pixel position of the mouse cursor, relative to the window's top-left.
     ///
     /// Populated by backends that support sub-cell precision (e.g. the software
-    /// renderer). `None` on character-mode backends such as crossterm.
+    /// renderer), and by crossterm on terminals that report mouse positions in pixels
+    /// (SGR-Pixels mode). `None` everywhere else.
+    ///
+    /// A windowed backend that scales its frame into the window reports the frame pixel under
+    /// the cursor, so the position lines up with the cell grid at any window size.
     pub pixel_position: Option<PhysicalPos>,
     /// Modifiers held down during the event.
     pub modifiers: KeyModifiers,
@@ -65,6 +65,19 @@ impl FrameClock {
         }
     }
 
+    /// Caps catch-up at `steps` logic steps per frame instead of the default five (builder
+    /// style).
+    ///
+    /// A higher cap lets a slow frame be made up in full at the cost of a longer stall while it
+    /// is; a lower one sheds simulated time sooner. `0` is treated as `1`: a cap of zero would
+    /// never let [`tick`](Self::tick) return `true`.
+    #[must_use]
+    pub fn with_max_catch_up(mut self, steps: u32) -> Self {
+        self.max_accumulate = self.step * steps.max(1);
+        self.accumulator = self.accumulator.min(self.max_accumulate);
+        self
+    }
+
     /// The fixed timestep duration.
     #[must_use]
     pub const fn step(&self) -> Duration {
@@ -147,6 +160,22 @@ mod tests {
         assert_eq!(steps, 5); // clamped to max_accumulate (5 steps)
     }
 
+    #[test]
+    fn with_max_catch_up_overrides_the_default_cap() {
+        let mut clock = FrameClock::new(60).with_max_catch_up(8);
+        clock.advance(Duration::from_secs(10));
+        let mut steps = 0;
+        while clock.tick() {
+            steps += 1;
+        }
+        assert_eq!(steps, 8);
+
+        let mut clock = FrameClock::new(60).with_max_catch_up(0);
+        clock.advance(Duration::from_secs(10));
+        assert!(clock.tick());
+        assert!(!clock.tick());
+    }
+
     #[test]
     fn a_huge_hz_does_not_produce_a_zero_step() {
         // retroglyph#729: `1.0 / hz` used to round below `Duration`'s 1ns resolution for `hz`
@@ -9,10 +9,13 @@ use crate::color::Style;
 #[cfg(any(test, feature = "egc"))]
 use crate::color::Tint;
 #[cfg(feature = "egc")]
+use crate::tile::Transform;
+#[cfg(feature = "egc")]
 use crate::tile::cap_grapheme;
 use crate::tile::{Tile, TileFlags};
 #[cfg(feature = "egc")]
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use grixy::ops::GridWrite;
 
 impl Grid {
@@ -39,6 +42,7 @@ impl Grid {
             layers: alloc::vec![Some(LayerBuf::new(width, height))],
             max_layer: 0,
             has_spans: false,
+            line_sizes: Vec::new(),
         }
     }
 
@@ -182,6 +186,8 @@ impl Grid {
     ///
     /// Both repairs are bounded by the shrunk edge, not the whole grid, so a growing resize pays
     /// nothing for either.
+    ///
+    /// Rows that survive keep their [`LineSize`](crate::grid::LineSize); new rows start normal.
     pub fn resize(&mut self, width: u16, height: u16) {
         let old_width = usize::from(self.width);
         let old_height = usize::from(self.height);
@@ -191,6 +197,7 @@ impl Grid {
         let height_shrank = new_height < old_height;
         self.width = width;
         self.height = height;
+        self.line_sizes.truncate(new_height);
         for layer in self.layers.iter_mut().flatten() {
             // The extras side-table is keyed by flat row-major index, which
             // shifts whenever the width changes: remap it in lockstep with
@@ -299,6 +306,8 @@ impl Grid {
                 TileExtra {
                     grapheme: Some(Arc::from(cap_grapheme(grapheme))),
                     tint: Tint::None,
+                    transform: Transform::IDENTITY,
+                    remap: None,
                 },
             );
         }
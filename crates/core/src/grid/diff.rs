@@ -6,7 +6,7 @@ use crate::backend::DrawCell;
 #[cfg(test)]
 use crate::color::Style;
 use crate::color::Tint;
-use crate::tile::Tile;
+use crate::tile::{Tile, Transform};
 #[cfg(test)]
 use alloc::vec::Vec;
 
@@ -19,7 +19,7 @@ impl Grid {
     /// - Layer in `self`, absent in `other` (newly allocated): all `width × height` tiles
     ///   yielded.
     /// - Layer in both, and `self` and `other` have matching dimensions: only positions where
-    ///   the `Tile` or its side-table entry (grapheme text, tint) differs are yielded.
+    ///   the `Tile` or its side-table entry (grapheme text, tint, transform, remap) differs are yielded.
     /// - Layer in both, but `self` and `other` have different dimensions: all positions in
     ///   `self` are considered changed, same as a newly allocated layer.
     /// - Layer in `other` but no longer in `self` (stopped being written): every position is
@@ -28,6 +28,10 @@ impl Grid {
     ///   change and layer teardown falls back to "nothing yielded" for that layer, matching a
     ///   layer absent from both.
     ///
+    /// Line sizes (see [`LineSize`](crate::grid::LineSize)) take part too: every cell of a row
+    /// whose size differs from `other`'s is yielded as changed, and cells past a double-size
+    /// row's visible half are never yielded at all.
+    ///
     /// This iterator is zero-allocation: it walks the layer buffers inline.
     pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = DrawCell<'a>> + 'a {
         let width = usize::from(self.width);
@@ -47,36 +51,41 @@ impl Grid {
                 // dimensions, checked by `other_layer` above) still does. Report every position
                 // as cleared so a compositing backend can retire the layer instead of continuing
                 // to show its stale content; see retroglyph#1018.
-                (None, Some(prev_lb)) => LayerDiff::Cleared(
-                    prev_lb.buf.as_ref().iter().enumerate().map(move |(i, _)| {
-                        let (x, y) = flat_index_to_xy(i, width);
-                        DrawCell {
-                            layer: id,
-                            pos: Pos::new(x, y),
-                            tile: &Tile::EMPTY,
-                            grapheme: None,
-                            tint: Tint::None,
-                        }
-                    }),
-                ),
+                (None, Some(prev_lb)) => {
+                    LayerDiff::Cleared(prev_lb.buf.as_ref().iter().enumerate().filter_map(
+                        move |(i, _)| {
+                            let (x, y) = flat_index_to_xy(i, width);
+                            self.cell_is_visible(x, y).then(|| DrawCell {
+                                layer: id,
+                                pos: Pos::new(x, y),
+                                tile: &Tile::EMPTY,
+                                grapheme: None,
+                                tint: Tint::None,
+                                transform: Transform::IDENTITY,
+                                remap: None,
+                                line: self.line_size(y),
+                            })
+                        },
+                    ))
+                }
                 // Newly allocated layer: all cells are "changed".
-                (Some(cur_lb), None) => LayerDiff::Full(
-                    cur_lb
-                        .buf
-                        .as_ref()
-                        .iter()
-                        .enumerate()
-                        .map(move |(i, tile)| {
+                (Some(cur_lb), None) => {
+                    LayerDiff::Full(cur_lb.buf.as_ref().iter().enumerate().filter_map(
+                        move |(i, tile)| {
                             let (x, y) = flat_index_to_xy(i, width);
-                            DrawCell {
+                            self.cell_is_visible(x, y).then(|| DrawCell {
                                 layer: id,
                                 pos: Pos::new(x, y),
                                 tile,
                                 grapheme: cur_lb.extra_for(i, tile),
                                 tint: cur_lb.tint_for(i, tile),
-                            }
-                        }),
-                ),
+                                transform: cur_lb.transform_for(i, tile),
+                                remap: cur_lb.remap_for(i, tile),
+                                line: self.line_size(y),
+                            })
+                        },
+                    ))
+                }
                 // Layer in both: only the differing cells. Compared by hand
                 // (rather than delegating to grixy's `GridDiff`) because a
                 // `Tile`-only comparison can't see grapheme-text changes: two
@@ -88,20 +97,30 @@ impl Grid {
                         move |(i, tile)| {
                             let prev_tile = &prev_lb.buf.as_ref()[i];
                             // The whole entry, not just its grapheme: a `Tile`-only comparison
-                            // cannot see a change to either member of the side table, and a
+                            // cannot see a change to any member of the side table, and a
                             // tint-only change is as real a redraw as a combining-mark change.
                             let cur_extra = cur_lb.entry_for(i, tile);
                             let prev_extra = prev_lb.entry_for(i, prev_tile);
-                            if tile == prev_tile && cur_extra == prev_extra {
+                            let (x, y) = flat_index_to_xy(i, width);
+                            // A row that changed size is redrawn whole: its cells move on screen
+                            // even where their tiles did not change.
+                            let line = self.line_size(y);
+                            if (tile == prev_tile
+                                && cur_extra == prev_extra
+                                && line == other.line_size(y))
+                                || !self.cell_is_visible(x, y)
+                            {
                                 return None;
                             }
-                            let (x, y) = flat_index_to_xy(i, width);
                             Some(DrawCell {
                                 layer: id,
                                 pos: Pos::new(x, y),
                                 tile,
                                 grapheme: cur_extra.and_then(|e| e.grapheme.as_deref()),
                                 tint: cur_extra.map_or(Tint::None, |e| e.tint),
+                                transform: cur_extra.map_or(Transform::IDENTITY, |e| e.transform),
+                                remap: cur_extra.and_then(|e| e.remap.as_deref()),
+                                line,
                             })
                         },
                     ))
@@ -141,6 +160,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::LineSize;
 
     #[test]
     fn diff_reports_a_written_cell_as_a_draw_cell() {
@@ -283,4 +303,33 @@ mod tests {
         prev2.set_tint(0, 0, 0, Tint::multiply(64, 128, 192));
         assert_eq!(cur.diff(&prev2).count(), 0);
     }
+
+    #[test]
+    fn diff_skips_cells_past_a_double_rows_visible_half() {
+        let mut cur = Grid::new(4, 1);
+        let prev = Grid::new(4, 1);
+        cur.put_tile(0, (1, 0), Tile::new('A', Style::default()));
+        cur.put_tile(0, (3, 0), Tile::new('B', Style::default()));
+        cur.set_line_size(0, LineSize::DoubleWidth);
+        let diffs: Vec<_> = cur.diff(&prev).collect();
+        assert!(diffs.iter().all(|c| c.pos.x < 2));
+        assert!(diffs.iter().any(|c| c.tile.glyph == 'A'));
+    }
+
+    #[test]
+    fn diff_resends_a_row_whose_line_size_changed() {
+        let mut cur = Grid::new(4, 2);
+        let mut prev = Grid::new(4, 2);
+        cur.put_tile(0, (0, 1), Tile::new('A', Style::default()));
+        prev.put_tile(0, (0, 1), Tile::new('A', Style::default()));
+        cur.set_line_size(1, LineSize::DoubleHeightTop);
+        let diffs: Vec<_> = cur.diff(&prev).collect();
+        // Both visible cells of row 1 are sent, carrying the new size; row 0 is unchanged.
+        assert_eq!(diffs.len(), 2);
+        assert!(
+            diffs
+                .iter()
+                .all(|c| c.pos.y == 1 && c.line == LineSize::DoubleHeightTop)
+        );
+    }
 }
@@ -16,7 +16,8 @@ impl Grid {
     /// layer-major (0 → `max_layer`) then row-major order. `grapheme` is
     /// `Some` only when [`TileFlags::HAS_EXTRA`] is set.
     ///
-    /// Unallocated layers are skipped. This is used by backends that need
+    /// Unallocated layers are skipped, and so are the cells past a double-size row's visible
+    /// half (see [`LineSize`](crate::grid::LineSize)). This is used by backends that need
     /// the full frame on every draw (see [`crate::backend::Compositing::PixelLayered`]'s
     /// `needs_full_frame` field).
     ///
@@ -26,25 +27,34 @@ impl Grid {
         (0..=self.max_layer)
             .filter_map(move |id| self.layer(id).map(|lb| (id, lb)))
             .flat_map(move |(id, lb)| {
-                lb.buf.as_ref().iter().enumerate().map(move |(i, tile)| {
-                    let (x, y) = flat_index_to_xy(i, width);
-                    DrawCell {
-                        layer: id,
-                        pos: Pos::new(x, y),
-                        tile,
-                        grapheme: lb.extra_for(i, tile),
-                        tint: lb.tint_for(i, tile),
-                    }
-                })
+                lb.buf
+                    .as_ref()
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, tile)| {
+                        let (x, y) = flat_index_to_xy(i, width);
+                        self.cell_is_visible(x, y).then(|| DrawCell {
+                            layer: id,
+                            pos: Pos::new(x, y),
+                            tile,
+                            grapheme: lb.extra_for(i, tile),
+                            tint: lb.tint_for(i, tile),
+                            transform: lb.transform_for(i, tile),
+                            remap: lb.remap_for(i, tile),
+                            line: self.line_size(y),
+                        })
+                    })
             })
     }
 
-    /// Clears every allocated layer.
+    /// Clears every allocated layer, and puts every row back to
+    /// [`LineSize::Normal`](crate::grid::LineSize::Normal).
     pub fn clear_all(&mut self) {
         for layer in self.layers.iter_mut().flatten() {
             layer.buf.clear();
             layer.extras.clear();
         }
+        self.clear_line_sizes();
     }
 
     /// Composites every allocated layer into `dst`'s layer 0, one tile per cell.
@@ -67,7 +77,8 @@ impl Grid {
     /// Because an explicit space is not empty, drawing one on a higher layer
     /// overwrites (erases) the glyph beneath it.
     ///
-    /// `dst` must have the same dimensions as `self`.
+    /// `dst` must have the same dimensions as `self`, and takes `self`'s line sizes along with
+    /// its cells.
     ///
     /// Walks layer buffers directly by flat index instead of calling
     /// [`tile`](Self::tile) per cell (see retroglyph#262): that recomputes a coordinate
@@ -81,6 +92,7 @@ impl Grid {
             "flatten_into requires dst to have the same dimensions as self"
         );
         dst.has_spans |= self.has_spans;
+        dst.copy_line_sizes_from(self);
         let layer0 = self.layer0();
         let cell_count = layer0.buf.as_ref().len();
 
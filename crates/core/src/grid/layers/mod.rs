@@ -514,6 +514,8 @@ mod tests {
             TileExtra {
                 grapheme: None,
                 tint: Tint::multiply(1, 2, 3),
+                transform: crate::tile::Transform::IDENTITY,
+                remap: None,
             },
         );
         assert_eq!(g.max_layer(), 0);
@@ -1,20 +1,25 @@
-//! Per-cell tint and grapheme-extras storage: [`Grid::tint`] and [`Grid::set_tint`], plus the
-//! shared side-table primitive [`Grid::set_extra`] both write through.
+//! Per-cell tint, transform, remap, and grapheme-extras storage: [`Grid::tint`],
+//! [`Grid::set_tint`], [`Grid::transform`], [`Grid::set_transform`], [`Grid::remap`], and
+//! [`Grid::set_remap`], plus the shared side-table primitive [`Grid::set_extra`] they all write
+//! through.
 
 use super::super::{Grid, Pos, TileExtra, to_grixy_pos};
 #[cfg(test)]
 use crate::color::Style;
-use crate::color::Tint;
+use crate::color::{Remap, Tint};
 #[cfg(test)]
 use crate::tile::Tile;
 use crate::tile::TileFlags;
+use crate::tile::Transform;
+use alloc::sync::Arc;
 use grixy::ops::GridRead;
 
 impl Grid {
     /// Sets the whole side-table entry for an already-written tile at `(x, y)` on `layer`,
     /// setting [`TileFlags::HAS_EXTRA`] to match. Does nothing if out of bounds. Crate-private:
-    /// the external ways in are [`write_grapheme`](Self::write_grapheme) and
-    /// [`set_tint`](Self::set_tint).
+    /// the external ways in are [`write_grapheme`](Self::write_grapheme),
+    /// [`set_tint`](Self::set_tint), [`set_transform`](Self::set_transform), and
+    /// [`set_remap`](Self::set_remap).
     ///
     /// An empty entry is removed rather than stored, so the flag means exactly "an entry
     /// exists".
@@ -34,7 +39,7 @@ impl Grid {
         }
     }
 
-    /// How a pixel backend recolours the sprite drawn for the cell at `(x, y)` on `layer`.
+    /// How a pixel backend recolors the sprite drawn for the cell at `(x, y)` on `layer`.
     ///
     /// [`Tint::None`] for a cell that has never been tinted, for a cell whose glyph was
     /// overwritten since (a glyph write drops the tint with the artwork it belonged to), and for
@@ -44,7 +49,7 @@ impl Grid {
     /// grapheme is: it is rare per cell and `Tile` has no room
     /// left. So it is read here, not through [`Tile::style`](crate::tile::Tile::style).
     ///
-    /// Cell backends have no sprite to recolour and ignore this entirely.
+    /// Cell backends have no sprite to recolor and ignore this entirely.
     #[must_use]
     pub fn tint(&self, layer: u8, x: u16, y: u16) -> Tint {
         let Some(lb) = self.layer(layer) else {
@@ -57,7 +62,7 @@ impl Grid {
         lb.tint_for(idx, tile)
     }
 
-    /// Sets how a pixel backend recolours the sprite drawn for the cell at `(x, y)` on `layer`.
+    /// Sets how a pixel backend recolors the sprite drawn for the cell at `(x, y)` on `layer`.
     ///
     /// Applies to the cell as it stands, so it belongs *after* the write that put the glyph
     /// there: writing a glyph over a tinted cell drops the tint, on the grounds that a tint
@@ -67,20 +72,101 @@ impl Grid {
     /// Setting [`Tint::None`] clears the tint, and drops the cell's side-table entry entirely if
     /// it held nothing else. Does nothing if `(x, y)` is out of bounds.
     pub fn set_tint(&mut self, layer: u8, x: u16, y: u16, tint: Tint) {
+        self.update_extra(layer, x, y, |e| e.tint = tint);
+    }
+
+    /// How a pixel backend flips, rotates, and scales the sprite drawn for the cell at `(x, y)`
+    /// on `layer`.
+    ///
+    /// [`Transform::IDENTITY`] under the same conditions [`tint`](Self::tint) answers
+    /// [`Tint::None`]: never set, overwritten since, or out of bounds. Like a tint it is grid
+    /// state rather than [`Tile`](crate::tile::Tile) state, and cell backends ignore it unless
+    /// they were handed a [`GlyphTransforms`](crate::tile::GlyphTransforms) table.
+    #[must_use]
+    pub fn transform(&self, layer: u8, x: u16, y: u16) -> Transform {
+        let Some(lb) = self.layer(layer) else {
+            return Transform::IDENTITY;
+        };
+        let Some(tile) = lb.buf.get(to_grixy_pos(Pos::new(x, y))) else {
+            return Transform::IDENTITY;
+        };
+        let idx = usize::from(y) * usize::from(self.width) + usize::from(x);
+        lb.transform_for(idx, tile)
+    }
+
+    /// Sets how a pixel backend flips, rotates, and scales the sprite drawn for the cell at
+    /// `(x, y)` on `layer`.
+    ///
+    /// Follows [`set_tint`](Self::set_tint) in every respect: it applies to the glyph already
+    /// written there and is dropped when that glyph is overwritten, a multi-cell span takes it
+    /// on its anchor, and [`Transform::IDENTITY`] clears it. Does nothing if `(x, y)` is out of
+    /// bounds.
+    pub fn set_transform(&mut self, layer: u8, x: u16, y: u16, transform: Transform) {
+        self.update_extra(layer, x, y, |e| e.transform = transform);
+    }
+
+    /// The palette swap a pixel backend applies to the sprite drawn for the cell at `(x, y)` on
+    /// `layer`.
+    ///
+    /// [`Remap::NONE`] under the same conditions [`tint`](Self::tint) answers [`Tint::None`]:
+    /// never set, overwritten since, or out of bounds. Cell backends ignore it.
+    #[must_use]
+    pub fn remap(&self, layer: u8, x: u16, y: u16) -> Remap {
+        let Some(lb) = self.layer(layer) else {
+            return Remap::NONE;
+        };
+        let Some(tile) = lb.buf.get(to_grixy_pos(Pos::new(x, y))) else {
+            return Remap::NONE;
+        };
+        let idx = usize::from(y) * usize::from(self.width) + usize::from(x);
+        lb.remap_for(idx, tile).copied().unwrap_or_default()
+    }
+
+    /// Sets the palette swap a pixel backend applies to the sprite drawn for the cell at
+    /// `(x, y)` on `layer`.
+    ///
+    /// Follows [`set_tint`](Self::set_tint) in every respect: it applies to the glyph already
+    /// written there and is dropped when that glyph is overwritten, a multi-cell span takes it
+    /// on its anchor, and [`Remap::NONE`] clears it. A remap and a tint
+    /// on the same cell compose, remap first. Does nothing if `(x, y)` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::color::Remap;
+    /// use retroglyph_core::grid::Grid;
+    ///
+    /// let mut grid = Grid::new(8, 2);
+    /// let blue_team = Remap::colors(&[((200, 30, 30), (30, 60, 200))]);
+    /// for x in 0..8 {
+    ///     grid.set_remap(0, x, 0, blue_team);
+    /// }
+    /// assert_eq!(grid.remap(0, 3, 0), blue_team);
+    ///
+    /// grid.set_remap(0, 3, 0, Remap::NONE);
+    /// assert_eq!(grid.remap(0, 3, 0), Remap::NONE);
+    /// ```
+    pub fn set_remap(&mut self, layer: u8, x: u16, y: u16, remap: Remap) {
+        let remap = (!remap.is_identity()).then(|| Arc::new(remap));
+        self.update_extra(layer, x, y, |e| e.remap = remap);
+    }
+
+    /// Rewrites one member of the side-table entry at `(x, y)` on `layer` through `f`, keeping
+    /// the others: the members are written by separate calls and none should clobber the rest.
+    /// Does nothing if out of bounds.
+    fn update_extra(&mut self, layer: u8, x: u16, y: u16, f: impl FnOnce(&mut TileExtra)) {
         if x >= self.width || y >= self.height {
             return;
         }
         let idx = usize::from(y) * usize::from(self.width) + usize::from(x);
         let pos = to_grixy_pos(Pos::new(x, y));
         let lb = self.layer_or_alloc(layer);
-        // Preserve any grapheme already stored for this cell: the two members of the entry are
-        // written by separate calls and neither should clobber the other.
-        let grapheme = if lb.buf[pos].flags.contains(TileFlags::HAS_EXTRA) {
-            lb.extras.get(&idx).and_then(|e| e.grapheme.clone())
+        let mut entry = if lb.buf[pos].flags.contains(TileFlags::HAS_EXTRA) {
+            lb.extras.get(&idx).cloned().unwrap_or_default()
         } else {
-            None
+            TileExtra::default()
         };
-        let entry = TileExtra { grapheme, tint };
+        f(&mut entry);
         if entry.is_empty() {
             lb.buf[pos].flags.remove(TileFlags::HAS_EXTRA);
             lb.extras.remove(&idx);
@@ -144,7 +230,7 @@ mod tests {
         g.set_tint(0, 1, 1, Tint::multiply(128, 128, 128));
 
         // A tint describes the artwork that was drawn, not the position, so replacing the
-        // artwork drops it rather than silently recolouring whatever lands there next.
+        // artwork drops it rather than silently recoloring whatever lands there next.
         g.write_grapheme(0, 1, 1, "#", Style::default());
         assert_eq!(g.tint(0, 1, 1), Tint::None);
     }
@@ -186,4 +272,87 @@ mod tests {
         assert_eq!(crate::grid::grapheme_at(&g, 0, 1, 1), None);
         assert_eq!(g.tint(0, 1, 1), Tint::multiply(128, 128, 128));
     }
+
+    // ── Transform storage ─────────────────────────────────────────────────
+    //
+    // A transform is the side table's third member and rides the same paths as a tint; these
+    // check it round-trips and coexists with the other two rather than re-testing the table.
+    #[test]
+    fn transform_round_trips_and_defaults_to_identity() {
+        let mut g = Grid::new(4, 4);
+        assert_eq!(g.transform(0, 1, 1), Transform::IDENTITY);
+
+        let t = Transform::IDENTITY.flipped_x().rotated(1).scaled(2);
+        g.set_transform(0, 1, 1, t);
+        assert_eq!(g.transform(0, 1, 1), t);
+        assert_eq!(g.transform(0, 2, 1), Transform::IDENTITY);
+
+        g.set_transform(0, 1, 1, Transform::IDENTITY);
+        assert_eq!(g.transform(0, 1, 1), Transform::IDENTITY);
+        assert!(
+            !g.layer(0).unwrap().buf[to_grixy_pos(Pos::new(1, 1))]
+                .flags
+                .contains(TileFlags::HAS_EXTRA)
+        );
+    }
+
+    #[test]
+    fn a_transform_and_a_tint_do_not_clobber_each_other() {
+        let mut g = Grid::new(4, 4);
+        g.set_tint(0, 1, 1, Tint::multiply(1, 2, 3));
+        g.set_transform(0, 1, 1, Transform::IDENTITY.flipped_y());
+        assert_eq!(g.tint(0, 1, 1), Tint::multiply(1, 2, 3));
+        assert_eq!(g.transform(0, 1, 1), Transform::IDENTITY.flipped_y());
+
+        g.set_tint(0, 1, 1, Tint::None);
+        assert_eq!(g.transform(0, 1, 1), Transform::IDENTITY.flipped_y());
+    }
+
+    #[test]
+    fn put_tile_drops_the_transform() {
+        let mut g = Grid::new(4, 4);
+        g.set_transform(0, 1, 1, Transform::IDENTITY.rotated(2));
+        g.put_tile(0, Pos::new(1, 1), Tile::new('x', Style::default()));
+        assert_eq!(g.transform(0, 1, 1), Transform::IDENTITY);
+    }
+
+    // ── Remap storage ─────────────────────────────────────────────────────
+    //
+    // The fourth member, on the same paths again.
+    #[test]
+    fn remap_round_trips_and_clears_with_none() {
+        let mut g = Grid::new(4, 4);
+        assert_eq!(g.remap(0, 1, 1), Remap::NONE);
+
+        let team = Remap::colors(&[((255, 0, 0), (0, 0, 255))]);
+        g.set_remap(0, 1, 1, team);
+        assert_eq!(g.remap(0, 1, 1), team);
+        assert_eq!(g.remap(0, 2, 1), Remap::NONE);
+        assert_eq!(g.remap(0, 9, 9), Remap::NONE);
+
+        g.set_remap(0, 1, 1, Remap::NONE);
+        assert_eq!(g.remap(0, 1, 1), Remap::NONE);
+        assert!(
+            !g.layer(0).unwrap().buf[to_grixy_pos(Pos::new(1, 1))]
+                .flags
+                .contains(TileFlags::HAS_EXTRA)
+        );
+    }
+
+    #[test]
+    fn a_remap_keeps_the_tint_and_is_dropped_by_put_tile() {
+        let mut g = Grid::new(4, 4);
+        let ramp = Remap::ramp(&[(0, 0, 0), (255, 0, 0)]);
+        g.set_tint(0, 1, 1, Tint::mix(255, 255, 255, 128));
+        g.set_remap(0, 1, 1, ramp);
+        assert_eq!(g.tint(0, 1, 1), Tint::mix(255, 255, 255, 128));
+        assert_eq!(g.remap(0, 1, 1), ramp);
+
+        // A cell's draw carries the remap borrowed from the side table.
+        let cell = g.layers().find(|c| c.pos == Pos::new(1, 1)).unwrap();
+        assert_eq!(cell.remap, Some(&ramp));
+
+        g.put_tile(0, Pos::new(1, 1), Tile::new('x', Style::default()));
+        assert_eq!(g.remap(0, 1, 1), Remap::NONE);
+    }
 }
@@ -0,0 +1,193 @@
+//! Per-row [`LineSize`]: the VT100 double-width and double-height line attributes
+//! (`DECDWL`/`DECDHL`), stored on [`Grid`] beside its layers.
+
+use super::Grid;
+
+/// How a whole grid row is drawn: at normal size, or as one of the VT100 double-size line
+/// attributes big title banners are made of.
+///
+/// Every double-size row shows only the first half of its columns: each cell is drawn two columns
+/// wide, so column `x` lands where columns `2x` and `2x + 1` would be, and everything from
+/// [`columns`](Self::columns) onward falls off the right edge. Those cells keep whatever they
+/// hold, and reappear if the row goes back to [`Normal`](Self::Normal), but no backend is sent
+/// them while the row is doubled.
+///
+/// A double-height line is two rows: [`DoubleHeightTop`](Self::DoubleHeightTop) draws the top half
+/// of each glyph and [`DoubleHeightBottom`](Self::DoubleHeightBottom) the bottom half, so a banner
+/// writes the same text into both, the way a VT100 expects it.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::grid::{Grid, LineSize};
+///
+/// let mut grid = Grid::new(80, 24);
+/// grid.set_line_size(0, LineSize::DoubleHeightTop);
+/// grid.set_line_size(1, LineSize::DoubleHeightBottom);
+///
+/// assert_eq!(grid.line_size(0), LineSize::DoubleHeightTop);
+/// assert_eq!(grid.line_columns(0), 40);
+/// assert_eq!(grid.line_columns(2), 80);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineSize {
+    /// One column per cell, the default (`DECSWL`, `ESC # 5`).
+    #[default]
+    Normal,
+    /// Each cell two columns wide, one row tall (`DECDWL`, `ESC # 6`).
+    DoubleWidth,
+    /// The top half of a double-height line (`DECDHL`, `ESC # 3`).
+    DoubleHeightTop,
+    /// The bottom half of a double-height line (`DECDHL`, `ESC # 4`).
+    DoubleHeightBottom,
+}
+
+impl LineSize {
+    /// How many of a `width`-column row's cells are visible at this size: all of them at
+    /// [`Normal`](Self::Normal), half (rounded down) at every double size.
+    #[must_use]
+    pub const fn columns(self, width: u16) -> u16 {
+        match self {
+            Self::Normal => width,
+            _ => width / 2,
+        }
+    }
+
+    /// Whether each cell is drawn two columns wide: true for every size but
+    /// [`Normal`](Self::Normal).
+    #[must_use]
+    pub const fn is_double_width(self) -> bool {
+        !matches!(self, Self::Normal)
+    }
+
+    /// Whether this row is one half of a double-height line.
+    #[must_use]
+    pub const fn is_double_height(self) -> bool {
+        matches!(self, Self::DoubleHeightTop | Self::DoubleHeightBottom)
+    }
+}
+
+impl Grid {
+    /// The size row `y` is drawn at; [`LineSize::Normal`] for a row that was never set, or one
+    /// past the bottom edge.
+    #[must_use]
+    pub fn line_size(&self, y: u16) -> LineSize {
+        self.line_sizes
+            .get(usize::from(y))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Draws row `y` at `size` from now on. A no-op for a row past the bottom edge, the same as
+    /// any other out-of-bounds write.
+    ///
+    /// Line sizes belong to the grid, not to a layer: a double-size row doubles every layer's
+    /// cells on it. Like tiles, they are frame content, so
+    /// [`Terminal::present`](crate::terminal::Terminal::present) resets every row to
+    /// [`LineSize::Normal`] along with the rest of the grid and an app sets its banners again
+    /// each frame.
+    pub fn set_line_size(&mut self, y: u16, size: LineSize) {
+        let row = usize::from(y);
+        if y >= self.height {
+            return;
+        }
+        // Left empty until some row actually leaves `Normal`, so a grid that never uses double
+        // size lines pays for nothing, the same trade the layer table makes.
+        if self.line_sizes.len() <= row {
+            if size == LineSize::Normal {
+                return;
+            }
+            self.line_sizes
+                .resize(usize::from(self.height), LineSize::Normal);
+        }
+        self.line_sizes[row] = size;
+    }
+
+    /// How many of row `y`'s cells are visible: the grid's width, or half of it on a double-size
+    /// row (see [`LineSize::columns`]).
+    ///
+    /// This is the edge to lay text out against on a banner row: [`Surface`](crate::surface::Surface)'s
+    /// `print*` methods and [`TextLayout`](crate::layout::TextLayout) already wrap and align to it.
+    #[must_use]
+    pub fn line_columns(&self, y: u16) -> u16 {
+        self.line_size(y).columns(self.width)
+    }
+
+    /// Whether `(x, y)` lies in the part of its row a backend is shown: every cell of a normal
+    /// row, and the left half of a double-size one.
+    pub(crate) fn cell_is_visible(&self, x: u16, y: u16) -> bool {
+        x < self.line_columns(y)
+    }
+
+    /// Resets every row to [`LineSize::Normal`].
+    pub(crate) fn clear_line_sizes(&mut self) {
+        self.line_sizes.clear();
+    }
+
+    /// Makes `self`'s line sizes an exact copy of `src`'s, for grids of the same height.
+    pub(crate) fn copy_line_sizes_from(&mut self, src: &Self) {
+        self.line_sizes.clone_from(&src.line_sizes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_start_normal_and_take_the_size_they_are_set_to() {
+        let mut grid = Grid::new(10, 3);
+        assert_eq!(grid.line_size(1), LineSize::Normal);
+        grid.set_line_size(1, LineSize::DoubleWidth);
+        assert_eq!(grid.line_size(1), LineSize::DoubleWidth);
+        assert_eq!(grid.line_size(0), LineSize::Normal);
+        assert_eq!(grid.line_size(2), LineSize::Normal);
+    }
+
+    #[test]
+    fn a_row_past_the_bottom_edge_is_ignored() {
+        let mut grid = Grid::new(10, 3);
+        grid.set_line_size(3, LineSize::DoubleWidth);
+        assert_eq!(grid.line_size(3), LineSize::Normal);
+    }
+
+    #[test]
+    fn double_size_rows_show_half_their_columns_rounded_down() {
+        let mut grid = Grid::new(11, 2);
+        grid.set_line_size(0, LineSize::DoubleHeightBottom);
+        assert_eq!(grid.line_columns(0), 5);
+        assert_eq!(grid.line_columns(1), 11);
+        assert!(grid.cell_is_visible(4, 0));
+        assert!(!grid.cell_is_visible(5, 0));
+        assert!(grid.cell_is_visible(10, 1));
+    }
+
+    #[test]
+    fn resizing_keeps_the_sizes_of_rows_that_survive() {
+        let mut grid = Grid::new(10, 3);
+        grid.set_line_size(0, LineSize::DoubleWidth);
+        grid.set_line_size(2, LineSize::DoubleWidth);
+        grid.resize(10, 2);
+        assert_eq!(grid.line_size(0), LineSize::DoubleWidth);
+        grid.resize(10, 3);
+        assert_eq!(grid.line_size(2), LineSize::Normal);
+    }
+
+    #[test]
+    fn clear_all_resets_every_row_to_normal() {
+        let mut grid = Grid::new(10, 3);
+        grid.set_line_size(0, LineSize::DoubleHeightTop);
+        grid.clear_all();
+        assert_eq!(grid.line_size(0), LineSize::Normal);
+    }
+
+    #[test]
+    fn flattening_carries_the_line_sizes() {
+        let mut grid = Grid::new(4, 2);
+        grid.set_line_size(1, LineSize::DoubleWidth);
+        let mut flat = Grid::new(4, 2);
+        grid.flatten_into(&mut flat);
+        assert_eq!(flat.line_size(1), LineSize::DoubleWidth);
+    }
+}
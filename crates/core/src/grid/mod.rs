@@ -101,6 +101,16 @@
 //! write; use [`clear_span`](crate::grid::Grid::clear_span) first if you reach for one of those on a grid
 //! that uses spans.
 //!
+//! ## Double-size lines
+//!
+//! Besides its layers, a grid carries one [`LineSize`](crate::grid::LineSize) per row, the VT100
+//! `DECDWL`/`DECDHL` line attributes: set with [`set_line_size`](crate::grid::Grid::set_line_size),
+//! a row is drawn with every cell two columns wide (and, for a double-height pair, two rows tall),
+//! so only its first [`line_columns`](crate::grid::Grid::line_columns) cells fit on screen. Cells
+//! past that edge are never sent to a backend, and every [`DrawCell`](crate::backend::DrawCell)
+//! carries its row's size in [`line`](crate::backend::DrawCell::line), so a backend learns about a
+//! banner from the same stream as its text.
+//!
 //! ## Naming: `put_*`/`write_*`/`print_*`
 //!
 //! `put_*` names a raw single-slot write: it stores exactly the [`Tile`](crate::tile::Tile)/`char`/grapheme
@@ -137,9 +147,10 @@
 //! layer 255). See [`max_layer`](crate::grid::Grid::max_layer) for the iteration cost this
 //! implies and [`Grid::new`](crate::grid::Grid::new) for the allocation cost of a first write.
 
-use crate::color::Tint;
+use crate::color::{Remap, Tint};
 use crate::tile::Tile;
 use crate::tile::TileFlags;
+use crate::tile::Transform;
 use alloc::collections::BTreeMap;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
@@ -150,6 +161,8 @@ use alpha_blend::BlendMode as SeparableBlendMode;
 use grixy::buf::GridBuf;
 use grixy::ops::layout::{LinearLayout, RowMajor};
 
+pub use lines::LineSize;
+
 /// `.width()`/`.height()` accessors for [`Size`](crate::grid::Size) (and [`Rect`](crate::grid::Rect)): re-exported so callers don't need
 /// a direct `ixy` dependency just to call them on this crate's own type aliases.
 pub use ixy::HasSize;
@@ -157,6 +170,7 @@ pub use ixy::HasSize;
 mod api;
 mod diff;
 mod layers;
+mod lines;
 mod spans;
 mod trait_impls;
 
@@ -367,16 +381,22 @@ pub(crate) struct LayerBuf {
 
 /// One cell's out-of-line data: everything that belongs to a tile but does not fit in one.
 ///
-/// [`Tile`](crate::tile::Tile) is exactly 20 bytes with no padding to spare, and both members here are rare enough
-/// per cell that inlining either would grow every tile of every layer to pay for a minority of
+/// [`Tile`](crate::tile::Tile) is exactly 20 bytes with no padding to spare, and every member here is rare enough
+/// per cell that inlining any of them would grow every tile of every layer to pay for a minority of
 /// them. They share one table, one flag, and one set of rekeying paths rather than each bringing
 /// their own.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct TileExtra {
     /// The full grapheme cluster, when [`Tile::glyph`](crate::tile::Tile::glyph) holds only its first codepoint.
     pub(crate) grapheme: Option<Arc<str>>,
-    /// How a pixel backend recolours this cell's sprite.
+    /// How a pixel backend recolors this cell's sprite.
     pub(crate) tint: Tint,
+    /// How a pixel backend flips, rotates, and scales this cell's sprite.
+    pub(crate) transform: Transform,
+    /// The palette swap a pixel backend applies to this cell's sprite. Held by [`Arc`] because
+    /// a table is several times the size of everything else here: inline, it would grow every
+    /// entry, and copying an entry (a blit, a resize) would copy the table with it.
+    pub(crate) remap: Option<Arc<Remap>>,
 }
 
 impl TileExtra {
@@ -385,7 +405,10 @@ impl TileExtra {
     /// Keeping the table free of empty entries is what lets `HAS_EXTRA` be set exactly when an
     /// entry exists, instead of the flag and the table disagreeing about an all-default value.
     fn is_empty(&self) -> bool {
-        self.grapheme.is_none() && self.tint == Tint::None
+        self.grapheme.is_none()
+            && self.tint == Tint::None
+            && self.transform.is_identity()
+            && self.remap.is_none()
     }
 }
 
@@ -420,6 +443,19 @@ impl LayerBuf {
         self.entry_for(idx, tile).map_or(Tint::None, |e| e.tint)
     }
 
+    /// Returns the transform for the tile at flat index `idx`, or [`Transform::IDENTITY`](crate::tile::Transform::IDENTITY) if
+    /// `tile` doesn't have [`TileFlags::HAS_EXTRA`] set.
+    fn transform_for(&self, idx: usize, tile: &Tile) -> Transform {
+        self.entry_for(idx, tile)
+            .map_or(Transform::IDENTITY, |e| e.transform)
+    }
+
+    /// Returns the palette swap for the tile at flat index `idx`, or `None` if `tile` doesn't
+    /// have [`TileFlags::HAS_EXTRA`] set or carries no remap.
+    fn remap_for(&self, idx: usize, tile: &Tile) -> Option<&Remap> {
+        self.entry_for(idx, tile)?.remap.as_deref()
+    }
+
     /// Returns a clone of the side-table entry at flat index `idx`, or `None` if `tile` doesn't
     /// have [`TileFlags::HAS_EXTRA`] set. Used to copy a cell's out-of-line data between grids
     /// (e.g. [`Grid::blit`](crate::grid::Grid::blit)); the grapheme rides along as an `Arc` clone rather than a fresh
@@ -486,6 +522,10 @@ pub struct Grid {
     /// at all: it degrades to one `bool` test. Clearing it again on the last span's removal
     /// would need span refcounting for no observable gain.
     has_spans: bool,
+    /// Each row's [`LineSize`], indexed by row; empty until some row leaves
+    /// [`LineSize::Normal`] (see [`set_line_size`](Self::set_line_size)), and never longer than
+    /// `height`.
+    line_sizes: Vec<LineSize>,
 }
 
 // ---------------------------------------------------------------------------
@@ -577,6 +617,9 @@ impl Grid {
     /// fallback, treat empty tiles as transparent) is wrong for a copy that is supposed to be a
     /// full replacement of identical geometry.
     ///
+    /// Copying layer 0 also copies `src`'s [`LineSize`]s, which belong to no single layer but
+    /// are only ever set alongside the base content they size.
+    ///
     /// If `layer` is unallocated on `src`, it becomes unallocated on `self` too (mirroring an
     /// always-empty layer exactly). Layer 0 can never hit this case: it is always allocated on
     /// every `Grid` (see [`Grid::new`](crate::grid::Grid::new)), on `src` as much as on `self`.
@@ -596,6 +639,11 @@ impl Grid {
             None => self.set_layer(layer, None),
         }
         self.has_spans |= src.has_spans;
+        // Line sizes double every layer on their row, so they travel with the base layer: a
+        // retained layer 0 keeps last frame's banners along with their text.
+        if layer == 0 {
+            self.copy_line_sizes_from(src);
+        }
     }
 }
 
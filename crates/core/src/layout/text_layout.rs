@@ -121,9 +121,24 @@ impl<'a> TextLayout<'a> {
     }
 
     /// Renders the text into `grid` on `layer`, clipping to the rect's bounds.
+    ///
+    /// A double-size row (see [`LineSize`](crate::grid::LineSize)) only shows the part of the
+    /// rect that falls in its visible half, so the text wraps to the narrowest such part among
+    /// the rect's rows and each line is aligned within its own row's part.
     pub fn render_to_grid(&self, grid: &mut Grid, layer: u8) {
-        let lines = wrap_line(self.line, self.rect.width());
         let rect = self.rect;
+        let visible_right = |grid: &Grid, row: u16| {
+            if grid.line_size(row).is_double_width() {
+                rect.right().min(grid.line_columns(row).max(rect.left()))
+            } else {
+                rect.right()
+            }
+        };
+        let wrap_width = (rect.top()..rect.bottom())
+            .map(|row| visible_right(grid, row) - rect.left())
+            .min()
+            .unwrap_or_else(|| rect.width());
+        let lines = wrap_line(self.line, wrap_width);
 
         #[allow(clippy::cast_possible_truncation)]
         let total_lines = lines.len().min(usize::from(rect.height())) as u16;
@@ -131,14 +146,14 @@ impl<'a> TextLayout<'a> {
         let y_offset = self.v_align.offset(rect.height(), total_lines);
 
         for (line_idx, wrapped) in lines.into_iter().take(total_lines as usize).enumerate() {
-            let x_offset = self.h_align.offset(rect.width(), wrapped.width);
-
             #[allow(clippy::cast_possible_truncation)]
             let row = rect.top() + y_offset + line_idx as u16;
+            let right = visible_right(grid, row);
+            let x_offset = self.h_align.offset(right - rect.left(), wrapped.width);
             let mut cx = rect.left() + x_offset;
 
             for glyph in wrapped.glyphs {
-                if cx + glyph.width > rect.right() {
+                if cx + glyph.width > right {
                     break;
                 }
                 grid.write_grapheme(layer, cx, row, &glyph.grapheme, glyph.style);
@@ -317,4 +332,25 @@ mod tests {
         assert_eq!(term.grid()[Pos::new(0, 0)].glyph(), ' ');
         assert_eq!(term.grid()[Pos::new(1, 0)].glyph(), ' ');
     }
+
+    #[test]
+    fn test_render_centers_within_a_double_rows_visible_half() {
+        use crate::backend::Headless;
+        use crate::grid::LineSize;
+        use crate::terminal::Terminal;
+
+        let mut term = Terminal::new(Headless::new(20, 2));
+        let line = Line::raw("hi");
+        {
+            let mut s = term.surface();
+            s.set_line_size(0, LineSize::DoubleWidth);
+            TextLayout::new(&line)
+                .rect(Rect::new(0, 0, 20, 1))
+                .h_align(HAlign::Center)
+                .render_to_surface(&mut s);
+        }
+        // Ten visible columns: "hi" centers at 4..6, not at the full row's 9..11.
+        assert_eq!(term.grid()[Pos::new(4, 0)].glyph(), 'h');
+        assert_eq!(term.grid()[Pos::new(5, 0)].glyph(), 'i');
+    }
 }
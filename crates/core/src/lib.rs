@@ -28,6 +28,17 @@
 //! Enables grapheme-cluster-aware text handling (via `unicode-segmentation`) for EGC-correct cell
 //! diffing and layout.
 //!
+//! ### `futures`
+//!
+//! ⚪ Optional.
+//!
+//! Implements `futures_core::Stream` for [`EventStream`](crate::terminal::EventStream) and adds
+//! [`from_stream`](crate::terminal::from_stream), which turns any `Stream<Item = Event>` into an
+//! [`EventSource`](crate::terminal::EventSource).
+//!
+//! The async driver and `EventStream` itself need nothing from this: they are built on
+//! `core::future` alone, so they work under any executor with or without it.
+//!
 //! ### `libm`
 //!
 //! ⚪ Optional.
@@ -158,6 +169,8 @@ pub mod layout;
 // and never `#[deprecated]` it, both of which would make it public API again despite the hiding.
 #[doc(hidden)]
 pub mod math;
+/// A stack of scenes (title, gameplay, overlays, ...) driven as one `App`.
+pub mod scene;
 // See the `too_long_first_doc_paragraph` comment above `animate`: same noisy-lint mis-attribution.
 #[allow(clippy::too_long_first_doc_paragraph)]
 /// The one grid-drawing primitive: an area-clipped, single-layer view over a [`Grid`](crate::grid::Grid).
@@ -0,0 +1,906 @@
+//! A stack of [`Scene`](crate::scene::Scene)s driven as one [`App`](crate::app::App).
+//!
+//! Most games are a handful of screens (title, gameplay, inventory, pause, game over) layered on
+//! top of each other, and every one of them needs the same state machine around
+//! [`App::update`](crate::app::App::update): which screen gets input, which ones draw, and what
+//! happens when one opens or closes another. [`SceneStack`](crate::scene::SceneStack) is that
+//! state machine. It owns a stack of boxed scenes and is itself an [`App`](crate::app::App), so
+//! every driver ([`run_on_with`](crate::app::run_on_with), `retroglyph-window`'s `run_app`,
+//! [`TestHarness`](crate::testing::TestHarness)) runs it unchanged.
+//!
+//! Each frame the stack:
+//!
+//! 1. drains the terminal's pending events and hands each one to the *top* scene only, via
+//!    [`Scene::event`](crate::scene::Scene::event);
+//! 2. calls [`Scene::update`](crate::scene::Scene::update) on the top scene;
+//! 3. draws every visible scene bottom-up via [`Scene::draw`](crate::scene::Scene::draw).
+//!
+//! A scene whose [`is_overlay`](crate::scene::Scene::is_overlay) returns `true` is transparent:
+//! the scenes below it keep drawing underneath, but only the overlay receives input and updates.
+//! An opaque scene hides everything below it, so those scenes are not drawn at all.
+//!
+//! Scenes change the stack by returning a [`Transition`](crate::scene::Transition) from `event`
+//! or `update`. A push, pop, or replace can animate with an
+//! [`Animation`](crate::scene::Animation): the outgoing and incoming views are drawn side by side
+//! for the animation's duration, advanced by [`Frame::delta`](crate::app::Frame::delta).
+//!
+//! # Idle frames
+//!
+//! The stack returns [`Flow::Idle`](crate::app::Flow::Idle) (and skips drawing) only when nothing
+//! could have changed: no event arrived, no transition ran or is still animating, and the top
+//! scene's `update` returned [`Transition::Idle`](crate::scene::Transition::Idle). An
+//! event-driven driver therefore blocks between inputs for a stack of static menus, and keeps
+//! presenting every frame while an animation is in flight.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+use crate::app::{App, Flow, Frame};
+use crate::backend::Backend;
+use crate::event::Event;
+use crate::grid::Rect;
+use crate::surface::Surface;
+use crate::terminal::Terminal;
+
+/// One screen of a [`SceneStack`](crate::scene::SceneStack): a title menu, the game itself, a
+/// pause overlay, ...
+///
+/// Only [`draw`](Self::draw) is required. Every other hook has a default that does nothing (or,
+/// for [`event`](Self::event)/[`update`](Self::update), keeps the scene running).
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::app::{App, Frame};
+/// use retroglyph_core::backend::{Backend, Headless};
+/// use retroglyph_core::color::Style;
+/// use retroglyph_core::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use retroglyph_core::scene::{Scene, SceneStack, Transition};
+/// use retroglyph_core::surface::Surface;
+/// use retroglyph_core::terminal::Terminal;
+/// use std::time::Duration;
+///
+/// struct Title;
+///
+/// impl<B: Backend> Scene<B> for Title {
+///     fn event(&mut self, _term: &mut Terminal<B>, event: &Event) -> Transition<B> {
+///         match event {
+///             Event::Key(k) if k.code == KeyCode::Enter => Transition::replace(Game),
+///             _ => Transition::Idle,
+///         }
+///     }
+///
+///     fn update(&mut self, _term: &mut Terminal<B>, _frame: &Frame) -> Transition<B> {
+///         Transition::Idle
+///     }
+///
+///     fn draw(&mut self, surface: &mut Surface<'_>, _frame: &Frame) {
+///         surface.print((0, 0), "title", Style::default());
+///     }
+/// }
+///
+/// struct Game;
+///
+/// impl<B: Backend> Scene<B> for Game {
+///     fn draw(&mut self, surface: &mut Surface<'_>, _frame: &Frame) {
+///         surface.print((0, 0), "game", Style::default());
+///     }
+/// }
+///
+/// let mut term = Terminal::new(Headless::new(5, 1));
+/// let mut stack = SceneStack::new(Title);
+/// stack.init(&mut term);
+///
+/// term.backend_mut()
+///     .push_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+/// stack.update(&mut term, &Frame::new(Duration::ZERO, 0));
+/// assert!(term.grid().to_string().starts_with("game"));
+/// ```
+pub trait Scene<B: Backend> {
+    /// Runs when this scene joins the stack: when it is pushed or swapped in by a
+    /// [`Transition`](crate::scene::Transition), or from [`App::init`](crate::app::App::init)
+    /// for the scenes a [`SceneStack`](crate::scene::SceneStack) was built with.
+    ///
+    /// Default: does nothing.
+    fn enter(&mut self, _term: &mut Terminal<B>) {}
+
+    /// Runs when this scene leaves the stack for good: once it is popped or replaced and has
+    /// stopped drawing (immediately, or after its outgoing [`Animation`](crate::scene::Animation)
+    /// finishes), or when the whole stack exits.
+    ///
+    /// Default: does nothing.
+    fn exit(&mut self, _term: &mut Terminal<B>) {}
+
+    /// Handles one input event. Only the top scene of the stack receives events.
+    ///
+    /// Return [`Transition::Idle`](crate::scene::Transition::Idle) for an event this scene
+    /// ignored, so the stack can skip redrawing for it.
+    ///
+    /// Default: ignores the event but still asks for a redraw
+    /// ([`Transition::Continue`](crate::scene::Transition::Continue)).
+    fn event(&mut self, _term: &mut Terminal<B>, _event: &Event) -> Transition<B> {
+        Transition::Continue
+    }
+
+    /// Advances this scene by one frame, after this frame's events. Only the top scene of the
+    /// stack is updated.
+    ///
+    /// Default: [`Transition::Continue`](crate::scene::Transition::Continue), i.e. redraw every
+    /// frame.
+    fn update(&mut self, _term: &mut Terminal<B>, _frame: &Frame) -> Transition<B> {
+        Transition::Continue
+    }
+
+    /// Draws this scene.
+    ///
+    /// Called on every visible scene, bottom-up, on each frame the stack renders; not only on the
+    /// top scene. While an [`Animation`](crate::scene::Animation) runs, a scene that is part of
+    /// both the outgoing and the incoming view is drawn twice in one frame, once into each
+    /// clipped (and, for a slide, translated) half of `surface`, so draw from state rather than
+    /// advancing it here.
+    fn draw(&mut self, surface: &mut Surface<'_>, frame: &Frame);
+
+    /// Whether this scene is a transparent overlay: the scenes below it keep drawing underneath,
+    /// but stop receiving input and updates while it is on top.
+    ///
+    /// Default: `false`, an opaque scene that hides everything below it.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// How this scene animates in when pushed or swapped in, and out (in the
+    /// [`reverse`](crate::scene::Direction::reverse) direction) when popped.
+    ///
+    /// Default: `None`, deferring to the stack's own
+    /// [`with_animation`](crate::scene::SceneStack::with_animation) default.
+    fn animation(&self) -> Option<Animation> {
+        None
+    }
+}
+
+/// What a [`Scene`](crate::scene::Scene) asks its stack to do next.
+///
+/// Returned from [`Scene::event`](crate::scene::Scene::event) and
+/// [`Scene::update`](crate::scene::Scene::update).
+#[non_exhaustive]
+pub enum Transition<B: Backend> {
+    /// Keep this scene on top, and redraw.
+    Continue,
+    /// Keep this scene on top; nothing changed. See the module docs' "Idle frames" section.
+    Idle,
+    /// Push a scene on top of this one. This scene stays on the stack, beneath the new one.
+    Push(Box<dyn Scene<B>>),
+    /// Pop this scene, revealing the one below. Popping the last scene exits the stack.
+    Pop,
+    /// Pop this scene and push another in its place.
+    Replace(Box<dyn Scene<B>>),
+    /// Exit every scene, top-down, and stop the loop with [`Flow::Exit`](crate::app::Flow::Exit).
+    Exit,
+}
+
+impl<B: Backend> Transition<B> {
+    /// [`Transition::Push`](crate::scene::Transition::Push), boxing `scene`.
+    #[must_use]
+    pub fn push(scene: impl Scene<B> + 'static) -> Self {
+        Self::Push(Box::new(scene))
+    }
+
+    /// [`Transition::Replace`](crate::scene::Transition::Replace), boxing `scene`.
+    #[must_use]
+    pub fn replace(scene: impl Scene<B> + 'static) -> Self {
+        Self::Replace(Box::new(scene))
+    }
+}
+
+impl<B: Backend> fmt::Debug for Transition<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Continue => "Continue",
+            Self::Idle => "Idle",
+            Self::Push(_) => "Push(..)",
+            Self::Pop => "Pop",
+            Self::Replace(_) => "Replace(..)",
+            Self::Exit => "Exit",
+        })
+    }
+}
+
+/// The direction an incoming view travels across the screen during an
+/// [`Animation`](crate::scene::Animation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Enters from the right edge, moving left.
+    Left,
+    /// Enters from the left edge, moving right.
+    Right,
+    /// Enters from the bottom edge, moving up.
+    Up,
+    /// Enters from the top edge, moving down.
+    Down,
+}
+
+impl Direction {
+    /// The opposite direction: what a popped scene uses to leave the way it came in.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+/// How the outgoing and incoming views share the screen during an
+/// [`Animation`](crate::scene::Animation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Effect {
+    /// Both views stay in place; the boundary between them sweeps across the screen.
+    Wipe(Direction),
+    /// The incoming view pushes the outgoing one off screen.
+    Slide(Direction),
+}
+
+/// An animated [`Transition`](crate::scene::Transition): an [`Effect`](crate::scene::Effect)
+/// played over `duration` of [`Frame::delta`](crate::app::Frame::delta) time.
+///
+/// A zero `duration` is an instant cut, same as no animation at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Animation {
+    effect: Effect,
+    duration: Duration,
+}
+
+impl Animation {
+    /// An animation playing `effect` over `duration`.
+    #[must_use]
+    pub const fn new(effect: Effect, duration: Duration) -> Self {
+        Self { effect, duration }
+    }
+
+    /// Returns the effect.
+    #[must_use]
+    pub const fn effect(&self) -> Effect {
+        self.effect
+    }
+
+    /// Returns the duration.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The same animation, played in the [`reverse`](crate::scene::Direction::reverse)
+    /// direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        let effect = match self.effect {
+            Effect::Wipe(direction) => Effect::Wipe(direction.reverse()),
+            Effect::Slide(direction) => Effect::Slide(direction.reverse()),
+        };
+        Self { effect, ..self }
+    }
+
+    /// Where the outgoing and incoming views land within `area` after `elapsed`: a clip rect and
+    /// a [`Surface::translate`](crate::surface::Surface::translate) origin for each, outgoing
+    /// first.
+    fn layout(&self, area: Rect, elapsed: Duration) -> [(Rect, (i32, i32)); 2] {
+        let (direction, slide) = match self.effect {
+            Effect::Wipe(direction) => (direction, false),
+            Effect::Slide(direction) => (direction, true),
+        };
+        let horizontal = matches!(direction, Direction::Left | Direction::Right);
+        let extent = if horizontal {
+            area.width()
+        } else {
+            area.height()
+        };
+        // Integer progress: how many rows/columns of `extent` the incoming view covers so far.
+        let covered = if elapsed >= self.duration {
+            extent
+        } else {
+            let covered = u128::from(extent) * elapsed.as_nanos() / self.duration.as_nanos().max(1);
+            u16::try_from(covered).unwrap_or(extent)
+        };
+        let rest = extent - covered;
+        let (x, y, w, h) = (area.left(), area.top(), area.width(), area.height());
+        let (out_clip, in_clip) = match direction {
+            Direction::Left => (Rect::new(x, y, rest, h), Rect::new(x + rest, y, covered, h)),
+            Direction::Right => (
+                Rect::new(x + covered, y, rest, h),
+                Rect::new(x, y, covered, h),
+            ),
+            Direction::Up => (Rect::new(x, y, w, rest), Rect::new(x, y + rest, w, covered)),
+            Direction::Down => (
+                Rect::new(x, y + covered, w, rest),
+                Rect::new(x, y, w, covered),
+            ),
+        };
+        if !slide {
+            return [(out_clip, (0, 0)), (in_clip, (0, 0))];
+        }
+        let (covered, rest) = (i32::from(covered), i32::from(rest));
+        let (out_shift, in_shift) = match direction {
+            Direction::Left => ((covered, 0), (-rest, 0)),
+            Direction::Right => ((-covered, 0), (rest, 0)),
+            Direction::Up => ((0, covered), (0, -rest)),
+            Direction::Down => ((0, -covered), (0, rest)),
+        };
+        [(out_clip, out_shift), (in_clip, in_shift)]
+    }
+}
+
+/// Which stack change an in-flight animation is playing.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Change {
+    /// The top scene is new; the outgoing view is everything below it.
+    Push,
+    /// The outgoing view is the whole stack plus the scene that left it.
+    Pop,
+    /// The top scene is new; the outgoing view is everything below it plus the scene it replaced.
+    Replace,
+}
+
+/// A push, pop, or replace whose [`Animation`] is still playing.
+struct InFlight<B: Backend> {
+    change: Change,
+    /// The popped or replaced scene, still drawing until the animation ends, when its
+    /// [`Scene::exit`] runs.
+    leaving: Option<Box<dyn Scene<B>>>,
+    animation: Animation,
+    elapsed: Duration,
+}
+
+/// What applying one [`Transition`] means for the current frame.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Idle,
+    Redraw,
+    Exit,
+}
+
+/// A stack of boxed [`Scene`](crate::scene::Scene)s, driven as one [`App`](crate::app::App).
+///
+/// See the [module docs](crate::scene) for what runs each frame, and [`Scene`](crate::scene::Scene)
+/// for an example.
+pub struct SceneStack<B: Backend> {
+    scenes: Vec<Box<dyn Scene<B>>>,
+    in_flight: Option<InFlight<B>>,
+    animation: Option<Animation>,
+    events: Vec<Event>,
+    /// Set until the first frame renders, which must draw regardless of what the scenes report.
+    dirty: bool,
+}
+
+impl<B: Backend> SceneStack<B> {
+    /// A stack holding just `root`.
+    ///
+    /// `root`'s [`Scene::enter`](crate::scene::Scene::enter) runs from
+    /// [`App::init`](crate::app::App::init), once a live terminal exists.
+    #[must_use]
+    pub fn new(root: impl Scene<B> + 'static) -> Self {
+        Self {
+            scenes: alloc::vec![Box::new(root) as Box<dyn Scene<B>>],
+            in_flight: None,
+            animation: None,
+            events: Vec::new(),
+            dirty: true,
+        }
+    }
+
+    /// Pushes `scene` on top before the stack starts running, e.g. to start on a menu overlaid
+    /// on a live game.
+    ///
+    /// Like the root's, `scene`'s [`Scene::enter`](crate::scene::Scene::enter) runs from
+    /// [`App::init`](crate::app::App::init), bottom-up.
+    #[must_use]
+    pub fn with_scene(mut self, scene: impl Scene<B> + 'static) -> Self {
+        self.scenes.push(Box::new(scene));
+        self
+    }
+
+    /// Animates every transition whose scene has no
+    /// [`animation`](crate::scene::Scene::animation) of its own with `animation`.
+    ///
+    /// Default: none, every transition is an instant cut.
+    #[must_use]
+    pub const fn with_animation(mut self, animation: Animation) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+
+    /// Returns how many scenes are on the stack, not counting one still animating out.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Returns whether the stack is empty, which only happens once it has exited.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Returns whether a transition animation is still playing.
+    #[must_use]
+    pub const fn is_animating(&self) -> bool {
+        self.in_flight.is_some()
+    }
+
+    /// Ends any in-flight animation now, running the leaving scene's [`Scene::exit`].
+    fn finish(&mut self, term: &mut Terminal<B>) {
+        if let Some(mut leaving) = self.in_flight.take().and_then(|f| f.leaving) {
+            leaving.exit(term);
+        }
+    }
+
+    /// Starts playing `animation` for `change`, or ends the change right away (exiting
+    /// `leaving`) if there is nothing to play.
+    fn start(
+        &mut self,
+        term: &mut Terminal<B>,
+        change: Change,
+        leaving: Option<Box<dyn Scene<B>>>,
+        animation: Option<Animation>,
+    ) {
+        match animation {
+            Some(animation) if !animation.duration().is_zero() => {
+                self.in_flight = Some(InFlight {
+                    change,
+                    leaving,
+                    animation,
+                    elapsed: Duration::ZERO,
+                });
+            }
+            _ => {
+                if let Some(mut leaving) = leaving {
+                    leaving.exit(term);
+                }
+            }
+        }
+    }
+
+    /// Applies one scene's requested `transition` to the stack.
+    fn apply(&mut self, term: &mut Terminal<B>, transition: Transition<B>) -> Outcome {
+        match transition {
+            Transition::Continue => Outcome::Redraw,
+            Transition::Idle => Outcome::Idle,
+            Transition::Push(mut scene) => {
+                self.finish(term);
+                let animation = scene.animation().or(self.animation);
+                scene.enter(term);
+                self.scenes.push(scene);
+                self.start(term, Change::Push, None, animation);
+                Outcome::Redraw
+            }
+            Transition::Pop => {
+                self.finish(term);
+                let Some(scene) = self.scenes.pop() else {
+                    return Outcome::Exit;
+                };
+                if self.scenes.is_empty() {
+                    self.start(term, Change::Pop, Some(scene), None);
+                    return Outcome::Exit;
+                }
+                let animation = scene.animation().or(self.animation).map(Animation::reverse);
+                self.start(term, Change::Pop, Some(scene), animation);
+                Outcome::Redraw
+            }
+            Transition::Replace(mut scene) => {
+                self.finish(term);
+                let leaving = self.scenes.pop();
+                let animation = scene.animation().or(self.animation);
+                scene.enter(term);
+                self.scenes.push(scene);
+                self.start(term, Change::Replace, leaving, animation);
+                Outcome::Redraw
+            }
+            Transition::Exit => Outcome::Exit,
+        }
+    }
+
+    /// Exits every scene still on the stack, top-down.
+    fn exit_all(&mut self, term: &mut Terminal<B>) {
+        self.finish(term);
+        while let Some(mut scene) = self.scenes.pop() {
+            scene.exit(term);
+        }
+    }
+
+    /// Draws the visible scenes of the stack `below` plus an optional `top`, bottom-up: the
+    /// topmost opaque scene and everything above it.
+    fn draw_view(
+        below: &mut [Box<dyn Scene<B>>],
+        top: Option<&mut Box<dyn Scene<B>>>,
+        surface: &mut Surface<'_>,
+        frame: &Frame,
+    ) {
+        if top.as_ref().is_none_or(|top| top.is_overlay()) {
+            let base = below.iter().rposition(|s| !s.is_overlay()).unwrap_or(0);
+            for scene in &mut below[base..] {
+                scene.draw(surface, frame);
+            }
+        }
+        if let Some(top) = top {
+            top.draw(surface, frame);
+        }
+    }
+
+    /// Draws this frame: the steady-state stack, or both halves of an in-flight animation.
+    fn draw(&mut self, term: &mut Terminal<B>, frame: &Frame) {
+        let mut surface = term.surface();
+        let Some(in_flight) = &mut self.in_flight else {
+            Self::draw_view(&mut self.scenes, None, &mut surface, frame);
+            return;
+        };
+        let [(out_clip, out_shift), (in_clip, in_shift)] = in_flight
+            .animation
+            .layout(surface.area(), in_flight.elapsed);
+        let split = match in_flight.change {
+            Change::Pop => self.scenes.len(),
+            Change::Push | Change::Replace => self.scenes.len().saturating_sub(1),
+        };
+        Self::draw_view(
+            &mut self.scenes[..split],
+            in_flight.leaving.as_mut(),
+            &mut surface.clip(out_clip).translate(out_shift),
+            frame,
+        );
+        Self::draw_view(
+            &mut self.scenes,
+            None,
+            &mut surface.clip(in_clip).translate(in_shift),
+            frame,
+        );
+    }
+}
+
+impl<B: Backend> fmt::Debug for SceneStack<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SceneStack")
+            .field("len", &self.scenes.len())
+            .field("animating", &self.in_flight.is_some())
+            .field("animation", &self.animation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: Backend> App<B> for SceneStack<B> {
+    fn init(&mut self, term: &mut Terminal<B>) {
+        for scene in &mut self.scenes {
+            scene.enter(term);
+        }
+    }
+
+    fn update(&mut self, term: &mut Terminal<B>, frame: &Frame) -> Flow {
+        if self.scenes.is_empty() {
+            return Flow::Exit;
+        }
+        let animating = self.in_flight.is_some();
+        if let Some(in_flight) = &mut self.in_flight {
+            in_flight.elapsed = in_flight.elapsed.saturating_add(frame.delta);
+            if in_flight.elapsed >= in_flight.animation.duration() {
+                self.finish(term);
+            }
+        }
+        let mut redraw = core::mem::take(&mut self.dirty) || animating;
+
+        let mut events = core::mem::take(&mut self.events);
+        term.drain_events_into(&mut events);
+        let mut outcome = Outcome::Idle;
+        for event in &events {
+            if matches!(event, Event::Resize(..)) {
+                redraw = true;
+            }
+            let Some(top) = self.scenes.last_mut() else {
+                break;
+            };
+            let transition = top.event(term, event);
+            outcome = self.apply(term, transition);
+            if outcome == Outcome::Exit {
+                break;
+            }
+            redraw |= outcome == Outcome::Redraw;
+        }
+        self.events = events;
+
+        if outcome != Outcome::Exit
+            && let Some(top) = self.scenes.last_mut()
+        {
+            let transition = top.update(term, frame);
+            outcome = self.apply(term, transition);
+            redraw |= outcome == Outcome::Redraw;
+        }
+        if outcome == Outcome::Exit {
+            self.exit_all(term);
+            return Flow::Exit;
+        }
+        if !redraw {
+            return Flow::Idle;
+        }
+        self.draw(term, frame);
+        Flow::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Headless;
+    use crate::color::Style;
+    use crate::event::{KeyCode, KeyEvent, KeyModifiers};
+    use alloc::rc::Rc;
+    use alloc::string::String;
+    use alloc::vec;
+    use core::cell::RefCell;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// A scene that fills its whole area with `glyph`, records its hooks into `log`, and reacts
+    /// to keys: `p` pushes an overlay, `o` pops, `r` replaces with a `'r'` scene, `q` exits.
+    struct Fill {
+        glyph: char,
+        overlay: bool,
+        log: Log,
+        animation: Option<Animation>,
+    }
+
+    impl Fill {
+        fn new(glyph: char, log: &Log) -> Self {
+            Self {
+                glyph,
+                overlay: false,
+                log: Rc::clone(log),
+                animation: None,
+            }
+        }
+
+        fn overlay(glyph: char, log: &Log) -> Self {
+            Self {
+                overlay: true,
+                ..Self::new(glyph, log)
+            }
+        }
+
+        fn record(&self, hook: &str) {
+            self.log
+                .borrow_mut()
+                .push(alloc::format!("{hook} {}", self.glyph));
+        }
+    }
+
+    impl Scene<Headless> for Fill {
+        fn enter(&mut self, _term: &mut Terminal<Headless>) {
+            self.record("enter");
+        }
+
+        fn exit(&mut self, _term: &mut Terminal<Headless>) {
+            self.record("exit");
+        }
+
+        fn event(&mut self, _term: &mut Terminal<Headless>, event: &Event) -> Transition<Headless> {
+            self.record("event");
+            match event {
+                Event::Key(k) => match k.code {
+                    KeyCode::Char('p') => Transition::push(Self::overlay('p', &self.log)),
+                    KeyCode::Char('o') => Transition::Pop,
+                    KeyCode::Char('r') => Transition::replace(Self::new('r', &self.log)),
+                    KeyCode::Char('q') => Transition::Exit,
+                    _ => Transition::Idle,
+                },
+                _ => Transition::Idle,
+            }
+        }
+
+        fn update(
+            &mut self,
+            _term: &mut Terminal<Headless>,
+            _frame: &Frame,
+        ) -> Transition<Headless> {
+            Transition::Idle
+        }
+
+        fn draw(&mut self, surface: &mut Surface<'_>, _frame: &Frame) {
+            if self.overlay {
+                // Overlays only cover their first cell, so what's underneath stays visible.
+                surface.put((0, 0), self.glyph, Style::default());
+            } else {
+                let area = Rect::new(0, 0, surface.width(), surface.height());
+                surface.fill_rect(area, self.glyph, Style::default());
+            }
+        }
+
+        fn is_overlay(&self) -> bool {
+            self.overlay
+        }
+
+        fn animation(&self) -> Option<Animation> {
+            self.animation
+        }
+    }
+
+    fn step(
+        stack: &mut SceneStack<Headless>,
+        term: &mut Terminal<Headless>,
+        key: Option<char>,
+        delta_ms: u64,
+    ) -> Flow {
+        if let Some(c) = key {
+            term.backend_mut().push_event(Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::NONE,
+            )));
+        }
+        let flow = stack.update(term, &Frame::new(Duration::from_millis(delta_ms), 0));
+        if flow == Flow::Continue {
+            let Ok(()) = term.present();
+        }
+        flow
+    }
+
+    fn view(term: &Terminal<Headless>) -> String {
+        String::from(term.backend().format_view().trim_end_matches('\n'))
+    }
+
+    fn log() -> Log {
+        Rc::new(RefCell::new(Vec::new()))
+    }
+
+    #[test]
+    fn first_frame_draws_then_idles_without_input() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(3, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log));
+        stack.init(&mut term);
+
+        assert_eq!(step(&mut stack, &mut term, None, 16), Flow::Continue);
+        assert_eq!(view(&term), "aaa");
+        assert_eq!(step(&mut stack, &mut term, None, 16), Flow::Idle);
+        assert_eq!(*log.borrow(), vec!["enter a"]);
+    }
+
+    #[test]
+    fn overlay_draws_over_the_scene_below_and_takes_its_input() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(3, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log));
+        stack.init(&mut term);
+
+        assert_eq!(step(&mut stack, &mut term, Some('p'), 16), Flow::Continue);
+        assert_eq!(view(&term), "paa");
+        assert_eq!(stack.len(), 2);
+
+        // A second key goes to the overlay only: the base scene logs no second event.
+        step(&mut stack, &mut term, Some('x'), 16);
+        assert_eq!(
+            *log.borrow(),
+            vec!["enter a", "event a", "enter p", "event p"]
+        );
+
+        step(&mut stack, &mut term, Some('o'), 16);
+        assert_eq!(view(&term), "aaa");
+        assert_eq!(log.borrow().last().map(String::as_str), Some("exit p"));
+    }
+
+    #[test]
+    fn opaque_scene_hides_the_stack_below() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(3, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log)).with_scene(Fill::overlay('b', &log));
+        stack.init(&mut term);
+        step(&mut stack, &mut term, None, 16);
+        assert_eq!(view(&term), "baa");
+
+        // `b` replaces itself with an opaque `r`: `a` below is no longer drawn.
+        step(&mut stack, &mut term, Some('r'), 16);
+        assert_eq!(view(&term), "rrr");
+        assert_eq!(
+            *log.borrow(),
+            vec!["enter a", "enter b", "event b", "enter r", "exit b"]
+        );
+    }
+
+    #[test]
+    fn popping_the_last_scene_exits() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(1, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log));
+        stack.init(&mut term);
+
+        assert_eq!(step(&mut stack, &mut term, Some('o'), 16), Flow::Exit);
+        assert!(stack.is_empty());
+        assert_eq!(log.borrow().last().map(String::as_str), Some("exit a"));
+    }
+
+    #[test]
+    fn exit_runs_every_exit_hook_top_down() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(1, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log)).with_scene(Fill::overlay('b', &log));
+        stack.init(&mut term);
+
+        assert_eq!(step(&mut stack, &mut term, Some('q'), 16), Flow::Exit);
+        assert_eq!(
+            *log.borrow(),
+            vec!["enter a", "enter b", "event b", "exit b", "exit a"]
+        );
+        assert_eq!(step(&mut stack, &mut term, None, 16), Flow::Exit);
+    }
+
+    #[test]
+    fn wipe_animation_sweeps_the_incoming_scene_in_over_its_duration() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(4, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log)).with_animation(Animation::new(
+            Effect::Wipe(Direction::Right),
+            Duration::from_millis(40),
+        ));
+        stack.init(&mut term);
+        step(&mut stack, &mut term, None, 0);
+
+        step(&mut stack, &mut term, Some('r'), 0);
+        assert!(stack.is_animating());
+        assert_eq!(view(&term), "aaaa");
+        // The replaced scene keeps drawing (and has not exited) until the wipe finishes.
+        assert_eq!(step(&mut stack, &mut term, None, 10), Flow::Continue);
+        assert_eq!(view(&term), "raaa");
+        step(&mut stack, &mut term, None, 20);
+        assert_eq!(view(&term), "rrra");
+        assert!(!log.borrow().contains(&String::from("exit a")));
+
+        step(&mut stack, &mut term, None, 10);
+        assert!(!stack.is_animating());
+        assert_eq!(view(&term), "rrrr");
+        assert_eq!(log.borrow().last().map(String::as_str), Some("exit a"));
+        assert_eq!(step(&mut stack, &mut term, None, 10), Flow::Idle);
+    }
+
+    #[test]
+    fn slide_layout_translates_both_views() {
+        let animation = Animation::new(Effect::Slide(Direction::Left), Duration::from_millis(4));
+        let area = Rect::new(0, 0, 8, 2);
+        let [(out_clip, out_shift), (in_clip, in_shift)] =
+            animation.layout(area, Duration::from_millis(1));
+        assert_eq!(out_clip, Rect::new(0, 0, 6, 2));
+        assert_eq!(out_shift, (2, 0));
+        assert_eq!(in_clip, Rect::new(6, 0, 2, 2));
+        assert_eq!(in_shift, (-6, 0));
+    }
+
+    #[test]
+    fn pop_plays_the_scene_animation_in_reverse() {
+        let log = log();
+        let mut term = Terminal::new(Headless::new(4, 1));
+        let mut stack = SceneStack::new(Fill::new('a', &log));
+        stack.init(&mut term);
+        step(&mut stack, &mut term, None, 0);
+
+        let mut top = Fill::new('b', &log);
+        top.animation = Some(Animation::new(
+            Effect::Wipe(Direction::Right),
+            Duration::from_millis(40),
+        ));
+        let outcome = stack.apply(&mut term, Transition::push(top));
+        assert!(outcome == Outcome::Redraw);
+        step(&mut stack, &mut term, None, 40);
+        assert_eq!(view(&term), "bbbb");
+
+        // `b` came in moving right, so it leaves moving left: `a` is revealed from the right.
+        step(&mut stack, &mut term, Some('o'), 0);
+        assert_eq!(view(&term), "bbbb");
+        step(&mut stack, &mut term, None, 10);
+        assert_eq!(view(&term), "bbba");
+    }
+
+    #[test]
+    fn transition_debug_names_the_variant() {
+        let log = log();
+        let push: Transition<Headless> = Transition::push(Fill::new('a', &log));
+        assert_eq!(alloc::format!("{push:?}"), "Push(..)");
+        assert_eq!(alloc::format!("{:?}", Transition::<Headless>::Pop), "Pop");
+    }
+}
@@ -67,6 +67,27 @@ impl Surface<'_> {
         i64::from(self.clip.right()) - i64::from(self.area.left()) + i64::from(self.origin_offset.0)
     }
 
+    /// [`wrap_right`](Self::wrap_right) for local row `y`, pulled in to the edge of a double-size
+    /// row's visible half (see [`Grid::line_columns`](crate::grid::Grid::line_columns)) so text
+    /// wraps where it stops being shown rather than running on off screen.
+    pub(super) fn wrap_right_on(&self, y: u16) -> i64 {
+        let right = self.wrap_right();
+        let Some(row) = self.grid_row(y) else {
+            return right;
+        };
+        let visible = i64::from(self.grid.line_columns(row)) - i64::from(self.area.left())
+            + i64::from(self.origin_offset.0);
+        right.min(visible)
+    }
+
+    /// The absolute grid row local row `y` lands on after this surface's translate offset, or
+    /// `None` when that falls outside [`area`](Self::area).
+    pub(super) fn grid_row(&self, y: u16) -> Option<u16> {
+        let sy = i32::from(y).checked_sub(self.origin_offset.1)?;
+        let sy = u16::try_from(sy).ok()?;
+        (sy < self.area.height()).then(|| self.area.top() + sy)
+    }
+
     /// Applies this surface's tint to the cell just written at `(x, y)`.
     ///
     /// Called after a write rather than as part of one, because a glyph write drops whatever
@@ -1,7 +1,7 @@
 //! [`print`](Surface::print) and friends: text writing, wrapping, and alignment.
 
 use crate::color::Style;
-use crate::grid::{Pos, Rect};
+use crate::grid::{LineSize, Pos, Rect};
 use crate::text::Line;
 #[cfg(not(feature = "egc"))]
 use unicode_width::UnicodeWidthChar;
@@ -12,8 +12,9 @@ impl Surface<'_> {
     /// Print `text` starting at `pos` in `style`.
     ///
     /// `\n` advances to the next row at the original column. Text that would extend beyond this
-    /// surface's clip wraps to the next row at the original column; cells outside the clip
-    /// (either axis) are dropped. When the `egc` feature is enabled, `text` is split into
+    /// surface's clip, or past the visible half of a double-size row (see
+    /// [`set_line_size`](Self::set_line_size)), wraps to the next row at the original column;
+    /// cells outside the clip (either axis) are dropped. When the `egc` feature is enabled, `text` is split into
     /// extended grapheme clusters (so combining marks and ZWJ sequences write as one cell each);
     /// otherwise it is split by `char`.
     ///
@@ -50,13 +51,14 @@ impl Surface<'_> {
         use unicode_segmentation::UnicodeSegmentation;
         use unicode_width::UnicodeWidthStr;
 
-        let right = self.wrap_right();
         let mut cx = pos.x;
         let mut cy = pos.y;
+        let mut right = self.wrap_right_on(cy);
         for grapheme in text.graphemes(true) {
             if grapheme == "\n" {
                 cx = pos.x;
                 cy = cy.saturating_add(1);
+                right = self.wrap_right_on(cy);
                 continue;
             }
             // A single grapheme's display width is 0, 1, or 2 per `unicode-width` (see
@@ -71,6 +73,7 @@ impl Surface<'_> {
             if i64::from(cx) >= right {
                 cx = pos.x;
                 cy = cy.saturating_add(1);
+                right = self.wrap_right_on(cy);
             }
         }
     }
@@ -78,13 +81,14 @@ impl Surface<'_> {
     /// [`print`](Self::print) implementation used when `egc` is disabled: splits on `char`.
     #[cfg(not(feature = "egc"))]
     fn print_chars(&mut self, pos: Pos, text: &str, style: Style) {
-        let right = self.wrap_right();
         let mut cx = pos.x;
         let mut cy = pos.y;
+        let mut right = self.wrap_right_on(cy);
         for ch in text.chars() {
             if ch == '\n' {
                 cx = pos.x;
                 cy = cy.saturating_add(1);
+                right = self.wrap_right_on(cy);
                 continue;
             }
             // A single char's display width is 0, 1, or 2 per `unicode-width` (see `Tile::width`'s
@@ -99,12 +103,14 @@ impl Surface<'_> {
             if i64::from(cx) >= right {
                 cx = pos.x;
                 cy = cy.saturating_add(1);
+                right = self.wrap_right_on(cy);
             }
         }
     }
 
     /// Print `line`'s styled spans starting at `pos`, one row, each span in its own style.
-    /// Stops once a span would start past this surface's clip.
+    /// Stops once a span would start past this surface's clip, or past the visible half of a
+    /// double-size row.
     ///
     /// # Examples
     ///
@@ -126,7 +132,7 @@ impl Surface<'_> {
         use unicode_width::UnicodeWidthStr;
 
         let pos = pos.into();
-        let right = self.wrap_right();
+        let right = self.wrap_right_on(pos.y);
         let mut cx = pos.x;
         for span in &line.spans {
             if i64::from(cx) >= right {
@@ -162,6 +168,9 @@ impl Surface<'_> {
     /// [`HAlign::Center`](crate::layout::HAlign::Center) itself saturates in
     /// [`TextLayout`](crate::layout::TextLayout)).
     ///
+    /// On a double-size row (see [`set_line_size`](Self::set_line_size)), `rect` is aligned
+    /// within the part of it the row shows.
+    ///
     /// Not gated behind the `egc` feature: unlike `TextLayout`, this needs nothing from it, so
     /// it's reachable from any crate that only measures with `unicode-width`, including
     /// `retroglyph-ui` without opting into `egc`.
@@ -197,7 +206,23 @@ impl Surface<'_> {
         // own use of this same cast for a single span).
         #[allow(clippy::cast_possible_truncation)]
         let text_width = UnicodeWidthStr::width(text) as u16;
-        let x_offset = align.offset(rect.width(), text_width);
+        // On a double-size row only the visible half of `rect` counts: centring against the
+        // full width would push the text off the row's right edge.
+        // `rect` ignores any translate (see below), so its row is plain `area`-local.
+        let row = (rect.top() < self.area.height())
+            .then(|| self.area.top() + rect.top())
+            .filter(|&row| self.grid.line_size(row).is_double_width());
+        let visible = row.map_or_else(
+            || rect.width(),
+            |row| {
+                self.grid
+                    .line_columns(row)
+                    .saturating_sub(self.area.left())
+                    .saturating_sub(rect.left())
+                    .min(rect.width())
+            },
+        );
+        let x_offset = align.offset(visible, text_width);
         let pos = (rect.left().saturating_add(x_offset), rect.top());
         // `rect` (like `pos` here) is local to `self.area` and deliberately independent of any
         // outstanding `translate`, matching a widget's own `area().at_origin()`. `print` itself
@@ -211,4 +236,40 @@ impl Surface<'_> {
         );
         self.translate(undo).print(pos, text, style);
     }
+
+    /// Draws local row `y` at `size` (see [`LineSize`]), for a title banner: a
+    /// [`DoubleWidth`](LineSize::DoubleWidth) row, or a
+    /// [`DoubleHeightTop`](LineSize::DoubleHeightTop)/[`DoubleHeightBottom`](LineSize::DoubleHeightBottom)
+    /// pair printed with the same text.
+    ///
+    /// The size covers the grid's whole row, not just this surface's area, since that is the
+    /// unit a terminal doubles; a row outside this surface's clip is left alone. Only the first
+    /// half of a double-size row is shown, and [`print`](Self::print) and its siblings wrap and
+    /// align to that half.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::backend::Headless;
+    /// use retroglyph_core::color::Style;
+    /// use retroglyph_core::grid::LineSize;
+    /// use retroglyph_core::terminal::Terminal;
+    ///
+    /// let mut term = Terminal::new(Headless::new(10, 1));
+    /// term.draw(|s| {
+    ///     s.set_line_size(0, LineSize::DoubleWidth);
+    ///     s.print((0, 0), "TITLE", Style::default());
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(term.backend().format_view(), "T I T L E \n");
+    /// ```
+    pub fn set_line_size(&mut self, y: u16, size: LineSize) {
+        let Some(row) = self.grid_row(y) else {
+            return;
+        };
+        if row >= self.clip.top() && row < self.clip.bottom() {
+            self.grid.set_line_size(row, size);
+        }
+    }
 }
@@ -1,7 +1,7 @@
 //! Input polling: [`poll`](Terminal::poll) and the queue/lookahead helpers built on it.
 //!
 //! Every event enters through [`poll_backend`](Terminal::poll_backend), the single point where a
-//! backend-sourced [`Event::Resize`] gets applied to the grids; [`poll`](Terminal::poll) and the
+//! backend-sourced [`Event::Resize`] (or [`Event::Resumed`]) gets applied to the grids; [`poll`](Terminal::poll) and the
 //! other methods here all route through it (directly, or via `queued_events`) so a resize is
 //! never applied twice for the same logical event.
 
@@ -49,12 +49,25 @@ impl<B: Backend> Terminal<B> {
     /// redundant grid resize/clear of a size that was already applied.
     fn poll_backend(&mut self, timeout: Duration) -> Option<Event> {
         let event = self.backend.poll_event(timeout)?;
-        if let Event::Resize(w, h) = event
-            && self.size() != crate::grid::Size::new(w, h)
-        {
-            self.resize(w, h);
+        Some(self.admit(event))
+    }
+
+    /// Applies a freshly-polled backend `event` to this terminal (see
+    /// [`poll_backend`](Self::poll_backend)) and hands it back: the part of `poll_backend` the
+    /// async [`poll_next_event`](Self::poll_next_event) shares, since it gets its event from
+    /// [`Input::poll_next_event`](crate::backend::Input::poll_next_event) instead.
+    ///
+    /// An [`Event::Resumed`] forgets the last presented frame, since whatever ran while the
+    /// process was suspended may have drawn over the screen.
+    pub(super) fn admit(&mut self, event: Event) -> Event {
+        match event {
+            Event::Resize(w, h) if self.size() != crate::grid::Size::new(w, h) => {
+                self.resize(w, h);
+            }
+            Event::Resumed => self.forget_presented(),
+            _ => {}
         }
-        Some(event)
+        event
     }
 
     /// Hands `events` back to this terminal's own queue, in order, so a later
@@ -163,14 +176,28 @@ impl<B: Backend> Terminal<B> {
         self.queued_events.push_back(event);
         true
     }
+
+    /// A handle other threads can use to inject events into this terminal's backend, or `None`
+    /// if the backend cannot receive them. Forwards to
+    /// [`Input::event_sender`](crate::backend::Input::event_sender).
+    ///
+    /// A sent event wakes a blocked [`wait_for_input`](Self::wait_for_input) (and so a
+    /// [`Flow::Idle`](crate::app::Flow::Idle) driver) straight away, and reaches the app through
+    /// [`poll`](Self::poll)/[`drain_events`](Self::drain_events) like any other input. See
+    /// [`EventSender`](crate::backend::EventSender) for timers built on top.
+    #[cfg(feature = "std")]
+    pub fn event_sender(&mut self) -> Option<crate::backend::EventSender> {
+        self.backend.event_sender()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::{Cursor, DrawCell, Headless, Input, Output};
+    use crate::backend::{Clipboard, Cursor, DrawCell, Headless, Input, Output};
     use crate::grid::{Rect, Size};
     use crate::terminal::LayerOp;
+    use core::task::{Context, Poll};
 
     #[test]
     fn test_terminal_poll_and_read() {
@@ -186,6 +213,29 @@ mod tests {
         assert_eq!(terminal.poll(Duration::MAX), Some(Event::Resize(80, 25)));
     }
 
+    #[test]
+    fn resumed_redraws_cells_that_did_not_change() {
+        use crate::color::Style;
+
+        let mut terminal = Terminal::new(Headless::new(4, 1));
+        let draw = |terminal: &mut Terminal<Headless>| {
+            terminal
+                .draw(|surface| surface.put((0, 0), 'X', Style::default()))
+                .unwrap();
+        };
+        draw(&mut terminal);
+        // Something else wrote over the screen while the process was stopped.
+        terminal.backend_mut().clear().unwrap();
+
+        draw(&mut terminal);
+        assert!(!terminal.backend().format_view().starts_with('X'));
+
+        terminal.backend_mut().push_event(Event::Resumed);
+        assert_eq!(terminal.poll(Duration::ZERO), Some(Event::Resumed));
+        draw(&mut terminal);
+        assert!(terminal.backend().format_view().starts_with('X'));
+    }
+
     #[test]
     fn test_terminal_has_input() {
         let backend = Headless::new(10, 10);
@@ -390,9 +440,14 @@ mod tests {
         fn push_event(&mut self, event: Event) {
             self.inner.push_event(event);
         }
+
+        fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+            self.inner.poll_next_event(cx)
+        }
     }
 
     impl Cursor for ResizeCounting {}
+    impl Clipboard for ResizeCounting {}
 
     #[test]
     fn test_terminal_poll_does_not_reapply_resize_buffered_by_wait_for_input() {
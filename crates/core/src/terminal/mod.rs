@@ -4,16 +4,22 @@
 //! axes of `Terminal`'s API, defined in private submodules; this module holds everything that
 //! isn't specifically about presenting a frame or reading input.
 
-use crate::backend::{Backend, CursorStyle};
+use crate::backend::{Backend, CursorStyle, DynBackend};
 use crate::event::Event;
 use crate::grid::{Grid, HasSize, Pos, Rect, Size};
 use crate::surface::Surface;
+use alloc::boxed::Box;
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 
 mod input;
 mod present;
 mod retain;
+mod stream;
+
+pub use stream::{EventSource, EventStream, FromFn, Next, from_fn};
+#[cfg(feature = "futures")]
+pub use stream::{FromStream, from_stream};
 
 /// A double-buffered terminal generic over a [`Backend`].
 ///
@@ -173,10 +179,7 @@ impl<B: Backend> Terminal<B> {
         }
         // Clearing previous forces a full redraw next present(), ensuring no
         // stale cells bleed into the resized layout.
-        self.previous.clear_all();
-        if let Some(flattened_previous) = &mut self.flattened_previous {
-            flattened_previous.clear_all();
-        }
+        self.forget_presented();
         // Defensive: `resize` already clears `previous` unconditionally, so the next `present`
         // would just copy empty content forward for a still-marked layer. Dropping any pending
         // op here too keeps that a non-event rather than relying on it: a pending retention would
@@ -186,6 +189,15 @@ impl<B: Backend> Terminal<B> {
         self.backend.resize(Size::new(width, height));
     }
 
+    /// Clears the record of the last presented frame, so the next [`present`](Self::present)
+    /// sends every drawn cell instead of just the ones that changed.
+    fn forget_presented(&mut self) {
+        self.previous.clear_all();
+        if let Some(flattened_previous) = &mut self.flattened_previous {
+            flattened_previous.clear_all();
+        }
+    }
+
     /// Show or hide the cursor.
     ///
     /// Forwards to [`Cursor::set_cursor_visible`](crate::backend::Cursor::set_cursor_visible) on
@@ -235,6 +247,61 @@ impl<B: Backend> Terminal<B> {
     pub const fn backend_mut(&mut self) -> &mut B {
         &mut self.backend
     }
+
+    /// Moves this terminal onto the backend `f` makes from the current one, keeping everything
+    /// else: both frame buffers, queued events, pending layer ops, and the present count.
+    ///
+    /// Meant for wrapping or unwrapping the same display (boxing it into a
+    /// [`DynBackend`](crate::backend::DynBackend), say), so the diff against the last presented
+    /// frame stays valid. `f` should hand back a backend showing what the old one showed, at the
+    /// same size; the grids are not resized to match.
+    pub fn map_backend<C: Backend>(self, f: impl FnOnce(B) -> C) -> Terminal<C> {
+        Terminal {
+            current: self.current,
+            previous: self.previous,
+            flattened_current: self.flattened_current,
+            flattened_previous: self.flattened_previous,
+            backend: f(self.backend),
+            queued_events: self.queued_events,
+            flattened_stale: self.flattened_stale,
+            present_count: self.present_count,
+            pending_layer_ops: self.pending_layer_ops,
+        }
+    }
+
+    /// This terminal with its backend boxed into a `Box<dyn DynBackend>`; see
+    /// [`map_backend`](Self::map_backend).
+    #[must_use]
+    pub fn into_dyn(self) -> Terminal<Box<dyn DynBackend>>
+    where
+        B: DynBackend,
+    {
+        self.map_backend(|backend| Box::new(backend) as Box<dyn DynBackend>)
+    }
+}
+
+impl Terminal<Box<dyn DynBackend>> {
+    /// Undoes [`into_dyn`](Terminal::into_dyn): this terminal on its concrete `B` again.
+    ///
+    /// # Errors
+    ///
+    /// Hands the terminal back unchanged if its backend is not a `B`.
+    // Same shape as `Box::downcast`: the caller gets its terminal back on a miss, and boxing it
+    // just to shrink the `Err` would cost an allocation on the path that has to keep using it.
+    #[allow(clippy::result_large_err)]
+    pub fn downcast<B: Backend + DynBackend>(self) -> Result<Terminal<B>, Self> {
+        if !self.backend.is::<B>() {
+            return Err(self);
+        }
+        Ok(self.map_backend(|backend| {
+            (backend as Box<dyn core::any::Any>)
+                .downcast::<B>()
+                .map_or_else(
+                    |_| unreachable!("checked by `is` above"),
+                    |backend| *backend,
+                )
+        }))
+    }
 }
 
 impl<B: Backend> core::fmt::Debug for Terminal<B> {
@@ -268,6 +335,29 @@ mod tests {
         assert_eq!(terminal.grid()[Pos::new(0, 0)].glyph(), 'X');
     }
 
+    #[test]
+    fn into_dyn_and_back_keeps_the_presented_frame_and_queued_events() {
+        let mut terminal = Terminal::new(Headless::new(4, 1));
+        terminal
+            .draw(|s| s.put((0, 0), 'a', Style::default()))
+            .unwrap();
+        terminal.requeue_events([Event::Close]);
+
+        let mut boxed = terminal.into_dyn();
+        assert_eq!(boxed.present_count(), 1);
+        assert_eq!(boxed.poll(core::time::Duration::ZERO), Some(Event::Close));
+        boxed
+            .draw(|s| {
+                s.put((0, 0), 'a', Style::default());
+                s.put((1, 0), 'b', Style::default());
+            })
+            .unwrap();
+
+        let terminal = boxed.downcast::<Headless>().unwrap();
+        assert_eq!(terminal.present_count(), 2);
+        assert!(terminal.backend().format_view().starts_with("ab"));
+    }
+
     #[test]
     fn test_terminal_size() {
         let term = Terminal::new(Headless::new(40, 20));
@@ -251,12 +251,13 @@ impl<B: Backend> Terminal<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::{Cursor, DrawCell, Headless, Input};
+    use crate::backend::{Clipboard, Cursor, DrawCell, Headless, Input};
     use crate::color::Color;
     use crate::color::Style;
     use crate::event::Event;
     use crate::grid::{Pos, Size};
     use alloc::vec::Vec;
+    use core::task::{Context, Poll};
     use core::time::Duration;
 
     /// Wraps [`Headless`] and fails the next [`flush`](Output::flush) or
@@ -348,10 +349,15 @@ mod tests {
         fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
             self.inner.poll_event(timeout)
         }
+
+        fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+            self.inner.poll_next_event(cx)
+        }
     }
 
     #[cfg(feature = "std")]
     impl Cursor for FlushOnceFailing {}
+    impl Clipboard for FlushOnceFailing {}
 
     #[test]
     fn test_draw_composites_layers_for_cell_backend() {
@@ -432,9 +438,14 @@ mod tests {
         fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
             None
         }
+
+        fn poll_next_event(&mut self, _cx: &mut Context<'_>) -> Poll<Event> {
+            Poll::Pending
+        }
     }
 
     impl Cursor for CompositingBackend {}
+    impl Clipboard for CompositingBackend {}
 
     #[test]
     fn test_composites_layers_diff_dispatches_only_changed_cells() {
@@ -536,9 +547,14 @@ mod tests {
         fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
             self.inner.poll_event(timeout)
         }
+
+        fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+            self.inner.poll_next_event(cx)
+        }
     }
 
     impl Cursor for TogglingCompositor {}
+    impl Clipboard for TogglingCompositor {}
 
     #[test]
     fn present_marks_flatten_buffers_stale_after_a_composites_layers_present() {
@@ -121,11 +121,12 @@ impl<B: Backend> Terminal<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::{Compositing, Cursor, DrawCell, Headless, Input, Output};
+    use crate::backend::{Clipboard, Compositing, Cursor, DrawCell, Headless, Input, Output};
     use crate::color::Style;
     use crate::event::Event;
     use crate::grid::{Pos, Size};
     use alloc::vec::Vec;
+    use core::task::{Context, Poll};
     use core::time::Duration;
 
     /// A `Compositing::PixelLayered` cell-recording backend, used to prove `retain_layer`'s
@@ -182,9 +183,14 @@ mod tests {
         fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
             None
         }
+
+        fn poll_next_event(&mut self, _cx: &mut Context<'_>) -> Poll<Event> {
+            Poll::Pending
+        }
     }
 
     impl Cursor for CompositingBackend {}
+    impl Clipboard for CompositingBackend {}
 
     // --- retain_layer ---
 
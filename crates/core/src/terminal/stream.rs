@@ -0,0 +1,394 @@
+//! Async input: [`Terminal::events`], the [`EventStream`] it returns, and the [`EventSource`]s
+//! that merge other event producers (channels, timers, sockets) into it.
+//!
+//! Nothing here depends on an executor. Every future and stream only ever registers the
+//! [`Waker`](core::task::Waker) it is handed, via
+//! [`Input::poll_next_event`](crate::backend::Input::poll_next_event) on the backend side and
+//! [`EventSource::poll_event`] on the source side, so the same code runs under tokio,
+//! async-std, smol, `wasm-bindgen-futures`, or a hand-rolled `block_on` in a test. With this
+//! crate's `futures` feature, [`EventStream`] also implements `futures_core::Stream`, and
+//! [`from_stream`] turns any `Stream<Item = Event>` into a source.
+
+use super::Terminal;
+use crate::backend::Backend;
+use crate::event::Event;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A producer of [`Event`]s other than the backend itself, polled alongside it by
+/// [`EventStream`] and [`run_on_async`](crate::app::run_on_async).
+///
+/// The typical source is the receiving half of a channel, mapping whatever arrives on it to an
+/// [`Event::Custom`] (or any other event). Wrap a `poll_recv`-style closure with [`from_fn`], or,
+/// with the `futures` feature, any `Stream<Item = Event>` with [`from_stream`]. A `Vec` of sources
+/// is itself a source, polled in order.
+///
+/// # Examples
+///
+/// ```
+/// use core::task::Poll;
+/// use retroglyph_core::event::Event;
+/// use retroglyph_core::terminal::{EventSource, from_fn};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel::<u64>();
+/// // A std channel has no waker of its own to register, so this source can only be checked
+/// // once per poll; async channels (tokio's `poll_recv`, for example) slot in the same way but
+/// // also wake the task when a message arrives.
+/// let mut source = from_fn(move |_cx| match rx.try_recv() {
+///     Ok(id) => Poll::Ready(Some(Event::Custom(id))),
+///     Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+///     Err(mpsc::TryRecvError::Disconnected) => Poll::Ready(None),
+/// });
+///
+/// tx.send(7).unwrap();
+/// let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// assert_eq!(source.poll_event(&mut cx), Poll::Ready(Some(Event::Custom(7))));
+/// ```
+pub trait EventSource {
+    /// Polls for this source's next event, registering `cx`'s waker to be woken when one may be
+    /// ready.
+    ///
+    /// Returns `Poll::Ready(None)` once the source is exhausted for good (a channel whose
+    /// senders all dropped, for example); it is not polled again after that.
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>>;
+}
+
+impl<S: EventSource + ?Sized> EventSource for Box<S> {
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        (**self).poll_event(cx)
+    }
+}
+
+/// Polls each source in order and returns the first ready event, dropping sources as they close;
+/// closed itself once every source has.
+impl<S: EventSource> EventSource for Vec<S> {
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let mut i = 0;
+        while i < self.len() {
+            match self[i].poll_event(cx) {
+                Poll::Ready(Some(event)) => return Poll::Ready(Some(event)),
+                Poll::Ready(None) => {
+                    self.remove(i);
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+        if self.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// No source at all: closed from the start.
+impl EventSource for () {
+    fn poll_event(&mut self, _cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        Poll::Ready(None)
+    }
+}
+
+/// An [`EventSource`] backed by a closure; see [`from_fn`].
+#[derive(Clone)]
+pub struct FromFn<F>(F);
+
+/// Wraps `f` as an [`EventSource`]: it is called with the polling task's
+/// [`Context`](core::task::Context) each time the source is polled.
+///
+/// Fits any `poll_recv`-shaped receiver directly, e.g. `from_fn(move |cx| rx.poll_recv(cx).map(|m|
+/// m.map(Event::Custom)))` for a tokio `mpsc::Receiver<u64>`.
+pub const fn from_fn<F>(f: F) -> FromFn<F>
+where
+    F: FnMut(&mut Context<'_>) -> Poll<Option<Event>>,
+{
+    FromFn(f)
+}
+
+impl<F> EventSource for FromFn<F>
+where
+    F: FnMut(&mut Context<'_>) -> Poll<Option<Event>>,
+{
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        (self.0)(cx)
+    }
+}
+
+/// An [`EventSource`] backed by a `futures_core::Stream`; see [`from_stream`].
+#[cfg(feature = "futures")]
+#[derive(Clone)]
+pub struct FromStream<S>(S);
+
+/// Wraps any `Stream<Item = Event>` (an async channel receiver, a `tokio-stream` interval mapped
+/// to [`Event::Custom`], ...) as an [`EventSource`].
+#[cfg(feature = "futures")]
+pub const fn from_stream<S>(stream: S) -> FromStream<S>
+where
+    S: futures_core::Stream<Item = Event> + Unpin,
+{
+    FromStream(stream)
+}
+
+#[cfg(feature = "futures")]
+impl<S> EventSource for FromStream<S>
+where
+    S: futures_core::Stream<Item = Event> + Unpin,
+{
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+}
+
+impl<B: Backend> Terminal<B> {
+    /// Polls for an input event without blocking: the async counterpart to
+    /// [`poll`](Self::poll).
+    ///
+    /// Returns a queued event first, exactly like `poll`, then asks the backend via
+    /// [`Input::poll_next_event`](crate::backend::Input::poll_next_event), which registers
+    /// `cx`'s waker when nothing is ready yet. An [`Event::Resize`] from the backend is applied
+    /// before it is returned, the same as on the blocking path.
+    pub fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        if let Some(event) = self.queued_events.pop_front() {
+            return Poll::Ready(event);
+        }
+        self.backend
+            .poll_next_event(cx)
+            .map(|event| self.admit(event))
+    }
+
+    /// Resolves once an input event is available, without consuming it: the async counterpart
+    /// to [`wait_for_input`](Self::wait_for_input).
+    ///
+    /// A discovered event is buffered, so the next [`poll`](Self::poll) (or the app's own
+    /// `update`) still observes it.
+    pub fn poll_input(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.queued_events.is_empty() {
+            return Poll::Ready(());
+        }
+        self.backend.poll_next_event(cx).map(|event| {
+            let event = self.admit(event);
+            self.queued_events.push_back(event);
+        })
+    }
+
+    /// Borrows this terminal as an [`EventStream`] of its input events.
+    ///
+    /// Merge in other producers with [`EventStream::with_source`]; reach the terminal again for
+    /// drawing with [`EventStream::terminal`] without giving up the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::pin::pin;
+    /// use core::task::{Context, Poll, Waker};
+    /// use retroglyph_core::backend::Headless;
+    /// use retroglyph_core::event::Event;
+    /// use retroglyph_core::terminal::Terminal;
+    ///
+    /// let mut term = Terminal::new(Headless::new(4, 1));
+    /// term.backend_mut().push_event(Event::FocusGained);
+    ///
+    /// let mut events = term.events();
+    /// let mut next = pin!(events.next());
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(next.as_mut().poll(&mut cx), Poll::Ready(Event::FocusGained));
+    /// ```
+    pub fn events(&mut self) -> EventStream<'_, B> {
+        EventStream {
+            term: self,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// An executor-agnostic stream of input events from a [`Terminal`], plus any merged-in
+/// [`EventSource`]s; see [`Terminal::events`].
+///
+/// Never ends: the terminal always has more input to wait for. Each poll checks the terminal
+/// first, then every source in the order it was added.
+pub struct EventStream<'a, B: Backend> {
+    term: &'a mut Terminal<B>,
+    sources: Vec<Box<dyn EventSource + 'a>>,
+}
+
+impl<'a, B: Backend> EventStream<'a, B> {
+    /// Merges `source`'s events into this stream (builder style).
+    #[must_use]
+    pub fn with_source(mut self, source: impl EventSource + 'a) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// The terminal this stream reads from, e.g. to draw and present in response to an event.
+    pub const fn terminal(&mut self) -> &mut Terminal<B> {
+        self.term
+    }
+
+    /// Polls for the next event from the terminal or any source, registering `cx`'s waker with
+    /// every one of them that is not ready yet.
+    pub fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        if let Poll::Ready(event) = self.term.poll_next_event(cx) {
+            return Poll::Ready(event);
+        }
+        match self.sources.poll_event(cx) {
+            Poll::Ready(Some(event)) => Poll::Ready(event),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// A future resolving to the next event; see [`poll_next_event`](Self::poll_next_event).
+    pub const fn next(&mut self) -> Next<'_, 'a, B> {
+        Next { stream: self }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<B: Backend> futures_core::Stream for EventStream<'_, B> {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        self.get_mut().poll_next_event(cx).map(Some)
+    }
+}
+
+/// The future returned by [`EventStream::next`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Next<'s, 'a, B: Backend> {
+    stream: &'s mut EventStream<'a, B>,
+}
+
+impl<B: Backend> Future for Next<'_, '_, B> {
+    type Output = Event;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Event> {
+        self.get_mut().stream.poll_next_event(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Headless;
+    use crate::event::{KeyCode, KeyEvent, KeyModifiers};
+    use crate::grid::Size;
+    use alloc::collections::VecDeque;
+    use alloc::sync::Arc;
+    use alloc::task::Wake;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use core::task::Waker;
+
+    /// Counts how many times it was woken.
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    /// A source handing out `events` in order, then closing.
+    fn queue(events: Vec<Event>) -> impl EventSource {
+        let mut events = VecDeque::from(events);
+        from_fn(move |_cx| Poll::Ready(events.pop_front()))
+    }
+
+    #[test]
+    fn headless_parks_until_push_event_wakes_it() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(term.poll_next_event(&mut cx), Poll::Pending);
+        assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+        term.backend_mut().push_event(key('a'));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(term.poll_next_event(&mut cx), Poll::Ready(key('a')));
+    }
+
+    #[test]
+    fn poll_next_event_applies_a_backend_resize() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        term.backend_mut().push_event(Event::Resize(5, 3));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(
+            term.poll_next_event(&mut cx),
+            Poll::Ready(Event::Resize(5, 3))
+        );
+        assert_eq!(term.size(), Size::new(5, 3));
+    }
+
+    #[test]
+    fn poll_input_buffers_the_event_for_the_next_poll() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        term.backend_mut().push_event(key('a'));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(term.poll_input(&mut cx), Poll::Ready(()));
+        assert_eq!(term.poll(core::time::Duration::ZERO), Some(key('a')));
+        assert_eq!(term.poll_input(&mut cx), Poll::Pending);
+    }
+
+    #[test]
+    fn stream_yields_terminal_events_before_source_events() {
+        let mut term = Terminal::new(Headless::new(2, 1));
+        term.backend_mut().push_event(key('a'));
+        let mut events = term
+            .events()
+            .with_source(queue(alloc::vec![Event::Custom(1)]));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(events.poll_next_event(&mut cx), Poll::Ready(key('a')));
+        assert_eq!(
+            events.poll_next_event(&mut cx),
+            Poll::Ready(Event::Custom(1))
+        );
+        // The source is closed now and the terminal is empty: pending, not ended.
+        assert_eq!(events.poll_next_event(&mut cx), Poll::Pending);
+
+        events.terminal().backend_mut().push_event(key('b'));
+        assert_eq!(events.poll_next_event(&mut cx), Poll::Ready(key('b')));
+    }
+
+    #[test]
+    fn vec_source_drops_closed_sources_and_closes_when_all_have() {
+        let mut sources = alloc::vec![queue(Vec::new()), queue(alloc::vec![Event::Custom(2)])];
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(
+            sources.poll_event(&mut cx),
+            Poll::Ready(Some(Event::Custom(2)))
+        );
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources.poll_event(&mut cx), Poll::Ready(None));
+        assert!(sources.is_empty());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn from_stream_and_stream_impl_round_trip() {
+        use futures_core::Stream;
+
+        let mut term = Terminal::new(Headless::new(2, 1));
+        let inner = term
+            .events()
+            .with_source(queue(alloc::vec![Event::Custom(3)]));
+        let mut source = from_stream(inner);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(
+            source.poll_event(&mut cx),
+            Poll::Ready(Some(Event::Custom(3)))
+        );
+        assert_eq!(Pin::new(&mut source.0).poll_next(&mut cx), Poll::Pending);
+    }
+}
@@ -194,7 +194,7 @@ impl TestHarness {
                 kind,
                 position,
                 // `Headless` is a character-mode backend: it has no sub-cell pixel position to
-                // report, matching the crossterm backend's own convention (see `MouseEvent`'s
+                // report, matching a terminal that doesn't report pixels (see `MouseEvent`'s
                 // docs) rather than guessing one.
                 pixel_position: None,
                 modifiers: KeyModifiers::NONE,
@@ -222,6 +222,35 @@ impl TestHarness {
         self.push_event(Event::Key(KeyEvent::new(code, modifiers)));
     }
 
+    /// Queues the [`Event::Interrupt`](crate::event::Event::Interrupt) a backend with signal
+    /// handling delivers for `SIGINT`.
+    pub fn interrupt(&mut self) {
+        self.push_event(Event::Interrupt);
+    }
+
+    /// Queues the [`Event::Terminate`](crate::event::Event::Terminate) a backend with signal
+    /// handling delivers for `SIGTERM`.
+    pub fn terminate(&mut self) {
+        self.push_event(Event::Terminate);
+    }
+
+    /// Queues the [`Event::HangUp`](crate::event::Event::HangUp) a backend with signal handling
+    /// delivers for `SIGHUP`.
+    pub fn hang_up(&mut self) {
+        self.push_event(Event::HangUp);
+    }
+
+    /// Queues an [`Event::Suspended`](crate::event::Event::Suspended) and, a frame later, the
+    /// [`Event::Resumed`](crate::event::Event::Resumed) a backend with signal handling delivers
+    /// for `Ctrl-Z` followed by `fg`.
+    ///
+    /// Nothing is actually suspended; the resume still makes the next present redraw every cell,
+    /// as it does on a real terminal.
+    pub fn suspend_and_resume(&mut self) {
+        self.push_event(Event::Suspended);
+        self.push_event(Event::Resumed);
+    }
+
     /// Resizes the backend and queues the matching [`Event::Resize`](crate::event::Event::Resize) a real terminal would also
     /// deliver.
     ///
@@ -658,6 +687,86 @@ mod tests {
         assert_eq!(app.seen, Some(Duration::from_millis(100)));
     }
 
+    #[test]
+    fn with_step_delta_drives_a_fixed_step_app_deterministically() {
+        use crate::app::{FixedApp, FixedStep, RunOptions};
+
+        struct Ticks(u32);
+        impl<B: Backend> FixedApp<B> for Ticks {
+            fn fixed_update(&mut self, _term: &mut Terminal<B>, _dt: Duration) -> Flow {
+                self.0 += 1;
+                Flow::Continue
+            }
+            fn render(&mut self, _term: &mut Terminal<B>, _frame: &Frame, _alpha: f64) -> Flow {
+                Flow::Continue
+            }
+        }
+
+        // 25ms per harness step against a 100 Hz simulation: 2.5 steps per frame, so ten frames
+        // are always exactly 25 steps, whatever the host's real frame rate.
+        let options = RunOptions::default().with_fixed_rate(100);
+        let mut harness = TestHarness::new(2, 1).with_step_delta(Duration::from_millis(25));
+        let mut app = FixedStep::new(Ticks(0), options);
+        harness.run_steps(&mut app, 10);
+        assert_eq!(app.app().0, 25);
+    }
+
+    #[test]
+    fn run_drives_a_scene_stack_through_an_overlay() {
+        use crate::scene::{Scene, SceneStack, Transition};
+        use crate::surface::Surface;
+
+        struct Game;
+        impl<B: Backend> Scene<B> for Game {
+            fn event(&mut self, _term: &mut Terminal<B>, event: &Event) -> Transition<B> {
+                match event {
+                    Event::Key(k) if k.code == KeyCode::Escape => Transition::push(Pause),
+                    _ => Transition::Idle,
+                }
+            }
+            fn update(&mut self, _term: &mut Terminal<B>, _frame: &Frame) -> Transition<B> {
+                Transition::Idle
+            }
+            fn draw(&mut self, surface: &mut Surface<'_>, _frame: &Frame) {
+                surface.print((0, 0), "game", Style::default());
+            }
+        }
+
+        struct Pause;
+        impl<B: Backend> Scene<B> for Pause {
+            fn event(&mut self, _term: &mut Terminal<B>, event: &Event) -> Transition<B> {
+                match event {
+                    Event::Key(k) if k.code == KeyCode::Escape => Transition::Pop,
+                    _ => Transition::Idle,
+                }
+            }
+            fn update(&mut self, _term: &mut Terminal<B>, _frame: &Frame) -> Transition<B> {
+                Transition::Idle
+            }
+            fn draw(&mut self, surface: &mut Surface<'_>, _frame: &Frame) {
+                surface.put((3, 0), '#', Style::default());
+            }
+            fn is_overlay(&self) -> bool {
+                true
+            }
+        }
+
+        let mut harness = TestHarness::new(4, 1);
+        let mut stack = SceneStack::new(Game);
+        harness.run(&mut stack);
+        assert_eq!(harness.view(), "game\n");
+
+        harness.key(KeyCode::Escape);
+        harness.run(&mut stack);
+        assert_eq!(stack.len(), 2);
+        assert_eq!(harness.view(), "gam#\n");
+
+        harness.key(KeyCode::Escape);
+        harness.run(&mut stack);
+        assert_eq!(stack.len(), 1);
+        assert_eq!(harness.view(), "game\n");
+    }
+
     #[test]
     fn resize_updates_backend_and_queues_event() {
         struct Resized {
@@ -682,6 +791,35 @@ mod tests {
         assert_eq!(app.seen, Some((8, 2)));
     }
 
+    #[test]
+    fn signal_helpers_queue_their_events_in_order() {
+        struct Records(Vec<Event>);
+        impl<B: Backend> App<B> for Records {
+            fn update(&mut self, term: &mut Terminal<B>, _frame: &Frame) -> Flow {
+                self.0.extend(term.drain_events());
+                Flow::Continue
+            }
+        }
+
+        let mut harness = TestHarness::new(4, 1);
+        let mut app = Records(Vec::new());
+        harness.interrupt();
+        harness.suspend_and_resume();
+        harness.hang_up();
+        harness.terminate();
+        harness.run(&mut app);
+        assert_eq!(
+            app.0,
+            [
+                Event::Interrupt,
+                Event::Suspended,
+                Event::Resumed,
+                Event::HangUp,
+                Event::Terminate,
+            ]
+        );
+    }
+
     #[test]
     fn run_error_display_message() {
         let err = RunError::ExceededMaxSteps { max_steps: 5 };
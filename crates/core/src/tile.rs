@@ -2,6 +2,7 @@
 
 use crate::color::Style;
 use crate::text::char_width;
+use alloc::collections::BTreeMap;
 #[cfg(feature = "egc")]
 use alloc::string::String;
 
@@ -384,6 +385,272 @@ impl Tile {
     }
 }
 
+/// How a pixel backend flips, rotates, and scales the sprite drawn for a cell.
+///
+/// One sheet entry can then face either way or spin without a second copy of the artwork: a
+/// monster turned around is `Transform::IDENTITY.flipped_x()`, a projectile heading down is
+/// `.rotated(1)`. Like [`Tint`](crate::color::Tint) it is per-cell grid state rather than a
+/// [`Tile`] field (`Tile` has no room left), set with
+/// [`Grid::set_transform`](crate::grid::Grid::set_transform) and streamed to backends on
+/// [`DrawCell::transform`](crate::backend::DrawCell::transform).
+///
+/// The parts apply in a fixed order, whatever order the builders are called in: the artwork is
+/// flipped first, then rotated clockwise, then scaled. [`source_pixel`](Self::source_pixel) is
+/// the reference for what that means pixel by pixel.
+///
+/// Pixel backends only, and sprites only: a bitmap-font glyph is drawn as authored. Cell backends
+/// have no sprite to transform and ignore it, unless they were handed a
+/// [`GlyphTransforms`] table saying which character stands in for a transformed one.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::tile::Transform;
+///
+/// let facing_left = Transform::IDENTITY.flipped_x();
+/// assert!(facing_left.is_flipped_x());
+///
+/// // A quarter turn swaps a sprite's width and height; a scale multiplies both.
+/// let big_spin = Transform::IDENTITY.rotated(1).scaled(2);
+/// assert_eq!(big_spin.size(8, 16), (32, 16));
+///
+/// // Flipping twice is no flip at all.
+/// assert!(facing_left.flipped_x().is_identity());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transform {
+    /// Bit 0 flips X, bit 1 flips Y, bits 2-3 are clockwise quarter turns.
+    bits: u8,
+    /// Integer scale factor, always at least 1.
+    scale: u8,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    const FLIP_X: u8 = 0b0001;
+    const FLIP_Y: u8 = 0b0010;
+    const TURNS_SHIFT: u8 = 2;
+
+    /// Draws the sprite exactly as authored.
+    pub const IDENTITY: Self = Self { bits: 0, scale: 1 };
+
+    /// This transform with its horizontal mirror toggled.
+    #[must_use]
+    pub const fn flipped_x(self) -> Self {
+        Self {
+            bits: self.bits ^ Self::FLIP_X,
+            scale: self.scale,
+        }
+    }
+
+    /// This transform with its vertical mirror toggled.
+    #[must_use]
+    pub const fn flipped_y(self) -> Self {
+        Self {
+            bits: self.bits ^ Self::FLIP_Y,
+            scale: self.scale,
+        }
+    }
+
+    /// This transform turned a further `quarter_turns` x 90° clockwise. Four turns is a full one.
+    #[must_use]
+    pub const fn rotated(self, quarter_turns: u8) -> Self {
+        let turns = (self.quarter_turns() + quarter_turns % 4) % 4;
+        Self {
+            bits: (self.bits & (Self::FLIP_X | Self::FLIP_Y)) | (turns << Self::TURNS_SHIFT),
+            scale: self.scale,
+        }
+    }
+
+    /// This transform drawing the sprite `factor` times its size. A `factor` of 0 is treated as 1.
+    ///
+    /// On top of, not instead of, the renderer's own scale: a sprite scaled by 2 on a renderer at
+    /// scale 3 covers six screen pixels per sprite pixel.
+    #[must_use]
+    pub const fn scaled(self, factor: u8) -> Self {
+        Self {
+            bits: self.bits,
+            scale: if factor == 0 { 1 } else { factor },
+        }
+    }
+
+    /// Whether the sprite is mirrored left to right.
+    #[must_use]
+    pub const fn is_flipped_x(self) -> bool {
+        self.bits & Self::FLIP_X != 0
+    }
+
+    /// Whether the sprite is mirrored top to bottom.
+    #[must_use]
+    pub const fn is_flipped_y(self) -> bool {
+        self.bits & Self::FLIP_Y != 0
+    }
+
+    /// How many 90° clockwise turns the sprite is rotated by, `0..4`.
+    #[must_use]
+    pub const fn quarter_turns(self) -> u8 {
+        self.bits >> Self::TURNS_SHIFT
+    }
+
+    /// The integer scale factor, at least 1.
+    #[must_use]
+    pub const fn scale(self) -> u8 {
+        self.scale
+    }
+
+    /// Whether this transform leaves the sprite exactly as authored.
+    ///
+    /// A flip in both axes is the same picture as a half turn, not the identity, so this is only
+    /// true of [`IDENTITY`](Self::IDENTITY) itself.
+    #[must_use]
+    pub const fn is_identity(self) -> bool {
+        self.bits == 0 && self.scale == 1
+    }
+
+    /// The size a `w` x `h` sprite is drawn at under this transform: swapped by an odd number of
+    /// quarter turns, then multiplied by [`scale`](Self::scale).
+    #[must_use]
+    pub const fn size(self, w: u32, h: u32) -> (u32, u32) {
+        let (w, h) = if self.quarter_turns() % 2 == 1 {
+            (h, w)
+        } else {
+            (w, h)
+        };
+        (w * self.scale as u32, h * self.scale as u32)
+    }
+
+    /// Which pixel of a `w` x `h` sprite lands at `(x, y)` of its transformed, *unscaled* image.
+    ///
+    /// `(x, y)` ranges over the rotated size, `h` x `w` after an odd number of quarter turns. The
+    /// scale is left out: each source pixel simply covers a `scale` x `scale` block.
+    ///
+    /// The reference implementation of the transform. The software renderer samples sprites
+    /// through it and the GPU sprite shaders match it, so the same transform draws the same
+    /// pixels on every pixel backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::tile::Transform;
+    ///
+    /// // A quarter turn clockwise puts the sprite's bottom-left pixel in the top-left corner.
+    /// let turned = Transform::IDENTITY.rotated(1);
+    /// assert_eq!(turned.source_pixel(0, 0, 8, 16), (0, 15));
+    ///
+    /// // A mirrored sprite's first column is its last.
+    /// assert_eq!(Transform::IDENTITY.flipped_x().source_pixel(0, 3, 8, 16), (7, 3));
+    /// ```
+    #[must_use]
+    pub const fn source_pixel(self, x: u32, y: u32, w: u32, h: u32) -> (u32, u32) {
+        // Undo the rotation first (it was applied last), landing in the flipped sprite.
+        let (fx, fy) = match self.quarter_turns() {
+            1 => (y, h - 1 - x),
+            2 => (w - 1 - x, h - 1 - y),
+            3 => (w - 1 - y, x),
+            _ => (x, y),
+        };
+        let sx = if self.is_flipped_x() { w - 1 - fx } else { fx };
+        let sy = if self.is_flipped_y() { h - 1 - fy } else { fy };
+        (sx, sy)
+    }
+}
+
+/// A substitution table a cell backend consults for a transformed cell: which character stands
+/// in for a glyph drawn under a given [`Transform`].
+///
+/// A terminal cannot mirror a character, but for many it has the mirror image as another
+/// character: `<` flipped is `>`, `/` is `\`. A backend handed one of these draws the
+/// substitute, and draws the glyph as authored wherever the table has no entry. Only the flips
+/// and rotation take part; a scale means nothing in a one-character cell and is ignored.
+///
+/// A substitute should have the same display width as the glyph it replaces, since the grid laid
+/// the row out for the original.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::tile::{GlyphTransforms, Transform};
+///
+/// let table = GlyphTransforms::mirrors().with('@', Transform::IDENTITY.rotated(2), '6');
+///
+/// let left = Transform::IDENTITY.flipped_x();
+/// assert_eq!(table.substitute('>', left), Some('<'));
+/// assert_eq!(table.substitute('>', left.scaled(3)), Some('<'));
+/// assert_eq!(table.substitute('@', Transform::IDENTITY.rotated(2)), Some('6'));
+/// assert_eq!(table.substitute('#', left), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphTransforms {
+    entries: BTreeMap<(char, u8), char>,
+}
+
+impl GlyphTransforms {
+    /// Pairs that are each other's mirror image left to right.
+    const MIRROR_X: [(char, char); 6] = [
+        ('<', '>'),
+        ('(', ')'),
+        ('[', ']'),
+        ('{', '}'),
+        ('/', '\\'),
+        ('\u{258C}', '\u{2590}'), // ▌ ▐
+    ];
+
+    /// Pairs that are each other's mirror image top to bottom.
+    const MIRROR_Y: [(char, char); 4] = [
+        ('^', 'v'),
+        ('/', '\\'),
+        ('\u{2580}', '\u{2584}'), // ▀ ▄
+        ('\u{25B2}', '\u{25BC}'), // ▲ ▼
+    ];
+
+    /// An empty table: every glyph is drawn as authored.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// A table of the common ASCII and block-element mirror pairs (`<`/`>`, `(`/`)`, `/`/`\`,
+    /// `^`/`v`, `▀`/`▄`, ...), each registered both ways for a horizontal or vertical flip.
+    #[must_use]
+    pub fn mirrors() -> Self {
+        let x = Transform::IDENTITY.flipped_x();
+        let y = Transform::IDENTITY.flipped_y();
+        let mut table = Self::new();
+        for (a, b) in Self::MIRROR_X {
+            table = table.with(a, x, b).with(b, x, a);
+        }
+        for (a, b) in Self::MIRROR_Y {
+            table = table.with(a, y, b).with(b, y, a);
+        }
+        table
+    }
+
+    /// This table with `glyph` drawn as `substitute` under `transform` (builder style). A later
+    /// entry for the same glyph and transform replaces an earlier one.
+    #[must_use]
+    pub fn with(mut self, glyph: char, transform: Transform, substitute: char) -> Self {
+        self.entries.insert((glyph, transform.bits), substitute);
+        self
+    }
+
+    /// The character to draw in place of `glyph` under `transform`, or `None` to draw `glyph`
+    /// itself.
+    #[must_use]
+    pub fn substitute(&self, glyph: char, transform: Transform) -> Option<char> {
+        if transform.bits == 0 {
+            return None;
+        }
+        self.entries.get(&(glyph, transform.bits)).copied()
+    }
+}
+
 /// Returns `grapheme` truncated to at most 8 codepoints (combining-mark bomb defence). If the
 /// input is already within the limit it is returned as-is.
 ///
@@ -751,4 +1018,39 @@ mod tests {
         let expected: String = core::iter::repeat_n('\u{0301}', 8).collect();
         assert_eq!(capped, expected);
     }
+
+    #[test]
+    fn test_transform_source_pixel_maps_every_pixel_back_into_the_sprite_once() {
+        // Each of the 8 flip/turn combinations must be a bijection from the transformed image
+        // onto the source sprite; a lopsided formula shows up as a repeat or an out-of-range hit.
+        let (w, h) = (3u32, 2u32);
+        for bits in 0..16u8 {
+            let t = Transform { bits, scale: 1 };
+            let (tw, th) = t.size(w, h);
+            let mut seen = [[false; 3]; 2];
+            for y in 0..th {
+                for x in 0..tw {
+                    let (sx, sy) = t.source_pixel(x, y, w, h);
+                    assert!(sx < w && sy < h, "{t:?} maps ({x},{y}) outside");
+                    assert!(!seen[sy as usize][sx as usize], "{t:?} repeats ({sx},{sy})");
+                    seen[sy as usize][sx as usize] = true;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_half_turn_equals_both_flips() {
+        let half = Transform::IDENTITY.rotated(2);
+        let both = Transform::IDENTITY.flipped_x().flipped_y();
+        for y in 0..4 {
+            for x in 0..5 {
+                assert_eq!(half.source_pixel(x, y, 5, 4), both.source_pixel(x, y, 5, 4));
+            }
+        }
+        assert_eq!(
+            Transform::IDENTITY.rotated(3).rotated(1),
+            Transform::IDENTITY
+        );
+    }
 }
@@ -0,0 +1,573 @@
+//! Tileset sprites drawn as inline images (the `tilesets` feature).
+//!
+//! [`SpriteGraphics`] sits between [`Crossterm`](crate::Crossterm)'s cell stream and
+//! [`TerminalRenderer`](retroglyph_terminal::TerminalRenderer): every cell whose glyph has a
+//! sprite is handed to the renderer as a blank cell in its own background color, and the sprite
+//! is drawn over it afterwards through whichever [`GraphicsProtocol`] the terminal answered the
+//! capability query for. The escape encoding itself lives in [`retroglyph_terminal::graphics`];
+//! this module only decides which cells need an image written this frame.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_core::backend::DrawCell;
+use retroglyph_core::color::{Remap, Style, Tint};
+use retroglyph_core::grid::{HasSize, Size};
+use retroglyph_core::tile::{Tile, Transform};
+use retroglyph_terminal::graphics::{self, GraphicsProtocol};
+use retroglyph_window::palette::Palette;
+use retroglyph_window::sprite_cache::{SpriteCache, SpriteTint};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, Write};
+
+/// One sprite as it appears on screen: the sprite, the cells it is stretched over, its
+/// recoloring, and its flips and turns. Every distinct key is its own image, uploaded (kitty) or
+/// encoded (Sixel) once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ImageKey {
+    glyph: char,
+    span: (u16, u16),
+    mask: Tint,
+    tint: Tint,
+    remap: Remap,
+    /// The cell's [`Transform`] without its scale: the image is stretched over the cells anyway.
+    transform: Transform,
+}
+
+/// How many images [`SpriteGraphics`] keeps uploaded (kitty) or encoded (Sixel) before it starts
+/// forgetting the least recently used ones that are off screen. A recoloring that changes every
+/// frame (a fade, a foreground gradient) is a new image each frame.
+const MAX_CACHED_IMAGES: usize = 256;
+
+/// A cached image, and the frame it was last written in.
+#[derive(Debug)]
+struct Cached<T> {
+    image: T,
+    used: u64,
+}
+
+/// Which cells show a sprite, and the images already sent to the terminal for them.
+#[derive(Debug)]
+pub(crate) struct SpriteGraphics {
+    protocol: GraphicsProtocol,
+    sprites: SpriteCache,
+    /// Pixel size of one cell, used to scale Sixel images; `None` when the terminal does not
+    /// report it, in which case sprites are painted at their own size.
+    cell_pixels: Option<(u16, u16)>,
+    size: Size,
+    /// The image each cell's sprite anchor shows, row-major; `None` for a text cell.
+    shown: Vec<Option<ImageKey>>,
+    /// Kitty image ids already transmitted.
+    uploaded: HashMap<ImageKey, Cached<u32>>,
+    /// The id the next kitty upload gets.
+    next_id: u32,
+    /// Sixel images already encoded.
+    encoded: HashMap<ImageKey, Cached<Vec<u8>>>,
+    /// How many frames have written images, for [`Cached::used`].
+    frame: u64,
+    /// Cells whose image has to be written this frame.
+    pending: BTreeSet<usize>,
+    /// Kitty placements to delete this frame, as `(cell, image)`.
+    removed: Vec<(usize, ImageKey)>,
+    /// Whether the Sixel cursor mode has been switched on yet; see [`SIXEL_CURSOR_RIGHT_ON`].
+    started: bool,
+}
+
+impl SpriteGraphics {
+    /// Draws `sprites` through `protocol` on a grid of `size` cells.
+    pub(crate) fn new(
+        protocol: GraphicsProtocol,
+        sprites: SpriteCache,
+        cell_pixels: Option<(u16, u16)>,
+        size: Size,
+    ) -> Self {
+        Self {
+            protocol,
+            sprites,
+            cell_pixels,
+            size,
+            shown: vec![None; cell_count(size)],
+            uploaded: HashMap::new(),
+            // Kitty reserves 0 for "no id".
+            next_id: 1,
+            encoded: HashMap::new(),
+            frame: 0,
+            pending: BTreeSet::new(),
+            removed: Vec::new(),
+            started: false,
+        }
+    }
+
+    pub(crate) const fn protocol(&self) -> GraphicsProtocol {
+        self.protocol
+    }
+
+    /// Records which of `cells` show a sprite, returning the tile to draw as text in place of
+    /// each one that does (or is covered by a span whose anchor does).
+    ///
+    /// The replacement is a blank in the cell's own background color: the sprite is drawn over
+    /// it, so any glyph left there would show through the sprite's transparent pixels.
+    pub(crate) fn prepare(&mut self, cells: &[DrawCell<'_>]) -> Vec<Option<Tile>> {
+        let width = usize::from(self.size.width());
+        let mut blanks = vec![None; cells.len()];
+        let mut covered = Vec::new();
+        for (i, cell) in cells.iter().enumerate() {
+            let tile = cell.tile;
+            if tile.is_wide_spacer() {
+                continue;
+            }
+            let index = usize::from(cell.pos.y) * width + usize::from(cell.pos.x);
+            if let Some((dx, dy)) = tile.span_offset() {
+                let anchor = cell
+                    .pos
+                    .x
+                    .checked_sub(dx)
+                    .zip(cell.pos.y.checked_sub(dy))
+                    .map(|(x, y)| usize::from(y) * width + usize::from(x));
+                covered.push((i, anchor));
+                continue;
+            }
+            let key = self.key(cell);
+            let Some(shown) = self.shown.get_mut(index) else {
+                continue;
+            };
+            let old = std::mem::replace(shown, key);
+            if old != key
+                && let Some(old) = old
+            {
+                self.removed.push((index, old));
+            }
+            if key.is_some() {
+                // A kitty placement survives the blank being redrawn under it; Sixel pixels
+                // don't, so those are repainted whenever their cell is.
+                if old != key || self.protocol == GraphicsProtocol::Sixel {
+                    self.pending.insert(index);
+                }
+                blanks[i] = Some(blank(tile));
+            } else {
+                self.pending.remove(&index);
+            }
+        }
+        // Covered cells last, once every anchor in this frame has been recorded.
+        for (i, anchor) in covered {
+            let Some(anchor) = anchor.filter(|&at| self.shown.get(at).copied().flatten().is_some())
+            else {
+                continue;
+            };
+            blanks[i] = Some(blank(cells[i].tile));
+            if self.protocol == GraphicsProtocol::Sixel {
+                self.pending.insert(anchor);
+            }
+        }
+        blanks
+    }
+
+    /// Writes every image change [`prepare`](Self::prepare) recorded, with the grid's first row
+    /// at screen row `row_offset`. Returns whether anything was written, in which case the
+    /// cursor has moved.
+    pub(crate) fn write<W: Write>(&mut self, out: &mut W, row_offset: u16) -> io::Result<bool> {
+        let wrote = !self.pending.is_empty() || !self.removed.is_empty();
+        if wrote && !self.started && self.protocol == GraphicsProtocol::Sixel {
+            out.write_all(SIXEL_CURSOR_RIGHT_ON)?;
+        }
+        self.started |= wrote;
+        self.frame += 1;
+        for (index, key) in std::mem::take(&mut self.removed) {
+            if let Some(cached) = self.uploaded.get(&key) {
+                graphics::write_kitty_delete_placement(out, cached.image, placement(index))?;
+            }
+        }
+        let width = usize::from(self.size.width()).max(1);
+        for index in std::mem::take(&mut self.pending) {
+            let Some(key) = self.shown.get(index).copied().flatten() else {
+                continue;
+            };
+            let (x, y) = (index % width, index / width);
+            let cursor = format!("\x1b[{};{}H", y + usize::from(row_offset) + 1, x + 1);
+            if self.protocol == GraphicsProtocol::Sixel {
+                let sixel = self.sixel(key)?;
+                out.write_all(cursor.as_bytes())?;
+                out.write_all(sixel)?;
+            } else {
+                let id = self.upload(out, key)?;
+                out.write_all(cursor.as_bytes())?;
+                graphics::write_kitty_place(out, id, placement(index), key.span.0, key.span.1)?;
+            }
+        }
+        if wrote {
+            self.evict(out)?;
+        }
+        Ok(wrote)
+    }
+
+    /// Once more than [`MAX_CACHED_IMAGES`] are cached, forgets the least recently used images
+    /// that aren't on screen until half that many are left, deleting evicted kitty uploads from
+    /// the terminal too.
+    fn evict<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let cached = self.uploaded.len() + self.encoded.len();
+        if cached <= MAX_CACHED_IMAGES {
+            return Ok(());
+        }
+        let on_screen: HashSet<ImageKey> = self.shown.iter().flatten().copied().collect();
+        let mut stale: Vec<(u64, ImageKey)> = self
+            .uploaded
+            .iter()
+            .map(|(&key, cached)| (cached.used, key))
+            .chain(self.encoded.iter().map(|(&key, cached)| (cached.used, key)))
+            .filter(|(_, key)| !on_screen.contains(key))
+            .collect();
+        stale.sort_unstable_by_key(|&(used, _)| used);
+        for (_, key) in stale.into_iter().take(cached - MAX_CACHED_IMAGES / 2) {
+            self.encoded.remove(&key);
+            if let Some(cached) = self.uploaded.remove(&key) {
+                graphics::write_kitty_delete_image(out, cached.image)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Forgets every image on screen after the screen was cleared, for a grid now `size` cells.
+    pub(crate) fn clear<W: Write>(&mut self, out: &mut W, size: Size) -> io::Result<()> {
+        self.size = size;
+        self.shown = vec![None; cell_count(size)];
+        self.pending.clear();
+        self.removed.clear();
+        // Clearing the screen erases Sixel pixels along with the text; kitty placements float
+        // above the text and have to be deleted explicitly.
+        if self.protocol == GraphicsProtocol::Kitty {
+            graphics::write_kitty_delete_all(out, false)?;
+        }
+        Ok(())
+    }
+
+    /// Frees everything this instance sent the terminal, before the backend lets go of it.
+    pub(crate) fn release<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        if !self.started {
+            return Ok(());
+        }
+        self.started = false;
+        if self.protocol == GraphicsProtocol::Sixel {
+            out.write_all(SIXEL_CURSOR_RIGHT_OFF)
+        } else {
+            graphics::write_kitty_delete_all(out, true)
+        }
+    }
+
+    /// The image `cell` shows, or `None` if its glyph has no sprite.
+    fn key(&self, cell: &DrawCell<'_>) -> Option<ImageKey> {
+        let tile = cell.tile;
+        let sprite = self.sprites.get(tile.glyph())?;
+        let SpriteTint { mask, tint, remap } = SpriteTint::resolve_with_palette(
+            sprite.color,
+            tile.style().foreground(),
+            cell.tint,
+            &Palette::DEFAULT,
+        )
+        .with_remap(cell.remap);
+        Some(ImageKey {
+            glyph: tile.glyph(),
+            span: tile.span(),
+            mask,
+            tint,
+            remap,
+            transform: cell.transform.scaled(1),
+        })
+    }
+
+    /// The recolored RGBA pixels for `key`, with their size.
+    fn pixels(&self, key: ImageKey) -> Option<(Vec<u8>, u32, u32)> {
+        let sprite = self.sprites.get(key.glyph)?;
+        let recolor = SpriteTint {
+            mask: key.mask,
+            tint: key.tint,
+            remap: key.remap,
+        };
+        let mut pixels = sprite.pixels.clone();
+        if !recolor.is_identity() {
+            for px in pixels.chunks_exact_mut(4) {
+                let (r, g, b) = recolor.apply((px[0], px[1], px[2]));
+                px[..3].copy_from_slice(&<[u8; 3]>::from((r, g, b)));
+            }
+        }
+        let (w, h) = (sprite.pixel_width, sprite.pixel_height);
+        if key.transform.is_identity() {
+            return Some((pixels, w, h));
+        }
+        let (tw, th) = key.transform.size(w, h);
+        let mut turned = Vec::with_capacity(pixels.len());
+        for y in 0..th {
+            for x in 0..tw {
+                let (sx, sy) = key.transform.source_pixel(x, y, w, h);
+                let at = ((sy * w + sx) * 4) as usize;
+                turned.extend_from_slice(&pixels[at..at + 4]);
+            }
+        }
+        Some((turned, tw, th))
+    }
+
+    /// Transmits `key`'s image on first use, returning its kitty image id.
+    fn upload<W: Write>(&mut self, out: &mut W, key: ImageKey) -> io::Result<u32> {
+        if let Some(cached) = self.uploaded.get_mut(&key) {
+            cached.used = self.frame;
+            return Ok(cached.image);
+        }
+        let id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+        if let Some((pixels, width, height)) = self.pixels(key) {
+            graphics::write_kitty_transmit(out, id, width, height, &pixels)?;
+        }
+        let used = self.frame;
+        self.uploaded.insert(key, Cached { image: id, used });
+        Ok(id)
+    }
+
+    /// Encodes `key`'s image as Sixel on first use, scaled to the cells it covers.
+    fn sixel(&mut self, key: ImageKey) -> io::Result<&[u8]> {
+        let used = self.frame;
+        if let Some(cached) = self.encoded.get_mut(&key) {
+            cached.used = used;
+        } else {
+            let mut sixel = Vec::new();
+            if let Some((pixels, width, height)) = self.pixels(key) {
+                let (to_width, to_height) = self.cell_pixels.map_or((width, height), |(w, h)| {
+                    (
+                        u32::from(w) * u32::from(key.span.0),
+                        u32::from(h) * u32::from(key.span.1),
+                    )
+                });
+                let scaled = graphics::scale_nearest(&pixels, width, height, to_width, to_height);
+                graphics::write_sixel(&mut sixel, to_width, to_height, &scaled)?;
+            }
+            self.encoded.insert(key, Cached { image: sixel, used });
+        }
+        Ok(self
+            .encoded
+            .get(&key)
+            .map_or(&[][..], |cached| cached.image.as_slice()))
+    }
+}
+
+/// DEC private mode 8452 on: after a Sixel image the cursor stays to the right of it, rather
+/// than moving below it, where an image on the bottom row would scroll the whole screen.
+const SIXEL_CURSOR_RIGHT_ON: &[u8] = b"\x1b[?8452h";
+const SIXEL_CURSOR_RIGHT_OFF: &[u8] = b"\x1b[?8452l";
+
+/// Kitty placement id for the sprite anchored at `index`; 0 means "no placement id".
+fn placement(index: usize) -> u32 {
+    u32::try_from(index).map_or(u32::MAX, |index| index.saturating_add(1))
+}
+
+fn cell_count(size: Size) -> usize {
+    usize::from(size.width()) * usize::from(size.height())
+}
+
+/// The text drawn under a sprite: nothing but `tile`'s background.
+fn blank(tile: &Tile) -> Tile {
+    Tile::new(' ', Style::new().bg(tile.style().background()))
+}
+
+/// A 2-tile sheet of 2x2 sprites for `'a'` (opaque red) and `'b'` (opaque green).
+#[cfg(test)]
+pub(crate) fn test_tileset() -> retroglyph_window::tileset::TilesetOptions {
+    use retroglyph_window::tileset::{Codepage, TilesetOptions};
+
+    let mut png = Vec::new();
+    let sheet = image::RgbaImage::from_fn(4, 2, |x, _| {
+        if x < 2 {
+            image::Rgba([255, 0, 0, 255])
+        } else {
+            image::Rgba([0, 255, 0, 255])
+        }
+    });
+    sheet
+        .write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    TilesetOptions::builder(png)
+        .tile_size(2, 2)
+        .columns(2)
+        .codepage(Codepage::Custom(vec!['a', 'b']))
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+pub(crate) fn test_sprites() -> SpriteCache {
+    SpriteCache::from_tilesets(&[test_tileset()]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retroglyph_core::grid::Pos;
+
+    fn frame(graphics: &mut SpriteGraphics, cells: &[(u16, u16, char)]) -> (Vec<bool>, String) {
+        let tiles: Vec<Tile> = cells
+            .iter()
+            .map(|&(_, _, glyph)| Tile::new(glyph, Style::new()))
+            .collect();
+        let draw: Vec<DrawCell<'_>> = cells
+            .iter()
+            .zip(&tiles)
+            .map(|(&(x, y, _), tile)| DrawCell::new(Pos { x, y }, tile))
+            .collect();
+        let blanks = graphics.prepare(&draw);
+        let mut out = Vec::new();
+        graphics.write(&mut out, 0).unwrap();
+        (
+            blanks.iter().map(Option::is_some).collect(),
+            String::from_utf8(out).unwrap(),
+        )
+    }
+
+    #[test]
+    fn sprite_cells_are_drawn_as_blanks_and_text_cells_untouched() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        let (blanks, _) = frame(&mut graphics, &[(0, 0, 'a'), (1, 0, 'x'), (2, 0, 'b')]);
+        assert_eq!(blanks, [true, false, true]);
+    }
+
+    #[test]
+    fn kitty_uploads_each_image_once_and_reuses_it_by_id() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        let (_, out) = frame(&mut graphics, &[(0, 0, 'a'), (3, 1, 'a')]);
+        assert_eq!(out.matches("a=t,").count(), 1);
+        assert!(out.contains("\x1b[1;1H\x1b_Ga=p,i=1,p=1,c=1,r=1,C=1,q=2\x1b\\"));
+        assert!(out.contains("\x1b[2;4H\x1b_Ga=p,i=1,p=8,c=1,r=1,C=1,q=2\x1b\\"));
+
+        // Redrawing the same sprite leaves its placement alone.
+        let (_, out) = frame(&mut graphics, &[(0, 0, 'a')]);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn a_flipped_sprite_is_its_own_image_and_a_scale_is_not() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        let tile = Tile::new('a', Style::new());
+        let flip = Transform::IDENTITY.flipped_x();
+        let draw = [
+            DrawCell::new(Pos { x: 0, y: 0 }, &tile),
+            DrawCell::new(Pos { x: 1, y: 0 }, &tile).with_transform(flip),
+            DrawCell::new(Pos { x: 2, y: 0 }, &tile).with_transform(flip.scaled(3)),
+        ];
+        graphics.prepare(&draw);
+        let mut out = Vec::new();
+        graphics.write(&mut out, 0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // The image is stretched over its cells whatever its size, so only the flip matters.
+        assert_eq!(out.matches("a=t,").count(), 2, "output: {out:?}");
+    }
+
+    #[test]
+    fn a_remapped_sprite_is_its_own_image() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        let tile = Tile::new('a', Style::new());
+        let silhouette = Remap::ramp(&[(255, 255, 255)]);
+        let draw = [
+            DrawCell::new(Pos { x: 0, y: 0 }, &tile),
+            DrawCell::new(Pos { x: 1, y: 0 }, &tile).with_remap(Some(&silhouette)),
+            DrawCell::new(Pos { x: 2, y: 0 }, &tile).with_remap(Some(&silhouette)),
+        ];
+        graphics.prepare(&draw);
+        let mut out = Vec::new();
+        graphics.write(&mut out, 0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("a=t,").count(), 2, "output: {out:?}");
+    }
+
+    #[test]
+    fn kitty_deletes_the_old_placement_when_a_cell_changes() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        frame(&mut graphics, &[(0, 0, 'a')]);
+        let (_, out) = frame(&mut graphics, &[(0, 0, 'b')]);
+        assert!(out.starts_with("\x1b_Ga=d,d=i,i=1,p=1,q=2\x1b\\"));
+        assert!(out.contains("i=2,p=1,"));
+
+        let (_, out) = frame(&mut graphics, &[(0, 0, 'x')]);
+        assert_eq!(out, "\x1b_Ga=d,d=i,i=2,p=1,q=2\x1b\\");
+    }
+
+    #[test]
+    fn sixel_repaints_every_redrawn_sprite_scaled_to_the_cell() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Sixel,
+            test_sprites(),
+            Some((4, 6)),
+            Size::new(4, 2),
+        );
+        let (_, out) = frame(&mut graphics, &[(1, 0, 'a')]);
+        assert!(
+            out.starts_with("\x1b[?8452h\x1b[1;2H\x1bP0;1;0q\"1;1;4;6"),
+            "output: {out:?}"
+        );
+        let (_, again) = frame(&mut graphics, &[(1, 0, 'a')]);
+        assert_eq!(again, out["\x1b[?8452h".len()..]);
+    }
+
+    #[test]
+    fn images_no_longer_shown_are_evicted_and_freed() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        let tile = Tile::new('a', Style::new());
+        let mut out = Vec::new();
+        // A tint fading over more frames than the cache holds: a new image every frame.
+        for step in 0..u16::try_from(MAX_CACHED_IMAGES * 2).unwrap() {
+            let [r, g] = step.to_le_bytes();
+            let tint = Tint::Multiply { r, g, b: 0 };
+            graphics.prepare(&[DrawCell::new(Pos { x: 0, y: 0 }, &tile).with_tint(tint)]);
+            graphics.write(&mut out, 0).unwrap();
+        }
+        assert!(graphics.uploaded.len() <= MAX_CACHED_IMAGES);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b_Ga=d,d=I,i=1,q=2\x1b\\"));
+        // The image on screen is never the one evicted.
+        let shown = graphics.shown[0].unwrap();
+        assert!(graphics.uploaded.contains_key(&shown));
+    }
+
+    #[test]
+    fn clear_forgets_what_was_shown() {
+        let mut graphics = SpriteGraphics::new(
+            GraphicsProtocol::Kitty,
+            test_sprites(),
+            None,
+            Size::new(4, 2),
+        );
+        frame(&mut graphics, &[(0, 0, 'a')]);
+        let mut out = Vec::new();
+        graphics.clear(&mut out, Size::new(2, 2)).unwrap();
+        assert_eq!(out, b"\x1b_Ga=d,d=a,q=2\x1b\\");
+        // Placed again, without uploading again.
+        let (_, out) = frame(&mut graphics, &[(0, 0, 'a')]);
+        assert_eq!(out, "\x1b[1;1H\x1b_Ga=p,i=1,p=1,c=1,r=1,C=1,q=2\x1b\\");
+    }
+}
@@ -29,12 +29,10 @@
 //! # Focus and lifecycle events
 //!
 //! With [`CrosstermOptions::focus_change`] enabled (the default), a terminal losing and regaining
-//! input focus is reported as [`Event::FocusLost`]/[`Event::FocusGained`]. This is the only
-//! lifecycle signal this backend currently has: unlike a windowed backend, there's no separate
-//! "suspended"/"paused" notion here, and this crate maps every focus change the same way
-//! regardless of the underlying reason (window manager focus switch, terminal minimized, or,
-//! notably on Wayland compositors, a terminal surface being hidden or unmapped without an
-//! accompanying resize).
+//! input focus is reported as [`Event::FocusLost`]/[`Event::FocusGained`]. This crate maps every
+//! focus change the same way regardless of the underlying reason (window manager focus switch,
+//! terminal minimized, or, notably on Wayland compositors, a terminal surface being hidden or
+//! unmapped without an accompanying resize).
 //!
 //! Terminal-side state (raw mode, the alternate screen, cursor position, last-written
 //! colors/attributes) is untouched by a focus change and is preserved across it: this backend
@@ -46,10 +44,24 @@
 //! backgrounded Wayland surface) should track [`Event::FocusLost`]/[`Event::FocusGained`] itself
 //! and skip its own draw calls in between.
 //!
-//! If `retroglyph-core` later adds a dedicated `Event::Suspended` (or similar) distinct from
-//! plain focus loss, this crate would need coordinated changes with `retroglyph-window` (which
-//! shares the `Event` enum) before mapping anything to it; no such variant exists today, so there
-//! is nothing for this backend to emit.
+//! The process itself being suspended, resumed, or told to quit is a different matter, reported
+//! only with [`CrosstermOptions::signals`] on (Unix only):
+//!
+//! | Signal    | Event                | What the backend does first                          |
+//! |-----------|----------------------|------------------------------------------------------|
+//! | `SIGINT`  | [`Event::Interrupt`] | nothing                                              |
+//! | `SIGTERM` | [`Event::Terminate`] | nothing                                              |
+//! | `SIGHUP`  | [`Event::HangUp`]    | nothing                                              |
+//! | `SIGTSTP` | [`Event::Suspended`] | nothing; the next poll stops the process             |
+//! | `SIGCONT` | [`Event::Resumed`]   | clears the screen for a full redraw                  |
+//!
+//! None of the signals runs its default action, so the app decides whether to exit. Suspending
+//! goes through [`Crossterm::suspend`]: the poll after the one that returned
+//! [`Event::Suspended`] hands the terminal back to the shell, stops the process, and, once the
+//! shell continues it, restores every option through the [`SuspendGuard`] before reporting
+//! [`Event::Resumed`]. In raw mode (the default) `Ctrl-C` and `Ctrl-Z` arrive as ordinary key
+//! presses rather than signals, so these come from `kill`, a process manager, or a closed
+//! session.
 //!
 //! # Tracing
 //!
@@ -82,6 +94,27 @@
 //! This crate has no code of its own gated on the flag; it exposes it so callers don't need to know
 //! which crate in the terminal family actually implements it.
 //!
+//! ### `event-stream`
+//!
+//! ⚪ Optional.
+//!
+//! Makes the async driver (`retroglyph_core::app::run_on_async`, `Terminal::events`) wait on
+//! crossterm's own async `EventStream` instead of re-polling the terminal on every executor turn.
+//!
+//! Without it, the async driver still sleeps until there is input: on unix a helper thread waits
+//! on the terminal for it, elsewhere it looks again every few milliseconds.
+//!
+//! ### `tilesets`
+//!
+//! ⚪ Optional.
+//!
+//! Draws tileset sprites as images, through the kitty graphics protocol or Sixel, on terminals that
+//! answer the capability query for either (`Crossterm::load_tilesets`).
+//!
+//! Decodes sprites with `retroglyph-window`'s `tilesets` support, so the same `TilesetOptions` that
+//! configure the pixel backends configure this one. Terminals that answer for neither keep drawing
+//! each sprite's glyph.
+//!
 //! ### `tracing`
 //!
 //! ⚪ Optional.
@@ -103,6 +136,47 @@
 //! mode, the alternate screen, and the other terminal-protocol negotiation always target the
 //! real process stdout regardless of `W`: see [`CrosstermOptions::build_with_writer`]'s docs
 //! for the exact split.
+//!
+//! # Capability probing
+//!
+//! At startup (in raw mode, on a real terminal) this backend asks the terminal what it supports:
+//! device attributes, `XTVERSION`, synchronized output, the kitty keyboard and graphics
+//! protocols, and its default colors. The answer, a [`TerminalCapabilities`] available from
+//! [`Crossterm::capabilities`], decides three things:
+//!
+//! - The [`ColorSupport`](retroglyph_terminal::ColorSupport) level, when neither the caller nor
+//!   `$NO_COLOR`/`$TERM` already settled it (see [`Crossterm::color_support`]).
+//! - Whether frames are wrapped in synchronized-update markers: not on a terminal that answered
+//!   without claiming mode 2026.
+//! - A single [`Event::ThemeChanged`], queued for the first [`poll_event`](Input::poll_event),
+//!   from the luminance of the reported background color.
+//!
+//! With mouse capture on, a terminal that claims SGR-Pixels mouse reporting (mode 1016) and
+//! reports its cell size in pixels (through `TIOCGWINSZ`, or `CSI 16 t` when that is zero) also
+//! gets the mode switched on: every [`MouseEvent`](retroglyph_core::event::MouseEvent) then
+//! carries a [`pixel_position`](retroglyph_core::event::MouseEvent::pixel_position) alongside
+//! its cell `position`, for sub-cell hit testing the way the pixel backends already allow.
+//!
+//! The probe waits at most [`CrosstermOptions::probe_timeout`] for an answer; a terminal that
+//! stays silent keeps the defaults. It isn't sent at all where `$TERM` is `dumb` or `linux` (the
+//! Linux console). Turn it off with [`CrosstermOptions::probe_capabilities`].
+//!
+//! # Clipboard
+//!
+//! [`Clipboard`] goes through OSC 52: the terminal emulator sets (or reports) the clipboard of
+//! the machine it runs on, so "copy to clipboard" works over SSH too. Setting is supported by
+//! most modern terminals (and by `tmux` with `set-clipboard on`); reading is allowed by few,
+//! since it would show every program writing to the terminal what the user copied, so treat
+//! [`clipboard_text`](Clipboard::clipboard_text) failing as the normal case and bracketed paste
+//! ([`Event::Paste`]) as the usual way text comes in.
+//!
+//! # Tileset sprites
+//!
+//! With the `tilesets` feature, [`Crossterm::load_tilesets`] takes the same
+//! [`TilesetOptions`](tileset::TilesetOptions) the pixel backends do and draws each sprite as an
+//! image, through the kitty graphics protocol or Sixel, on a terminal that answers the
+//! capability query for either. Everywhere else, including every terminal that stays silent,
+//! cells keep drawing the sprite's glyph, so the same app still reads as text.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/crates-lurey-io/retroglyph/main/docs/public/assets/logo.svg"
@@ -120,14 +194,28 @@
 #[doc = include_str!("../README.md")]
 struct ReadmeDoctests;
 
+#[cfg(feature = "tilesets")]
+mod graphics;
+mod query;
+mod signals;
+#[cfg(unix)]
+mod wake;
+
+// Re-exported so a crossterm-only app can build the same `TilesetOptions` the pixel backends take
+// without a direct `retroglyph-window` dependency.
+#[cfg(feature = "tilesets")]
+pub use retroglyph_window::{sprite_cache, tileset};
+
 use core::time::Duration;
 use retroglyph_core::backend::DrawCell;
-use retroglyph_core::backend::{Cursor, CursorStyle, Input, Output};
+use retroglyph_core::backend::{Clipboard, ClipboardError, Cursor, CursorStyle};
+use retroglyph_core::backend::{EventInbox, EventSender, Input, Output};
 use retroglyph_core::event::Event;
 use retroglyph_core::grid::HasSize;
-use retroglyph_core::grid::{Pos, Size};
+use retroglyph_core::grid::{Pos, Rect, Size};
 use retroglyph_core::terminal::Terminal;
 use retroglyph_terminal::TerminalRenderer;
+use retroglyph_terminal::capabilities::TerminalCapabilities;
 use std::collections::VecDeque;
 use std::io::{BufWriter, IsTerminal, Stdout};
 
@@ -160,7 +248,8 @@ fn keyboard_enhancement_flags() -> crossterm::event::KeyboardEnhancementFlags {
 ///   some CI systems, and similar) also forces `ColorSupport::None`. A bare `"dumb"` is never
 ///   emitted by a terminal that actually supports color, so this carries no false-positive risk,
 ///   unlike the heuristic described next.
-/// - Otherwise, [`ColorSupport::Truecolor`](retroglyph_terminal::ColorSupport::Truecolor):
+/// - Otherwise, `probed` if the terminal answered, else
+///   [`ColorSupport::Truecolor`](retroglyph_terminal::ColorSupport::Truecolor):
 ///   matches [`TerminalRenderer`](retroglyph_terminal::TerminalRenderer)'s own default and this
 ///   crate's pre-existing behavior (always pass `Color::Rgb` through verbatim). Degrading below
 ///   this is opt-in on one of the two positive signals above; their absence is not itself a
@@ -180,9 +269,16 @@ fn keyboard_enhancement_flags() -> crossterm::event::KeyboardEnhancementFlags {
 /// narrower levels remain fully available as an explicit choice via
 /// [`CrosstermOptions::color_support`](CrosstermOptions::color_support); they just aren't guessed
 /// at automatically.
+///
+/// The one narrower signal that *is* unambiguous is the terminal's own answer: `probed`, from
+/// the startup capability query's `DECRQSS` round trip (see
+/// [`TerminalCapabilities::color_support`]), which reports whether a 24-bit color actually came
+/// back intact. It is used when present, ranking below the two environment signals above, which
+/// are explicit requests for no color at all.
 fn detect_color_support(
     no_color: Option<&str>,
     term: Option<&str>,
+    probed: Option<retroglyph_terminal::ColorSupport>,
 ) -> retroglyph_terminal::ColorSupport {
     use retroglyph_terminal::ColorSupport;
 
@@ -192,17 +288,32 @@ fn detect_color_support(
     if term == Some("dumb") {
         return ColorSupport::None;
     }
-    ColorSupport::Truecolor
+    probed.unwrap_or(ColorSupport::Truecolor)
 }
 
 /// Thin env-reading wrapper around [`detect_color_support`]; reads the real process environment.
-fn detect_color_support_from_env() -> retroglyph_terminal::ColorSupport {
+fn detect_color_support_from_env(
+    probed: Option<retroglyph_terminal::ColorSupport>,
+) -> retroglyph_terminal::ColorSupport {
     detect_color_support(
         std::env::var("NO_COLOR").ok().as_deref(),
         std::env::var("TERM").ok().as_deref(),
+        probed,
     )
 }
 
+/// Whether to send the startup capability query to a terminal whose `$TERM` is `term`, as a pure
+/// function of its value so it's testable without mutating the real process environment.
+///
+/// Two values say the query is wasted: `"dumb"`, which promises no escape sequences at all, and
+/// `"linux"`, the Linux virtual console, which answers only the device attributes query that
+/// ends the batch and doesn't parse the rest, so parts of it can end up on screen as text.
+/// Everything else is asked; a terminal that stays silent only costs the
+/// [`probe_timeout`](CrosstermOptions::probe_timeout).
+fn term_worth_probing(term: Option<&str>) -> bool {
+    !matches!(term, Some("dumb" | "linux"))
+}
+
 // Tracks whether the currently-live `Crossterm` instance (there's normally at most one, since
 // each holds exclusive use of stdout/raw mode) actually entered the alternate screen / enabled
 // raw mode, so `restore_terminal` (shared by `Drop` and the process-wide panic hook, neither of
@@ -313,12 +424,19 @@ fn write_restore_sequence<W: std::io::Write>(w: &mut W) -> std::io::Result<()> {
         w,
         crossterm::event::DisableBracketedPaste,
         crossterm::event::DisableFocusChange,
+        crossterm::style::Print(DISABLE_PIXEL_MOUSE),
         crossterm::event::DisableMouseCapture,
         crossterm::style::SetAttribute(crossterm::style::Attribute::Reset),
         crossterm::cursor::Show
     )
 }
 
+/// Switches SGR-Pixels mouse reporting (mode 1016) on; crossterm has no command for it. Written
+/// after `EnableMouseCapture`, which turns on plain SGR reporting (mode 1006) that this refines.
+const ENABLE_PIXEL_MOUSE: &str = "\x1b[?1016h";
+/// Switches SGR-Pixels mouse reporting back off, leaving plain SGR reporting in place.
+const DISABLE_PIXEL_MOUSE: &str = "\x1b[?1016l";
+
 /// Helper function to restore the terminal to its normal state.
 /// This is called during drops and emergency panic hooks.
 fn restore_terminal() {
@@ -352,11 +470,13 @@ fn restore_terminal() {
 /// better at a call site than `CrosstermOptions::new()` but the two are
 /// otherwise identical (`builder()` just calls `Self::new()`).
 ///
-/// This crate does not attempt to auto-detect terminal
-/// capabilities (no `TERM` parsing, no `supports_keyboard_enhancement()`
-/// query): those queries can block for seconds on terminals that never
-/// respond. `CrosstermOptions` is the opt-out mechanism instead: callers who
-/// know their environment don't support a feature can disable it explicitly.
+/// The protocol toggles are not auto-detected: every one of them is harmless on a terminal
+/// that ignores it, so they default to on. What *is* detected, by one batched query at startup
+/// with a bounded wait (see [`probe_capabilities`](Self::probe_capabilities)), is only what
+/// changes the bytes worth writing: color depth, synchronized output, inline image support, and
+/// whether mouse positions can be reported in pixels.
+/// Callers who know their environment doesn't support a feature can still disable it
+/// explicitly.
 ///
 /// ```
 /// use retroglyph_crossterm::Crossterm;
@@ -364,7 +484,7 @@ fn restore_terminal() {
 /// let options = Crossterm::builder().mouse_capture(false).kitty_protocol(false);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-// Six independent, unrelated terminal protocol toggles, not a state machine in disguise: each
+// Seven independent, unrelated terminal protocol toggles, not a state machine in disguise: each
 // maps to one crossterm enable/disable command pair (or, for raw_mode/alt_screen, one
 // enable/leave pair) and is meaningful on its own.
 #[allow(clippy::struct_excessive_bools)]
@@ -381,6 +501,12 @@ pub struct CrosstermOptions {
     // (or even a bare `ColorSupport`) without losing the ability to tell "caller explicitly
     // wants `Truecolor`" apart from "caller didn't say, go detect it".
     color_support: Option<retroglyph_terminal::ColorSupport>,
+    // `Some(rows)` renders into an inline viewport of that many rows instead of the whole
+    // screen; see `inline`.
+    inline: Option<u16>,
+    probe_capabilities: bool,
+    probe_timeout: Duration,
+    signals: bool,
 }
 
 impl CrosstermOptions {
@@ -439,6 +565,29 @@ impl CrosstermOptions {
         self
     }
 
+    /// Renders into an inline viewport of `rows` lines below the shell's own output instead of
+    /// taking over the whole screen, the way build tools draw a live progress display.
+    ///
+    /// The viewport is reserved at construction, starting on the cursor's line (or the next one,
+    /// if the shell left the cursor mid-line) and scrolling the screen up when fewer than `rows`
+    /// lines are left below it. [`Output::size`] then reports the viewport, the full screen width
+    /// by `rows` (capped at the screen height), and the alternate screen is never entered,
+    /// whatever [`alt_screen`](Self::alt_screen) says. The last frame stays in the scrollback
+    /// when the backend is dropped, with the shell's next prompt starting on the line below it.
+    ///
+    /// [`Terminal::resize`] grows or shrinks the viewport to its new height, and
+    /// [`Crossterm::insert_before`] prints permanent lines above it. On a real terminal resize
+    /// the viewport keeps its top row where it fits, and moves up just enough to stay on screen
+    /// where it doesn't; see [`Crossterm::viewport`] for where it currently sits.
+    ///
+    /// Mouse events are reported relative to the viewport's top row, and those on rows outside
+    /// it are dropped: the screen there belongs to the shell, not the app.
+    #[must_use]
+    pub const fn inline(mut self, rows: u16) -> Self {
+        self.inline = Some(rows);
+        self
+    }
+
     /// Sets whether to enable raw mode (`crossterm::terminal::enable_raw_mode`).
     ///
     /// Disabling this leaves the terminal in cooked mode, so the OS/shell keep handling line
@@ -462,6 +611,50 @@ impl CrosstermOptions {
         self
     }
 
+    /// Sets whether to query the terminal's capabilities at startup (on by default).
+    ///
+    /// The query goes out once, after raw mode is enabled, and only when stdin and stdout are
+    /// both a terminal, `$TERM` is neither `dumb` nor `linux` (the Linux console, which answers
+    /// almost none of it), and the backend is not in [plain mode](Crossterm::plain_mode); see the
+    /// crate-level "Capability probing" docs for what the answer decides. Construction waits
+    /// for the terminal to finish answering, at most [`probe_timeout`](Self::probe_timeout).
+    /// Keys typed during that wait are lost.
+    ///
+    /// With this off (or raw mode off), nothing is asked: color support comes from
+    /// `$NO_COLOR`/`$TERM` alone, synchronized-update markers are always written, and no
+    /// [`Event::ThemeChanged`] is emitted.
+    #[must_use]
+    pub const fn probe_capabilities(mut self, enabled: bool) -> Self {
+        self.probe_capabilities = enabled;
+        self
+    }
+
+    /// Sets whether to report `SIGINT`, `SIGTERM`, `SIGHUP`, `SIGTSTP`, and `SIGCONT` as events
+    /// instead of letting them act on the process (off by default).
+    ///
+    /// See the crate-level "Focus and lifecycle events" docs for the events and what the backend
+    /// does on each. The signals are caught from construction until the backend is dropped;
+    /// afterwards they act on the process as usual again. While caught, a blocking
+    /// [`poll_event`](Input::poll_event) waits in short slices, the way it does while an
+    /// [`EventSender`] is live, so a signal is noticed within a few milliseconds. Does nothing
+    /// outside Unix.
+    #[must_use]
+    pub const fn signals(mut self, enabled: bool) -> Self {
+        self.signals = enabled;
+        self
+    }
+
+    /// Sets how long the startup capability query waits for the terminal to finish answering
+    /// (half a second by default); see [`probe_capabilities`](Self::probe_capabilities).
+    ///
+    /// Terminals answer in a round-trip, so the full wait only happens on one that answers
+    /// nothing. Whatever arrived before the timeout is still used.
+    #[must_use]
+    pub const fn probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = timeout;
+        self
+    }
+
     /// Builds the [`Crossterm`] backend with these options, rendering to standard output.
     ///
     /// Equivalent to [`Crossterm::with_options`]; this is the terminal step of the
@@ -524,7 +717,8 @@ impl CrosstermOptions {
 impl Default for CrosstermOptions {
     /// Every feature enabled; matches [`Crossterm::new`]'s historical behavior. `color_support`
     /// defaults to `None` (auto-detect from the environment at build time; see
-    /// [`CrosstermOptions::color_support`]).
+    /// [`CrosstermOptions::color_support`]). Signal handling, which changes what the process
+    /// does on a signal, is the one thing left off (see [`CrosstermOptions::signals`]).
     fn default() -> Self {
         Self {
             mouse_capture: true,
@@ -534,6 +728,10 @@ impl Default for CrosstermOptions {
             alt_screen: true,
             raw_mode: true,
             color_support: None,
+            inline: None,
+            probe_capabilities: true,
+            probe_timeout: query::PROBE_TIMEOUT,
+            signals: false,
         }
     }
 }
@@ -585,6 +783,16 @@ impl retroglyph_core::app::Launch for CrosstermOptions {
     }
 }
 
+/// How long one blocking `crossterm::event::poll` runs while an [`EventSender`] is live and
+/// there is no wake pipe to wait on (off unix, or if creating one failed), bounding how late
+/// [`Input::poll_event`] notices a sent event. Without the `event-stream` feature, also how long
+/// a task parked in [`Input::poll_next_event`] sleeps before looking again when no thread can
+/// wait on the terminal for it.
+///
+/// Short enough to be imperceptible for UI work (a blink timer, a finished download), long
+/// enough that an idle app still only wakes a hundred times a second.
+const SENDER_POLL_SLICE: Duration = Duration::from_millis(10);
+
 /// A terminal rendering backend powered by `crossterm`.
 ///
 /// Generic over the content writer `W`: the sink that receives rendered cell output
@@ -639,11 +847,45 @@ pub struct Crossterm<W: std::io::Write = BufWriter<Stdout>> {
     // the rest back (see `retroglyph-examples`' FPS overlay toggle). Both used to lose every
     // event they pushed here.
     pushed_events: VecDeque<Event>,
+    // Events sent from other threads through `Input::event_sender`, created on the first call to
+    // it. `None` keeps `poll_event`'s blocking path a single uninterrupted `crossterm::event::poll`
+    // for the common case of an app that never asks for a sender.
+    inbox: Option<EventInbox>,
+    // Created alongside `inbox`: what a blocked `poll_event` waits on so a send wakes it at once.
+    // `None` if it couldn't be created, which leaves `poll_event` polling in `SENDER_POLL_SLICE`s.
+    #[cfg(unix)]
+    sender_wake: Option<wake::SenderWake>,
+    // Crossterm's own async reader, created on the first `Input::poll_next_event` call so a
+    // purely blocking app never starts the background thread behind it.
+    #[cfg(feature = "event-stream")]
+    event_stream: Option<crossterm::event::EventStream>,
+    // Without `event-stream`: what wakes a task parked in `Input::poll_next_event`, created on
+    // its first call. `None` off unix or if it couldn't be created, which leaves the task woken
+    // every `SENDER_POLL_SLICE` to look again.
+    #[cfg(all(unix, not(feature = "event-stream")))]
+    task_wake: Option<wake::TaskWake>,
     // The options this instance was built with, retained so `suspend`/`SuspendGuard::resume` can
     // redo the exact enable sequence [`build_from_options`](Self::build_from_options) ran at
     // construction (raw mode, the alternate screen, mouse capture, focus-change reporting,
     // bracketed paste, the kitty keyboard protocol) rather than guessing which features were on.
     options: CrosstermOptions,
+    // Where the inline viewport sits on the real screen, or `None` when rendering full-screen
+    // (the default). `renderer`'s row offset always matches its `top`.
+    viewport: Option<Viewport>,
+    // Tileset sprites drawn as images, once `load_tilesets` found a protocol the terminal
+    // speaks; `None` draws every cell as text.
+    #[cfg(feature = "tilesets")]
+    graphics: Option<graphics::SpriteGraphics>,
+    // What the terminal answered the startup capability query with, or `None` if it was never
+    // asked; see `CrosstermOptions::probe_capabilities`.
+    capabilities: Option<TerminalCapabilities>,
+    // The cell size in pixels, `(width, height)`, while SGR-Pixels mouse reporting is on: mouse
+    // events then arrive in pixels and are divided back into cells by this. `None` (the mode
+    // left off) reports cells as usual; see `adopt_capabilities`.
+    pixel_mouse: Option<(u16, u16)>,
+    // Set once `poll_event` has handed out the `Event::Suspended` for a real `SIGTSTP`: the next
+    // poll stops the process, giving the app one update to react first. See `signals`.
+    stop_pending: bool,
 }
 
 impl Crossterm {
@@ -775,12 +1017,34 @@ impl<W: std::io::Write> Crossterm<W> {
 
     /// Returns the configured [`ColorSupport`](retroglyph_terminal::ColorSupport) level.
     ///
-    /// Set explicitly via [`CrosstermOptions::color_support`], or auto-detected from
-    /// `$NO_COLOR`/`$TERM` if not overridden; see that method's docs for the detection rules.
+    /// Set explicitly via [`CrosstermOptions::color_support`], or auto-detected if not
+    /// overridden: `$NO_COLOR`/`$TERM` first, then the terminal's answer to the startup
+    /// capability query (see [`TerminalCapabilities::color_support`]), then truecolor. See
+    /// `detect_color_support` in this crate's source for the exact rules.
     pub const fn color_support(&self) -> retroglyph_terminal::ColorSupport {
         self.renderer.color_support()
     }
 
+    /// Sets which character stands in for a glyph drawn with a
+    /// [`Transform`](retroglyph_core::tile::Transform), or `None` (the default) to ignore
+    /// transforms. See
+    /// [`TerminalRenderer::set_glyph_transforms`](retroglyph_terminal::TerminalRenderer::set_glyph_transforms).
+    pub fn set_glyph_transforms(
+        &mut self,
+        transforms: Option<retroglyph_core::tile::GlyphTransforms>,
+    ) {
+        self.renderer.set_glyph_transforms(transforms);
+    }
+
+    /// Returns what the terminal answered the startup capability query with, or `None` if it
+    /// was not asked (see [`CrosstermOptions::probe_capabilities`] for when it is).
+    ///
+    /// A terminal that was asked but stayed silent yields a
+    /// [`TerminalCapabilities`] with [`answered`](TerminalCapabilities::answered) `false`.
+    pub const fn capabilities(&self) -> Option<&TerminalCapabilities> {
+        self.capabilities.as_ref()
+    }
+
     /// Returns a mutable reference to the content writer.
     pub const fn writer_mut(&mut self) -> &mut W {
         self.renderer.writer_mut()
@@ -789,14 +1053,64 @@ impl<W: std::io::Write> Crossterm<W> {
     /// Updates the cached size field if `event` is a `crossterm::event::Event::Resize`; a
     /// no-op for every other event kind.
     ///
+    /// While SGR-Pixels mouse reporting is on, the cell size in pixels is re-read too, since a
+    /// font size change reaches the app as a resize.
+    ///
     /// Split out of [`Input::poll_event`] so it can be exercised directly in tests without
     /// requiring a real terminal event source. See retroglyph#279.
-    const fn refresh_cached_size_on_resize(&mut self, event: &crossterm::event::Event) {
+    ///
+    /// With an inline viewport, the cached size is the viewport's, refitted to the new screen
+    /// height (see [`CrosstermOptions::inline`]).
+    fn refresh_cached_size_on_resize(&mut self, event: &crossterm::event::Event) {
         if let crossterm::event::Event::Resize(width, height) = *event {
-            self.cached_size = Size::new(width, height);
+            self.cached_size = match &mut self.viewport {
+                Some(viewport) => {
+                    viewport.fit_screen(height);
+                    self.renderer.set_row_offset(viewport.top);
+                    Size::new(width, viewport.height)
+                }
+                None => Size::new(width, height),
+            };
+            if self.pixel_mouse.is_some() {
+                self.pixel_mouse = window_cell_pixels().or(self.pixel_mouse);
+            }
         }
     }
 
+    /// Maps a freshly read crossterm event the way both [`Input::poll_event`] and the async
+    /// `poll_next_event` need: refreshes the cached size first, then reports a resize as the
+    /// cached size, mouse positions reported in pixels (see `pixel_mouse`) as cells plus
+    /// pixels, and, with an inline viewport, mouse positions relative to the viewport, dropping
+    /// mouse events on rows outside it.
+    fn admit_crossterm_event(&mut self, event: crossterm::event::Event) -> Option<Event> {
+        self.refresh_cached_size_on_resize(&event);
+        let mapped = from_crossterm_event(event)?;
+        Some(match mapped {
+            Event::Resize(..) => Event::Resize(self.cached_size.width(), self.cached_size.height()),
+            Event::Mouse(mut mouse) => {
+                if let Some(cell) = self.pixel_mouse {
+                    mouse = from_pixel_mouse_event(mouse, cell);
+                }
+                if let Some(viewport) = self.viewport {
+                    let rows = viewport.top..viewport.top.saturating_add(viewport.height);
+                    if !rows.contains(&mouse.position.y) {
+                        return None;
+                    }
+                    mouse.position.y -= viewport.top;
+                    if let (Some(pixels), Some((_, cell_height))) =
+                        (&mut mouse.pixel_position, self.pixel_mouse)
+                    {
+                        pixels.y = pixels
+                            .y
+                            .saturating_sub(u32::from(viewport.top) * u32::from(cell_height));
+                    }
+                }
+                Event::Mouse(mouse)
+            }
+            mapped => mapped,
+        })
+    }
+
     fn build_from_options(
         options: CrosstermOptions,
         writer: W,
@@ -828,25 +1142,251 @@ impl<W: std::io::Write> Crossterm<W> {
         let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
 
         // Use the caller's explicit override if given; otherwise detect from
-        // `$NO_COLOR`/`$TERM`. Unlike `plain` above (which needs `writer`'s own
-        // `IsTerminal` status and so can't be detected for an arbitrary `build_with_writer`
-        // sink), these are process environment variables independent of `writer`, so detection
-        // applies the same way regardless of which `build*` method was called.
+        // `$NO_COLOR`/`$TERM` (refined by the capability probe below, if it runs). Unlike `plain`
+        // above (which needs `writer`'s own `IsTerminal` status and so can't be detected for an
+        // arbitrary `build_with_writer` sink), these are process environment variables
+        // independent of `writer`, so detection applies the same way regardless of which
+        // `build*` method was called.
         let color_support = options
             .color_support
-            .unwrap_or_else(detect_color_support_from_env);
+            .unwrap_or_else(|| detect_color_support_from_env(None));
+
+        let mut renderer =
+            TerminalRenderer::with_plain_mode(writer, plain).with_color_support(color_support);
+        let viewport = match options.inline {
+            Some(rows) => {
+                // Only a real terminal can answer the cursor-position query; anything else (a
+                // pipe, `cargo test`'s captured stdout) starts the viewport at the top.
+                let cursor = if !plain && std::io::stdout().is_terminal() {
+                    crossterm::cursor::position().ok()
+                } else {
+                    None
+                };
+                let (viewport, scroll) = Viewport::reserve(rows, height, cursor);
+                if !plain {
+                    scroll_screen(renderer.writer_mut(), height, scroll)?;
+                    renderer.writer_mut().flush()?;
+                }
+                renderer.set_row_offset(viewport.top);
+                Some(viewport)
+            }
+            None => None,
+        };
+        let rows = viewport.map_or(height, |viewport| viewport.height);
 
-        Ok(Self {
-            renderer: TerminalRenderer::with_plain_mode(writer, plain)
-                .with_color_support(color_support),
+        let mut term = Self {
+            renderer,
             _instance_guard: instance_guard,
-            cached_size: Size::new(width, height),
+            cached_size: Size::new(width, rows),
             pushed_events: VecDeque::new(),
+            inbox: None,
+            #[cfg(unix)]
+            sender_wake: None,
+            #[cfg(feature = "event-stream")]
+            event_stream: None,
+            #[cfg(all(unix, not(feature = "event-stream")))]
+            task_wake: None,
             options,
-        })
+            viewport,
+            #[cfg(feature = "tilesets")]
+            graphics: None,
+            capabilities: None,
+            pixel_mouse: None,
+            stop_pending: false,
+        };
+        // Ask only where the reply can be read back: raw mode (or it would be line-buffered and
+        // echoed) and a real terminal on both ends. The query itself targets the real stdout,
+        // like the rest of the protocol setup, whatever `writer` is.
+        if options.probe_capabilities
+            && options.raw_mode
+            && !plain
+            && std::io::stdout().is_terminal()
+            && term_worth_probing(std::env::var("TERM").ok().as_deref())
+        {
+            term.adopt_capabilities(query::probe(options.probe_timeout));
+        }
+        if options.signals {
+            signals::listen(term.inbox().sender())?;
+        }
+        Ok(term)
+    }
+
+    /// The inbox [`Input::event_sender`] and signal handling send to, created (along with the
+    /// wake pipe that lets a send interrupt a blocked [`Input::poll_event`]) on first use.
+    fn inbox(&mut self) -> &EventInbox {
+        if self.inbox.is_none() {
+            let inbox = EventInbox::new();
+            #[cfg(unix)]
+            {
+                self.sender_wake = wake::SenderWake::new(&inbox).ok();
+            }
+            self.inbox = Some(inbox);
+        }
+        self.inbox.get_or_insert_with(EventInbox::new)
+    }
+
+    /// Acts on the terminal's answer to the startup capability query: refines the
+    /// auto-detected color support, turns synchronized output off on a terminal that answered
+    /// without claiming it, queues an [`Event::ThemeChanged`] for the reported background, and
+    /// switches on SGR-Pixels mouse reporting where the terminal has it and mouse capture is on.
+    fn adopt_capabilities(&mut self, capabilities: TerminalCapabilities) {
+        if self.options.color_support.is_none() {
+            self.renderer
+                .set_color_support(detect_color_support_from_env(capabilities.color_support()));
+        }
+        if capabilities.answered {
+            // A terminal that answered everything but never claimed mode 2026 doesn't have it.
+            self.renderer
+                .set_synchronized_output(capabilities.synchronized_output == Some(true));
+        }
+        if let Some(theme) = capabilities.theme() {
+            self.pushed_events.push_back(Event::ThemeChanged(theme));
+        }
+        // Pixels are only any use with a cell size to divide them back into cells by; without
+        // one, the mode stays off and positions keep arriving in cells.
+        if self.options.mouse_capture && capabilities.pixel_mouse == Some(true) {
+            self.pixel_mouse = window_cell_pixels().or(capabilities.cell_pixels);
+            if self.pixel_mouse.is_some() {
+                let _ = enable_pixel_mouse();
+            }
+        }
+        self.capabilities = Some(capabilities);
+    }
+}
+
+/// Where an inline viewport (see [`CrosstermOptions::inline`]) sits on the real screen.
+///
+/// Pure bookkeeping: each method returns what the screen has to do (scroll up, shift the
+/// viewport down) and leaves emitting it to [`Crossterm`], so the geometry is testable without a
+/// terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Viewport {
+    /// Screen row of the viewport's first line.
+    top: u16,
+    /// Rows the viewport occupies: `requested`, capped at `screen_height`.
+    height: u16,
+    /// Rows the app asked for, kept so a screen that shrank below it and then grew back gives
+    /// them back.
+    requested: u16,
+    /// Rows of the real screen.
+    screen_height: u16,
+}
+
+/// What [`Crossterm::insert_before`] does to make room for one more line above the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InsertRoom {
+    /// There are blank rows below the viewport: shift the viewport down one row, and write the
+    /// line on the row it vacated.
+    ShiftDown,
+    /// The viewport already touches the bottom of the screen: scroll everything above it up one
+    /// row (the topmost line into the scrollback), and write the line just above the viewport.
+    ScrollAbove,
+    /// The viewport fills the whole screen, leaving nothing above it to scroll.
+    None,
+}
+
+impl Viewport {
+    /// Places a viewport of `rows` lines for a cursor at `cursor` (column, row) on a screen
+    /// `screen_height` rows tall, returning it along with how many rows the screen has to scroll
+    /// up first to fit it below the cursor.
+    fn reserve(rows: u16, screen_height: u16, cursor: Option<(u16, u16)>) -> (Self, u16) {
+        let screen_height = screen_height.max(1);
+        let requested = rows.max(1);
+        let height = requested.min(screen_height);
+        let (column, row) = cursor.unwrap_or((0, 0));
+        // Never draw over the end of a line the shell left the cursor on.
+        let row = if column > 0 {
+            row.saturating_add(1)
+        } else {
+            row
+        };
+        let scroll = row.saturating_add(height).saturating_sub(screen_height);
+        let viewport = Self {
+            top: row.saturating_sub(scroll),
+            height,
+            requested,
+            screen_height,
+        };
+        (viewport, scroll)
+    }
+
+    /// Follows a real terminal resize to `screen_height` rows: the viewport keeps its top row
+    /// if it still fits, otherwise moves up just enough to stay on screen.
+    const fn fit_screen(&mut self, screen_height: u16) {
+        self.screen_height = if screen_height == 0 { 1 } else { screen_height };
+        self.height = if self.requested < self.screen_height {
+            self.requested
+        } else {
+            self.screen_height
+        };
+        let max_top = self.screen_height - self.height;
+        if self.top > max_top {
+            self.top = max_top;
+        }
+    }
+
+    /// Grows or shrinks the viewport to `rows`, returning how many rows the screen has to
+    /// scroll up to fit a viewport that grew past the bottom.
+    fn set_height(&mut self, rows: u16) -> u16 {
+        self.requested = rows.max(1);
+        self.height = self.requested.min(self.screen_height);
+        let scroll = self
+            .top
+            .saturating_add(self.height)
+            .saturating_sub(self.screen_height);
+        self.top -= scroll;
+        scroll
+    }
+
+    /// Decides how the next inserted line makes room, and moves the viewport accordingly.
+    const fn insert_room(&mut self) -> InsertRoom {
+        if self.top + self.height < self.screen_height {
+            self.top += 1;
+            InsertRoom::ShiftDown
+        } else if self.top > 0 {
+            InsertRoom::ScrollAbove
+        } else {
+            InsertRoom::None
+        }
     }
 }
 
+/// Scrolls the whole screen (`screen_height` rows tall) up by `rows`, pushing its top lines into
+/// the scrollback: line feeds at the bottom row, which every terminal treats that way, unlike the
+/// scroll-up control sequence.
+fn scroll_screen<W: std::io::Write>(
+    writer: &mut W,
+    screen_height: u16,
+    rows: u16,
+) -> std::io::Result<()> {
+    if rows == 0 {
+        return Ok(());
+    }
+    write!(writer, "\x1b[{screen_height};1H")?;
+    for _ in 0..rows {
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// The cell size in pixels, `(width, height)`, from the real terminal's `TIOCGWINSZ`, or `None`
+/// when it reports no pixel size (many terminals leave it zero).
+fn window_cell_pixels() -> Option<(u16, u16)> {
+    let size = crossterm::terminal::window_size().ok()?;
+    let width = size.width.checked_div(size.columns)?;
+    let height = size.height.checked_div(size.rows)?;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+/// Switches SGR-Pixels mouse reporting on, on the real process stdout like the rest of the
+/// protocol setup in [`enable_terminal_features`].
+fn enable_pixel_mouse() -> std::io::Result<()> {
+    crossterm::execute!(
+        std::io::stdout(),
+        crossterm::style::Print(ENABLE_PIXEL_MOUSE)
+    )
+}
+
 /// Enables the terminal-protocol features `options` selects, targeting the real process stdout.
 ///
 /// Shared by [`Crossterm::build_from_options`] (initial construction) and
@@ -866,7 +1406,9 @@ fn enable_terminal_features(options: CrosstermOptions) -> std::io::Result<()> {
     // content sink a caller may have swapped in via `build_with_writer`. See that method's docs.
     let mut stdout = std::io::stdout();
 
-    if options.alt_screen {
+    // An inline viewport lives in the normal screen's scrollback, so it never enters the
+    // alternate screen; see `CrosstermOptions::inline`.
+    if options.alt_screen && options.inline.is_none() {
         crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
         ALT_SCREEN_ACTIVE.store(true, Ordering::Release);
     }
@@ -910,8 +1452,18 @@ impl<W: std::io::Write> Drop for Crossterm<W> {
         // would only flush once this whole `drop` returns and Rust drops `renderer` for real,
         // dumping any still-queued escape bytes onto the shell *after* the alternate screen has
         // already been left. See retroglyph#716.
+        #[cfg(feature = "tilesets")]
+        if let Some(graphics) = &mut self.graphics {
+            let _ = graphics.release(self.renderer.writer_mut());
+        }
+        if self.viewport.is_some() {
+            let _ = self.leave_viewport();
+        }
         let _ = self.renderer.flush();
         restore_terminal();
+        if self.options.signals {
+            signals::release();
+        }
     }
 }
 
@@ -933,10 +1485,9 @@ impl<W: std::io::Write> Crossterm<W> {
     /// suspended may have written arbitrary content to the real screen that this backend's diff
     /// state doesn't know about.
     ///
-    /// Does not handle `Ctrl+Z`/`SIGTSTP`: this is an explicit API for the common case (a key
-    /// binding that shells out), not a signal handler. An app that also wants to
-    /// suspend on `SIGTSTP` needs to install its own signal handler and call this method (and
-    /// [`SuspendGuard::resume`]) from it.
+    /// Does not handle `SIGTSTP` by itself: this is an explicit API for the common case (a key
+    /// binding that shells out). With [`CrosstermOptions::signals`] on, the backend calls it on
+    /// `SIGTSTP` too, around actually stopping the process.
     ///
     /// # Errors
     ///
@@ -955,6 +1506,33 @@ impl<W: std::io::Write> Crossterm<W> {
             resumed: false,
         })
     }
+
+    /// Stops the process for the `SIGTSTP` whose [`Event::Suspended`] the last poll handed out,
+    /// if there is one, suspended through [`suspend`](Self::suspend) so the shell gets its
+    /// terminal back until the process is continued.
+    fn stop_if_pending(&mut self) {
+        if core::mem::take(&mut self.stop_pending)
+            && let Ok(guard) = self.suspend()
+        {
+            let _ = signals::stop();
+            let _ = guard.resume();
+        }
+    }
+
+    /// Does the bookkeeping an event from the inbox needs before the app sees it: arms the stop
+    /// for a real `SIGTSTP`'s [`Event::Suspended`], and clears the screen on [`Event::Resumed`],
+    /// since whatever ran in the meantime may have drawn over it (the [`Terminal`] then forgets
+    /// its last frame, so the next present redraws all of it).
+    fn admit_sent_event(&mut self, event: Event) -> Event {
+        match event {
+            Event::Suspended => self.stop_pending = signals::take_stop_request(),
+            Event::Resumed if self.options.signals => {
+                let _ = Output::clear(self);
+            }
+            _ => {}
+        }
+        event
+    }
 }
 
 /// RAII guard returned by [`Crossterm::suspend`]; see that method's docs for the full contract.
@@ -989,6 +1567,9 @@ impl<W: std::io::Write> SuspendGuard<'_, W> {
         }
         self.resumed = true;
         enable_terminal_features(self.crossterm.options)?;
+        if self.crossterm.pixel_mouse.is_some() {
+            enable_pixel_mouse()?;
+        }
         // The shelled-out program may have written arbitrary content to the real screen; forget
         // the tracked cursor/style state so the next `draw` re-emits full escape sequences
         // instead of skipping them under the assumption the terminal is still in the last-known
@@ -1018,6 +1599,25 @@ impl<W: std::io::Write> Output for Crossterm<W> {
         let size = self.cached_size;
         let content =
             content.filter(move |cell| cell.pos.x < size.width() && cell.pos.y < size.height());
+        #[cfg(feature = "tilesets")]
+        if let Some(graphics) = &mut self.graphics {
+            // Sprite cells go out as blanks first, then the images over them, all inside the
+            // same synchronized update.
+            let cells: Vec<DrawCell<'_>> = content.collect();
+            let blanks = graphics.prepare(&cells);
+            let text = cells.iter().zip(&blanks).map(|(cell, blank)| {
+                blank.as_ref().map_or(*cell, |tile| {
+                    DrawCell::on_layer(cell.layer, cell.pos, tile).with_tint(cell.tint)
+                })
+            });
+            self.renderer.draw_frame(text)?;
+            let row_offset = self.renderer.row_offset();
+            if graphics.write(self.renderer.writer_mut(), row_offset)? {
+                // The images moved the cursor behind the renderer's back.
+                self.renderer.reset_state();
+            }
+            return Ok(());
+        }
         // Begin synchronized update so the terminal holds rendering until
         // flush() sends the matching End marker.
         self.renderer.draw_frame(content)
@@ -1040,7 +1640,20 @@ impl<W: std::io::Write> Output for Crossterm<W> {
         // (see `cached_size`'s docs): without this, `size()` drifted permanently from the real
         // terminal size after any resize, since nothing else here ever wrote to it
         // (retroglyph#763).
-        self.cached_size = size;
+        if let Some(viewport) = self.viewport {
+            // The screen's own resizes arrive here with the viewport's height already applied
+            // (see `refresh_cached_size_on_resize`), so any other height is the app asking for a
+            // taller or shorter viewport.
+            if size.height() != viewport.height {
+                let _ = self.resize_viewport(size.height());
+            }
+            let rows = self
+                .viewport
+                .map_or(viewport.height, |viewport| viewport.height);
+            self.cached_size = Size::new(size.width(), rows);
+        } else {
+            self.cached_size = size;
+        }
         let _ = self.clear();
     }
 
@@ -1057,7 +1670,15 @@ impl<W: std::io::Write> Output for Crossterm<W> {
         // leaves the BCE-tinted patch on screen permanently: exactly the "gaps where the
         // background doesn't clear" symptom after a resize, since nothing ever draws over it
         // again.
-        self.renderer.clear_screen()
+        #[cfg(feature = "tilesets")]
+        if let Some(graphics) = &mut self.graphics {
+            graphics.clear(self.renderer.writer_mut(), self.cached_size)?;
+        }
+        match self.viewport {
+            // Only the viewport is this backend's to clear; everything above it is the shell's.
+            Some(viewport) => self.renderer.clear_rows(0..viewport.height),
+            None => self.renderer.clear_screen(),
+        }
     }
 }
 
@@ -1073,6 +1694,7 @@ impl<W: std::io::Write> Input for Crossterm<W> {
     /// to throttle on its behalf.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        self.stop_if_pending();
         // Pushed events jump the queue: they were either injected by a test harness or handed
         // back by a driver that already read them off the real terminal, so in both cases they
         // are older than anything crossterm still has waiting.
@@ -1083,21 +1705,43 @@ impl<W: std::io::Write> Input for Crossterm<W> {
         let start = std::time::Instant::now();
 
         loop {
+            // Sent events have no file descriptor crossterm's `poll` could wait on alongside the
+            // terminal's, so while a sender is live crossterm only polls without blocking, and
+            // the wait happens on the wake pipe instead (see `wake`). Without one, the wait is
+            // sliced into short polls with the inbox checked in between; see `SENDER_POLL_SLICE`.
+            let sending = match &self.inbox {
+                Some(inbox) => {
+                    if let Some(event) = inbox.try_recv() {
+                        return Some(self.admit_sent_event(event));
+                    }
+                    inbox.has_senders()
+                }
+                None => false,
+            };
+            #[cfg(unix)]
+            let woken_by_pipe = sending && self.sender_wake.is_some();
+            #[cfg(not(unix))]
+            let woken_by_pipe = false;
+            let slice = match (sending, woken_by_pipe) {
+                (_, true) => Duration::ZERO,
+                (true, false) => SENDER_POLL_SLICE,
+                (false, _) => Duration::MAX,
+            };
             // Cap the polling timeout to 1 hour to prevent system-call overflow of massive durations (like Duration::MAX).
             let poll_timeout = timeout
                 .saturating_sub(start.elapsed())
-                .min(Duration::from_secs(3600));
+                .min(Duration::from_secs(3600))
+                .min(slice);
 
             match crossterm::event::poll(poll_timeout) {
                 Ok(true) => {
                     let Ok(event) = crossterm::event::read() else {
                         return None;
                     };
-                    // Refresh the cached size in lockstep with the resize event the app
+                    // Refreshes the cached size in lockstep with the resize event the app
                     // itself is about to receive, so `size()` (no syscall) stays consistent
                     // with what already triggered this event. See retroglyph#279.
-                    self.refresh_cached_size_on_resize(&event);
-                    if let Some(mapped) = from_crossterm_event(event) {
+                    if let Some(mapped) = self.admit_crossterm_event(event) {
                         return Some(mapped);
                     }
                     // An unmappable event was consumed. In non-blocking mode
@@ -1108,7 +1752,15 @@ impl<W: std::io::Write> Input for Crossterm<W> {
                     }
                 }
                 Ok(false) => {
-                    // Timeout elapsed on this poll chunk.
+                    // Timeout elapsed on this poll chunk, or there is nothing yet and the wait
+                    // belongs to the wake pipe.
+                    #[cfg(unix)]
+                    if let Some(wake) = self.sender_wake.as_ref().filter(|_| woken_by_pipe) {
+                        let left = timeout.saturating_sub(start.elapsed());
+                        if !left.is_zero() && wake.wait(left).is_err() {
+                            return None;
+                        }
+                    }
                 }
                 Err(_) => {
                     return None;
@@ -1139,6 +1791,103 @@ impl<W: std::io::Write> Input for Crossterm<W> {
     fn push_event(&mut self, event: Event) {
         self.pushed_events.push_back(event);
     }
+
+    /// Hands out a sender whose events [`poll_event`](Self::poll_event) returns after pushed
+    /// events and ahead of the real terminal's.
+    ///
+    /// A send wakes a blocked `poll_event`, or a task parked in the async
+    /// [`poll_next_event`](Self::poll_next_event), immediately. On unix
+    /// the blocking wait then happens on the terminal and a self-pipe the send writes to. Off
+    /// unix, `poll_event` polls the terminal in slices of a few milliseconds while any sender is
+    /// live and checks for sent events in between, so a send is noticed within one slice rather
+    /// than after the whole `timeout`.
+    fn event_sender(&mut self) -> Option<EventSender> {
+        Some(self.inbox().sender())
+    }
+
+    /// Polls crossterm's async `EventStream`, registering `cx`'s waker with it, so an async
+    /// driver sleeps until the terminal actually has input.
+    ///
+    /// Pushed events still jump the queue, as in [`poll_event`](Self::poll_event). A read error
+    /// or a closed stream is reported the way `poll_event` reports one, as no event: the task is
+    /// woken to poll again rather than left parked on a stream that will never wake it.
+    #[cfg(feature = "event-stream")]
+    fn poll_next_event(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Event> {
+        use futures_core::Stream;
+        use std::task::Poll;
+
+        self.stop_if_pending();
+        if let Some(event) = self.pushed_events.pop_front() {
+            return Poll::Ready(event);
+        }
+        if let Some(event) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.poll_recv(cx.waker()))
+        {
+            return Poll::Ready(self.admit_sent_event(event));
+        }
+        loop {
+            let stream = self
+                .event_stream
+                .get_or_insert_with(crossterm::event::EventStream::new);
+            match std::pin::Pin::new(stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    // Same bookkeeping as the blocking path: see `poll_event`.
+                    if let Some(mapped) = self.admit_crossterm_event(event) {
+                        return Poll::Ready(mapped);
+                    }
+                }
+                Poll::Ready(Some(Err(_)) | None) => {
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// Takes whatever the terminal already has without blocking, then parks until it has more,
+    /// the window is resized, or an event is sent.
+    ///
+    /// On unix a thread waits on the terminal meanwhile (see
+    /// `retroglyph_terminal::wake::InputWatcher`). Elsewhere, or if that thread can't be started,
+    /// the task is woken to look again after a few milliseconds. The `event-stream` feature
+    /// replaces all of this with crossterm's own `EventStream`.
+    #[cfg(not(feature = "event-stream"))]
+    fn poll_next_event(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Event> {
+        use std::task::Poll;
+
+        if let Some(event) = self.poll_event(Duration::ZERO) {
+            return Poll::Ready(event);
+        }
+        if let Some(event) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.poll_recv(cx.waker()))
+        {
+            return Poll::Ready(self.admit_sent_event(event));
+        }
+        #[cfg(unix)]
+        {
+            if self.task_wake.is_none() {
+                self.task_wake = wake::TaskWake::new().ok();
+            }
+            if let Some(wake) = &self.task_wake {
+                wake.register(cx.waker());
+                return Poll::Pending;
+            }
+        }
+        let waker = cx.waker().clone();
+        let spawned = std::thread::Builder::new().spawn(move || {
+            std::thread::sleep(SENDER_POLL_SLICE);
+            waker.wake();
+        });
+        if spawned.is_err() {
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
+    }
 }
 
 impl<W: std::io::Write> Crossterm<W> {
@@ -1160,6 +1909,86 @@ impl<W: std::io::Write> Crossterm<W> {
         writer.flush()
     }
 
+    /// Loads tileset sprites and, if the terminal can show images, draws every cell whose glyph
+    /// has one as that sprite instead of as text.
+    ///
+    /// Decodes `tilesets` exactly the way the pixel backends do (later tilesets win on codepoint
+    /// collision, see [`SpriteCache::from_tilesets`](sprite_cache::SpriteCache::from_tilesets)),
+    /// so one tileset configuration drives every backend. It then draws them through whichever
+    /// inline image protocol the terminal answered the startup capability query for (asking now,
+    /// waiting at most [`CrosstermOptions::probe_timeout`], if that query was turned off):
+    ///
+    /// - **Kitty graphics** (kitty, `WezTerm`, Ghostty, Konsole): each distinct sprite is uploaded
+    ///   once and then placed by id in every cell that shows it, scaled by the terminal to fill
+    ///   the cell (or the whole footprint of a span). Preferred when a terminal speaks both.
+    /// - **Sixel** (foot, mlterm, xterm, `WezTerm`, Windows Terminal): each sprite is painted
+    ///   into its cell, scaled to the cell's pixel size when the terminal reports one, and
+    ///   repainted whenever the cell is redrawn.
+    ///
+    /// Returns the protocol in use, or `None` when sprites keep drawing as their glyphs: the
+    /// terminal answered neither, did not answer in time, the backend is in
+    /// [plain mode](Self::plain_mode), raw mode is off (the reply could not be read), stdin or
+    /// stdout is not a terminal, or the tilesets hold no sprites. Non-unix platforms are never
+    /// queried. Keys typed during the query, before the terminal answers, are lost.
+    ///
+    /// A sprite's [`SpriteAlign`](tileset::SpriteAlign) is not honored here: the image is
+    /// stretched over its whole cell box. Calling this again replaces the sprites loaded before;
+    /// clear the screen afterwards so cells drawn with the old set are redrawn.
+    ///
+    /// Available only when the `tilesets` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`TilesetError`](tileset::TilesetError) decoding a tileset fails with;
+    /// the previously loaded sprites, if any, stay in use.
+    #[cfg(feature = "tilesets")]
+    pub fn load_tilesets(
+        &mut self,
+        tilesets: &[tileset::TilesetOptions],
+    ) -> Result<Option<retroglyph_terminal::graphics::GraphicsProtocol>, tileset::TilesetError>
+    {
+        let sprites = sprite_cache::SpriteCache::from_tilesets(tilesets)?;
+        let can_ask = !sprites.is_empty()
+            && !self.plain_mode()
+            && self.options.raw_mode
+            && std::io::stdout().is_terminal();
+        // Reuse the startup probe's answer; ask now only if it was turned off.
+        let protocol = if can_ask {
+            self.capabilities
+                .as_ref()
+                .map_or_else(|| query::probe(self.options.probe_timeout), Clone::clone)
+                .graphics
+        } else {
+            None
+        };
+        let cell_pixels = window_cell_pixels();
+        self.set_graphics(protocol.map(|protocol| {
+            graphics::SpriteGraphics::new(protocol, sprites, cell_pixels, self.cached_size)
+        }));
+        Ok(protocol)
+    }
+
+    /// Returns the inline image protocol tileset sprites are drawn through, or `None` when they
+    /// are drawn as their glyphs; see [`load_tilesets`](Self::load_tilesets).
+    ///
+    /// Available only when the `tilesets` feature is enabled.
+    #[cfg(feature = "tilesets")]
+    pub fn graphics_protocol(&self) -> Option<retroglyph_terminal::graphics::GraphicsProtocol> {
+        self.graphics
+            .as_ref()
+            .map(graphics::SpriteGraphics::protocol)
+    }
+
+    /// Swaps in `graphics`, freeing what the old sprites left on the terminal and forcing the
+    /// next frame to redraw.
+    #[cfg(feature = "tilesets")]
+    fn set_graphics(&mut self, graphics: Option<graphics::SpriteGraphics>) {
+        if let Some(mut old) = std::mem::replace(&mut self.graphics, graphics) {
+            let _ = old.release(self.renderer.writer_mut());
+        }
+        self.renderer.reset_state();
+    }
+
     /// Rings the terminal bell (writes the `BEL` control character, `\x07`).
     ///
     /// Crossterm has no dedicated `Command` type for this (unlike [`Self::set_title`]'s
@@ -1178,6 +2007,123 @@ impl<W: std::io::Write> Crossterm<W> {
     }
 }
 
+impl<W: std::io::Write> Crossterm<W> {
+    /// Where the inline viewport sits on the real screen, in screen cells, or `None` when this
+    /// backend renders full-screen. See [`CrosstermOptions::inline`].
+    pub fn viewport(&self) -> Option<Rect> {
+        self.viewport
+            .map(|viewport| Rect::new(0, viewport.top, self.cached_size.width(), viewport.height))
+    }
+
+    /// Prints `text` as permanent lines just above the inline viewport, the way a build tool
+    /// logs each finished step above its live progress display.
+    ///
+    /// Each line of `text` moves the viewport down a row while there is room below it, then
+    /// scrolls everything above it up, so older lines end up in the scrollback. The viewport's
+    /// own content is carried along rather than redrawn. Lines are written without styling and
+    /// cut at the screen width, since a wrapped line would overrun the viewport.
+    ///
+    /// In [plain mode](Self::plain_mode) the lines are simply written out, one per line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `std::io::Error` with [`std::io::ErrorKind::Unsupported`] without an inline
+    /// viewport (there is nothing to print above), with [`std::io::ErrorKind::InvalidInput`] if
+    /// the viewport fills the whole screen (there is nowhere to print), or the writer's own error
+    /// if writing or flushing fails.
+    pub fn insert_before(&mut self, text: &str) -> std::io::Result<()> {
+        let Some(mut viewport) = self.viewport else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "insert_before needs an inline viewport (CrosstermOptions::inline)",
+            ));
+        };
+        let width = self.cached_size.width();
+        if self.renderer.plain_mode() {
+            let writer = self.renderer.writer_mut();
+            for line in text.lines() {
+                writeln!(writer, "{line}")?;
+            }
+            return writer.flush();
+        }
+        let result = (|| {
+            let writer = self.renderer.writer_mut();
+            for line in text.lines() {
+                let (line, _) = retroglyph_core::text::split_at_width(line, width);
+                let row = match viewport.insert_room() {
+                    InsertRoom::ShiftDown => {
+                        // Scroll just the viewport and the blank row below it down by one: the
+                        // region is 1-indexed `top..=top + height` of the viewport before the
+                        // move, and `insert_room` already advanced `top` past it.
+                        let first = viewport.top;
+                        let last = viewport.top + viewport.height;
+                        write!(writer, "\x1b[{first};{last}r\x1b[1T\x1b[r")?;
+                        viewport.top - 1
+                    }
+                    InsertRoom::ScrollAbove => {
+                        // A line feed on the bottom margin of a region starting at the top of
+                        // the screen scrolls that region, sending its top line to the
+                        // scrollback where the terminal keeps one.
+                        let above = viewport.top;
+                        write!(writer, "\x1b[1;{above}r\x1b[{above};1H\n\x1b[r")?;
+                        viewport.top - 1
+                    }
+                    InsertRoom::None => {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            "the inline viewport fills the whole screen; nothing fits above it",
+                        ));
+                    }
+                };
+                write!(writer, "\x1b[{};1H\x1b[0m\x1b[2K{line}", row + 1)?;
+            }
+            writer.flush()
+        })();
+        // Whatever got written moved the viewport and the pen: keep tracking in step even when
+        // a later line failed.
+        self.viewport = Some(viewport);
+        self.renderer.set_row_offset(viewport.top);
+        self.renderer.reset_state();
+        result
+    }
+
+    /// Moves the inline viewport to `rows` lines tall, on behalf of [`Output::resize`]: clears the
+    /// old rows (the new ones are cleared by the caller) and scrolls the screen up if the
+    /// viewport grew past the bottom.
+    fn resize_viewport(&mut self, rows: u16) -> std::io::Result<()> {
+        let Some(mut viewport) = self.viewport else {
+            return Ok(());
+        };
+        self.renderer.clear_rows(0..viewport.height)?;
+        let scroll = viewport.set_height(rows);
+        if !self.renderer.plain_mode() {
+            scroll_screen(self.renderer.writer_mut(), viewport.screen_height, scroll)?;
+        }
+        self.viewport = Some(viewport);
+        self.renderer.set_row_offset(viewport.top);
+        Ok(())
+    }
+
+    /// Leaves the cursor on the line below the inline viewport with the pen reset, so the last
+    /// frame stays in the scrollback and whatever the shell prints next starts underneath it.
+    fn leave_viewport(&mut self) -> std::io::Result<()> {
+        let Some(viewport) = self.viewport else {
+            return Ok(());
+        };
+        let plain = self.renderer.plain_mode();
+        let writer = self.renderer.writer_mut();
+        if plain {
+            return writer.write_all(b"\n");
+        }
+        let below = viewport.top + viewport.height;
+        if below < viewport.screen_height {
+            write!(writer, "\x1b[0m\x1b[{};1H", below + 1)
+        } else {
+            write!(writer, "\x1b[0m\x1b[{below};1H\r\n")
+        }
+    }
+}
+
 impl<W: std::io::Write> Cursor for Crossterm<W> {
     /// Queues the show/hide escape without flushing; the next [`Output::flush`] call drains it
     /// along with everything else. A caller that hides the cursor and moves it in the same frame
@@ -1202,6 +2148,55 @@ impl<W: std::io::Write> Cursor for Crossterm<W> {
     }
 }
 
+/// The clipboard of the machine the terminal emulator runs on, through OSC 52; see the
+/// crate-level "Clipboard" docs.
+impl<W: std::io::Write> Clipboard for Crossterm<W> {
+    /// Asks the terminal for its clipboard, waiting at most
+    /// [`CrosstermOptions::probe_timeout`] for the answer.
+    ///
+    /// Fails in [plain mode](Self::plain_mode), without raw mode, or when stdin/stdout is not a
+    /// terminal, since the reply could not be read back; and on the many terminals that don't
+    /// share their clipboard with programs at all. Keys typed while the query is in flight are
+    /// lost.
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        use retroglyph_terminal::clipboard::{OSC52_QUERY, Osc52Reply, parse_osc52_reply};
+
+        if self.plain_mode() || !self.options.raw_mode || !std::io::stdout().is_terminal() {
+            return Err(ClipboardError::new(
+                "not attached to an interactive terminal",
+            ));
+        }
+        let reply = query::ask(OSC52_QUERY, self.options.probe_timeout, |reply| {
+            parse_osc52_reply(reply) != Osc52Reply::Pending
+        })
+        .ok_or_else(|| ClipboardError::new("not attached to an interactive terminal"))?;
+        match parse_osc52_reply(&reply) {
+            Osc52Reply::Answered(Some(text)) => Ok(text),
+            Osc52Reply::Answered(None) | Osc52Reply::Pending => Err(ClipboardError::new(
+                "the terminal did not share its clipboard",
+            )),
+        }
+    }
+
+    /// Writes the OSC 52 copy sequence and flushes it, so the copy happens now rather than with
+    /// the next frame.
+    ///
+    /// Fails only in [plain mode](Self::plain_mode) (there is no terminal to ask) or if the
+    /// write fails: whether the terminal honors the sequence can't be known, since it sends
+    /// nothing back either way.
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        if self.plain_mode() {
+            return Err(ClipboardError::new(
+                "not attached to an interactive terminal",
+            ));
+        }
+        let writer = self.renderer.writer_mut();
+        retroglyph_terminal::clipboard::write_osc52(writer, text)
+            .and_then(|()| writer.flush())
+            .map_err(|e| ClipboardError::new(e.to_string()))
+    }
+}
+
 const fn from_crossterm_key_code(
     code: crossterm::event::KeyCode,
 ) -> Option<retroglyph_core::event::KeyCode> {
@@ -1346,7 +2341,8 @@ const fn from_crossterm_mouse_event_kind(
 const fn from_crossterm_mouse_event(
     m: crossterm::event::MouseEvent,
 ) -> retroglyph_core::event::MouseEvent {
-    // Crossterm is a character-mode backend; it has no sub-cell resolution.
+    // Cells, as crossterm reports them; in SGR-Pixels mode these are really pixels, which
+    // `from_pixel_mouse_event` sorts out afterwards.
     retroglyph_core::event::MouseEvent::new(
         from_crossterm_mouse_event_kind(m.kind),
         Pos {
@@ -1357,6 +2353,25 @@ const fn from_crossterm_mouse_event(
     )
 }
 
+/// Reads back a mouse event reported in SGR-Pixels mode, whose column and row crossterm passed
+/// through as pixels: the pixels become its `pixel_position`, and the cell they fall in (cells
+/// being `width` by `height` pixels) its `position`.
+fn from_pixel_mouse_event(
+    mut mouse: retroglyph_core::event::MouseEvent,
+    (width, height): (u16, u16),
+) -> retroglyph_core::event::MouseEvent {
+    let Pos { x, y } = mouse.position;
+    mouse.pixel_position = Some(retroglyph_core::event::PhysicalPos {
+        x: u32::from(x),
+        y: u32::from(y),
+    });
+    mouse.position = Pos {
+        x: x / width.max(1),
+        y: y / height.max(1),
+    };
+    mouse
+}
+
 // Taking ownership matches the call site: `crossterm::event::read()` hands us
 // a freshly-owned `Event` with nothing else holding a reference to it.
 //
@@ -1575,6 +2590,9 @@ mod tests {
         assert!(options.bracketed_paste);
         assert!(options.alt_screen);
         assert!(options.raw_mode);
+        assert!(options.probe_capabilities);
+        // Signal handling changes what the process does on a signal, so it's opt-in.
+        assert!(!options.signals);
     }
 
     #[test]
@@ -1787,6 +2805,132 @@ mod tests {
         assert_eq!(term.poll_event(Duration::ZERO), None);
     }
 
+    #[test]
+    fn set_clipboard_text_writes_osc_52_and_flushes() {
+        let (_lock, mut term) = headless_term();
+        term.set_clipboard_text("seed 42").unwrap();
+        assert_eq!(term.writer().as_slice(), b"\x1b]52;c;c2VlZCA0Mg==\x1b\\");
+    }
+
+    #[test]
+    fn clipboard_text_needs_a_terminal_to_answer() {
+        let (_lock, mut term) = headless_term();
+        assert!(term.clipboard_text().is_err());
+    }
+
+    #[test]
+    fn headless_backends_never_probe_capabilities() {
+        let (_lock, mut term) = headless_term();
+        assert_eq!(term.capabilities(), None);
+        assert!(term.renderer.synchronized_output());
+        assert_eq!(term.poll_event(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn probed_capabilities_set_synchronized_output_and_queue_the_theme() {
+        use retroglyph_core::event::SystemTheme;
+        use retroglyph_terminal::ColorSupport;
+
+        let _lock = TEST_GUARD_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut term = headless_options()
+            .color_support(ColorSupport::Ansi16)
+            .build_with_writer(Vec::new())
+            .unwrap();
+        term.adopt_capabilities(TerminalCapabilities::from_reply(
+            b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1bP1$r0;48;2;1;2;3m\x1b\\\x1b[?62c",
+        ));
+
+        assert!(term.capabilities().is_some_and(|caps| caps.answered));
+        // No DECRQM reply among a complete answer: the terminal doesn't do mode 2026.
+        assert!(!term.renderer.synchronized_output());
+        // An explicit color support level is never overridden by the probe.
+        assert_eq!(term.color_support(), ColorSupport::Ansi16);
+        assert_eq!(
+            term.poll_event(Duration::ZERO),
+            Some(Event::ThemeChanged(SystemTheme::Light))
+        );
+    }
+
+    #[test]
+    fn a_silent_terminal_keeps_synchronized_output_on() {
+        let (_lock, mut term) = headless_term();
+        term.adopt_capabilities(TerminalCapabilities::from_reply(b""));
+        assert!(term.renderer.synchronized_output());
+        assert_eq!(term.poll_event(Duration::ZERO), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signals_arrive_as_events_and_resuming_clears_the_screen() {
+        use signal_hook::consts::{SIGCONT, SIGHUP};
+        use signal_hook::low_level::raise;
+
+        let _lock = TEST_GUARD_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut term = headless_options()
+            .signals(true)
+            .build_with_writer(Vec::new())
+            .unwrap();
+        // The signal thread forwards in the background, and with no terminal to wait on,
+        // `poll_event` returns straight away; so wait here instead.
+        let next_event = |term: &mut Crossterm<Vec<u8>>| {
+            let deadline = std::time::Instant::now() + Duration::from_secs(5);
+            while std::time::Instant::now() < deadline {
+                if let Some(event) = term.poll_event(Duration::ZERO) {
+                    return Some(event);
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            None
+        };
+
+        raise(SIGHUP).unwrap();
+        assert_eq!(next_event(&mut term), Some(Event::HangUp));
+        assert!(term.writer().is_empty());
+
+        // Continuing a process that was never stopped does nothing to it, but is still reported.
+        raise(SIGCONT).unwrap();
+        assert_eq!(next_event(&mut term), Some(Event::Resumed));
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert!(written.contains("\x1b[2J"), "output: {written:?}");
+    }
+
+    #[test]
+    fn an_injected_suspend_does_not_stop_the_process() {
+        let (_lock, mut term) = headless_term();
+        term.push_event(Event::Suspended);
+        assert_eq!(term.poll_event(Duration::ZERO), Some(Event::Suspended));
+        let sender = term.event_sender().unwrap();
+        sender.send(Event::Suspended).unwrap();
+        assert_eq!(term.poll_event(Duration::ZERO), Some(Event::Suspended));
+        assert!(!term.stop_pending);
+        assert_eq!(term.poll_event(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn sent_events_follow_pushed_events_out_of_poll_event() {
+        let _lock = TEST_GUARD_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Ok(mut term) = headless_options().build_with_writer(Vec::new()) else {
+            return;
+        };
+
+        let sender = term.event_sender().expect("crossterm supports senders");
+        std::thread::spawn(move || sender.send(Event::Custom(2)))
+            .join()
+            .unwrap()
+            .unwrap();
+        term.push_event(Event::Custom(1));
+
+        assert_eq!(term.poll_event(Duration::ZERO), Some(Event::Custom(1)));
+        assert_eq!(term.poll_event(Duration::ZERO), Some(Event::Custom(2)));
+        assert_eq!(term.poll_event(Duration::ZERO), None);
+    }
+
     #[test]
     fn build_with_writer_renders_cell_content_into_a_custom_sink() {
         // The whole point of a generic content writer: draw/flush output lands in `writer`
@@ -2072,6 +3216,311 @@ mod tests {
         );
     }
 
+    /// A [`headless_term`] whose inline viewport sits at `top`, `height` rows tall, on a screen
+    /// `screen_height` rows tall, with the construction-time output already discarded.
+    fn headless_inline_term(
+        top: u16,
+        height: u16,
+        screen_height: u16,
+    ) -> (std::sync::MutexGuard<'static, ()>, Crossterm<Vec<u8>>) {
+        let (lock, mut term) = headless_term();
+        term.viewport = Some(Viewport {
+            top,
+            height,
+            requested: height,
+            screen_height,
+        });
+        term.renderer.set_row_offset(top);
+        term.cached_size = Size::new(10, height);
+        term.writer_mut().clear();
+        (lock, term)
+    }
+
+    #[test]
+    fn inline_viewport_reserves_rows_below_the_cursor() {
+        // Plenty of room: starts on the cursor's own line.
+        let (viewport, scroll) = Viewport::reserve(5, 24, Some((0, 3)));
+        assert_eq!((viewport.top, viewport.height, scroll), (3, 5, 0));
+        // A cursor mid-line moves to the next line first.
+        let (viewport, scroll) = Viewport::reserve(5, 24, Some((7, 3)));
+        assert_eq!((viewport.top, scroll), (4, 0));
+        // Too close to the bottom: the screen scrolls up just enough.
+        let (viewport, scroll) = Viewport::reserve(5, 24, Some((0, 22)));
+        assert_eq!((viewport.top, scroll), (19, 3));
+        // Taller than the screen: capped, but the request is remembered.
+        let (viewport, scroll) = Viewport::reserve(40, 24, None);
+        assert_eq!(
+            (viewport.top, viewport.height, viewport.requested, scroll),
+            (0, 24, 40, 0)
+        );
+    }
+
+    #[test]
+    fn inline_viewport_stays_anchored_across_screen_resizes() {
+        let (mut viewport, _) = Viewport::reserve(5, 24, Some((0, 10)));
+        viewport.fit_screen(30);
+        assert_eq!((viewport.top, viewport.height), (10, 5));
+        viewport.fit_screen(12);
+        assert_eq!((viewport.top, viewport.height), (7, 5));
+        viewport.fit_screen(3);
+        assert_eq!((viewport.top, viewport.height), (0, 3));
+        viewport.fit_screen(24);
+        assert_eq!((viewport.top, viewport.height), (0, 5));
+    }
+
+    #[test]
+    fn inline_build_reports_the_viewport_as_its_size() {
+        let _lock = TEST_GUARD_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let term = headless_options()
+            .inline(3)
+            .build_with_writer(Vec::new())
+            .unwrap();
+        assert_eq!(term.size().height(), 3);
+        assert_eq!(term.viewport().map(|area| area.height()), Some(3));
+    }
+
+    #[test]
+    fn inline_draws_and_clears_are_offset_to_the_viewport() {
+        let (_lock, mut term) = headless_inline_term(6, 2, 24);
+        let tile = Tile::new('X', retroglyph_core::color::Style::default());
+
+        term.draw(core::iter::once(DrawCell::new(Pos { x: 1, y: 1 }, &tile)))
+            .unwrap();
+        // Row 2 is outside a 2-row viewport and must not spill onto the screen below it.
+        term.draw(core::iter::once(DrawCell::new(Pos { x: 0, y: 2 }, &tile)))
+            .unwrap();
+        term.clear().unwrap();
+
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert!(written.contains("\x1b[8;2H"), "output: {written:?}");
+        assert_eq!(written.matches('X').count(), 1, "output: {written:?}");
+        assert!(
+            written.ends_with("\x1b[0m\x1b[7;1H\x1b[2K\x1b[8;1H\x1b[2K"),
+            "output: {written:?}"
+        );
+    }
+
+    #[test]
+    fn insert_before_shifts_the_viewport_down_then_scrolls_lines_above_it() {
+        let (_lock, mut term) = headless_inline_term(1, 2, 4);
+
+        term.insert_before("first\nsecond").unwrap();
+
+        // One free row below the viewport: the first line takes the viewport's old top row and
+        // the viewport moves down into the free row. The second line finds the viewport at the
+        // bottom and scrolls the single row above it instead.
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert_eq!(
+            written,
+            "\x1b[2;4r\x1b[1T\x1b[r\x1b[2;1H\x1b[0m\x1b[2Kfirst\
+             \x1b[1;2r\x1b[2;1H\n\x1b[r\x1b[2;1H\x1b[0m\x1b[2Ksecond"
+        );
+        assert_eq!(term.viewport(), Some(Rect::new(0, 2, 10, 2)));
+        assert_eq!(term.renderer.row_offset(), 2);
+    }
+
+    #[test]
+    fn insert_before_cuts_lines_at_the_screen_width() {
+        let (_lock, mut term) = headless_inline_term(1, 2, 24);
+        term.insert_before("0123456789abcdef").unwrap();
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert!(
+            written.ends_with("\x1b[2K0123456789"),
+            "output: {written:?}"
+        );
+    }
+
+    #[test]
+    fn insert_before_needs_an_inline_viewport_with_room_above() {
+        let (lock, mut term) = headless_term();
+        let err = term.insert_before("line").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        drop((lock, term));
+
+        let (_lock, mut term) = headless_inline_term(0, 4, 4);
+        let err = term.insert_before("line").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn resizing_an_inline_terminal_grows_the_viewport_and_scrolls_to_fit() {
+        let (_lock, mut term) = headless_inline_term(20, 3, 24);
+
+        Output::resize(&mut term, Size::new(10, 6));
+
+        assert_eq!(term.size(), Size::new(10, 6));
+        assert_eq!(term.viewport(), Some(Rect::new(0, 18, 10, 6)));
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert!(written.contains("\x1b[24;1H\n\n"), "output: {written:?}");
+    }
+
+    #[test]
+    fn inline_resize_and_mouse_events_are_reported_relative_to_the_viewport() {
+        let (_lock, mut term) = headless_inline_term(10, 5, 24);
+
+        assert_eq!(
+            term.admit_crossterm_event(crossterm::event::Event::Resize(100, 12)),
+            Some(Event::Resize(100, 5))
+        );
+        assert_eq!(term.viewport(), Some(Rect::new(0, 7, 100, 5)));
+
+        let click = crossterm::event::Event::Mouse(crossterm::event::MouseEvent {
+            kind: crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            column: 4,
+            row: 9,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        });
+        match term.admit_crossterm_event(click) {
+            Some(Event::Mouse(mouse)) => assert_eq!(mouse.position, Pos { x: 4, y: 2 }),
+            other => panic!("expected a mouse event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inline_mouse_events_outside_the_viewport_are_dropped() {
+        let (_lock, mut term) = headless_inline_term(10, 5, 24);
+        let click = |row| {
+            crossterm::event::Event::Mouse(crossterm::event::MouseEvent {
+                kind: crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left),
+                column: 4,
+                row,
+                modifiers: crossterm::event::KeyModifiers::NONE,
+            })
+        };
+
+        // The viewport covers rows 10 through 14: the shell's output above it and the screen
+        // below it are not the app's.
+        assert_eq!(term.admit_crossterm_event(click(0)), None);
+        assert_eq!(term.admit_crossterm_event(click(9)), None);
+        assert_eq!(term.admit_crossterm_event(click(15)), None);
+        assert_eq!(term.admit_crossterm_event(click(23)), None);
+        for (row, y) in [(10, 0), (14, 4)] {
+            match term.admit_crossterm_event(click(row)) {
+                Some(Event::Mouse(mouse)) => assert_eq!(mouse.position, Pos { x: 4, y }),
+                other => panic!("expected a mouse event on row {row}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn pixel_mouse_events_report_both_the_cell_and_the_pixel() {
+        let (_lock, mut term) = headless_term();
+        term.pixel_mouse = Some((10, 20));
+        let click = crossterm::event::Event::Mouse(crossterm::event::MouseEvent {
+            kind: crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            column: 45,
+            row: 61,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        });
+        match term.admit_crossterm_event(click) {
+            Some(Event::Mouse(mouse)) => {
+                assert_eq!(mouse.position, Pos { x: 4, y: 3 });
+                assert_eq!(
+                    mouse.pixel_position,
+                    Some(retroglyph_core::event::PhysicalPos { x: 45, y: 61 })
+                );
+            }
+            other => panic!("expected a mouse event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inline_pixel_mouse_events_are_relative_to_the_viewport_too() {
+        let (_lock, mut term) = headless_inline_term(10, 5, 24);
+        term.pixel_mouse = Some((8, 16));
+        let top = term.viewport.unwrap().top;
+        let click = crossterm::event::Event::Mouse(crossterm::event::MouseEvent {
+            kind: crossterm::event::MouseEventKind::Moved,
+            column: 12,
+            row: (top + 2) * 16 + 5,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        });
+        match term.admit_crossterm_event(click) {
+            Some(Event::Mouse(mouse)) => {
+                assert_eq!(mouse.position, Pos { x: 1, y: 2 });
+                assert_eq!(
+                    mouse.pixel_position,
+                    Some(retroglyph_core::event::PhysicalPos { x: 12, y: 37 })
+                );
+            }
+            other => panic!("expected a mouse event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pixel_mouse_stays_off_without_mouse_capture() {
+        let (_lock, mut term) = headless_term();
+        term.adopt_capabilities(TerminalCapabilities::from_reply(
+            b"\x1b[?1016;2$y\x1b[6;16;8t\x1b[?62c",
+        ));
+        assert_eq!(term.pixel_mouse, None);
+    }
+
+    #[test]
+    fn leaving_an_inline_viewport_parks_the_cursor_below_the_last_frame() {
+        {
+            let (_lock, mut term) = headless_inline_term(3, 2, 24);
+            term.leave_viewport().unwrap();
+            assert_eq!(term.writer().as_slice(), b"\x1b[0m\x1b[6;1H");
+        }
+        let (_lock, mut term) = headless_inline_term(22, 2, 24);
+        term.leave_viewport().unwrap();
+        assert_eq!(term.writer().as_slice(), b"\x1b[0m\x1b[24;1H\r\n");
+    }
+
+    #[cfg(feature = "tilesets")]
+    #[test]
+    fn tilesets_fall_back_to_glyphs_when_the_terminal_is_not_asked() {
+        let (_lock, mut term) = headless_term();
+        // Raw mode is off, so the capability query's reply could never be read.
+        assert_eq!(
+            term.load_tilesets(&[graphics::test_tileset()]).unwrap(),
+            None
+        );
+        assert_eq!(term.graphics_protocol(), None);
+
+        let tile = Tile::new('a', retroglyph_core::color::Style::default());
+        term.draw(core::iter::once(DrawCell::new(Pos { x: 0, y: 0 }, &tile)))
+            .unwrap();
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        assert!(written.contains('a'), "output: {written:?}");
+        assert!(!written.contains("\x1b_G"), "output: {written:?}");
+    }
+
+    #[cfg(feature = "tilesets")]
+    #[test]
+    fn tileset_sprites_draw_as_kitty_images_over_blank_cells() {
+        let (_lock, mut term) = headless_term();
+        let size = term.size();
+        term.set_graphics(Some(graphics::SpriteGraphics::new(
+            retroglyph_terminal::graphics::GraphicsProtocol::Kitty,
+            graphics::test_sprites(),
+            None,
+            size,
+        )));
+
+        let sprite = Tile::new('a', retroglyph_core::color::Style::default());
+        let text = Tile::new('x', retroglyph_core::color::Style::default());
+        term.draw(
+            [
+                DrawCell::new(Pos { x: 0, y: 0 }, &sprite),
+                DrawCell::new(Pos { x: 1, y: 0 }, &text),
+            ]
+            .into_iter(),
+        )
+        .unwrap();
+
+        let written = String::from_utf8(term.writer().clone()).unwrap();
+        let (cells, images) = written.split_once("\x1b_G").unwrap();
+        assert!(cells.ends_with(" x"), "output: {written:?}");
+        assert!(
+            images.contains("\x1b[1;1H\x1b_Ga=p,i=1,p=1,c=1,r=1,C=1,q=2"),
+            "output: {written:?}"
+        );
+    }
+
     #[test]
     fn resize_event_refreshes_the_cached_size() {
         // `poll_event` refreshes the cached size in lockstep with any `Event::Resize` it reads
@@ -2194,12 +3643,12 @@ mod tests {
         use retroglyph_terminal::ColorSupport;
 
         assert_eq!(
-            detect_color_support(Some("1"), Some("dumb")),
+            detect_color_support(Some("1"), Some("dumb"), None),
             ColorSupport::None
         );
         // Any non-empty value counts, per https://no-color.org.
         assert_eq!(
-            detect_color_support(Some("anything"), None),
+            detect_color_support(Some("anything"), None, None),
             ColorSupport::None
         );
     }
@@ -2209,7 +3658,7 @@ mod tests {
         use retroglyph_terminal::ColorSupport;
 
         assert_eq!(
-            detect_color_support(Some(""), None),
+            detect_color_support(Some(""), None, None),
             ColorSupport::Truecolor
         );
     }
@@ -2221,7 +3670,19 @@ mod tests {
         // test).
         use retroglyph_terminal::ColorSupport;
 
-        assert_eq!(detect_color_support(None, Some("dumb")), ColorSupport::None);
+        assert_eq!(
+            detect_color_support(None, Some("dumb"), None),
+            ColorSupport::None
+        );
+    }
+
+    #[test]
+    fn the_capability_probe_skips_dumb_terminals_and_the_linux_console() {
+        assert!(!term_worth_probing(Some("dumb")));
+        assert!(!term_worth_probing(Some("linux")));
+        assert!(term_worth_probing(Some("xterm-256color")));
+        assert!(term_worth_probing(Some("")));
+        assert!(term_worth_probing(None));
     }
 
     #[test]
@@ -2232,9 +3693,12 @@ mod tests {
         // crate's pre-existing always-truecolor behavior.
         use retroglyph_terminal::ColorSupport;
 
-        assert_eq!(detect_color_support(None, None), ColorSupport::Truecolor);
         assert_eq!(
-            detect_color_support(None, Some("xterm")),
+            detect_color_support(None, None, None),
+            ColorSupport::Truecolor
+        );
+        assert_eq!(
+            detect_color_support(None, Some("xterm"), None),
             ColorSupport::Truecolor
         );
         // retroglyph#585 CI: this workspace's own PTY test harness spawns every example with
@@ -2242,11 +3706,30 @@ mod tests {
         // a limit (rather than the common, often truecolor-capable default it is) silently
         // downgraded every snapshot test's rendered colors and broke all of them at once.
         assert_eq!(
-            detect_color_support(None, Some("xterm-256color")),
+            detect_color_support(None, Some("xterm-256color"), None),
             ColorSupport::Truecolor
         );
     }
 
+    #[test]
+    fn detect_color_support_uses_the_probed_level_unless_the_environment_forces_none() {
+        use retroglyph_terminal::ColorSupport;
+
+        let probed = Some(ColorSupport::Indexed256);
+        assert_eq!(
+            detect_color_support(None, Some("xterm-256color"), probed),
+            ColorSupport::Indexed256
+        );
+        assert_eq!(
+            detect_color_support(Some("1"), None, probed),
+            ColorSupport::None
+        );
+        assert_eq!(
+            detect_color_support(None, Some("dumb"), probed),
+            ColorSupport::None
+        );
+    }
+
     #[test]
     fn crossterm_options_color_support_override_is_used_verbatim() {
         use retroglyph_terminal::ColorSupport;
@@ -0,0 +1,99 @@
+//! Questions asked of the terminal itself: a query written to stdout, and the reply read back
+//! off stdin with a timeout. The startup capability probe is one; reading the clipboard through
+//! OSC 52 is the other.
+//!
+//! Parsing lives in [`retroglyph_terminal`]; this module only owns the TTY side, which needs raw
+//! mode and a real file descriptor.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use core::time::Duration;
+use retroglyph_terminal::capabilities::{self, TerminalCapabilities};
+
+/// How long [`probe`] waits for the terminal to finish answering by default; see
+/// [`CrosstermOptions::probe_timeout`](crate::CrosstermOptions::probe_timeout).
+///
+/// Practically every terminal answers the device attributes query that ends the batch within a
+/// round-trip, so this only runs out on one that answers nothing at all (or over a very slow
+/// link), where the conservative defaults are the right call anyway.
+pub(crate) const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Asks the terminal on stdin/stdout what it supports, waiting up to `timeout` for the answer.
+///
+/// Returns whatever had arrived when the terminal finished answering or the timeout ran out
+/// (check [`TerminalCapabilities::answered`]), or [`TerminalCapabilities::default`] when stdin is
+/// not a terminal.
+pub(crate) fn probe(timeout: Duration) -> TerminalCapabilities {
+    let reply = ask(capabilities::QUERY, timeout, |reply| {
+        TerminalCapabilities::from_reply(reply).answered
+    });
+    reply.map_or_else(TerminalCapabilities::default, |reply| {
+        TerminalCapabilities::from_reply(&reply)
+    })
+}
+
+/// Writes `query` to stdout and collects what comes back on stdin until `done` says the reply
+/// is complete or `timeout` runs out, whichever is first.
+///
+/// `None` when stdin is not a terminal or the query could not be written; otherwise whatever
+/// arrived, complete or not. Must run in raw mode: in cooked mode the reply would sit in the line
+/// buffer (and be echoed) instead of arriving here. Reads the file descriptor directly, not
+/// through crossterm's event reader, which would swallow device attributes replies and turn the
+/// rest into key presses.
+#[cfg(unix)]
+pub(crate) fn ask(
+    query: &str,
+    timeout: Duration,
+    mut done: impl FnMut(&[u8]) -> bool,
+) -> Option<Vec<u8>> {
+    use rustix::event::{PollFd, PollFlags, Timespec};
+    use std::io::{self, IsTerminal, Write};
+    use std::os::fd::AsFd;
+
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return None;
+    }
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(query.as_bytes())
+        .and_then(|()| stdout.flush())
+        .ok()?;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut reply = Vec::new();
+    let mut buf = [0; 256];
+    loop {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        let Ok(left) = Timespec::try_from(left) else {
+            break;
+        };
+        let mut fds = [PollFd::new(&stdin, PollFlags::IN)];
+        match rustix::event::poll(&mut fds, Some(&left)) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(rustix::io::Errno::INTR) => continue,
+            Err(_) => break,
+        }
+        match rustix::io::read(stdin.as_fd(), &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => reply.extend_from_slice(&buf[..read]),
+        }
+        if done(&reply) {
+            break;
+        }
+    }
+    Some(reply)
+}
+
+/// Non-unix terminals are never asked anything.
+#[cfg(not(unix))]
+pub(crate) fn ask(
+    _query: &str,
+    _timeout: Duration,
+    _done: impl FnMut(&[u8]) -> bool,
+) -> Option<Vec<u8>> {
+    None
+}
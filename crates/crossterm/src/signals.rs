@@ -0,0 +1,122 @@
+//! Opt-in handling of the signals a terminal app gets from its shell and its session (see
+//! [`CrosstermOptions::signals`](crate::CrosstermOptions::signals)): each becomes an [`Event`]
+//! sent through the backend's inbox instead of running its default action.
+//!
+//! The handlers are registered once per process and never removed, because `signal-hook` cannot
+//! put a signal's default action back: unregistering `SIGTERM` would leave the process ignoring
+//! it. A signal that arrives while no backend is listening runs its default action by hand
+//! instead.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_core::backend::EventSender;
+
+#[cfg(unix)]
+use retroglyph_core::event::Event;
+#[cfg(unix)]
+use signal_hook::consts::{SIGCONT, SIGHUP, SIGINT, SIGTERM, SIGTSTP};
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::sync::{Mutex, PoisonError};
+
+/// Where signals are sent while a backend with signal handling is live.
+#[cfg(unix)]
+static TARGET: Mutex<Option<EventSender>> = Mutex::new(None);
+
+/// Set when `SIGTSTP` arrives, and taken by the backend once it hands out the matching
+/// [`Event::Suspended`]: that event, and not one some other sender made up, is the one the
+/// process actually stops after.
+#[cfg(unix)]
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Sends every handled signal to `sender` from now on, registering the handlers (and starting the
+/// thread that forwards them) the first time.
+#[cfg(unix)]
+pub(crate) fn listen(sender: EventSender) -> std::io::Result<()> {
+    static STARTED: Mutex<bool> = Mutex::new(false);
+
+    let mut started = STARTED.lock().unwrap_or_else(PoisonError::into_inner);
+    if !*started {
+        let signals =
+            signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP, SIGTSTP, SIGCONT])?;
+        std::thread::Builder::new()
+            .name("retroglyph-signals".into())
+            .spawn(move || forward(signals))?;
+        *started = true;
+    }
+    drop(started);
+    *TARGET.lock().unwrap_or_else(PoisonError::into_inner) = Some(sender);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+// Keeps the unix signature so callers don't need a cfg of their own.
+#[allow(clippy::unnecessary_wraps, clippy::missing_const_for_fn)]
+pub(crate) fn listen(_sender: EventSender) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Stops sending signals anywhere: they run their default actions again.
+// Only does anything on unix, where it can't be `const`.
+#[cfg_attr(not(unix), allow(clippy::missing_const_for_fn))]
+pub(crate) fn release() {
+    #[cfg(unix)]
+    {
+        *TARGET.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        STOP_REQUESTED.store(false, Ordering::Release);
+    }
+}
+
+/// Whether a `SIGTSTP` is waiting for the process to stop, clearing the request.
+// Only does anything on unix, where it can't be `const`.
+#[cfg_attr(not(unix), allow(clippy::missing_const_for_fn))]
+pub(crate) fn take_stop_request() -> bool {
+    #[cfg(unix)]
+    return STOP_REQUESTED.swap(false, Ordering::AcqRel);
+    #[cfg(not(unix))]
+    false
+}
+
+/// Stops the process the way `SIGTSTP`'s default action would, returning once it is continued.
+#[cfg(unix)]
+pub(crate) fn stop() -> std::io::Result<()> {
+    // `SIGSTOP` rather than `SIGTSTP`, which would only come straight back to the handler.
+    signal_hook::low_level::raise(signal_hook::consts::SIGSTOP)
+}
+
+#[cfg(not(unix))]
+// Keeps the unix signature so callers don't need a cfg of their own.
+#[allow(clippy::unnecessary_wraps, clippy::missing_const_for_fn)]
+pub(crate) fn stop() -> std::io::Result<()> {
+    Ok(())
+}
+
+/// The signal thread: turns each signal into its event for [`TARGET`], or runs the signal's
+/// default action when nothing takes the event.
+#[cfg(unix)]
+fn forward(mut signals: signal_hook::iterator::Signals) {
+    for signal in signals.forever() {
+        let event = match signal {
+            SIGINT => Event::Interrupt,
+            SIGTERM => Event::Terminate,
+            SIGHUP => Event::HangUp,
+            SIGTSTP => {
+                STOP_REQUESTED.store(true, Ordering::Release);
+                Event::Suspended
+            }
+            _ => Event::Resumed,
+        };
+        let sent = TARGET
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|target| target.send(event).is_ok());
+        if !sent {
+            STOP_REQUESTED.store(false, Ordering::Release);
+            let _ = signal_hook::low_level::emulate_default_handler(signal);
+        }
+    }
+}
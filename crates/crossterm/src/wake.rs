@@ -0,0 +1,103 @@
+//! What lets a blocked [`Input::poll_event`](retroglyph_core::backend::Input::poll_event) return
+//! the moment another thread sends an event, instead of noticing on its next poll.
+//!
+//! `crossterm::event::poll` can only wait on the terminal, so once a sender exists the backend
+//! waits itself: on the terminal's file descriptor and a
+//! [`WakePipe`](retroglyph_terminal::wake::WakePipe) that the inbox's notify hook writes to, then
+//! lets crossterm read whatever turned up. `SIGWINCH` writes to the same pipe, since crossterm
+//! only notices a resize from inside its own `poll`.
+//!
+//! An async task parked in `Input::poll_next_event` (without the `event-stream` feature) has no
+//! thread to block, so `TaskWake` hands the same wait to an
+//! [`InputWatcher`](retroglyph_terminal::wake::InputWatcher) instead.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_core::backend::EventInbox;
+#[cfg(not(feature = "event-stream"))]
+use retroglyph_terminal::wake::InputWatcher;
+use retroglyph_terminal::wake::WakePipe;
+use std::io::{self, IsTerminal};
+use std::os::fd::{AsFd, OwnedFd};
+#[cfg(not(feature = "event-stream"))]
+use std::task::Waker;
+use std::time::Duration;
+
+/// A descriptor for what crossterm reads input from: stdin, or `/dev/tty` when stdin is
+/// redirected.
+fn terminal_input() -> io::Result<OwnedFd> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        stdin.as_fd().try_clone_to_owned()
+    } else {
+        Ok(std::fs::File::open("/dev/tty")?.into())
+    }
+}
+
+/// The terminal and the pipe that cuts a wait on it short.
+pub(crate) struct SenderWake {
+    pipe: WakePipe,
+    /// See [`terminal_input`].
+    tty: OwnedFd,
+    resize: signal_hook::SigId,
+}
+
+impl SenderWake {
+    /// Creates the pipe and hooks it up to `inbox`'s sends and to `SIGWINCH`.
+    pub(crate) fn new(inbox: &EventInbox) -> io::Result<Self> {
+        let pipe = WakePipe::new()?;
+        let tty = terminal_input()?;
+        let resize =
+            signal_hook::low_level::pipe::register(signal_hook::consts::SIGWINCH, pipe.writer()?)?;
+        let waker = pipe.waker();
+        inbox.set_notify(move || waker.wake());
+        Ok(Self { pipe, tty, resize })
+    }
+
+    /// Blocks until the terminal has input, an event is sent, the window is resized, or
+    /// `timeout` runs out.
+    pub(crate) fn wait(&self, timeout: Duration) -> io::Result<()> {
+        self.pipe.wait(&self.tty, Some(timeout))
+    }
+}
+
+impl Drop for SenderWake {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.resize);
+    }
+}
+
+/// Wakes a task parked in `Input::poll_next_event` once the terminal has input or the window is
+/// resized. Sent events wake it through the inbox's own waker.
+#[cfg(not(feature = "event-stream"))]
+pub(crate) struct TaskWake {
+    watcher: InputWatcher,
+    resize: signal_hook::SigId,
+}
+
+#[cfg(not(feature = "event-stream"))]
+impl TaskWake {
+    /// Starts watching the terminal, and hooks `SIGWINCH` up to the watcher.
+    pub(crate) fn new() -> io::Result<Self> {
+        let watcher = InputWatcher::new(terminal_input()?)?;
+        let resize = signal_hook::low_level::pipe::register(
+            signal_hook::consts::SIGWINCH,
+            watcher.writer()?,
+        )?;
+        Ok(Self { watcher, resize })
+    }
+
+    /// Wakes `waker` once there is something for crossterm to read.
+    pub(crate) fn register(&self, waker: &Waker) {
+        self.watcher.register(waker);
+    }
+}
+
+#[cfg(not(feature = "event-stream"))]
+impl Drop for TaskWake {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.resize);
+    }
+}
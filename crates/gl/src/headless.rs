@@ -218,10 +218,17 @@ fn render_to_frame(ctx: &HeadlessContext, renderer: &GlRenderer) -> Result<Frame
         }
 
         // `build_resources` set the viewport/projection; clear once, then composite every layer
-        // back-to-front (upload + two instanced passes each) into the bound framebuffer, the same
-        // loop the windowed `present` runs.
-        res.clear(gl);
-        crate::draw_all_layers(&renderer.layers, gl, &mut res, renderer.cell_count() as i32);
+        // back-to-front (upload + two instanced passes each), through the CRT pass when it's on,
+        // into the bound framebuffer: the same frame the windowed `present` draws.
+        crate::draw_frame(
+            &renderer.layers,
+            gl,
+            &mut res,
+            renderer.cell_count() as i32,
+            &renderer.presentation(),
+            Some(framebuffer),
+        )
+        .map_err(|e| format!("draw frame: {e}"))?;
         gl.finish();
 
         let mut buf = vec![0u8; (w * h * 4) as usize];
@@ -304,17 +311,18 @@ fn paint_layers(out: &mut impl Output, cells: &[(u8, Pos, Tile)]) {
         .ok();
 }
 
-/// [`paint_layers`] with `tint` applied to every cell.
+/// [`paint_layers`] with every cell passed through `f` first, to set its tint, transform, or
+/// remap.
 #[cfg(feature = "tilesets")]
-fn paint_layers_tinted(
+fn paint_layers_with<'a>(
     out: &mut impl Output,
-    cells: &[(u8, Pos, Tile)],
-    tint: retroglyph_core::color::Tint,
+    cells: &'a [(u8, Pos, Tile)],
+    f: impl Fn(DrawCell<'a>) -> DrawCell<'a>,
 ) {
     out.draw_layers(
         cells
             .iter()
-            .map(|(l, p, t)| DrawCell::on_layer(*l, *p, t).with_tint(tint)),
+            .map(|(l, p, t)| f(DrawCell::on_layer(*l, *p, t))),
     )
     .ok();
 }
@@ -447,12 +455,13 @@ fn glyph_matches_font_coverage_fg_vs_bg() {
     let glyph = r.glyphs.fonts().resolve('A').expect("'A' is in CP437");
     let gw = u32::from(glyph.font().glyph_width());
     let gh = u32::from(glyph.font().glyph_height());
-    let rows = glyph.rows();
+    // Decoded through the font, the same as the atlas builder, rather than re-deriving the row
+    // layout here.
+    let set: Vec<(u8, u8)> = glyph.font().glyph_pixels(glyph.index()).collect();
     for y in 0..gh {
-        let mask = rows[y as usize];
         for x in 0..gw {
-            // Bit 7 (MSB) is the leftmost pixel, matching the atlas builder.
-            let set = (mask >> (7 - x)) & 1 == 1;
+            #[allow(clippy::cast_possible_truncation)]
+            let set = set.contains(&(x as u8, y as u8));
             let expected = if set { RED } else { BLUE };
             assert_eq!(frame.rgb(x, y), expected, "glyph pixel ({x},{y})");
         }
@@ -515,6 +524,191 @@ fn matches_software_backend_with_layers_pixel_for_pixel() {
     assert_frames_match(&frame, sw_pixels);
 }
 
+/// The CRT pass runs on the scene the layers composited into, so it's checked against the CPU
+/// reference's [`output_pixels`](retroglyph_software::SoftwareRenderer::output_pixels). The two
+/// evaluate the same `f32` formula, but a GPU may fuse or reorder its multiply-adds, so channels
+/// may round one or two steps apart, and a sample landing right on a pixel boundary may floor to
+/// the neighboring pixel.
+#[test]
+fn crt_effects_match_the_software_reference_within_tolerance() {
+    use retroglyph_window::crt::CrtEffects;
+    let Some(ctx) = context_or_skip("crt_effects_match_the_software_reference") else {
+        return;
+    };
+
+    let (cols, rows, scale) = (8u16, 5u16, 2u16);
+    let cells = sample_grid(cols, rows);
+    let effects = CrtEffects::CLASSIC;
+
+    let mut gl = gl_renderer(cols, rows, scale);
+    gl.set_crt(effects);
+    paint(&mut gl, &cells);
+    let frame = render_to_frame(&ctx, &gl).expect("render");
+
+    let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .grid_size(cols, rows)
+        .scale(scale)
+        .crt(effects)
+        .build()
+        .expect("default-font builds")
+        .into_renderer()
+        .expect("headless software renderer");
+    paint(&mut sw, &cells);
+    assert_ne!(
+        sw.output_pixels(),
+        sw.pixels(),
+        "the effects changed nothing"
+    );
+    assert_frames_close(&frame, sw.output_pixels(), 2, 0.01);
+
+    // Toggled off at runtime, the pass is skipped and the frame is exact again.
+    gl.set_crt(CrtEffects::NONE);
+    let frame = render_to_frame(&ctx, &gl).expect("render");
+    assert_frames_match(&frame, sw.pixels());
+}
+
+#[test]
+fn integer_scaling_doubles_the_frame_between_bars() {
+    use retroglyph_window::presenter::Presenter as _;
+    use retroglyph_window::scaling::ScalingMode;
+    let Some(ctx) = context_or_skip("integer_scaling_doubles_the_frame_between_bars") else {
+        return;
+    };
+
+    let (cols, rows) = (4u16, 3u16);
+    let cells = sample_grid(cols, rows);
+    let bars = (0x20, 0x40, 0x60);
+    let mut gl = GlBackendBuilder::new()
+        .grid_size(cols, rows)
+        .scaling(ScalingMode::Integer { bars })
+        .build()
+        .expect("default-font builds a renderer");
+    paint(&mut gl, &cells);
+    let (frame_w, frame_h) = gl.geometry.surface_size(cols, rows);
+    // Room for 2x but not 3x on either axis, with an odd margin left over on each.
+    gl.resize_surface(frame_w * 2 + 7, frame_h * 2 + 5);
+    let frame = render_to_frame(&ctx, &gl).expect("render");
+    let viewport = gl
+        .geometry()
+        .viewport()
+        .expect("a scaling mode reports its viewport");
+    assert_eq!(viewport.size, (frame_w * 2, frame_h * 2));
+
+    let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .grid_size(cols, rows)
+        .build()
+        .expect("default-font builds")
+        .into_renderer()
+        .expect("headless software renderer");
+    paint(&mut sw, &cells);
+    let software = sw.pixels();
+
+    let (left, top) = viewport.origin;
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let inside = (left..left + viewport.size.0).contains(&x)
+                && (top..top + viewport.size.1).contains(&y);
+            let expected = if inside {
+                let px = software[(((y - top) / 2) * frame_w + (x - left) / 2) as usize];
+                ((px >> 16) as u8, (px >> 8) as u8, px as u8)
+            } else {
+                bars
+            };
+            assert_eq!(frame.rgb(x, y), expected, "pixel ({x}, {y})");
+        }
+    }
+}
+
+/// Two 16x16 double-width glyphs: `'語'` fills the top half of its left cell and the bottom half
+/// of its right one, `'字'` a checkerboard of 2x2 blocks, so a half landing in the wrong cell (or
+/// the wrong way round) shows up as a pixel difference.
+static DOUBLE_WIDTH_DATA: [u8; 64] = {
+    let mut data = [0; 64];
+    let mut row = 0;
+    while row < 16 {
+        let (left, right) = if row < 8 { (0xFF, 0) } else { (0, 0xFF) };
+        data[row * 2] = left;
+        data[row * 2 + 1] = right;
+        let checker = if row % 4 < 2 {
+            0b1100_1100
+        } else {
+            0b0011_0011
+        };
+        data[32 + row * 2] = checker;
+        data[32 + row * 2 + 1] = checker;
+        row += 1;
+    }
+    data
+};
+
+/// The default font plus [`DOUBLE_WIDTH_DATA`] as its double-width font.
+fn double_width_fonts() -> retroglyph_window::font::FontChain<'static> {
+    use retroglyph_window::font::{BitmapFont, FontChain, unscii16};
+    const CHARSET: [char; 2] = ['語', '字'];
+    FontChain::from(unscii16::FONT).with_wide_font(BitmapFont::with_charset(
+        &DOUBLE_WIDTH_DATA,
+        16,
+        16,
+        2,
+        &CHARSET,
+    ))
+}
+
+/// A wide character's double-width glyph is drawn across its cell and its spacer, the same as
+/// the CPU rasterizer draws it: on layer 0, on a higher layer with a sub-cell offset that spills
+/// the right half past the spacer, and next to a wide character no font covers, which keeps the
+/// single-cell notdef path. The scene comes straight off a [`Grid`](retroglyph_core::grid::Grid),
+/// which is what writes the lead/spacer pairs.
+#[test]
+fn matches_software_backend_for_double_width_glyphs() {
+    use retroglyph_core::grid::Grid;
+
+    let Some(ctx) = context_or_skip("matches_software_backend_for_double_width_glyphs") else {
+        return;
+    };
+
+    let (cols, rows, scale) = (6u16, 2u16, 2u16);
+    let style = Style::new()
+        .fg(Color::rgb(GREEN.0, GREEN.1, GREEN.2))
+        .bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+    let mut grid = Grid::new(cols, rows);
+    grid.put_tile(0, (0, 0), Tile::new('語', style));
+    grid.put_tile(0, (2, 0), Tile::new('字', style));
+    grid.put_tile(0, (4, 0), Tile::new('あ', style));
+    grid.put_tile(1, (1, 1), Tile::new('語', style).with_offset(3, -2));
+    let scene: Vec<(u8, Pos, Tile)> = grid
+        .layers()
+        .map(|cell| (cell.layer, cell.pos, *cell.tile))
+        .collect();
+
+    let mut gl = GlBackendBuilder::new()
+        .font(double_width_fonts())
+        .grid_size(cols, rows)
+        .scale(scale)
+        .build()
+        .expect("chain builds");
+    paint_layers(&mut gl, &scene);
+    let frame = render_to_frame(&ctx, &gl).expect("render");
+
+    let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .font(double_width_fonts())
+        .grid_size(cols, rows)
+        .scale(scale)
+        .build()
+        .expect("chain builds")
+        .into_renderer()
+        .expect("headless software renderer");
+    paint_layers(&mut sw, &scene);
+
+    assert_frames_match(&frame, sw.pixels());
+    // And the glyph really spans both cells: '語''s top-left quadrant is lit, the spacer's is not,
+    // and the spacer's bottom half is.
+    let (cw, _) = gl.geometry.cell_size();
+    assert_eq!(frame.rgb(0, 0), GREEN);
+    assert_eq!(frame.rgb(cw, 0), BLUE);
+    assert_eq!(frame.rgb(cw, 20), GREEN);
+}
+
 /// A deterministic two-layer frame in the layer-major, all-cells order `Grid::layers` produces: a
 /// full base layer plus a higher layer mixing empty (transparent) cells, occupied cells with a
 /// `Color::Default` background (opaque, inheriting the base background), and occupied cells with
@@ -609,6 +803,38 @@ fn assert_frames_match(frame: &Frame, software: &[u32]) {
     );
 }
 
+/// Asserts `frame` matches `software` to within `tolerance` per channel, allowing at most
+/// `max_outliers` (a fraction of all pixels) to be off by more.
+fn assert_frames_close(frame: &Frame, software: &[u32], tolerance: u8, max_outliers: f64) {
+    assert_eq!(
+        (frame.width * frame.height) as usize,
+        software.len(),
+        "frame size vs software buffer"
+    );
+    let mut outliers = Vec::new();
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let (r, g, b) = frame.rgb(x, y);
+            let px = software[(y * frame.width + x) as usize];
+            let want = [(px >> 16) as u8, (px >> 8) as u8, px as u8];
+            if [r, g, b]
+                .iter()
+                .zip(want)
+                .any(|(got, want)| got.abs_diff(want) > tolerance)
+            {
+                outliers.push(format!("({x},{y}): gl={:?} sw={want:?}", (r, g, b)));
+            }
+        }
+    }
+    let allowed = (f64::from(frame.width * frame.height) * max_outliers) as usize;
+    assert!(
+        outliers.len() <= allowed,
+        "{} GL vs software pixels differ by more than {tolerance} (allowed {allowed}): {}",
+        outliers.len(),
+        outliers[..outliers.len().min(8)].join(", ")
+    );
+}
+
 /// A 2-tile PNG tileset (issue #366): tile 0 solid red, tile 1 solid green, each `8x16` (the
 /// Unscii cell), laid out in two columns.
 #[cfg(feature = "tilesets")]
@@ -689,11 +915,230 @@ fn sprite_cells_render_their_tileset_colors() {
     }
 }
 
+/// Animated tileset entries draw the same frame on both backends at every timestamp: one
+/// declared through `animate`, one through a metadata file, on a grid that never changes.
+#[cfg(feature = "tilesets")]
+#[test]
+fn matches_software_backend_for_animated_sprites() {
+    use core::time::Duration;
+    use retroglyph_window::animation::SpriteAnimation;
+    use retroglyph_window::presenter::Presenter as _;
+    use retroglyph_window::tileset::{Codepage, TilesetOptions};
+
+    let Some(ctx) = context_or_skip("matches_software_backend_for_animated_sprites") else {
+        return;
+    };
+
+    let tileset = || {
+        TilesetOptions::builder(two_tile_png())
+            .tile_size(8, 16)
+            .columns(2)
+            .codepage(Codepage::Custom(vec!['A', 'B']))
+            .animate(
+                'A',
+                SpriteAnimation::uniform([0, 1], Duration::from_millis(100)),
+            )
+            .animation_metadata("C pingpong 1:30 0:30 1:30")
+            .build()
+            .expect("valid animated tileset")
+    };
+    let bg = Style::new().bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+    let scene: Vec<(u8, Pos, Tile)> = ['A', 'B', 'C', '.']
+        .into_iter()
+        .zip(0..)
+        .map(|(ch, x)| (0, Pos::new(x, 0), Tile::new(ch, bg)))
+        .collect();
+
+    let mut gl = GlBackendBuilder::new()
+        .grid_size(4, 1)
+        .scale(2)
+        .tileset(tileset())
+        .build()
+        .expect("gl renderer with tileset");
+    let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .grid_size(4, 1)
+        .scale(2)
+        .tileset(tileset())
+        .build()
+        .expect("default-font builds")
+        .into_renderer()
+        .expect("headless software renderer");
+
+    for millis in [0, 40, 120, 250, 1_000] {
+        let time = Duration::from_millis(millis);
+        gl.set_animation_time(time);
+        sw.set_animation_time(time);
+        paint_layers(&mut gl, &scene);
+        paint_layers(&mut sw, &scene);
+        let frame = render_to_frame(&ctx, &gl).expect("render");
+        assert_frames_match(&frame, sw.pixels());
+    }
+}
+
+/// An 8x16 tile with a different color in every pixel and a transparent corner, so any flip or
+/// turn that lands a pixel in the wrong place changes the frame.
+#[cfg(feature = "tilesets")]
+fn asymmetric_tile_png() -> Vec<u8> {
+    use image::ImageEncoder as _;
+    let (w, h) = (8u32, 16u32);
+    let mut img = image::RgbaImage::new(w, h);
+    for y in 0..h {
+        for x in 0..w {
+            #[allow(clippy::cast_possible_truncation)]
+            let px = if x + y < 3 {
+                [0, 0, 0, 0]
+            } else {
+                [(x * 32) as u8, (y * 16) as u8, 0x80, 0xFF]
+            };
+            img.put_pixel(x, y, image::Rgba(px));
+        }
+    }
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(img.as_raw(), w, h, image::ExtendedColorType::Rgba8)
+        .expect("encode test tileset PNG");
+    png
+}
+
+/// The same sprite under every flip and turn, and scaled, drawn through both backends: the
+/// vertex shader's corner-to-texel mapping must agree with `Transform::source_pixel`, which the
+/// software blit samples through.
+#[cfg(feature = "tilesets")]
+#[test]
+fn matches_software_backend_for_transformed_sprites() {
+    use retroglyph_core::tile::Transform;
+    use retroglyph_window::tileset::{Codepage, TilesetOptions};
+
+    let Some(ctx) = context_or_skip("matches_software_backend_for_transformed_sprites") else {
+        return;
+    };
+
+    let tileset = || {
+        TilesetOptions::builder(asymmetric_tile_png())
+            .tile_size(8, 16)
+            .columns(1)
+            .codepage(Codepage::Custom(vec!['A']))
+            .build()
+            .expect("valid single-tile tileset")
+    };
+    let bg = Style::new().bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+    let scene: Vec<(u8, Pos, Tile)> = (0..2)
+        .flat_map(|y| (0..4).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let ch = if (x, y) == (0, 0) { 'A' } else { ' ' };
+            (0, Pos::new(x, y), Tile::new(ch, bg))
+        })
+        .collect();
+
+    let mut gl = GlBackendBuilder::new()
+        .grid_size(4, 2)
+        .scale(2)
+        .tileset(tileset())
+        .build()
+        .expect("gl renderer with tileset");
+    let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .grid_size(4, 2)
+        .scale(2)
+        .tileset(tileset())
+        .build()
+        .expect("default-font builds")
+        .into_renderer()
+        .expect("headless software renderer");
+
+    let id = Transform::IDENTITY;
+    for transform in [
+        id.flipped_x(),
+        id.flipped_y(),
+        id.rotated(1),
+        id.rotated(2),
+        id.rotated(3).flipped_x(),
+        id.rotated(1).scaled(2),
+    ] {
+        paint_layers_with(&mut gl, &scene, |cell| cell.with_transform(transform));
+        paint_layers_with(&mut sw, &scene, |cell| cell.with_transform(transform));
+        let frame = render_to_frame(&ctx, &gl).expect("render");
+        assert_frames_match(&frame, sw.pixels());
+    }
+}
+
+/// Palette swaps drawn through both backends, on an art sheet and a mask sheet and under a tint:
+/// the fragment shader's table search and luma must agree with `Remap::lookup`, which the
+/// software blit calls, on every pixel of a sprite with a different color in each.
+#[cfg(feature = "tilesets")]
+#[test]
+fn matches_software_backend_for_remapped_sprites() {
+    use retroglyph_core::color::{Remap, Tint};
+    use retroglyph_window::tileset::{Codepage, SheetColor, TilesetOptions};
+
+    let Some(ctx) = context_or_skip("matches_software_backend_for_remapped_sprites") else {
+        return;
+    };
+
+    let tileset = |color| {
+        TilesetOptions::builder(asymmetric_tile_png())
+            .tile_size(8, 16)
+            .columns(1)
+            .codepage(Codepage::Custom(vec!['A']))
+            .color(color)
+            .build()
+            .expect("valid single-tile tileset")
+    };
+    let style = Style::new()
+        .fg(Color::rgb(200, 120, 40))
+        .bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+    let scene = [(0, Pos::new(0, 0), Tile::new('A', style))];
+
+    // The asymmetric tile's pixel (x, y) is `(x * 32, y * 16, 0x80)`: key three of them, one
+    // twice to check the first entry wins, and fill the table to its last entry.
+    let mut pairs = vec![
+        ((32, 48, 0x80), (255, 255, 0)),
+        ((96, 160, 0x80), (0, 255, 255)),
+        ((32, 48, 0x80), (1, 2, 3)),
+    ];
+    pairs.resize(Remap::MAX_ENTRIES - 1, ((1, 1, 1), (9, 9, 9)));
+    pairs.push(((224, 240, 0x80), (10, 20, 30)));
+    let remaps = [
+        Remap::colors(&pairs),
+        Remap::ramp(&[(40, 20, 10), (110, 70, 40), (180, 140, 90), (240, 220, 180)]),
+        Remap::ramp(&[(255, 255, 255)]),
+        Remap::ramp(&[(0, 0, 0); Remap::MAX_ENTRIES]),
+    ];
+
+    for sheet in [SheetColor::Art, SheetColor::Mask] {
+        let mut gl = GlBackendBuilder::new()
+            .grid_size(1, 1)
+            .scale(1)
+            .tileset(tileset(sheet))
+            .build()
+            .expect("gl renderer with tileset");
+        let mut sw = retroglyph_software::config::SoftwareBackendBuilder::new()
+            .grid_size(1, 1)
+            .scale(1)
+            .tileset(tileset(sheet))
+            .build()
+            .expect("default-font builds")
+            .into_renderer()
+            .expect("headless software renderer");
+        for remap in &remaps {
+            for tint in [Tint::None, Tint::mix(255, 255, 255, 128)] {
+                paint_layers_with(&mut gl, &scene, |cell| {
+                    cell.with_remap(Some(remap)).with_tint(tint)
+                });
+                paint_layers_with(&mut sw, &scene, |cell| {
+                    cell.with_remap(Some(remap)).with_tint(tint)
+                });
+                let frame = render_to_frame(&ctx, &gl).expect("render");
+                assert_frames_match(&frame, sw.pixels());
+            }
+        }
+    }
+}
+
 #[cfg(feature = "tilesets")]
 #[test]
 fn a_tinted_sprite_matches_what_sprite_tint_apply_computes() {
     use retroglyph_core::color::Tint;
-    use retroglyph_window::palette::DEFAULT_FG;
+    use retroglyph_window::palette::Palette;
     use retroglyph_window::sprite_cache::SpriteTint;
     use retroglyph_window::tileset::{Codepage, SheetColor, TilesetOptions};
 
@@ -742,20 +1187,25 @@ fn a_tinted_sprite_matches_what_sprite_tint_apply_computes() {
             .tileset(opts)
             .build()
             .expect("gl renderer with tileset");
-        paint_layers_tinted(
+        paint_layers_with(
             &mut r,
             &[(
                 0,
                 Pos::new(0, 0),
                 Tile::new('A', Style::new().bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2))),
             )],
-            tint,
+            |cell| cell.with_tint(tint),
         );
 
         let frame = render_to_frame(&ctx, &r).expect("render");
         // Tile 0 of `two_tile_png` is opaque red.
-        let want =
-            SpriteTint::resolve(SheetColor::Art, Color::Default, tint, DEFAULT_FG).apply(RED);
+        let want = SpriteTint::resolve_with_palette(
+            SheetColor::Art,
+            Color::Default,
+            tint,
+            &Palette::DEFAULT,
+        )
+        .apply(RED);
         let (cw, ch) = r.geometry.cell_size();
         for y in 0..ch {
             for x in 0..cw {
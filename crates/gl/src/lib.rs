@@ -63,6 +63,15 @@
 //! Forwards `retroglyph-core`'s `dev` feature, which forces development diagnostics on in a build
 //! that would otherwise compile them out (see [`retroglyph_core::dev`]).
 //!
+//! ### `png-fonts`
+//!
+//! ⚪ Optional.
+//!
+//! Loading PNG font sheets at runtime (`BitmapFont::from_png_sheet`); BDF and PSF fonts load
+//! without it.
+//!
+//! Forwards to `retroglyph-window`'s `png-fonts` feature.
+//!
 //! ### `tilesets`
 //!
 //! ⚪ Optional.
@@ -79,6 +88,12 @@
 //! the trait requires whenever `retroglyph-window/tilesets` is on, regardless of this crate's own
 //! flag (retroglyph#1192). Harmless outside a test build: `retroglyph-software` is dev-only, so
 //! this half of the forward is a no-op for a plain `cargo build`/`check`.
+//!
+//! ### `truetype`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards to `retroglyph-window`'s `truetype` feature.
 //! <!-- gen-features:end -->
 
 #![doc(
@@ -125,24 +140,32 @@ mod context;
 
 // Re-exports a dependency's own public types so a consumer can build a custom atlas without a
 // separate direct dependency on retroglyph-window (STYLE_GUIDE.md exception 3).
-pub use retroglyph_window::font::{self as font, BitmapFont, FontChain};
+pub use retroglyph_window::font::{self as font, BitmapFont, FontChain, FontLoadError};
 
 use context::GlContext;
+#[cfg(feature = "tilesets")]
+use core::time::Duration;
 use error::SurfaceError;
-use renderer::{FLAG_HAS_BG, FLAG_HAS_GLYPH, GlResources, Instance};
+use renderer::{
+    FLAG_HAS_BG, FLAG_HAS_GLYPH, GlResources, Instance, Placement, Presentation, line_flags,
+};
 use retroglyph_core::backend::Compositing;
 use retroglyph_core::backend::DrawCell;
 use retroglyph_core::backend::Output;
 use retroglyph_core::color::Color;
 use retroglyph_core::dev_only;
 use retroglyph_core::grid::HasSize;
-use retroglyph_core::grid::Size;
+use retroglyph_core::grid::{LineSize, Size};
 use retroglyph_core::tile::Tile;
+#[cfg(feature = "tilesets")]
+use retroglyph_core::tile::Transform;
 use retroglyph_window::atlas::GlyphAtlas;
+use retroglyph_window::crt::CrtEffects;
 use retroglyph_window::diagnostics::DiagnosticLog;
-use retroglyph_window::geometry::CellGeometry;
-use retroglyph_window::palette::{DEFAULT_BG, DEFAULT_FG};
+use retroglyph_window::geometry::{CellGeometry, Viewport};
+use retroglyph_window::palette::Palette;
 use retroglyph_window::presenter::{Presenter, WindowHandle, cell_art_glyph};
+use retroglyph_window::scaling::ScalingMode;
 #[cfg(feature = "tilesets")]
 use retroglyph_window::sprite_cache::SpriteTint;
 use shaders::GlslFlavor;
@@ -219,6 +242,13 @@ pub struct GlRenderer {
     geometry: CellGeometry,
     /// Atlas slot for the space glyph, used to initialize blank cells.
     space_glyph: u16,
+    /// The palette colors resolve through. Cells are rebuilt from the grid every frame, so a
+    /// [`set_palette`](Self::set_palette) shows on the next [`Output::draw_layers`].
+    palette: Palette,
+    /// The CRT post-processing effects drawn over each presented frame.
+    crt: CrtEffects,
+    /// How the frame is fitted into the window.
+    scaling: ScalingMode,
     /// Per-layer state (index = grid layer id): each layer's cell instances (each `cols * rows`
     /// in row-major order) and, with `tilesets`, its sprite instances. `layers[0]` is the
     /// always-opaque base; higher layers composite over it back-to-front (see
@@ -229,10 +259,15 @@ pub struct GlRenderer {
     /// can be rebuilt after a WebGL2 context loss.
     #[cfg(feature = "tilesets")]
     sprite_set: Option<SpriteSet>,
+    /// The clock animated sprites are drawn at; see [`Presenter::animation_time`]. Read by
+    /// [`Output::draw_layers`], which rebuilds every sprite instance each frame anyway.
+    #[cfg(feature = "tilesets")]
+    animation_time: Duration,
     /// The oversized-sprite, dropped-tint, and notdef-glyph dedup state, so a redraw loop logs
     /// each offending glyph once instead of every frame. See `retroglyph_window::diagnostics`.
     diagnostics: DiagnosticLog,
-    /// The current surface size in physical pixels (set by [`resize_surface`](Presenter::resize_surface)).
+    /// The current surface size in physical pixels (set by [`resize_surface`](Presenter::resize_surface)):
+    /// the frame's size under [`ScalingMode::Reflow`], the whole window's otherwise.
     surface_size: (u32, u32),
     /// GL context + resources. `None` until [`init_surface`](Presenter::init_surface).
     gpu: Option<Gpu>,
@@ -249,7 +284,15 @@ impl GlRenderer {
     /// Builds a renderer for the given glyph cache, grid size, and scale. Called by
     /// [`config::GlBackendBuilder::build`].
     ///
-    pub(crate) fn new(glyphs: GlyphAtlas, cols: u16, rows: u16, scale: u16) -> Self {
+    pub(crate) fn new(
+        glyphs: GlyphAtlas,
+        cols: u16,
+        rows: u16,
+        scale: u16,
+        palette: Palette,
+        crt: CrtEffects,
+        scaling: ScalingMode,
+    ) -> Self {
         let (cell_w, cell_h) = glyphs.cell_size();
         let geometry = CellGeometry::new(
             u16::try_from(cell_w).unwrap_or(u16::MAX),
@@ -258,7 +301,7 @@ impl GlRenderer {
         );
         let space_glyph = glyphs.space_slot();
         let count = usize::from(cols) * usize::from(rows);
-        let base = base_blank(space_glyph);
+        let base = base_blank(space_glyph, &palette);
         let layers = vec![Layer::blank(base, count)];
         Self {
             glyphs,
@@ -266,15 +309,85 @@ impl GlRenderer {
             rows,
             geometry,
             space_glyph,
+            palette,
+            crt,
+            scaling,
             layers,
             #[cfg(feature = "tilesets")]
             sprite_set: None,
+            #[cfg(feature = "tilesets")]
+            animation_time: Duration::ZERO,
             diagnostics: DiagnosticLog::default(),
             surface_size: geometry.surface_size(cols, rows),
             gpu: None,
         }
     }
 
+    /// The palette colors currently resolve through.
+    #[must_use]
+    pub const fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Replaces the palette colors resolve through, e.g. when the player picks another theme.
+    /// It takes effect on the next [`Output::draw_layers`], which rebuilds every cell.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// The CRT post-processing effects currently drawn over each frame.
+    #[must_use]
+    pub const fn crt(&self) -> CrtEffects {
+        self.crt
+    }
+
+    /// Turns the CRT post-processing effects on, off, or to new strengths. It takes effect on the
+    /// next [`present`](Presenter::present); [`CrtEffects::NONE`] skips the pass entirely.
+    pub const fn set_crt(&mut self, effects: CrtEffects) {
+        self.crt = effects;
+    }
+
+    /// How the frame is fitted into the window.
+    #[must_use]
+    pub const fn scaling(&self) -> ScalingMode {
+        self.scaling
+    }
+
+    /// The size the layers composite at: the surface itself under [`ScalingMode::Reflow`], and the
+    /// grid's own size under the modes that scale it into the surface.
+    const fn frame_size(&self) -> (u32, u32) {
+        if self.scaling.reflows() {
+            self.surface_size
+        } else {
+            self.geometry.surface_size(self.cols, self.rows)
+        }
+    }
+
+    /// Where the frame is shown on the surface, or `None` under [`ScalingMode::Reflow`].
+    fn viewport(&self) -> Option<Viewport> {
+        (!self.scaling.reflows())
+            .then(|| self.scaling.viewport(self.frame_size(), self.surface_size))
+    }
+
+    /// The stages the composited frame goes through to reach the surface. A scaling mode that
+    /// happens to show the frame 1:1 on a surface its own size skips the scaling pass.
+    fn presentation(&self) -> Presentation {
+        let placement = self
+            .viewport()
+            .filter(|viewport| !viewport.is_unscaled() || viewport.frame != self.surface_size)
+            .map(|viewport| Placement {
+                target: self.surface_size,
+                viewport,
+                filter: self.scaling.filter(),
+                bars: self.scaling.bars(),
+            });
+        Presentation {
+            crt: self.crt,
+            frame: self.frame_size(),
+            placement,
+        }
+    }
+
     /// Attaches a decoded sprite atlas (issue #366). Called by
     /// [`config::GlBackendBuilder::build`] when a tileset was registered; the GPU atlas is built
     /// later in [`build_resources`](Self::build_resources).
@@ -287,21 +400,22 @@ impl GlRenderer {
     /// glyph drawn. Layer 0 always paints its background (the opaque base), so an untouched base
     /// cell is the default background.
     const fn base_blank(&self) -> Instance {
-        base_blank(self.space_glyph)
+        base_blank(self.space_glyph, &self.palette)
     }
 
-    /// Reports a sprite drawn larger than one cell without a span to reserve the cells it covers.
+    /// Reports a sprite drawn larger than one cell, at its transformed `size`, without a span to
+    /// reserve the cells it covers.
     ///
     /// Shares `retroglyph-window`'s diagnostic with the software backend so both name the same
     /// fix. A tile that already declares a span is fine and says nothing.
     #[cfg(feature = "tilesets")]
-    fn warn_if_sprite_needs_span(&mut self, tile: &Tile, sprite: SpriteSlot) {
+    fn warn_if_sprite_needs_span(&mut self, tile: &Tile, size: (u32, u32)) {
         if tile.is_span_anchor() {
             return;
         }
         self.diagnostics.sprite_needs_span(
             tile.glyph(),
-            (u32::from(sprite.w), u32::from(sprite.h)),
+            size,
             (
                 u32::from(self.geometry.glyph_w),
                 u32::from(self.geometry.glyph_h),
@@ -322,15 +436,16 @@ impl GlRenderer {
     }
 
     /// Pushes one sprite instance for `tile` on layer `l` at cell `(cx, cy)`: aligns it within its
-    /// span box, warns once if it needed a span but didn't declare one, and resolves its tint
-    /// against `sprite`'s sheet color. Shared verbatim between the layer-0 and higher-layer sprite
+    /// span box at its transformed size, warns once if it needed a span but didn't declare one,
+    /// and resolves its tint and remap against `sprite`'s sheet color. Shared verbatim between the layer-0 and higher-layer sprite
     /// dispatch branches in `draw_layers` (retroglyph#1374): the CPU/GPU parity contract (span
-    /// alignment, oversize warning, `SpriteTint::resolve` argument order) lives in exactly one
+    /// alignment, oversize warning, `SpriteTint::resolve_with_palette` argument order) lives in exactly one
     /// place, so a fix here reaches every layer instead of needing to land twice.
     ///
     /// The caller still owns which `Instance` is written and how `inherited_bg`/`sprite_bg` update;
     /// this only appends to `self.layers[l].sprites`.
     #[cfg(feature = "tilesets")]
+    #[allow(clippy::too_many_arguments)]
     fn emit_sprite(
         &mut self,
         l: usize,
@@ -339,21 +454,37 @@ impl GlRenderer {
         tile: &Tile,
         sprite: SpriteSlot,
         tint: retroglyph_core::color::Tint,
+        transform: Transform,
+        remap: Option<&retroglyph_core::color::Remap>,
     ) {
         let (span_w, span_h) = tile.span();
-        let align =
-            sprite.align_offset(span_w, span_h, self.geometry.glyph_w, self.geometry.glyph_h);
-        self.warn_if_sprite_needs_span(tile, sprite);
-        self.layers[l].sprites.push(SpriteInstance::new(
-            cx,
-            cy,
-            sprite.layer,
-            sprite.w,
-            sprite.h,
-            tile.dx() + align.0,
-            tile.dy() + align.1,
-            SpriteTint::resolve(sprite.color, tile.style().foreground(), tint, DEFAULT_FG),
-        ));
+        let align = sprite.align_offset(
+            transform,
+            span_w,
+            span_h,
+            self.geometry.glyph_w,
+            self.geometry.glyph_h,
+        );
+        self.warn_if_sprite_needs_span(tile, sprite.size(transform));
+        self.layers[l].sprites.push(
+            SpriteInstance::new(
+                cx,
+                cy,
+                sprite.layer,
+                sprite.w,
+                sprite.h,
+                tile.dx() + align.0,
+                tile.dy() + align.1,
+                SpriteTint::resolve_with_palette(
+                    sprite.color,
+                    tile.style().foreground(),
+                    tint,
+                    &self.palette,
+                )
+                .with_remap(remap),
+            )
+            .with_transform(transform),
+        );
     }
 
     /// Reports a character that resolved to the atlas's substituted "not defined" glyph rather
@@ -374,6 +505,41 @@ impl GlRenderer {
         });
     }
 
+    /// The atlas slot `tile` draws and, for a wide character whose double-width glyph the atlas
+    /// holds, the slot of that glyph's right half, which its spacer cell draws next. Warns about a
+    /// notdef substitution only when the character falls back to a single cell.
+    fn cell_slots(&mut self, tile: &Tile) -> (Option<u16>, Option<u16>) {
+        if tile.is_wide()
+            && let Some([left, right]) = self.glyphs.resolve_wide(tile.glyph())
+        {
+            return (Some(left), Some(right));
+        }
+        self.warn_if_notdef_glyph(tile.glyph());
+        (self.glyphs.resolve(tile.glyph()), None)
+    }
+
+    /// Marks every layer's cells on each double-size row with that row's [`line_flags`], and
+    /// hides the right half of the row: core never streams those cells, so they still hold the
+    /// blanks `draw_layers` reset them to, which would otherwise paint over the doubled left half.
+    fn apply_line_sizes(&mut self, line_sizes: &[LineSize]) {
+        let cols = usize::from(self.cols);
+        for (y, &line) in line_sizes.iter().enumerate() {
+            if line == LineSize::Normal {
+                continue;
+            }
+            let visible = usize::from(line.columns(self.cols));
+            for layer in &mut self.layers {
+                let row = &mut layer.cells[y * cols..(y + 1) * cols];
+                for cell in &mut row[..visible] {
+                    cell.flags |= line_flags(line);
+                }
+                for cell in &mut row[visible..] {
+                    cell.flags = 0;
+                }
+            }
+        }
+    }
+
     /// Total cell count for the current grid.
     fn cell_count(&self) -> usize {
         usize::from(self.cols) * usize::from(self.rows)
@@ -397,7 +563,7 @@ impl GlRenderer {
         gl: &glow::Context,
         flavor: GlslFlavor,
     ) -> Result<GlResources, SurfaceError> {
-        let (w, h) = self.surface_size;
+        let (w, h) = self.frame_size();
         let atlas = self.glyphs.data();
         #[cfg_attr(not(feature = "tilesets"), allow(unused_mut))]
         let mut res = GlResources::new(gl, flavor, &atlas, self.cell_count())?;
@@ -432,9 +598,11 @@ impl GlRenderer {
 /// A free function over `&[Layer]` rather than a `GlRenderer` method: [`Presenter::present`] holds
 /// a split borrow of `self.gpu` (see its own comment) while still needing `self.layers`, and a
 /// `&self` method here would re-borrow the whole renderer and conflict with that. The headless
-/// (`headless.rs`) and WebGL2 smoke (`webgl_smoke.rs`) render tests share this too, so the loop
-/// they exercise is exactly the one `present` runs.
+/// (`headless.rs`) and WebGL2 smoke (`webgl_smoke.rs`) render tests share this too, through
+/// [`draw_frame`], so the loop they exercise is exactly the one `present` runs.
 #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+// `res` is only written by the sprite pass; without `tilesets` the upload/draw calls read it.
+#[cfg_attr(not(feature = "tilesets"), allow(clippy::needless_pass_by_ref_mut))]
 fn draw_all_layers(layers: &[Layer], gl: &glow::Context, res: &mut GlResources, cell_count: i32) {
     for layer in layers {
         res.upload(gl, &layer.cells);
@@ -444,6 +612,31 @@ fn draw_all_layers(layers: &[Layer], gl: &glow::Context, res: &mut GlResources,
     }
 }
 
+/// Draws one whole frame of `layers` onto `target` (`None` for the default framebuffer): a clear
+/// and [`draw_all_layers`], composited into the offscreen scene and then drawn to `target` through
+/// `presentation`'s CRT and scaling passes when it has any, or straight into `target` when it
+/// doesn't.
+///
+/// Shared by [`Presenter::present`] and the render tests for the same reason `draw_all_layers` is.
+/// Expects `target` bound and the projection set for a `presentation.frame`-sized frame.
+///
+/// # Errors
+///
+/// Returns [`SurfaceError::Init`] if an offscreen target can't be created.
+fn draw_frame(
+    layers: &[Layer],
+    gl: &glow::Context,
+    res: &mut GlResources,
+    cell_count: i32,
+    presentation: &Presentation,
+    target: Option<glow::Framebuffer>,
+) -> Result<(), SurfaceError> {
+    res.begin_scene(gl, presentation)?;
+    res.clear(gl);
+    draw_all_layers(layers, gl, res, cell_count);
+    res.finish_scene(gl, presentation, target)
+}
+
 /// `(u8, u8, u8)` -> `[u8; 3]`, for packing resolved colors into an [`Instance`].
 const fn to_arr(rgb: (u8, u8, u8)) -> [u8; 3] {
     [rgb.0, rgb.1, rgb.2]
@@ -451,11 +644,11 @@ const fn to_arr(rgb: (u8, u8, u8)) -> [u8; 3] {
 
 /// The base-layer blank instance for `space_glyph`: opaque default background, no glyph. Free
 /// function so [`GlRenderer::new`] can build it before `self` exists.
-const fn base_blank(space_glyph: u16) -> Instance {
+const fn base_blank(space_glyph: u16, palette: &Palette) -> Instance {
     Instance::new(
         space_glyph,
-        to_arr(DEFAULT_FG),
-        to_arr(DEFAULT_BG),
+        to_arr(palette.foreground()),
+        to_arr(palette.background()),
         0,
         0,
         FLAG_HAS_BG,
@@ -468,13 +661,10 @@ const fn base_blank(space_glyph: u16) -> Instance {
 ///
 /// A `slot` of `None` is a character no font in the chain can draw, not even as the substituted
 /// solid block; the cell keeps its background and draws no glyph, matching `retroglyph-software`.
-const fn base_instance(slot: Option<u16>, tile: &Tile) -> Instance {
-    let fg = to_arr(tile.style().foreground().resolve_rgb(DEFAULT_FG));
-    let bg = to_arr(tile.style().background().resolve_rgb(DEFAULT_BG));
-    let (slot, drawable) = match slot {
-        Some(slot) => (slot, FLAG_HAS_GLYPH),
-        None => (0, 0),
-    };
+fn base_instance(slot: Option<u16>, tile: &Tile, palette: &Palette) -> Instance {
+    let fg = to_arr(palette.foreground_rgb(tile.style().foreground()));
+    let bg = to_arr(palette.background_rgb(tile.style().background()));
+    let (slot, drawable) = slot.map_or((0, 0), |slot| (slot, FLAG_HAS_GLYPH));
     let flags = FLAG_HAS_BG
         | if cell_art_glyph(tile).is_none() {
             0
@@ -517,7 +707,7 @@ impl Output for GlRenderer {
         // opaque and erases the glyph beneath it, repainting whichever background a lower layer
         // last established (down to layer 0's default). This relies on the layer-major stream order
         // above, so a layer's lower neighbours are always processed first.
-        let mut inherited_bg = vec![to_arr(DEFAULT_BG); cell_count];
+        let mut inherited_bg = vec![to_arr(self.palette.background()); cell_count];
 
         // Per-cell record of whether the occupant drawn at that index dispatched to a sprite
         // (issue #366), keyed the same way as `inherited_bg`. A span's covered cells hold only a
@@ -530,12 +720,20 @@ impl Output for GlRenderer {
 
         let cols = usize::from(self.cols);
         let rows = usize::from(self.rows);
+        // Each row's line size, read off the cells streamed for it and applied to every layer
+        // once the stream is done (see `apply_line_sizes`).
+        let mut line_sizes = vec![LineSize::Normal; rows];
+        // The right half of the last wide character's double-width glyph, waiting for its spacer
+        // cell as `(layer, index, slot, dx, dy)`: the spacer, streamed right after its lead, draws
+        // it in the lead's offset in place of its own blank.
+        let mut wide_right: Option<(usize, usize, u16, i16, i16)> = None;
         for draw_cell in content {
             let (layer_id, pos, tile) = (draw_cell.layer, draw_cell.pos, draw_cell.tile);
             let (x, y) = (usize::from(pos.x), usize::from(pos.y));
             if x >= cols || y >= rows {
                 continue;
             }
+            line_sizes[y] = draw_cell.line;
             let l = usize::from(layer_id);
             while self.layers.len() <= l {
                 // Higher layers default to fully transparent cells (flags == 0).
@@ -545,18 +743,31 @@ impl Output for GlRenderer {
                 ));
             }
             let idx = y * cols + x;
+            let spacer = wide_right
+                .take()
+                .filter(|&(wl, widx, ..)| (wl, widx) == (l, idx) && tile.is_wide_spacer())
+                .map(|(_, _, slot, dx, dy)| (slot, dx, dy));
 
             // A cell whose glyph has a sprite draws the sprite instead of a bitmap glyph (issue
             // #366); the glyph instance keeps only the background (per `resolve_bg_fill`).
+            // On a double-size row the sprite keeps its pixel size and moves with its cell, which
+            // starts at column `2x` there.
             #[cfg(feature = "tilesets")]
             #[allow(clippy::cast_possible_truncation)]
-            let (cx, cy) = (x as u16, y as u16);
+            let (cx, cy) = (
+                if draw_cell.line.is_double_width() {
+                    x as u16 * 2
+                } else {
+                    x as u16
+                },
+                y as u16,
+            );
 
             // A cell covered by a multi-cell span (retroglyph#412) draws no glyph of its own: the
             // span's anchor emitted one sprite across the whole footprint, and this cell's glyph
             // is that sprite's text fallback, for backends that can't draw it. Every cell of a span
             // shares one `Style` (see `Grid::write_span_cells`), so this cell's own tile already
-            // carries the same colours as the anchor; the anchor is consulted only to answer "does
+            // carries the same colors as the anchor; the anchor is consulted only to answer "does
             // this span dispatch to a sprite" (via `sprite_bg`), the same split
             // `retroglyph-software`'s `resolve_cell_bg` documents. Resolving the running inherited
             // background at this cell's own index, not the anchor's, keeps a span from smearing one
@@ -568,10 +779,10 @@ impl Output for GlRenderer {
                     .filter(|&anchor_idx| anchor_idx < cell_count);
                 if let Some(anchor_idx) = anchor_idx {
                     let has_sprite = sprite_bg[anchor_idx];
-                    let fg = to_arr(tile.style().foreground().resolve_rgb(DEFAULT_FG));
+                    let fg = to_arr(self.palette.foreground_rgb(tile.style().foreground()));
                     let bg_color = tile.style().background();
                     let (bg, has_bg) = if l == 0 || bg_color != Color::Default {
-                        (to_arr(bg_color.resolve_rgb(DEFAULT_BG)), FLAG_HAS_BG)
+                        (to_arr(self.palette.background_rgb(bg_color)), FLAG_HAS_BG)
                     } else if has_sprite {
                         (inherited_bg[idx], 0)
                     } else {
@@ -587,17 +798,22 @@ impl Output for GlRenderer {
             }
 
             if layer_id == 0 {
-                let slot = self.glyphs.resolve(tile.glyph());
-                self.warn_if_notdef_glyph(tile.glyph());
-                let inst = base_instance(slot, tile);
+                let (slot, right) = match spacer {
+                    Some((slot, ..)) => (Some(slot), None),
+                    None => self.cell_slots(tile),
+                };
+                let mut inst = base_instance(slot, tile, &self.palette);
+                if let Some((_, dx, dy)) = spacer {
+                    (inst.dx, inst.dy) = (dx, dy);
+                }
                 // Sprite dispatch is gated on `cell_art_glyph`, not the raw `tile.glyph()`: a
                 // blank layer-0 cell (`is_empty()`, e.g. an untouched grid cell) draws no art at
                 // all, even if its glyph happens to have a registered sprite (retroglyph#762).
                 #[cfg(feature = "tilesets")]
                 {
                     let art_glyph = cell_art_glyph(tile);
-                    if let Some(sprite) =
-                        art_glyph.and_then(|g| self.sprite_set.as_ref().and_then(|s| s.slot(g)))
+                    if let Some(sprite) = art_glyph
+                        .and_then(|g| self.sprite_set.as_ref()?.slot_at(g, self.animation_time))
                     {
                         // Keep layer 0's opaque background; drop the glyph, the sprite covers it.
                         let sprite_inst = Instance::new(
@@ -611,13 +827,25 @@ impl Output for GlRenderer {
                         inherited_bg[idx] = sprite_inst.bg;
                         sprite_bg[idx] = true;
                         self.layers[0].cells[idx] = sprite_inst;
-                        self.emit_sprite(0, cx, cy, tile, sprite, draw_cell.tint);
+                        self.emit_sprite(
+                            0,
+                            cx,
+                            cy,
+                            tile,
+                            sprite,
+                            draw_cell.tint,
+                            draw_cell.transform,
+                            draw_cell.remap,
+                        );
                         continue;
                     }
                     if let Some(g) = art_glyph {
                         self.warn_if_tint_needs_sprite(g, draw_cell.tint);
                     }
                 }
+                if let Some(right) = right {
+                    wide_right = Some((0, idx + 1, right, inst.dx, inst.dy));
+                }
                 inherited_bg[idx] = inst.bg;
                 self.layers[0].cells[idx] = inst;
                 continue;
@@ -630,28 +858,35 @@ impl Output for GlRenderer {
                     Instance::new(self.space_glyph, [0; 3], [0; 3], 0, 0, 0);
                 continue;
             }
-            // Occupied higher-layer tile: opaque background (own colour, or the inherited one when
+            // Occupied higher-layer tile: opaque background (own color, or the inherited one when
             // the tile's background is `Default`) plus its glyph, unless no font in the chain can
             // draw that character at all (see `base_instance`).
-            let resolved = self.glyphs.resolve(tile.glyph());
-            self.warn_if_notdef_glyph(tile.glyph());
+            let (resolved, right) = match spacer {
+                Some((slot, ..)) => (Some(slot), None),
+                None => self.cell_slots(tile),
+            };
+            let (dx, dy) = spacer.map_or_else(|| (tile.dx(), tile.dy()), |(_, dx, dy)| (dx, dy));
             let glyph = resolved.unwrap_or(0);
             let has_glyph = if resolved.is_some() {
                 FLAG_HAS_GLYPH
             } else {
                 0
             };
-            let fg = to_arr(tile.style().foreground().resolve_rgb(DEFAULT_FG));
+            let fg = to_arr(self.palette.foreground_rgb(tile.style().foreground()));
             let bg_color = tile.style().background();
             let bg = if bg_color == Color::Default {
                 inherited_bg[idx]
             } else {
-                let resolved = to_arr(bg_color.resolve_rgb(DEFAULT_BG));
+                let resolved = to_arr(self.palette.background_rgb(bg_color));
                 inherited_bg[idx] = resolved;
                 resolved
             };
             #[cfg(feature = "tilesets")]
-            if let Some(sprite) = self.sprite_set.as_ref().and_then(|s| s.slot(tile.glyph())) {
+            if let Some(sprite) = self
+                .sprite_set
+                .as_ref()
+                .and_then(|s| s.slot_at(tile.glyph(), self.animation_time))
+            {
                 // No bitmap glyph. An occupied higher-layer sprite cell with a `Default` background
                 // paints no background (the sprite's own alpha provides coverage, so lower layers
                 // show through its transparent pixels), matching `resolve_bg_fill`'s has_sprite
@@ -663,15 +898,28 @@ impl Output for GlRenderer {
                 };
                 sprite_bg[idx] = true;
                 self.layers[l].cells[idx] = Instance::new(glyph, fg, bg, 0, 0, has_bg);
-                self.emit_sprite(l, cx, cy, tile, sprite, draw_cell.tint);
+                self.emit_sprite(
+                    l,
+                    cx,
+                    cy,
+                    tile,
+                    sprite,
+                    draw_cell.tint,
+                    draw_cell.transform,
+                    draw_cell.remap,
+                );
                 continue;
             }
             #[cfg(feature = "tilesets")]
             self.warn_if_tint_needs_sprite(tile.glyph(), draw_cell.tint);
+            if let Some(right) = right {
+                wide_right = Some((l, idx + 1, right, dx, dy));
+            }
             sprite_bg[idx] = false;
             self.layers[l].cells[idx] =
-                Instance::new(glyph, fg, bg, tile.dx(), tile.dy(), FLAG_HAS_BG | has_glyph);
+                Instance::new(glyph, fg, bg, dx, dy, FLAG_HAS_BG | has_glyph);
         }
+        self.apply_line_sizes(&line_sizes);
         Ok(())
     }
 
@@ -750,7 +998,7 @@ impl Presenter for GlRenderer {
     fn present(&mut self) -> Result<(), SurfaceError> {
         let cell_count = self.cell_count();
         let cols = i32::from(self.cols);
-        let (w, h) = self.surface_size;
+        let (w, h) = self.frame_size();
         let (cell_w, cell_h) = self.geometry.cell_size();
         let (cell_w, cell_h) = (cell_w as f32, cell_h as f32);
 
@@ -777,6 +1025,7 @@ impl Presenter for GlRenderer {
             }
         }
 
+        let presentation = self.presentation();
         // Split borrow: `gpu` borrows `self.gpu`, while `self.layers` is a disjoint field, so
         // direct field access to it stays legal below.
         let Some(gpu) = self.gpu.as_mut() else {
@@ -793,16 +1042,38 @@ impl Presenter for GlRenderer {
         gpu.res
             .set_projection(&gpu.ctx.gl, w as f32, h as f32, cell_w, cell_h, cols);
         // Composite every layer back-to-front: clear once, then upload and draw each layer's
-        // instances in turn (issue #368). This backend requests full frames, so `self.layers`
-        // already holds the whole current frame.
-        gpu.res.clear(&gpu.ctx.gl);
+        // instances in turn (issue #368), through the CRT and scaling passes if they're on. This
+        // backend requests full frames, so `self.layers` already holds the whole current frame.
         #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-        draw_all_layers(&self.layers, &gpu.ctx.gl, &mut gpu.res, cell_count as i32);
+        draw_frame(
+            &self.layers,
+            &gpu.ctx.gl,
+            &mut gpu.res,
+            cell_count as i32,
+            &presentation,
+            None,
+        )?;
         gpu.ctx.present()
     }
 
     fn geometry(&self) -> CellGeometry {
-        self.geometry
+        self.viewport().map_or(self.geometry, |viewport| {
+            self.geometry.with_viewport(viewport)
+        })
+    }
+
+    fn scaling(&self) -> ScalingMode {
+        self.scaling
+    }
+
+    #[cfg(feature = "tilesets")]
+    fn animation_time(&self) -> Duration {
+        self.animation_time
+    }
+
+    #[cfg(feature = "tilesets")]
+    fn set_animation_time(&mut self, time: Duration) {
+        self.animation_time = time;
     }
 }
 
@@ -816,14 +1087,14 @@ impl Drop for GlRenderer {
 
 #[cfg(all(test, feature = "default-font"))]
 mod compositing_tests {
-    use super::{FLAG_HAS_BG, FLAG_HAS_GLYPH};
+    use super::{FLAG_HAS_BG, FLAG_HAS_GLYPH, line_flags};
     use crate::config::GlBackendBuilder;
     use retroglyph_core::backend::Compositing;
     use retroglyph_core::backend::DrawCell;
     use retroglyph_core::backend::Output;
     use retroglyph_core::color::Color;
     use retroglyph_core::color::Style;
-    use retroglyph_core::grid::Pos;
+    use retroglyph_core::grid::{LineSize, Pos};
     use retroglyph_core::tile::Tile;
 
     const RED: Color = Color::rgb(255, 0, 0);
@@ -849,6 +1120,26 @@ mod compositing_tests {
         assert_eq!(inst.flags, FLAG_HAS_BG | FLAG_HAS_GLYPH);
     }
 
+    #[test]
+    fn draw_layers_marks_double_size_rows_and_hides_their_right_half() {
+        let mut r = GlBackendBuilder::new()
+            .grid_size(4, 2)
+            .build()
+            .expect("default-font builds");
+        let tile = Tile::new('A', Style::new());
+        let wide = |x| DrawCell::new(Pos::new(x, 0), &tile).with_line(LineSize::DoubleWidth);
+        r.draw_layers([wide(0), wide(1)].into_iter())
+            .expect("draw is infallible");
+
+        let flags = FLAG_HAS_BG | FLAG_HAS_GLYPH | line_flags(LineSize::DoubleWidth);
+        assert_eq!(r.layers[0].cells[0].flags, flags);
+        assert_eq!(r.layers[0].cells[1].flags, flags);
+        assert_eq!(r.layers[0].cells[2].flags, 0);
+        assert_eq!(r.layers[0].cells[3].flags, 0);
+        // The normal row below is untouched.
+        assert_eq!(r.layers[0].cells[4].flags, FLAG_HAS_BG);
+    }
+
     #[test]
     fn build_rejects_a_grid_and_scale_that_overflow_the_surface_size() {
         // retroglyph#729: `CellGeometry::surface_size` multiplied cols/rows/scale as plain `u32`,
@@ -925,11 +1216,50 @@ mod compositing_tests {
         // beneath), inheriting the background from below: here the untouched base cell.
         assert_eq!(r.layers[1].cells[1].flags, FLAG_HAS_BG | FLAG_HAS_GLYPH);
         assert_eq!(r.layers[1].cells[1].bg, r.layers[0].cells[1].bg);
-        // Higher-layer glyph with a real background: both, with its own colour.
+        // Higher-layer glyph with a real background: both, with its own color.
         assert_eq!(r.layers[1].cells[2].flags, FLAG_HAS_BG | FLAG_HAS_GLYPH);
         assert_eq!(r.layers[1].cells[2].bg, [255, 0, 0]);
     }
 
+    #[test]
+    fn draw_layers_resolves_colors_through_the_palette() {
+        use retroglyph_core::color::AnsiColor;
+        use retroglyph_window::palette::Palette;
+
+        let mut r = GlBackendBuilder::new()
+            .grid_size(2, 1)
+            .palette(Palette::CGA.with_background((1, 2, 3)))
+            .build()
+            .expect("default-font builds");
+        let blue = Tile::new('A', Style::new().fg(Color::BLUE));
+        let blank = Tile::default();
+        let plain = Tile::new('B', Style::new());
+        let stream = [
+            DrawCell::on_layer(0, Pos::new(0, 0), &blue),
+            DrawCell::on_layer(0, Pos::new(1, 0), &blank),
+            DrawCell::on_layer(1, Pos::new(0, 0), &blank),
+            DrawCell::on_layer(1, Pos::new(1, 0), &plain),
+        ];
+        r.draw_layers(stream.iter().copied())
+            .expect("draw_layers is infallible");
+
+        assert_eq!(r.layers[0].cells[0].fg, [0x00, 0x00, 0xAA]);
+        assert_eq!(r.layers[0].cells[0].bg, [1, 2, 3]);
+        // `Color::Default` on a higher layer: the palette's foreground, and the background
+        // inherited from the base layer's palette default.
+        assert_eq!(r.layers[1].cells[1].fg, [0xAA, 0xAA, 0xAA]);
+        assert_eq!(r.layers[1].cells[1].bg, [1, 2, 3]);
+
+        r.set_palette(Palette::DEFAULT);
+        r.draw_layers(stream.iter().copied())
+            .expect("draw_layers is infallible");
+        assert_eq!(
+            r.layers[0].cells[0].fg,
+            <[u8; 3]>::from(AnsiColor::Blue.to_rgb())
+        );
+        assert_eq!(r.layers[0].cells[1].bg, [0, 0, 0]);
+    }
+
     #[test]
     fn draw_layers_full_frame_drops_a_removed_higher_layer() {
         let mut r = GlBackendBuilder::new()
@@ -1167,6 +1497,56 @@ mod compositing_tests {
         assert!(!r.diagnostics.has_reported_notdef('A'));
     }
 
+    /// A wide character covered by a double-width font draws the left half of its glyph, and its
+    /// spacer the right half at the lead's offset, on layer 0 and above alike. The fallback to a
+    /// single notdef cell, and its report, only happens for a character no such font covers.
+    #[test]
+    fn draw_layers_splits_a_double_width_glyph_across_its_spacer() {
+        use retroglyph_core::grid::Grid;
+        use retroglyph_window::font::{BitmapFont, FontChain, unscii16};
+
+        static WIDE_DATA: [u8; 32] = [0xFF; 32];
+        const WIDE_CHARSET: [char; 1] = ['語'];
+        let fonts = FontChain::from(unscii16::FONT).with_wide_font(BitmapFont::with_charset(
+            &WIDE_DATA,
+            16,
+            16,
+            1,
+            &WIDE_CHARSET,
+        ));
+        let mut r = GlBackendBuilder::new()
+            .font(fonts)
+            .grid_size(4, 1)
+            .build()
+            .expect("chain builds");
+        let slots = r.glyphs.resolve_wide('語').expect("double-width coverage");
+
+        let mut grid = Grid::new(4, 1);
+        grid.put_tile(0, (0, 0), Tile::new('語', Style::new()).with_offset(1, 2))
+            .unwrap();
+        grid.put_tile(0, (2, 0), Tile::new('あ', Style::new()))
+            .unwrap();
+        grid.put_tile(1, (0, 0), Tile::new('語', Style::new()))
+            .unwrap();
+        r.draw_layers(grid.layers())
+            .expect("draw_layers is infallible");
+
+        for layer in 0..2 {
+            let cells = &r.layers[layer].cells;
+            assert_eq!([cells[0].glyph, cells[1].glyph], slots);
+            assert_eq!(cells[1].flags, FLAG_HAS_BG | FLAG_HAS_GLYPH);
+            assert_eq!((cells[1].dx, cells[1].dy), (cells[0].dx, cells[0].dy));
+        }
+        assert_eq!((r.layers[0].cells[1].dx, r.layers[0].cells[1].dy), (1, 2));
+        assert!(!r.diagnostics.has_reported_notdef('語'));
+        // 'あ' has no double-width glyph here: notdef in its own cell, a blank spacer.
+        assert_eq!(r.layers[0].cells[3].glyph, r.space_glyph);
+        assert_eq!(
+            r.diagnostics.has_reported_notdef('あ'),
+            retroglyph_core::dev::DEV
+        );
+    }
+
     /// The `layer_id != 0` branch is a separate code path from layer 0's; it must report the same
     /// thing.
     #[test]
@@ -1465,4 +1845,91 @@ mod output_conformance_tests {
     fn satisfies_the_output_contract() {
         retroglyph_core::testing::conformance::assert_output_contract(GlObserver::new);
     }
+
+    /// A wide character's lead and spacer land in the cells, and on the layers, where
+    /// [`Headless`](retroglyph_core::backend::Headless) records them: the lead draws the left
+    /// half of its double-width glyph, the spacer the right half at the lead's offset, and one no
+    /// font covers keeps a single notdef cell and a blank spacer. `headless.rs` only compares
+    /// pixels against `retroglyph-software`, which shares this renderer's idea of a wide glyph;
+    /// `Headless` is the cell-level record both of them are drawn from.
+    #[test]
+    fn wide_glyph_cells_match_headless() {
+        use crate::renderer::FLAG_HAS_GLYPH;
+        use retroglyph_core::backend::Headless;
+        use retroglyph_core::color::Style;
+        use retroglyph_core::grid::{Grid, Pos};
+        use retroglyph_core::tile::Tile;
+        use retroglyph_window::font::{BitmapFont, FontChain, unscii16};
+
+        static WIDE_DATA: [u8; 64] = [0xFF; 64];
+        const WIDE_CHARSET: [char; 2] = ['語', '字'];
+        let fonts = FontChain::from(unscii16::FONT).with_wide_font(BitmapFont::with_charset(
+            &WIDE_DATA,
+            16,
+            16,
+            2,
+            &WIDE_CHARSET,
+        ));
+        let (cols, rows) = (6u16, 2u16);
+        let mut renderer = GlBackendBuilder::new()
+            .font(fonts)
+            .grid_size(cols, rows)
+            .build()
+            .expect("chain builds");
+        let mut headless = Headless::new(cols, rows);
+
+        let mut grid = Grid::new(cols, rows);
+        grid.put_tile(0, (0, 0), Tile::new('語', Style::new()))
+            .unwrap();
+        grid.put_tile(0, (2, 0), Tile::new('字', Style::new()).with_offset(1, 2))
+            .unwrap();
+        grid.put_tile(0, (4, 0), Tile::new('あ', Style::new()))
+            .unwrap();
+        grid.put_tile(1, (1, 1), Tile::new('語', Style::new()).with_offset(3, -2))
+            .unwrap();
+        renderer
+            .draw_layers(grid.layers())
+            .expect("draw_layers is infallible");
+        headless
+            .draw_layers(grid.layers())
+            .expect("draw_layers is infallible");
+
+        let recorded = headless.layer_grid();
+        assert_eq!(renderer.layers.len(), usize::from(recorded.max_layer()) + 1);
+        for (layer, drawn) in renderer.layers.iter().enumerate() {
+            // The right half the next spacer draws, and the lead's offset it draws it at.
+            let mut right: Option<(u16, i16, i16)> = None;
+            for y in 0..rows {
+                for x in 0..cols {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let tile = recorded
+                        .tile(layer as u8, Pos::new(x, y))
+                        .copied()
+                        .unwrap_or_default();
+                    let cell = &drawn.cells[usize::from(y) * usize::from(cols) + usize::from(x)];
+                    let at = format!("layer {layer}, ({x}, {y})");
+                    if tile.is_wide_spacer() {
+                        if let Some((slot, dx, dy)) = right.take() {
+                            assert_eq!(cell.glyph, slot, "{at}: the glyph's right half");
+                            assert_eq!((cell.dx, cell.dy), (dx, dy), "{at}: the lead's offset");
+                        } else {
+                            assert_eq!(cell.glyph, renderer.space_glyph, "{at}: a blank");
+                        }
+                    } else if tile.is_wide() {
+                        assert_ne!(cell.flags & FLAG_HAS_GLYPH, 0, "{at}: the lead draws");
+                        assert_eq!((cell.dx, cell.dy), (tile.dx(), tile.dy()), "{at}");
+                        if let Some([left, slot]) = renderer.glyphs.resolve_wide(tile.glyph()) {
+                            assert_eq!(cell.glyph, left, "{at}: the glyph's left half");
+                            right = Some((slot, tile.dx(), tile.dy()));
+                        } else {
+                            let notdef = renderer.glyphs.resolve(tile.glyph()).unwrap_or(0);
+                            assert_eq!(cell.glyph, notdef, "{at}: a single notdef cell");
+                        }
+                    } else {
+                        assert!(right.is_none(), "{at}: a lead with no spacer after it");
+                    }
+                }
+            }
+        }
+    }
 }
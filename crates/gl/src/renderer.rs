@@ -4,7 +4,10 @@
 //! buffer, the projection uniforms, and the glyph atlas texture. It is created once the GL context
 //! exists (from [`Presenter::init_surface`](retroglyph_window::presenter::Presenter::init_surface)) and driven
 //! once per frame: [`upload`](GlResources::upload) pushes changed cells, [`draw`](GlResources::draw)
-//! issues a single `draw_elements_instanced`.
+//! issues a single `draw_elements_instanced`. With CRT effects on, or a
+//! [`ScalingMode`](retroglyph_window::scaling::ScalingMode) that scales the frame, the layers
+//! composite into an offscreen scene texture instead, and [`finish_scene`](GlResources::finish_scene)
+//! draws it to the real target through the post-processing shader and then the scaling shader.
 
 // `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
 // is intentional, so it's allowed crate-locally.
@@ -15,7 +18,11 @@ use crate::shaders::{GlslFlavor, Shader, source};
 #[cfg(feature = "tilesets")]
 use crate::sprites::{SpriteInstance, SpriteSet};
 use glow::HasContext as _;
+use retroglyph_core::grid::LineSize;
 use retroglyph_window::atlas::{ATLAS_COLS, ATLAS_ROWS, AtlasData};
+use retroglyph_window::crt::CrtEffects;
+use retroglyph_window::geometry::Viewport;
+use retroglyph_window::scaling::ScaleFilter;
 
 /// Per-cell instance data, tightly packed to 16 bytes and uploaded straight to the GPU.
 ///
@@ -30,6 +37,7 @@ pub(crate) struct Instance {
     /// Compositing flags ([`FLAG_HAS_BG`] | [`FLAG_HAS_GLYPH`]). A cleared bit makes the matching
     /// pass `discard` this cell, so a transparent background or empty glyph in a higher layer lets
     /// the layer beneath show through: the GPU form of `Grid::flatten_into`'s occlusion rule.
+    /// Bits 2-3 carry the row's line size (see [`line_flags`]).
     pub flags: u8,
     _pad: u8,
     /// Foreground RGB (uploaded as normalized `u8`).
@@ -54,6 +62,19 @@ pub(crate) const FLAG_HAS_BG: u8 = 1 << 0;
 /// it), so a higher layer's untouched cells don't erase the layer beneath.
 pub(crate) const FLAG_HAS_GLYPH: u8 = 1 << 1;
 
+/// [`Instance::flags`] bits 2-3 for a cell on a `line`-sized row: 0 normal, 1 double width, 2 the
+/// top half of a double-height line, 3 the bottom half. The vertex shader widens such a cell's
+/// quad to two columns and, on a double-height half, samples only that half of the glyph.
+pub(crate) const fn line_flags(line: LineSize) -> u8 {
+    let kind = match line {
+        LineSize::Normal => 0,
+        LineSize::DoubleWidth => 1,
+        LineSize::DoubleHeightTop => 2,
+        LineSize::DoubleHeightBottom => 3,
+    };
+    kind << 2
+}
+
 impl Instance {
     /// A cell with the given glyph, colors, sub-cell pixel offset, and compositing flags.
     pub(crate) const fn new(
@@ -118,6 +139,8 @@ pub(crate) struct GlResources {
     /// was loaded.
     #[cfg(feature = "tilesets")]
     sprites: Option<SpriteGpu>,
+    /// The CRT post-processing and scaling programs and their offscreen targets.
+    post: PostGpu,
     u_screen: Option<glow::UniformLocation>,
     u_cell: Option<glow::UniformLocation>,
     u_cols: Option<glow::UniformLocation>,
@@ -190,6 +213,7 @@ impl GlResources {
             gl.bind_vertex_array(None);
 
             let atlas_tex = upload_atlas(gl, atlas)?;
+            let post = PostGpu::new(gl, flavor)?;
 
             let u_screen = gl.get_uniform_location(program, "u_screen");
             let u_cell = gl.get_uniform_location(program, "u_cell");
@@ -214,6 +238,7 @@ impl GlResources {
                 atlas: atlas_tex,
                 #[cfg(feature = "tilesets")]
                 sprites: None,
+                post,
                 u_screen,
                 u_cell,
                 u_cols,
@@ -394,12 +419,175 @@ impl GlResources {
             gl.delete_buffer(self.instance_vbo);
             gl.delete_texture(self.atlas);
         }
+        self.post.delete(gl);
         #[cfg(feature = "tilesets")]
         if let Some(sprites) = &self.sprites {
             sprites.delete(gl);
         }
     }
 
+    /// Redirects the frame into the offscreen scene texture when `presentation` has a stage to
+    /// run after compositing, (re)allocating it at the frame's size on first use or after a
+    /// resize. Call before [`clear`](Self::clear); with no stages this does nothing and the layers
+    /// draw straight to the bound target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SurfaceError::Init`] if the scene texture or its framebuffer can't be created.
+    pub(crate) fn begin_scene(
+        &mut self,
+        gl: &glow::Context,
+        presentation: &Presentation,
+    ) -> Result<(), SurfaceError> {
+        if !presentation.needs_scene() {
+            return Ok(());
+        }
+        let (width, height) = presentation.frame;
+        // SAFETY: `gl` is the live context these resources belong to.
+        unsafe {
+            let scene = SceneTarget::ensure(&mut self.post.scene, gl, width, height, "scene")?;
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(scene.framebuffer));
+        }
+        Ok(())
+    }
+
+    /// Draws the scene [`begin_scene`](Self::begin_scene) redirected the frame into onto `target`
+    /// (`None` for the default framebuffer): through the CRT shader when its effects are on, then
+    /// scaled into the [`Placement`]'s viewport when there is one. No-op with neither.
+    ///
+    /// With both, the CRT pass writes a second frame-sized texture for the scaling pass to read:
+    /// the effects work in frame pixels, so they can't be folded into the scaled draw.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SurfaceError::Init`] if that second texture or its framebuffer can't be created.
+    pub(crate) fn finish_scene(
+        &mut self,
+        gl: &glow::Context,
+        presentation: &Presentation,
+        target: Option<glow::Framebuffer>,
+    ) -> Result<(), SurfaceError> {
+        let scene = self.post.scene.as_ref().map(|scene| scene.texture);
+        let Some(scene) = scene.filter(|_| presentation.needs_scene()) else {
+            return Ok(());
+        };
+        let crt = presentation.crt;
+        let Some(placement) = &presentation.placement else {
+            if crt.is_enabled() {
+                self.draw_crt(gl, &crt, scene, target, presentation.frame);
+            }
+            return Ok(());
+        };
+        let source = if crt.is_enabled() {
+            let (width, height) = presentation.frame;
+            // SAFETY: `gl` is the live context these resources belong to.
+            let crt_out = unsafe {
+                SceneTarget::ensure(&mut self.post.crt_out, gl, width, height, "CRT output")?
+            };
+            let (framebuffer, texture) = (crt_out.framebuffer, crt_out.texture);
+            self.draw_crt(gl, &crt, scene, Some(framebuffer), presentation.frame);
+            texture
+        } else {
+            scene
+        };
+        self.draw_scaled(gl, source, placement, target);
+        Ok(())
+    }
+
+    /// Draws the `width`x`height` frame in `scene` onto `target` through the CRT shader.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_precision_loss
+    )]
+    fn draw_crt(
+        &self,
+        gl: &glow::Context,
+        crt: &CrtEffects,
+        scene: glow::Texture,
+        target: Option<glow::Framebuffer>,
+        (width, height): (u32, u32),
+    ) {
+        let post = &self.post;
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, target);
+            gl.viewport(0, 0, width as i32, height as i32);
+            gl.disable(glow::BLEND);
+            gl.use_program(Some(post.program));
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(scene));
+            gl.uniform_1_i32(post.u_scene.as_ref(), 0);
+            gl.uniform_2_f32(post.u_size.as_ref(), width as f32, height as f32);
+            let [scanlines, glow_strength, curvature, fringe] = crt.to_array();
+            gl.uniform_4_f32(
+                post.u_crt.as_ref(),
+                scanlines,
+                glow_strength,
+                curvature,
+                fringe,
+            );
+            gl.bind_vertex_array(Some(post.vao));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            gl.bind_vertex_array(None);
+        }
+    }
+
+    /// Clears `target` to the placement's bar color and draws the frame in `source` into its
+    /// viewport through the scaling shader, resampled with its filter.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_precision_loss
+    )]
+    fn draw_scaled(
+        &self,
+        gl: &glow::Context,
+        source: glow::Texture,
+        placement: &Placement,
+        target: Option<glow::Framebuffer>,
+    ) {
+        let post = &self.post;
+        let (target_w, target_h) = placement.target;
+        let Viewport { origin, size, .. } = placement.viewport;
+        // GL's viewport is bottom-left origin; `Viewport`'s is top-left. A frame cropped at the
+        // bottom edge puts this below zero, which GL allows.
+        let y = target_h as i32 - (origin.1 + size.1) as i32;
+        let filter = match placement.filter {
+            ScaleFilter::Nearest => glow::NEAREST,
+            ScaleFilter::Linear => glow::LINEAR,
+        };
+        let (r, g, b) = placement.bars;
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, target);
+            gl.viewport(0, 0, target_w as i32, target_h as i32);
+            gl.clear_color(
+                f32::from(r) / 255.0,
+                f32::from(g) / 255.0,
+                f32::from(b) / 255.0,
+                1.0,
+            );
+            gl.clear(glow::COLOR_BUFFER_BIT);
+            gl.viewport(origin.0 as i32, y, size.0 as i32, size.1 as i32);
+            gl.disable(glow::BLEND);
+            gl.use_program(Some(post.scale_program));
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(source));
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, filter as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, filter as i32);
+            gl.uniform_1_i32(post.u_scale_scene.as_ref(), 0);
+            gl.uniform_4_f32(
+                post.u_scale_viewport.as_ref(),
+                origin.0 as f32,
+                y as f32,
+                size.0 as f32,
+                size.1 as f32,
+            );
+            gl.bind_vertex_array(Some(post.vao));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            gl.bind_vertex_array(None);
+        }
+    }
+
     /// Sets the glyph size on the sprite program too (issue #366), so a sprite cell's `dx`/`dy`
     /// pixel offset scales correctly. No-op without a sprite atlas.
     #[cfg(feature = "tilesets")]
@@ -559,9 +747,179 @@ const fn bytemuck_u16(data: &[u16]) -> &[u8] {
     unsafe { core::slice::from_raw_parts(data.as_ptr().cast::<u8>(), size_of_val(data)) }
 }
 
+/// What happens to a composited frame on its way to the target: the CRT pass, then scaling.
+pub(crate) struct Presentation {
+    /// The CRT effects; skipped while they're off.
+    pub crt: CrtEffects,
+    /// The frame's own size in pixels, which the layers composite at.
+    pub frame: (u32, u32),
+    /// Where the frame lands on the target, or `None` to draw it 1:1 at the origin.
+    pub placement: Option<Placement>,
+}
+
+impl Presentation {
+    /// Whether any stage runs after compositing, which then has to composite offscreen.
+    pub(crate) const fn needs_scene(&self) -> bool {
+        self.crt.is_enabled() || self.placement.is_some()
+    }
+}
+
+/// Where and how the scaling pass draws a frame onto its target.
+pub(crate) struct Placement {
+    /// The target's size in pixels.
+    pub target: (u32, u32),
+    /// The rectangle of the target the frame is scaled into.
+    pub viewport: Viewport,
+    /// How the frame is resampled.
+    pub filter: ScaleFilter,
+    /// The color the rest of the target is cleared to.
+    pub bars: (u8, u8, u8),
+}
+
+/// The CRT post-processing and scaling programs, plus the offscreen frames they read (created on
+/// first use).
+struct PostGpu {
+    program: glow::Program,
+    /// An empty VAO: the fullscreen triangle comes from `gl_VertexID`, but core profiles still
+    /// require a VAO bound to draw.
+    vao: glow::VertexArray,
+    u_scene: Option<glow::UniformLocation>,
+    u_size: Option<glow::UniformLocation>,
+    u_crt: Option<glow::UniformLocation>,
+    /// The scaling pass, sharing the CRT pass's vertex stage.
+    scale_program: glow::Program,
+    u_scale_scene: Option<glow::UniformLocation>,
+    u_scale_viewport: Option<glow::UniformLocation>,
+    /// The layers' composited frame. `None` until a stage first needs it.
+    scene: Option<SceneTarget>,
+    /// The CRT pass's output, when the scaling pass reads it rather than the CRT pass drawing to
+    /// the target directly. `None` until then.
+    crt_out: Option<SceneTarget>,
+}
+
+/// An `RGBA8` frame-sized texture a pass renders into, attached to its own framebuffer.
+struct SceneTarget {
+    framebuffer: glow::Framebuffer,
+    texture: glow::Texture,
+    size: (u32, u32),
+}
+
+impl PostGpu {
+    unsafe fn new(gl: &glow::Context, flavor: GlslFlavor) -> Result<Self, SurfaceError> {
+        unsafe {
+            let program =
+                build_program_stages(gl, flavor, Shader::PostVertex, Shader::PostFragment)?;
+            let scale_program =
+                build_program_stages(gl, flavor, Shader::PostVertex, Shader::ScaleFragment)?;
+            let vao = gl
+                .create_vertex_array()
+                .map_err(|e| SurfaceError::Init(format!("create post VAO: {e}")))?;
+            Ok(Self {
+                program,
+                vao,
+                u_scene: gl.get_uniform_location(program, "u_scene"),
+                u_size: gl.get_uniform_location(program, "u_size"),
+                u_crt: gl.get_uniform_location(program, "u_crt"),
+                scale_program,
+                u_scale_scene: gl.get_uniform_location(scale_program, "u_scene"),
+                u_scale_viewport: gl.get_uniform_location(scale_program, "u_viewport"),
+                scene: None,
+                crt_out: None,
+            })
+        }
+    }
+
+    fn delete(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_program(self.scale_program);
+            gl.delete_vertex_array(self.vao);
+        }
+        for target in [&self.scene, &self.crt_out].into_iter().flatten() {
+            target.delete(gl);
+        }
+    }
+}
+
+impl SceneTarget {
+    /// The target in `slot`, sized `width`x`height`; a size change rebuilds it. `what` names it
+    /// in errors.
+    #[allow(clippy::cast_possible_wrap)]
+    unsafe fn ensure<'a>(
+        slot: &'a mut Option<Self>,
+        gl: &glow::Context,
+        width: u32,
+        height: u32,
+        what: &str,
+    ) -> Result<&'a Self, SurfaceError> {
+        unsafe {
+            if let Some(target) = slot.take() {
+                if target.size == (width, height) {
+                    return Ok(slot.insert(target));
+                }
+                target.delete(gl);
+            }
+            let texture = gl
+                .create_texture()
+                .map_err(|e| SurfaceError::Init(format!("create {what} texture: {e}")))?;
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA8 as i32,
+                width as i32,
+                height as i32,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(None),
+            );
+            for (param, value) in [
+                (glow::TEXTURE_MIN_FILTER, glow::NEAREST),
+                (glow::TEXTURE_MAG_FILTER, glow::NEAREST),
+                (glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE),
+                (glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE),
+            ] {
+                gl.tex_parameter_i32(glow::TEXTURE_2D, param, value as i32);
+            }
+            let framebuffer = gl
+                .create_framebuffer()
+                .map_err(|e| SurfaceError::Init(format!("create {what} framebuffer: {e}")))?;
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+            gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            let target = Self {
+                framebuffer,
+                texture,
+                size: (width, height),
+            };
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                target.delete(gl);
+                return Err(SurfaceError::Init(format!(
+                    "{what} framebuffer incomplete: {status:#06x}"
+                )));
+            }
+            Ok(slot.insert(target))
+        }
+    }
+
+    fn delete(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_framebuffer(self.framebuffer);
+            gl.delete_texture(self.texture);
+        }
+    }
+}
+
 /// Byte stride of one [`SpriteInstance`], as GL wants it (`i32`).
 #[cfg(feature = "tilesets")]
-pub(crate) const SPRITE_STRIDE: i32 = 24;
+pub(crate) const SPRITE_STRIDE: i32 = 96;
 
 /// Initial sprite-instance buffer capacity, in sprites. `draw` grows it when a layer needs more.
 #[cfg(feature = "tilesets")]
@@ -632,7 +990,7 @@ impl SpriteGpu {
             gl.enable_vertex_attrib_array(4);
             gl.vertex_attrib_divisor(4, 1);
             // a_mask (4 u8 @14, raw 0..255), a_tint (4 u8 @18, raw 0..255), a_tint_op (1 u16 @22).
-            // Unnormalized `IPointer` uploads so the fragment shader recolours in exact integer
+            // Unnormalized `IPointer` uploads so the fragment shader recolors in exact integer
             // math (matching `Tint::apply`'s `u8` arithmetic) instead of lossy normalized floats.
             gl.vertex_attrib_pointer_i32(5, 4, glow::UNSIGNED_BYTE, SPRITE_STRIDE, 14);
             gl.enable_vertex_attrib_array(5);
@@ -643,6 +1001,26 @@ impl SpriteGpu {
             gl.vertex_attrib_pointer_i32(7, 1, glow::UNSIGNED_SHORT, SPRITE_STRIDE, 22);
             gl.enable_vertex_attrib_array(7);
             gl.vertex_attrib_divisor(7, 1);
+            // a_transform (2 u16 @24): flip/turn bits, then scale.
+            gl.vertex_attrib_pointer_i32(8, 2, glow::UNSIGNED_SHORT, SPRITE_STRIDE, 24);
+            gl.enable_vertex_attrib_array(8);
+            gl.vertex_attrib_divisor(8, 1);
+            // a_remap (2 u16 @28): the remap kind and entry count, then its source and target
+            // colors as two uvec4s each (8 u32 @32 and @64), packed `0x00RRGGBB`.
+            gl.vertex_attrib_pointer_i32(9, 2, glow::UNSIGNED_SHORT, SPRITE_STRIDE, 28);
+            gl.enable_vertex_attrib_array(9);
+            gl.vertex_attrib_divisor(9, 1);
+            for (location, offset) in [(10, 32), (11, 48), (12, 64), (13, 80)] {
+                gl.vertex_attrib_pointer_i32(
+                    location,
+                    4,
+                    glow::UNSIGNED_INT,
+                    SPRITE_STRIDE,
+                    offset,
+                );
+                gl.enable_vertex_attrib_array(location);
+                gl.vertex_attrib_divisor(location, 1);
+            }
             gl.bind_vertex_array(None);
 
             let atlas = upload_sprite_atlas(gl, set)?;
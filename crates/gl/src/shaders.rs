@@ -42,7 +42,7 @@ layout(location = 1) in uint  a_glyph;  // atlas layer (glyph id), per instance
 layout(location = 2) in vec3  a_fg;     // foreground RGB (normalized u8), per instance
 layout(location = 3) in vec3  a_bg;     // background RGB (normalized u8), per instance
 layout(location = 4) in ivec2 a_offset; // sub-cell (dx, dy) in unscaled font pixels, per instance
-layout(location = 5) in uint  a_flags;  // compositing flags: bit0 = has bg, bit1 = has glyph
+layout(location = 5) in uint  a_flags;  // bit0 = has bg, bit1 = has glyph, bits 2-3 = line size
 
 uniform vec2 u_screen;     // surface size in physical pixels
 uniform vec2 u_cell;       // cell size in physical pixels (glyph size * scale)
@@ -59,20 +59,27 @@ out vec2 v_uv;
 void main() {
     int col = gl_InstanceID % u_cols;
     int row = gl_InstanceID / u_cols;
-    vec2 origin = vec2(float(col), float(row)) * u_cell;
-    vec2 px = origin + a_corner * u_cell;
+    // A double-size row (line size 1-3) draws each cell two columns wide, and a double-height half
+    // (2 = top, 3 = bottom) stretches the glyph to twice the row's height and shows only its half.
+    uint line = (a_flags >> 2) & 3u;
+    vec2 stretch = vec2(line == 0u ? 1.0 : 2.0, line >= 2u ? 2.0 : 1.0);
+    vec2 origin = vec2(float(col) * stretch.x, float(row)) * u_cell;
+    vec2 px = origin + a_corner * u_cell * vec2(stretch.x, 1.0);
     // On the glyph pass, shift the whole quad by the sub-cell offset so the glyph is free to spill
     // past the cell edge into neighbors. dx/dy are in unscaled font pixels; u_cell / u_glyph is the
     // integer scale, so this converts them to physical pixels. The background pass leaves the quad
     // pinned to the cell, so backgrounds never move.
     if (u_draw_glyph == 1) {
-        px += vec2(a_offset) * (u_cell / u_glyph);
+        px += vec2(a_offset) * (u_cell / u_glyph) * stretch;
     }
     // Pixel space (y-down, origin top-left) -> clip space (y-up). Flipping y here means the atlas
     // can store glyph row 0 first and sample with v_uv.y = a_corner.y (0 at the cell's top).
     vec2 clip = vec2(px.x / u_screen.x * 2.0 - 1.0, 1.0 - px.y / u_screen.y * 2.0);
     gl_Position = vec4(clip, 0.0, 1.0);
     v_uv = a_corner;
+    if (line >= 2u) {
+        v_uv.y = a_corner.y * 0.5 + (line == 3u ? 0.5 : 0.0);
+    }
     v_glyph = a_glyph;
     v_fg = a_fg;
     v_bg = a_bg;
@@ -140,8 +147,14 @@ layout(location = 2) in uint  a_layer;  // sprite atlas array layer
 layout(location = 3) in uvec2 a_sprite; // sprite size in unscaled pixels (may exceed a cell)
 layout(location = 4) in ivec2 a_offset; // sub-cell (dx, dy) in unscaled pixels
 layout(location = 5) in uvec4 a_mask;   // sheet stage: rgb = multiply factor, a = 255 when active
-layout(location = 6) in uvec4 a_tint;   // cell stage: rgb = colour, a = mix amount
+layout(location = 6) in uvec4 a_tint;   // cell stage: rgb = color, a = mix amount
 layout(location = 7) in uint  a_tint_op; // 0 none, 1 multiply, 2 mix
+layout(location = 8) in uvec2 a_transform; // x = flip X (bit 0), flip Y (bit 1), turns (bits 2-3); y = scale
+layout(location = 9) in uvec2 a_remap;        // x = 0 none, 1 colors, 2 ramp; y = entry count
+layout(location = 10) in uvec4 a_remap_from0; // remap sources 0-3, packed 0x00RRGGBB
+layout(location = 11) in uvec4 a_remap_from1; // remap sources 4-7
+layout(location = 12) in uvec4 a_remap_to0;   // remap targets 0-3, packed the same way
+layout(location = 13) in uvec4 a_remap_to1;   // remap targets 4-7
 
 uniform vec2 u_screen;     // surface size in physical pixels
 uniform vec2 u_cell;       // cell size in physical pixels (glyph size * scale)
@@ -154,34 +167,66 @@ flat out vec2 v_uv_scale; // maps the [0,1] quad onto the sprite's sub-rect with
 flat out uvec4 v_mask;
 flat out uvec4 v_tint;
 flat out uint v_tint_op;
+flat out uvec2 v_remap;
+flat out uvec4 v_remap_from0;
+flat out uvec4 v_remap_from1;
+flat out uvec4 v_remap_to0;
+flat out uvec4 v_remap_to1;
 
 void main() {
     vec2 scale = u_cell / u_glyph;
     vec2 origin = vec2(a_cell) * u_cell + vec2(a_offset) * scale;
-    vec2 px = origin + a_corner * (vec2(a_sprite) * scale);
+    // An odd number of quarter turns swaps the quad's sides; the transform's own scale multiplies
+    // the renderer's.
+    uint turns = (a_transform.x >> 2u) & 3u;
+    vec2 size = (turns & 1u) == 1u ? vec2(a_sprite.yx) : vec2(a_sprite);
+    vec2 px = origin + a_corner * (size * float(a_transform.y) * scale);
     vec2 clip = vec2(px.x / u_screen.x * 2.0 - 1.0, 1.0 - px.y / u_screen.y * 2.0);
     gl_Position = vec4(clip, 0.0, 1.0);
-    v_uv = a_corner;
+    // The corner's place in the source sprite: `Transform::source_pixel` in normalized
+    // coordinates. The rotation is undone first, then the flips.
+    vec2 uv = a_corner;
+    if (turns == 1u) {
+        uv = vec2(a_corner.y, 1.0 - a_corner.x);
+    } else if (turns == 2u) {
+        uv = vec2(1.0) - a_corner;
+    } else if (turns == 3u) {
+        uv = vec2(1.0 - a_corner.y, a_corner.x);
+    }
+    if ((a_transform.x & 1u) != 0u) {
+        uv.x = 1.0 - uv.x;
+    }
+    if ((a_transform.x & 2u) != 0u) {
+        uv.y = 1.0 - uv.y;
+    }
+    v_uv = uv;
     v_layer = a_layer;
     v_uv_scale = vec2(a_sprite) / u_sprite_tex;
     v_mask = a_mask;
     v_tint = a_tint;
     v_tint_op = a_tint_op;
+    v_remap = a_remap;
+    v_remap_from0 = a_remap_from0;
+    v_remap_from1 = a_remap_from1;
+    v_remap_to0 = a_remap_to0;
+    v_remap_to1 = a_remap_to1;
 }
 ";
 
 /// Fragment shader body for the RGBA sprite pass. Samples the sprite's sub-rect (top-left of its
-/// layer, the rest of the layer is transparent padding), recolours it, and outputs straight-alpha
+/// layer, the rest of the layer is transparent padding), recolors it, and outputs straight-alpha
 /// RGBA; the caller enables source-over blending, so a sprite's transparent pixels let the layers
 /// below show through.
 ///
-/// The recolour is two stages in the order `retroglyph_window::sprite_cache::SpriteTint::apply`
-/// defines: the sheet's own treatment (a multiply by the cell's foreground for a mask sheet),
-/// then the cell's tint. Alpha is never touched by either, so which pixels are opaque, and
-/// therefore the blending above, is identical tinted or not.
+/// The recolor follows the order `retroglyph_window::sprite_cache::SpriteTint::apply` defines:
+/// the cell's palette swap, or for a pixel it leaves alone the sheet's own treatment (a multiply
+/// by the cell's foreground for a mask sheet), then the cell's tint. Alpha is never touched by
+/// any of them, so which pixels are opaque, and therefore the blending above, is identical tinted
+/// or not.
 ///
 /// The math is a bit-exact GLSL translation of `gem::channel::multiply_u8`/`mix_u8`, the two
-/// functions `retroglyph_core::color::Tint::apply` calls, done in `uint`/`int` on the source texel's
+/// functions `retroglyph_core::color::Tint::apply` calls, and of `Remap::lookup`'s table search and
+/// luma, done in `uint`/`int` on the source texel's
 /// reconstructed 0..255 channel values rather than in normalized float. This mirrors
 /// `SpriteTint::apply` exactly rather than merely approximating it: the CPU rasteriser calls that
 /// function directly, and a parity test renders the same sprite through both to keep them honest
@@ -196,6 +241,11 @@ flat in vec2 v_uv_scale;
 flat in uvec4 v_mask;
 flat in uvec4 v_tint;
 flat in uint v_tint_op;
+flat in uvec2 v_remap;
+flat in uvec4 v_remap_from0;
+flat in uvec4 v_remap_from1;
+flat in uvec4 v_remap_to0;
+flat in uvec4 v_remap_to1;
 
 out vec4 frag;
 
@@ -205,13 +255,22 @@ uint multiply_u8(uint a, uint b) {
 }
 
 // Mirrors `gem::channel::mix_u8`. `b - a` can be negative, so this runs in `int`, matching the
-// round-half-away-from-zero behaviour of the Rust reference (`delta >= 0` rounds up, else down).
+// round-half-away-from-zero behavior of the Rust reference (`delta >= 0` rounds up, else down).
 int mix_u8(int a, int b, int t) {
     int delta = (b - a) * t;
     int rounded = delta >= 0 ? (delta + 127) / 255 : (delta - 127) / 255;
     return a + rounded;
 }
 
+// Entry `i` of a remap table split across two `uvec4`s.
+uint remap_entry(uvec4 lo, uvec4 hi, uint i) {
+    return i < 4u ? lo[i] : hi[i - 4u];
+}
+
+uvec3 unpack_rgb(uint c) {
+    return uvec3((c >> 16u) & 255u, (c >> 8u) & 255u, c & 255u);
+}
+
 void main() {
     vec2 uv = v_uv * v_uv_scale;
     vec4 src = texture(u_sprites, vec3(uv, float(v_layer)));
@@ -228,6 +287,23 @@ void main() {
         v_mask.a == 0u ? src_u8.b : multiply_u8(src_u8.b, v_mask.b)
     );
 
+    // Ahead of stage 1, in effect: a pixel the remap maps replaces stage 1's result with its
+    // target. Kind 1 searches for an exact source color, first entry winning; kind 2 picks the
+    // ramp shade nearest the pixel's integer luma.
+    if (v_remap.x == 1u) {
+        uint key = (src_u8.r << 16u) | (src_u8.g << 8u) | src_u8.b;
+        for (uint i = 0u; i < v_remap.y; i++) {
+            if (remap_entry(v_remap_from0, v_remap_from1, i) == key) {
+                rgb = unpack_rgb(remap_entry(v_remap_to0, v_remap_to1, i));
+                break;
+            }
+        }
+    } else if (v_remap.x == 2u) {
+        uint gray = (77u * src_u8.r + 150u * src_u8.g + 29u * src_u8.b + 128u) >> 8u;
+        uint index = (gray * (v_remap.y - 1u) + 127u) / 255u;
+        rgb = unpack_rgb(remap_entry(v_remap_to0, v_remap_to1, index));
+    }
+
     // Stage 2: the cell's tint.
     if (v_tint_op == 1u) {
         rgb = uvec3(
@@ -248,25 +324,115 @@ void main() {
 }
 ";
 
+/// Vertex shader body for the CRT post-processing pass: one triangle that covers the whole
+/// viewport, generated from `gl_VertexID` so the pass needs no vertex buffer.
+const VERTEX_POST_BODY: &str = r"
+void main() {
+    // Vertices 0, 1, 2 land on (0, 0), (2, 0), (0, 2): a triangle whose [0, 1] corner is the screen.
+    vec2 corner = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    gl_Position = vec4(corner * 2.0 - 1.0, 0.0, 1.0);
+}
+";
+
+/// Fragment shader body for the CRT post-processing pass. Reads the composited frame from
+/// `u_scene` and applies the per-pixel recipe `retroglyph_window::crt` specifies, step for step
+/// and in the same operation order as `retroglyph-software`'s CPU reference, which the headless
+/// render tests compare it against.
+///
+/// Frame coordinates are top-left origin like the CPU frame's, so `gl_FragCoord`'s bottom-left
+/// y is flipped on the way in and `fetch` flips it back to address the texture.
+const FRAGMENT_POST_BODY: &str = r"
+uniform highp sampler2D u_scene;
+uniform vec2 u_size; // frame size in physical pixels
+uniform vec4 u_crt;  // scanlines, glow, curvature, fringe
+
+out vec4 frag;
+
+// The frame pixel at top-left-origin `p`, clamped to the frame.
+vec3 fetch(ivec2 p) {
+    ivec2 size = ivec2(u_size);
+    ivec2 q = clamp(p, ivec2(0), size - 1);
+    return texelFetch(u_scene, ivec2(q.x, size.y - 1 - q.y), 0).rgb;
+}
+
+void main() {
+    float scanlines = u_crt.x;
+    float glow = u_crt.y;
+    float curvature = u_crt.z;
+    float fringe = u_crt.w;
+
+    // Curvature: warp the pixel center in [-1, 1] space; samples past the edge are black.
+    vec2 c = vec2(gl_FragCoord.x, u_size.y - gl_FragCoord.y) / u_size * 2.0 - 1.0;
+    vec2 p = (c * (1.0 + curvature * (c.yx * c.yx)) + 1.0) * 0.5 * u_size;
+    if (!(p.x >= 0.0 && p.y >= 0.0 && p.x < u_size.x && p.y < u_size.y)) {
+        frag = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    ivec2 s = ivec2(floor(p));
+
+    // Fringe: red from the right, blue from the left.
+    vec3 rgb = vec3(
+        fetch(ivec2(int(floor(p.x + fringe)), s.y)).r,
+        fetch(s).g,
+        fetch(ivec2(int(floor(p.x - fringe)), s.y)).b
+    );
+
+    // Glow: the mean of the nine taps 2 pixels apart, added on top.
+    vec3 sum = vec3(0.0);
+    for (int dy = -2; dy <= 2; dy += 2) {
+        for (int dx = -2; dx <= 2; dx += 2) {
+            sum += fetch(s + ivec2(dx, dy));
+        }
+    }
+    rgb += glow * sum / 9.0;
+
+    // Scanlines: darken odd rows.
+    if ((s.y & 1) == 1) {
+        rgb *= 1.0 - scanlines;
+    }
+
+    frag = vec4(clamp(rgb, 0.0, 1.0), 1.0);
+}
+";
+
+/// Fragment shader body for the scaling pass: draws a finished frame into the viewport a
+/// `retroglyph_window::scaling::ScalingMode` places it at. Drawn with the GL viewport set to that
+/// rectangle, so the post vertex stage's fullscreen triangle covers exactly it; the texture's own
+/// filter (nearest or linear) does the resampling.
+const FRAGMENT_SCALE_BODY: &str = r"
+uniform highp sampler2D u_scene;
+uniform vec4 u_viewport; // x, y (bottom-left origin), width, height in target pixels
+
+out vec4 frag;
+
+void main() {
+    vec2 uv = (gl_FragCoord.xy - u_viewport.xy) / u_viewport.zw;
+    frag = vec4(texture(u_scene, uv).rgb, 1.0);
+}
+";
+
 /// Builds a complete shader source string for `flavor`, prepending the right `#version` line (and,
 /// for ES, the precision qualifiers a fragment shader needs).
 pub(crate) fn source(flavor: GlslFlavor, body: Shader) -> String {
     let mut out = String::new();
-    let is_fragment = match body {
-        Shader::Fragment => true,
+    let sampler = match body {
+        Shader::Fragment => Some("sampler2DArray"),
         #[cfg(feature = "tilesets")]
-        Shader::SpriteFragment => true,
-        _ => false,
+        Shader::SpriteFragment => Some("sampler2DArray"),
+        Shader::PostFragment | Shader::ScaleFragment => Some("sampler2D"),
+        _ => None,
     };
     match flavor {
         GlslFlavor::Desktop330 => out.push_str("#version 330 core\n"),
         GlslFlavor::Es300 => {
             out.push_str("#version 300 es\n");
-            // ES requires explicit default precision. The fragment shaders also sample an array
-            // texture, so give both float and the sampler a high precision default.
+            // ES requires explicit default precision. The fragment shaders also sample a texture,
+            // so give both float and that sampler type a high precision default.
             out.push_str("precision highp float;\nprecision highp int;\n");
-            if is_fragment {
-                out.push_str("precision highp sampler2DArray;\n");
+            if let Some(sampler) = sampler {
+                out.push_str("precision highp ");
+                out.push_str(sampler);
+                out.push_str(";\n");
             }
         }
     }
@@ -277,6 +443,9 @@ pub(crate) fn source(flavor: GlslFlavor, body: Shader) -> String {
         Shader::SpriteVertex => VERTEX_SPRITE_BODY,
         #[cfg(feature = "tilesets")]
         Shader::SpriteFragment => FRAGMENT_SPRITE_BODY,
+        Shader::PostVertex => VERTEX_POST_BODY,
+        Shader::PostFragment => FRAGMENT_POST_BODY,
+        Shader::ScaleFragment => FRAGMENT_SCALE_BODY,
     });
     out
 }
@@ -294,6 +463,13 @@ pub(crate) enum Shader {
     /// The sprite fragment stage ([`FRAGMENT_SPRITE_BODY`], issue #366).
     #[cfg(feature = "tilesets")]
     SpriteFragment,
+    /// The CRT post-processing vertex stage ([`VERTEX_POST_BODY`]).
+    PostVertex,
+    /// The CRT post-processing fragment stage ([`FRAGMENT_POST_BODY`]).
+    PostFragment,
+    /// The scaling-pass fragment stage ([`FRAGMENT_SCALE_BODY`]); pairs with
+    /// [`PostVertex`](Self::PostVertex).
+    ScaleFragment,
 }
 
 #[cfg(test)]
@@ -402,4 +578,33 @@ mod tests {
         );
         assert!(fs.contains("discard"), "fragment missing discard");
     }
+
+    #[test]
+    fn post_fragment_samples_a_2d_scene_and_applies_every_effect() {
+        let fs = source(GlslFlavor::Es300, Shader::PostFragment);
+        assert!(fs.contains("precision highp sampler2D;"));
+        assert!(!fs.contains("sampler2DArray"));
+        for needle in [
+            "uniform vec4 u_crt",
+            "curvature * (c.yx * c.yx)",
+            "floor(p.x + fringe)",
+            "glow * sum / 9.0",
+            "rgb *= 1.0 - scanlines",
+        ] {
+            assert!(fs.contains(needle), "post fragment missing `{needle}`");
+        }
+        let vs = source(GlslFlavor::Desktop330, Shader::PostVertex);
+        assert!(
+            vs.contains("gl_VertexID"),
+            "post vertex must need no buffers"
+        );
+    }
+
+    #[test]
+    fn scale_fragment_maps_the_viewport_onto_the_whole_scene() {
+        let fs = source(GlslFlavor::Es300, Shader::ScaleFragment);
+        assert!(fs.contains("precision highp sampler2D;"));
+        assert!(fs.contains("(gl_FragCoord.xy - u_viewport.xy) / u_viewport.zw"));
+        assert!(fs.contains("texture(u_scene, uv)"));
+    }
 }
@@ -13,7 +13,10 @@
 // is intentional, so it's allowed crate-locally.
 #![allow(clippy::redundant_pub_crate)]
 
-use retroglyph_core::color::Tint;
+use core::time::Duration;
+use retroglyph_core::color::{Remap, Tint};
+use retroglyph_core::tile::Transform;
+use retroglyph_window::animation::SpriteAnimations;
 use retroglyph_window::sprite_cache::{SpriteCache, SpriteTint};
 use retroglyph_window::tileset::{SheetColor, SpriteAlign};
 use std::collections::HashMap;
@@ -35,29 +38,32 @@ pub(crate) struct SpriteSlot {
 
 impl SpriteSlot {
     /// The offset, in unscaled pixels, from the anchor cell's top-left corner to this sprite's
-    /// own top-left pixel, for a span of `span_w` x `span_h` cells of `glyph_w` x `glyph_h`.
-    pub(crate) fn align_offset(
+    /// own top-left pixel, for a span of `span_w` x `span_h` cells of `glyph_w` x `glyph_h`, with
+    /// the sprite drawn at its `transform`ed size.
+    pub(crate) const fn align_offset(
         self,
+        transform: Transform,
         span_w: u16,
         span_h: u16,
         glyph_w: u16,
         glyph_h: u16,
     ) -> (i16, i16) {
-        self.align.offset_in_span(
-            u32::from(self.w),
-            u32::from(self.h),
-            span_w,
-            span_h,
-            glyph_w,
-            glyph_h,
-        )
+        let (w, h) = self.size(transform);
+        self.align
+            .offset_in_span(w, h, span_w, span_h, glyph_w, glyph_h)
+    }
+
+    /// The size, in unscaled pixels, this sprite is drawn at under `transform`.
+    pub(crate) const fn size(self, transform: Transform) -> (u32, u32) {
+        transform.size(self.w as u32, self.h as u32)
     }
 }
 
 /// One sprite instance for the sprite draw pass: which cell, which atlas layer, the sprite's pixel
-/// size, the sub-cell offset, and how the sprite is recoloured. Matches the
-/// `a_cell`/`a_layer`/`a_sprite`/`a_offset`/`a_mask`/`a_tint`/`a_tint_op` attributes in the
-/// sprite vertex shader (see `shaders.rs`). `#[repr(C)]`, 24 bytes.
+/// size, the sub-cell offset, how the sprite is recolored, and how it is flipped, turned, and
+/// scaled. Matches the `a_cell`/`a_layer`/`a_sprite`/`a_offset`/`a_mask`/`a_tint`/`a_tint_op`/
+/// `a_transform`/`a_remap`/`a_remap_from*`/`a_remap_to*` attributes in the sprite vertex shader
+/// (see `shaders.rs`). `#[repr(C)]`, 96 bytes.
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub(crate) struct SpriteInstance {
@@ -75,17 +81,37 @@ pub(crate) struct SpriteInstance {
     /// Sheet stage (`a_mask`): RGB multiply factor, with `a` = 255 when the sheet is a mask and
     /// 0 when it is art, so the shader can select without a branch.
     pub mask: [u8; 4],
-    /// Cell stage (`a_tint`): RGB colour, with `a` carrying `Tint::Mix`'s amount.
+    /// Cell stage (`a_tint`): RGB color, with `a` carrying `Tint::Mix`'s amount.
     pub tint: [u8; 4],
     /// Which operation the cell stage is: 0 none, 1 multiply, 2 mix. Matches `Tint`'s variants.
     ///
-    /// A `u16` rather than a `u8` so the struct lands on 24 bytes with no tail padding, which is
-    /// the stride `renderer.rs` declares to the vertex array.
+    /// A `u16` rather than a `u8` so the fields after it stay 2-byte aligned with no padding,
+    /// which keeps the struct at the stride `renderer.rs` declares to the vertex array.
     pub tint_op: u16,
+    /// `a_transform.x`: the flip and quarter-turn bits of [`Transform`], laid out the same way
+    /// (bit 0 flips X, bit 1 flips Y, bits 2-3 are clockwise turns).
+    pub transform: u16,
+    /// `a_transform.y`: [`Transform::scale`].
+    pub scale: u16,
+    /// `a_remap.x`: which kind of [`Remap`] the remap stage is: 0 none, 1 exact colors, 2 ramp.
+    pub remap_op: u16,
+    /// `a_remap.y`: how many of the entries below are in use.
+    pub remap_len: u16,
+    /// `a_remap_from0`/`a_remap_from1`: each [`Remap::sources`] color packed `0x00RRGGBB`, the
+    /// shape the shader packs the sampled texel into to compare. Unused by a ramp.
+    pub remap_from: [u32; Remap::MAX_ENTRIES],
+    /// `a_remap_to0`/`a_remap_to1`: each [`Remap::targets`] color, packed the same way.
+    pub remap_to: [u32; Remap::MAX_ENTRIES],
+}
+
+/// `rgb` packed `0x00RRGGBB`, the form the remap stage's colors take in [`SpriteInstance`].
+const fn pack_rgb((r, g, b): (u8, u8, u8)) -> u32 {
+    (r as u32) << 16 | (g as u32) << 8 | b as u32
 }
 
 impl SpriteInstance {
-    #[allow(clippy::too_many_arguments)]
+    // `remap_len` is at most `Remap::MAX_ENTRIES`.
+    #[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
     pub(crate) const fn new(
         col: u16,
         row: u16,
@@ -94,20 +120,39 @@ impl SpriteInstance {
         h: u16,
         dx: i16,
         dy: i16,
-        recolour: SpriteTint,
+        recolor: SpriteTint,
     ) -> Self {
-        let mask = match recolour.mask {
+        let mask = match recolor.mask {
             Tint::Multiply { r, g, b } => [r, g, b, 255],
             _ => [255, 255, 255, 0],
         };
-        let (tint, tint_op) = match recolour.tint {
+        let (tint, tint_op) = match recolor.tint {
             Tint::Multiply { r, g, b } => ([r, g, b, 255], 1),
             Tint::Mix { r, g, b, amount } => ([r, g, b, amount], 2),
             // `Tint::None`, and any operation added to that `#[non_exhaustive]` enum after this
             // renderer was written: dropped rather than guessed at, so the artwork renders as
-            // authored instead of being recoloured by a misread payload.
+            // authored instead of being recolored by a misread payload.
             _ => ([0, 0, 0, 0], 0),
         };
+        let remap = recolor.remap;
+        let remap_op = if remap.is_identity() {
+            0
+        } else if remap.is_ramp() {
+            2
+        } else {
+            1
+        };
+        let mut remap_from = [0; Remap::MAX_ENTRIES];
+        let mut remap_to = [0; Remap::MAX_ENTRIES];
+        let (sources, targets) = (remap.sources(), remap.targets());
+        let mut i = 0;
+        while i < targets.len() {
+            if i < sources.len() {
+                remap_from[i] = pack_rgb(sources[i]);
+            }
+            remap_to[i] = pack_rgb(targets[i]);
+            i += 1;
+        }
         Self {
             col,
             row,
@@ -119,7 +164,28 @@ impl SpriteInstance {
             mask,
             tint,
             tint_op,
+            transform: 0,
+            scale: 1,
+            remap_op,
+            remap_len: targets.len() as u16,
+            remap_from,
+            remap_to,
+        }
+    }
+
+    /// This instance drawn under `transform`. `w`/`h` stay the sprite's own size, which is what
+    /// the shader samples the atlas by; the quad it covers is sized from both.
+    pub(crate) const fn with_transform(mut self, transform: Transform) -> Self {
+        let mut bits = transform.quarter_turns() << 2;
+        if transform.is_flipped_x() {
+            bits |= 1;
+        }
+        if transform.is_flipped_y() {
+            bits |= 2;
         }
+        self.transform = bits as u16;
+        self.scale = transform.scale() as u16;
+        self
     }
 }
 
@@ -127,11 +193,13 @@ impl SpriteInstance {
 pub(crate) struct SpriteSet {
     /// `char` -> array layer.
     slots: HashMap<char, u16>,
+    /// The cache's animated codepoints, resolved to a frame's `char` before the `slots` lookup.
+    animations: SpriteAnimations,
     /// Per-layer sprite pixel size `(w, h)`.
     sizes: Vec<(u16, u16)>,
     /// Per-layer sprite placement within a span's cell box.
     aligns: Vec<SpriteAlign>,
-    /// Per-layer sheet colour mode, carried through so a mask sheet can be recoloured by `fg`.
+    /// Per-layer sheet color mode, carried through so a mask sheet can be recolored by `fg`.
     colors: Vec<SheetColor>,
     /// One layer's texture size in texels: the max sprite `(w, h)` across the set.
     tex_w: u32,
@@ -189,6 +257,7 @@ impl SpriteSet {
 
         Some(Self {
             slots,
+            animations: cache.animations().clone(),
             sizes,
             aligns,
             colors,
@@ -212,6 +281,12 @@ impl SpriteSet {
         })
     }
 
+    /// The atlas slot a cell holding `ch` shows at animation time `time`: its current frame's if
+    /// `ch` is animated, else [`slot`](Self::slot)'s. Matches `SpriteCache::get_at`.
+    pub(crate) fn slot_at(&self, ch: char, time: Duration) -> Option<SpriteSlot> {
+        self.slot(self.animations.glyph_at(ch, time))
+    }
+
     /// One layer's texture size in texels `(w, h)`.
     pub(crate) const fn tex_size(&self) -> (u32, u32) {
         (self.tex_w, self.tex_h)
@@ -231,7 +306,7 @@ impl SpriteSet {
 #[cfg(test)]
 mod tests {
     use super::{SpriteInstance, SpriteTint};
-    use retroglyph_core::color::Tint;
+    use retroglyph_core::color::{Remap, Tint};
 
     /// The vertex-array setup in `renderer.rs` describes this struct to the GPU by hand: a byte
     /// stride and a per-attribute offset. Nothing checks that description against the struct, so
@@ -245,8 +320,8 @@ mod tests {
         );
     }
 
-    fn inst(recolour: SpriteTint) -> SpriteInstance {
-        SpriteInstance::new(0, 0, 0, 8, 16, 0, 0, recolour)
+    fn inst(recolor: SpriteTint) -> SpriteInstance {
+        SpriteInstance::new(0, 0, 0, 8, 16, 0, 0, recolor)
     }
 
     #[test]
@@ -260,11 +335,12 @@ mod tests {
 
     #[test]
     fn a_mask_sheet_encodes_its_multiply_with_the_stage_enabled() {
-        let recolour = SpriteTint {
+        let recolor = SpriteTint {
             mask: Tint::multiply(10, 20, 30),
             tint: Tint::None,
+            ..SpriteTint::default()
         };
-        assert_eq!(inst(recolour).mask, [10, 20, 30, 255]);
+        assert_eq!(inst(recolor).mask, [10, 20, 30, 255]);
     }
 
     #[test]
@@ -272,23 +348,54 @@ mod tests {
         let mul = SpriteTint {
             mask: Tint::None,
             tint: Tint::multiply(1, 2, 3),
+            ..SpriteTint::default()
         };
         let mix = SpriteTint {
             mask: Tint::None,
             tint: Tint::mix(4, 5, 6, 7),
+            ..SpriteTint::default()
         };
         // The shader branches on these, so the numbers are a contract with `shaders.rs`.
         assert_eq!((inst(mul).tint_op, inst(mul).tint), (1, [1, 2, 3, 255]));
         assert_eq!((inst(mix).tint_op, inst(mix).tint), (2, [4, 5, 6, 7]));
     }
 
+    #[test]
+    fn a_transform_encodes_the_bits_the_shader_decodes() {
+        use retroglyph_core::tile::Transform;
+        let i = inst(SpriteTint::default());
+        assert_eq!((i.transform, i.scale), (0, 1));
+        let t = Transform::IDENTITY.flipped_y().rotated(3).scaled(2);
+        let i = i.with_transform(t);
+        // `shaders.rs` reads bit 0 as flip X, bit 1 as flip Y, and bits 2-3 as quarter turns.
+        assert_eq!((i.transform, i.scale), (0b1110, 2));
+    }
+
+    #[test]
+    fn a_remap_encodes_its_kind_and_packed_entries() {
+        let swap = SpriteTint::default().with_remap(Some(&Remap::colors(&[(
+            (0x12, 0x34, 0x56),
+            (0xAB, 0xCD, 0xEF),
+        )])));
+        let i = inst(swap);
+        assert_eq!((i.remap_op, i.remap_len), (1, 1));
+        assert_eq!((i.remap_from[0], i.remap_to[0]), (0x0012_3456, 0x00AB_CDEF));
+
+        let ramp = SpriteTint::default().with_remap(Some(&Remap::ramp(&[(1, 2, 3), (4, 5, 6)])));
+        let i = inst(ramp);
+        assert_eq!((i.remap_op, i.remap_len), (2, 2));
+        assert_eq!(i.remap_to[..2], [0x0001_0203, 0x0004_0506]);
+        assert_eq!(inst(SpriteTint::default()).remap_op, 0);
+    }
+
     #[test]
     fn an_unknown_tint_op_renders_the_artwork_as_authored() {
         // `Tint` is `#[non_exhaustive]`. A variant added after this renderer was written must
-        // fall through to "no recolour" rather than being encoded as a misread payload.
+        // fall through to "no recolor" rather than being encoded as a misread payload.
         let none = SpriteTint {
             mask: Tint::None,
             tint: Tint::None,
+            ..SpriteTint::default()
         };
         assert_eq!(inst(none).tint_op, 0);
     }
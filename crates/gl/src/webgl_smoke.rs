@@ -141,10 +141,17 @@ fn render_to_frame(gl: &glow::Context, renderer: &GlRenderer) -> Frame {
         );
 
         // `build_resources` set the viewport/projection; clear once, then composite every layer
-        // back-to-front (upload + two instanced passes each), the same loop the windowed
-        // `present` runs, so a single-layer frame and a multi-layer one both go through it.
-        res.clear(gl);
-        crate::draw_all_layers(&renderer.layers, gl, &mut res, renderer.cell_count() as i32);
+        // back-to-front (upload + two instanced passes each), the same frame the windowed
+        // `present` draws, so a single-layer frame and a multi-layer one both go through it.
+        crate::draw_frame(
+            &renderer.layers,
+            gl,
+            &mut res,
+            renderer.cell_count() as i32,
+            &renderer.presentation(),
+            Some(framebuffer),
+        )
+        .expect("draw frame");
         // Fail loudly on any GL error from the draw passes (e.g. an attribute type mismatch that
         // silently drops a draw) rather than only on the pixel assertions downstream.
         let err = gl.get_error();
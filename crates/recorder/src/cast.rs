@@ -206,6 +206,8 @@ mod tests {
             tile: Tile::new(glyph, style),
             grapheme: None,
             tint: retroglyph_core::color::Tint::None,
+            transform: retroglyph_core::tile::Transform::IDENTITY,
+            remap: retroglyph_core::color::Remap::NONE,
         };
         let frames = vec![
             CapturedFrame {
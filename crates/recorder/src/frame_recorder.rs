@@ -2,10 +2,13 @@
 //! `Headless`-driven capture source.
 
 use crate::OwnedCell;
-use retroglyph_core::backend::{Compositing, Cursor, CursorStyle, DrawCell, Input, Output};
+use retroglyph_core::backend::{
+    Clipboard, ClipboardError, Compositing, Cursor, CursorStyle, DrawCell, Input, Output,
+};
 use retroglyph_core::event::Event;
 use retroglyph_core::grid::{Pos, Size};
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
 /// One changed frame: every cell [`Output::draw_layers`] reported changed on that call, with the
@@ -185,6 +188,8 @@ impl<B: Output> Output for FrameRecorder<B> {
             DrawCell::on_layer(cell.layer, cell.pos, &cell.tile)
                 .with_grapheme(cell.grapheme.as_deref())
                 .with_tint(cell.tint)
+                .with_transform(cell.transform)
+                .with_remap((!cell.remap.is_identity()).then_some(&cell.remap))
         });
         self.inner.draw_layers(forwarded)?;
         self.handle.record(cells);
@@ -220,6 +225,10 @@ impl<B: Input> Input for FrameRecorder<B> {
     fn push_event(&mut self, event: Event) {
         self.inner.push_event(event);
     }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        self.inner.poll_next_event(cx)
+    }
 }
 
 impl<B: Cursor> Cursor for FrameRecorder<B> {
@@ -236,6 +245,16 @@ impl<B: Cursor> Cursor for FrameRecorder<B> {
     }
 }
 
+impl<B: Clipboard> Clipboard for FrameRecorder<B> {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.inner.clipboard_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.inner.set_clipboard_text(text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,15 @@
 //! [`InputRecorder`] and its [`RecorderHandle`].
 
-use retroglyph_core::backend::{Compositing, Cursor, CursorStyle, DrawCell, Input, Output};
+use retroglyph_core::backend::{
+    Clipboard, ClipboardError, Compositing, Cursor, CursorStyle, DrawCell, Input, Output,
+};
 use retroglyph_core::event::Event;
 use retroglyph_core::grid::{HasSize, Pos, Size};
 use retroglyph_core::testing::InputRecording;
 use std::io;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
 use std::time::{Duration, Instant};
 
 /// State shared between an [`InputRecorder`] and every [`RecorderHandle`] cloned from it.
@@ -250,6 +253,12 @@ impl<B: Input> Input for InputRecorder<B> {
     fn push_event(&mut self, event: Event) {
         self.inner.push_event(event);
     }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        let event = ready!(self.inner.poll_next_event(cx));
+        self.handle.record(event.clone());
+        Poll::Ready(event)
+    }
 }
 
 impl<B: Cursor> Cursor for InputRecorder<B> {
@@ -266,6 +275,16 @@ impl<B: Cursor> Cursor for InputRecorder<B> {
     }
 }
 
+impl<B: Clipboard> Clipboard for InputRecorder<B> {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.inner.clipboard_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.inner.set_clipboard_text(text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,9 +2,9 @@
 //! the borrowed original.
 
 use retroglyph_core::backend::DrawCell;
-use retroglyph_core::color::Tint;
+use retroglyph_core::color::{Remap, Tint};
 use retroglyph_core::grid::Pos;
-use retroglyph_core::tile::Tile;
+use retroglyph_core::tile::{Tile, Transform};
 
 /// An owned copy of a single [`DrawCell`], the shape [`FrameRecorder`](crate::FrameRecorder)
 /// buffers instead of the borrowed original.
@@ -13,7 +13,8 @@ use retroglyph_core::tile::Tile;
 /// a capture that outlives one `draw_layers` call (every capture source in this crate) must copy
 /// it out immediately rather than defer to export time, when the grid it borrowed from has
 /// already moved on to the next frame. `grapheme` is the one field that costs an allocation here;
-/// everything else (`Tile`, [`Pos`], `layer`, [`Tint`]) is already `Copy`.
+/// everything else (`Tile`, [`Pos`], `layer`, [`Tint`], [`Transform`], and the borrowed
+/// [`Remap`], copied out by value) is already `Copy`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OwnedCell {
     /// See [`DrawCell::layer`].
@@ -29,6 +30,10 @@ pub struct OwnedCell {
     pub grapheme: Option<String>,
     /// See [`DrawCell::tint`].
     pub tint: Tint,
+    /// See [`DrawCell::transform`].
+    pub transform: Transform,
+    /// See [`DrawCell::remap`]. [`Remap::NONE`] where that is `None`.
+    pub remap: Remap,
 }
 
 impl From<DrawCell<'_>> for OwnedCell {
@@ -39,6 +44,8 @@ impl From<DrawCell<'_>> for OwnedCell {
             tile: *cell.tile,
             grapheme: cell.grapheme.map(str::to_owned),
             tint: cell.tint,
+            transform: cell.transform,
+            remap: cell.remap.copied().unwrap_or_default(),
         }
     }
 }
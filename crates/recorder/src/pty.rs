@@ -341,6 +341,8 @@ fn diff(
                 tile: Tile::new(glyph, *style),
                 grapheme,
                 tint: retroglyph_core::color::Tint::None,
+                transform: retroglyph_core::tile::Transform::IDENTITY,
+                remap: retroglyph_core::color::Remap::NONE,
             });
         }
     }
@@ -2,8 +2,8 @@
 //!
 //! Add this crate and you get the double-buffered `Terminal`/`App` game loop, styled cells, text
 //! and layout helpers, and input events -- `app`, `color`, `event`, `frames`, `grid`, `layout`,
-//! `surface`, `terminal`, `text`, `tile`, `symbols` -- plus a [`prelude`] with the handful of
-//! names a program can't avoid, and one feature-gated module per backend (`crossterm`,
+//! `scene`, `surface`, `terminal`, `text`, `tile`, `symbols` -- plus a [`prelude`] with the
+//! handful of names a program can't avoid, and one feature-gated module per backend (`crossterm`,
 //! `software`, `gl`, `wgpu`, `terminal_wasm`, `ui`). Writing a new backend instead of a game?
 //! Depend on [`retroglyph-core`](retroglyph_core) directly for its lower-level `backend`, `dev`,
 //! and `math` modules.
@@ -62,6 +62,15 @@
 //! Forwards each enabled backend's own `default-font` feature (an embedded Unscii 16 bitmap font),
 //! so a caller doesn't need to know which backend crate actually owns it.
 //!
+//! ### `futures`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards to `retroglyph-core`'s `futures` feature (a `futures_core::Stream` impl for
+//! `terminal::EventStream`, plus `terminal::from_stream`) and, with `crossterm` also enabled, to
+//! `retroglyph-crossterm`'s `event-stream` feature, so the async driver waits on real terminal
+//! input instead of re-polling it.
+//!
 //! ### `gl`
 //!
 //! ⚪ Optional.
@@ -70,6 +79,13 @@
 //! WebGL2 wasm). Also pulls in the curated windowed re-exports (`WindowConfig`, `PresenterBuilder`,
 //! `Windowed`, `WindowedLaunchError`, `run_app`, `run_app_on`).
 //!
+//! ### `png-fonts`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards each enabled backend's own `png-fonts` feature (`BitmapFont::from_png_sheet`, loading
+//! PNG font sheets at runtime; BDF and PSF fonts load without it). Mirrors `default-font` above.
+//!
 //! ### `serde`
 //!
 //! ⚪ Optional.
@@ -118,6 +134,13 @@
 //! Forwards to `retroglyph-crossterm`'s `tracing` feature: instruments `draw`/`flush`/`poll_event`
 //! with `tracing` spans for profiling render/input time.
 //!
+//! ### `truetype`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards each enabled backend's own `truetype` feature (`BitmapFont::from_truetype`,
+//! rasterizing TrueType/OpenType fonts into antialiased glyph tables). Mirrors `default-font` above.
+//!
 //! ### `ui`
 //!
 //! 🟢 Enabled by default.
@@ -154,15 +177,14 @@ struct ReadmeDoctests;
 struct WorkspaceReadmeDoctests;
 
 pub use retroglyph_core::{
-    app, backend, color, event, frames, grid, layout, surface, symbols, terminal, text, tile,
+    app, backend, color, event, frames, grid, layout, scene, surface, symbols, terminal, text, tile,
 };
 
 /// The trait a backend's own builder implements to be driven end to end by
 /// [`launch`](Launch::launch): the user names the backend (`CrosstermOptions`, `Windowed<B>`, ...)
 /// and gets back that backend's own unwrapped error, rather than a facade-wide one. See this
-/// trait's own docs for why there is no unified error here (`retroglyph::LaunchError`, tracked by
-/// #430, is only needed if the facade grows an entry point that can return either backend's
-/// error).
+/// trait's own docs for why there is no unified error here ([`LaunchError`] belongs to
+/// [`run_any`], the one entry point that can return any backend's error).
 pub use retroglyph_core::app::Launch;
 
 // `retroglyph_core::testing` also holds `conformance` (`Observable`, `assert_output_contract`,
@@ -171,8 +193,8 @@ pub use retroglyph_core::app::Launch;
 // game-facing items are re-exported here, individually, rather than the whole module.
 //
 // `RunError` is re-exported by its own name rather than a facade-specific alias: a future
-// unified driver error (`LaunchError`, tracked by #430) must not also be named `RunError`, or the
-// two would collide right here at `retroglyph::RunError`.
+// unified driver error (`LaunchError`, returned by `run_any`) must not also be named `RunError`,
+// or the two would collide right here at `retroglyph::RunError`.
 #[cfg(feature = "testing")]
 pub use retroglyph_core::testing::{RunError, TestHarness};
 
@@ -183,6 +205,21 @@ pub use retroglyph_core::testing::{RunError, TestHarness};
 /// The names a program cannot avoid, glob-importable in one line.
 pub mod prelude;
 
+#[cfg(any(
+    feature = "crossterm",
+    feature = "software",
+    feature = "gl",
+    feature = "wgpu"
+))]
+mod select;
+#[cfg(any(
+    feature = "crossterm",
+    feature = "software",
+    feature = "gl",
+    feature = "wgpu"
+))]
+pub use select::{BackendKind, Backends, LaunchError, run_any};
+
 /// A real-terminal [`Backend`](retroglyph_core::backend::Backend) via
 /// [`crossterm`](https://crates.io/crates/crossterm).
 #[cfg(feature = "crossterm")]
@@ -218,7 +255,7 @@ pub use retroglyph_wgpu as wgpu;
 pub use retroglyph_window::presenter_builder::PresenterBuilder;
 #[cfg(any(feature = "software", feature = "gl", feature = "wgpu"))]
 pub use retroglyph_window::winit::{
-    WindowConfig, Windowed, WindowedLaunchError, run_app, run_app_on,
+    WindowConfig, Windowed, WindowedLaunchError, run_app, run_app_on, run_app_on_dyn,
 };
 
 // ── `run_default`: one call, no per-app `#[cfg]` table ──────────────────────────────
@@ -0,0 +1,555 @@
+//! [`run_any`]: picks a backend at runtime instead of at compile time.
+//!
+//! [`run_default`](crate::run_default) settles on one backend per build, so an app is written
+//! against that backend's concrete type. [`run_any`] instead takes an app written against
+//! [`Box<dyn DynBackend>`](crate::backend::DynBackend) and chooses among every backend this build
+//! enabled when the program starts, from a `--backend <name>` flag or the `RG_BACKEND`
+//! environment variable (the same `RG_*` convention the examples already honor).
+
+use core::fmt;
+use std::ffi::{OsStr, OsString};
+
+use retroglyph_core::app::{App, RunOptions};
+use retroglyph_core::backend::DynBackend;
+
+/// One of the backends [`run_any`] can pick, whether or not this build enabled it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BackendKind {
+    /// A real terminal, via the `crossterm` feature.
+    Crossterm,
+    /// A CPU-rendered window, via the `software` feature.
+    Software,
+    /// An OpenGL window, via the `gl` feature.
+    Gl,
+    /// A `wgpu` window, via the `wgpu` feature.
+    Wgpu,
+}
+
+impl BackendKind {
+    /// Every backend kind, in [`run_any`]'s fallback priority order for windowed backends,
+    /// with the terminal one last.
+    pub const ALL: [Self; 4] = [Self::Software, Self::Gl, Self::Wgpu, Self::Crossterm];
+
+    /// The name `--backend`/`RG_BACKEND` spell this backend with, which is also its Cargo
+    /// feature name.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Crossterm => "crossterm",
+            Self::Software => "software",
+            Self::Gl => "gl",
+            Self::Wgpu => "wgpu",
+        }
+    }
+
+    /// Looks a backend up by [`name`](Self::name), ignoring ASCII case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Whether this build enabled the backend's Cargo feature.
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            Self::Crossterm => cfg!(feature = "crossterm"),
+            Self::Software => cfg!(feature = "software"),
+            Self::Gl => cfg!(feature = "gl"),
+            Self::Wgpu => cfg!(feature = "wgpu"),
+        }
+    }
+
+    /// Whether the backend opens a window (as opposed to drawing into the current terminal).
+    #[must_use]
+    pub const fn is_windowed(self) -> bool {
+        !matches!(self, Self::Crossterm)
+    }
+
+    /// The backend [`run_any`] would pick for this process: the one named by a
+    /// `--backend <name>`/`--backend=<name>` argument, else by `RG_BACKEND`, else the first
+    /// enabled windowed backend when a display is available, else `crossterm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LaunchError::UnknownBackend`] if the requested name isn't a backend at all, or
+    /// [`LaunchError::Unavailable`] if it names one this build didn't enable (including when
+    /// nothing was requested and no backend is enabled that could run here).
+    pub fn detect() -> Result<Self, LaunchError> {
+        let requested = requested_backend(
+            std::env::args_os().skip(1),
+            std::env::var("RG_BACKEND").ok(),
+        );
+        select(requested.as_deref(), has_display())
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The error [`run_any`] can fail with: either no backend could be picked, or the picked one
+/// failed with its own error.
+///
+/// Each backend keeps its own error type (the same one its [`Launch`](crate::Launch) impl
+/// returns), wrapped in the variant named after it, so a caller can still tell which backend
+/// failed and why.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LaunchError {
+    /// `--backend`/`RG_BACKEND` named something that isn't a backend.
+    UnknownBackend(String),
+    /// The picked backend's Cargo feature isn't enabled in this build.
+    Unavailable(BackendKind),
+    /// The `crossterm` backend failed to start or to present.
+    #[cfg(feature = "crossterm")]
+    Crossterm(std::io::Error),
+    /// The `software` backend failed to build its presenter or to run its window.
+    #[cfg(feature = "software")]
+    Software(crate::WindowedLaunchError<crate::software::config::SoftwareBackendError>),
+    /// The `gl` backend failed to build its presenter or to run its window.
+    #[cfg(feature = "gl")]
+    Gl(crate::WindowedLaunchError<crate::gl::config::GlBackendError>),
+    /// The `wgpu` backend failed to build its presenter or to run its window.
+    #[cfg(feature = "wgpu")]
+    Wgpu(crate::WindowedLaunchError<crate::wgpu::config::WgpuBackendError>),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownBackend(name) => {
+                write!(f, "unknown backend {name:?} (expected one of: ")?;
+                for (i, kind) in BackendKind::ALL.into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(kind.name())?;
+                }
+                f.write_str(")")
+            }
+            Self::Unavailable(kind) => {
+                write!(
+                    f,
+                    "backend `{kind}` is not enabled; enable the `{kind}` feature"
+                )
+            }
+            #[cfg(feature = "crossterm")]
+            Self::Crossterm(err) => write!(f, "crossterm backend failed: {err}"),
+            #[cfg(feature = "software")]
+            Self::Software(err) => write!(f, "software backend failed: {err}"),
+            #[cfg(feature = "gl")]
+            Self::Gl(err) => write!(f, "gl backend failed: {err}"),
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu(err) => write!(f, "wgpu backend failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnknownBackend(_) | Self::Unavailable(_) => None,
+            #[cfg(feature = "crossterm")]
+            Self::Crossterm(err) => Some(err),
+            #[cfg(feature = "software")]
+            Self::Software(err) => Some(err),
+            #[cfg(feature = "gl")]
+            Self::Gl(err) => Some(err),
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu(err) => Some(err),
+        }
+    }
+}
+
+/// Picks a backend at runtime (see [`BackendKind::detect`]) and drives `app` on it with that
+/// backend's default configuration, paced by [`RunOptions::default()`].
+///
+/// The app is written once against `Box<dyn DynBackend>` and runs unchanged on whichever backend
+/// is picked; [`Terminal::backend`](crate::terminal::Terminal::backend) plus
+/// `downcast_ref` still reaches the concrete backend when an app needs something only one of
+/// them offers. [`Backends`] does the same with each backend's configuration supplied instead.
+///
+/// # Errors
+///
+/// Returns [`LaunchError::UnknownBackend`]/[`LaunchError::Unavailable`] if no backend could be
+/// picked, or the picked backend's own error wrapped in its variant.
+///
+/// # Examples
+///
+/// ```no_run
+/// use retroglyph::backend::DynBackend;
+/// use retroglyph::prelude::*;
+///
+/// struct Game;
+///
+/// impl App<Box<dyn DynBackend>> for Game {
+///     fn update(&mut self, term: &mut Terminal<Box<dyn DynBackend>>, _frame: &Frame) -> Flow {
+///         term.surface().put((5, 5), '@', Style::new().fg(Color::GREEN));
+///         Flow::Continue
+///     }
+/// }
+///
+/// fn main() -> Result<(), retroglyph::LaunchError> {
+///     // `my-game --backend crossterm`, or `RG_BACKEND=software my-game`.
+///     retroglyph::run_any(Game)
+/// }
+/// ```
+pub fn run_any<A>(app: A) -> Result<(), LaunchError>
+where
+    A: App<Box<dyn DynBackend>> + 'static,
+{
+    Backends::new("retroglyph").launch(app, RunOptions::default())
+}
+
+/// What [`run_any`] builds each backend from, for an app that has outgrown the defaults.
+///
+/// Every backend starts from the same default configuration `run_any` uses, so only the ones
+/// that need something else have to be set. Each setter exists only when this build enabled its
+/// backend, so configuring one takes the same `#[cfg(feature = ...)]` the app's own use of that
+/// backend already needs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use retroglyph::app::RunOptions;
+/// use retroglyph::backend::DynBackend;
+/// use retroglyph::prelude::*;
+///
+/// struct Game;
+///
+/// impl App<Box<dyn DynBackend>> for Game {
+///     fn update(&mut self, term: &mut Terminal<Box<dyn DynBackend>>, _frame: &Frame) -> Flow {
+///         term.surface().put((5, 5), '@', Style::new().fg(Color::GREEN));
+///         Flow::Continue
+///     }
+/// }
+///
+/// fn main() -> Result<(), retroglyph::LaunchError> {
+///     let backends = retroglyph::Backends::new("My Game");
+///     #[cfg(feature = "software")]
+///     let backends = backends.software(
+///         retroglyph::software::config::SoftwareBackendBuilder::new()
+///             .grid_size(80, 30)
+///             .scale(2),
+///     );
+///     backends.launch(Game, RunOptions::animated(60))
+/// }
+/// ```
+pub struct Backends {
+    title: String,
+    /// Set by [`pick`](Self::pick); [`BackendKind::detect`] decides otherwise.
+    picked: Option<BackendKind>,
+    #[cfg(feature = "crossterm")]
+    crossterm: crate::crossterm::CrosstermOptions,
+    /// Set by [`map_crossterm`](Self::map_crossterm).
+    #[cfg(feature = "crossterm")]
+    #[allow(clippy::type_complexity)]
+    map_crossterm: Option<Box<dyn FnOnce(crate::crossterm::Crossterm) -> Box<dyn DynBackend>>>,
+    #[cfg(feature = "software")]
+    software: crate::software::config::SoftwareBackendBuilder,
+    #[cfg(feature = "gl")]
+    gl: crate::gl::config::GlBackendBuilder,
+    #[cfg(feature = "wgpu")]
+    wgpu: crate::wgpu::config::WgpuBackendBuilder,
+}
+
+impl Backends {
+    /// Every backend at its default configuration, with `title` as a windowed backend's title
+    /// bar text.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            picked: None,
+            #[cfg(feature = "crossterm")]
+            crossterm: crate::crossterm::CrosstermOptions::new(),
+            #[cfg(feature = "crossterm")]
+            map_crossterm: None,
+            #[cfg(feature = "software")]
+            software: crate::software::config::SoftwareBackendBuilder::new(),
+            #[cfg(feature = "gl")]
+            gl: crate::gl::config::GlBackendBuilder::new(),
+            #[cfg(feature = "wgpu")]
+            wgpu: crate::wgpu::config::WgpuBackendBuilder::new(),
+        }
+    }
+
+    /// Runs on `kind` instead of the backend [`BackendKind::detect`] picks, e.g. one the app
+    /// already detected itself to decide how to configure it.
+    #[must_use]
+    pub const fn pick(mut self, kind: BackendKind) -> Self {
+        self.picked = Some(kind);
+        self
+    }
+
+    /// Builds the `crossterm` backend from `options`.
+    #[cfg(feature = "crossterm")]
+    #[must_use]
+    pub const fn crossterm(mut self, options: crate::crossterm::CrosstermOptions) -> Self {
+        self.crossterm = options;
+        self
+    }
+
+    /// Wraps the `crossterm` backend in whatever `wrap` returns before the app sees it: an input
+    /// filter, a recorder, anything else that is itself a backend.
+    ///
+    /// Only the terminal backend can be wrapped. A windowed backend's event loop drives the
+    /// concrete backend it built, and would have nothing to present through a wrapper.
+    #[cfg(feature = "crossterm")]
+    #[must_use]
+    pub fn map_crossterm<F>(mut self, wrap: F) -> Self
+    where
+        F: FnOnce(crate::crossterm::Crossterm) -> Box<dyn DynBackend> + 'static,
+    {
+        self.map_crossterm = Some(Box::new(wrap));
+        self
+    }
+
+    /// Builds the `software` backend from `builder`.
+    #[cfg(feature = "software")]
+    #[must_use]
+    pub fn software(mut self, builder: crate::software::config::SoftwareBackendBuilder) -> Self {
+        self.software = builder;
+        self
+    }
+
+    /// Builds the `gl` backend from `builder`.
+    #[cfg(feature = "gl")]
+    #[must_use]
+    pub fn gl(mut self, builder: crate::gl::config::GlBackendBuilder) -> Self {
+        self.gl = builder;
+        self
+    }
+
+    /// Builds the `wgpu` backend from `builder`.
+    #[cfg(feature = "wgpu")]
+    #[must_use]
+    pub fn wgpu(mut self, builder: crate::wgpu::config::WgpuBackendBuilder) -> Self {
+        self.wgpu = builder;
+        self
+    }
+
+    /// Picks a backend (see [`pick`](Self::pick)) and drives `app` on it, paced by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`run_any`].
+    pub fn launch<A>(self, app: A, options: RunOptions) -> Result<(), LaunchError>
+    where
+        A: App<Box<dyn DynBackend>> + 'static,
+    {
+        let kind = match self.picked {
+            Some(kind) => kind,
+            None => BackendKind::detect()?,
+        };
+        match kind {
+            #[cfg(feature = "crossterm")]
+            BackendKind::Crossterm => {
+                let backend = self.crossterm.build().map_err(LaunchError::Crossterm)?;
+                let terminal = match self.map_crossterm {
+                    Some(wrap) => crate::terminal::Terminal::new(wrap(backend)),
+                    None => crate::terminal::Terminal::new(backend).into_dyn(),
+                };
+                retroglyph_core::app::run_on_with(terminal, app, options)
+                    .map_err(|err| LaunchError::Crossterm(into_io_error(err)))
+            }
+            #[cfg(feature = "software")]
+            BackendKind::Software => crate::Windowed::new(self.software, self.title)
+                .launch_dyn(app, options)
+                .map_err(LaunchError::Software),
+            #[cfg(feature = "gl")]
+            BackendKind::Gl => crate::Windowed::new(self.gl, self.title)
+                .launch_dyn(app, options)
+                .map_err(LaunchError::Gl),
+            #[cfg(feature = "wgpu")]
+            BackendKind::Wgpu => crate::Windowed::new(self.wgpu, self.title)
+                .launch_dyn(app, options)
+                .map_err(LaunchError::Wgpu),
+            // Unreachable only when every backend feature is on.
+            #[allow(unreachable_patterns)]
+            _ => Err(LaunchError::Unavailable(kind)),
+        }
+    }
+}
+
+impl fmt::Debug for Backends {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backends")
+            .field("title", &self.title)
+            .field("picked", &self.picked)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Unwraps a type-erased crossterm error back into the `io::Error` it started as.
+#[cfg(feature = "crossterm")]
+fn into_io_error(err: retroglyph_core::backend::DynBackendError) -> std::io::Error {
+    match err.into_inner().downcast::<std::io::Error>() {
+        Ok(err) => *err,
+        Err(err) => std::io::Error::other(err),
+    }
+}
+
+/// The backend name a `--backend <name>`/`--backend=<name>` argument asks for, or failing
+/// that, `env` (the `RG_BACKEND` variable). The last `--backend` wins, like most CLI flags.
+/// Arguments that aren't UTF-8 can't name a backend, and are skipped.
+fn requested_backend(
+    args: impl IntoIterator<Item = OsString>,
+    env: Option<String>,
+) -> Option<String> {
+    let mut requested = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(arg) = arg.to_str() else {
+            continue;
+        };
+        if arg == "--backend" {
+            if let Some(name) = args.next().as_deref().and_then(OsStr::to_str) {
+                requested = Some(name.to_owned());
+            }
+        } else if let Some(name) = arg.strip_prefix("--backend=") {
+            requested = Some(name.to_owned());
+        }
+    }
+    requested.or_else(|| env.filter(|name| !name.trim().is_empty()))
+}
+
+/// Whether a windowed backend has a display to open a window on.
+///
+/// Only a desktop Unix without `DISPLAY` or `WAYLAND_DISPLAY` (an SSH session, a container, a
+/// text console) is known not to; everywhere else a window server is assumed.
+fn has_display() -> bool {
+    if cfg!(all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )) {
+        ["DISPLAY", "WAYLAND_DISPLAY"]
+            .into_iter()
+            .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()))
+    } else {
+        true
+    }
+}
+
+/// [`BackendKind::detect`]'s decision, separated from the process environment it reads.
+fn select(requested: Option<&str>, has_display: bool) -> Result<BackendKind, LaunchError> {
+    if let Some(name) = requested {
+        let kind = BackendKind::from_name(name)
+            .ok_or_else(|| LaunchError::UnknownBackend(name.to_owned()))?;
+        return if kind.is_enabled() {
+            Ok(kind)
+        } else {
+            Err(LaunchError::Unavailable(kind))
+        };
+    }
+    let mut enabled = BackendKind::ALL
+        .into_iter()
+        .filter(|kind| kind.is_enabled());
+    let windowed = enabled.clone().find(|kind| kind.is_windowed());
+    let terminal = enabled.find(|kind| !kind.is_windowed());
+    let picked = if has_display {
+        windowed.or(terminal)
+    } else {
+        // Without a display a window can't open, but a windowed-only build has nothing better to
+        // try: let it fail with its own event loop error rather than a vaguer one here.
+        terminal.or(windowed)
+    };
+    picked.ok_or(LaunchError::Unavailable(BackendKind::Crossterm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn names_round_trip_and_ignore_case() {
+        for kind in BackendKind::ALL {
+            assert_eq!(BackendKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(BackendKind::from_name("WGPU"), Some(BackendKind::Wgpu));
+        assert_eq!(BackendKind::from_name("vulkan"), None);
+    }
+
+    #[test]
+    fn flag_wins_over_env_in_either_spelling() {
+        let env = Some("gl".to_owned());
+        assert_eq!(
+            requested_backend(args(&["--backend", "wgpu"]), env.clone()).as_deref(),
+            Some("wgpu")
+        );
+        assert_eq!(
+            requested_backend(args(&["--fps", "off", "--backend=software"]), env.clone())
+                .as_deref(),
+            Some("software")
+        );
+        assert_eq!(
+            requested_backend(args(&["--fps"]), env).as_deref(),
+            Some("gl")
+        );
+        assert_eq!(requested_backend(args(&[]), Some("  ".to_owned())), None);
+        assert_eq!(requested_backend(args(&["--backend"]), None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn arguments_that_are_not_utf8_are_skipped() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = || OsString::from_vec(vec![0xff, 0xfe]);
+        let mut list = args(&["--backend", "gl"]);
+        list.push(invalid());
+        assert_eq!(requested_backend(list, None).as_deref(), Some("gl"));
+
+        let list = vec![OsString::from("--backend"), invalid()];
+        assert_eq!(
+            requested_backend(list, Some("wgpu".to_owned())).as_deref(),
+            Some("wgpu")
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_an_error_that_lists_the_choices() {
+        let err = select(Some("vulkan"), true).unwrap_err();
+        assert!(matches!(&err, LaunchError::UnknownBackend(name) if name == "vulkan"));
+        assert!(err.to_string().contains("crossterm"));
+    }
+
+    #[test]
+    fn a_requested_backend_is_picked_only_when_enabled() {
+        for kind in BackendKind::ALL {
+            match select(Some(kind.name()), false) {
+                Ok(picked) => {
+                    assert!(kind.is_enabled());
+                    assert_eq!(picked, kind);
+                }
+                Err(LaunchError::Unavailable(missing)) => {
+                    assert!(!kind.is_enabled());
+                    assert_eq!(missing, kind);
+                }
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn no_display_falls_back_to_crossterm() {
+        assert_eq!(select(None, false).unwrap(), BackendKind::Crossterm);
+    }
+
+    #[cfg(feature = "software")]
+    #[test]
+    fn a_display_prefers_a_windowed_backend() {
+        assert_eq!(select(None, true).unwrap(), BackendKind::Software);
+    }
+}
@@ -7,7 +7,10 @@
 //! `retroglyph_window::winit::run_windowed` to open a window, or use it
 //! directly for in-memory rendering.
 
+use retroglyph_window::crt::CrtEffects;
 use retroglyph_window::font::FontChain;
+use retroglyph_window::palette::Palette;
+use retroglyph_window::scaling::ScalingMode;
 #[cfg(feature = "tilesets")]
 use retroglyph_window::tileset::TilesetOptions;
 use std::fmt;
@@ -163,6 +166,14 @@ pub struct SoftwareBackend {
     /// A scale of 2 renders each 1-bit font pixel as a 2×2 block, making
     /// the Unscii 16 font display at 16×32 pixels per cell. Default is 1.
     pub scale: u16,
+    /// The palette colors resolve through. Change it on a running renderer with
+    /// [`SoftwareRenderer::set_palette`](crate::SoftwareRenderer::set_palette).
+    pub palette: Palette,
+    /// The CRT post-processing effects applied to the presented frame. Change them on a running
+    /// renderer with [`SoftwareRenderer::set_crt`](crate::SoftwareRenderer::set_crt).
+    pub crt: CrtEffects,
+    /// How the frame is fitted into the window when the window is resized.
+    pub scaling: ScalingMode,
     /// Registered tileset options, loaded at
     /// [`into_renderer`](SoftwareBackend::into_renderer) time.
     #[cfg(feature = "tilesets")]
@@ -194,6 +205,9 @@ impl SoftwareBackend {
             cols: 80,
             rows: 25,
             scale: 1,
+            palette: Palette::DEFAULT,
+            crt: CrtEffects::NONE,
+            scaling: ScalingMode::Reflow,
             #[cfg(feature = "tilesets")]
             tilesets: Vec::new(),
         }
@@ -292,6 +306,78 @@ impl SoftwareBackendBuilder {
         self
     }
 
+    /// Sets the palette [`Color::Ansi`](retroglyph_core::color::Color::Ansi),
+    /// [`Color::Indexed`](retroglyph_core::color::Color::Indexed), and
+    /// [`Color::Default`](retroglyph_core::color::Color::Default) resolve through. Defaults to
+    /// [`Palette::DEFAULT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_software::config::SoftwareBackendBuilder;
+    /// use retroglyph_window::palette::Palette;
+    ///
+    /// let backend = SoftwareBackendBuilder::new()
+    ///     .palette(Palette::CGA)
+    ///     .build()
+    ///     .expect("backend init failed");
+    /// assert_eq!(backend.palette, Palette::CGA);
+    /// ```
+    #[must_use]
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.options.palette = palette;
+        self
+    }
+
+    /// Sets the CRT post-processing effects (scanlines, glow, curvature, color fringing) applied to
+    /// every presented frame. Defaults to [`CrtEffects::NONE`].
+    ///
+    /// The effects apply to [`SoftwareRenderer::output_pixels`](crate::SoftwareRenderer::output_pixels)
+    /// and to what reaches the window; [`SoftwareRenderer::pixels`](crate::SoftwareRenderer::pixels)
+    /// stays the plain composited grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_software::config::SoftwareBackendBuilder;
+    /// use retroglyph_window::crt::CrtEffects;
+    ///
+    /// let backend = SoftwareBackendBuilder::new()
+    ///     .crt(CrtEffects::CLASSIC)
+    ///     .build()
+    ///     .expect("backend init failed");
+    /// assert!(backend.crt.is_enabled());
+    /// ```
+    #[must_use]
+    pub const fn crt(mut self, effects: CrtEffects) -> Self {
+        self.options.crt = effects;
+        self
+    }
+
+    /// Sets how the frame is fitted into the window when the window is resized. Defaults to
+    /// [`ScalingMode::Reflow`], which resizes the grid instead.
+    ///
+    /// This backend always scales nearest-neighbor, whatever
+    /// [`ScaleFilter`](retroglyph_window::scaling::ScaleFilter) the mode asks for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_software::config::SoftwareBackendBuilder;
+    /// use retroglyph_window::scaling::ScalingMode;
+    ///
+    /// let backend = SoftwareBackendBuilder::new()
+    ///     .scaling(ScalingMode::Integer { bars: (0x10, 0x10, 0x10) })
+    ///     .build()
+    ///     .expect("backend init failed");
+    /// assert!(!backend.scaling.reflows());
+    /// ```
+    #[must_use]
+    pub const fn scaling(mut self, mode: ScalingMode) -> Self {
+        self.options.scaling = mode;
+        self
+    }
+
     /// Registers a tileset for loading when the backend starts.
     ///
     /// Multiple tilesets can be registered; they are all loaded when
@@ -324,7 +410,7 @@ impl SoftwareBackendBuilder {
     ///
     /// Returns [`SoftwareBackendError::ZeroGrid`] if `cols` or `rows` was set to `0`.
     pub fn build(self) -> Result<SoftwareBackend, SoftwareBackendError> {
-        let Some(fonts) = self.options.fonts else {
+        let Some(fonts) = &self.options.fonts else {
             return Err(SoftwareBackendError::NoFont);
         };
         if fonts.glyph_size().is_none() {
@@ -360,6 +446,18 @@ impl retroglyph_window::presenter_builder::PresenterBuilder for SoftwareBackendB
         self.font(fonts)
     }
 
+    fn palette(self, palette: Palette) -> Self {
+        self.palette(palette)
+    }
+
+    fn crt(self, effects: CrtEffects) -> Self {
+        self.crt(effects)
+    }
+
+    fn scaling(self, mode: ScalingMode) -> Self {
+        self.scaling(mode)
+    }
+
     #[cfg(feature = "tilesets")]
     fn tileset(self, opts: TilesetOptions) -> Self {
         self.tileset(opts)
@@ -0,0 +1,131 @@
+//! The CPU reference implementation of the CRT post-processing stage.
+//!
+//! [`apply`] follows the per-pixel recipe in [`retroglyph_window::crt`] step for step, in `f32`
+//! and in the same operation order as the `retroglyph-gl` and `retroglyph-wgpu` post shaders, so
+//! the GPU backends can be checked against it within a rounding tolerance.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary is
+// intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_window::crt::CrtEffects;
+
+/// Writes `src` (a `width` x `height` frame of `0x00RRGGBB` pixels) with `effects` applied into
+/// `out`, resizing `out` to match.
+// Plain multiplies and adds rather than `mul_add`, to round the way the shaders do.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::suboptimal_flops
+)]
+pub(crate) fn apply(
+    effects: &CrtEffects,
+    src: &[u32],
+    width: usize,
+    height: usize,
+    out: &mut Vec<u32>,
+) {
+    out.clear();
+    out.resize(src.len(), 0);
+    if width == 0 || height == 0 || src.len() != width * height {
+        return;
+    }
+    let [scanlines, glow, curvature, fringe] = effects.to_array();
+    let (frame_w, frame_h) = (width as f32, height as f32);
+    let (max_x, max_y) = (width as i64 - 1, height as i64 - 1);
+    let fetch =
+        |x: i64, y: i64| src[y.clamp(0, max_y) as usize * width + x.clamp(0, max_x) as usize];
+
+    for y in 0..height {
+        for x in 0..width {
+            let cx = (x as f32 + 0.5) / frame_w * 2.0 - 1.0;
+            let cy = (y as f32 + 0.5) / frame_h * 2.0 - 1.0;
+            let px = (cx * (1.0 + curvature * (cy * cy)) + 1.0) * 0.5 * frame_w;
+            let py = (cy * (1.0 + curvature * (cx * cx)) + 1.0) * 0.5 * frame_h;
+            if !(px >= 0.0 && py >= 0.0 && px < frame_w && py < frame_h) {
+                continue; // Past the curved screen's edge: black, as `out` already is.
+            }
+            let (sx, sy) = (px.floor() as i64, py.floor() as i64);
+
+            let mut rgb = [
+                channel(fetch((px + fringe).floor() as i64, sy), 16),
+                channel(fetch(sx, sy), 8),
+                channel(fetch((px - fringe).floor() as i64, sy), 0),
+            ];
+            if glow > 0.0 {
+                let mut sum = [0.0f32; 3];
+                for dy in [-2, 0, 2] {
+                    for dx in [-2, 0, 2] {
+                        let texel = fetch(sx + dx, sy + dy);
+                        for (total, shift) in sum.iter_mut().zip([16, 8, 0]) {
+                            *total += channel(texel, shift);
+                        }
+                    }
+                }
+                for (value, total) in rgb.iter_mut().zip(sum) {
+                    *value += glow * total / 9.0;
+                }
+            }
+            if sy & 1 == 1 {
+                for value in &mut rgb {
+                    *value *= 1.0 - scanlines;
+                }
+            }
+
+            let [red, green, blue] =
+                rgb.map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u32);
+            out[y * width + x] = (red << 16) | (green << 8) | blue;
+        }
+    }
+}
+
+/// The channel at bit `shift` of a `0x00RRGGBB` pixel, in `[0, 1]`.
+fn channel(pixel: u32, shift: u32) -> f32 {
+    f32::from(((pixel >> shift) & 0xFF) as u8) / 255.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CrtEffects, apply};
+
+    fn run(effects: CrtEffects, src: &[u32], width: usize) -> Vec<u32> {
+        let mut out = Vec::new();
+        apply(&effects, src, width, src.len() / width, &mut out);
+        out
+    }
+
+    #[test]
+    fn fringe_pulls_red_and_blue_apart_at_an_edge() {
+        // Black on the left half, white on the right, 8 pixels wide and 2 tall.
+        let src: Vec<u32> = (0..16)
+            .map(|i| if i % 8 < 4 { 0 } else { 0x00FF_FFFF })
+            .collect();
+        let out = run(CrtEffects::NONE.with_fringe(1.0), &src, 8);
+        // Red is read one pixel to the right, so it reaches white a pixel early; blue a pixel late.
+        assert_eq!(out[3], 0x00FF_0000);
+        assert_eq!(out[4], 0x00FF_FF00);
+        assert_eq!(out[5], 0x00FF_FFFF);
+    }
+
+    #[test]
+    fn curvature_blackens_the_corners_but_keeps_the_center() {
+        let src = vec![0x0040_8020; 32 * 32];
+        let out = run(CrtEffects::NONE.with_curvature(0.3), &src, 32);
+        assert_eq!(out[0], 0, "top-left corner");
+        assert_eq!(out[32 * 32 - 1], 0, "bottom-right corner");
+        assert_eq!(out[16 * 32 + 16], 0x0040_8020, "center");
+    }
+
+    #[test]
+    fn glow_brightens_a_dark_pixel_next_to_a_lit_one() {
+        let mut src = vec![0; 5 * 5];
+        src[2 * 5 + 4] = 0x0090_9090;
+        let out = run(CrtEffects::NONE.with_glow(1.0), &src, 5);
+        // (2, 2) sees the lit pixel 2 to its right: one ninth of 0x90 is 0x10.
+        assert_eq!(out[2 * 5 + 2], 0x0010_1010);
+        // (1, 2) sees it 3 away, outside the 2-pixel taps, and stays black.
+        assert_eq!(out[2 * 5 + 1], 0);
+    }
+}
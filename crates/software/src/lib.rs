@@ -65,6 +65,15 @@
 //! Forwards `retroglyph-core`'s `dev` feature, which forces development diagnostics on in a build
 //! that would otherwise compile them out (see [`retroglyph_core::dev`]).
 //!
+//! ### `png-fonts`
+//!
+//! ⚪ Optional.
+//!
+//! Loading PNG font sheets at runtime (`BitmapFont::from_png_sheet`); BDF and PSF fonts load
+//! without it.
+//!
+//! Forwards to `retroglyph-window`'s `png-fonts` feature.
+//!
 //! ### `tilesets`
 //!
 //! ⚪ Optional.
@@ -72,6 +81,12 @@
 //! PNG sprite sheet tilesets with alpha-blended CPU blit support.
 //!
 //! Adds `alpha-blend` and forwards to `retroglyph-window`'s `tilesets` feature for decode/config.
+//!
+//! ### `truetype`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards to `retroglyph-window`'s `truetype` feature.
 //! <!-- gen-features:end -->
 
 #![doc(
@@ -83,6 +98,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod config;
+mod crt;
+mod scale;
 
 // The sprite/tileset decode + config now lives in `retroglyph-window` (winit-free), shared with
 // `retroglyph-gl` the same way `BitmapFont` is. Re-exported here so the existing
@@ -113,7 +130,7 @@ use surface::WindowSurface;
 struct ReadmeDoctests;
 
 use retroglyph_core::backend::DrawCell;
-use retroglyph_core::backend::{Compositing, Cursor, Input, Output};
+use retroglyph_core::backend::{Clipboard, Compositing, Cursor, Input, Output};
 use retroglyph_core::color::Color;
 
 // The bitmap font lives in `retroglyph-window`'s winit-free `font` module (both graphical
@@ -122,7 +139,7 @@ use retroglyph_core::color::Color;
 // sparing a consumer an extra direct dependency on `retroglyph-window` just for this type, the
 // same rationale as `retroglyph-core`'s kept `HasSize` re-export; `unscii16` etc. are reached
 // through `retroglyph_window::font` directly.
-pub use retroglyph_window::font::{BitmapFont, FontChain};
+pub use retroglyph_window::font::{BitmapFont, FontChain, FontLoadError};
 
 #[cfg(feature = "tilesets")]
 use alpha_blend::rgba::U8x4Rgba;
@@ -130,20 +147,24 @@ use config::{SoftwareBackend, SoftwareBackendError};
 use grixy::buf::GridBuf;
 use grixy::ops::GridWrite;
 use grixy::ops::layout::{LinearLayout, RowMajor};
-use retroglyph_core::color::Tint;
+use retroglyph_core::color::{Remap, Tint};
 use retroglyph_core::event::{Event, push_coalesced};
 use retroglyph_core::grid::HasSize;
-use retroglyph_core::grid::{Pos, Size};
-use retroglyph_core::tile::Tile;
+use retroglyph_core::grid::{LineSize, Pos, Size};
+use retroglyph_core::tile::{Tile, Transform};
+use retroglyph_window::crt::CrtEffects;
 use retroglyph_window::diagnostics::DiagnosticLog;
-use retroglyph_window::geometry::CellGeometry;
-use retroglyph_window::palette::{DEFAULT_BG, DEFAULT_FG};
+use retroglyph_window::font::{GlyphSpan, ScanlineCache, WideHalf};
+use retroglyph_window::geometry::{CellGeometry, Viewport};
+use retroglyph_window::palette::Palette;
 use retroglyph_window::presenter::WindowHandle;
 use retroglyph_window::presenter::cell_art_glyph;
+use retroglyph_window::scaling::ScalingMode;
 #[cfg(feature = "tilesets")]
 use retroglyph_window::sprite_cache::{Sprite, SpriteCache, SpriteTint};
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 
 // ── Public types ──────────────────────────────────────────────────────────────
@@ -169,6 +190,9 @@ pub struct SoftwareRenderer {
     /// time. Always present; the `Option` wrapper in `SoftwareBackend` is only for the builder
     /// validation step.
     fonts: FontChain<'static>,
+    /// `fonts`' glyphs expanded into set-pixel runs as they are first drawn, so a redraw doesn't
+    /// re-extract each glyph's bits across its row bytes every frame.
+    scanlines: ScanlineCache,
     ctx: RenderContext,
     #[cfg(feature = "tilesets")]
     sprite_cache: Arc<SpriteCache>,
@@ -176,6 +200,9 @@ pub struct SoftwareRenderer {
 
 struct RenderContext {
     event_buffer: VecDeque<Event>,
+    /// Registered by [`Input::poll_next_event`] while `event_buffer` is empty, woken by the next
+    /// [`push_event`](SoftwareRenderer::push_event).
+    event_waker: Option<Waker>,
     pixel_buf: GridBuf<u32, Vec<u32>, RowMajor>,
     window_surface: Option<WindowSurface>,
     /// Cell/surface pixel geometry (glyph size x scale); constant for the renderer's lifetime
@@ -193,6 +220,17 @@ struct RenderContext {
     /// between two `present()` calls. `None` means no rows changed
     /// (nothing to present).
     damage_rows: Option<(u32, u32)>,
+    /// `pixel_buf` with the configured [`CrtEffects`] applied, which is what
+    /// [`present`](SoftwareRenderer::present) shows. Empty while the effects are off, so the plain
+    /// buffer is presented as-is without a copy.
+    crt_buf: Vec<u32>,
+    /// The window size last passed to [`resize_surface`](SoftwareRenderer::resize_surface), or
+    /// `None` before the first call. What a non-reflowing
+    /// [`ScalingMode`](retroglyph_window::scaling::ScalingMode) places the frame in.
+    window_size: Option<(u32, u32)>,
+    /// The window-sized frame [`present`](SoftwareRenderer::present) sends while the frame is
+    /// scaled into a [`Viewport`]: the output frame scaled, with letterbox bars around it.
+    window_buf: Vec<u32>,
     /// Shadow copy of every allocated layer's tiles and tints from the last `draw_layers` call,
     /// one [`LayerShadow`] per layer indexed `[layer_id]`. Used to find dirty cells without
     /// touching core's diff model: `draw_layers` already receives every cell on every allocated
@@ -223,9 +261,25 @@ struct RenderContext {
     /// revisited by the dirty-cell path and the stale spill survives unless this frame's
     /// `full_repaint` also accounts for what the *previous* frame offset.
     prev_offset: bool,
+    /// Each row's [`LineSize`] in the last `draw_layers` call, read off the cells streamed for
+    /// it. A row that changes size moves every cell on it, so any change forces a full repaint,
+    /// the same as `prev_layer_count`. Empty until the first call, or after the shadow is
+    /// invalidated.
+    line_sizes: Vec<LineSize>,
+    /// Scratch buffer `draw_layers` fills with this frame's line sizes before comparing them
+    /// against `line_sizes`, kept so a frame doesn't allocate one.
+    next_line_sizes: Vec<LineSize>,
     /// The oversized-sprite, dropped-tint, and notdef-glyph dedup state, so a 60fps redraw loop
     /// logs each offending glyph once instead of every frame.
     diagnostics: DiagnosticLog,
+    /// The clock animated sprites are drawn at; see
+    /// [`Presenter::animation_time`](retroglyph_window::presenter::Presenter::animation_time).
+    #[cfg(feature = "tilesets")]
+    animation_time: Duration,
+    /// Each animated codepoint's frame index as of the last `draw_layers` call. A cell whose
+    /// glyph's frame moved since then is repainted even though its tile didn't change.
+    #[cfg(feature = "tilesets")]
+    animation_frames: Vec<(char, usize)>,
 }
 
 impl RenderContext {
@@ -240,23 +294,34 @@ impl RenderContext {
         // against a shadow that no longer describes the current frame's layer set.
         self.prev_layer_count = usize::MAX;
         self.prev_offset = false;
+        self.line_sizes.clear();
     }
 }
 
 /// One layer's per-cell shadow copy from the last `draw_layers` call: its tiles and their tints,
-/// always allocated and replaced together so the two can't independently drift the way the
+/// transforms, and remaps, always allocated and replaced together so the two can't independently drift the way the
 /// `prev_tiles`/`prev_tints` parallel `Vec`s they replace once did (retroglyph#567, retroglyph#694).
+///
+/// Remaps are boxed because almost no cell has one and a [`Remap`] is a whole table: inline, it
+/// would multiply the shadow's size for every cell to pay for the few that do.
 struct LayerShadow {
     tiles: GridBuf<Tile, Vec<Tile>, RowMajor>,
     tints: GridBuf<Tint, Vec<Tint>, RowMajor>,
+    transforms: GridBuf<Transform, Vec<Transform>, RowMajor>,
+    remaps: GridBuf<RemapSlot, Vec<RemapSlot>, RowMajor>,
 }
 
+/// One cell's entry in [`LayerShadow::remaps`].
+type RemapSlot = Option<Box<Remap>>;
+
 impl LayerShadow {
-    /// Allocates a shadow filled with default tiles/no tint, sized for a `cols` x `rows` grid.
+    /// Allocates a shadow filled with default tiles/no tint/no transform/no remap, sized for a `cols` x `rows` grid.
     fn new(cols: usize, rows: usize) -> Self {
         Self {
             tiles: GridBuf::new_filled(cols, rows, Tile::default()),
             tints: GridBuf::new_filled(cols, rows, Tint::None),
+            transforms: GridBuf::new_filled(cols, rows, Transform::IDENTITY),
+            remaps: GridBuf::from_buffer(vec![None; cols * rows], cols),
         }
     }
 
@@ -280,13 +345,18 @@ impl SoftwareRenderer {
         Self {
             options,
             fonts,
+            scanlines: ScanlineCache::new(),
             ctx: RenderContext {
                 event_buffer: VecDeque::new(),
+                event_waker: None,
                 pixel_buf: GridBuf::from_buffer(vec![0u32; buf_w * buf_h], buf_w),
                 window_surface: None,
                 geometry,
                 prev_pixels: vec![0u32; buf_w * buf_h],
                 damage_rows: None,
+                crt_buf: Vec::new(),
+                window_size: None,
+                window_buf: Vec::new(),
                 layers: Vec::new(),
                 dirty_mask: Vec::new(),
                 // Sentinel distinct from any real layer count (always < 256), so the very first
@@ -294,7 +364,13 @@ impl SoftwareRenderer {
                 // the full-repaint path once, seeding `layers` for every subsequent frame.
                 prev_layer_count: usize::MAX,
                 prev_offset: false,
+                line_sizes: Vec::new(),
+                next_line_sizes: Vec::new(),
                 diagnostics: DiagnosticLog::default(),
+                #[cfg(feature = "tilesets")]
+                animation_time: Duration::ZERO,
+                #[cfg(feature = "tilesets")]
+                animation_frames: Vec::new(),
             },
             #[cfg(feature = "tilesets")]
             sprite_cache,
@@ -324,6 +400,116 @@ impl SoftwareRenderer {
         self.ctx.pixel_buf.as_ref()
     }
 
+    /// The frame [`present`](Self::present) shows: [`pixels`](Self::pixels) with the configured
+    /// [`CrtEffects`] applied, or `pixels` itself while the effects are off.
+    ///
+    /// Same size and pixel format as `pixels`, and brought up to date by every call that changes
+    /// either the buffer or the effects.
+    #[must_use]
+    pub fn output_pixels(&self) -> &[u32] {
+        if self.ctx.crt_buf.is_empty() {
+            self.ctx.pixel_buf.as_ref()
+        } else {
+            &self.ctx.crt_buf
+        }
+    }
+
+    /// The CRT post-processing effects currently applied.
+    #[must_use]
+    pub const fn crt(&self) -> CrtEffects {
+        self.options.crt
+    }
+
+    /// Replaces the CRT post-processing effects, e.g. when the player toggles them in a settings
+    /// menu. Pass [`CrtEffects::NONE`] to turn them off.
+    ///
+    /// Takes effect immediately: [`output_pixels`](Self::output_pixels) is recomputed from the
+    /// current frame, and the next [`present`](Self::present) resends the whole frame, with no
+    /// redraw needed.
+    pub fn set_crt(&mut self, effects: CrtEffects) {
+        self.options.crt = effects;
+        self.refresh_crt();
+        self.ctx.damage_rows = self.full_damage();
+    }
+
+    /// Re-derives `crt_buf` from `pixel_buf` for the current effects, or empties it while they are
+    /// off.
+    fn refresh_crt(&mut self) {
+        if self.options.crt.is_enabled() {
+            let (width, height) = self.surface_dims();
+            crt::apply(
+                &self.options.crt,
+                self.ctx.pixel_buf.as_ref(),
+                width,
+                height,
+                &mut self.ctx.crt_buf,
+            );
+        } else {
+            self.ctx.crt_buf.clear();
+        }
+    }
+
+    /// The pixel buffer's `(width, height)`.
+    fn surface_dims(&self) -> (usize, usize) {
+        let (width, height) = self
+            .ctx
+            .geometry
+            .surface_size(self.options.cols, self.options.rows);
+        // u32 always fits in usize (all targets: 32- and 64-bit).
+        (
+            usize::try_from(width).expect("surface width fits usize"),
+            usize::try_from(height).expect("surface height fits usize"),
+        )
+    }
+
+    /// A damage band covering every row, or `None` for an empty buffer.
+    fn full_damage(&self) -> Option<(u32, u32)> {
+        let (_, height) = self
+            .ctx
+            .geometry
+            .surface_size(self.options.cols, self.options.rows);
+        (height > 0).then_some((0, height))
+    }
+
+    /// How the frame is fitted into the window.
+    #[must_use]
+    pub const fn scaling(&self) -> ScalingMode {
+        self.options.scaling
+    }
+
+    /// Where the frame is shown in the window, or `None` under [`ScalingMode::Reflow`], where the
+    /// surface is the frame. Before the first [`resize_surface`](Self::resize_surface) the window
+    /// is taken to be exactly the frame's size.
+    fn viewport(&self) -> Option<Viewport> {
+        if self.options.scaling.reflows() {
+            return None;
+        }
+        let frame = self
+            .ctx
+            .geometry
+            .surface_size(self.options.cols, self.options.rows);
+        Some(
+            self.options
+                .scaling
+                .viewport(frame, self.ctx.window_size.unwrap_or(frame)),
+        )
+    }
+
+    /// The palette colors currently resolve through.
+    #[must_use]
+    pub const fn palette(&self) -> &Palette {
+        &self.options.palette
+    }
+
+    /// Replaces the palette colors resolve through, e.g. when the player picks another theme.
+    ///
+    /// Every cell is repainted on the next [`Output::draw_layers`] call, since an unchanged tile
+    /// can still change color.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.options.palette = palette;
+        self.ctx.invalidate_shadow();
+    }
+
     /// Pushes an event into the internal buffer, to be drained by
     /// [`Input::poll_event`].
     ///
@@ -335,6 +521,9 @@ impl SoftwareRenderer {
     /// rule.
     pub fn push_event(&mut self, event: Event) {
         push_coalesced(&mut self.ctx.event_buffer, event);
+        if let Some(waker) = self.ctx.event_waker.take() {
+            waker.wake();
+        }
     }
 
     /// Initializes the window surface from a raw window/display handle.
@@ -356,16 +545,23 @@ impl SoftwareRenderer {
         Ok(())
     }
 
-    /// Resizes the window surface to `width` x `height` pixels. No-op if the
-    /// surface has not been initialized via [`init_surface`](Self::init_surface).
+    /// Resizes the window surface to `width` x `height` pixels, and under a non-reflowing
+    /// [`ScalingMode`] re-places the frame in it. The surface resize is a no-op if the surface
+    /// has not been initialized via [`init_surface`](Self::init_surface).
     pub fn resize_surface(&mut self, width: u32, height: u32) {
+        self.ctx.window_size = Some((width, height));
+        if !self.options.scaling.reflows() {
+            // The whole window is repainted through the new viewport.
+            self.ctx.damage_rows = Some((0, height));
+        }
         if let Some(surf) = &mut self.ctx.window_surface {
             surf.resize(width, height);
         }
     }
 
-    /// Presents the pixel buffer to the window surface. No-op in headless
-    /// mode (no surface initialized).
+    /// Presents [`output_pixels`](Self::output_pixels) to the window surface, scaled into the
+    /// window under a non-reflowing [`ScalingMode`]. No-op in headless mode (no surface
+    /// initialized).
     ///
     /// # Errors
     ///
@@ -376,6 +572,8 @@ impl SoftwareRenderer {
     /// tracking are unaffected by a failed present, so the next successful present resends
     /// the current frame rather than a stale one.
     pub fn present(&mut self) -> Result<(), SurfaceError> {
+        let full_damage = self.full_damage();
+        let viewport = self.viewport();
         let Some(surface) = self.ctx.window_surface.as_mut() else {
             return Ok(()); // headless mode, nothing to present
         };
@@ -384,7 +582,30 @@ impl SoftwareRenderer {
         let Some(damage) = self.ctx.damage_rows else {
             return Ok(());
         };
-        let result = surface.present(self.ctx.pixel_buf.as_ref(), damage);
+        // The CRT effects carry a change past the rows it touched (the glow reaches two rows out,
+        // curvature moves rows bodily), so with them on every changed frame is sent whole.
+        let (pixels, damage) = if self.ctx.crt_buf.is_empty() {
+            (self.ctx.pixel_buf.as_ref(), damage)
+        } else {
+            (self.ctx.crt_buf.as_slice(), full_damage.unwrap_or(damage))
+        };
+        // A scaled frame is resent whole, bars and all: a changed frame row lands on a different
+        // number of window rows at every scale.
+        let (pixels, damage) = match (viewport, self.ctx.window_size) {
+            (Some(viewport), Some((width, height))) => {
+                let (r, g, b) = self.options.scaling.bars();
+                scale::blit(
+                    pixels,
+                    &viewport,
+                    (width, height),
+                    (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b),
+                    &mut self.ctx.window_buf,
+                );
+                (self.ctx.window_buf.as_slice(), (0, height))
+            }
+            _ => (pixels, damage),
+        };
+        let result = surface.present(pixels, damage);
         // Only drop the damage once it's actually been presented, so a later
         // present() with no new draw_layers() call is a no-op instead of
         // re-presenting stale damage. On failure, leave it set so the next
@@ -480,7 +701,9 @@ impl SoftwareRenderer {
     /// `tilesets` feature there is no sprite cache at all, so this always returns `false`.
     #[cfg(feature = "tilesets")]
     fn has_sprite(&self, glyph: char) -> bool {
-        self.sprite_cache.get(glyph).is_some()
+        self.sprite_cache
+            .get_at(glyph, self.ctx.animation_time)
+            .is_some()
     }
 
     // Signature has to match the `tilesets` arm above (both are called uniformly as
@@ -492,6 +715,47 @@ impl SoftwareRenderer {
         false
     }
 
+    /// Marks dirty every cell, on any layer, holding an animated glyph whose frame has moved since
+    /// the last call, and returns whether there was one.
+    ///
+    /// An animation advancing changes a cell's pixels with its tile, and so the shadow diff,
+    /// unchanged. Runs after the stream so it sees this frame's tiles, and before span and wide
+    /// character expansion so an animated span anchor repaints its whole footprint.
+    #[cfg(feature = "tilesets")]
+    fn mark_animated_cells_dirty(&mut self, cols: usize, rows: usize) -> bool {
+        let animations = self.sprite_cache.animations();
+        if animations.is_empty() {
+            return false;
+        }
+        let frames: Vec<(char, usize)> = animations.frames_at(self.ctx.animation_time).collect();
+        let moved: Vec<char> = frames
+            .iter()
+            .zip(&self.ctx.animation_frames)
+            .filter(|(now, then)| now.1 != then.1)
+            .map(|(now, _)| now.0)
+            .collect();
+        self.ctx.animation_frames = frames;
+        if moved.is_empty() {
+            return false;
+        }
+        let mut any = false;
+        // A layer this frame didn't stream may still be sized for an older grid.
+        for layer in self
+            .ctx
+            .layers
+            .iter()
+            .filter(|layer| layer.fits(cols, rows))
+        {
+            for (idx, tile) in layer.tiles.as_ref().iter().enumerate() {
+                if moved.contains(&tile.glyph()) {
+                    self.ctx.dirty_mask[idx] = true;
+                    any = true;
+                }
+            }
+        }
+        any
+    }
+
     /// Grows `layers` to cover `layer_idx` if this is the first time it has been seen, or
     /// replaces that layer's [`LayerShadow`] wholesale if a previous grid size left it stale.
     /// Each replacement uses [`LayerShadow::new`], never a resize-in-place: a stale layer's
@@ -533,14 +797,29 @@ impl SoftwareRenderer {
             },
         );
         let has_sprite = self.has_sprite(anchor_glyph);
-        resolve_bg_fill(&self.ctx.layers, layer_id, idx, has_sprite)
+        resolve_bg_fill(
+            &self.ctx.layers,
+            &self.options.palette,
+            layer_id,
+            idx,
+            has_sprite,
+        )
     }
 
     /// Fills a cell's background rectangle when `bg_fill` is opaque. The rectangle is always the
     /// full, unshifted cell: sub-cell `dx`/`dy` offsets move only the glyph, never the background.
-    fn fill_cell_bg(&mut self, cell_w: usize, cell_h: usize, pos: Pos, bg_fill: Option<u32>) {
+    /// On a double-size row that cell is two columns wide (see [`line_scale`]).
+    fn fill_cell_bg(
+        &mut self,
+        cell_w: usize,
+        cell_h: usize,
+        pos: Pos,
+        line: LineSize,
+        bg_fill: Option<u32>,
+    ) {
         if let Some(bg) = bg_fill {
-            let cell = ixy::Rect::new(usize::from(pos.x), usize::from(pos.y), 1, 1);
+            let wide = line_scale(line).0;
+            let cell = ixy::Rect::new(usize::from(pos.x) * wide, usize::from(pos.y), wide, 1);
             let rect = cell * ixy::Size::new(cell_w, cell_h);
             self.ctx.pixel_buf.fill_rect_solid(rect, bg);
         }
@@ -553,7 +832,15 @@ impl SoftwareRenderer {
     ///
     /// A sprite is additionally shifted by its alignment inside the tile's span box (see
     /// [`Sprite::align_offset`]), which is `(0, 0)` unless the span reserves more cells than the
-    /// artwork fills.
+    /// artwork fills, and drawn flipped, turned, and scaled by the cell's [`Transform`].
+    ///
+    /// On a double-size row the cell starts at column `2x`, and a bitmap-font glyph is stretched
+    /// by [`line_scale`]; a double-height half keeps only the half of the glyph that lands inside
+    /// its own row. A sprite keeps its pixel size, the same as on the GPU backends.
+    ///
+    /// `lead` is the wide character a spacer cell continues (see [`wide_lead`]): that cell draws
+    /// the right half of the character's double-width glyph, if a font has one, instead of its
+    /// own blank.
     #[allow(clippy::too_many_arguments)]
     fn blit_cell_glyph(
         &mut self,
@@ -562,14 +849,19 @@ impl SoftwareRenderer {
         cell_h: usize,
         scale: usize,
         pos: Pos,
+        line: LineSize,
         tile: Tile,
+        lead: Option<Tile>,
         // Only ever read inside the `tilesets`-gated sprite path below: a bitmap-font glyph is
         // always drawn in the cell's own foreground color, never tinted (tints apply to
         // sprites only, per `Surface::with_tint`), so a `tilesets`-off build has no use for it.
         tint: Tint,
+        // Sprite-only for the same reason: a bitmap-font glyph is drawn as authored.
+        transform: Transform,
+        remap: Option<Remap>,
     ) {
         #[cfg(not(feature = "tilesets"))]
-        let _ = tint;
+        let _ = (tint, transform, remap);
 
         // A span-covered or blank cell draws no art at all (see `cell_art_glyph`): neither a
         // sprite nor a bitmap-font glyph. Deciding that once here, before either lookup, is the
@@ -580,7 +872,8 @@ impl SoftwareRenderer {
             return;
         };
 
-        let px_x = usize::from(pos.x) * cell_w;
+        let wide = line_scale(line).0;
+        let px_x = usize::from(pos.x) * cell_w * wide;
         let px_y = usize::from(pos.y) * cell_h;
 
         // Sprite cache dispatch: sprite wins over bitmap font.
@@ -588,11 +881,17 @@ impl SoftwareRenderer {
         {
             let buf_h = self.ctx.pixel_buf.as_ref().len() / buf_w;
             let (glyph_w, glyph_h) = (self.ctx.geometry.glyph_w, self.ctx.geometry.glyph_h);
-            if let Some(sprite) = self.sprite_cache.get(art_glyph) {
+            if let Some(sprite) = self.sprite_cache.get_at(art_glyph, self.ctx.animation_time) {
                 let (span_w, span_h) = tile.span();
-                let align = sprite.align_offset(span_w, span_h, glyph_w, glyph_h);
-                let recolor =
-                    SpriteTint::resolve(sprite.color, tile.style().foreground(), tint, DEFAULT_FG);
+                let align =
+                    sprite.transformed_align_offset(transform, span_w, span_h, glyph_w, glyph_h);
+                let recolor = SpriteTint::resolve_with_palette(
+                    sprite.color,
+                    tile.style().foreground(),
+                    tint,
+                    &self.options.palette,
+                )
+                .with_remap(remap.as_ref());
                 blit_sprite(
                     self.ctx.pixel_buf.as_mut(),
                     buf_w,
@@ -604,11 +903,12 @@ impl SoftwareRenderer {
                     sprite,
                     scale,
                     recolor,
+                    transform,
                 );
                 if !tile.is_span_anchor() {
                     self.ctx.diagnostics.sprite_needs_span(
                         art_glyph,
-                        (sprite.pixel_width, sprite.pixel_height),
+                        transform.size(sprite.pixel_width, sprite.pixel_height),
                         (u32::from(glyph_w), u32::from(glyph_h)),
                     );
                 }
@@ -619,16 +919,43 @@ impl SoftwareRenderer {
             // effect here (retroglyph#564, #537's exact trap).
             self.ctx.diagnostics.tint_needs_sprite(art_glyph, tint);
         }
+        // A wide character drawn as a sprite has no glyph for its spacer to finish.
+        #[cfg(feature = "tilesets")]
+        let lead = lead.filter(|lead| {
+            self.sprite_cache
+                .get_at(lead.glyph(), self.ctx.animation_time)
+                .is_none()
+        });
 
+        let (wide, tall) = line_scale(line);
+        // A double-height half is drawn into its own row's band of the buffer, with the glyph
+        // twice the band's height: the band clips it to the top half, or, shifted up a row, to
+        // the bottom one.
+        let (buffer, px_y) = if line.is_double_height() {
+            let band = px_y * buf_w..(px_y + cell_h) * buf_w;
+            let top = if line == LineSize::DoubleHeightBottom {
+                -i64::try_from(cell_h).expect("cell height fits i64")
+            } else {
+                0
+            };
+            (&mut self.ctx.pixel_buf.as_mut()[band], top)
+        } else {
+            let top = i64::try_from(px_y).expect("pixel row fits i64");
+            (self.ctx.pixel_buf.as_mut(), top)
+        };
+        let px_x = i64::try_from(px_x).expect("pixel column fits i64");
         blit_glyph(
-            self.ctx.pixel_buf.as_mut(),
+            buffer,
             buf_w,
             px_x,
             px_y,
             &tile,
+            lead.as_ref(),
             art_glyph,
             &self.fonts,
-            scale,
+            &mut self.scanlines,
+            &self.options.palette,
+            (scale * wide, scale * tall),
             &mut self.ctx.diagnostics,
         );
     }
@@ -690,7 +1017,7 @@ impl SoftwareBackend {
     /// this crate supports (`usize` is at least 32 bits on every 32- and 64-bit
     /// platform), so this is not reachable in practice.
     pub fn into_renderer(self) -> Result<SoftwareRenderer, SoftwareBackendError> {
-        let Some(fonts) = self.fonts else {
+        let Some(fonts) = self.fonts.clone() else {
             return Err(SoftwareBackendError::NoFont);
         };
         let Some((glyph_w, glyph_h)) = fonts.glyph_size() else {
@@ -764,6 +1091,10 @@ impl Output for SoftwareRenderer {
     /// sprite-transparency rule that decides whether a background is painted at all is resolved
     /// against the *anchor* (see `resolve_cell_bg`), so one span never sits on two different
     /// backdrops.
+    ///
+    /// A cell on a double-size row (see [`LineSize`]) paints two columns wide; see the private
+    /// `blit_cell_glyph` helper for how its glyph is stretched.
+    #[allow(clippy::too_many_lines)]
     fn draw_layers<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
     where
         I: Iterator<Item = DrawCell<'a>>,
@@ -794,6 +1125,8 @@ impl Output for SoftwareRenderer {
         let mut any_offset = false;
         let mut any_dirty = false;
         let mut max_layer_seen: i32 = -1;
+        self.ctx.next_line_sizes.clear();
+        self.ctx.next_line_sizes.resize(rows, LineSize::Normal);
 
         for draw_cell in content {
             let (layer_id, pos, tile) = (draw_cell.layer, draw_cell.pos, draw_cell.tile);
@@ -805,13 +1138,20 @@ impl Output for SoftwareRenderer {
             }
             let layer_idx = usize::from(layer_id);
             max_layer_seen = max_layer_seen.max(i32::from(layer_id));
+            self.ctx.next_line_sizes[usize::from(pos.y)] = draw_cell.line;
             self.ensure_layer_shadow(layer_idx, cols, rows);
 
             let idx = usize::from(pos.y) * cols + usize::from(pos.x);
             let shadow = &mut self.ctx.layers[layer_idx];
             let slot = &mut shadow.tiles.as_mut()[idx];
             let tint_slot = &mut shadow.tints.as_mut()[idx];
-            if *slot != *tile || *tint_slot != draw_cell.tint {
+            let transform_slot = &mut shadow.transforms.as_mut()[idx];
+            let remap_slot = &mut shadow.remaps.as_mut()[idx];
+            if *slot != *tile
+                || *tint_slot != draw_cell.tint
+                || *transform_slot != draw_cell.transform
+                || remap_slot.as_deref() != draw_cell.remap
+            {
                 // `dirty_mask` is a single array shared across layers, not one per layer: marking
                 // an index dirty here forces every layer to repaint that cell below, even ones
                 // unchanged at this position, because a lower layer's background fill covers the
@@ -821,20 +1161,35 @@ impl Output for SoftwareRenderer {
                 any_dirty = true;
                 *slot = *tile;
                 *tint_slot = draw_cell.tint;
+                *transform_slot = draw_cell.transform;
+                *remap_slot = draw_cell.remap.map(|remap| Box::new(*remap));
             }
-            if tile.dx() != 0 || tile.dy() != 0 {
+            // A turned or scaled sprite can cover cells its tile never declared, exactly like an
+            // offset one, so it takes the same full-repaint path. A flip alone stays in place.
+            if tile.dx() != 0
+                || tile.dy() != 0
+                || draw_cell.transform.scale() > 1
+                || draw_cell.transform.quarter_turns() % 2 == 1
+            {
                 any_offset = true;
             }
         }
 
+        #[cfg(feature = "tilesets")]
+        {
+            any_dirty |= self.mark_animated_cells_dirty(cols, rows);
+        }
+
         if any_dirty {
             // Runs after the whole stream, so every layer's shadow copy is current: a span's
             // footprint has to be read off an anchor this frame actually wrote. A covered cell's
             // tile does not change when only the anchor's artwork does, so a dirty cell anywhere
             // in a span has to dirty the whole span here; without that, the previous sprite's
-            // pixels would survive in cells the diff considers unchanged.
+            // pixels would survive in cells the diff considers unchanged. A wide character and its
+            // spacer are the same: the spacer draws half of the lead's glyph.
             for layer in &self.ctx.layers {
                 expand_dirty_spans(&mut self.ctx.dirty_mask, layer.tiles.as_ref(), cols, rows);
+                expand_dirty_wide_chars(&mut self.ctx.dirty_mask, layer.tiles.as_ref(), cols);
             }
         }
 
@@ -842,6 +1197,8 @@ impl Output for SoftwareRenderer {
         let layer_count_now = (max_layer_seen + 1) as usize;
         let layers_changed = layer_count_now != self.ctx.prev_layer_count;
         self.ctx.prev_layer_count = layer_count_now;
+        let lines_changed = self.ctx.next_line_sizes != self.ctx.line_sizes;
+        core::mem::swap(&mut self.ctx.line_sizes, &mut self.ctx.next_line_sizes);
 
         // Falls back to a full clear-and-repaint of every cell when either:
         // - any tile this frame or the last one has a nonzero sub-cell offset (`Tile::dx`/`dy`):
@@ -854,8 +1211,10 @@ impl Output for SoftwareRenderer {
         //   already compares against last frame's state instead of only this frame's; or
         // - the number of allocated layers changed since the last call: a layer's cells falling
         //   out of (or into) the frame can't be diffed against a shadow copy that no longer
-        //   describes this frame's layer set.
-        let full_repaint = any_offset || self.ctx.prev_offset || layers_changed;
+        //   describes this frame's layer set; or
+        // - a row changed its line size: every cell on it moves, and the right half of a row that
+        //   went double-size holds cells that are no longer streamed to be repainted over.
+        let full_repaint = any_offset || self.ctx.prev_offset || layers_changed || lines_changed;
         self.ctx.prev_offset = any_offset;
 
         if full_repaint {
@@ -865,10 +1224,14 @@ impl Output for SoftwareRenderer {
                 let layer_id = layer_id as u8;
                 // Pass 1: lay down every cell's background on this layer first.
                 for idx in 0..cell_count {
-                    let bg_fill = self.resolve_cell_bg(layer_id, idx, cols);
                     let (x, y) = flat_index_to_xy(idx, cols);
+                    let line = self.ctx.line_sizes[usize::from(y)];
+                    if x >= line.columns(self.options.cols) {
+                        continue;
+                    }
+                    let bg_fill = self.resolve_cell_bg(layer_id, idx, cols);
                     let pos = Pos::new(x, y);
-                    self.fill_cell_bg(cell_w, cell_h, pos, bg_fill);
+                    self.fill_cell_bg(cell_w, cell_h, pos, line, bg_fill);
                 }
                 // Pass 2: blit every cell's glyph over those backgrounds. A glyph offset past its
                 // right/bottom edge now spills onto the neighbor's already-painted background
@@ -876,11 +1239,22 @@ impl Output for SoftwareRenderer {
                 // uniform in all four directions: the two-pass mechanism of the sub-cell
                 // offset/spill contract on `retroglyph_window::presenter::Presenter` (see its rustdoc).
                 for idx in 0..cell_count {
-                    let tile = self.ctx.layers[layer_id as usize].tiles.as_ref()[idx];
-                    let tint = self.ctx.layers[layer_id as usize].tints.as_ref()[idx];
                     let (x, y) = flat_index_to_xy(idx, cols);
+                    let line = self.ctx.line_sizes[usize::from(y)];
+                    if x >= line.columns(self.options.cols) {
+                        continue;
+                    }
+                    let tiles = self.ctx.layers[layer_id as usize].tiles.as_ref();
+                    let (tile, lead) = (tiles[idx], wide_lead(tiles, idx, x));
+                    let tint = self.ctx.layers[layer_id as usize].tints.as_ref()[idx];
+                    let transform = self.ctx.layers[layer_id as usize].transforms.as_ref()[idx];
+                    let remap = self.ctx.layers[layer_id as usize].remaps.as_ref()[idx]
+                        .as_deref()
+                        .copied();
                     let pos = Pos::new(x, y);
-                    self.blit_cell_glyph(buf_w, cell_w, cell_h, scale, pos, tile, tint);
+                    self.blit_cell_glyph(
+                        buf_w, cell_w, cell_h, scale, pos, line, tile, lead, tint, transform, remap,
+                    );
                 }
             }
         } else if any_dirty {
@@ -896,25 +1270,43 @@ impl Output for SoftwareRenderer {
                     if !self.ctx.dirty_mask[idx] {
                         continue;
                     }
-                    let bg_fill = self.resolve_cell_bg(layer_id, idx, cols);
                     let (x, y) = flat_index_to_xy(idx, cols);
+                    let line = self.ctx.line_sizes[usize::from(y)];
+                    if x >= line.columns(self.options.cols) {
+                        continue;
+                    }
+                    let bg_fill = self.resolve_cell_bg(layer_id, idx, cols);
                     let pos = Pos::new(x, y);
-                    self.fill_cell_bg(cell_w, cell_h, pos, bg_fill);
+                    self.fill_cell_bg(cell_w, cell_h, pos, line, bg_fill);
                 }
                 for idx in 0..cell_count {
                     if !self.ctx.dirty_mask[idx] {
                         continue;
                     }
-                    let tile = self.ctx.layers[usize::from(layer_id)].tiles.as_ref()[idx];
-                    let tint = self.ctx.layers[usize::from(layer_id)].tints.as_ref()[idx];
                     let (x, y) = flat_index_to_xy(idx, cols);
+                    let line = self.ctx.line_sizes[usize::from(y)];
+                    if x >= line.columns(self.options.cols) {
+                        continue;
+                    }
+                    let tiles = self.ctx.layers[usize::from(layer_id)].tiles.as_ref();
+                    let (tile, lead) = (tiles[idx], wide_lead(tiles, idx, x));
+                    let tint = self.ctx.layers[usize::from(layer_id)].tints.as_ref()[idx];
+                    let transform = self.ctx.layers[usize::from(layer_id)].transforms.as_ref()[idx];
+                    let remap = self.ctx.layers[usize::from(layer_id)].remaps.as_ref()[idx]
+                        .as_deref()
+                        .copied();
                     let pos = Pos::new(x, y);
-                    self.blit_cell_glyph(buf_w, cell_w, cell_h, scale, pos, tile, tint);
+                    self.blit_cell_glyph(
+                        buf_w, cell_w, cell_h, scale, pos, line, tile, lead, tint, transform, remap,
+                    );
                 }
             }
         }
 
         self.update_damage(buf_w);
+        if full_repaint || any_dirty {
+            self.refresh_crt();
+        }
         Ok(())
     }
 
@@ -946,6 +1338,7 @@ impl Output for SoftwareRenderer {
         // call can't misread stale entries against the new layout, and force that call onto the
         // full-repaint path.
         self.ctx.invalidate_shadow();
+        self.refresh_crt();
         self.ctx.damage_rows = if new_h == 0 {
             None
         } else {
@@ -956,6 +1349,7 @@ impl Output for SoftwareRenderer {
 
     fn clear(&mut self) -> Result<(), Self::Error> {
         self.ctx.pixel_buf.clear();
+        self.refresh_crt();
         // The per-cell shadow is now stale versus what's actually on screen (blank); forget it,
         // mirroring `resize` above, so the next `draw_layers` call can't diff against pre-clear
         // state and takes the full-repaint path instead of painting nothing.
@@ -983,6 +1377,16 @@ impl Input for SoftwareRenderer {
     fn push_event(&mut self, event: Event) {
         Self::push_event(self, event);
     }
+
+    /// Parks until [`push_event`](Self::push_event) queues something: the browser's event
+    /// handlers are the only source, and each push wakes the task.
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        if let Some(event) = self.ctx.event_buffer.pop_front() {
+            return Poll::Ready(event);
+        }
+        self.ctx.event_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
 }
 
 // ── Cursor impl ──────────────────────────────────────────────────────────────────
@@ -997,6 +1401,12 @@ impl Cursor for SoftwareRenderer {
     }
 }
 
+// ── Clipboard impl ───────────────────────────────────────────────────────────
+
+/// No clipboard: a bare framebuffer has no windowing system to ask. `retroglyph-window`'s
+/// `WindowBackend`, which owns the window this renderer is presented into, provides one.
+impl Clipboard for SoftwareRenderer {}
+
 // ── Presenter impl ───────────────────────────────────────────────────────────
 
 // `SoftwareRenderer`'s own `Output` impl above already satisfies `Presenter: Output`, so this
@@ -1017,28 +1427,28 @@ impl retroglyph_window::presenter::Presenter for SoftwareRenderer {
     }
 
     fn geometry(&self) -> CellGeometry {
-        self.ctx.geometry
+        self.viewport().map_or(self.ctx.geometry, |viewport| {
+            self.ctx.geometry.with_viewport(viewport)
+        })
+    }
+
+    fn scaling(&self) -> ScalingMode {
+        self.options.scaling
+    }
+
+    #[cfg(feature = "tilesets")]
+    fn animation_time(&self) -> Duration {
+        self.ctx.animation_time
+    }
+
+    #[cfg(feature = "tilesets")]
+    fn set_animation_time(&mut self, time: Duration) {
+        self.ctx.animation_time = time;
     }
 }
 
 // ── Grid compositing ──────────────────────────────────────────────────────────
 
-/// Paints the set ("on") pixels of glyph `glyph_index` in `font` into `buffer` as `color`, with
-/// each source pixel scaled to a `scale x scale` destination block.
-///
-/// Set pixels come from [`BitmapFont::glyph_pixels`], the one place the 1-bit MSB-first bit layout
-/// is decoded (shared with the GL atlas builder), so this backend and `retroglyph-gl` can't
-/// disagree on which texels a glyph covers, and neither hardcodes an 8-pixel row.
-///
-/// The glyph's top-left destination corner is `(origin_x, origin_y)`
-/// (already including any sub-cell `dx`/`dy` offset, scaled). When the whole
-/// glyph's destination bounding box fits inside `buffer` (the overwhelmingly
-/// common case, since it only fails for cells with a nonzero `dx`/`dy` that
-/// pushes them past a buffer edge), this takes a fast path with no per-pixel
-/// bounds check: it fills each `scale`-wide destination run in one slice
-/// `fill` call. Otherwise it falls back to a row-clamped path that clips
-/// each destination run to the buffer bounds once per row, rather than
-/// checking every pixel.
 /// Decodes a flat row-major cell index into `(x, y)`, given the grid's `cols`.
 ///
 /// Delegates to [`RowMajor`]'s [`LinearLayout::index_to_pos`] instead of hand-rolling
@@ -1049,6 +1459,34 @@ fn flat_index_to_xy(idx: usize, cols: usize) -> (u16, u16) {
     (pos.x as u16, pos.y as u16)
 }
 
+/// How many times wider and taller than a normal cell each cell of a `line`-sized row is drawn:
+/// `(2, 1)` on a double-width row, `(2, 2)` on either half of a double-height one.
+const fn line_scale(line: LineSize) -> (usize, usize) {
+    (
+        if line.is_double_width() { 2 } else { 1 },
+        if line.is_double_height() { 2 } else { 1 },
+    )
+}
+
+/// Paints the covered pixels of a glyph into `buffer` as `color`, given as the glyph's
+/// `spans` (see [`GlyphSpan`]) and its `glyph_size` in unscaled pixels, with each source pixel
+/// scaled to an `sx x sy` destination block, where `scale` is `(sx, sy)`: the two differ only on
+/// a double-size row (see [`line_scale`]).
+///
+/// The spans come from [`BitmapFont::glyph_spans`] (through the renderer's [`ScanlineCache`]),
+/// which shares its bit decoding with the GL/wgpu atlas builder, so this backend and the GPU
+/// backends can't disagree on which texels a glyph covers, and neither hardcodes an 8-pixel row.
+///
+/// The glyph's top-left destination corner is `(origin_x, origin_y)`
+/// (already including any sub-cell `dx`/`dy` offset, scaled). When the whole
+/// glyph's destination bounding box fits inside `buffer` (the overwhelmingly
+/// common case, since it only fails for cells with a nonzero `dx`/`dy` that
+/// pushes them past a buffer edge), this takes a fast path with no per-pixel
+/// bounds check: it fills each span's `len * sx`-wide destination run in one slice
+/// `fill` call (or, for a partially covered span of an antialiased font, blends it; see
+/// [`paint_run`]). Otherwise it falls back to a row-clamped path that clips
+/// each destination run to the buffer bounds once per row, rather than
+/// checking every pixel.
 #[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
 fn blit_glyph_mask(
     buffer: &mut [u32],
@@ -1056,13 +1494,14 @@ fn blit_glyph_mask(
     buf_h: usize,
     origin_x: i64,
     origin_y: i64,
-    font: &BitmapFont,
-    glyph_index: u8,
-    scale: usize,
+    spans: &[GlyphSpan],
+    glyph_size: (u8, u8),
+    scale: (usize, usize),
     color: u32,
 ) {
-    let glyph_w = usize::from(font.glyph_width()) * scale;
-    let glyph_h = usize::from(font.glyph_height()) * scale;
+    let (sx, sy) = scale;
+    let glyph_w = usize::from(glyph_size.0) * sx;
+    let glyph_h = usize::from(glyph_size.1) * sy;
 
     #[allow(clippy::cast_sign_loss)]
     let in_bounds = origin_x >= 0
@@ -1075,12 +1514,17 @@ fn blit_glyph_mask(
         let ox = origin_x as usize;
         #[allow(clippy::cast_sign_loss)]
         let oy = origin_y as usize;
-        for (src_x, src_y) in font.glyph_pixels(glyph_index) {
-            let x0 = ox + usize::from(src_x) * scale;
-            let y0 = oy + usize::from(src_y) * scale;
-            for sdy in 0..scale {
+        for span in spans {
+            let x0 = ox + usize::from(span.x) * sx;
+            let y0 = oy + usize::from(span.y) * sy;
+            let run = usize::from(span.len) * sx;
+            for sdy in 0..sy {
                 let row_start = (y0 + sdy) * buf_w + x0;
-                buffer[row_start..row_start + scale].fill(color);
+                paint_run(
+                    &mut buffer[row_start..row_start + run],
+                    color,
+                    span.coverage,
+                );
             }
         }
         return;
@@ -1091,15 +1535,15 @@ fn blit_glyph_mask(
         clippy::cast_sign_loss,
         clippy::similar_names
     )]
-    for (src_x, src_y) in font.glyph_pixels(glyph_index) {
-        for sdy in 0..scale {
-            let y = origin_y + (usize::from(src_y) * scale + sdy) as i64;
+    for span in spans {
+        for sdy in 0..sy {
+            let y = origin_y + (usize::from(span.y) * sy + sdy) as i64;
             if y < 0 || y as usize >= buf_h {
                 continue;
             }
             let y = y as usize;
-            let x_start = origin_x + (usize::from(src_x) * scale) as i64;
-            let x_end = x_start + scale as i64;
+            let x_start = origin_x + (usize::from(span.x) * sx) as i64;
+            let x_end = x_start + (usize::from(span.len) * sx) as i64;
             let x0 = x_start.max(0);
             let x1 = x_end.min(buf_w as i64);
             if x0 >= x1 {
@@ -1107,11 +1551,29 @@ fn blit_glyph_mask(
             }
             let row_start = y * buf_w + x0 as usize;
             let row_end = y * buf_w + x1 as usize;
-            buffer[row_start..row_end].fill(color);
+            paint_run(&mut buffer[row_start..row_end], color, span.coverage);
         }
     }
 }
 
+/// Paints one destination run of a glyph span: a plain `fill` at full coverage (every span of a
+/// 1-bit font), otherwise `color` blended over each pixel already there by `coverage`, per
+/// channel in sRGB-encoded space like a sprite (see `docs/references/core/color-space.md`).
+fn paint_run(run: &mut [u32], color: u32, coverage: u8) {
+    if coverage == 0xFF {
+        run.fill(color);
+        return;
+    }
+    let a = u32::from(coverage);
+    for pixel in run {
+        let mix = |shift: u32| {
+            let (src, dst) = ((color >> shift) & 0xFF, (*pixel >> shift) & 0xFF);
+            ((src * a + dst * (255 - a) + 127) / 255) << shift
+        };
+        *pixel = mix(16) | mix(8) | mix(0);
+    }
+}
+
 /// Blits a glyph's set bits into `buffer` at `(px_x, px_y)` plus sub-cell
 /// offset from `tile.dx`/`tile.dy`. Only the foreground (glyph) pixels are
 /// painted; background is left untouched.
@@ -1119,37 +1581,59 @@ fn blit_glyph_mask(
 /// `art_glyph` is the caller-resolved [`cell_art_glyph`] answer for `tile`: whether this cell
 /// draws at all (span-covered and blank cells are filtered before this is ever called) is decided
 /// once by the caller, not re-derived here.
+///
+/// A wide character's cell draws the left half of its double-width glyph, and its spacer (with
+/// `lead` set to the wide character's tile) the right half, in the lead's color and offset. When
+/// no [double-width font](FontChain::with_wide_font) covers the character, both fall back to the
+/// single-cell path: the lead draws the chain's glyph (usually notdef) and the spacer its blank.
 #[allow(clippy::cast_possible_truncation, clippy::too_many_arguments)]
 fn blit_glyph(
     buffer: &mut [u32],
     buf_w: usize,
-    px_x: usize,
-    px_y: usize,
+    px_x: i64,
+    px_y: i64,
     tile: &Tile,
+    lead: Option<&Tile>,
     art_glyph: char,
     fonts: &FontChain<'static>,
-    scale: usize,
+    scanlines: &mut ScanlineCache,
+    palette: &Palette,
+    scale: (usize, usize),
     diagnostics: &mut DiagnosticLog,
 ) {
-    let fg = resolve_color(tile.style().foreground(), DEFAULT_FG);
+    let wide = match lead {
+        Some(lead) => fonts
+            .resolve_wide(lead.glyph(), WideHalf::Right)
+            .map(|glyph| (glyph, lead)),
+        None if tile.is_wide() => fonts
+            .resolve_wide(art_glyph, WideHalf::Left)
+            .map(|glyph| (glyph, tile)),
+        None => None,
+    };
+    let (glyph, tile) = if let Some(wide) = wide {
+        wide
+    } else {
+        // Nothing in the chain can draw this character, not even a substitute box: leave the
+        // cell at its background rather than pointing at a glyph index some font doesn't have.
+        let Some(glyph) = fonts.resolve(art_glyph) else {
+            return;
+        };
+        // A resolved glyph flagged `is_notdef` drew the fallback, not `art_glyph`'s own shape --
+        // a legitimate cell on its own (a solid block can be drawn on purpose), so this is the
+        // only place a caller finds out it happened at all (retroglyph#1292).
+        if glyph.is_notdef() {
+            diagnostics.notdef_glyph(art_glyph);
+        }
+        (glyph, tile)
+    };
+    let fg = pack_rgb(palette.foreground_rgb(tile.style().foreground()));
 
     #[allow(clippy::cast_possible_wrap)]
-    let origin_x = px_x as i64 + i64::from(tile.dx()) * scale as i64;
+    let origin_x = px_x + i64::from(tile.dx()) * scale.0 as i64;
     #[allow(clippy::cast_possible_wrap)]
-    let origin_y = px_y as i64 + i64::from(tile.dy()) * scale as i64;
-
-    // Nothing in the chain can draw this character, not even a substitute box: leave the cell
-    // at its background rather than pointing at a glyph index some font doesn't have.
-    let Some(glyph) = fonts.resolve(art_glyph) else {
-        return;
-    };
-    // A resolved glyph flagged `is_notdef` drew the fallback, not `art_glyph`'s own shape -- a
-    // legitimate cell on its own (a solid block can be drawn on purpose), so this is the only
-    // place a caller finds out it happened at all (retroglyph#1292).
-    if glyph.is_notdef() {
-        diagnostics.notdef_glyph(art_glyph);
-    }
+    let origin_y = px_y + i64::from(tile.dy()) * scale.1 as i64;
     let buf_h = buffer.len() / buf_w;
+    let columns = glyph.cell_columns();
 
     blit_glyph_mask(
         buffer,
@@ -1157,8 +1641,8 @@ fn blit_glyph(
         buf_h,
         origin_x,
         origin_y,
-        &glyph.font(),
-        glyph.index(),
+        scanlines.spans(&glyph),
+        (columns.end - columns.start, glyph.font().glyph_height()),
         scale,
         fg,
     );
@@ -1172,6 +1656,10 @@ fn blit_glyph(
 /// [`Sprite::align_offset`]). A sprite larger than one cell extends past the anchor cell into
 /// the cells its span covers.
 ///
+/// `transform` flips, turns, and scales the sprite about that same corner: the destination is
+/// [`Transform::size`] large, and each of its pixels is sampled from the source pixel
+/// [`Transform::source_pixel`] names, covering `scale * transform.scale()` screen pixels a side.
+///
 /// Pixels outside `buffer` bounds are silently clipped.
 ///
 /// Blending uses pure integer `U8x4Rgba::source_over`. Fully opaque pixels
@@ -1187,7 +1675,8 @@ fn blit_glyph(
     clippy::cast_possible_wrap,
     clippy::cast_sign_loss,
     clippy::similar_names,
-    clippy::too_many_arguments
+    clippy::too_many_arguments,
+    clippy::too_many_lines
 )]
 fn blit_sprite(
     buffer: &mut [u32],
@@ -1200,12 +1689,19 @@ fn blit_sprite(
     sprite: &Sprite,
     scale: usize,
     recolor: SpriteTint,
+    transform: Transform,
 ) {
     let origin_x = cell_px_x as i64 + i64::from(offset_x) * scale as i64;
     let origin_y = cell_px_y as i64 + i64::from(offset_y) * scale as i64;
 
     let src_w = sprite.pixel_width as usize;
-    let src_h = sprite.pixel_height as usize;
+    // The transformed image's size in sprite pixels, and the screen pixels each one covers.
+    let (dst_w, dst_h) = Transform::IDENTITY
+        .rotated(transform.quarter_turns())
+        .size(sprite.pixel_width, sprite.pixel_height);
+    let (dst_w, dst_h) = (dst_w as usize, dst_h as usize);
+    let scale = scale * usize::from(transform.scale());
+    let plain = transform.is_identity();
 
     // Precompute once whether the sprite's whole destination bounding box
     // fits inside `buffer`: true for the overwhelmingly common case (no
@@ -1213,16 +1709,29 @@ fn blit_sprite(
     // below skips the per-destination-pixel bounds check entirely; only
     // sprites clipped by a nonzero `dx`/`dy` or a screen edge fall back to
     // the clamped, per-row-checked slow path.
-    let glyph_w = src_w * scale;
-    let glyph_h = src_h * scale;
+    let glyph_w = dst_w * scale;
+    let glyph_h = dst_h * scale;
     let in_bounds = origin_x >= 0
         && origin_y >= 0
         && origin_x as usize + glyph_w <= buf_w
         && origin_y as usize + glyph_h <= buf_h;
     let identity = recolor.is_identity();
 
-    for src_y in 0..src_h {
-        for src_x in 0..src_w {
+    for out_y in 0..dst_h {
+        for out_x in 0..dst_w {
+            // `plain` is loop-invariant like `identity` below: an untransformed sprite reads its
+            // pixels in order, exactly as before transforms existed.
+            let (src_x, src_y) = if plain {
+                (out_x, out_y)
+            } else {
+                let (x, y) = transform.source_pixel(
+                    out_x as u32,
+                    out_y as u32,
+                    sprite.pixel_width,
+                    sprite.pixel_height,
+                );
+                (x as usize, y as usize)
+            };
             let src_idx = (src_y * src_w + src_x) * 4;
             let src = U8x4Rgba::new(
                 sprite.pixels[src_idx],
@@ -1252,20 +1761,20 @@ fn blit_sprite(
             let rgb = src.to_rgb_u32();
             if src.alpha() == 255 {
                 if in_bounds {
-                    let x0 = origin_x as usize + src_x * scale;
-                    let y0 = origin_y as usize + src_y * scale;
+                    let x0 = origin_x as usize + out_x * scale;
+                    let y0 = origin_y as usize + out_y * scale;
                     for dy in 0..scale {
                         let row_start = (y0 + dy) * buf_w + x0;
                         buffer[row_start..row_start + scale].fill(rgb);
                     }
                 } else {
                     for dy in 0..scale {
-                        let dst_y = origin_y + (src_y * scale + dy) as i64;
+                        let dst_y = origin_y + (out_y * scale + dy) as i64;
                         if dst_y < 0 || dst_y as usize >= buf_h {
                             continue;
                         }
                         let dst_y = dst_y as usize;
-                        let x_start = origin_x + (src_x * scale) as i64;
+                        let x_start = origin_x + (out_x * scale) as i64;
                         let x_end = x_start + scale as i64;
                         let x0 = x_start.max(0);
                         let x1 = x_end.min(buf_w as i64);
@@ -1282,8 +1791,8 @@ fn blit_sprite(
 
             // Each source pixel maps to `scale x scale` destination pixels.
             if in_bounds {
-                let x0 = origin_x as usize + src_x * scale;
-                let y0 = origin_y as usize + src_y * scale;
+                let x0 = origin_x as usize + out_x * scale;
+                let y0 = origin_y as usize + out_y * scale;
                 for dy in 0..scale {
                     let row = (y0 + dy) * buf_w;
                     for dx in 0..scale {
@@ -1297,14 +1806,14 @@ fn blit_sprite(
             }
 
             for dy in 0..scale {
-                let dst_y = origin_y + (src_y * scale + dy) as i64;
+                let dst_y = origin_y + (out_y * scale + dy) as i64;
                 if dst_y < 0 || dst_y as usize >= buf_h {
                     continue;
                 }
                 let dst_y = dst_y as usize;
 
                 for dx in 0..scale {
-                    let dst_x = origin_x + (src_x * scale + dx) as i64;
+                    let dst_x = origin_x + (out_x * scale + dx) as i64;
                     if dst_x < 0 || dst_x as usize >= buf_w {
                         continue;
                     }
@@ -1367,15 +1876,38 @@ fn expand_dirty_spans(dirty: &mut [bool], layer: &[Tile], cols: usize, rows: usi
     }
 }
 
-// The canonical `Color::Default` fallbacks live in `retroglyph-window`'s `palette` module so the
-// gl and software backends can't drift; imported above as `(u8, u8, u8)` triples.
+/// Extends `dirty` so that a wide character and its spacer are always repainted together.
+///
+/// The spacer draws the right half of the wide character's glyph, but its own tile holds only a
+/// blank in the same style, so it is unchanged whenever only the character is; the reverse holds
+/// for the lead when a later write replaced just the spacer.
+fn expand_dirty_wide_chars(dirty: &mut [bool], layer: &[Tile], cols: usize) {
+    for idx in 0..layer.len().min(dirty.len()) {
+        if let Some(x) = idx.checked_rem(cols)
+            && x + 1 < cols
+            && layer[idx].is_wide()
+            && (dirty[idx] || dirty[idx + 1])
+        {
+            dirty[idx] = true;
+            dirty[idx + 1] = true;
+        }
+    }
+}
+
+/// The wide character the spacer at `idx` (column `x`) of `layer` continues, if it is one.
+fn wide_lead(layer: &[Tile], idx: usize, x: u16) -> Option<Tile> {
+    if x == 0 || !layer[idx].is_wide_spacer() {
+        return None;
+    }
+    Some(layer[idx - 1]).filter(Tile::is_wide)
+}
 
 /// Determines the background this layer/tile should paint at `idx`, if any,
 /// mirroring [`Grid::flatten_into`](retroglyph_core::grid::Grid)'s background-inheritance rule so
 /// cell and pixel backends agree (retroglyph#304).
 ///
-/// - Layer 0 always paints: its own background, substituting [`DEFAULT_BG`] for
-///   [`Color::Default`].
+/// - Layer 0 always paints: its own background, substituting the palette's
+///   [`background`](Palette::background) for [`Color::Default`].
 /// - A higher layer's empty tile paints nothing (`None`): it doesn't contribute to the flattened
 ///   cell at all.
 /// - A higher layer's occupied (non-empty) tile with a non-[`Color::Default`] background paints
@@ -1383,7 +1915,7 @@ fn expand_dirty_spans(dirty: &mut [bool], layer: &[Tile], cols: usize, rows: usi
 /// - A higher layer's occupied tile with a [`Color::Default`] background still paints, *unless*
 ///   `has_sprite` is `true`: this is the fix for retroglyph#304, an occupied space is opaque and
 ///   erases whatever glyph a lower layer drew there, even though its own background is the
-///   default one. What it paints with is *not* [`DEFAULT_BG`] though: matching `flatten_into`'s
+///   default one. What it paints with is *not* the palette's background though: matching `flatten_into`'s
 ///   `if tile.style.bg != Color::Default` guard, a `Color::Default` background never overwrites
 ///   the destination background, so this walks back down through the layers below `layer_id`
 ///   (down to and including layer 0) to find whichever one last established a background, and
@@ -1394,6 +1926,7 @@ fn expand_dirty_spans(dirty: &mut [bool], layer: &[Tile], cols: usize, rows: usi
 ///   the cell-backend-parity rule this function otherwise implements just doesn't apply to them.
 fn resolve_bg_fill(
     layers: &[LayerShadow],
+    palette: &Palette,
     layer_id: u8,
     idx: usize,
     has_sprite: bool,
@@ -1401,13 +1934,13 @@ fn resolve_bg_fill(
     let layer_idx = usize::from(layer_id);
     let tile = layers[layer_idx].tiles.as_ref()[idx];
     if layer_idx == 0 {
-        return Some(resolve_color(tile.style().background(), DEFAULT_BG));
+        return Some(pack_rgb(palette.background_rgb(tile.style().background())));
     }
     if tile.is_empty() {
         return None;
     }
     if tile.style().background() != Color::Default {
-        return Some(resolve_color(tile.style().background(), DEFAULT_BG));
+        return Some(pack_rgb(palette.background_rgb(tile.style().background())));
     }
     if has_sprite {
         return None;
@@ -1415,22 +1948,19 @@ fn resolve_bg_fill(
     for below in (0..layer_idx).rev() {
         let bg = layers[below].tiles.as_ref()[idx].style().background();
         if below == 0 || bg != Color::Default {
-            return Some(resolve_color(bg, DEFAULT_BG));
+            return Some(pack_rgb(palette.background_rgb(bg)));
         }
     }
     unreachable!("the loop above always terminates at `below == 0`, which always returns")
 }
 
-/// Resolve a [`Color`] to a packed `0x00RRGGBB` value, substituting `default` for
-/// [`Color::Default`].
+/// Packs a [`Palette`]-resolved `(r, g, b)` triple into this backend's `0x00RRGGBB` `u32` pixel
+/// format.
 ///
-/// Delegates the actual palette resolution to `retroglyph-core`'s [`Color::resolve_rgb`], the
-/// single canonical color-to-RGB path every graphical backend shares (so the CPU rasterizer and
-/// `retroglyph-gl`'s GPU atlas agree on every pixel color); this only repacks core's `(r, g, b)`
-/// triple into this backend's `0x00RRGGBB` `u32` pixel format. The `default` fallback is a triple
-/// too (from [`retroglyph_window::palette`]), so no unpack step is needed.
-fn resolve_color(color: Color, default: (u8, u8, u8)) -> u32 {
-    let (r, g, b) = color.resolve_rgb(default);
+/// Color resolution itself is the palette's, the single color-to-RGB path every graphical backend
+/// shares (so the CPU rasterizer and `retroglyph-gl`'s GPU atlas agree on every pixel color);
+/// this only repacks the result.
+fn pack_rgb((r, g, b): (u8, u8, u8)) -> u32 {
     (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b)
 }
 
@@ -1442,7 +1972,7 @@ mod tests {
     use config::SoftwareBackendBuilder;
     use retroglyph_core::color::Color;
     use retroglyph_core::color::Style;
-    use retroglyph_core::grid::{Pos, Size};
+    use retroglyph_core::grid::{LineSize, Pos, Size};
 
     fn test_renderer() -> SoftwareRenderer {
         SoftwareBackendBuilder::new()
@@ -2158,6 +2688,52 @@ mod tests {
         );
     }
 
+    /// Draws `glyph` at cell `(0, 0)` of a 2x1 grid on a `line`-sized row, streaming only the
+    /// cells core would (the left half of a double-size row), and returns the pixels.
+    fn draw_on_line(glyph: char, line: LineSize) -> Vec<u32> {
+        let mut r = damage_renderer(2, 1);
+        let tiles = glyph_scene(2, 1, Color::rgb(10, 10, 10), (0, 0), glyph);
+        let visible = usize::from(line.columns(2));
+        let content = tiles[..visible].iter().enumerate().map(|(x, t)| {
+            #[allow(clippy::cast_possible_truncation)]
+            let pos = Pos::new(x as u16, 0);
+            DrawCell::on_layer(0, pos, t).with_line(line)
+        });
+        r.draw_layers(content).unwrap();
+        r.pixels().to_vec()
+    }
+
+    #[test]
+    fn double_size_rows_stretch_the_glyph_over_two_columns() {
+        let (cell_w, cell_h) = (8usize, 16usize);
+        let buf_w = 2 * cell_w;
+        let normal = draw_on_line('A', LineSize::Normal);
+        let wide = draw_on_line('A', LineSize::DoubleWidth);
+        let top = draw_on_line('A', LineSize::DoubleHeightTop);
+        let bottom = draw_on_line('A', LineSize::DoubleHeightBottom);
+        for y in 0..cell_h {
+            for x in 0..buf_w {
+                let at = |py: usize| normal[py * buf_w + x / 2];
+                assert_eq!(wide[y * buf_w + x], at(y), "double width at ({x}, {y})");
+                assert_eq!(top[y * buf_w + x], at(y / 2), "top half at ({x}, {y})");
+                let half = cell_h / 2 + y / 2;
+                assert_eq!(bottom[y * buf_w + x], at(half), "bottom half at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn a_line_size_change_repaints_the_whole_row() {
+        let mut r = damage_renderer(2, 1);
+        let tiles = glyph_scene(2, 1, Color::rgb(10, 10, 10), (1, 0), '@');
+        draw_scene(&mut r, 2, &tiles);
+        let cell =
+            DrawCell::on_layer(0, Pos::new(0, 0), &tiles[0]).with_line(LineSize::DoubleWidth);
+        r.draw_layers(core::iter::once(cell)).unwrap();
+        // The '@' in column 1 has fallen off the row; column 0's blank background covers it.
+        assert!(r.pixels().iter().all(|&p| p == 0x000A_0A0A));
+    }
+
     #[test]
     fn sub_cell_offset_forces_full_frame_fallback_even_for_unchanged_cells() {
         // 2x1 grid. Frame 1: cell 0 has an offset glyph, cell 1 is plain. Frame 2: identical
@@ -2230,25 +2806,149 @@ mod tests {
         );
     }
     //
-    // `resolve_color` now delegates entirely to core's `Color::resolve_rgb`; this asserts the
-    // packing is correct and that ANSI resolution agrees with core across the full 16-color
+    // Color resolution delegates entirely to the palette; this asserts the packing is correct and
+    // that the default palette's ANSI resolution agrees with core across the full 16-color
     // palette, so a regression in either the delegation or the packing is caught.
 
     #[test]
-    fn resolve_color_matches_core_for_all_ansi_variants() {
+    fn default_palette_matches_core_for_all_ansi_variants() {
         use retroglyph_core::color::AnsiColor;
         for index in 0..16u8 {
             let ansi = AnsiColor::try_from(index).expect("0..16 are valid ANSI indices");
             let (r, g, b) = ansi.to_rgb();
             let core_rgb = (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b);
             assert_eq!(
-                resolve_color(Color::Ansi(ansi), DEFAULT_BG),
+                pack_rgb(Palette::DEFAULT.background_rgb(Color::Ansi(ansi))),
                 core_rgb,
-                "{ansi:?}: resolve_color no longer matches retroglyph-core's Color::resolve_rgb"
+                "{ansi:?}: the default palette no longer matches retroglyph-core's \
+                 Color::resolve_rgb"
             );
         }
     }
 
+    /// Draws one full-block cell, blue on the default background, and returns its two colors:
+    /// `(glyph, background)`.
+    fn palette_cell(renderer: &mut SoftwareRenderer) -> (u32, u32) {
+        let tile = Tile::new('█', Style::new().fg(Color::BLUE));
+        renderer
+            .draw_layers(std::iter::once(DrawCell::on_layer(
+                0,
+                Pos::new(0, 0),
+                &tile,
+            )))
+            .unwrap();
+        let glyph = renderer.pixels()[renderer.pixels().len() / 2];
+        let blank = Tile::new(' ', Style::new());
+        renderer
+            .draw_layers(std::iter::once(DrawCell::on_layer(
+                0,
+                Pos::new(0, 0),
+                &blank,
+            )))
+            .unwrap();
+        (glyph, renderer.pixels()[0])
+    }
+
+    #[test]
+    fn ansi_and_default_colors_resolve_through_the_configured_palette() {
+        let mut r = SoftwareBackendBuilder::new()
+            .font(retroglyph_window::font::unscii16::FONT)
+            .grid_size(1, 1)
+            .palette(Palette::CGA.with_background((0x10, 0x20, 0x30)))
+            .build()
+            .unwrap()
+            .into_renderer()
+            .unwrap();
+        assert_eq!(palette_cell(&mut r), (0x0000_00AA, 0x0010_2030));
+    }
+
+    #[test]
+    fn set_palette_recolors_unchanged_cells_on_the_next_draw() {
+        use retroglyph_core::color::AnsiColor;
+        let mut r = SoftwareBackendBuilder::new()
+            .font(retroglyph_window::font::unscii16::FONT)
+            .grid_size(1, 1)
+            .build()
+            .unwrap()
+            .into_renderer()
+            .unwrap();
+        let tile = Tile::new(' ', Style::new().bg(Color::BLUE));
+        let frame = || std::iter::once(DrawCell::on_layer(0, Pos::new(0, 0), &tile));
+        r.draw_layers(frame()).unwrap();
+        assert_eq!(r.pixels()[0], pack_rgb(AnsiColor::Blue.to_rgb()));
+
+        // The same frame again: only the palette changed, so the dirty-cell path alone would
+        // leave the cell as it was.
+        r.set_palette(Palette::CGA);
+        r.draw_layers(frame()).unwrap();
+        assert_eq!(r.pixels()[0], 0x0000_00AA);
+        assert_eq!(r.palette(), &Palette::CGA);
+    }
+
+    #[test]
+    fn scanlines_darken_odd_rows_of_the_output_only() {
+        let mut r = SoftwareBackendBuilder::new()
+            .grid_size(2, 1)
+            .crt(CrtEffects::NONE.with_scanlines(0.5))
+            .build()
+            .unwrap()
+            .into_renderer()
+            .unwrap();
+        draw_fill(&mut r, 2, 1, &bg_tile(200, 100, 0), None);
+        let width = 2 * 8;
+        // The composited frame is untouched; the output halves every odd row.
+        assert!(r.pixels().iter().all(|&p| p == 0x00C8_6400));
+        assert_eq!(r.output_pixels()[0], 0x00C8_6400);
+        assert_eq!(r.output_pixels()[width], 0x0064_3200);
+        assert_eq!(r.output_pixels()[2 * width], 0x00C8_6400);
+    }
+
+    #[test]
+    fn set_crt_toggles_the_effects_on_a_running_renderer() {
+        let mut r = damage_renderer(2, 1);
+        draw_fill(&mut r, 2, 1, &bg_tile(200, 100, 0), None);
+        assert_eq!(r.output_pixels(), r.pixels());
+
+        r.ctx.damage_rows = None;
+        r.set_crt(CrtEffects::CLASSIC);
+        assert_eq!(r.crt(), CrtEffects::CLASSIC);
+        assert_ne!(r.output_pixels(), r.pixels());
+        // The whole frame has to be re-presented, even though no cell changed.
+        assert_eq!(r.ctx.damage_rows, Some((0, CELL_H_PX)));
+
+        r.set_crt(CrtEffects::NONE);
+        assert_eq!(r.output_pixels(), r.pixels());
+    }
+
+    #[test]
+    fn a_letterboxed_renderer_reports_its_viewport_through_geometry() {
+        use retroglyph_window::presenter::Presenter;
+        use retroglyph_window::scaling::ScalingMode;
+
+        // 4x2 cells of 8x16 is a 32x32 frame; a 100x70 window fits it twice, centered.
+        let mut r = SoftwareBackendBuilder::new()
+            .grid_size(4, 2)
+            .scale(1)
+            .scaling(ScalingMode::Integer { bars: (9, 9, 9) })
+            .build()
+            .unwrap()
+            .into_renderer()
+            .unwrap();
+        Presenter::resize_surface(&mut r, 100, 70);
+        let geometry = Presenter::geometry(&r);
+        assert_eq!(
+            geometry.viewport(),
+            Some(Viewport {
+                frame: (32, 32),
+                origin: (18, 3),
+                size: (64, 64),
+            })
+        );
+        // Window (18 + 2 * 24, 3 + 2 * 16) is the top-left of cell (3, 1).
+        assert_eq!(geometry.pixel_to_cell(66.0, 35.0), Some(Pos { x: 3, y: 1 }));
+        assert_eq!(r.size(), Size::new(4, 2), "the grid keeps its size");
+    }
+
     // ── Output/Cursor conformance (retroglyph#763) ──────────────────────────────────────────
 
     fn conformance_renderer(size: Size) -> SoftwareRenderer {
@@ -2322,6 +3022,7 @@ mod tests {
     }
 
     impl Cursor for SoftwareObserver {}
+    impl Clipboard for SoftwareObserver {}
 
     impl retroglyph_core::testing::conformance::Observable for SoftwareObserver {
         fn snapshot(&mut self) -> u64 {
@@ -2635,8 +3336,13 @@ mod span_tests {
             Tint::mix(0, 255, 0, 200),
             Tint::mix(255, 255, 255, 255),
         ] {
-            let expected = SpriteTint::resolve(SheetColor::Art, Color::Default, tint, DEFAULT_FG)
-                .apply((255, 0, 0));
+            let expected = SpriteTint::resolve_with_palette(
+                SheetColor::Art,
+                Color::Default,
+                tint,
+                &Palette::DEFAULT,
+            )
+            .apply((255, 0, 0));
             let want =
                 u32::from(expected.0) << 16 | u32::from(expected.1) << 8 | u32::from(expected.2);
             assert_eq!(sprite_pixel(Color::Default, tint), want, "tint {tint:?}");
@@ -2663,6 +3369,27 @@ mod span_tests {
         );
     }
 
+    #[test]
+    fn a_remap_swaps_the_sprite_color_and_repaints_on_its_own() {
+        // The same byte-identical tile as above, with only the remap moving between frames.
+        let mut r = renderer_with_sprite(1, 1, 8, 16, 8, SpriteAlign::TopLeft);
+        let mut grid = Grid::new(1, 1);
+        grid.write_span(0, 0, 0, &["S"], Style::new()).unwrap();
+        let tile = *grid.tile(0, (0, 0)).unwrap();
+        let team = Remap::colors(&[((255, 0, 0), (0, 0, 255))]);
+
+        let mut draw = |remap: Option<&Remap>| {
+            r.draw_layers(std::iter::once(
+                DrawCell::new(Pos::new(0, 0), &tile).with_remap(remap),
+            ))
+            .unwrap();
+            px(&r, 1, 0, 0)
+        };
+        assert_eq!(draw(None), RED);
+        assert_eq!(draw(Some(&team)), 0x0000_00FF, "red swapped for blue");
+        assert_eq!(draw(None), RED, "clearing the remap repaints as authored");
+    }
+
     #[test]
     fn sprite_align_center_shifts_the_blit_within_the_span_box() {
         // An 8x16 sprite centered in a 2x1 span of 8x16 cells: 8px of slack, so it starts at x=4.
@@ -2854,6 +3581,113 @@ mod span_tests {
         );
     }
 
+    // ── Sprite transforms ───────────────────────────────────────────────────
+
+    /// `paint`, with `transform` applied to every cell.
+    fn paint_transformed(renderer: &mut SoftwareRenderer, grid: &Grid, transform: Transform) {
+        let tiles: Vec<(u8, Pos, Tile)> = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .map(|(x, y)| (0u8, Pos::new(x, y), *grid.tile(0, (x, y)).unwrap()))
+            .collect();
+        renderer
+            .draw_layers(
+                tiles.iter().map(|(l, pos, tile)| {
+                    DrawCell::on_layer(*l, *pos, tile).with_transform(transform)
+                }),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn a_flipped_sprite_is_mirrored_in_place() {
+        // Red in the left half, transparent in the right: mirrored, the halves swap.
+        let mut r = renderer_with_sprite(1, 1, 8, 16, 4, SpriteAlign::TopLeft);
+        let mut grid = Grid::new(1, 1);
+        grid.write_span(0, 0, 0, &["S"], Style::new().bg(Color::rgb(0, 0, 255)))
+            .unwrap();
+        paint_transformed(&mut r, &grid, Transform::IDENTITY.flipped_x());
+
+        for y in 0..16 {
+            assert_eq!(px(&r, 1, 0, y), BLUE, "mirrored-away half at (0, {y})");
+            assert_eq!(px(&r, 1, 7, y), RED, "mirrored-in half at (7, {y})");
+        }
+    }
+
+    #[test]
+    fn a_turned_and_scaled_sprite_takes_its_transformed_size() {
+        // An 8x16 sprite turned a quarter clockwise is 16x8, and 32x16 at scale 2: its red left
+        // half becomes the top half and runs across all four cells.
+        let mut r = renderer_with_sprite(4, 1, 8, 16, 4, SpriteAlign::TopLeft);
+        let mut grid = Grid::new(4, 1);
+        grid.write_span(0, 0, 0, &["S"], Style::new().bg(Color::rgb(0, 0, 255)))
+            .unwrap();
+        paint_transformed(&mut r, &grid, Transform::IDENTITY.rotated(1).scaled(2));
+
+        assert_eq!(px(&r, 4, 0, 0), RED);
+        assert_eq!(px(&r, 4, 31, 7), RED, "the far end of the turned sprite");
+        assert_ne!(
+            px(&r, 4, 31, 8),
+            RED,
+            "the transparent half is now the bottom"
+        );
+        assert_eq!(px(&r, 4, 0, 8), BLUE);
+    }
+
+    // ── Animated sprites ────────────────────────────────────────────────────
+
+    #[test]
+    fn an_animated_sprite_advances_with_no_grid_change() {
+        use retroglyph_window::animation::SpriteAnimation;
+        use retroglyph_window::presenter::Presenter as _;
+
+        // 'S' -> tile 0 (red) and '#' -> tile 1 (green); 'S' cycles between the two.
+        let opts = TilesetOptions::builder(two_solid_tiles_png())
+            .tile_size(8, 16)
+            .columns(2)
+            .codepage(Codepage::Custom(vec!['S', '#']))
+            .animate(
+                'S',
+                SpriteAnimation::uniform([0, 1], Duration::from_millis(100)),
+            )
+            .build()
+            .expect("valid 2-tile tileset");
+        let mut r = SoftwareBackendBuilder::new()
+            .font(retroglyph_window::font::unscii16::FONT)
+            .grid_size(1, 1)
+            .scale(1)
+            .tileset(opts)
+            .build()
+            .unwrap()
+            .into_renderer()
+            .unwrap();
+        let mut grid = Grid::new(1, 1);
+        grid.write_span(0, 0, 0, &["S"], Style::default()).unwrap();
+
+        paint(&mut r, &grid);
+        assert_eq!(px(&r, 1, 0, 0), RED);
+
+        // Same grid, later clock: the cell is repainted with the next frame.
+        r.advance_animations(Duration::from_millis(150));
+        r.ctx.damage_rows = None;
+        paint(&mut r, &grid);
+        assert_eq!(px(&r, 1, 0, 0), GREEN);
+        assert!(r.ctx.damage_rows.is_some(), "the frame change is damage");
+
+        // Still inside the second frame: nothing moved, so nothing is repainted.
+        r.advance_animations(Duration::from_millis(10));
+        r.ctx.damage_rows = None;
+        paint(&mut r, &grid);
+        assert_eq!(r.ctx.damage_rows, None);
+
+        r.set_animation_time(Duration::from_millis(200));
+        paint(&mut r, &grid);
+        assert_eq!(
+            px(&r, 1, 0, 0),
+            RED,
+            "the loop wraps back to the first frame"
+        );
+    }
+
     // ── Dropped-tint diagnostic (retroglyph#564) ───────────────────────────
 
     #[test]
@@ -2981,13 +3815,163 @@ mod font_chain_tests {
     #[test]
     fn charset_fallback_glyph_takes_the_cells_foreground_color() {
         let chain = FontChain::new(unscii16::FONT, &FALLBACKS);
-        let red = render(chain, '▘', RED);
+        let red = render(chain.clone(), '▘', RED);
         let blue = render(chain, '▘', BLUE);
 
         assert_eq!(red[0], RED_PX);
         assert_eq!(blue[0], BLUE_PX);
     }
 
+    /// Two 16x16 double-width glyphs: `'語'` fills the top half of its left cell and the bottom
+    /// half of its right one, `'字'` the other way around.
+    static DOUBLE_WIDTH_DATA: [u8; 64] = {
+        let mut data = [0; 64];
+        let mut row = 0;
+        while row < 16 {
+            let (left, right) = if row < 8 { (0xFF, 0) } else { (0, 0xFF) };
+            data[row * 2] = left;
+            data[row * 2 + 1] = right;
+            data[32 + row * 2] = right;
+            data[32 + row * 2 + 1] = left;
+            row += 1;
+        }
+        data
+    };
+    const DOUBLE_WIDTH_CHARSET: [char; 2] = ['語', '字'];
+
+    /// A wide character's glyph spans its own cell and its spacer, and rewriting only the
+    /// character repaints the spacer too, even though the spacer's tile did not change.
+    #[test]
+    fn double_width_glyph_spans_the_lead_and_its_spacer() {
+        use retroglyph_core::grid::Grid;
+
+        let fonts = FontChain::from(unscii16::FONT).with_wide_font(BitmapFont::with_charset(
+            &DOUBLE_WIDTH_DATA,
+            16,
+            16,
+            2,
+            &DOUBLE_WIDTH_CHARSET,
+        ));
+        let mut renderer = SoftwareBackendBuilder::new()
+            .font(fonts)
+            .grid_size(2, 1)
+            .scale(1)
+            .build()
+            .expect("chain builds")
+            .into_renderer()
+            .expect("renderer builds");
+        let style = Style::new().fg(RED).bg(BLACK);
+        let mut grid = Grid::new(2, 1);
+
+        for (ch, top_left) in [('語', true), ('字', false)] {
+            grid.put_tile(0, (0, 0), Tile::new(ch, style)).unwrap();
+            assert!(grid.tile(0, (1, 0)).unwrap().is_wide_spacer());
+            renderer.draw_layers(grid.layers()).unwrap();
+
+            let pixels = renderer.pixels();
+            for y in 0..16 {
+                for x in 0..16 {
+                    let set = (x < 8) == (y < 8);
+                    let expected = if set == top_left { RED_PX } else { BLACK_PX };
+                    assert_eq!(pixels[y * 16 + x], expected, "{ch} pixel ({x},{y})");
+                }
+            }
+        }
+        assert!(!renderer.ctx.diagnostics.has_reported_notdef('語'));
+    }
+
+    /// A glyph wider than 8 pixels (#164) draws its whole two-byte rows, including the columns
+    /// past the first byte, and the second cell, drawn from the scanline cache, matches the
+    /// first.
+    #[test]
+    fn wide_glyph_draws_every_column() {
+        // One 12x2 glyph: row 0 is columns 0-1 and 7-11, row 1 is column 11 alone.
+        static WIDE_DATA: [u8; 4] = [0b0000_1100, 0b0001_1111, 0b0000_0000, 0b0000_0001];
+        const WIDE_CHARSET: [char; 1] = ['W'];
+        let font = BitmapFont::with_charset(&WIDE_DATA, 12, 2, 1, &WIDE_CHARSET);
+        let mut renderer = SoftwareBackendBuilder::new()
+            .font(font)
+            .grid_size(2, 1)
+            .scale(1)
+            .build()
+            .expect("font builds")
+            .into_renderer()
+            .expect("renderer builds");
+        let tile = Tile::new('W', Style::new().fg(RED).bg(BLACK));
+
+        renderer
+            .draw_layers(
+                [Pos::new(0, 0), Pos::new(1, 0)]
+                    .into_iter()
+                    .map(|pos| DrawCell::on_layer(0, pos, &tile)),
+            )
+            .unwrap();
+        let pixels = renderer.pixels();
+        for y in 0..2 {
+            for x in 0..24 {
+                let col = x % 12;
+                let set = if y == 0 {
+                    !(2..7).contains(&col)
+                } else {
+                    col == 11
+                };
+                let expected = if set { RED_PX } else { BLACK_PX };
+                assert_eq!(pixels[y * 24 + x], expected, "pixel ({x},{y})");
+            }
+        }
+    }
+
+    /// A TrueType font's glyph edges blend the foreground over the background by their coverage,
+    /// while its fully covered pixels, and a bitmap fallback font in the same chain, still fill
+    /// solid.
+    #[cfg(feature = "truetype")]
+    #[test]
+    fn truetype_edges_blend_with_the_background() {
+        use retroglyph_window::testing::truetype_rect_font;
+        static SOLID: [u8; 10] = [0xFF; 10];
+        const SOLID_CHARSET: [char; 1] = ['B'];
+
+        // 'A' is 2.5 pixels wide and 4 tall at 10px, sitting on the baseline 8 rows down.
+        let outline = truetype_rect_font(&[('A', [0, 0, 250, 400])]);
+        let primary = BitmapFont::from_truetype(&outline, 10.0).expect("font rasterizes");
+        let fallback = BitmapFont::with_charset(&SOLID, 5, 10, 1, &SOLID_CHARSET);
+        let mut renderer = SoftwareBackendBuilder::new()
+            .font(FontChain::from(primary).with_fallback(fallback))
+            .grid_size(2, 1)
+            .scale(1)
+            .build()
+            .expect("font builds")
+            .into_renderer()
+            .expect("renderer builds");
+        let style = Style::new().fg(RED).bg(BLACK);
+        let (a, b) = (Tile::new('A', style), Tile::new('B', style));
+
+        renderer
+            .draw_layers(
+                [
+                    DrawCell::on_layer(0, Pos::new(0, 0), &a),
+                    DrawCell::on_layer(0, Pos::new(1, 0), &b),
+                ]
+                .into_iter(),
+            )
+            .unwrap();
+        let pixels = renderer.pixels();
+        for y in 0..10 {
+            for x in 0..5 {
+                let pixel = pixels[y * 10 + x];
+                match (x, (4..8).contains(&y)) {
+                    (0 | 1, true) => assert_eq!(pixel, RED_PX, "pixel ({x},{y})"),
+                    (2, true) => assert!(
+                        (0x70_0000..0x90_0000).contains(&pixel) && pixel.trailing_zeros() >= 16,
+                        "pixel ({x},{y}) is {pixel:#08x}, not half red"
+                    ),
+                    _ => assert_eq!(pixel, BLACK_PX, "pixel ({x},{y})"),
+                }
+                assert_eq!(pixels[y * 10 + 5 + x], RED_PX, "fallback pixel ({x},{y})");
+            }
+        }
+    }
+
     /// A character no font in the chain covers still gets the solid-block substitute, so the
     /// chain is not a way to silently lose glyphs.
     #[test]
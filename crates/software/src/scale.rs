@@ -0,0 +1,118 @@
+//! Nearest-neighbor scaling of the finished frame into the window, for the
+//! [`ScalingMode`](retroglyph_window::scaling::ScalingMode)s that don't reflow the grid.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary is
+// intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_window::geometry::Viewport;
+
+/// Writes a `window`-sized frame into `out` (resizing it to match): `src`, a `viewport.frame`-sized
+/// frame of `0x00RRGGBB` pixels, scaled nearest-neighbor into `viewport`, and `bars` everywhere
+/// else.
+///
+/// The frame pixel shown at window pixel `x` is `(x - origin + 0.5) * frame / size`, floored: the
+/// one whose span the window pixel's center falls in.
+pub(crate) fn blit(
+    src: &[u32],
+    viewport: &Viewport,
+    window: (u32, u32),
+    bars: u32,
+    out: &mut Vec<u32>,
+) {
+    let (window_w, window_h) = (widen(window.0), widen(window.1));
+    out.clear();
+    out.resize(window_w * window_h, bars);
+    let frame_w = widen(viewport.frame.0);
+    let empty = src.is_empty() || viewport.size.0 == 0 || viewport.size.1 == 0;
+    if empty || src.len() != frame_w * widen(viewport.frame.1) {
+        return;
+    }
+    let columns = source_indices(
+        viewport.origin.0,
+        viewport.size.0,
+        viewport.frame.0,
+        window_w,
+    );
+    let rows = source_indices(
+        viewport.origin.1,
+        viewport.size.1,
+        viewport.frame.1,
+        window_h,
+    );
+    for (y, src_y) in rows {
+        let src_row = &src[src_y * frame_w..][..frame_w];
+        let out_row = &mut out[y * window_w..][..window_w];
+        for &(x, src_x) in &columns {
+            out_row[x] = src_row[src_x];
+        }
+    }
+}
+
+/// For each window pixel along one axis that the viewport covers (`origin..origin + size`, cut
+/// off at `window`), that pixel's index and the frame pixel shown there.
+fn source_indices(origin: u32, size: u32, frame: u32, window: usize) -> Vec<(usize, usize)> {
+    let (origin, size, frame) = (widen(origin), widen(size), widen(frame));
+    (0..size)
+        .map(|i| {
+            (
+                origin + i,
+                ((2 * i + 1) * frame / (2 * size)).min(frame - 1),
+            )
+        })
+        .take_while(|&(at, _)| at < window)
+        .collect()
+}
+
+/// `u32` to `usize`, which is lossless on every target this crate builds for.
+fn widen(value: u32) -> usize {
+    usize::try_from(value).expect("u32 fits usize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Viewport, blit};
+
+    const BAR: u32 = 0x0011_2233;
+
+    #[test]
+    fn a_doubled_frame_is_centered_between_bars() {
+        // A 2x1 frame doubled into the middle of a 6x4 window.
+        let viewport = Viewport {
+            frame: (2, 1),
+            origin: (1, 1),
+            size: (4, 2),
+        };
+        let mut out = Vec::new();
+        blit(&[1, 2], &viewport, (6, 4), BAR, &mut out);
+        #[rustfmt::skip]
+        let expected = [
+            BAR, BAR, BAR, BAR, BAR, BAR,
+            BAR, 1,   1,   2,   2,   BAR,
+            BAR, 1,   1,   2,   2,   BAR,
+            BAR, BAR, BAR, BAR, BAR, BAR,
+        ];
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn a_fractional_scale_picks_the_pixel_under_each_center() {
+        // 3 frame pixels across 4 window pixels: centers at 0.375, 1.125, 1.875, 2.625.
+        let viewport = Viewport {
+            frame: (3, 1),
+            origin: (0, 0),
+            size: (4, 1),
+        };
+        let mut out = Vec::new();
+        blit(&[1, 2, 3], &viewport, (4, 1), BAR, &mut out);
+        assert_eq!(out, [1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn a_frame_bigger_than_the_window_is_cropped() {
+        let viewport = Viewport::unscaled((3, 2));
+        let mut out = Vec::new();
+        blit(&[1, 2, 3, 4, 5, 6], &viewport, (2, 1), BAR, &mut out);
+        assert_eq!(out, [1, 2]);
+    }
+}
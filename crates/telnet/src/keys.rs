@@ -0,0 +1,330 @@
+//! Turns the data bytes a telnet client sends into key presses.
+//!
+//! A client in character mode sends exactly what its terminal would hand a local program: UTF-8
+//! text, C0 control bytes for Ctrl+letter, and the xterm family's escape sequences for
+//! everything else. Telnet itself adds one wrinkle, `CR NUL`/`CR LF` for Enter (RFC 854), which
+//! is folded into a single [`KeyCode::Enter`] here.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_core::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const ESC: u8 = 0x1b;
+
+/// Escape sequences longer than this are garbage, not a key still arriving.
+const MAX_SEQUENCE: usize = 16;
+
+/// Decodes key presses out of the data stream, holding on to a sequence split across reads.
+#[derive(Debug, Default)]
+pub(crate) struct KeyDecoder {
+    /// An escape sequence or UTF-8 character seen only in part so far.
+    pending: Vec<u8>,
+    /// The last byte was a `CR`, so a `LF` or `NUL` right after it belongs to the same Enter.
+    after_cr: bool,
+}
+
+/// Where an escape sequence stands.
+enum Sequence {
+    /// More bytes are needed.
+    Incomplete,
+    /// Complete; `None` if it doesn't name a key this crate knows.
+    Done(Option<KeyEvent>),
+}
+
+impl KeyDecoder {
+    /// Consumes one data byte, calling `emit` for the key it completes, if any.
+    pub(crate) fn feed(&mut self, byte: u8, emit: &mut impl FnMut(KeyEvent)) {
+        if core::mem::take(&mut self.after_cr) && matches!(byte, b'\n' | 0) {
+            return;
+        }
+        match self.pending.first() {
+            None if byte == ESC || byte >= 0x80 => {
+                self.pending.push(byte);
+                self.complete_utf8(emit);
+            }
+            None => {
+                self.after_cr = byte == b'\r';
+                if let Some(key) = control_or_ascii(byte) {
+                    emit(key);
+                }
+            }
+            Some(&ESC) if byte == ESC && self.pending.len() == 1 => {
+                emit(KeyEvent::new(KeyCode::Escape, KeyModifiers::NONE));
+            }
+            Some(&ESC) => {
+                self.pending.push(byte);
+                match escape_sequence(&self.pending) {
+                    Sequence::Incomplete => return,
+                    Sequence::Done(key) => key.into_iter().for_each(&mut *emit),
+                }
+                self.pending.clear();
+            }
+            Some(_) if (0x80..0xc0).contains(&byte) => {
+                self.pending.push(byte);
+                self.complete_utf8(emit);
+            }
+            Some(_) => {
+                // A UTF-8 character cut short: drop what there was of it and start over.
+                self.pending.clear();
+                self.feed(byte, emit);
+            }
+        }
+    }
+
+    /// Called once the bytes available right now are used up.
+    ///
+    /// An `ESC` with nothing after it in the same read is the Escape key on its own: a client
+    /// sends an escape sequence in one write, so the rest of it would have arrived with it.
+    pub(crate) fn end_of_read(&mut self, emit: &mut impl FnMut(KeyEvent)) {
+        if self.pending == [ESC] {
+            self.pending.clear();
+            emit(KeyEvent::new(KeyCode::Escape, KeyModifiers::NONE));
+        }
+    }
+
+    /// Emits the pending UTF-8 character once all of its bytes are in.
+    fn complete_utf8(&mut self, emit: &mut impl FnMut(KeyEvent)) {
+        let Some(&lead) = self.pending.first() else {
+            return;
+        };
+        let len = match lead {
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            ESC => return,
+            _ => {
+                self.pending.clear();
+                return;
+            }
+        };
+        if self.pending.len() < len {
+            return;
+        }
+        if let Some(c) = core::str::from_utf8(&self.pending)
+            .ok()
+            .and_then(|s| s.chars().next())
+        {
+            emit(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        self.pending.clear();
+    }
+}
+
+/// A single byte that isn't the start of a sequence.
+fn control_or_ascii(byte: u8) -> Option<KeyEvent> {
+    let plain = |code| Some(KeyEvent::new(code, KeyModifiers::NONE));
+    let ctrl = |c: u8| {
+        Some(KeyEvent::new(
+            KeyCode::Char(char::from(c)),
+            KeyModifiers::CONTROL,
+        ))
+    };
+    match byte {
+        b'\r' | b'\n' => plain(KeyCode::Enter),
+        b'\t' => plain(KeyCode::Tab),
+        0x08 | 0x7f => plain(KeyCode::Backspace),
+        0 => ctrl(b' '),
+        0x01..=0x1a => ctrl(b'a' + byte - 1),
+        0x1c..=0x1f => ctrl(byte + 0x40),
+        0x20..=0x7e => plain(KeyCode::Char(char::from(byte))),
+        _ => None,
+    }
+}
+
+/// Decodes `seq`, which starts with `ESC`.
+fn escape_sequence(seq: &[u8]) -> Sequence {
+    match seq {
+        [ESC] | [ESC, b'[' | b'O'] => Sequence::Incomplete,
+        [ESC, b'[', rest @ ..] => {
+            let Some((&last, params)) = rest.split_last() else {
+                return Sequence::Incomplete;
+            };
+            match last {
+                0x40..=0x7e => Sequence::Done(csi(params, last)),
+                0x20..=0x3f if seq.len() < MAX_SEQUENCE => Sequence::Incomplete,
+                _ => Sequence::Done(None),
+            }
+        }
+        [ESC, b'O', last] => Sequence::Done(ss3(*last, KeyModifiers::NONE)),
+        // Meta sends Alt+key as the key prefixed with `ESC`.
+        [ESC, byte] => Sequence::Done(control_or_ascii(*byte).map(|mut key| {
+            key.modifiers |= KeyModifiers::ALT;
+            key
+        })),
+        _ => Sequence::Done(None),
+    }
+}
+
+/// `CSI <params> <last>`: cursor and editing keys, function keys, and their modified forms.
+fn csi(params: &[u8], last: u8) -> Option<KeyEvent> {
+    let mut params = params
+        .split(|&b| b == b';')
+        .map(|p| core::str::from_utf8(p).ok()?.parse::<u16>().ok());
+    let first = params.next().flatten();
+    let modifiers = params
+        .next()
+        .flatten()
+        .map_or(KeyModifiers::NONE, modifiers);
+    let code = match (last, first) {
+        (b'Z', _) => KeyCode::BackTab,
+        (b'~', Some(n)) => match n {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            11..=15 => KeyCode::F(u8::try_from(n - 10).ok()?),
+            17..=21 => KeyCode::F(u8::try_from(n - 11).ok()?),
+            23..=26 => KeyCode::F(u8::try_from(n - 12).ok()?),
+            28 | 29 => KeyCode::F(u8::try_from(n - 13).ok()?),
+            31..=34 => KeyCode::F(u8::try_from(n - 14).ok()?),
+            _ => return None,
+        },
+        _ => return ss3(last, modifiers),
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// The keys that share a final byte between `CSI` and `SS3` (`ESC O`) forms.
+const fn ss3(last: u8, modifiers: KeyModifiers) -> Option<KeyEvent> {
+    let code = match last {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'P'..=b'S' => KeyCode::F(last - b'P' + 1),
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// xterm's modifier parameter: one more than a Shift/Alt/Ctrl/Meta bitmask.
+fn modifiers(param: u16) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    [
+        (1, KeyModifiers::SHIFT),
+        (2, KeyModifiers::ALT),
+        (4, KeyModifiers::CONTROL),
+        (8, KeyModifiers::SUPER),
+    ]
+    .into_iter()
+    .filter(|&(bit, _)| bits & bit != 0)
+    .fold(KeyModifiers::NONE, |all, (_, modifier)| all | modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(reads: &[&[u8]]) -> Vec<KeyEvent> {
+        let mut decoder = KeyDecoder::default();
+        let mut keys = Vec::new();
+        let mut emit = |key| keys.push(key);
+        for read in reads {
+            for &byte in *read {
+                decoder.feed(byte, &mut emit);
+            }
+            decoder.end_of_read(&mut emit);
+        }
+        keys
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn text_and_control_bytes() {
+        assert_eq!(
+            keys(&["aé🦀".as_bytes(), b"\t\x7f\x03"]),
+            [
+                key(KeyCode::Char('a')),
+                key(KeyCode::Char('é')),
+                key(KeyCode::Char('🦀')),
+                key(KeyCode::Tab),
+                key(KeyCode::Backspace),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]
+        );
+    }
+
+    #[test]
+    fn cr_nul_and_cr_lf_are_one_enter() {
+        assert_eq!(
+            keys(&[b"\r\0", b"\r", b"\n", b"\n"]),
+            [
+                key(KeyCode::Enter),
+                key(KeyCode::Enter),
+                key(KeyCode::Enter)
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_and_function_keys() {
+        assert_eq!(
+            keys(&[b"\x1b[A\x1bOB\x1b[3~\x1b[15~\x1bOP\x1b[Z"]),
+            [
+                key(KeyCode::Up),
+                key(KeyCode::Down),
+                key(KeyCode::Delete),
+                key(KeyCode::F(5)),
+                key(KeyCode::F(1)),
+                key(KeyCode::BackTab),
+            ]
+        );
+    }
+
+    #[test]
+    fn modified_keys() {
+        assert_eq!(
+            keys(&[b"\x1b[1;5C\x1b[5;3~\x1bx"]),
+            [
+                KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::PageUp, KeyModifiers::ALT),
+                KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_lone_escape_resolves_at_the_end_of_a_read() {
+        assert_eq!(
+            keys(&[b"\x1b", b"q", b"\x1b\x1b"]),
+            [
+                key(KeyCode::Escape),
+                key(KeyCode::Char('q')),
+                key(KeyCode::Escape),
+                key(KeyCode::Escape),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_sequence_split_across_reads_is_reassembled() {
+        assert_eq!(
+            keys(&[b"\x1b[", b"1;2", b"D"]),
+            [KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT)]
+        );
+    }
+
+    #[test]
+    fn unknown_and_runaway_sequences_are_dropped() {
+        let mut runaway = b"\x1b[".to_vec();
+        runaway.resize(64, b'1');
+        runaway.push(b'a');
+        assert_eq!(
+            keys(&[b"\x1b[99~\x1b[?1$y", &runaway]),
+            // The runaway sequence is cut off at the cap; what follows is ordinary typing.
+            [key(KeyCode::Char('1')); 48]
+                .into_iter()
+                .chain([key(KeyCode::Char('a'))])
+                .collect::<Vec<_>>()
+        );
+    }
+}
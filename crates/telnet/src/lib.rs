@@ -0,0 +1,635 @@
+//! A telnet server [`Backend`](retroglyph_core::backend::Backend): serves an [`App`](retroglyph_core::app::App) to players
+//! connecting with a plain telnet client, one session per connection.
+//!
+//! [`TelnetServer`] listens on TCP and, for each client, negotiates the connection, builds a
+//! [`Telnet`] backend over the socket, and runs a fresh `App` on its own thread:
+//!
+//! ```no_run
+//! use retroglyph_core::app::{App, Flow, Frame};
+//! use retroglyph_core::backend::Backend;
+//! use retroglyph_core::color::Style;
+//! use retroglyph_core::event::{Event, KeyCode};
+//! use retroglyph_core::terminal::Terminal;
+//! use retroglyph_telnet::TelnetServer;
+//!
+//! #[derive(Default)]
+//! struct Lobby {
+//!     drawn: bool,
+//! }
+//!
+//! impl<B: Backend> App<B> for Lobby {
+//!     fn update(&mut self, term: &mut Terminal<B>, _frame: &Frame) -> Flow {
+//!         let mut changed = !self.drawn;
+//!         while let Some(event) = term.poll(std::time::Duration::ZERO) {
+//!             changed = true;
+//!             if matches!(event, Event::Close) {
+//!                 return Flow::Exit;
+//!             }
+//!             if let Event::Key(key) = event
+//!                 && key.code == KeyCode::Char('q')
+//!             {
+//!                 return Flow::Exit;
+//!             }
+//!         }
+//!         if !changed {
+//!             // Nothing new to show: skip the present and sleep until the player types.
+//!             return Flow::Idle;
+//!         }
+//!         self.drawn = true;
+//!         term.surface().put((0, 0), '@', Style::default());
+//!         Flow::Continue
+//!     }
+//! }
+//!
+//! TelnetServer::bind("0.0.0.0:2323")?.serve(Lobby::default)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Cell-diffing and ANSI/SGR output are delegated to [`retroglyph_terminal::TerminalRenderer`],
+//! exactly as for `retroglyph-crossterm`; only the transport differs. A session's output goes
+//! straight to its socket, and its input is whatever that client sends.
+//!
+//! # Negotiation
+//!
+//! A telnet client starts out in line mode, echoing locally and sending a line at a time. On
+//! connect the server offers to echo and to suppress go-ahead (`ECHO` and `SGA`), which moves
+//! the client to sending each key as it is pressed, and asks for two options:
+//!
+//! - `NAWS` (window size): the client's answer sizes the session, and every later one (the
+//!   player resized their window) arrives as an [`Event::Resize`].
+//! - `TTYPE` (terminal type): the names the client reports pick the session's
+//!   [`ColorSupport`]; see [`Telnet::terminal_types`]. Clients following the MUD Terminal Type
+//!   Standard report their exact color support, which wins over guessing from names.
+//!
+//! The server waits for both answers, up to [`TelnetOptions::negotiation_timeout`], before the
+//! session's `App` starts, so [`App::init`](retroglyph_core::app::App::init) sees the real size.
+//! A client that refuses or ignores an option gets [`TelnetOptions::default_size`] and 16 colors.
+//!
+//! # Input
+//!
+//! Keys arrive as [`Event::Key`], decoded from the UTF-8 text, control bytes, and xterm escape
+//! sequences a client's terminal sends: cursor and editing keys, F1 to F20, and their Shift,
+//! Alt, and Ctrl forms. An `ESC` with nothing after it is the Escape key. A client disconnecting
+//! is reported once as [`Event::Close`]; after that, drawing fails with the socket's error and
+//! polling only waits out its timeout. A served session ends once its app has taken the `Close`,
+//! even if the app ignores it.
+//!
+//! # Clipboard
+//!
+//! [`Clipboard::set_clipboard_text`] writes OSC 52 to the client (see
+//! [`retroglyph_terminal::clipboard`]), which sets the player's own clipboard if their terminal
+//! allows it. Reading it back is not supported: a server can't tell a clipboard reply from typed
+//! keys without the round trip `retroglyph-crossterm` makes against a local TTY.
+//!
+//! # Features
+//!
+//! <!-- gen-features:start -->
+//! This crate has no default features; every feature below is optional and off unless enabled.
+//!
+//! ### `dev`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards `retroglyph-core`'s `dev` feature, which forces development diagnostics on in a build
+//! that would otherwise compile them out (see [`retroglyph_core::dev`]).
+//!
+//! ### `egc`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards to `retroglyph-terminal`'s (and `retroglyph-core`'s) `egc` feature for
+//! grapheme-cluster-aware cell diffing.
+//! <!-- gen-features:end -->
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/crates-lurey-io/retroglyph/main/docs/public/assets/logo.svg"
+)]
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/crates-lurey-io/retroglyph/main/docs/public/assets/logo.svg"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+// Compile the code blocks in this crate's own README as doctests so its quick start is
+// type-checked on every test run and cannot silently rot. See `retroglyph-terminal-wasm`'s
+// matching include.
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
+
+mod keys;
+mod protocol;
+
+use keys::KeyDecoder;
+use protocol::{Parser, Received};
+use retroglyph_core::backend::{
+    Clipboard, ClipboardError, Cursor, CursorStyle, DrawCell, EventSender, Input, Output,
+};
+use retroglyph_core::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use retroglyph_core::grid::{HasSize, Pos, Size};
+use retroglyph_terminal::session::{
+    self, SessionOptions, SocketInput, SocketServer, SocketSession,
+};
+use retroglyph_terminal::{ColorSupport, TerminalRenderer};
+use std::io::{self, BufWriter, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// How long [`Telnet`] waits for a client to answer the opening negotiation by default; see
+/// [`TelnetOptions::negotiation_timeout`].
+pub const NEGOTIATION_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Builder-style configuration for a [`Telnet`] session.
+///
+/// ```
+/// use std::time::Duration;
+/// use retroglyph_telnet::TelnetOptions;
+/// use retroglyph_terminal::ColorSupport;
+///
+/// let options = TelnetOptions::new()
+///     .negotiation_timeout(Duration::from_secs(1))
+///     .color_support(ColorSupport::Indexed256);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TelnetOptions {
+    negotiation_timeout: Duration,
+    // `None` means pick from the client's `TTYPE` answers; `Some` is an explicit override.
+    color_support: Option<ColorSupport>,
+    default_size: Size,
+}
+
+impl Default for TelnetOptions {
+    fn default() -> Self {
+        Self {
+            negotiation_timeout: NEGOTIATION_TIMEOUT,
+            color_support: None,
+            default_size: Size::new(80, 24),
+        }
+    }
+}
+
+impl TelnetOptions {
+    /// Creates the default options: half a second to negotiate, colors from the client's
+    /// terminal type, 80x24 if it doesn't report a size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long a new session waits for the client to answer the window size and terminal
+    /// type requests before its `App` starts ([`NEGOTIATION_TIMEOUT`] by default).
+    ///
+    /// Clients answer in a round trip, so the full wait only happens on one that ignores telnet
+    /// negotiation altogether (a raw TCP client such as `nc`). Answers arriving later still
+    /// apply.
+    #[must_use]
+    pub const fn negotiation_timeout(mut self, timeout: Duration) -> Self {
+        self.negotiation_timeout = timeout;
+        self
+    }
+
+    /// Uses `color_support` for every session instead of picking it from the client's terminal
+    /// type.
+    #[must_use]
+    pub const fn color_support(mut self, color_support: ColorSupport) -> Self {
+        self.color_support = Some(color_support);
+        self
+    }
+
+    /// Sets the size a session gets when its client doesn't report one (80x24 by default).
+    #[must_use]
+    pub const fn default_size(mut self, width: u16, height: u16) -> Self {
+        self.default_size = Size::new(width, height);
+        self
+    }
+
+    /// Negotiates with the client on `stream` and builds a [`Telnet`] backend over it.
+    ///
+    /// Blocks until the client has answered or [`negotiation_timeout`](Self::negotiation_timeout)
+    /// runs out, then switches the client's terminal to its alternate screen and hides the
+    /// cursor; dropping the backend undoes both.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket fails while negotiating.
+    pub fn build(self, stream: TcpStream) -> io::Result<Telnet> {
+        let reader = stream.try_clone()?;
+        stream.set_nodelay(true)?;
+        let mut renderer = TerminalRenderer::new(BufWriter::new(stream));
+        renderer.set_color_support(self.color_support.unwrap_or(ColorSupport::Ansi16));
+        let mut telnet = Telnet {
+            renderer,
+            input: SocketInput::new(reader),
+            parser: Parser::default(),
+            keys: KeyDecoder::default(),
+            size: self.default_size,
+            terminal_types: Vec::new(),
+            color_support: self.color_support,
+            negotiating: true,
+            awaiting_naws: true,
+            awaiting_ttype: true,
+        };
+        telnet.negotiate(self.negotiation_timeout)?;
+        telnet.send(b"\x1b[?1049h\x1b[?25l")?;
+        telnet.renderer.clear_screen()?;
+        Ok(telnet)
+    }
+}
+
+/// A [`Backend`](retroglyph_core::backend::Backend) for one telnet session: renders to the
+/// client over its socket and reads keys back from it.
+///
+/// Usually built for you by [`TelnetServer`]; build one directly with [`Telnet::new`] or
+/// [`TelnetOptions::build`] to drive a connection accepted some other way.
+pub struct Telnet {
+    renderer: TerminalRenderer<BufWriter<TcpStream>>,
+    /// Reads a second handle to the same socket.
+    input: SocketInput,
+    parser: Parser,
+    keys: KeyDecoder,
+    size: Size,
+    terminal_types: Vec<String>,
+    /// The caller's [`TelnetOptions::color_support`], which `TTYPE` answers don't override.
+    color_support: Option<ColorSupport>,
+    /// Still inside [`TelnetOptions::build`]: a window size sizes the session rather than
+    /// arriving as an event.
+    negotiating: bool,
+    awaiting_naws: bool,
+    awaiting_ttype: bool,
+}
+
+impl Telnet {
+    /// Negotiates with the client on `stream` using the default [`TelnetOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket fails while negotiating.
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        TelnetOptions::default().build(stream)
+    }
+
+    /// The terminal type names the client reported, in order.
+    ///
+    /// Clients report one name per request and cycle through what they have; this crate keeps
+    /// asking until a name repeats, so this holds all of them (or none, if the client doesn't do
+    /// `TTYPE`). MUD clients typically answer with their own name, a terminal type, and an
+    /// `MTTS <bits>` capability list.
+    #[must_use]
+    pub fn terminal_types(&self) -> &[String] {
+        &self.terminal_types
+    }
+
+    /// The color support this session renders with; see [`TelnetOptions::color_support`].
+    #[must_use]
+    pub const fn color_support(&self) -> ColorSupport {
+        self.renderer.color_support()
+    }
+
+    /// The address of the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket has been closed.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.input.stream().peer_addr()
+    }
+
+    /// Sends the greeting and reads answers until both options are settled or `timeout` runs
+    /// out.
+    fn negotiate(&mut self, timeout: Duration) -> io::Result<()> {
+        self.send(protocol::GREETING)?;
+        let deadline = Instant::now() + timeout;
+        while (self.awaiting_naws || self.awaiting_ttype) && !self.input.is_closed() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            session::read(self, Some(left));
+        }
+        self.negotiating = false;
+        Ok(())
+    }
+
+    /// Writes `bytes` to the client and flushes.
+    fn send(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let writer = self.renderer.writer_mut();
+        writer.write_all(bytes)?;
+        writer.flush()
+    }
+
+    fn handle(&mut self, received: Received) {
+        let input = &mut self.input;
+        match received {
+            Received::Data(byte) => self.keys.feed(byte, &mut |key| input.push(Event::Key(key))),
+            Received::InterruptProcess => {
+                let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+                input.push(Event::Key(key));
+            }
+            Received::Negotiate(verb, option) => {
+                match (verb, option) {
+                    (protocol::WONT, protocol::NAWS) => self.awaiting_naws = false,
+                    (protocol::WONT, protocol::TTYPE) => self.awaiting_ttype = false,
+                    (protocol::WILL, protocol::TTYPE) => {
+                        let _ = self.send(protocol::SEND_TTYPE);
+                    }
+                    _ => {}
+                }
+                if let Some(reply) = protocol::reply_to(verb, option) {
+                    let _ = self.send(&reply);
+                }
+            }
+            Received::Subnegotiation(protocol::NAWS, payload) => {
+                self.awaiting_naws = false;
+                if let Some((width, height)) = protocol::parse_naws(&payload) {
+                    if self.negotiating {
+                        self.size = Size::new(width, height);
+                    } else {
+                        input.push(Event::Resize(width, height));
+                    }
+                }
+            }
+            Received::Subnegotiation(protocol::TTYPE, payload) => {
+                if let Some(name) = protocol::parse_ttype(&payload) {
+                    self.admit_terminal_type(name);
+                }
+            }
+            Received::Subnegotiation(..) => {}
+        }
+    }
+
+    /// Records a `TTYPE` answer, asks for the next one unless the client has run out, and
+    /// updates the color support.
+    fn admit_terminal_type(&mut self, name: String) {
+        // A client repeats its last name once it has no more; MTTS is always the last.
+        let done = self.terminal_types.last() == Some(&name)
+            || name.starts_with("MTTS ")
+            || self.terminal_types.len() >= 3;
+        if self.terminal_types.last() != Some(&name) {
+            self.terminal_types.push(name);
+        }
+        if done {
+            self.awaiting_ttype = false;
+        } else {
+            let _ = self.send(protocol::SEND_TTYPE);
+        }
+        if self.color_support.is_none() {
+            let color_support = protocol::color_support_for(&self.terminal_types);
+            if color_support != self.renderer.color_support() {
+                self.renderer.set_color_support(color_support);
+                // Colors already on screen were written at the old level.
+                self.renderer.reset_state();
+            }
+        }
+    }
+}
+
+impl Drop for Telnet {
+    fn drop(&mut self) {
+        // Leave the client's terminal the way it was before the session; it may well be gone.
+        let _ = self.send(b"\x1b[0m\x1b[?25h\x1b[?1049l");
+    }
+}
+
+impl Output for Telnet {
+    type Error = io::Error;
+
+    fn draw_layers<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = DrawCell<'a>>,
+    {
+        let size = self.size;
+        let content =
+            content.filter(move |cell| cell.pos.x < size.width() && cell.pos.y < size.height());
+        self.renderer.draw_frame(content)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.renderer.end_frame()
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn resize(&mut self, size: Size) {
+        self.size = size;
+        // The client reflows its own screen on a resize; forget tracked cursor and color state
+        // so the next draw re-emits it all.
+        self.renderer.reset_state();
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.renderer.clear_screen()
+    }
+}
+
+impl SocketSession for Telnet {
+    fn input(&self) -> &SocketInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut SocketInput {
+        &mut self.input
+    }
+
+    fn receive(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if let Some(received) = self.parser.feed(byte) {
+                self.handle(received);
+            }
+        }
+        let input = &mut self.input;
+        self.keys
+            .end_of_read(&mut |key| input.push(Event::Key(key)));
+    }
+}
+
+impl Input for Telnet {
+    fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        session::poll_event(self, timeout)
+    }
+
+    fn push_event(&mut self, event: Event) {
+        self.input.push(event);
+    }
+
+    /// Takes whatever the client has already sent, then parks until the socket has more or an
+    /// event is sent; see [`session::poll_next_event`].
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        session::poll_next_event(self, cx)
+    }
+
+    fn event_sender(&mut self) -> Option<EventSender> {
+        Some(self.input.event_sender())
+    }
+}
+
+impl Cursor for Telnet {
+    fn set_cursor_visible(&mut self, visible: bool) {
+        let _ = self.renderer.set_cursor_visible(visible);
+    }
+
+    fn set_cursor_position(&mut self, position: Pos) {
+        let _ = self.renderer.move_cursor_to(position);
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) {
+        let _ = self.renderer.set_cursor_style(style);
+    }
+}
+
+/// OSC 52: sets the player's clipboard where their terminal allows it; see the crate docs.
+impl Clipboard for Telnet {
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        let writer = self.renderer.writer_mut();
+        retroglyph_terminal::clipboard::write_osc52(writer, text)
+            .and_then(|()| writer.flush())
+            .map_err(|err| ClipboardError::new(err.to_string()))
+    }
+}
+
+impl SessionOptions for TelnetOptions {
+    type Session = Telnet;
+
+    const PROTOCOL: &'static str = "telnet";
+
+    fn build(self, stream: TcpStream) -> io::Result<Telnet> {
+        Self::build(self, stream)
+    }
+}
+
+/// Listens for telnet clients and serves each one its own [`App`](retroglyph_core::app::App),
+/// negotiating with each before its app starts.
+///
+/// See the crate docs for an example, and [`SocketServer`] for its methods.
+pub type TelnetServer = SocketServer<TelnetOptions>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retroglyph_core::app::{App, Flow, Frame, RunOptions};
+    use retroglyph_core::terminal::Terminal;
+    use retroglyph_terminal::session::Session;
+    use std::net::TcpListener;
+
+    /// A connected `(server side, client side)` pair over loopback.
+    fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (server, client)
+    }
+
+    fn quick() -> TelnetOptions {
+        TelnetOptions::new().negotiation_timeout(Duration::from_millis(50))
+    }
+
+    #[test]
+    fn options_default_to_the_negotiation_timeout_and_80x24() {
+        let options = TelnetOptions::new();
+        assert_eq!(options.negotiation_timeout, NEGOTIATION_TIMEOUT);
+        assert_eq!(options.color_support, None);
+        assert_eq!(options.default_size, Size::new(80, 24));
+    }
+
+    #[test]
+    fn a_client_that_ignores_negotiation_gets_the_defaults() {
+        let (server, _client) = socket_pair();
+        let telnet = quick().default_size(40, 12).build(server).unwrap();
+        assert_eq!(telnet.size(), Size::new(40, 12));
+        assert_eq!(telnet.color_support(), ColorSupport::Ansi16);
+        assert!(telnet.terminal_types().is_empty());
+    }
+
+    #[test]
+    fn an_explicit_color_support_is_kept() {
+        let (server, mut client) = socket_pair();
+        client
+            .write_all(b"\xff\xfa\x18\x00xterm-256color\xff\xf0")
+            .unwrap();
+        let telnet = quick()
+            .color_support(ColorSupport::None)
+            .build(server)
+            .unwrap();
+        assert_eq!(telnet.terminal_types(), ["xterm-256color"]);
+        assert_eq!(telnet.color_support(), ColorSupport::None);
+    }
+
+    #[test]
+    fn a_disconnect_is_one_close_event() {
+        let (server, client) = socket_pair();
+        let mut telnet = quick().build(server).unwrap();
+        drop(client);
+        assert_eq!(
+            telnet.poll_event(Duration::from_secs(5)),
+            Some(Event::Close)
+        );
+        assert_eq!(telnet.poll_event(Duration::ZERO), None);
+        // Polling again idles for the timeout rather than returning straight away.
+        let start = Instant::now();
+        assert_eq!(telnet.poll_event(Duration::from_millis(20)), None);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn a_served_session_ends_even_if_the_app_ignores_close() {
+        struct IgnoresClose;
+
+        impl App<Telnet> for IgnoresClose {
+            fn update(&mut self, term: &mut Terminal<Telnet>, _frame: &Frame) -> Flow {
+                while term.poll(Duration::ZERO).is_some() {}
+                Flow::Idle
+            }
+        }
+
+        let (server, client) = socket_pair();
+        let telnet = quick().build(server).unwrap();
+        let (done, ended) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = retroglyph_core::app::run_with(
+                telnet,
+                Session(IgnoresClose),
+                RunOptions::default(),
+            );
+            let _ = done.send(());
+        });
+        drop(client);
+        assert!(ended.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn sent_events_wake_a_waiting_poll() {
+        let (server, _client) = socket_pair();
+        let mut telnet = quick().build(server).unwrap();
+        let sender = telnet.event_sender().unwrap();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            sender.send(Event::FocusGained).unwrap();
+        });
+        assert_eq!(
+            telnet.poll_event(Duration::from_secs(5)),
+            Some(Event::FocusGained)
+        );
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn client_input_still_wakes_a_poll_while_a_sender_is_live() {
+        let (server, mut client) = socket_pair();
+        let mut telnet = quick().build(server).unwrap();
+        let _sender = telnet.event_sender().unwrap();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            client.write_all(b"a").unwrap();
+            client
+        });
+        assert_eq!(
+            telnet.poll_event(Duration::from_secs(5)),
+            Some(Event::Key(KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::NONE
+            )))
+        );
+        drop(thread.join().unwrap());
+    }
+}
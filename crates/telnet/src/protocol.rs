@@ -0,0 +1,297 @@
+//! The telnet protocol itself (RFC 854): splitting a connection's incoming bytes into the data
+//! stream and the commands interleaved with it, and the handful of options this crate negotiates.
+//!
+//! Nothing here touches a socket; [`Telnet`](crate::Telnet) feeds it what it reads and writes
+//! back whatever replies it asks for.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_terminal::ColorSupport;
+
+/// Interpret As Command: starts every command, and doubles to send a literal `0xFF` data byte.
+pub(crate) const IAC: u8 = 255;
+pub(crate) const DONT: u8 = 254;
+pub(crate) const DO: u8 = 253;
+pub(crate) const WONT: u8 = 252;
+pub(crate) const WILL: u8 = 251;
+/// Subnegotiation begin.
+pub(crate) const SB: u8 = 250;
+/// Interrupt Process: what a client in line mode sends for Ctrl+C.
+pub(crate) const IP: u8 = 244;
+/// Subnegotiation end.
+pub(crate) const SE: u8 = 240;
+
+/// The server echoes what the client types (RFC 857). Claiming it stops the client echoing
+/// keys locally on top of the game's own screen.
+pub(crate) const ECHO: u8 = 1;
+/// Suppress Go Ahead (RFC 858). With ECHO, moves the client out of line mode so every key is
+/// sent as it is pressed.
+pub(crate) const SGA: u8 = 3;
+/// Terminal Type (RFC 1091).
+pub(crate) const TTYPE: u8 = 24;
+/// Negotiate About Window Size (RFC 1073).
+pub(crate) const NAWS: u8 = 31;
+
+/// `TTYPE` subnegotiation: the client's answer.
+const TTYPE_IS: u8 = 0;
+/// `TTYPE` subnegotiation: the server's request.
+const TTYPE_SEND: u8 = 1;
+
+/// What the server says as soon as a client connects: it will echo and suppress go-ahead
+/// (character-at-a-time mode), and it wants the window size and terminal type.
+pub(crate) const GREETING: &[u8] = &[
+    IAC, WILL, ECHO, IAC, WILL, SGA, IAC, DO, SGA, IAC, DO, NAWS, IAC, DO, TTYPE,
+];
+
+/// Asks for the client's (next) terminal type.
+pub(crate) const SEND_TTYPE: &[u8] = &[IAC, SB, TTYPE, TTYPE_SEND, IAC, SE];
+
+/// The largest subnegotiation kept; anything longer is cut off. Both options this crate reads
+/// are a few bytes long, so this only bounds what a misbehaving client can make a session hold.
+const MAX_SUBNEGOTIATION: usize = 256;
+
+/// One unit of what a client sent: a data byte or a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Received {
+    /// A byte of the data stream: typed keys, pasted text.
+    Data(u8),
+    /// `IAC <verb> <option>`, with `verb` one of [`WILL`], [`WONT`], [`DO`] or [`DONT`].
+    Negotiate(u8, u8),
+    /// `IAC SB <option> <payload> IAC SE`, with any doubled `IAC` in the payload undone.
+    Subnegotiation(u8, Vec<u8>),
+    /// `IAC IP`.
+    InterruptProcess,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum State {
+    #[default]
+    Data,
+    Iac,
+    Verb(u8),
+    SubOption,
+    Sub,
+    SubIac,
+}
+
+/// Splits incoming bytes into [`Received`] units, one byte at a time, so a command split across
+/// reads comes out whole.
+#[derive(Debug, Default)]
+pub(crate) struct Parser {
+    state: State,
+    option: u8,
+    payload: Vec<u8>,
+}
+
+impl Parser {
+    /// Consumes one byte, returning a unit if it completed one.
+    pub(crate) fn feed(&mut self, byte: u8) -> Option<Received> {
+        match (self.state, byte) {
+            (State::Data, IAC) => self.state = State::Iac,
+            (State::Data, _) => return Some(Received::Data(byte)),
+            (State::Iac, IAC) => {
+                self.state = State::Data;
+                return Some(Received::Data(IAC));
+            }
+            (State::Iac, WILL | WONT | DO | DONT) => self.state = State::Verb(byte),
+            (State::Iac, SB) => self.state = State::SubOption,
+            (State::Iac, IP) => {
+                self.state = State::Data;
+                return Some(Received::InterruptProcess);
+            }
+            // NOP, Go Ahead, Are You There and the rest carry nothing a game needs.
+            (State::Iac, _) => self.state = State::Data,
+            (State::Verb(verb), _) => {
+                self.state = State::Data;
+                return Some(Received::Negotiate(verb, byte));
+            }
+            (State::SubOption, _) => {
+                self.option = byte;
+                self.payload.clear();
+                self.state = State::Sub;
+            }
+            (State::Sub, IAC) => self.state = State::SubIac,
+            (State::Sub, _) => self.push_payload(byte),
+            (State::SubIac, SE) => {
+                self.state = State::Data;
+                return Some(Received::Subnegotiation(
+                    self.option,
+                    core::mem::take(&mut self.payload),
+                ));
+            }
+            (State::SubIac, _) => {
+                // `IAC IAC` is an escaped `0xFF`; anything else is malformed, kept as-is.
+                self.push_payload(byte);
+                self.state = State::Sub;
+            }
+        }
+        None
+    }
+
+    fn push_payload(&mut self, byte: u8) {
+        if self.payload.len() < MAX_SUBNEGOTIATION {
+            self.payload.push(byte);
+        }
+    }
+}
+
+/// The reply to a client's `verb` about `option`, if one is owed.
+///
+/// Offers for options this crate asked about need no answer. Anything else the client offers or
+/// asks for is refused, which can't start a negotiation loop: a refusal is never answered.
+pub(crate) const fn reply_to(verb: u8, option: u8) -> Option<[u8; 3]> {
+    match (verb, option) {
+        (WILL, NAWS | TTYPE | SGA) | (DO, ECHO | SGA) | (WONT | DONT, _) => None,
+        (WILL, _) => Some([IAC, DONT, option]),
+        (DO, _) => Some([IAC, WONT, option]),
+        _ => None,
+    }
+}
+
+/// Reads a `NAWS` payload: width and height, 16 bits each, big-endian. `None` if it's the wrong
+/// length or reports a zero dimension (which clients send when they don't know).
+pub(crate) fn parse_naws(payload: &[u8]) -> Option<(u16, u16)> {
+    let &[w0, w1, h0, h1] = payload else {
+        return None;
+    };
+    let (width, height) = (u16::from_be_bytes([w0, w1]), u16::from_be_bytes([h0, h1]));
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+/// Reads a `TTYPE IS` payload: the terminal type name.
+pub(crate) fn parse_ttype(payload: &[u8]) -> Option<String> {
+    let (&TTYPE_IS, name) = payload.split_first()? else {
+        return None;
+    };
+    Some(String::from_utf8_lossy(name).into_owned())
+}
+
+/// The color support the terminal types a client reported imply.
+///
+/// Clients that follow the MUD Terminal Type Standard answer repeated `TTYPE SEND`s with a
+/// client name, a terminal type, then `MTTS <bits>`; the bits are exact, so they win. Otherwise
+/// the names are all there is, and a name that doesn't say `256color` or truecolor gets the 16
+/// ANSI colors: unlike a local terminal, a telnet client reporting a plain `xterm` or `ansi` is as
+/// likely to be a decades-old client as a modern one, and there is no query to ask instead.
+pub(crate) fn color_support_for(names: &[String]) -> ColorSupport {
+    const MTTS_ANSI: u32 = 1;
+    const MTTS_256: u32 = 8;
+    const MTTS_TRUECOLOR: u32 = 256;
+
+    let mtts = names.iter().find_map(|name| {
+        let bits = name.strip_prefix("MTTS ")?;
+        bits.trim().parse::<u32>().ok()
+    });
+    if let Some(bits) = mtts {
+        return if bits & MTTS_TRUECOLOR != 0 {
+            ColorSupport::Truecolor
+        } else if bits & MTTS_256 != 0 {
+            ColorSupport::Indexed256
+        } else if bits & MTTS_ANSI != 0 {
+            ColorSupport::Ansi16
+        } else {
+            ColorSupport::None
+        };
+    }
+    let names: Vec<String> = names.iter().map(|name| name.to_ascii_lowercase()).collect();
+    let says = |needle: &str| names.iter().any(|name| name.contains(needle));
+    if says("truecolor") || says("24bit") || says("direct") {
+        ColorSupport::Truecolor
+    } else if says("256") {
+        ColorSupport::Indexed256
+    } else if !names.is_empty() && names.iter().all(|name| name == "dumb") {
+        ColorSupport::None
+    } else {
+        ColorSupport::Ansi16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> Vec<Received> {
+        let mut parser = Parser::default();
+        bytes.iter().filter_map(|&b| parser.feed(b)).collect()
+    }
+
+    #[test]
+    fn commands_are_split_out_of_the_data_stream() {
+        assert_eq!(
+            parse(&[b'a', IAC, WILL, NAWS, IAC, IAC, IAC, 241, b'b', IAC, IP]),
+            [
+                Received::Data(b'a'),
+                Received::Negotiate(WILL, NAWS),
+                Received::Data(IAC),
+                Received::Data(b'b'),
+                Received::InterruptProcess,
+            ]
+        );
+    }
+
+    #[test]
+    fn subnegotiations_unescape_their_payload() {
+        assert_eq!(
+            parse(&[IAC, SB, NAWS, 0, 80, IAC, IAC, 0, 24, IAC, SE, b'x']),
+            [
+                Received::Subnegotiation(NAWS, vec![0, 80, IAC, 0, 24]),
+                Received::Data(b'x'),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_runaway_subnegotiation_is_capped() {
+        let mut bytes = vec![IAC, SB, TTYPE];
+        bytes.resize(bytes.len() + 10_000, b'x');
+        bytes.extend([IAC, SE]);
+        let [Received::Subnegotiation(TTYPE, payload)] = &parse(&bytes)[..] else {
+            panic!("expected one subnegotiation");
+        };
+        assert_eq!(payload.len(), MAX_SUBNEGOTIATION);
+    }
+
+    #[test]
+    fn unsupported_options_are_refused_once() {
+        assert_eq!(reply_to(WILL, NAWS), None);
+        assert_eq!(reply_to(DO, ECHO), None);
+        assert_eq!(reply_to(WILL, 39), Some([IAC, DONT, 39]));
+        assert_eq!(reply_to(DO, 39), Some([IAC, WONT, 39]));
+        assert_eq!(reply_to(WONT, 39), None);
+        assert_eq!(reply_to(DONT, ECHO), None);
+    }
+
+    #[test]
+    fn naws_and_ttype_payloads() {
+        assert_eq!(parse_naws(&[1, 4, 0, 50]), Some((260, 50)));
+        assert_eq!(parse_naws(&[0, 0, 0, 50]), None);
+        assert_eq!(parse_naws(&[0, 80, 0]), None);
+        assert_eq!(
+            parse_ttype(b"\x00xterm-256color").as_deref(),
+            Some("xterm-256color")
+        );
+        assert_eq!(parse_ttype(b"\x01"), None);
+    }
+
+    #[test]
+    fn color_support_prefers_mtts_over_names() {
+        let names = |names: &[&str]| names.iter().map(|&n| n.to_owned()).collect::<Vec<_>>();
+        assert_eq!(
+            color_support_for(&names(&["MUDLET", "ANSI-TRUECOLOR", "MTTS 9"])),
+            ColorSupport::Indexed256
+        );
+        assert_eq!(
+            color_support_for(&names(&["xterm-256color"])),
+            ColorSupport::Indexed256
+        );
+        assert_eq!(
+            color_support_for(&names(&["xterm-direct"])),
+            ColorSupport::Truecolor
+        );
+        assert_eq!(color_support_for(&names(&["ANSI"])), ColorSupport::Ansi16);
+        assert_eq!(color_support_for(&names(&["dumb"])), ColorSupport::None);
+        assert_eq!(color_support_for(&[]), ColorSupport::Ansi16);
+    }
+}
@@ -0,0 +1,216 @@
+//! End-to-end: a [`TelnetServer`] on loopback, driven by a scripted client that negotiates like a
+//! real telnet client would, types keys, resizes, and hangs up.
+
+use retroglyph_core::app::{App, Flow, Frame};
+use retroglyph_core::color::Style;
+use retroglyph_core::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use retroglyph_core::grid::Size;
+use retroglyph_core::terminal::Terminal;
+use retroglyph_telnet::{Telnet, TelnetServer};
+use retroglyph_terminal::ColorSupport;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+const IAC: u8 = 255;
+const WILL: u8 = 251;
+const DO: u8 = 253;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const ECHO: u8 = 1;
+const TTYPE: u8 = 24;
+const NAWS: u8 = 31;
+
+/// What the session's app reports back to the test.
+#[derive(Debug, PartialEq)]
+enum Seen {
+    Started(Size, ColorSupport, Vec<String>),
+    Event(Event),
+}
+
+/// Reports everything it sees, and draws how many keys it has seen so far.
+struct Scripted {
+    seen: mpsc::Sender<Seen>,
+    keys: usize,
+    drawn: bool,
+}
+
+impl App<Telnet> for Scripted {
+    fn init(&mut self, term: &mut Terminal<Telnet>) {
+        let backend = term.backend();
+        let _ = self.seen.send(Seen::Started(
+            term.size(),
+            backend.color_support(),
+            backend.terminal_types().to_vec(),
+        ));
+    }
+
+    fn update(&mut self, term: &mut Terminal<Telnet>, _frame: &Frame) -> Flow {
+        let mut changed = !self.drawn;
+        while let Some(event) = term.poll(Duration::ZERO) {
+            changed = true;
+            let close = event == Event::Close;
+            if matches!(event, Event::Key(_)) {
+                self.keys += 1;
+            }
+            let _ = self.seen.send(Seen::Event(event));
+            if close {
+                return Flow::Exit;
+            }
+        }
+        if !changed {
+            return Flow::Idle;
+        }
+        self.drawn = true;
+        term.surface()
+            .print((0, 0), &format!("keys={}", self.keys), Style::default());
+        Flow::Continue
+    }
+}
+
+/// The client end, reading with a deadline so a broken server fails the test instead of hanging
+/// it.
+struct Client {
+    stream: TcpStream,
+    received: Vec<u8>,
+}
+
+impl Client {
+    fn read_until(&mut self, what: &[u8]) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut buf = [0; 4096];
+        while !self.received.windows(what.len()).any(|w| w == what) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            assert!(!left.is_zero(), "never received {what:?}");
+            self.stream.set_read_timeout(Some(left)).unwrap();
+            let len = self.stream.read(&mut buf).unwrap();
+            assert_ne!(len, 0, "server hung up before sending {what:?}");
+            self.received.extend_from_slice(&buf[..len]);
+        }
+    }
+
+    fn send(&mut self, bytes: &[u8]) {
+        self.stream.write_all(bytes).unwrap();
+    }
+}
+
+fn naws(width: u16, height: u16) -> Vec<u8> {
+    let [w0, w1] = width.to_be_bytes();
+    let [h0, h1] = height.to_be_bytes();
+    vec![IAC, SB, NAWS, w0, w1, h0, h1, IAC, SE]
+}
+
+fn ttype_is(name: &str) -> Vec<u8> {
+    let mut bytes = vec![IAC, SB, TTYPE, 0];
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend([IAC, SE]);
+    bytes
+}
+
+fn next(seen: &mpsc::Receiver<Seen>) -> Seen {
+    seen.recv_timeout(Duration::from_secs(10)).unwrap()
+}
+
+#[test]
+fn a_scripted_client_negotiates_types_resizes_and_hangs_up() {
+    let server = TelnetServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let (tx, seen) = mpsc::channel();
+    std::thread::spawn(move || {
+        server.serve(move || Scripted {
+            seen: tx.clone(),
+            keys: 0,
+            drawn: false,
+        })
+    });
+
+    let mut client = Client {
+        stream: TcpStream::connect(addr).unwrap(),
+        received: Vec::new(),
+    };
+    client.read_until(&[IAC, WILL, ECHO]);
+    client.read_until(&[IAC, DO, NAWS]);
+    client.read_until(&[IAC, DO, TTYPE]);
+
+    client.send(&[IAC, WILL, NAWS]);
+    client.send(&naws(100, 30));
+    client.send(&[IAC, WILL, TTYPE]);
+    // The server keeps asking until the client runs out of names, which it signals by
+    // repeating the last one.
+    let send_ttype = [IAC, SB, TTYPE, 1, IAC, SE];
+    client.read_until(&send_ttype);
+    client.received.clear();
+    client.send(&ttype_is("MUDLET"));
+    client.read_until(&send_ttype);
+    client.received.clear();
+    client.send(&ttype_is("xterm-256color"));
+    client.read_until(&send_ttype);
+    client.send(&ttype_is("xterm-256color"));
+
+    assert_eq!(
+        next(&seen),
+        Seen::Started(
+            Size::new(100, 30),
+            ColorSupport::Indexed256,
+            vec!["MUDLET".to_owned(), "xterm-256color".to_owned()]
+        )
+    );
+    client.read_until(b"keys=0");
+
+    client.send(b"q\x1b[A");
+    assert_eq!(
+        next(&seen),
+        Seen::Event(Event::Key(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE
+        )))
+    );
+    assert_eq!(
+        next(&seen),
+        Seen::Event(Event::Key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE)))
+    );
+    // Only the changed cell is redrawn: the count's digit, sixth column of the first row.
+    client.read_until(b"\x1b[1;6H2");
+
+    client.send(&naws(120, 40));
+    assert_eq!(next(&seen), Seen::Event(Event::Resize(120, 40)));
+
+    drop(client);
+    assert_eq!(next(&seen), Seen::Event(Event::Close));
+}
+
+#[test]
+fn every_connection_gets_its_own_app() {
+    let server = TelnetServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let (tx, seen) = mpsc::channel();
+    std::thread::spawn(move || {
+        server.serve(move || Scripted {
+            seen: tx.clone(),
+            keys: 0,
+            drawn: false,
+        })
+    });
+
+    let connect = || {
+        let mut client = Client {
+            stream: TcpStream::connect(addr).unwrap(),
+            received: Vec::new(),
+        };
+        client.read_until(&[IAC, DO, TTYPE]);
+        client.send(&naws(20, 5));
+        client.send(&[IAC, 252, TTYPE]);
+        client
+    };
+    let mut first = connect();
+    let mut second = connect();
+    for _ in 0..2 {
+        assert!(matches!(next(&seen), Seen::Started(size, ..) if size == Size::new(20, 5)));
+    }
+
+    first.send(b"a");
+    first.read_until(b"\x1b[1;6H1");
+    second.read_until(b"keys=0");
+    assert!(!second.received.windows(7).any(|w| w == b"\x1b[1;6H1"));
+}
@@ -0,0 +1,125 @@
+// Reference decoder and canvas renderer for `TerminalWasm`'s `OutputMode::Cells` records.
+//
+// The layout mirrors this crate's `cell_format` module: five little-endian `u32` words per cell,
+// read in place from wasm memory through a `Uint32Array` view.
+
+export const RECORD_WORDS = 5;
+
+const COLOR_KIND_MASK = 0xff000000;
+const COLOR_ANSI = 1 << 24;
+const COLOR_INDEXED = 2 << 24;
+const COLOR_RGB = 3 << 24;
+
+const FLAG_LINE_MASK = 0b11;
+const FLAG_WIDE = 1 << 2;
+const FLAG_CLEAR = 1 << 3;
+
+// The xterm defaults for the 16 ANSI colors.
+const ANSI = [
+  '#000000', '#cd0000', '#00cd00', '#cdcd00', '#0000ee', '#cd00cd', '#00cdcd', '#e5e5e5',
+  '#7f7f7f', '#ff0000', '#00ff00', '#ffff00', '#5c5cff', '#ff00ff', '#00ffff', '#ffffff',
+];
+
+function hex(r, g, b) {
+  return '#' + ((r << 16) | (g << 8) | b).toString(16).padStart(6, '0');
+}
+
+// The 256-color palette: the 16 ANSI colors, a 6x6x6 cube, then a 24-step gray ramp.
+function indexed(index) {
+  if (index < 16) return ANSI[index];
+  if (index < 232) {
+    const level = (n) => (n === 0 ? 0 : 55 + n * 40);
+    const i = index - 16;
+    return hex(level(Math.floor(i / 36)), level(Math.floor(i / 6) % 6), level(i % 6));
+  }
+  const gray = 8 + (index - 232) * 10;
+  return hex(gray, gray, gray);
+}
+
+// Returns a CSS color for a color word, or `null` for the renderer's own default.
+export function cssColor(word) {
+  const value = word & 0xffffff;
+  switch ((word & COLOR_KIND_MASK) >>> 0) {
+    case COLOR_ANSI:
+      return ANSI[value & 0xf];
+    case COLOR_INDEXED:
+      return indexed(value & 0xff);
+    case COLOR_RGB:
+      return hex(value >> 16, (value >> 8) & 0xff, value & 0xff);
+    default:
+      return null;
+  }
+}
+
+// Decodes `len` words starting at byte offset `ptr` of `memory` (a `WebAssembly.Memory`) into
+// `{ x, y, ch, fg, bg, line, wide, clear }` objects, in draw order.
+export function decodeCells(memory, ptr, len) {
+  const words = new Uint32Array(memory.buffer, ptr, len);
+  const cells = [];
+  for (let i = 0; i + RECORD_WORDS <= words.length; i += RECORD_WORDS) {
+    const flags = words[i + 4];
+    cells.push({
+      x: words[i] & 0xffff,
+      y: words[i] >>> 16,
+      ch: String.fromCodePoint(words[i + 1]),
+      fg: cssColor(words[i + 2]),
+      bg: cssColor(words[i + 3]),
+      line: flags & FLAG_LINE_MASK,
+      wide: (flags & FLAG_WIDE) !== 0,
+      clear: (flags & FLAG_CLEAR) !== 0,
+    });
+  }
+  return cells;
+}
+
+// Paints decoded cells onto a `<canvas>`, one `cellWidth` x `cellHeight` box per cell.
+export class CanvasCellRenderer {
+  constructor(canvas, { cellWidth = 9, cellHeight = 18, font = '15px monospace',
+                        fg = '#e5e5e5', bg = '#000000' } = {}) {
+    this.ctx = canvas.getContext('2d');
+    this.canvas = canvas;
+    this.cellWidth = cellWidth;
+    this.cellHeight = cellHeight;
+    this.font = font;
+    this.fg = fg;
+    this.bg = bg;
+  }
+
+  // Decodes and paints the records at `ptr`; see `decodeCells`.
+  apply(memory, ptr, len) {
+    for (const cell of decodeCells(memory, ptr, len)) {
+      if (cell.clear) {
+        this.ctx.fillStyle = this.bg;
+        this.ctx.fillRect(0, 0, this.canvas.width, this.canvas.height);
+      } else {
+        this.paint(cell);
+      }
+    }
+  }
+
+  paint(cell) {
+    const { ctx, cellWidth: w, cellHeight: h } = this;
+    // Double-size lines draw every cell two columns wide; double height additionally shows
+    // only the top or bottom half of a glyph stretched to twice the row's height.
+    const scaleX = cell.line === 0 ? 1 : 2;
+    const scaleY = cell.line >= 2 ? 2 : 1;
+    const x = cell.x * w * scaleX;
+    const y = cell.y * h;
+    const width = w * scaleX * (cell.wide ? 2 : 1);
+
+    ctx.fillStyle = cell.bg ?? this.bg;
+    ctx.fillRect(x, y, width, h);
+
+    ctx.save();
+    ctx.beginPath();
+    ctx.rect(x, y, width, h);
+    ctx.clip();
+    ctx.translate(x, cell.line === 3 ? y - h : y);
+    ctx.scale(scaleX, scaleY);
+    ctx.font = this.font;
+    ctx.textBaseline = 'top';
+    ctx.fillStyle = cell.fg ?? this.fg;
+    ctx.fillText(cell.ch, 0, 0);
+    ctx.restore();
+  }
+}
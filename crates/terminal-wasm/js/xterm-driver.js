@@ -3,6 +3,7 @@ import init, {
   wasm_terminal_resize,
   wasm_terminal_push_key,
   wasm_terminal_take_output,
+  wasm_terminal_take_clipboard,
 } from './pkg.js';
 
 // `code` values above 0x110000 select a named key; see this crate's `key_codes` module for the
@@ -43,6 +44,13 @@ async function main() {
   function frame() {
     const ansi = wasm_terminal_take_output(handle);
     if (ansi) term.write(ansi);
+    // Text the game copied (e.g. a seed) goes to the browser clipboard.
+    const copied = wasm_terminal_take_clipboard(handle);
+    // The write is refused while the page lacks focus or clipboard permission; say so rather
+    // than leave the rejection unhandled.
+    if (copied !== undefined) {
+      navigator.clipboard.writeText(copied).catch((err) => console.warn('copy failed:', err));
+    }
     requestAnimationFrame(frame);
   }
   requestAnimationFrame(frame);
@@ -63,6 +63,9 @@
 ///   [`FrameClock`](retroglyph_core::frames::FrameClock) caps steps-per-frame to avoid), just on the raw
 ///   delta feeding into `Frame` instead. All FFI functions are no-ops (returning an empty string
 ///   for `wasm_app_tick`) if called before `wasm_app_init`.
+/// - `wasm_app_take_clipboard() -> Option<String>` / `wasm_app_report_clipboard(text)`: relay
+///   the browser clipboard, as [`TerminalWasm::take_clipboard`](crate::TerminalWasm::take_clipboard)
+///   and [`TerminalWasm::report_clipboard`](crate::TerminalWasm::report_clipboard) describe.
 /// - `wasm_app_exited() -> bool`: `true` once `$A::update` has returned `Flow::Exit` at least
 ///   once. A browser tab has no native "exit the process" the way a windowed backend's event loop
 ///   does, so this crate can't stop JS's `requestAnimationFrame` loop for it; check this after
@@ -222,6 +225,31 @@ macro_rules! app_entry {
                 })
             }
 
+            /// Take the text `$A` copied since the last call, for `navigator.clipboard.writeText`;
+            /// `undefined` if nothing was copied or before `wasm_app_init`.
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            #[must_use]
+            #[allow(missing_docs)]
+            pub fn wasm_app_take_clipboard() -> ::std::option::Option<::std::string::String> {
+                __RG_WASM_APP.with(|cell| {
+                    cell.borrow_mut()
+                        .as_mut()
+                        .and_then(|s| s.term.backend_mut().take_clipboard())
+                })
+            }
+
+            /// Report the browser clipboard's current text, for `$A`'s next
+            /// `Clipboard::clipboard_text` to return.
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            #[allow(missing_docs)]
+            pub fn wasm_app_report_clipboard(text: ::std::string::String) {
+                __RG_WASM_APP.with(|cell| {
+                    if let Some(s) = cell.borrow_mut().as_mut() {
+                        s.term.backend_mut().report_clipboard(text);
+                    }
+                });
+            }
+
             // Required symbol for the wasm32 binary target; JS never calls it directly in this
             // entry mode (no event loop to kick off at module-load time; everything is pushed
             // in from JS instead). Matches the equivalent comment on
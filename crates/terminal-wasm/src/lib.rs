@@ -26,7 +26,9 @@
 //! The `wasm32` build additionally exposes free functions
 //! (`wasm_terminal_new`, `wasm_terminal_resize`, `wasm_terminal_push_key`,
 //! `wasm_terminal_push_mouse`, `wasm_terminal_push_paste`,
-//! `wasm_terminal_take_output`, in this crate's `wasm` module, only
+//! `wasm_terminal_take_output`, `wasm_terminal_take_clipboard`,
+//! `wasm_terminal_report_clipboard`, `wasm_terminal_set_output_mode`, `wasm_terminal_cells_ptr`,
+//! `wasm_terminal_cells_len`, `wasm_terminal_clear_cells`, in this crate's `wasm` module, only
 //! compiled for `target_arch = "wasm32"`, so it won't appear in docs built
 //! natively) that operate on an opaque handle, since
 //! `retroglyph_core::event::Event` is not itself `wasm-bindgen`-compatible.
@@ -62,6 +64,29 @@
 #![doc = include_str!("../js/xterm-driver.js")]
 //! ```
 //!
+//! The same driver can display an app running natively on a server instead:
+//! `retroglyph-websocket`'s `js/websocket-pkg.js` exports these functions forwarded over a
+//! WebSocket, so importing it in place of `./pkg.js` (and passing the server's URL to `init`) is
+//! the only change.
+//!
+//! # Painting cells without a terminal emulator
+//!
+//! A driver that paints its own HTML canvas or DOM grid doesn't need ANSI at all. After
+//! `wasm_terminal_set_output_mode(handle, 1)` ([`output_modes::CELLS`], see
+//! [`OutputMode::Cells`]), each frame's changed cells accumulate as packed [`cell_format`] records
+//! instead, read straight out of wasm memory:
+//!
+//! ```js
+//! const ptr = wasm_terminal_cells_ptr(handle);
+//! const len = wasm_terminal_cells_len(handle);
+//! renderer.apply(wasm.memory, ptr, len); // a `Uint32Array` view, no copy
+//! wasm_terminal_clear_cells(handle);
+//! ```
+//!
+//! `crates/terminal-wasm/js/cell-renderer.js` in the source tree is a small reference
+//! implementation: a `decodeCells` function for the record layout and a `CanvasCellRenderer` that
+//! paints decoded cells onto a `<canvas>` 2D context.
+//!
 //! # Features
 //!
 //! <!-- gen-features:start -->
@@ -172,7 +197,9 @@ struct ReadmeDoctests;
 mod app_entry;
 
 use retroglyph_core::backend::DrawCell;
-use retroglyph_core::backend::{Cursor, CursorStyle, Input, Output};
+use retroglyph_core::backend::{
+    Clipboard, ClipboardError, Cursor, CursorStyle, EventInbox, EventSender, Input, Output,
+};
 use retroglyph_core::event::{Event, push_coalesced};
 use retroglyph_core::grid::HasSize;
 use retroglyph_core::grid::{Pos, Size};
@@ -180,6 +207,7 @@ use retroglyph_core::terminal::Terminal;
 use retroglyph_terminal::TerminalRenderer;
 use std::collections::VecDeque;
 use std::io;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 
 /// A `std::io::Write` sink backed by a `String` instead of a `Vec<u8>`.
@@ -233,6 +261,41 @@ pub struct TerminalWasm {
     renderer: TerminalRenderer<Utf8Sink>,
     size: Size,
     event_queue: VecDeque<Event>,
+    /// Created by the first [`Input::event_sender`] call; drained into `event_queue` by every
+    /// [`poll_event`](Input::poll_event).
+    inbox: Option<EventInbox>,
+    /// Registered by [`Input::poll_next_event`] while `event_queue` is empty, woken by the next
+    /// [`push_event`](Input::push_event).
+    waker: Option<Waker>,
+    /// The browser clipboard's text as last reported by
+    /// [`report_clipboard`](Self::report_clipboard) or copied by the app itself.
+    clipboard: Option<String>,
+    /// Text copied since the last [`take_clipboard`](Self::take_clipboard), waiting for JS to
+    /// hand it to the browser.
+    copied: Option<String>,
+    /// What [`draw_layers`](Output::draw_layers) renders into; see [`OutputMode`].
+    mode: OutputMode,
+    /// Packed [`cell_format`] records drawn since the last [`clear_cells`](Self::clear_cells),
+    /// only ever written in [`OutputMode::Cells`].
+    cells: Vec<u32>,
+}
+
+/// What a [`TerminalWasm`] renders each frame into.
+///
+/// Set with [`TerminalWasm::set_output_mode`] (or `wasm_terminal_set_output_mode` from JS, with
+/// one of [`output_modes`]'s constants).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// ANSI text for a browser terminal emulator such as xterm.js, drained with
+    /// [`take_output`](TerminalWasm::take_output).
+    #[default]
+    Ansi,
+    /// The frame's changed cells as packed [`cell_format`] records, read in place with
+    /// [`cells`](TerminalWasm::cells), for a driver that paints its own canvas or DOM grid.
+    ///
+    /// No ANSI is produced in this mode, and the cursor is not reported: a custom renderer draws
+    /// (or hides) its own.
+    Cells,
 }
 
 /// The maximum number of events [`TerminalWasm::push_event`] will hold at once.
@@ -284,6 +347,12 @@ impl TerminalWasm {
             renderer: TerminalRenderer::new(Utf8Sink(String::new())),
             size: Size::new(width, height),
             event_queue: VecDeque::new(),
+            waker: None,
+            inbox: None,
+            clipboard: None,
+            copied: None,
+            mode: OutputMode::Ansi,
+            cells: Vec::new(),
         }
     }
 
@@ -327,6 +396,9 @@ impl TerminalWasm {
         {
             self.event_queue.pop_front();
         }
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
     }
 
     /// Drains and returns the ANSI bytes rendered since the last call, as a
@@ -381,6 +453,158 @@ impl TerminalWasm {
         buf.push_str(sink);
         sink.clear();
     }
+
+    /// Takes the text the app copied with [`Clipboard::set_clipboard_text`] since the last call,
+    /// if any.
+    ///
+    /// The browser's Async Clipboard API is asynchronous and only reachable from JS, so a copy
+    /// waits here until the driver picks it up, typically once per animation frame next to
+    /// [`take_output`](Self::take_output), and passes it to `navigator.clipboard.writeText`. Only
+    /// the latest copy is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::backend::Clipboard;
+    /// use retroglyph_terminal_wasm::TerminalWasm;
+    ///
+    /// let mut backend = TerminalWasm::new(10, 3);
+    /// backend.set_clipboard_text("seed 42").unwrap();
+    /// assert_eq!(backend.take_clipboard().as_deref(), Some("seed 42"));
+    /// assert_eq!(backend.take_clipboard(), None);
+    /// ```
+    #[must_use]
+    pub const fn take_clipboard(&mut self) -> Option<String> {
+        self.copied.take()
+    }
+
+    /// Records `text` as the browser clipboard's current contents, for the next
+    /// [`Clipboard::clipboard_text`] to return.
+    ///
+    /// Reading the browser clipboard needs a promise and, in most browsers, a permission prompt or
+    /// a user gesture, none of which a synchronous `clipboard_text` call can wait on. The driver
+    /// reads it instead (e.g. `navigator.clipboard.readText()` on focus, or a `paste` event's
+    /// `clipboardData`) and reports what it found here.
+    pub fn report_clipboard(&mut self, text: String) {
+        self.clipboard = Some(text);
+    }
+
+    /// Calls `wake` after every event sent through an [`EventSender`] from
+    /// [`event_sender`](Input::event_sender), on the sending thread.
+    ///
+    /// Nothing here blocks, so a send has no wait to cut short; what it can wake is the driver. A
+    /// driver that stops its `requestAnimationFrame` loop while the app is idle uses this to
+    /// schedule the next frame as soon as a timer callback or a worker sends something, rather
+    /// than finding the event on a frame it runs anyway. Replaces any earlier hook.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::backend::Input;
+    /// use retroglyph_core::event::Event;
+    /// use retroglyph_terminal_wasm::TerminalWasm;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let mut backend = TerminalWasm::new(10, 3);
+    /// let frame_wanted = Arc::new(AtomicBool::new(false));
+    /// let flag = Arc::clone(&frame_wanted);
+    /// backend.set_wake_hook(move || flag.store(true, Ordering::SeqCst));
+    ///
+    /// let sender = backend.event_sender().unwrap();
+    /// sender.send(Event::Custom(1)).unwrap();
+    /// assert!(frame_wanted.load(Ordering::SeqCst));
+    /// ```
+    pub fn set_wake_hook(&mut self, wake: impl Fn() + Send + Sync + 'static) {
+        self.inbox
+            .get_or_insert_with(EventInbox::new)
+            .set_notify(wake);
+    }
+
+    /// Returns what [`draw_layers`](Output::draw_layers) currently renders into.
+    #[must_use]
+    pub const fn output_mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    /// Switches what [`draw_layers`](Output::draw_layers) renders into, discarding anything
+    /// pending in either form.
+    ///
+    /// Only cells that changed since the previous frame are ever drawn, so switch before the
+    /// first frame for the new mode to start from a full one.
+    pub fn set_output_mode(&mut self, mode: OutputMode) {
+        self.mode = mode;
+        self.renderer.writer_mut().0.clear();
+        self.renderer.reset_state();
+        self.cells.clear();
+    }
+
+    /// Returns the [`cell_format`] records drawn since the last
+    /// [`clear_cells`](Self::clear_cells), in draw order.
+    ///
+    /// Always empty in [`OutputMode::Ansi`]. From JS, `wasm_terminal_cells_ptr` and
+    /// `wasm_terminal_cells_len` locate this slice in wasm memory so it can be read through a
+    /// `Uint32Array` view without copying; `js/cell-renderer.js` in the source tree is a reference
+    /// decoder and canvas renderer for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::color::{Color, Style};
+    /// use retroglyph_core::terminal::Terminal;
+    /// use retroglyph_terminal_wasm::{OutputMode, TerminalWasm, cell_format};
+    ///
+    /// let mut backend = TerminalWasm::new(10, 3);
+    /// backend.set_output_mode(OutputMode::Cells);
+    /// let mut term = Terminal::new(backend);
+    /// term.draw(|s| s.put((2, 1), '@', Style::default().fg(Color::rgb(255, 0, 0))))?;
+    ///
+    /// let cells = term.backend().cells();
+    /// assert_eq!(cells.len() % cell_format::RECORD_WORDS, 0);
+    /// let at = cells
+    ///     .chunks_exact(cell_format::RECORD_WORDS)
+    ///     .find(|record| record[cell_format::CODEPOINT] == u32::from('@'))
+    ///     .unwrap();
+    /// assert_eq!(at[cell_format::POS], 2 | (1 << 16));
+    /// assert_eq!(at[cell_format::FG], cell_format::COLOR_RGB | 0xFF_00_00);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn cells(&self) -> &[u32] {
+        &self.cells
+    }
+
+    /// Discards the records returned by [`cells`](Self::cells), keeping the allocation for the
+    /// next frame.
+    ///
+    /// Call this once the driver has painted them, typically once per animation frame.
+    pub fn clear_cells(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Appends one [`cell_format`] record per drawable cell of `content`.
+    fn push_cells<'a>(&mut self, content: impl Iterator<Item = DrawCell<'a>>) {
+        use retroglyph_core::tile::TileFlags;
+        for cell in content {
+            // The right half of a wide glyph carries nothing to paint; its left half's
+            // `FLAG_WIDE` already covers both columns, matching `TerminalRenderer`'s own skip.
+            if cell.tile.flags().contains(TileFlags::WIDE_CHAR_SPACER) {
+                continue;
+            }
+            let style = cell.tile.style();
+            let mut flags = cell_format::line_flags(cell.line);
+            if cell.tile.width() == 2 {
+                flags |= cell_format::FLAG_WIDE;
+            }
+            self.cells.extend_from_slice(&[
+                u32::from(cell.pos.x) | (u32::from(cell.pos.y) << 16),
+                u32::from(cell.tile.glyph()),
+                cell_format::encode_color(style.foreground()),
+                cell_format::encode_color(style.background()),
+                flags,
+            ]);
+        }
+    }
 }
 
 /// Resizes `term` to `(width, height)` cells, doing everything a correct resize needs in one call.
@@ -430,11 +654,20 @@ impl Output for TerminalWasm {
         let size = self.size;
         let content =
             content.filter(move |cell| cell.pos.x < size.width() && cell.pos.y < size.height());
-        self.renderer.draw_frame(content)
+        match self.mode {
+            OutputMode::Ansi => self.renderer.draw_frame(content),
+            OutputMode::Cells => {
+                self.push_cells(content);
+                Ok(())
+            }
+        }
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
-        self.renderer.end_frame()
+        match self.mode {
+            OutputMode::Ansi => self.renderer.end_frame(),
+            OutputMode::Cells => Ok(()),
+        }
     }
 
     fn size(&self) -> Size {
@@ -457,7 +690,16 @@ impl Output for TerminalWasm {
         // erase paints the whole screen with. This backend has no direct handle to the emulator,
         // so both the reset and the "clear screen, cursor home" CSI sequence that follows it are
         // just more ANSI bytes for JS to forward, same as every other draw call.
-        self.renderer.clear_screen()
+        match self.mode {
+            OutputMode::Ansi => self.renderer.clear_screen(),
+            OutputMode::Cells => {
+                // Anything still pending is about to be erased anyway.
+                self.cells.clear();
+                self.cells
+                    .extend_from_slice(&[0, 0, 0, 0, cell_format::FLAG_CLEAR]);
+                Ok(())
+            }
+        }
     }
 }
 
@@ -465,6 +707,10 @@ impl Input for TerminalWasm {
     fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
         // Never blocks: there is no runtime loop to block in. `_timeout` is
         // ignored, matching Headless's push-driven, non-blocking contract.
+        // Sent events go through `push_event` so they share its coalescing and cap.
+        while let Some(event) = self.inbox.as_ref().and_then(EventInbox::try_recv) {
+            Self::push_event(self, event);
+        }
         self.event_queue.pop_front()
     }
 
@@ -473,15 +719,66 @@ impl Input for TerminalWasm {
     fn push_event(&mut self, event: Event) {
         Self::push_event(self, event);
     }
+
+    /// Waits on the queue [`push_event`](Input::push_event) fills and on the inbox: either one
+    /// wakes a parked task.
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        if let Some(event) = self.poll_event(Duration::ZERO) {
+            return Poll::Ready(event);
+        }
+        if let Some(event) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.poll_recv(cx.waker()))
+        {
+            return Poll::Ready(event);
+        }
+        self.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Hands out a sender whose events the next [`poll_event`](Input::poll_event) picks up.
+    ///
+    /// Sending never blocks and needs no threads, so this works on `wasm32` too: a JS callback
+    /// (a `setTimeout`, a `fetch` completion) that reaches Rust can send through a sender it was
+    /// given. [`EventSender::schedule`](retroglyph_core::backend::EventSender::schedule) and its
+    /// repeating counterpart need threads and exist only off `wasm32`. See
+    /// [`set_wake_hook`](TerminalWasm::set_wake_hook) for letting a send wake the driver.
+    fn event_sender(&mut self) -> Option<EventSender> {
+        Some(self.inbox.get_or_insert_with(EventInbox::new).sender())
+    }
 }
 
+/// The browser clipboard, relayed through JS: see [`take_clipboard`](TerminalWasm::take_clipboard)
+/// and [`report_clipboard`](TerminalWasm::report_clipboard).
+impl Clipboard for TerminalWasm {
+    /// Returns the text last reported by the driver or copied by the app, and fails if there is
+    /// neither.
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.clipboard
+            .clone()
+            .ok_or_else(|| ClipboardError::new("the browser clipboard has not been reported"))
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.clipboard = Some(text.to_owned());
+        self.copied = Some(text.to_owned());
+        Ok(())
+    }
+}
+
+/// The cursor is only written in [`OutputMode::Ansi`]; [`OutputMode::Cells`] does not report it.
 impl Cursor for TerminalWasm {
     fn set_cursor_visible(&mut self, visible: bool) {
-        let _ = self.renderer.set_cursor_visible(visible);
+        if self.mode == OutputMode::Ansi {
+            let _ = self.renderer.set_cursor_visible(visible);
+        }
     }
 
     fn set_cursor_position(&mut self, position: Pos) {
-        let _ = self.renderer.move_cursor_to(position);
+        if self.mode == OutputMode::Ansi {
+            let _ = self.renderer.move_cursor_to(position);
+        }
     }
 
     /// Writes the `DECSCUSR` cursor-shape escape.
@@ -492,7 +789,9 @@ impl Cursor for TerminalWasm {
     /// (<https://chromium.googlesource.com/apps/libapps/+/HEAD/hterm/docs/ControlSequences.md>)
     /// implement `CSI Ps SP q` identically.
     fn set_cursor_style(&mut self, style: CursorStyle) {
-        let _ = self.renderer.set_cursor_style(style);
+        if self.mode == OutputMode::Ansi {
+            let _ = self.renderer.set_cursor_style(style);
+        }
     }
 }
 
@@ -683,6 +982,92 @@ pub mod mouse_buttons {
     pub const RIGHT: u8 = 2;
 }
 
+/// `mode` values for `wasm_terminal_set_output_mode`, one per [`OutputMode`].
+pub mod output_modes {
+    /// [`OutputMode::Ansi`](crate::OutputMode::Ansi), the default.
+    pub const ANSI: u8 = 0;
+    /// [`OutputMode::Cells`](crate::OutputMode::Cells).
+    pub const CELLS: u8 = 1;
+}
+
+pub mod cell_format {
+    //! The packed record layout [`TerminalWasm::cells`](crate::TerminalWasm::cells) returns in
+    //! [`OutputMode::Cells`](crate::OutputMode::Cells).
+    //!
+    //! Each drawn cell is [`RECORD_WORDS`] consecutive `u32`s, so JS can walk a `Uint32Array`
+    //! view of wasm memory in steps of five:
+    //!
+    //! | Word | Contents |
+    //! | --- | --- |
+    //! | [`POS`] | column in the low 16 bits, row in the high 16 bits |
+    //! | [`CODEPOINT`] | the glyph's Unicode scalar value (the base character of a cluster) |
+    //! | [`FG`] / [`BG`] | one of the `COLOR_*` kinds in the top byte, its value in the low 24 bits |
+    //! | [`FLAGS`] | the `FLAG_*` bits |
+    //!
+    //! A record with [`FLAG_CLEAR`] set is not a cell: it tells the renderer to erase everything
+    //! to the default background before applying the records after it. Values are
+    //! little-endian, the only byte order wasm memory has.
+
+    use retroglyph_core::color::Color;
+    use retroglyph_core::grid::LineSize;
+
+    /// The number of `u32` words in one record.
+    pub const RECORD_WORDS: usize = 5;
+
+    /// Index of the packed `x | y << 16` position word.
+    pub const POS: usize = 0;
+    /// Index of the codepoint word.
+    pub const CODEPOINT: usize = 1;
+    /// Index of the foreground color word.
+    pub const FG: usize = 2;
+    /// Index of the background color word.
+    pub const BG: usize = 3;
+    /// Index of the flags word.
+    pub const FLAGS: usize = 4;
+
+    /// Color kind: the renderer's own default foreground or background. The low 24 bits are 0.
+    pub const COLOR_DEFAULT: u32 = 0;
+    /// Color kind: one of the 16 ANSI colors, its index (`0`-`15`) in the low byte.
+    pub const COLOR_ANSI: u32 = 1 << 24;
+    /// Color kind: a 256-color palette index in the low byte.
+    pub const COLOR_INDEXED: u32 = 2 << 24;
+    /// Color kind: 24-bit RGB as `0xRRGGBB` in the low 24 bits.
+    pub const COLOR_RGB: u32 = 3 << 24;
+    /// Masks the color kind out of a color word.
+    pub const COLOR_KIND_MASK: u32 = 0xFF << 24;
+
+    /// Mask of the two flag bits holding the row's line size: `0` normal, `1` double width, `2`
+    /// the top half of double height, `3` its bottom half (see
+    /// [`LineSize`]).
+    pub const FLAG_LINE_MASK: u32 = 0b11;
+    /// The glyph is two columns wide and covers the next cell too, which is never sent.
+    pub const FLAG_WIDE: u32 = 1 << 2;
+    /// Not a cell: erase the whole display before applying the records that follow.
+    pub const FLAG_CLEAR: u32 = 1 << 3;
+
+    pub(crate) fn encode_color(color: Color) -> u32 {
+        match color {
+            Color::Ansi(ansi) => COLOR_ANSI | u32::from(ansi.to_index()),
+            Color::Indexed(index) => COLOR_INDEXED | u32::from(index),
+            Color::Rgb { r, g, b } => {
+                COLOR_RGB | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b)
+            }
+            // Covers `Color::Default` plus any future variant (`Color` is `#[non_exhaustive]`),
+            // the same fallback `TerminalRenderer` takes.
+            _ => COLOR_DEFAULT,
+        }
+    }
+
+    pub(crate) const fn line_flags(line: LineSize) -> u32 {
+        match line {
+            LineSize::Normal => 0,
+            LineSize::DoubleWidth => 1,
+            LineSize::DoubleHeightTop => 2,
+            LineSize::DoubleHeightBottom => 3,
+        }
+    }
+}
+
 /// `code` values for [`decode_key_event`]'s named (non-printable) keys.
 ///
 /// Values start at `0x0011_0000`, above the Unicode scalar value space
@@ -744,7 +1129,7 @@ pub mod key_codes {
 /// [`wasm_terminal_free`].
 #[cfg(target_arch = "wasm32")]
 pub mod wasm {
-    use super::{TerminalWasm, decode_key_event, decode_mouse_event};
+    use super::{OutputMode, TerminalWasm, decode_key_event, decode_mouse_event, output_modes};
     use std::cell::RefCell;
     use std::collections::HashMap;
     use wasm_bindgen::prelude::wasm_bindgen;
@@ -778,13 +1163,22 @@ pub mod wasm {
     #[wasm_bindgen]
     #[must_use]
     pub fn wasm_terminal_new(width: u16, height: u16) -> u32 {
+        wasm_terminal_insert(TerminalWasm::new(width, height))
+    }
+
+    /// Registers an existing [`TerminalWasm`] and returns a handle to it, like [`wasm_terminal_new`].
+    ///
+    /// Useful for one whose frames a [`Terminal`](retroglyph_core::terminal::Terminal) already
+    /// drew. Rust-only: `TerminalWasm` isn't a `wasm-bindgen` type, so this isn't exported to JS.
+    #[must_use]
+    pub fn wasm_terminal_insert(backend: TerminalWasm) -> u32 {
         let handle = NEXT_HANDLE.with_borrow_mut(|next| {
             let handle = *next;
             *next += 1;
             handle
         });
         INSTANCES.with_borrow_mut(|instances| {
-            instances.insert(handle, TerminalWasm::new(width, height));
+            instances.insert(handle, backend);
         });
         handle
     }
@@ -899,6 +1293,79 @@ pub mod wasm {
         )
         .unwrap_or_default()
     }
+
+    /// Switches the terminal identified by `handle` to `mode`, one of [`output_modes`]'s
+    /// constants; see [`TerminalWasm::set_output_mode`]. Silently ignores an unknown `mode`.
+    #[wasm_bindgen]
+    pub fn wasm_terminal_set_output_mode(handle: u32, mode: u8) {
+        let mode = match mode {
+            output_modes::ANSI => OutputMode::Ansi,
+            output_modes::CELLS => OutputMode::Cells,
+            _ => return,
+        };
+        with_terminal(handle, "wasm_terminal_set_output_mode", |term| {
+            term.set_output_mode(mode);
+        });
+    }
+
+    /// Returns the byte offset in wasm memory of the cell records pending for the terminal
+    /// identified by `handle` (see [`TerminalWasm::cells`]), or `0` if `handle` is unknown.
+    ///
+    /// Pair it with [`wasm_terminal_cells_len`] to build a view without copying:
+    /// `new Uint32Array(memory.buffer, ptr, len)`. The view is only valid until the next draw or
+    /// [`wasm_terminal_clear_cells`] call, either of which may move or overwrite the records.
+    #[wasm_bindgen]
+    #[must_use]
+    pub fn wasm_terminal_cells_ptr(handle: u32) -> *const u32 {
+        with_terminal(handle, "wasm_terminal_cells_ptr", |term| {
+            term.cells().as_ptr()
+        })
+        .unwrap_or(std::ptr::null())
+    }
+
+    /// Returns how many `u32` words of cell records are pending for `handle`, or `0` if unknown.
+    ///
+    /// Always a multiple of [`cell_format::RECORD_WORDS`](crate::cell_format::RECORD_WORDS).
+    #[wasm_bindgen]
+    #[must_use]
+    pub fn wasm_terminal_cells_len(handle: u32) -> usize {
+        with_terminal(handle, "wasm_terminal_cells_len", |term| term.cells().len()).unwrap_or(0)
+    }
+
+    /// Discards the cell records pending for the terminal identified by `handle`, once the driver
+    /// has painted them; see [`TerminalWasm::clear_cells`].
+    #[wasm_bindgen]
+    pub fn wasm_terminal_clear_cells(handle: u32) {
+        with_terminal(
+            handle,
+            "wasm_terminal_clear_cells",
+            TerminalWasm::clear_cells,
+        );
+    }
+
+    /// Takes the text the app copied since the last call for the terminal identified by `handle`.
+    ///
+    /// For the driver to pass to `navigator.clipboard.writeText`; see
+    /// [`TerminalWasm::take_clipboard`]. `undefined` if nothing was copied or `handle` is unknown.
+    #[wasm_bindgen]
+    #[must_use]
+    pub fn wasm_terminal_take_clipboard(handle: u32) -> Option<String> {
+        with_terminal(
+            handle,
+            "wasm_terminal_take_clipboard",
+            TerminalWasm::take_clipboard,
+        )
+        .flatten()
+    }
+
+    /// Reports the browser clipboard's current text to the terminal identified by `handle`; see
+    /// [`TerminalWasm::report_clipboard`].
+    #[wasm_bindgen]
+    pub fn wasm_terminal_report_clipboard(handle: u32, text: String) {
+        with_terminal(handle, "wasm_terminal_report_clipboard", |term| {
+            term.report_clipboard(text);
+        });
+    }
 }
 
 #[cfg(test)]
@@ -1013,6 +1480,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cell_mode_records_changed_cells_instead_of_ansi() {
+        use retroglyph_core::color::{AnsiColor, Color};
+
+        let mut backend = TerminalWasm::new(4, 2);
+        backend.set_output_mode(OutputMode::Cells);
+        let mut term = Terminal::new(backend);
+        term.draw(|s| {
+            s.put((1, 0), 'A', Style::default().fg(Color::Indexed(0x2A)));
+            s.put(
+                (3, 1),
+                'B',
+                Style::default().bg(Color::Ansi(AnsiColor::Red)),
+            );
+        })
+        .unwrap();
+        assert_eq!(term.backend_mut().take_output(), "");
+
+        let records: Vec<&[u32]> = term
+            .backend()
+            .cells()
+            .chunks_exact(cell_format::RECORD_WORDS)
+            .filter(|r| r[cell_format::CODEPOINT] != u32::from(' '))
+            .collect();
+        assert_eq!(
+            records,
+            [
+                &[1, u32::from('A'), cell_format::COLOR_INDEXED | 0x2A, 0, 0][..],
+                &[
+                    3 | 1 << 16,
+                    u32::from('B'),
+                    0,
+                    cell_format::COLOR_ANSI | 1,
+                    0
+                ][..],
+            ]
+        );
+
+        // Unchanged cells are not resent next frame, and the cursor never writes anything.
+        term.backend_mut().clear_cells();
+        term.set_cursor_visible(true);
+        term.draw(|s| {
+            s.put((1, 0), 'A', Style::default().fg(Color::Indexed(0x2A)));
+            s.put(
+                (3, 1),
+                'B',
+                Style::default().bg(Color::Ansi(AnsiColor::Red)),
+            );
+        })
+        .unwrap();
+        assert!(term.backend().cells().is_empty());
+        assert_eq!(term.backend_mut().take_output(), "");
+    }
+
+    #[test]
+    fn cell_mode_flags_wide_glyphs_line_sizes_and_clears() {
+        use retroglyph_core::grid::LineSize;
+
+        let mut backend = TerminalWasm::new(6, 2);
+        backend.set_output_mode(OutputMode::Cells);
+        let mut term = Terminal::new(backend);
+        term.draw(|s| {
+            s.put((0, 1), '字', Style::default());
+            s.set_line_size(0, LineSize::DoubleHeightBottom);
+        })
+        .unwrap();
+        let cells = term.backend().cells();
+        let wide = cells
+            .chunks_exact(cell_format::RECORD_WORDS)
+            .find(|r| r[cell_format::CODEPOINT] == u32::from('字'))
+            .unwrap();
+        assert_eq!(wide[cell_format::FLAGS], cell_format::FLAG_WIDE);
+        assert!(
+            cells
+                .chunks_exact(cell_format::RECORD_WORDS)
+                .filter(|r| r[cell_format::POS] >> 16 == 0)
+                .all(|r| r[cell_format::FLAGS] & cell_format::FLAG_LINE_MASK == 3)
+        );
+        // The spacer right of the wide glyph is never sent.
+        assert!(
+            !cells
+                .chunks_exact(cell_format::RECORD_WORDS)
+                .any(|r| r[cell_format::POS] == 1 | 1 << 16)
+        );
+
+        Output::clear(term.backend_mut()).unwrap();
+        assert_eq!(
+            term.backend().cells(),
+            [0, 0, 0, 0, cell_format::FLAG_CLEAR]
+        );
+    }
+
+    #[test]
+    fn set_output_mode_discards_pending_output() {
+        let mut term = Terminal::new(TerminalWasm::new(4, 2));
+        term.draw(|s| s.put((0, 0), 'x', Style::default())).unwrap();
+        term.backend_mut().set_output_mode(OutputMode::Cells);
+        assert_eq!(term.backend().output_mode(), OutputMode::Cells);
+        assert_eq!(term.backend_mut().take_output(), "");
+
+        term.draw(|s| s.put((0, 0), 'y', Style::default())).unwrap();
+        assert!(!term.backend().cells().is_empty());
+        term.backend_mut().set_output_mode(OutputMode::Ansi);
+        assert!(term.backend().cells().is_empty());
+    }
+
     #[test]
     fn push_event_then_poll_roundtrips() {
         let mut backend = TerminalWasm::new(10, 3);
@@ -1311,6 +1884,23 @@ mod tests {
         assert!(decode_mouse_event(0, 0, mouse_actions::UP, 0xFF, 0).is_none());
     }
 
+    #[test]
+    fn sent_events_are_polled_after_already_pushed_ones() {
+        let mut backend = TerminalWasm::new(4, 2);
+        let sender = backend
+            .event_sender()
+            .expect("TerminalWasm supports senders");
+        backend.push_event(Event::Custom(1));
+        sender.send(Event::Custom(2)).unwrap();
+
+        assert_eq!(backend.poll_event(Duration::ZERO), Some(Event::Custom(1)));
+        assert_eq!(backend.poll_event(Duration::ZERO), Some(Event::Custom(2)));
+        assert_eq!(backend.poll_event(Duration::ZERO), None);
+
+        drop(backend);
+        assert!(sender.is_closed());
+    }
+
     #[test]
     fn push_event_caps_queue_and_drops_oldest_under_burst() {
         // A burst well past `EVENT_QUEUE_CAP`, of non-coalescing events (alternating key codes,
@@ -1337,6 +1927,22 @@ mod tests {
         assert_eq!(drained, EVENT_QUEUE_CAP);
     }
 
+    #[test]
+    fn the_clipboard_relays_copies_out_and_reported_text_in() {
+        let mut backend = TerminalWasm::new(10, 3);
+        assert!(backend.clipboard_text().is_err());
+
+        backend.report_clipboard("from the page".into());
+        assert_eq!(backend.clipboard_text().unwrap(), "from the page");
+        assert_eq!(backend.take_clipboard(), None, "a report is not a copy");
+
+        backend.set_clipboard_text("first").unwrap();
+        backend.set_clipboard_text("second").unwrap();
+        assert_eq!(backend.clipboard_text().unwrap(), "second");
+        assert_eq!(backend.take_clipboard().as_deref(), Some("second"));
+        assert_eq!(backend.take_clipboard(), None);
+    }
+
     #[test]
     fn push_event_coalesces_consecutive_moved_events() {
         use retroglyph_core::event::{MouseButton, MouseEvent, MouseEventKind};
@@ -17,16 +17,76 @@
 //! registry's lifecycle and its robustness fixes from #131/#132: unique handles, and safe,
 //! non-panicking no-ops for `resize`/`push_key`/`take_output` on a freed or never-issued handle.
 //! `push_paste` (#98) follows the exact same no-op-on-unknown-handle contract.
+//!
+//! The one exception is the cell output mode's wire format: the tests at the bottom draw through
+//! a `Terminal<TerminalWasm>` built here directly, register its backend with the Rust-only
+//! `wasm_terminal_insert`, and hand `wasm_terminal_cells_ptr`/`wasm_terminal_cells_len` to
+//! `js/cell-renderer.js`'s `decodeCells`, so Node reads the records out of this module's own wasm
+//! memory exactly as a browser driver would.
 #![cfg(target_arch = "wasm32")]
 
+use retroglyph_core::color::{AnsiColor, Color, Style};
+use retroglyph_core::grid::LineSize;
+use retroglyph_core::terminal::Terminal;
 use retroglyph_terminal_wasm::wasm::{
-    wasm_terminal_free, wasm_terminal_new, wasm_terminal_push_focus, wasm_terminal_push_key,
-    wasm_terminal_push_mouse, wasm_terminal_push_paste, wasm_terminal_resize,
-    wasm_terminal_take_output,
+    wasm_terminal_cells_len, wasm_terminal_cells_ptr, wasm_terminal_clear_cells,
+    wasm_terminal_free, wasm_terminal_insert, wasm_terminal_new, wasm_terminal_push_focus,
+    wasm_terminal_push_key, wasm_terminal_push_mouse, wasm_terminal_push_paste,
+    wasm_terminal_resize, wasm_terminal_set_output_mode, wasm_terminal_take_output,
+};
+use retroglyph_terminal_wasm::{
+    OutputMode, TerminalWasm, mouse_actions, mouse_buttons, output_modes,
 };
-use retroglyph_terminal_wasm::{mouse_actions, mouse_buttons};
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen_test::wasm_bindgen_test;
 
+#[wasm_bindgen(module = "/js/cell-renderer.js")]
+extern "C" {
+    /// One `{ x, y, ch, fg, bg, line, wide, clear }` object from `decodeCells`.
+    type DecodedCell;
+
+    #[wasm_bindgen(js_name = decodeCells)]
+    fn decode_cells(memory: &JsValue, ptr: *const u32, len: usize) -> Vec<DecodedCell>;
+
+    #[wasm_bindgen(method, getter)]
+    fn x(this: &DecodedCell) -> u16;
+    #[wasm_bindgen(method, getter)]
+    fn y(this: &DecodedCell) -> u16;
+    #[wasm_bindgen(method, getter)]
+    fn ch(this: &DecodedCell) -> String;
+    #[wasm_bindgen(method, getter)]
+    fn fg(this: &DecodedCell) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    fn bg(this: &DecodedCell) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    fn line(this: &DecodedCell) -> u8;
+    #[wasm_bindgen(method, getter)]
+    fn wide(this: &DecodedCell) -> bool;
+    #[wasm_bindgen(method, getter)]
+    fn clear(this: &DecodedCell) -> bool;
+}
+
+/// Decodes everything the terminal identified by `handle` has pending through the reference JS
+/// decoder, locating the records with the exported FFI the same way a browser driver does.
+fn decode_pending(handle: u32) -> Vec<DecodedCell> {
+    decode_cells(
+        &wasm_bindgen::memory(),
+        wasm_terminal_cells_ptr(handle),
+        wasm_terminal_cells_len(handle),
+    )
+}
+
+/// Moves `term`'s backend, and whatever records it has pending, into the FFI's handle registry.
+fn register(term: Terminal<TerminalWasm>) -> u32 {
+    let mut handle = 0;
+    let _ = term.map_backend(|backend| {
+        handle = wasm_terminal_insert(backend);
+        TerminalWasm::new(0, 0)
+    });
+    handle
+}
+
 #[wasm_bindgen_test]
 fn new_handles_are_unique() {
     let a = wasm_terminal_new(10, 3);
@@ -119,3 +179,77 @@ fn push_mouse_with_an_unknown_action_is_silently_dropped_not_a_panic() {
     wasm_terminal_push_mouse(handle, 0, 0, 0xFF, mouse_buttons::LEFT, 0);
     wasm_terminal_free(handle);
 }
+
+#[wasm_bindgen_test]
+fn cells_on_a_fresh_or_unknown_handle_are_empty() {
+    let handle = wasm_terminal_new(10, 3);
+    wasm_terminal_set_output_mode(handle, output_modes::CELLS);
+    assert_eq!(wasm_terminal_cells_len(handle), 0);
+    wasm_terminal_clear_cells(handle);
+    wasm_terminal_free(handle);
+
+    assert!(wasm_terminal_cells_ptr(handle).is_null());
+    assert_eq!(wasm_terminal_cells_len(handle), 0);
+    // An unknown mode is ignored rather than guessed at.
+    wasm_terminal_set_output_mode(handle, 0xFF);
+}
+
+#[wasm_bindgen_test]
+fn cell_records_decode_through_the_reference_js_renderer() {
+    let mut backend = TerminalWasm::new(6, 2);
+    backend.set_output_mode(OutputMode::Cells);
+    let mut term = Terminal::new(backend);
+    term.draw(|s| {
+        s.put(
+            (2, 0),
+            '@',
+            Style::default()
+                .fg(Color::rgb(255, 128, 0))
+                .bg(Color::Ansi(AnsiColor::Blue)),
+        );
+        s.put((0, 1), '字', Style::default().fg(Color::Indexed(196)));
+        s.set_line_size(1, LineSize::DoubleWidth);
+    })
+    .unwrap();
+    assert_eq!(
+        term.backend_mut().take_output(),
+        "",
+        "cell mode must not produce ANSI"
+    );
+
+    let handle = register(term);
+    let cells = decode_pending(handle);
+    let at = cells.iter().find(|c| c.ch() == "@").expect("the '@' cell");
+    assert_eq!((at.x(), at.y()), (2, 0));
+    assert_eq!(at.fg().as_deref(), Some("#ff8000"));
+    assert_eq!(at.bg().as_deref(), Some("#0000ee"));
+    assert_eq!((at.line(), at.wide(), at.clear()), (0, false, false));
+
+    let wide = cells
+        .iter()
+        .find(|c| c.ch() == "字")
+        .expect("the wide cell");
+    assert_eq!((wide.x(), wide.y()), (0, 1));
+    assert_eq!(wide.fg().as_deref(), Some("#ff0000"));
+    assert_eq!(wide.bg(), None);
+    assert_eq!((wide.line(), wide.wide()), (1, true));
+
+    wasm_terminal_clear_cells(handle);
+    assert!(decode_pending(handle).is_empty());
+    wasm_terminal_free(handle);
+}
+
+#[wasm_bindgen_test]
+fn clearing_in_cell_mode_sends_a_clear_record() {
+    use retroglyph_core::backend::Output as _;
+
+    let mut backend = TerminalWasm::new(4, 2);
+    backend.set_output_mode(OutputMode::Cells);
+    backend.clear().unwrap();
+
+    let handle = wasm_terminal_insert(backend);
+    let decoded = decode_pending(handle);
+    assert_eq!(decoded.len(), 1);
+    assert!(decoded[0].clear());
+    wasm_terminal_free(handle);
+}
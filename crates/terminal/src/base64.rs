@@ -0,0 +1,98 @@
+//! Standard (RFC 4648, padded) base64, the encoding both the kitty graphics protocol and OSC 52
+//! carry their payloads in, and that `retroglyph-websocket`'s handshake answers with.
+//!
+//! Public for the sibling backend crates only, hence hidden from the docs.
+
+/// Appends the base64 encoding of `bytes` to `out`.
+pub fn encode(bytes: &[u8], out: &mut Vec<u8>) {
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        let sextet = |shift: u32| ALPHABET[(n >> shift & 0x3f) as usize];
+        out.push(sextet(18));
+        out.push(sextet(12));
+        out.push(if chunk.len() > 1 { sextet(6) } else { b'=' });
+        out.push(if chunk.len() > 2 { sextet(0) } else { b'=' });
+    }
+}
+
+/// Decodes `text`, or `None` if it is not valid base64. Padding is optional, and ASCII
+/// whitespace (which some terminals wrap long replies with) is skipped.
+#[must_use]
+pub fn decode(text: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let mut bits = 0_u32;
+    let mut count = 0_u32;
+    let mut padding = false;
+    for &byte in text.iter().filter(|b| !b.is_ascii_whitespace()) {
+        if byte == b'=' {
+            padding = true;
+            continue;
+        }
+        if padding {
+            return None;
+        }
+        let value = ALPHABET.iter().position(|&c| c == byte)?;
+        bits = bits << 6 | u32::try_from(value).ok()?;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            out.push(u8::try_from(bits >> count & 0xff).ok()?);
+        }
+    }
+    // A lone leftover sextet can't hold a byte: the input was cut short.
+    (count < 6).then_some(out)
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_4648: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn encode_matches_the_rfc_4648_test_vectors() {
+        for (input, expected) in RFC_4648 {
+            let mut out = Vec::new();
+            encode(input.as_bytes(), &mut out);
+            assert_eq!(out, expected.as_bytes(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn decode_inverts_encode_with_or_without_padding() {
+        for (expected, input) in RFC_4648 {
+            assert_eq!(
+                decode(input.as_bytes()).as_deref(),
+                Some(expected.as_bytes())
+            );
+            let unpadded = input.trim_end_matches('=');
+            assert_eq!(
+                decode(unpadded.as_bytes()).as_deref(),
+                Some(expected.as_bytes())
+            );
+        }
+        assert_eq!(decode(b"Zm9v\nYmFy").as_deref(), Some(&b"foobar"[..]));
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert_eq!(decode(b"Zm9v!"), None);
+        assert_eq!(decode(b"Zg==Zg"), None);
+        assert_eq!(decode(b"Z"), None);
+    }
+}
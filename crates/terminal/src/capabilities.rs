@@ -0,0 +1,384 @@
+//! What a terminal says it supports, read back from its replies to a batch of queries.
+//!
+//! [`QUERY`] asks every question at once; [`TerminalCapabilities::from_reply`] parses whatever
+//! came back. This module only deals in bytes, like the rest of the crate: writing the query to
+//! the terminal, reading the reply with a timeout, and acting on the answer belong to a backend
+//! with a real TTY (see `retroglyph-crossterm`'s `CrosstermOptions::probe_capabilities`).
+//!
+//! Every field is optional in spirit: a terminal that ignores a query simply leaves its field at
+//! the "unknown" value, and nothing here guesses an answer for it.
+
+use crate::ColorSupport;
+use crate::graphics::GraphicsProtocol;
+use retroglyph_core::event::SystemTheme;
+
+/// Every capability query, in one write.
+///
+/// In order: the kitty graphics query (see
+/// [`graphics::PROBE_QUERY`](crate::graphics::PROBE_QUERY)), `XTVERSION`, secondary device
+/// attributes (DA2), `DECRQM` for synchronized output (mode 2026) and SGR-Pixels mouse
+/// reporting (mode 1016), the kitty keyboard protocol flags, the OSC 10/11 foreground and
+/// background colors, the cell size in pixels (`CSI 16 t`), a `DECRQSS` round trip of a truecolor
+/// SGR (which tells a terminal that keeps 24-bit colors apart from one that rounds them to its
+/// palette), and finally primary device attributes (DA1).
+///
+/// DA1 goes last on purpose: practically every terminal answers it, and terminals answer in
+/// order, so its reply marks the end of everything the terminal is going to say. The trailing
+/// SGR reset undoes the color the `DECRQSS` round trip set.
+pub const QUERY: &str = concat!(
+    "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\",
+    "\x1b[>0q",
+    "\x1b[>c",
+    "\x1b[?2026$p",
+    "\x1b[?1016$p",
+    "\x1b[?u",
+    "\x1b]10;?\x1b\\",
+    "\x1b]11;?\x1b\\",
+    "\x1b[16t",
+    "\x1b[48;2;1;2;3m\x1bP$qm\x1b\\\x1b[0m",
+    "\x1b[c",
+);
+
+/// The terminal's answers to [`QUERY`].
+///
+/// Build one with [`from_reply`](Self::from_reply). [`Default`] is a terminal that answered
+/// nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TerminalCapabilities {
+    /// Whether the primary device attributes reply arrived, which means the terminal has
+    /// answered everything it is going to. `false` for a partial reply cut off by a timeout.
+    pub answered: bool,
+    /// Primary device attributes (DA1) parameters, e.g. `[62, 4, 22]`: the terminal class,
+    /// then feature codes (`4` is Sixel, `22` is ANSI color).
+    pub device_attributes: Vec<u16>,
+    /// Secondary device attributes (DA2): terminal type, firmware version, and ROM cartridge
+    /// number, whatever a given terminal chooses to put in them.
+    pub secondary_attributes: Option<[u16; 3]>,
+    /// The terminal's name and version from `XTVERSION`, e.g. `kitty(0.35.2)` or
+    /// `WezTerm 20240203`.
+    pub version: Option<String>,
+    /// Whether synchronized output (mode 2026) is supported, from `DECRQM`; `None` when the
+    /// terminal did not answer the request at all.
+    pub synchronized_output: Option<bool>,
+    /// Whether SGR-Pixels mouse reporting (mode 1016), which reports mouse positions in pixels
+    /// rather than cells, is supported, from `DECRQM`; `None` when the terminal did not answer.
+    pub pixel_mouse: Option<bool>,
+    /// Whether the kitty keyboard protocol is supported.
+    pub kitty_keyboard: bool,
+    /// The image protocol sprites can be drawn with, kitty graphics preferred over Sixel.
+    pub graphics: Option<GraphicsProtocol>,
+    /// Whether 24-bit SGR colors survive a `DECRQSS` round trip: `Some(false)` when the
+    /// terminal reported the test color back as something else (a palette index); `None` when
+    /// it does not implement `DECRQSS`.
+    pub direct_color: Option<bool>,
+    /// The default foreground color, from OSC 10.
+    pub foreground: Option<(u8, u8, u8)>,
+    /// The default background color, from OSC 11.
+    pub background: Option<(u8, u8, u8)>,
+    /// The size of one cell in pixels, `(width, height)`, from `CSI 16 t`.
+    pub cell_pixels: Option<(u16, u16)>,
+}
+
+impl TerminalCapabilities {
+    /// Parses everything a terminal has sent back so far in reply to [`QUERY`].
+    ///
+    /// Bytes that belong to no reply (keys the user pressed while the query was in flight) are
+    /// skipped. Call again with the longer buffer as more bytes arrive; once
+    /// [`answered`](Self::answered) is `true`, nothing more is coming.
+    ///
+    /// ```
+    /// use retroglyph_terminal::capabilities::TerminalCapabilities;
+    ///
+    /// let caps = TerminalCapabilities::from_reply(b"\x1b[?2026;2$y\x1b]11;rgb:0000/0000/0000\x1b\\\x1b[?62;22c");
+    /// assert!(caps.answered);
+    /// assert_eq!(caps.synchronized_output, Some(true));
+    /// assert_eq!(caps.background, Some((0, 0, 0)));
+    /// ```
+    #[must_use]
+    pub fn from_reply(reply: &[u8]) -> Self {
+        let mut caps = Self::default();
+        let mut kitty_graphics = false;
+        let mut rest = reply;
+        while let Some(start) = rest.iter().position(|&b| b == 0x1b) {
+            rest = &rest[start..];
+            let Some(&kind) = rest.get(1) else {
+                break;
+            };
+            let body = &rest[2..];
+            let consumed = match kind {
+                b'[' => caps.csi(body),
+                b']' => string_end(body, true).map(|(end, next)| {
+                    caps.osc(&body[..end]);
+                    next
+                }),
+                b'P' => string_end(body, false).map(|(end, next)| {
+                    caps.dcs(&body[..end]);
+                    next
+                }),
+                b'_' => string_end(body, false).map(|(end, next)| {
+                    kitty_graphics |= is_kitty_graphics_ok(&body[..end]);
+                    next
+                }),
+                _ => Some(0),
+            };
+            // An unterminated reply is still arriving: everything after it is too.
+            let Some(consumed) = consumed else {
+                break;
+            };
+            if caps.answered {
+                break;
+            }
+            rest = &body[consumed..];
+        }
+        caps.graphics = if kitty_graphics {
+            Some(GraphicsProtocol::Kitty)
+        } else if caps.device_attributes.contains(&4) {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        };
+        caps
+    }
+
+    /// The [`ColorSupport`] the replies pin down, or `None` when they don't say.
+    ///
+    /// Only the `DECRQSS` round trip counts: a terminal that reported the 24-bit test color back
+    /// intact gets [`ColorSupport::Truecolor`], and one that reported it back as anything else
+    /// gets [`ColorSupport::Indexed256`]. Terminal names and device attribute classes are not
+    /// read as evidence either way; too many terminals that render 24-bit color fine identify as
+    /// something that didn't.
+    #[must_use]
+    pub const fn color_support(&self) -> Option<ColorSupport> {
+        match self.direct_color {
+            Some(true) => Some(ColorSupport::Truecolor),
+            Some(false) => Some(ColorSupport::Indexed256),
+            None => None,
+        }
+    }
+
+    /// Whether the default background ([`background`](Self::background)) is dark or light, by
+    /// its relative luminance; `None` when the terminal did not report it.
+    #[must_use]
+    pub fn theme(&self) -> Option<SystemTheme> {
+        let (r, g, b) = self.background?;
+        let luminance = 0.0722_f32.mul_add(
+            f32::from(b),
+            0.2126_f32.mul_add(f32::from(r), 0.7152 * f32::from(g)),
+        );
+        Some(if luminance < 127.5 {
+            SystemTheme::Dark
+        } else {
+            SystemTheme::Light
+        })
+    }
+
+    /// Handles one CSI reply whose body (after `ESC [`) starts `body`, returning how many bytes
+    /// of `body` it spans, or `None` if it is cut off.
+    fn csi(&mut self, body: &[u8]) -> Option<usize> {
+        let end = body.iter().position(|b| (0x40..=0x7e).contains(b))?;
+        let (head, last) = (&body[..end], body[end]);
+        let (prefix, params) = match head.first() {
+            Some(&prefix @ (b'?' | b'>')) => (Some(prefix), &head[1..]),
+            _ => (None, head),
+        };
+        match (prefix, last) {
+            (Some(b'?'), b'c') => {
+                self.device_attributes = numbers(params, b';').collect();
+                self.answered = true;
+            }
+            (Some(b'>'), b'c') => {
+                let mut values = numbers(params, b';');
+                self.secondary_attributes = Some([
+                    values.next().unwrap_or(0),
+                    values.next().unwrap_or(0),
+                    values.next().unwrap_or(0),
+                ]);
+            }
+            (Some(b'?'), b'y') => {
+                if let Some(params) = params.strip_suffix(b"$") {
+                    let mut values = numbers(params, b';');
+                    let mode = values.next();
+                    // 1 and 2 are "set"/"reset", 3 "permanently set"; 0 is "not recognized" and
+                    // 4 "permanently reset".
+                    let supported = Some(matches!(values.next(), Some(1..=3)));
+                    match mode {
+                        Some(2026) => self.synchronized_output = supported,
+                        Some(1016) => self.pixel_mouse = supported,
+                        _ => {}
+                    }
+                }
+            }
+            (Some(b'?'), b'u') => self.kitty_keyboard = true,
+            (None, b't') => {
+                // `CSI 6 ; height ; width t`; a zero size means the terminal doesn't know.
+                let mut values = numbers(params, b';');
+                if values.next() == Some(6) {
+                    let (height, width) = (values.next(), values.next());
+                    self.cell_pixels = width.zip(height).filter(|&(w, h)| w > 0 && h > 0);
+                }
+            }
+            _ => {}
+        }
+        Some(end + 1)
+    }
+
+    /// Handles one OSC reply's payload.
+    fn osc(&mut self, payload: &[u8]) {
+        let (code, value) = split_once(payload, b';');
+        match code {
+            b"10" => self.foreground = parse_rgb(value),
+            b"11" => self.background = parse_rgb(value),
+            _ => {}
+        }
+    }
+
+    /// Handles one DCS reply's payload.
+    fn dcs(&mut self, payload: &[u8]) {
+        if let Some(version) = payload.strip_prefix(b">|") {
+            self.version = Some(String::from_utf8_lossy(version).into_owned());
+        } else if let Some(sgr) = payload.strip_prefix(b"1$r") {
+            // The SGR comes back in either separator style (`48;2;1;2;3` or `48:2::1:2:3`).
+            let values: Vec<u16> = sgr
+                .strip_suffix(b"m")
+                .unwrap_or(sgr)
+                .split(|&b| b == b';' || b == b':')
+                .filter(|value| !value.is_empty())
+                .filter_map(number)
+                .collect();
+            self.direct_color = Some(values.windows(5).any(|w| w == [48, 2, 1, 2, 3]));
+        }
+        // `0$r` means the terminal understood DECRQSS but could not report the SGR, which says
+        // nothing either way.
+    }
+}
+
+/// Whether an APC payload is the kitty graphics protocol's `OK` to [`QUERY`]'s image query.
+fn is_kitty_graphics_ok(payload: &[u8]) -> bool {
+    let Some(payload) = payload.strip_prefix(b"G") else {
+        return false;
+    };
+    let (keys, message) = split_once(payload, b';');
+    message == b"OK" && keys.split(|&b| b == b',').any(|key| key == b"i=31")
+}
+
+/// Finds the end of a control string body: the index its terminator starts at, and the index
+/// just past the terminator. ST (`ESC \`) always ends one; BEL also ends an OSC.
+fn string_end(body: &[u8], bel: bool) -> Option<(usize, usize)> {
+    body.iter().enumerate().find_map(|(i, &b)| match b {
+        0x07 if bel => Some((i, i + 1)),
+        0x1b if body.get(i + 1) == Some(&b'\\') => Some((i, i + 2)),
+        _ => None,
+    })
+}
+
+/// Parses an X11 color spec, `rgb:R/G/B` with 1 to 4 hex digits per channel.
+fn parse_rgb(spec: &[u8]) -> Option<(u8, u8, u8)> {
+    let spec = core::str::from_utf8(spec.strip_prefix(b"rgb:")?).ok()?;
+    let mut channels = spec.split('/').map(|channel| {
+        let digits = u32::try_from(channel.len())
+            .ok()
+            .filter(|n| (1..=4).contains(n))?;
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        // Scale an n-digit value to 8 bits: `ffff` and `f` both mean full intensity.
+        let max = (1 << (4 * digits)) - 1;
+        u8::try_from((value * 255 + max / 2) / max).ok()
+    });
+    let rgb = (channels.next()??, channels.next()??, channels.next()??);
+    channels.next().is_none().then_some(rgb)
+}
+
+fn numbers(params: &[u8], separator: u8) -> impl Iterator<Item = u16> + '_ {
+    params
+        .split(move |&b| b == separator)
+        .filter(|value| !value.is_empty())
+        .filter_map(number)
+}
+
+fn number(digits: &[u8]) -> Option<u16> {
+    core::str::from_utf8(digits).ok()?.parse().ok()
+}
+
+fn split_once(bytes: &[u8], separator: u8) -> (&[u8], &[u8]) {
+    bytes
+        .iter()
+        .position(|&b| b == separator)
+        .map_or((bytes, &[][..]), |at| (&bytes[..at], &bytes[at + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Roughly what kitty answers [`QUERY`] with.
+    const KITTY: &[u8] = b"\x1b_Gi=31;OK\x1b\\\x1bP>|kitty(0.35.2)\x1b\\\x1b[>1;4000;35c\
+        \x1b[?2026;2$y\x1b[?1016;2$y\x1b[?0u\x1b]10;rgb:dddd/dddd/dddd\x1b\\\
+        \x1b]11;rgb:0000/0000/0000\x1b\\\x1b[6;20;10t\x1bP1$r0;48:2:1:2:3m\x1b\\\x1b[?62;c";
+
+    #[test]
+    fn parses_every_reply_to_the_query() {
+        let caps = TerminalCapabilities::from_reply(KITTY);
+        assert_eq!(
+            caps,
+            TerminalCapabilities {
+                answered: true,
+                device_attributes: vec![62],
+                secondary_attributes: Some([1, 4000, 35]),
+                version: Some("kitty(0.35.2)".into()),
+                synchronized_output: Some(true),
+                pixel_mouse: Some(true),
+                kitty_keyboard: true,
+                graphics: Some(GraphicsProtocol::Kitty),
+                direct_color: Some(true),
+                foreground: Some((0xdd, 0xdd, 0xdd)),
+                background: Some((0, 0, 0)),
+                cell_pixels: Some((10, 20)),
+            }
+        );
+        assert_eq!(caps.color_support(), Some(ColorSupport::Truecolor));
+        assert_eq!(caps.theme(), Some(SystemTheme::Dark));
+    }
+
+    #[test]
+    fn a_reply_cut_off_mid_sequence_is_not_answered_yet() {
+        for end in 0..KITTY.len() - 1 {
+            let caps = TerminalCapabilities::from_reply(&KITTY[..end]);
+            assert!(!caps.answered, "answered after {end} bytes");
+        }
+    }
+
+    #[test]
+    fn a_terminal_answering_only_da1_has_no_capabilities() {
+        let caps = TerminalCapabilities::from_reply(b"\x1b[?6c");
+        assert!(caps.answered);
+        assert_eq!(caps.synchronized_output, None);
+        assert_eq!(caps.color_support(), None);
+        assert_eq!(caps.theme(), None);
+        assert_eq!(caps.graphics, None);
+    }
+
+    #[test]
+    fn unsupported_synchronized_output_and_rounded_colors_are_reported_as_such() {
+        let caps = TerminalCapabilities::from_reply(
+            b"\x1b[?2026;0$y\x1bP1$r0;48;5;16m\x1b\\\x1b[?64;4;22c",
+        );
+        assert_eq!(caps.synchronized_output, Some(false));
+        assert_eq!(caps.pixel_mouse, None);
+        assert_eq!(caps.color_support(), Some(ColorSupport::Indexed256));
+        assert_eq!(caps.graphics, Some(GraphicsProtocol::Sixel));
+    }
+
+    #[test]
+    fn osc_colors_accept_bel_and_short_channels() {
+        let caps = TerminalCapabilities::from_reply(b"\x1b]11;rgb:f/f/f\x07\x1b[?1c");
+        assert_eq!(caps.background, Some((255, 255, 255)));
+        assert_eq!(caps.theme(), Some(SystemTheme::Light));
+    }
+
+    #[test]
+    fn keys_typed_during_the_query_are_skipped() {
+        let caps = TerminalCapabilities::from_reply(b"q\x1b[A\x1bOP\x1b[?2026;1$y\x1b[?62c");
+        assert!(caps.answered);
+        assert_eq!(caps.synchronized_output, Some(true));
+    }
+}
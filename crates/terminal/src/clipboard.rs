@@ -0,0 +1,164 @@
+//! Clipboard access through OSC 52, the escape sequence that lets a program set (and, where the
+//! terminal allows it, read) the clipboard of whatever machine the terminal emulator runs on.
+//!
+//! Because the terminal does the work, this reaches the user's clipboard from anywhere the
+//! terminal's byte stream goes: over SSH, inside a container, through `tmux` (with its
+//! `set-clipboard` option on). Setting is widely supported; reading is not, since it would let
+//! any program that writes to the terminal see what was copied, so most terminals ignore the
+//! query or ask the user first.
+//!
+//! Like the rest of this crate, only bytes: [`write_osc52`] encodes a copy,
+//! [`OSC52_QUERY`]/[`parse_osc52_reply`] a read, and sending them (and reading the reply with a
+//! timeout) belongs to a backend with a real TTY.
+
+use crate::base64;
+use std::io::{self, Write};
+
+/// Sets the clipboard to `text`.
+///
+/// Targets the `c` (clipboard) selection, the one a desktop's copy and paste use.
+///
+/// ```
+/// let mut out = Vec::new();
+/// retroglyph_terminal::clipboard::write_osc52(&mut out, "hi")?;
+/// assert_eq!(out, b"\x1b]52;c;aGk=\x1b\\");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_osc52<W: Write>(out: &mut W, text: &str) -> io::Result<()> {
+    let mut payload = b"\x1b]52;c;".to_vec();
+    base64::encode(text.as_bytes(), &mut payload);
+    payload.extend_from_slice(b"\x1b\\");
+    out.write_all(&payload)
+}
+
+/// The query a backend writes to read the clipboard.
+///
+/// The OSC 52 read request, then primary device attributes (DA1): a terminal that refuses to
+/// share the clipboard says nothing at all, and the DA1 reply that practically every terminal
+/// sends marks the end of its answer, so a silent refusal is noticed without waiting out a
+/// timeout. Feed whatever comes back to [`parse_osc52_reply`].
+pub const OSC52_QUERY: &str = "\x1b]52;c;?\x1b\\\x1b[c";
+
+/// What a terminal's reply to [`OSC52_QUERY`] says so far; see [`parse_osc52_reply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Osc52Reply {
+    /// Neither the clipboard nor the device attributes reply has arrived yet: keep reading.
+    Pending,
+    /// The terminal has answered. `None` means it did not share the clipboard, or shared
+    /// something that isn't UTF-8 text.
+    Answered(Option<String>),
+}
+
+/// Parses the bytes a terminal has sent back so far in reply to [`OSC52_QUERY`].
+///
+/// Bytes that are neither reply (keys the user pressed while the query was in flight) are
+/// skipped. Both string terminators, ST and BEL, are accepted.
+///
+/// ```
+/// use retroglyph_terminal::clipboard::{Osc52Reply, parse_osc52_reply};
+///
+/// assert_eq!(
+///     parse_osc52_reply(b"\x1b]52;c;aGk=\x07"),
+///     Osc52Reply::Answered(Some("hi".into()))
+/// );
+/// assert_eq!(parse_osc52_reply(b"\x1b[?62;22c"), Osc52Reply::Answered(None));
+/// assert_eq!(parse_osc52_reply(b"\x1b]52;c;aG"), Osc52Reply::Pending);
+/// ```
+#[must_use]
+pub fn parse_osc52_reply(reply: &[u8]) -> Osc52Reply {
+    let mut rest = reply;
+    while let Some(start) = rest.iter().position(|&b| b == 0x1b) {
+        rest = &rest[start..];
+        if let Some(body) = rest.strip_prefix(b"\x1b]52;") {
+            let Some((end, next)) = osc_end(body) else {
+                return Osc52Reply::Pending;
+            };
+            // `c;<base64>`; the selection letter echoes the one asked for.
+            let payload = body[..end]
+                .iter()
+                .position(|&b| b == b';')
+                .map_or(&[][..], |at| &body[at + 1..end]);
+            if payload != b"?" {
+                return Osc52Reply::Answered(
+                    base64::decode(payload).and_then(|bytes| String::from_utf8(bytes).ok()),
+                );
+            }
+            rest = &body[next..];
+        } else if let Some(body) = rest.strip_prefix(b"\x1b[?") {
+            let Some(end) = body
+                .iter()
+                .position(|b| !(b.is_ascii_digit() || *b == b';'))
+            else {
+                return Osc52Reply::Pending;
+            };
+            if body[end] == b'c' {
+                return Osc52Reply::Answered(None);
+            }
+            rest = &body[end..];
+        } else {
+            rest = &rest[1..];
+        }
+    }
+    Osc52Reply::Pending
+}
+
+/// Finds the end of an OSC body: the index its terminator (BEL or ST) starts at, and the index
+/// just past it.
+fn osc_end(body: &[u8]) -> Option<(usize, usize)> {
+    body.iter().enumerate().find_map(|(i, &b)| match b {
+        0x07 => Some((i, i + 1)),
+        0x1b if body.get(i + 1) == Some(&b'\\') => Some((i, i + 2)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_osc52_round_trips_through_the_reply_parser() {
+        let mut out = Vec::new();
+        write_osc52(&mut out, "seed 1234 ✓").unwrap();
+        assert_eq!(
+            parse_osc52_reply(&out),
+            Osc52Reply::Answered(Some("seed 1234 ✓".into()))
+        );
+    }
+
+    #[test]
+    fn the_clipboard_reply_wins_over_the_device_attributes_after_it() {
+        assert_eq!(
+            parse_osc52_reply(b"x\x1b]52;c;Zm9v\x1b\\\x1b[?62c"),
+            Osc52Reply::Answered(Some("foo".into()))
+        );
+    }
+
+    #[test]
+    fn a_partial_reply_is_pending() {
+        let reply = b"\x1b]52;c;Zm9v\x1b\\";
+        for end in 0..reply.len() - 1 {
+            assert_eq!(
+                parse_osc52_reply(&reply[..end]),
+                Osc52Reply::Pending,
+                "{end}"
+            );
+        }
+    }
+
+    #[test]
+    fn an_empty_or_undecodable_clipboard_is_answered_with_none() {
+        assert_eq!(
+            parse_osc52_reply(b"\x1b]52;c;!!\x07"),
+            Osc52Reply::Answered(None)
+        );
+        assert_eq!(
+            parse_osc52_reply(b"\x1b]52;c;\x07"),
+            Osc52Reply::Answered(Some(String::new()))
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! Inline image output: the kitty graphics protocol and Sixel.
+//!
+//! Encoders only, like the rest of this crate: every function here writes escape sequences to an
+//! [`std::io::Write`] sink and leaves deciding *which* cells get an image, and remembering what
+//! is already on screen, to the backend. Pixels are always straight-alpha RGBA8, row-major, the
+//! layout `retroglyph-window`'s decoded tileset sprites already use.
+//!
+//! The two protocols behave differently enough that a backend has to treat them differently:
+//!
+//! - **Kitty** images are uploaded once under a numeric id ([`write_kitty_transmit`]) and then
+//!   placed as often as needed by that id ([`write_kitty_place`]). A placement floats above the
+//!   text layer and survives text being redrawn under it, so it only has to be touched when the
+//!   cell's image actually changes ([`write_kitty_delete_placement`]). The terminal scales the
+//!   image to the cell box it is placed in, and keeps every upload until it is deleted
+//!   ([`write_kitty_delete_image`]).
+//! - **Sixel** images are pixels painted straight into the cell grid ([`write_sixel`]): nothing
+//!   is uploaded or reused, text drawn into a cell erases the image there, and the terminal draws
+//!   them at their own pixel size, so a backend scales them to the cell first
+//!   ([`scale_nearest`]).
+//!
+//! [`PROBE_QUERY`] and [`parse_probe_reply`] find out which of the two (if either) a terminal
+//! speaks. Neither protocol has a reliable environment-variable tell, so asking is the only
+//! option.
+
+use crate::base64;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+/// An inline image protocol a terminal can display sprites through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GraphicsProtocol {
+    /// The kitty graphics protocol (kitty, `WezTerm`, Ghostty, Konsole).
+    Kitty,
+    /// DEC Sixel graphics (xterm with `-ti vt340`, foot, mlterm, `WezTerm`, Windows Terminal).
+    Sixel,
+}
+
+/// The query a backend writes to find out which [`GraphicsProtocol`] the terminal supports.
+///
+/// Two requests back to back: a kitty graphics query (a 1x1 image transmitted with `a=q`, which
+/// a kitty-protocol terminal answers with `OK` without storing anything), then primary device
+/// attributes (DA1), which practically every terminal answers, and whose attribute `4` means
+/// Sixel. Terminals answer in order, so the DA1 reply marks the end of the answer: a kitty reply
+/// either arrived before it or is not coming. Feed whatever comes back to [`parse_probe_reply`].
+pub const PROBE_QUERY: &str = "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c";
+
+/// What a terminal's reply to [`PROBE_QUERY`] says so far; see [`parse_probe_reply`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeReply {
+    /// The device attributes reply has not arrived yet: keep reading.
+    Pending,
+    /// The terminal has answered. `None` means it supports neither protocol, and sprites should
+    /// fall back to their glyphs.
+    Answered(Option<GraphicsProtocol>),
+}
+
+/// Parses the bytes a terminal has sent back so far in reply to [`PROBE_QUERY`].
+///
+/// Kitty wins when the terminal speaks both, since its placements can be reused and scaled by
+/// the terminal. Bytes that are neither reply (keys the user pressed while the query was in
+/// flight) are skipped.
+///
+/// ```
+/// use retroglyph_terminal::graphics::{GraphicsProtocol, ProbeReply, parse_probe_reply};
+///
+/// assert_eq!(parse_probe_reply(b"\x1b[?62;4;22c"), ProbeReply::Answered(Some(GraphicsProtocol::Sixel)));
+/// assert_eq!(parse_probe_reply(b"\x1b_Gi=31;OK\x1b\\"), ProbeReply::Pending);
+/// ```
+#[must_use]
+pub fn parse_probe_reply(reply: &[u8]) -> ProbeReply {
+    let mut kitty = false;
+    let mut rest = reply;
+    while let Some(start) = rest.iter().position(|&b| b == 0x1b) {
+        rest = &rest[start..];
+        if let Some(body) = rest.strip_prefix(b"\x1b_G") {
+            let Some(end) = find(body, b"\x1b\\") else {
+                return ProbeReply::Pending;
+            };
+            let (keys, message) = split_once(&body[..end], b';');
+            kitty |= message == b"OK" && keys.split(|&b| b == b',').any(|key| key == b"i=31");
+            rest = &body[end + 2..];
+        } else if let Some(body) = rest.strip_prefix(b"\x1b[?") {
+            let Some(end) = body
+                .iter()
+                .position(|b| !(b.is_ascii_digit() || *b == b';'))
+            else {
+                return ProbeReply::Pending;
+            };
+            if body[end] == b'c' {
+                let sixel = body[..end].split(|&b| b == b';').any(|param| param == b"4");
+                return ProbeReply::Answered(if kitty {
+                    Some(GraphicsProtocol::Kitty)
+                } else if sixel {
+                    Some(GraphicsProtocol::Sixel)
+                } else {
+                    None
+                });
+            }
+            rest = &body[end..];
+        } else {
+            rest = &rest[1..];
+        }
+    }
+    ProbeReply::Pending
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn split_once(bytes: &[u8], separator: u8) -> (&[u8], &[u8]) {
+    bytes
+        .iter()
+        .position(|&b| b == separator)
+        .map_or((bytes, &[][..]), |at| (&bytes[..at], &bytes[at + 1..]))
+}
+
+/// Largest base64 payload the kitty protocol accepts in one escape sequence.
+const KITTY_CHUNK: usize = 4096;
+
+/// Uploads `rgba` (`width` x `height` pixels) to the terminal under image `id`, without
+/// displaying it; see [`write_kitty_place`].
+///
+/// The payload is split into the protocol's 4096-byte chunks. Replies are suppressed (`q=2`), so
+/// nothing comes back on the input stream for the backend's event parser to trip over.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_kitty_transmit<W: Write>(
+    out: &mut W,
+    id: u32,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> io::Result<()> {
+    let mut payload = Vec::with_capacity(rgba.len().div_ceil(3) * 4);
+    base64::encode(rgba, &mut payload);
+    let mut chunks = payload.chunks(KITTY_CHUNK).peekable();
+    write!(out, "\x1b_Ga=t,f=32,s={width},v={height},i={id},q=2")?;
+    if chunks.peek().is_none() {
+        return write!(out, "\x1b\\");
+    }
+    let mut first = true;
+    while let Some(chunk) = chunks.next() {
+        let more = u8::from(chunks.peek().is_some());
+        if first {
+            write!(out, ",m={more};")?;
+            first = false;
+        } else {
+            write!(out, "\x1b_Gm={more};")?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+    Ok(())
+}
+
+/// Displays uploaded image `id` at the cursor, scaled to fill `cols` x `rows` cells.
+///
+/// `placement` names this placement within the image, so the same image can be shown in any
+/// number of cells at once and each removed on its own with [`write_kitty_delete_placement`].
+/// Placing an image with a `placement` it already has moves that placement instead of adding a
+/// second one. The cursor is left where it was (`C=1`).
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_kitty_place<W: Write>(
+    out: &mut W,
+    id: u32,
+    placement: u32,
+    cols: u16,
+    rows: u16,
+) -> io::Result<()> {
+    write!(
+        out,
+        "\x1b_Ga=p,i={id},p={placement},c={cols},r={rows},C=1,q=2\x1b\\"
+    )
+}
+
+/// Removes one placement of image `id`, keeping the uploaded image for reuse.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_kitty_delete_placement<W: Write>(
+    out: &mut W,
+    id: u32,
+    placement: u32,
+) -> io::Result<()> {
+    write!(out, "\x1b_Ga=d,d=i,i={id},p={placement},q=2\x1b\\")
+}
+
+/// Deletes image `id` along with its placements, freeing the terminal's copy of its pixels.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_kitty_delete_image<W: Write>(out: &mut W, id: u32) -> io::Result<()> {
+    write!(out, "\x1b_Ga=d,d=I,i={id},q=2\x1b\\")
+}
+
+/// Removes every placement on screen. With `free_images`, the uploaded images are deleted too,
+/// and every id has to be transmitted again before it can be placed.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_kitty_delete_all<W: Write>(out: &mut W, free_images: bool) -> io::Result<()> {
+    let what = if free_images { 'A' } else { 'a' };
+    write!(out, "\x1b_Ga=d,d={what},q=2\x1b\\")
+}
+
+/// A straight RGB triple, one byte per channel.
+type Rgb = (u8, u8, u8);
+
+/// Most colors one Sixel image registers; more than this are quantized down.
+const SIXEL_COLORS: usize = 256;
+
+/// Paints `rgba` (`width` x `height` pixels) as a Sixel image with its top-left corner at the
+/// cursor.
+///
+/// Pixels with alpha below 128 are left untouched (transparent), so the cell's background shows
+/// through them; Sixel has no partial transparency. An image with more than 256 distinct opaque
+/// colors is quantized to 3-3-2 bit RGB.
+///
+/// Where the cursor ends up afterwards is terminal-specific: a backend should reposition it
+/// explicitly before writing anything else.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_sixel<W: Write>(out: &mut W, width: u32, height: u32, rgba: &[u8]) -> io::Result<()> {
+    let (width, height) = (width as usize, height as usize);
+    let pixels = rgba.len() / 4;
+    let (palette, indices) = sixel_palette(&rgba[..pixels.min(width * height) * 4]);
+
+    // `P2 = 1`: pixels no color register sets stay as they were, rather than painted with
+    // color 0, which is what makes the transparent ones transparent.
+    write!(out, "\x1bP0;1;0q\"1;1;{width};{height}")?;
+    for (register, &(r, g, b)) in palette.iter().enumerate() {
+        let percent = |channel: u8| (u32::from(channel) * 100 + 127) / 255;
+        write!(
+            out,
+            "#{register};2;{};{};{}",
+            percent(r),
+            percent(g),
+            percent(b)
+        )?;
+    }
+
+    let mut row = Vec::with_capacity(width);
+    for band in 0..height.div_ceil(6) {
+        let rows = band * 6..((band + 1) * 6).min(height);
+        let mut first = true;
+        for register in 0..palette.len() {
+            row.clear();
+            row.extend((0..width).map(|x| {
+                rows.clone().fold(0u8, |bits, y| {
+                    let at = y * width + x;
+                    if indices.get(at).copied().flatten() == Some(register) {
+                        bits | 1 << (y - rows.start)
+                    } else {
+                        bits
+                    }
+                })
+            }));
+            let Some(last) = row.iter().rposition(|&bits| bits != 0) else {
+                continue;
+            };
+            if !first {
+                // Carriage return: the next color overprints the same band.
+                out.write_all(b"$")?;
+            }
+            first = false;
+            write!(out, "#{register}")?;
+            write_sixel_run(out, &row[..=last])?;
+        }
+        out.write_all(b"-")?;
+    }
+    out.write_all(b"\x1b\\")
+}
+
+/// Builds a Sixel color palette for `rgba` and maps each pixel to its register, `None` for a
+/// transparent one.
+fn sixel_palette(rgba: &[u8]) -> (Vec<Rgb>, Vec<Option<usize>>) {
+    let opaque = |px: &[u8]| (px[3] >= 128).then_some((px[0], px[1], px[2]));
+    let mut distinct = HashSet::new();
+    for rgb in rgba.chunks_exact(4).filter_map(opaque) {
+        distinct.insert(rgb);
+        if distinct.len() > SIXEL_COLORS {
+            break;
+        }
+    }
+    let quantize = distinct.len() > SIXEL_COLORS;
+    let mut registers = HashMap::new();
+    let mut palette = Vec::new();
+    let indices = rgba
+        .chunks_exact(4)
+        .map(|px| {
+            let rgb = opaque(px)?;
+            let rgb = if quantize { quantize_332(rgb) } else { rgb };
+            Some(*registers.entry(rgb).or_insert_with(|| {
+                palette.push(rgb);
+                palette.len() - 1
+            }))
+        })
+        .collect();
+    (palette, indices)
+}
+
+/// Rounds a color to the nearest of the 256 colors 3-3-2 bit RGB can express.
+fn quantize_332((r, g, b): Rgb) -> Rgb {
+    let level = |channel: u8, levels: u16| {
+        let step = (u16::from(channel) * (levels - 1) + 127) / 255;
+        u8::try_from(step * 255 / (levels - 1)).unwrap_or(u8::MAX)
+    };
+    (level(r, 8), level(g, 8), level(b, 4))
+}
+
+/// Writes one color's row of sixels, run-length encoding repeats (`!count` + sixel).
+fn write_sixel_run<W: Write>(out: &mut W, row: &[u8]) -> io::Result<()> {
+    let mut x = 0;
+    while x < row.len() {
+        let bits = row[x];
+        let run = row[x..].iter().take_while(|&&b| b == bits).count();
+        let sixel = char::from(0x3f + bits);
+        if run > 3 {
+            write!(out, "!{run}{sixel}")?;
+        } else {
+            for _ in 0..run {
+                write!(out, "{sixel}")?;
+            }
+        }
+        x += run;
+    }
+    Ok(())
+}
+
+/// Scales `rgba` (`width` x `height` pixels) to `to_width` x `to_height` by nearest neighbor,
+/// which keeps pixel art's hard edges.
+///
+/// Returns an empty buffer if either target dimension is zero.
+#[must_use]
+pub fn scale_nearest(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    to_width: u32,
+    to_height: u32,
+) -> Vec<u8> {
+    if (width, height) == (to_width, to_height) {
+        return rgba.to_vec();
+    }
+    let (width, height) = (width as usize, height as usize);
+    let (to_width, to_height) = (to_width as usize, to_height as usize);
+    let mut scaled = vec![0; to_width * to_height * 4];
+    if width == 0 || height == 0 {
+        return scaled;
+    }
+    for (y, row) in scaled.chunks_exact_mut(to_width * 4).enumerate() {
+        let from_y = y * height / to_height;
+        for (x, px) in row.chunks_exact_mut(4).enumerate() {
+            let from = (from_y * width + x * width / to_width) * 4;
+            if let Some(source) = rgba.get(from..from + 4) {
+                px.copy_from_slice(source);
+            }
+        }
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(bytes: &[u8]) -> String {
+        String::from_utf8(bytes.to_vec()).expect("escape output is ASCII")
+    }
+
+    #[test]
+    fn probe_reply_waits_for_the_device_attributes() {
+        assert_eq!(parse_probe_reply(b""), ProbeReply::Pending);
+        assert_eq!(parse_probe_reply(b"\x1b_Gi=31;OK"), ProbeReply::Pending);
+        assert_eq!(parse_probe_reply(b"\x1b[?62;4"), ProbeReply::Pending);
+    }
+
+    #[test]
+    fn probe_reply_prefers_kitty_over_sixel() {
+        assert_eq!(
+            parse_probe_reply(b"\x1b_Gi=31;OK\x1b\\\x1b[?62;4;22c"),
+            ProbeReply::Answered(Some(GraphicsProtocol::Kitty))
+        );
+        assert_eq!(
+            parse_probe_reply(b"\x1b[?62;4;22c"),
+            ProbeReply::Answered(Some(GraphicsProtocol::Sixel))
+        );
+    }
+
+    #[test]
+    fn probe_reply_without_either_protocol_falls_back_to_glyphs() {
+        assert_eq!(
+            parse_probe_reply(b"\x1b_Gi=31;ENOTSUPPORTED:no\x1b\\\x1b[?62;22c"),
+            ProbeReply::Answered(None)
+        );
+        // Attribute 42 is not attribute 4.
+        assert_eq!(
+            parse_probe_reply(b"\x1b[?1;42c"),
+            ProbeReply::Answered(None)
+        );
+    }
+
+    #[test]
+    fn probe_reply_skips_keys_typed_while_waiting() {
+        assert_eq!(
+            parse_probe_reply(b"jj\x1b[A\x1b[?64;4c"),
+            ProbeReply::Answered(Some(GraphicsProtocol::Sixel))
+        );
+    }
+
+    #[test]
+    fn kitty_transmit_splits_large_payloads_into_chunks() {
+        let rgba = vec![0xff; 64 * 64 * 4];
+        let mut out = Vec::new();
+        write_kitty_transmit(&mut out, 7, 64, 64, &rgba).unwrap();
+        let out = text(&out);
+        assert!(out.starts_with("\x1b_Ga=t,f=32,s=64,v=64,i=7,q=2,m=1;"));
+        // 16 KiB of pixels is 21848 base64 bytes: five full chunks and a partial one.
+        assert_eq!(out.matches("\x1b_Gm=1;").count(), 4);
+        assert_eq!(out.matches("\x1b_Gm=0;").count(), 1);
+        assert!(out.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn kitty_transmit_of_a_small_image_is_one_sequence() {
+        let mut out = Vec::new();
+        write_kitty_transmit(&mut out, 1, 1, 1, &[255, 0, 0, 255]).unwrap();
+        assert_eq!(
+            text(&out),
+            "\x1b_Ga=t,f=32,s=1,v=1,i=1,q=2,m=0;/wAA/w==\x1b\\"
+        );
+    }
+
+    #[test]
+    fn sixel_leaves_transparent_pixels_unpainted() {
+        // 2x2: red, transparent / transparent, red.
+        let rgba = [
+            255, 0, 0, 255, 0, 0, 0, 0, //
+            0, 0, 0, 0, 255, 0, 0, 255,
+        ];
+        let mut out = Vec::new();
+        write_sixel(&mut out, 2, 2, &rgba).unwrap();
+        // One register; column 0 sets bit 0 (row 0), column 1 sets bit 1 (row 1).
+        assert_eq!(text(&out), "\x1bP0;1;0q\"1;1;2;2#0;2;100;0;0#0@A-\x1b\\");
+    }
+
+    #[test]
+    fn sixel_run_length_encodes_repeats() {
+        let rgba = [0, 0, 255, 255].repeat(8);
+        let mut out = Vec::new();
+        write_sixel(&mut out, 8, 1, &rgba).unwrap();
+        assert!(text(&out).contains("#0!8@"));
+    }
+
+    #[test]
+    fn sixel_quantizes_past_256_colors() {
+        let rgba: Vec<u8> = (0..=u16::MAX)
+            .step_by(97)
+            .flat_map(|n| {
+                let [hi, lo] = n.to_be_bytes();
+                [hi, lo, hi ^ lo, 255]
+            })
+            .collect();
+        let (palette, _) = sixel_palette(&rgba);
+        assert!(palette.len() <= SIXEL_COLORS, "{} registers", palette.len());
+    }
+
+    #[test]
+    fn scale_nearest_doubles_each_pixel() {
+        let rgba = [1, 1, 1, 1, 2, 2, 2, 2];
+        let scaled = scale_nearest(&rgba, 2, 1, 4, 2);
+        let firsts: Vec<u8> = scaled.chunks_exact(4).map(|px| px[0]).collect();
+        assert_eq!(firsts, [1, 1, 2, 2, 1, 1, 2, 2]);
+    }
+}
@@ -53,6 +53,49 @@
 //! This crate has EGC-aware and non-EGC-aware code paths gated on the same flag name.
 //! <!-- gen-features:end -->
 //!
+//! # Inline images
+//!
+//! The [`graphics`] module encodes images for the two inline image protocols terminals speak,
+//! kitty graphics and Sixel, and parses the reply to the query that tells them apart. Like
+//! [`TerminalRenderer`], it only produces bytes: `retroglyph-crossterm` uses it to draw tileset
+//! sprites in cells, and decides which cells get one.
+//!
+//! # Clipboard
+//!
+//! The [`clipboard`] module encodes OSC 52, the escape sequence that sets (and on some
+//! terminals reads) the clipboard of the machine the terminal emulator runs on, which reaches
+//! the user's clipboard even over SSH. `retroglyph-crossterm` implements its `Clipboard` facet
+//! with it.
+//!
+//! # Capability queries
+//!
+//! The [`capabilities`] module holds the batch of queries (device attributes, `XTVERSION`,
+//! `DECRQM`, OSC 10/11, ...) a backend can send at startup, and parses the replies into a
+//! [`TerminalCapabilities`](capabilities::TerminalCapabilities). Sending the query and reading
+//! the reply with a timeout is up to the backend; `retroglyph-crossterm` does it by default.
+//!
+//! # Waking a blocked poll
+//!
+//! On unix, the [`wake`] module's [`WakePipe`](wake::WakePipe) lets another thread cut short a
+//! wait on a terminal or socket: `retroglyph-crossterm`, `retroglyph-telnet` and
+//! `retroglyph-websocket` wait on it alongside their input while an `EventSender` is live, so a
+//! sent event is seen the moment it is sent.
+//!
+//! # Serving over a socket
+//!
+//! The [`session`] module is the part of `retroglyph-telnet` and `retroglyph-websocket` that
+//! isn't their wire protocol: a connection's input queue and waits, and the
+//! [`SocketServer`](session::SocketServer) that runs an `App` per connection. Each of those crates
+//! keeps only the codec that turns its peer's bytes into events.
+//!
+//! # Double-size lines
+//!
+//! A row the app set to a double-size [`LineSize`] is drawn with the VT100 line attributes:
+//! `DECDWL` (`ESC # 6`) for double width, `DECDHL` (`ESC # 3`/`ESC # 4`) for the top and bottom
+//! halves of double height, and `DECSWL` (`ESC # 5`) to put a row back. [`TerminalRenderer`]
+//! remembers which attribute each row has and only writes one when a row's size changes. Plain
+//! mode writes none of them.
+//!
 //! # Why not part of `retroglyph-window`
 //!
 //! `retroglyph-window` splits input (winit event loop) from output (`Presenter`) because every
@@ -92,8 +135,10 @@
 //! [`Color::to_indexed`]/[`Color::to_ansi`] (or forces [`Color::Default`]) before writing its SGR
 //! sequence, so the emitted bytes match what was actually requested rather than relying on the
 //! terminal to downsample. This crate does not auto-detect terminal capabilities itself (that
-//! belongs to a backend that actually has access to `$TERM`/`$NO_COLOR`, e.g.
-//! `retroglyph-crossterm`'s `CrosstermOptions`); [`ColorSupport::Truecolor`] remains the default.
+//! belongs to a backend that actually has access to the TTY and `$TERM`/`$NO_COLOR`, e.g.
+//! `retroglyph-crossterm`'s `CrosstermOptions`, which can choose from
+//! [`TerminalCapabilities::color_support`](capabilities::TerminalCapabilities::color_support));
+//! [`ColorSupport::Truecolor`] remains the default.
 //!
 //! Callers that need a specific, correct color regardless of `ColorSupport` should use
 //! [`Color::Indexed`] or [`Color::Ansi`] explicitly instead of [`Color::Rgb`]; both are passed
@@ -117,11 +162,22 @@
 #[doc = include_str!("../README.md")]
 struct ReadmeDoctests;
 
+#[doc(hidden)]
+pub mod base64;
+pub mod capabilities;
+pub mod clipboard;
+pub mod graphics;
+// The browser has no sockets to serve from.
+#[cfg(not(target_family = "wasm"))]
+pub mod session;
+#[cfg(unix)]
+pub mod wake;
+
 use retroglyph_core::backend::CursorStyle;
 use retroglyph_core::backend::DrawCell;
 use retroglyph_core::color::Color;
-use retroglyph_core::grid::Pos;
-use retroglyph_core::tile::Tile;
+use retroglyph_core::grid::{LineSize, Pos};
+use retroglyph_core::tile::{GlyphTransforms, Tile};
 use std::io::{self, Write};
 
 /// How aggressively [`TerminalRenderer`] quantizes [`Color`] before emitting an SGR sequence.
@@ -257,6 +313,21 @@ pub struct TerminalRenderer<W> {
     cursor: Option<Pos>,
     plain: bool,
     color_support: ColorSupport,
+    /// Whether the synchronized-update markers are written; see
+    /// [`set_synchronized_output`](Self::set_synchronized_output).
+    synchronized_output: bool,
+    /// Screen row that grid row 0 is drawn on; see [`set_row_offset`](Self::set_row_offset).
+    row_offset: u16,
+    /// The line attribute last written to each grid row, indexed by row; rows past the end are
+    /// [`LineSize::Normal`], which is what a terminal starts every row as.
+    line_sizes: Vec<LineSize>,
+    /// Set by [`reset_state`](Self::reset_state): `line_sizes` no longer describes the screen
+    /// and is dropped before the next draw. A flag rather than clearing it there, so that
+    /// `reset_state` can stay a `const fn`.
+    line_sizes_stale: bool,
+    /// Which character stands in for a transformed glyph; see
+    /// [`set_glyph_transforms`](Self::set_glyph_transforms).
+    glyph_transforms: Option<GlyphTransforms>,
 }
 
 impl<W: Write> TerminalRenderer<W> {
@@ -270,6 +341,11 @@ impl<W: Write> TerminalRenderer<W> {
             cursor: None,
             plain: false,
             color_support: ColorSupport::Truecolor,
+            synchronized_output: true,
+            row_offset: 0,
+            line_sizes: Vec::new(),
+            line_sizes_stale: false,
+            glyph_transforms: None,
         }
     }
 
@@ -287,6 +363,11 @@ impl<W: Write> TerminalRenderer<W> {
             cursor: None,
             plain,
             color_support: ColorSupport::Truecolor,
+            synchronized_output: true,
+            row_offset: 0,
+            line_sizes: Vec::new(),
+            line_sizes_stale: false,
+            glyph_transforms: None,
         }
     }
 
@@ -333,6 +414,58 @@ impl<W: Write> TerminalRenderer<W> {
         self.plain = plain;
     }
 
+    /// Returns whether the synchronized-update markers are written. See
+    /// [`set_synchronized_output`](Self::set_synchronized_output).
+    pub const fn synchronized_output(&self) -> bool {
+        self.synchronized_output
+    }
+
+    /// Enables or disables the synchronized-update markers (on by default).
+    ///
+    /// With this off, [`begin_synchronized_update`](Self::begin_synchronized_update) and
+    /// [`end_synchronized_update`](Self::end_synchronized_update) write nothing. Terminals that
+    /// don't understand mode 2026 are meant to ignore it, so this is only worth turning off once
+    /// the terminal has said it doesn't support it (a `DECRQM` reply; see
+    /// [`TerminalCapabilities::synchronized_output`](capabilities::TerminalCapabilities::synchronized_output)),
+    /// which saves the bytes and spares terminals that echo unknown sequences.
+    pub const fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized_output = enabled;
+    }
+
+    /// Returns the screen row grid row 0 is drawn on. See
+    /// [`set_row_offset`](Self::set_row_offset).
+    pub const fn row_offset(&self) -> u16 {
+        self.row_offset
+    }
+
+    /// Draws grid row 0 on screen row `rows` (0-indexed) instead of the top of the screen.
+    ///
+    /// Every absolute cursor move this renderer emits ([`draw`](Self::draw),
+    /// [`move_cursor_to`](Self::move_cursor_to), [`clear_rows`](Self::clear_rows)) is shifted
+    /// down by `rows`, which is what an inline viewport (a grid rendered below the shell's own
+    /// output instead of over the whole screen) needs. Also resets tracked cursor position,
+    /// since a tracked position from before the shift no longer names the same screen cell.
+    pub const fn set_row_offset(&mut self, rows: u16) {
+        self.row_offset = rows;
+        self.reset_cursor_tracking();
+    }
+
+    /// Returns the glyph-substitution table, if one is set. See
+    /// [`set_glyph_transforms`](Self::set_glyph_transforms).
+    pub const fn glyph_transforms(&self) -> Option<&GlyphTransforms> {
+        self.glyph_transforms.as_ref()
+    }
+
+    /// Draws a cell carrying a [`DrawCell::transform`] as the character `transforms` gives for it.
+    ///
+    /// A terminal cannot flip or rotate a character, so by default the transform is ignored and
+    /// the glyph drawn as authored. With a table set, a mirrored `<` can come out as `>`; glyphs
+    /// the table has no entry for are still drawn as authored, and so is a multi-codepoint
+    /// grapheme, since the table maps single characters.
+    pub fn set_glyph_transforms(&mut self, transforms: Option<GlyphTransforms>) {
+        self.glyph_transforms = transforms;
+    }
+
     /// Returns a reference to the underlying writer.
     pub const fn writer(&self) -> &W {
         &self.writer
@@ -353,10 +486,14 @@ impl<W: Write> TerminalRenderer<W> {
     /// Call this after an external clear (e.g. `\x1b[2J`) so the next [`draw`](Self::draw)
     /// doesn't skip a `MoveTo`/color/attribute escape under the assumption the terminal is still
     /// in the last-known state.
+    ///
+    /// Every row's line attribute is assumed back to normal too, which is what erasing the whole
+    /// display does to it.
     pub const fn reset_state(&mut self) {
         self.last_fg = None;
         self.last_bg = None;
         self.cursor = None;
+        self.line_sizes_stale = true;
     }
 
     /// Resets only tracked cursor position, leaving tracked color/attribute state alone.
@@ -378,13 +515,13 @@ impl<W: Write> TerminalRenderer<W> {
     ///
     /// A no-op in [plain mode](Self::set_plain_mode): synchronized updates are themselves a
     /// control code with nothing to synchronize once cell output has already degraded to plain
-    /// text.
+    /// text. Also a no-op with [`set_synchronized_output`](Self::set_synchronized_output) off.
     ///
     /// # Errors
     ///
     /// Returns an error if the writer fails.
     pub fn begin_synchronized_update(&mut self) -> io::Result<()> {
-        if self.plain {
+        if self.plain || !self.synchronized_output {
             return Ok(());
         }
         write!(self.writer, "\x1b[?2026h")
@@ -393,14 +530,15 @@ impl<W: Write> TerminalRenderer<W> {
     /// Ends a synchronized update (`\x1b[?2026l`). See
     /// [`begin_synchronized_update`](Self::begin_synchronized_update).
     ///
-    /// A no-op in [plain mode](Self::set_plain_mode); see
+    /// A no-op in [plain mode](Self::set_plain_mode) or with
+    /// [`set_synchronized_output`](Self::set_synchronized_output) off; see
     /// [`begin_synchronized_update`](Self::begin_synchronized_update).
     ///
     /// # Errors
     ///
     /// Returns an error if the writer fails.
     pub fn end_synchronized_update(&mut self) -> io::Result<()> {
-        if self.plain {
+        if self.plain || !self.synchronized_output {
             return Ok(());
         }
         write!(self.writer, "\x1b[?2026l")
@@ -446,6 +584,10 @@ impl<W: Write> TerminalRenderer<W> {
     where
         I: Iterator<Item = DrawCell<'a>>,
     {
+        if self.line_sizes_stale {
+            self.line_sizes.clear();
+            self.line_sizes_stale = false;
+        }
         for draw_cell in content {
             let (pos, cell, extra) = (draw_cell.pos, draw_cell.tile, draw_cell.grapheme);
             #[cfg(not(feature = "egc"))]
@@ -462,6 +604,8 @@ impl<W: Write> TerminalRenderer<W> {
                 continue;
             }
 
+            self.set_line_size(pos.y, draw_cell.line)?;
+
             // Applied here (last-known-state comparisons and the SGR sequences below both use
             // the degraded color), not on `Tile::style` itself: `ColorSupport` degrades what is
             // *emitted*, not the tile's own requested color.
@@ -476,7 +620,7 @@ impl<W: Write> TerminalRenderer<W> {
                 write!(
                     self.buf,
                     "\x1b[{};{}H",
-                    pos.y.saturating_add(1),
+                    pos.y.saturating_add(self.row_offset).saturating_add(1),
                     pos.x.saturating_add(1)
                 )?;
             }
@@ -505,7 +649,8 @@ impl<W: Write> TerminalRenderer<W> {
                 self.last_bg = Some(bg);
             }
 
-            let cell_width = Self::write_glyph(&mut self.buf, cell, extra)?;
+            let substitute = self.substitute(&draw_cell);
+            let cell_width = Self::write_glyph(&mut self.buf, cell, extra, substitute)?;
 
             // After printing, the terminal cursor advances by the cell's
             // display width. Track that so the next cell can skip the move.
@@ -517,6 +662,34 @@ impl<W: Write> TerminalRenderer<W> {
         Ok(())
     }
 
+    /// Writes row `y`'s line attribute if it differs from the last one written there.
+    ///
+    /// The attribute applies to whichever row the cursor is on, so this moves there first and
+    /// forgets the tracked cursor position: the cell being drawn needs its own move afterwards.
+    fn set_line_size(&mut self, y: u16, line: LineSize) -> io::Result<()> {
+        let row = usize::from(y);
+        if self.line_sizes.get(row).copied().unwrap_or_default() == line {
+            return Ok(());
+        }
+        let code = match line {
+            LineSize::Normal => '5',
+            LineSize::DoubleWidth => '6',
+            LineSize::DoubleHeightTop => '3',
+            LineSize::DoubleHeightBottom => '4',
+        };
+        write!(
+            self.buf,
+            "\x1b[{};1H\x1b#{code}",
+            y.saturating_add(self.row_offset).saturating_add(1)
+        )?;
+        if self.line_sizes.len() <= row {
+            self.line_sizes.resize(row + 1, LineSize::Normal);
+        }
+        self.line_sizes[row] = line;
+        self.cursor = None;
+        Ok(())
+    }
+
     /// Plain-mode half of [`draw`](Self::draw): no escape sequences, cell text degraded to
     /// readable ASCII (row changes become `\n`, gaps become spaces), renders into `self.buf`.
     /// See [`draw`](Self::draw) for the shared contract, [`set_plain_mode`](Self::set_plain_mode)
@@ -569,7 +742,8 @@ impl<W: Write> TerminalRenderer<W> {
                 write!(self.buf, " ")?;
             }
 
-            let cell_width = Self::write_glyph(&mut self.buf, cell, extra)?;
+            let substitute = self.substitute(&draw_cell);
+            let cell_width = Self::write_glyph(&mut self.buf, cell, extra, substitute)?;
 
             self.cursor = Some(Pos {
                 x: pos.x.saturating_add(cell_width),
@@ -579,11 +753,32 @@ impl<W: Write> TerminalRenderer<W> {
         Ok(())
     }
 
-    /// Writes `cell`'s printable text (the full grapheme from `extra` when the `egc` feature
-    /// provides one, otherwise just the primary glyph) to `out`, returning its precomputed
-    /// display width ([`Tile::width`]) so the caller can advance the tracked cursor position.
-    /// Shared by [`draw_escape`](Self::draw_escape) and [`draw_plain`](Self::draw_plain).
-    fn write_glyph(out: &mut Vec<u8>, cell: &Tile, extra: Option<&str>) -> io::Result<u16> {
+    /// The character [`set_glyph_transforms`](Self::set_glyph_transforms) draws in place of
+    /// `draw_cell`'s glyph, or `None` to draw the cell as authored.
+    fn substitute(&self, draw_cell: &DrawCell<'_>) -> Option<char> {
+        if draw_cell.grapheme.is_some() {
+            return None;
+        }
+        self.glyph_transforms
+            .as_ref()?
+            .substitute(draw_cell.tile.glyph(), draw_cell.transform)
+    }
+
+    /// Writes `cell`'s printable text (`substitute` when one was looked up, else the full
+    /// grapheme from `extra` when the `egc` feature provides one, otherwise just the primary
+    /// glyph) to `out`, returning its precomputed display width ([`Tile::width`]) so the caller
+    /// can advance the tracked cursor position. Shared by [`draw_escape`](Self::draw_escape) and
+    /// [`draw_plain`](Self::draw_plain).
+    fn write_glyph(
+        out: &mut Vec<u8>,
+        cell: &Tile,
+        extra: Option<&str>,
+        substitute: Option<char>,
+    ) -> io::Result<u16> {
+        if let Some(ch) = substitute {
+            write!(out, "{ch}")?;
+            return Ok(cell.width());
+        }
         #[cfg(not(feature = "egc"))]
         let _ = extra;
         #[cfg(feature = "egc")]
@@ -681,13 +876,41 @@ impl<W: Write> TerminalRenderer<W> {
         write!(
             self.writer,
             "\x1b[{};{}H",
-            position.y.saturating_add(1),
+            position.y.saturating_add(self.row_offset).saturating_add(1),
             position.x.saturating_add(1)
         )?;
         self.reset_cursor_tracking();
         Ok(())
     }
 
+    /// Erases grid rows `rows` (shifted by [`row_offset`](Self::row_offset)) and resets tracked
+    /// state, leaving every other screen row alone.
+    ///
+    /// The partial-screen counterpart of [`clear_screen`](Self::clear_screen), with the same SGR
+    /// reset before erasing (see there for why) and the same flush and
+    /// [`reset_state`](Self::reset_state) afterwards. Each row is erased with `EL 2`
+    /// (`\x1b[2K`) after a move to its first column. Erasing a line leaves its line attribute
+    /// alone, so unlike `reset_state` this keeps the tracked line sizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails to write or flush.
+    pub fn clear_rows(&mut self, rows: core::ops::Range<u16>) -> io::Result<()> {
+        write!(self.writer, "\x1b[0m")?;
+        for row in rows {
+            write!(
+                self.writer,
+                "\x1b[{};1H\x1b[2K",
+                row.saturating_add(self.row_offset).saturating_add(1)
+            )?;
+        }
+        self.writer.flush()?;
+        let stale = self.line_sizes_stale;
+        self.reset_state();
+        self.line_sizes_stale = stale;
+        Ok(())
+    }
+
     /// Shows or hides the cursor (DECTCEM, `CSI ?25 h`/`CSI ?25 l`), without flushing.
     ///
     /// # Errors
@@ -1019,6 +1242,23 @@ mod tests {
         assert_eq!(out.matches("\x1b[1;1H").count(), 2, "output: {out:?}");
     }
 
+    #[test]
+    fn line_sizes_are_written_once_per_change() {
+        let tile = Tile::new('X', Style::default());
+        let mut renderer = TerminalRenderer::new(Vec::new());
+        let wide = |x| DrawCell::new(Pos { x, y: 0 }, &tile).with_line(LineSize::DoubleWidth);
+        renderer.draw([wide(0), wide(1)].into_iter()).unwrap();
+        renderer.draw(core::iter::once(wide(0))).unwrap();
+        renderer
+            .draw(core::iter::once(DrawCell::new(Pos { x: 0, y: 0 }, &tile)))
+            .unwrap();
+        renderer.flush().unwrap();
+        let out = String::from_utf8(renderer.into_writer()).unwrap();
+        assert_eq!(out.matches("\x1b#6").count(), 1, "output: {out:?}");
+        assert!(out.starts_with("\x1b[1;1H\x1b#6"), "output: {out:?}");
+        assert!(out.contains("\x1b[1;1H\x1b#5"), "output: {out:?}");
+    }
+
     #[test]
     fn synchronized_update_markers() {
         let mut renderer = TerminalRenderer::new(Vec::new());
@@ -1161,6 +1401,38 @@ mod tests {
         assert_eq!(out, "     A\n  B");
     }
 
+    #[test]
+    fn glyph_transforms_substitute_only_transformed_cells_with_an_entry() {
+        use retroglyph_core::tile::Transform;
+        let arrow = Tile::new('>', Style::default());
+        let hash = Tile::new('#', Style::default());
+        let left = Transform::IDENTITY.flipped_x();
+        let cells = || {
+            [
+                DrawCell::new(Pos { x: 0, y: 0 }, &arrow).with_transform(left),
+                DrawCell::new(Pos { x: 1, y: 0 }, &arrow),
+                DrawCell::new(Pos { x: 2, y: 0 }, &hash).with_transform(left),
+            ]
+            .into_iter()
+        };
+
+        // No table: the transform is ignored in both draw paths.
+        for plain in [false, true] {
+            let mut renderer = TerminalRenderer::with_plain_mode(Vec::new(), plain);
+            renderer.draw(cells()).unwrap();
+            let out = String::from_utf8(renderer.into_writer()).unwrap();
+            assert!(out.ends_with(">>#"), "{out:?}");
+        }
+
+        for plain in [false, true] {
+            let mut renderer = TerminalRenderer::with_plain_mode(Vec::new(), plain);
+            renderer.set_glyph_transforms(Some(GlyphTransforms::mirrors()));
+            renderer.draw(cells()).unwrap();
+            let out = String::from_utf8(renderer.into_writer()).unwrap();
+            assert!(out.ends_with("<>#"), "{out:?}");
+        }
+    }
+
     #[test]
     fn plain_mode_suppresses_synchronized_update_markers() {
         let mut renderer = TerminalRenderer::with_plain_mode(Vec::new(), true);
@@ -1171,6 +1443,59 @@ mod tests {
         assert_eq!(out, "");
     }
 
+    #[test]
+    fn disabling_synchronized_output_suppresses_its_markers() {
+        let tile = Tile::new('A', Style::default());
+        let mut renderer = TerminalRenderer::new(Vec::new());
+        renderer.set_synchronized_output(false);
+        renderer
+            .draw_frame(core::iter::once(DrawCell::new(Pos { x: 0, y: 0 }, &tile)))
+            .unwrap();
+        renderer.end_frame().unwrap();
+        let out = String::from_utf8(renderer.into_writer()).unwrap();
+        assert!(!out.contains("2026"), "{out:?}");
+        assert!(out.ends_with('A'), "{out:?}");
+    }
+
+    #[test]
+    fn row_offset_shifts_draws_and_cursor_moves_down() {
+        let tile = Tile::new('A', Style::default());
+        let mut renderer = TerminalRenderer::new(Vec::new());
+        renderer.set_row_offset(10);
+        renderer
+            .draw(core::iter::once(DrawCell::new(Pos { x: 2, y: 1 }, &tile)))
+            .unwrap();
+        renderer.move_cursor_to(Pos { x: 0, y: 0 }).unwrap();
+        let out = String::from_utf8(renderer.into_writer()).unwrap();
+        assert!(out.starts_with("\x1b[12;3H"), "output: {out:?}");
+        assert!(out.ends_with("\x1b[11;1H"), "output: {out:?}");
+    }
+
+    #[test]
+    fn clear_rows_erases_only_the_given_rows_and_forgets_tracked_state() {
+        let tile = Tile::new('A', Style::default());
+        let mut renderer = TerminalRenderer::new(Vec::new());
+        renderer.set_row_offset(3);
+        renderer
+            .draw(core::iter::once(DrawCell::new(Pos { x: 0, y: 0 }, &tile)))
+            .unwrap();
+        renderer.writer_mut().clear();
+
+        renderer.clear_rows(0..2).unwrap();
+        assert_eq!(
+            String::from_utf8(renderer.writer().clone()).unwrap(),
+            "\x1b[0m\x1b[4;1H\x1b[2K\x1b[5;1H\x1b[2K"
+        );
+
+        // Tracked cursor and colors were reset, so redrawing the same cell re-emits both.
+        renderer.writer_mut().clear();
+        renderer
+            .draw(core::iter::once(DrawCell::new(Pos { x: 0, y: 0 }, &tile)))
+            .unwrap();
+        let out = String::from_utf8(renderer.into_writer()).unwrap();
+        assert!(out.starts_with("\x1b[4;1H\x1b["), "output: {out:?}");
+    }
+
     #[test]
     fn plain_mode_setter_and_getter_round_trip() {
         let mut renderer = TerminalRenderer::new(Vec::new());
@@ -0,0 +1,428 @@
+//! Serving an [`App`] over TCP, one session per connection, whatever the wire protocol.
+//!
+//! `retroglyph-telnet` and `retroglyph-websocket` differ only in what the bytes on the socket
+//! mean. Everything else lives here:
+//!
+//! - [`SocketInput`] holds the input half of a connection: the socket, the queued events, the
+//!   [`EventInbox`] behind [`Input::event_sender`](retroglyph_core::backend::Input::event_sender),
+//!   and (on unix) the [`wake`](crate::wake) pipe and watcher that let a send or the peer cut a
+//!   wait short.
+//! - [`poll_event`] and [`poll_next_event`] implement
+//!   [`Input`](retroglyph_core::backend::Input)'s waits for any [`SocketSession`], reading through
+//!   [`read`].
+//! - [`SocketServer`] accepts connections and runs a fresh `App` on a thread for each, through
+//!   the protocol's [`SessionOptions::build`].
+//!
+//! A protocol crate implements [`SocketSession::receive`] to decode what its peer sends, and
+//! forwards its `Input` methods to the functions here.
+
+#[cfg(unix)]
+use crate::wake::{InputWatcher, WakePipe};
+use retroglyph_core::app::{App, Flow, Frame, RunOptions};
+use retroglyph_core::backend::{Backend, EventInbox, EventSender};
+use retroglyph_core::event::{Event, push_coalesced};
+use retroglyph_core::terminal::Terminal;
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::fd::AsFd;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// How long a single socket read waits while an [`EventSender`] is live and there is no wake
+/// pipe to wait on (off unix, or if creating one failed), so sent events are picked up without
+/// waiting for the peer to send anything. Same value and reason as `retroglyph-crossterm`'s.
+const SENDER_POLL_SLICE: Duration = Duration::from_millis(10);
+
+/// The input half of a socket session: where its events come from and wait.
+pub struct SocketInput {
+    stream: TcpStream,
+    event_queue: VecDeque<Event>,
+    /// Created by the first [`event_sender`](Self::event_sender) call; drained by every
+    /// [`poll_event`].
+    inbox: Option<EventInbox>,
+    /// Created alongside `inbox`, whose sends write to it: a blocked [`poll_event`] waits on it
+    /// and the socket together, so a send wakes it at once.
+    #[cfg(unix)]
+    wake: Option<WakePipe>,
+    /// Waits on the socket while a task is parked in [`poll_next_event`]. Started by the first
+    /// poll that finds nothing to read.
+    #[cfg(unix)]
+    watcher: Option<InputWatcher>,
+    /// The peer has gone; [`Event::Close`] has been queued.
+    closed: bool,
+}
+
+impl SocketInput {
+    /// Reads events from `stream`, a handle to the session's socket.
+    #[must_use]
+    pub const fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            event_queue: VecDeque::new(),
+            inbox: None,
+            #[cfg(unix)]
+            wake: None,
+            #[cfg(unix)]
+            watcher: None,
+            closed: false,
+        }
+    }
+
+    /// The socket this reads from.
+    #[must_use]
+    pub const fn stream(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Whether the peer has gone, either by closing the connection or because
+    /// [`close`](Self::close) was called.
+    #[must_use]
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Queues `event`, coalescing it with the one before it where that loses nothing; see
+    /// [`push_coalesced`].
+    pub fn push(&mut self, event: Event) {
+        push_coalesced(&mut self.event_queue, event);
+    }
+
+    /// Marks the peer gone and queues the one [`Event::Close`] that reports it.
+    pub fn close(&mut self) {
+        if !self.closed {
+            self.closed = true;
+            self.event_queue.push_back(Event::Close);
+        }
+    }
+
+    /// A sender whose events [`poll_event`] returns, waking it if it is waiting on the socket.
+    pub fn event_sender(&mut self) -> EventSender {
+        if self.inbox.is_none() {
+            let inbox = EventInbox::new();
+            #[cfg(unix)]
+            {
+                self.wake = WakePipe::new().ok();
+                if let Some(waker) = self.wake.as_ref().map(WakePipe::waker) {
+                    inbox.set_notify(move || waker.wake());
+                }
+            }
+            self.inbox = Some(inbox);
+        }
+        self.inbox.get_or_insert_with(EventInbox::new).sender()
+    }
+
+    /// Reads into `buf`, waiting up to `timeout` (forever if `None`) for something to arrive.
+    /// Returns how many bytes arrived; a hang-up or socket error closes the session instead.
+    fn read_into(&mut self, buf: &mut [u8], timeout: Option<Duration>) -> usize {
+        let set = match timeout {
+            Some(timeout) if timeout.is_zero() => self.stream.set_nonblocking(true),
+            timeout => self
+                .stream
+                .set_nonblocking(false)
+                .and_then(|()| self.stream.set_read_timeout(timeout)),
+        };
+        match set.and_then(|()| self.stream.read(buf)) {
+            Ok(0) => {
+                self.close();
+                0
+            }
+            Ok(len) => len,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::Interrupted
+                ) =>
+            {
+                0
+            }
+            Err(_) => {
+                self.close();
+                0
+            }
+        }
+    }
+}
+
+/// A backend serving one connection: a [`SocketInput`], and the protocol that decodes what the
+/// peer sends.
+pub trait SocketSession {
+    /// The session's input half.
+    fn input(&self) -> &SocketInput;
+
+    /// The session's input half, mutably.
+    fn input_mut(&mut self) -> &mut SocketInput;
+
+    /// Decodes `bytes`, the next the peer sent, queuing what they mean with
+    /// [`SocketInput::push`].
+    fn receive(&mut self, bytes: &[u8]);
+}
+
+/// Reads whatever the peer of `session` has sent, waiting up to `timeout` (forever if `None`) for
+/// something to arrive, and hands it to [`SocketSession::receive`].
+pub fn read(session: &mut impl SocketSession, timeout: Option<Duration>) {
+    let mut buf = [0; 4096];
+    let len = session.input_mut().read_into(&mut buf, timeout);
+    if len > 0 {
+        session.receive(&buf[..len]);
+    }
+}
+
+/// [`Input::poll_event`](retroglyph_core::backend::Input::poll_event) for a socket session.
+///
+/// Once the peer has gone, waits out `timeout` (on the inbox, in case a sender is live) rather
+/// than returning at once, so a loop that keeps polling a closed session idles instead of
+/// spinning.
+pub fn poll_event(session: &mut impl SocketSession, timeout: Duration) -> Option<Event> {
+    let start = Instant::now();
+    loop {
+        let input = session.input_mut();
+        while let Some(event) = input.inbox.as_ref().and_then(EventInbox::try_recv) {
+            push_coalesced(&mut input.event_queue, event);
+        }
+        if let Some(event) = input.event_queue.pop_front() {
+            return Some(event);
+        }
+        if input.closed {
+            let left = timeout.saturating_sub(start.elapsed());
+            if let Some(event) = input
+                .inbox
+                .as_ref()
+                .and_then(|inbox| inbox.recv_timeout(left))
+            {
+                return Some(event);
+            }
+            std::thread::sleep(timeout.saturating_sub(start.elapsed()));
+            return None;
+        }
+        // Sent events can't wake a blocked socket read, so while a sender is live the wait
+        // happens on the wake pipe and the socket together, and the read doesn't block. Without
+        // a pipe the wait is sliced instead; see `SENDER_POLL_SLICE`.
+        let sending = input.inbox.as_ref().is_some_and(EventInbox::has_senders);
+        let left = timeout.saturating_sub(start.elapsed());
+        #[cfg(unix)]
+        if let Some(wake) = input.wake.as_ref().filter(|_| sending) {
+            if wake
+                .wait(&input.stream, (left != Duration::MAX).then_some(left))
+                .is_err()
+            {
+                return None;
+            }
+            read(session, Some(Duration::ZERO));
+            if session.input().event_queue.is_empty() && start.elapsed() >= timeout {
+                return None;
+            }
+            continue;
+        }
+        let left = if sending {
+            left.min(SENDER_POLL_SLICE)
+        } else {
+            left
+        };
+        read(session, (left != Duration::MAX).then_some(left));
+        if session.input().event_queue.is_empty() && start.elapsed() >= timeout {
+            return None;
+        }
+    }
+}
+
+/// [`Input::poll_next_event`](retroglyph_core::backend::Input::poll_next_event) for a socket
+/// session.
+///
+/// Takes whatever the peer has already sent, then parks until the socket has more or an event is
+/// sent. On unix an [`InputWatcher`] waits on the socket meanwhile; elsewhere (or if the watcher
+/// can't be started) the task is woken to look again after a few milliseconds.
+pub fn poll_next_event(session: &mut impl SocketSession, cx: &mut Context<'_>) -> Poll<Event> {
+    if let Some(event) = poll_event(session, Duration::ZERO) {
+        return Poll::Ready(event);
+    }
+    let input = session.input_mut();
+    if let Some(event) = input
+        .inbox
+        .as_ref()
+        .and_then(|inbox| inbox.poll_recv(cx.waker()))
+    {
+        return Poll::Ready(event);
+    }
+    if input.closed {
+        // Only a sent event can still arrive, and the inbox wakes the task for that.
+        return Poll::Pending;
+    }
+    #[cfg(unix)]
+    {
+        if input.watcher.is_none() {
+            input.watcher = input
+                .stream
+                .as_fd()
+                .try_clone_to_owned()
+                .and_then(InputWatcher::new)
+                .ok();
+        }
+        if let Some(watcher) = &input.watcher {
+            watcher.register(cx.waker());
+            return Poll::Pending;
+        }
+    }
+    let waker = cx.waker().clone();
+    let spawned = std::thread::Builder::new().spawn(move || {
+        std::thread::sleep(SENDER_POLL_SLICE);
+        waker.wake();
+    });
+    if spawned.is_err() {
+        cx.waker().wake_by_ref();
+    }
+    Poll::Pending
+}
+
+/// How a protocol turns an accepted connection into a session, for [`SocketServer`].
+pub trait SessionOptions: Copy + Default + Send + 'static {
+    /// The backend each session runs its `App` on.
+    type Session: Backend + SocketSession;
+
+    /// The protocol's name, which each session's thread is named after (`"telnet 1.2.3.4:5678"`).
+    const PROTOCOL: &'static str;
+
+    /// Opens a session on `stream`, blocking until it is ready for its `App`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket fails or the peer doesn't speak the protocol.
+    fn build(self, stream: TcpStream) -> io::Result<Self::Session>;
+}
+
+/// Listens for connections and serves each one its own [`App`], over the protocol `O` opens
+/// sessions with.
+#[derive(Debug)]
+pub struct SocketServer<O> {
+    listener: TcpListener,
+    options: O,
+}
+
+impl<O: SessionOptions> SocketServer<O> {
+    /// Listens on `addr`, with the default options for every session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            options: O::default(),
+        })
+    }
+
+    /// Uses `options` for every session.
+    #[must_use]
+    pub const fn with_options(mut self, options: O) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The address the server is listening on: how to find the port after binding port 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket can't report it.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for the next connection and opens its session, for a caller that runs sessions
+    /// itself (wrapping each app in a [`Session`] to end it once the peer has gone).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting the connection or opening the session fails.
+    pub fn accept(&self) -> io::Result<O::Session> {
+        let (stream, _) = self.listener.accept()?;
+        self.options.build(stream)
+    }
+
+    /// Serves every connection with a fresh app from `new_app`, each session on its own thread,
+    /// using [`run`](retroglyph_core::app::run)'s event-driven loop. Blocks for as long as the
+    /// server runs.
+    ///
+    /// A session ends when its app returns [`Flow::Exit`] or its peer disconnects (once the app
+    /// has taken the [`Event::Close`], even if it carries on regardless); the other sessions
+    /// carry on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener itself fails. A connection that fails on its own (the
+    /// peer hangs up mid-handshake, say) only ends that session.
+    pub fn serve<A, F>(self, new_app: F) -> io::Result<()>
+    where
+        A: App<O::Session>,
+        F: Fn() -> A + Send + Sync + 'static,
+    {
+        self.serve_with(RunOptions::default(), new_app)
+    }
+
+    /// [`serve`](Self::serve), with each session's loop run by
+    /// [`run_with`](retroglyph_core::app::run_with) under `run_options` (e.g.
+    /// [`RunOptions::animated`] for a real-time game).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener itself fails; see [`serve`](Self::serve).
+    pub fn serve_with<A, F>(self, run_options: RunOptions, new_app: F) -> io::Result<()>
+    where
+        A: App<O::Session>,
+        F: Fn() -> A + Send + Sync + 'static,
+    {
+        let new_app = Arc::new(new_app);
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::ConnectionAborted
+                            | io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::Interrupted
+                    ) =>
+                {
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let options = self.options;
+            let new_app = Arc::clone(&new_app);
+            let name = stream.peer_addr().map_or_else(
+                |_| format!("{} session", O::PROTOCOL),
+                |peer| format!("{} {peer}", O::PROTOCOL),
+            );
+            // A session that fails ends on its own; there is nobody else to tell.
+            let _ = std::thread::Builder::new().name(name).spawn(move || {
+                if let Ok(backend) = options.build(stream) {
+                    let _ =
+                        retroglyph_core::app::run_with(backend, Session(new_app()), run_options);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A session's app as [`SocketServer::serve_with`] runs it: ends the session once the peer has
+/// gone and the app has taken its [`Event::Close`], whether or not the app acted on it.
+#[derive(Debug, Default)]
+pub struct Session<A>(pub A);
+
+impl<B: Backend + SocketSession, A: App<B>> App<B> for Session<A> {
+    fn init(&mut self, term: &mut Terminal<B>) {
+        self.0.init(term);
+    }
+
+    fn update(&mut self, term: &mut Terminal<B>, frame: &Frame) -> Flow {
+        let flow = self.0.update(term, frame);
+        if term.backend().input().is_closed() && !term.wait_for_input(Duration::ZERO) {
+            return Flow::Exit;
+        }
+        flow
+    }
+}
@@ -0,0 +1,356 @@
+//! Waking a thread blocked on terminal or socket input from another thread.
+//!
+//! A backend that blocks in `poll(2)` on its input (a TTY, a client's socket) cannot also block
+//! on a [`Condvar`](std::sync::Condvar), so an event sent from a worker thread has nothing to
+//! wake it with. [`WakePipe`] is the classic self-pipe answer: the pipe's read end is polled
+//! alongside the input, and [`PipeWaker::wake`] (installed as the inbox's
+//! [`set_notify`](retroglyph_core::backend::EventInbox::set_notify) hook) writes a byte to the
+//! other end, so the wait returns the moment something is sent.
+//!
+//! [`InputWatcher`] does the same for an async task: it waits on the input from a thread of its
+//! own while the task is parked, and wakes the task's [`Waker`] once there is something to read.
+//!
+//! Unix only: elsewhere, backends fall back to polling in short slices.
+
+use rustix::event::{PollFd, PollFlags, Timespec};
+use std::io;
+use std::os::fd::{AsFd, BorrowedFd, OwnedFd};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::Waker;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// The longest single `poll(2)` wait: longer (or unbounded) waits are made in chunks of this
+/// size so no platform timeout overflows.
+const MAX_WAIT_CHUNK: Duration = Duration::from_secs(3600);
+
+/// A self-pipe: a wait on input that any thread can cut short.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_terminal::wake::WakePipe;
+/// use std::time::{Duration, Instant};
+///
+/// let (input, _keep_open) = std::io::pipe()?;
+/// let pipe = WakePipe::new()?;
+/// let waker = pipe.waker();
+/// std::thread::spawn(move || waker.wake());
+///
+/// let start = Instant::now();
+/// pipe.wait(&input, None)?;
+/// assert!(start.elapsed() < Duration::from_secs(5));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct WakePipe {
+    read: OwnedFd,
+    write: Arc<OwnedFd>,
+}
+
+impl WakePipe {
+    /// Creates the pipe.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the pipe could not be created (the process is out of file
+    /// descriptors, say).
+    pub fn new() -> io::Result<Self> {
+        let (read, write) = rustix::pipe::pipe()?;
+        for fd in [&read, &write] {
+            rustix::io::ioctl_fionbio(fd, true)?;
+            rustix::io::fcntl_setfd(fd, rustix::io::FdFlags::CLOEXEC)?;
+        }
+        Ok(Self {
+            read,
+            write: Arc::new(write),
+        })
+    }
+
+    /// A handle that cuts the current (or next) [`wait`](Self::wait) short, from any thread.
+    #[must_use]
+    pub fn waker(&self) -> PipeWaker {
+        PipeWaker(Arc::clone(&self.write))
+    }
+
+    /// A second descriptor for the pipe's write end, for a signal handler to write to (see
+    /// `signal_hook::low_level::pipe::register`): a signal then wakes the wait like
+    /// [`PipeWaker::wake`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the descriptor could not be duplicated.
+    pub fn writer(&self) -> io::Result<OwnedFd> {
+        Ok(rustix::io::fcntl_dupfd_cloexec(&*self.write, 0)?)
+    }
+
+    /// Blocks until `input` is readable (or hung up), the pipe is woken, or `timeout` runs out
+    /// (`None` waits as long as it takes).
+    ///
+    /// Returning says only that something *may* be ready; the caller re-checks its input and
+    /// its queue either way. Every wake pending when this returns is consumed, so one wake never
+    /// cuts two waits short.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if polling fails for a reason other than an interrupting signal.
+    pub fn wait(&self, input: &impl AsFd, timeout: Option<Duration>) -> io::Result<()> {
+        let result = self.wait_fd(input.as_fd(), timeout);
+        self.drain();
+        result
+    }
+
+    fn wait_fd(&self, input: BorrowedFd<'_>, timeout: Option<Duration>) -> io::Result<()> {
+        let chunk = timeout.map_or(MAX_WAIT_CHUNK, |timeout| timeout.min(MAX_WAIT_CHUNK));
+        let chunk = Timespec::try_from(chunk).map_err(|_| io::ErrorKind::InvalidInput)?;
+        loop {
+            let mut fds = [
+                PollFd::from_borrowed_fd(input, PollFlags::IN),
+                PollFd::new(&self.read, PollFlags::IN),
+            ];
+            match rustix::event::poll(&mut fds, Some(&chunk)) {
+                // A chunk of an unbounded wait ran out: keep waiting.
+                Ok(0) if timeout.is_none() => {}
+                Ok(_) => return Ok(()),
+                Err(rustix::io::Errno::INTR) => {
+                    if timeout.is_some() {
+                        return Ok(());
+                    }
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Empties the pipe of pending wakes.
+    fn drain(&self) {
+        let mut buf = [0; 64];
+        while matches!(rustix::io::read(&self.read, &mut buf), Ok(len) if len > 0) {}
+    }
+}
+
+/// Cuts a [`WakePipe::wait`] short; see [`WakePipe::waker`].
+#[derive(Clone, Debug)]
+pub struct PipeWaker(Arc<OwnedFd>);
+
+impl PipeWaker {
+    /// Wakes the pipe's current wait, or the next one if nothing is waiting yet.
+    pub fn wake(&self) {
+        // A full pipe already holds a pending wake, so a failed write loses nothing.
+        let _ = rustix::io::write(&*self.0, &[1]);
+    }
+}
+
+/// Wakes an async task parked on an input descriptor once there is something to read.
+///
+/// A backend registers the task's waker with [`register`](Self::register) each time it returns
+/// [`Poll::Pending`](std::task::Poll::Pending); a thread owned by the watcher waits on the input
+/// meanwhile and wakes that waker, once, when the input becomes readable. Nothing runs between
+/// a wake and the next registration, so input the task leaves unread does not spin the thread.
+#[derive(Debug)]
+pub struct InputWatcher {
+    shared: Arc<Watch>,
+    pipe: PipeWaker,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug, Default)]
+struct Watch {
+    state: Mutex<WatchState>,
+    armed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct WatchState {
+    waker: Option<Waker>,
+    stopped: bool,
+}
+
+impl Watch {
+    fn state(&self) -> std::sync::MutexGuard<'_, WatchState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl InputWatcher {
+    /// Starts watching `input`, a descriptor for the backend's input (a duplicate of it, since
+    /// the watcher owns it).
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the wake pipe or the watcher thread could not be created.
+    pub fn new(input: OwnedFd) -> io::Result<Self> {
+        let pipe = WakePipe::new()?;
+        let waker = pipe.waker();
+        let shared = Arc::new(Watch::default());
+        let watch = Arc::clone(&shared);
+        let thread = std::thread::Builder::new()
+            .name("retroglyph-input-watcher".into())
+            .spawn(move || {
+                loop {
+                    {
+                        let mut state = watch.state();
+                        while state.waker.is_none() && !state.stopped {
+                            state = watch
+                                .armed
+                                .wait(state)
+                                .unwrap_or_else(PoisonError::into_inner);
+                        }
+                        if state.stopped {
+                            return;
+                        }
+                    }
+                    if pipe.wait(&input, None).is_err() {
+                        // The input can't be polled; wake the task so it finds out by reading.
+                        let waker = watch.state().waker.take();
+                        if let Some(waker) = waker {
+                            waker.wake();
+                        }
+                        return;
+                    }
+                    let waker = {
+                        let mut state = watch.state();
+                        if state.stopped {
+                            return;
+                        }
+                        state.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            })?;
+        Ok(Self {
+            shared,
+            pipe: waker,
+            thread: Some(thread),
+        })
+    }
+
+    /// Wakes `waker` once the input is readable, replacing any waker registered earlier. Wakes
+    /// it straight away if the input already is.
+    pub fn register(&self, waker: &Waker) {
+        let mut state = self.shared.state();
+        if let Some(current) = &mut state.waker {
+            current.clone_from(waker);
+        } else {
+            state.waker = Some(waker.clone());
+            drop(state);
+            self.shared.armed.notify_one();
+        }
+    }
+
+    /// Wakes the registered waker (or the next one to register) without the input being
+    /// readable, e.g. from a signal the task has to see. Same pipe as [`WakePipe::waker`].
+    #[must_use]
+    pub fn waker(&self) -> PipeWaker {
+        self.pipe.clone()
+    }
+
+    /// A descriptor for the wake pipe's write end, for a signal handler to write to; see
+    /// [`WakePipe::writer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the descriptor could not be duplicated.
+    pub fn writer(&self) -> io::Result<OwnedFd> {
+        Ok(rustix::io::fcntl_dupfd_cloexec(&*self.pipe.0, 0)?)
+    }
+}
+
+impl Drop for InputWatcher {
+    fn drop(&mut self) {
+        self.shared.state().stopped = true;
+        self.shared.armed.notify_one();
+        self.pipe.wake();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::time::Instant;
+
+    struct Count(AtomicUsize);
+
+    impl Wake for Count {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn wait_for(count: &Count, at_least: usize) {
+        let start = Instant::now();
+        while count.0.load(Ordering::SeqCst) < at_least {
+            assert!(start.elapsed() < Duration::from_secs(5), "never woken");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn wait_times_out_with_nothing_ready() {
+        let (input, _writer) = io::pipe().unwrap();
+        let pipe = WakePipe::new().unwrap();
+        let start = Instant::now();
+        pipe.wait(&input, Some(Duration::from_millis(20))).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn one_wake_cuts_one_wait_short() {
+        let (input, _writer) = io::pipe().unwrap();
+        let pipe = WakePipe::new().unwrap();
+        pipe.waker().wake();
+        pipe.waker().wake();
+        let start = Instant::now();
+        pipe.wait(&input, Some(Duration::from_secs(5))).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let start = Instant::now();
+        pipe.wait(&input, Some(Duration::from_millis(20))).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn wait_returns_once_the_input_is_readable() {
+        let (input, mut writer) = io::pipe().unwrap();
+        let pipe = WakePipe::new().unwrap();
+        writer.write_all(b"x").unwrap();
+        let start = Instant::now();
+        pipe.wait(&input, None).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn watcher_wakes_a_registered_waker_once_the_input_is_readable() {
+        let (input, mut writer) = io::pipe().unwrap();
+        let watcher = InputWatcher::new(input.into()).unwrap();
+        let count = Arc::new(Count(AtomicUsize::new(0)));
+        watcher.register(&Waker::from(Arc::clone(&count)));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(count.0.load(Ordering::SeqCst), 0);
+
+        writer.write_all(b"x").unwrap();
+        wait_for(&count, 1);
+        // Unread input does not wake anything until the next registration.
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        watcher.register(&Waker::from(Arc::clone(&count)));
+        wait_for(&count, 2);
+    }
+
+    #[test]
+    fn watcher_waker_wakes_without_input() {
+        let (input, _writer) = io::pipe().unwrap();
+        let watcher = InputWatcher::new(input.into()).unwrap();
+        let count = Arc::new(Count(AtomicUsize::new(0)));
+        watcher.register(&Waker::from(Arc::clone(&count)));
+        watcher.waker().wake();
+        wait_for(&count, 1);
+    }
+}
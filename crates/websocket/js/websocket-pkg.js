@@ -0,0 +1,86 @@
+// Stands in for the `wasm-bindgen`-generated `pkg.js` of a `retroglyph-terminal-wasm` build: the
+// same `wasm_terminal_*` functions, forwarded over a WebSocket to a `retroglyph-websocket`
+// server instead of into an in-page `TerminalWasm`. A driver written against the in-browser
+// backend (such as that crate's `js/xterm-driver.js`) attaches to a native app by importing this
+// module instead and passing the server's URL to `init`.
+//
+// Messages to the server are text: `r cols rows`, `k code mods`, `m x y action button mods`,
+// `p text`, `f 1|0`, `c text`. From the server, binary messages are ANSI output and a text
+// message `c text` is text the app copied.
+
+let socket = null;
+let output = '';
+let copied;
+const decoder = new TextDecoder();
+
+// Connects to the server at `url` (by default the page's own host and port, for a server behind
+// the same reverse proxy as the page) and resolves once the connection is open.
+export default function init(url) {
+  if (url === undefined) {
+    const scheme = location.protocol === 'https:' ? 'wss:' : 'ws:';
+    url = `${scheme}//${location.host}/`;
+  }
+  return new Promise((resolve, reject) => {
+    socket = new WebSocket(url);
+    socket.binaryType = 'arraybuffer';
+    socket.onopen = () => resolve();
+    socket.onerror = (error) => reject(error);
+    socket.onmessage = (message) => {
+      if (typeof message.data !== 'string') {
+        output += decoder.decode(message.data, { stream: true });
+      } else if (message.data.startsWith('c ')) {
+        copied = message.data.slice(2);
+      }
+    };
+  });
+}
+
+function send(message) {
+  if (socket && socket.readyState === WebSocket.OPEN) socket.send(message);
+}
+
+// The server runs one session per connection, so there is only ever the one handle.
+export function wasm_terminal_new(width, height) {
+  send(`r ${width} ${height}`);
+  return 1;
+}
+
+export function wasm_terminal_free(_handle) {
+  if (socket) socket.close();
+}
+
+export function wasm_terminal_resize(_handle, width, height) {
+  send(`r ${width} ${height}`);
+}
+
+export function wasm_terminal_push_key(_handle, code, mods) {
+  send(`k ${code} ${mods}`);
+}
+
+export function wasm_terminal_push_mouse(_handle, x, y, action, button, mods) {
+  send(`m ${x} ${y} ${action} ${button} ${mods}`);
+}
+
+export function wasm_terminal_push_paste(_handle, text) {
+  send(`p ${text}`);
+}
+
+export function wasm_terminal_push_focus(_handle, focused) {
+  send(`f ${focused ? 1 : 0}`);
+}
+
+export function wasm_terminal_take_output(_handle) {
+  const ansi = output;
+  output = '';
+  return ansi;
+}
+
+export function wasm_terminal_take_clipboard(_handle) {
+  const text = copied;
+  copied = undefined;
+  return text;
+}
+
+export function wasm_terminal_report_clipboard(_handle, text) {
+  send(`c ${text}`);
+}
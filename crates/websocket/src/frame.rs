@@ -0,0 +1,281 @@
+//! WebSocket framing (RFC 6455 section 5): cutting the byte stream a client sends into messages,
+//! and wrapping what the server sends.
+//!
+//! Nothing here touches a socket; [`WebSocket`](crate::WebSocket) feeds the decoder what it reads
+//! and writes out what [`encode`] produces.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+const CONTINUATION: u8 = 0x0;
+pub(crate) const TEXT: u8 = 0x1;
+pub(crate) const BINARY: u8 = 0x2;
+pub(crate) const CLOSE: u8 = 0x8;
+pub(crate) const PING: u8 = 0x9;
+pub(crate) const PONG: u8 = 0xA;
+
+/// The largest message accepted, fragments included. Input messages are a few bytes; only a
+/// paste comes anywhere near this.
+const MAX_MESSAGE: usize = 1024 * 1024;
+
+/// A complete message from the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    /// Must be answered with a pong carrying the same payload.
+    Ping(Vec<u8>),
+    /// The client is closing the connection.
+    Close,
+}
+
+/// Why the client's framing can't be read any further. The connection is closed on any of these,
+/// as the RFC requires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Malformed {
+    /// Client frames must be masked.
+    Unmasked,
+    /// Reserved bits set without an extension negotiated, or an unknown opcode.
+    Reserved,
+    /// A control frame that is fragmented or longer than 125 bytes, or a continuation with nothing
+    /// to continue.
+    BadFragment,
+    /// Longer than [`MAX_MESSAGE`].
+    TooLong,
+    /// A text message that isn't UTF-8.
+    NotUtf8,
+}
+
+/// Buffers the bytes a client sends and hands back each message once all of it has arrived.
+#[derive(Debug, Default)]
+pub(crate) struct Decoder {
+    buffer: Vec<u8>,
+    /// The opcode and payload so far of a fragmented message.
+    fragments: Option<(u8, Vec<u8>)>,
+}
+
+impl Decoder {
+    /// Adds bytes read from the client.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// The next complete message, if one has arrived.
+    pub(crate) fn next_message(&mut self) -> Result<Option<Message>, Malformed> {
+        loop {
+            let Some((fin, opcode, payload)) = self.next_frame()? else {
+                return Ok(None);
+            };
+            let (opcode, payload) = match (opcode, &mut self.fragments) {
+                (PING, _) => return Ok(Some(Message::Ping(payload))),
+                (CLOSE, _) => return Ok(Some(Message::Close)),
+                (PONG, _) => continue,
+                (CONTINUATION, None) | (TEXT | BINARY, Some(_)) => {
+                    return Err(Malformed::BadFragment);
+                }
+                (CONTINUATION, Some((_, so_far))) => {
+                    if so_far.len() + payload.len() > MAX_MESSAGE {
+                        return Err(Malformed::TooLong);
+                    }
+                    so_far.extend_from_slice(&payload);
+                    if !fin {
+                        continue;
+                    }
+                    self.fragments.take().unwrap_or_default()
+                }
+                (TEXT | BINARY, None) if !fin => {
+                    self.fragments = Some((opcode, payload));
+                    continue;
+                }
+                (TEXT | BINARY, None) => (opcode, payload),
+                _ => return Err(Malformed::Reserved),
+            };
+            return if opcode == TEXT {
+                String::from_utf8(payload)
+                    .map(|text| Some(Message::Text(text)))
+                    .map_err(|_| Malformed::NotUtf8)
+            } else {
+                Ok(Some(Message::Binary(payload)))
+            };
+        }
+    }
+
+    /// Takes the next whole frame off the buffer: `(fin, opcode, unmasked payload)`.
+    fn next_frame(&mut self) -> Result<Option<(bool, u8, Vec<u8>)>, Malformed> {
+        let &[first, second, ..] = &self.buffer[..] else {
+            return Ok(None);
+        };
+        let fin = first & 0x80 != 0;
+        let opcode = first & 0x0f;
+        if first & 0x70 != 0 {
+            return Err(Malformed::Reserved);
+        }
+        if second & 0x80 == 0 {
+            return Err(Malformed::Unmasked);
+        }
+        let (len, mut at) = match second & 0x7f {
+            126 => {
+                let Some(&[a, b]) = self.buffer.get(2..4) else {
+                    return Ok(None);
+                };
+                (u64::from(u16::from_be_bytes([a, b])), 4)
+            }
+            127 => {
+                let Some(bytes) = self.buffer.get(2..10) else {
+                    return Ok(None);
+                };
+                let mut len = [0; 8];
+                len.copy_from_slice(bytes);
+                (u64::from_be_bytes(len), 10)
+            }
+            len => (u64::from(len), 2),
+        };
+        if opcode & 0x8 != 0 && (!fin || len > 125) {
+            return Err(Malformed::BadFragment);
+        }
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= MAX_MESSAGE)
+            .ok_or(Malformed::TooLong)?;
+        let Some(&[m0, m1, m2, m3]) = self.buffer.get(at..at + 4) else {
+            return Ok(None);
+        };
+        at += 4;
+        if self.buffer.len() < at + len {
+            return Ok(None);
+        }
+        let mask = [m0, m1, m2, m3];
+        let payload = self.buffer[at..at + len]
+            .iter()
+            .zip(mask.iter().cycle())
+            .map(|(byte, mask)| byte ^ mask)
+            .collect();
+        self.buffer.drain(..at + len);
+        Ok(Some((fin, opcode, payload)))
+    }
+}
+
+/// Appends one unfragmented, unmasked (server-to-client) frame to `out`.
+pub(crate) fn encode(opcode: u8, payload: &[u8], out: &mut Vec<u8>) {
+    out.push(0x80 | opcode);
+    let len = payload.len();
+    if let Ok(short @ 0..=125) = u8::try_from(len) {
+        out.push(short);
+    } else if let Ok(medium) = u16::try_from(len) {
+        out.push(126);
+        out.extend_from_slice(&medium.to_be_bytes());
+    } else {
+        out.push(127);
+        out.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+    out.extend_from_slice(payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A client frame: masked, as a browser sends it.
+    fn masked(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x37, 0xfa, 0x21, 0x3d];
+        let mut frame = vec![u8::from(fin) << 7 | opcode];
+        let len = u16::try_from(payload.len()).unwrap();
+        if let Ok(short @ 0..=125) = u8::try_from(len) {
+            frame.push(0x80 | short);
+        } else {
+            frame.push(0xfe);
+            frame.extend_from_slice(&len.to_be_bytes());
+        }
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+        frame
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Vec<Message>, Malformed> {
+        let mut decoder = Decoder::default();
+        decoder.push(bytes);
+        let mut messages = Vec::new();
+        while let Some(message) = decoder.next_message()? {
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    #[test]
+    fn the_rfc_example_text_frame_decodes() {
+        // RFC 6455 section 5.7: a single-frame masked text message "Hello".
+        assert_eq!(
+            decode(&[
+                0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58
+            ]),
+            Ok(vec![Message::Text("Hello".to_owned())])
+        );
+    }
+
+    #[test]
+    fn a_frame_split_across_reads_comes_out_whole() {
+        let frame = masked(true, BINARY, &[7; 300]);
+        let mut decoder = Decoder::default();
+        decoder.push(&frame[..3]);
+        assert_eq!(decoder.next_message(), Ok(None));
+        decoder.push(&frame[3..100]);
+        assert_eq!(decoder.next_message(), Ok(None));
+        decoder.push(&frame[100..]);
+        assert_eq!(
+            decoder.next_message(),
+            Ok(Some(Message::Binary(vec![7; 300])))
+        );
+    }
+
+    #[test]
+    fn fragments_are_joined_around_interleaved_control_frames() {
+        let mut bytes = masked(false, TEXT, b"Hel");
+        bytes.extend(masked(true, PING, b"hi"));
+        bytes.extend(masked(true, CONTINUATION, b"lo"));
+        bytes.extend(masked(true, CLOSE, &[]));
+        assert_eq!(
+            decode(&bytes),
+            Ok(vec![
+                Message::Ping(b"hi".to_vec()),
+                Message::Text("Hello".to_owned()),
+                Message::Close,
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_framing_is_refused() {
+        assert_eq!(decode(&[0x81, 0x01, b'x']), Err(Malformed::Unmasked));
+        assert_eq!(
+            decode(&masked(true, CONTINUATION, b"x")),
+            Err(Malformed::BadFragment)
+        );
+        assert_eq!(
+            decode(&masked(false, PING, b"x")),
+            Err(Malformed::BadFragment)
+        );
+        assert_eq!(decode(&masked(true, 0x3, b"x")), Err(Malformed::Reserved));
+        assert_eq!(
+            decode(&masked(true, TEXT, &[0xff])),
+            Err(Malformed::NotUtf8)
+        );
+    }
+
+    #[test]
+    fn encode_picks_the_shortest_length_form() {
+        let mut out = Vec::new();
+        encode(TEXT, b"Hello", &mut out);
+        assert_eq!(out, [0x81, 0x05, b'H', b'e', b'l', b'l', b'o']);
+
+        out.clear();
+        encode(BINARY, &[0; 256], &mut out);
+        assert_eq!(&out[..4], [0x82, 126, 0x01, 0x00]);
+        assert_eq!(out.len(), 4 + 256);
+
+        out.clear();
+        encode(BINARY, &vec![0; 70_000], &mut out);
+        assert_eq!(&out[..2], [0x82, 127]);
+        assert_eq!(&out[2..10], 70_000u64.to_be_bytes());
+    }
+}
@@ -0,0 +1,231 @@
+//! The opening handshake (RFC 6455 section 4): the HTTP `Upgrade` request a browser sends and the
+//! `101 Switching Protocols` answer that turns the connection into a WebSocket.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_terminal::base64;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+/// Appended to the client's key before hashing; fixed by the RFC.
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The longest request head read. A browser's is well under 1 KiB; this only bounds what a client
+/// that never finishes its headers can make a session hold.
+const MAX_REQUEST: usize = 8 * 1024;
+
+/// Reads the upgrade request from `stream` and answers it, within `timeout`.
+///
+/// Returns whatever the client sent after the request head, which already belongs to the
+/// WebSocket framing. A request that isn't a WebSocket upgrade is answered with an HTTP error and
+/// fails with [`io::ErrorKind::InvalidData`].
+pub(crate) fn accept(stream: &mut TcpStream, timeout: Duration) -> io::Result<Vec<u8>> {
+    let deadline = Instant::now() + timeout;
+    let mut received = Vec::new();
+    let mut buf = [0; 1024];
+    let head_len = loop {
+        if let Some(end) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if received.len() > MAX_REQUEST {
+            return reject(stream, "431 Request Header Fields Too Large");
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        stream.set_read_timeout(Some(left))?;
+        let len = stream.read(&mut buf)?;
+        if len == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        received.extend_from_slice(&buf[..len]);
+    };
+    let head = String::from_utf8_lossy(&received[..head_len]);
+    match accept_key(&head) {
+        Ok(accept) => {
+            write!(
+                stream,
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                 Sec-WebSocket-Accept: {accept}\r\n\r\n"
+            )?;
+            stream.flush()?;
+            Ok(received.split_off(head_len))
+        }
+        Err(status) => reject(stream, status),
+    }
+}
+
+/// Answers with an HTTP error `status` and fails.
+fn reject<T>(stream: &mut impl Write, status: &str) -> io::Result<T> {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status}\r\nSec-WebSocket-Version: 13\r\nContent-Length: 0\r\n\
+         Connection: close\r\n\r\n"
+    );
+    let _ = stream.flush();
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("not a WebSocket upgrade ({status})"),
+    ))
+}
+
+/// Checks an upgrade request head and computes its `Sec-WebSocket-Accept`, or names the HTTP
+/// status to refuse it with.
+fn accept_key(head: &str) -> Result<String, &'static str> {
+    let mut lines = head.split("\r\n");
+    let request = lines.next().unwrap_or_default();
+    if !request.starts_with("GET ") {
+        return Err("405 Method Not Allowed");
+    }
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+    let header = |wanted: &str| {
+        headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+            .map(|&(_, value)| value)
+    };
+    let has_token = |wanted: &str, token: &str| {
+        header(wanted).is_some_and(|value| {
+            value
+                .split(',')
+                .any(|item| item.trim().eq_ignore_ascii_case(token))
+        })
+    };
+    if !has_token("Upgrade", "websocket") || !has_token("Connection", "upgrade") {
+        return Err("400 Bad Request");
+    }
+    if header("Sec-WebSocket-Version") != Some("13") {
+        return Err("426 Upgrade Required");
+    }
+    let key = header("Sec-WebSocket-Key").ok_or("400 Bad Request")?;
+    let mut input = key.as_bytes().to_vec();
+    input.extend_from_slice(ACCEPT_GUID.as_bytes());
+    let mut accept = Vec::with_capacity(28);
+    base64::encode(&sha1(&input), &mut accept);
+    Ok(accept.into_iter().map(char::from).collect())
+}
+
+/// SHA-1 (FIPS 180-4). The handshake is its only use here, and it needs no collision
+/// resistance: the hash only proves the server read the client's key.
+// `a` through `e`, `f`, `k` and `w` are the standard's own names for its working variables.
+#[allow(clippy::many_single_char_names)]
+fn sha1(message: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    let bits = (message.len() as u64).wrapping_mul(8);
+    padded.extend_from_slice(&bits.to_be_bytes());
+
+    for block in padded.chunks_exact(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (total, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *total = total.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write as _;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+    }
+
+    #[test]
+    fn sha1_matches_the_fips_test_vectors() {
+        assert_eq!(
+            hex(&sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(&sha1(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn the_rfc_example_key_is_accepted() {
+        let head = "GET /chat HTTP/1.1\r\nHost: server.example.com\r\nUpgrade: websocket\r\n\
+                    Connection: keep-alive, Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                    Sec-WebSocket-Version: 13\r\n\r\n";
+        assert_eq!(
+            accept_key(head).as_deref(),
+            Ok("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+        );
+    }
+
+    #[test]
+    fn requests_that_are_not_upgrades_are_refused() {
+        assert_eq!(
+            accept_key("GET / HTTP/1.1\r\nHost: x\r\n\r\n"),
+            Err("400 Bad Request")
+        );
+        assert_eq!(
+            accept_key("POST / HTTP/1.1\r\n\r\n"),
+            Err("405 Method Not Allowed")
+        );
+        assert_eq!(
+            accept_key(
+                "GET / HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                 Sec-WebSocket-Version: 8\r\nSec-WebSocket-Key: x\r\n\r\n"
+            ),
+            Err("426 Upgrade Required")
+        );
+    }
+}
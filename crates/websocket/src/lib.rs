@@ -0,0 +1,659 @@
+//! A WebSocket server [`Backend`](retroglyph_core::backend::Backend): runs an
+//! [`App`](retroglyph_core::app::App) natively and displays it in a browser terminal such as
+//! [xterm.js](https://xtermjs.org/), one session per connection.
+//!
+//! This is the opposite split from `retroglyph-terminal-wasm`, which runs the app inside the
+//! page: here the app stays on the server, and the page only draws what it sends and reports the
+//! player's input. [`WebSocketServer`] listens on TCP and, for each browser that connects, accepts
+//! the WebSocket handshake, builds a [`WebSocket`] backend over the connection, and runs a fresh
+//! `App` on its own thread:
+//!
+//! ```no_run
+//! use retroglyph_core::app::{App, Flow, Frame};
+//! use retroglyph_core::backend::Backend;
+//! use retroglyph_core::color::Style;
+//! use retroglyph_core::event::Event;
+//! use retroglyph_core::terminal::Terminal;
+//! use retroglyph_websocket::WebSocketServer;
+//!
+//! #[derive(Default)]
+//! struct Lobby {
+//!     drawn: bool,
+//! }
+//!
+//! impl<B: Backend> App<B> for Lobby {
+//!     fn update(&mut self, term: &mut Terminal<B>, _frame: &Frame) -> Flow {
+//!         let mut changed = !self.drawn;
+//!         while let Some(event) = term.poll(std::time::Duration::ZERO) {
+//!             changed = true;
+//!             if matches!(event, Event::Close) {
+//!                 return Flow::Exit;
+//!             }
+//!         }
+//!         if !changed {
+//!             // Nothing new to show: skip the present and sleep until the player acts.
+//!             return Flow::Idle;
+//!         }
+//!         self.drawn = true;
+//!         term.surface().put((0, 0), '@', Style::default());
+//!         Flow::Continue
+//!     }
+//! }
+//!
+//! WebSocketServer::bind("0.0.0.0:8080")?.serve(Lobby::default)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Cell-diffing and ANSI/SGR output are delegated to [`retroglyph_terminal::TerminalRenderer`],
+//! exactly as for `retroglyph-crossterm`; only the transport differs. Each flushed frame goes to
+//! the browser as one binary WebSocket message.
+//!
+//! # From the browser
+//!
+//! `js/websocket-pkg.js` in this crate's source tree stands in for the `wasm-bindgen` module of a
+//! `retroglyph-terminal-wasm` build: it exports the same `wasm_terminal_*` functions, forwarding
+//! each over the socket. A driver written for the in-browser backend, such as that crate's
+//! `js/xterm-driver.js`, attaches to a server by importing it in place of `./pkg.js` and passing
+//! the server's URL to `init`:
+//!
+//! ```js
+//! import init, { wasm_terminal_new /* , ... */ } from './websocket-pkg.js';
+//!
+//! await init('ws://localhost:8080');
+//! ```
+//!
+//! The rest of the driver is unchanged.
+//!
+//! # Wire protocol
+//!
+//! Drivers that don't use `websocket-pkg.js` speak this directly. From the browser, every
+//! message is text, a letter and space-separated fields:
+//!
+//! | Message | Meaning |
+//! | --- | --- |
+//! | `r <cols> <rows>` | The terminal's size in cells. The first one sizes the session; later ones arrive as [`Event::Resize`]. |
+//! | `k <code> <mods>` | A key, encoded as for [`decode_key_event`](retroglyph_terminal_wasm::decode_key_event). |
+//! | `m <x> <y> <action> <button> <mods>` | A mouse event, encoded as for [`decode_mouse_event`](retroglyph_terminal_wasm::decode_mouse_event). |
+//! | `p <text>` | Pasted text, as one [`Event::Paste`]. |
+//! | `f <1 or 0>` | Focus gained or lost. |
+//! | `c <text>` | The browser clipboard's text; see [Clipboard](#clipboard). |
+//!
+//! From the server, a binary message is ANSI output to write to the terminal as-is, and a text
+//! message `c <text>` is text the app copied. Messages that don't decode are dropped, as the
+//! in-browser FFI drops them.
+//!
+//! The session's `App` starts once the first size has arrived, up to
+//! [`WebSocketOptions::handshake_timeout`] after the connection opened, so
+//! [`App::init`](retroglyph_core::app::App::init) sees the real size. A browser closing the
+//! connection (or the tab) is reported once as [`Event::Close`]; after that, drawing fails with
+//! the socket's error and polling only waits out its timeout. A served session ends once its app
+//! has taken the `Close`, even if the app ignores it.
+//!
+//! The server doesn't check the page's `Origin`, so any page the player opens can connect, just
+//! as any telnet client can connect to `retroglyph-telnet`. Put it behind a reverse proxy that
+//! does, if that matters for the game.
+//!
+//! # Clipboard
+//!
+//! [`Clipboard::set_clipboard_text`] sends the text to the driver, which passes it to
+//! `navigator.clipboard.writeText`. [`Clipboard::clipboard_text`] returns the text the driver last
+//! reported, or the app's own last copy, the same as `retroglyph-terminal-wasm`.
+//!
+//! # Features
+//!
+//! <!-- gen-features:start -->
+//! This crate has no default features; every feature below is optional and off unless enabled.
+//!
+//! ### `dev`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards `retroglyph-core`'s `dev` feature, which forces development diagnostics on in a build
+//! that would otherwise compile them out (see [`retroglyph_core::dev`]).
+//!
+//! ### `egc`
+//!
+//! ⚪ Optional.
+//!
+//! Forwards to `retroglyph-terminal`'s (and `retroglyph-core`'s) `egc` feature for
+//! grapheme-cluster-aware cell diffing.
+//! <!-- gen-features:end -->
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/crates-lurey-io/retroglyph/main/docs/public/assets/logo.svg"
+)]
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/crates-lurey-io/retroglyph/main/docs/public/assets/logo.svg"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+// Compile the code blocks in this crate's own README as doctests so its quick start is
+// type-checked on every test run and cannot silently rot. See `retroglyph-terminal-wasm`'s
+// matching include.
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
+
+mod frame;
+mod handshake;
+mod messages;
+
+use frame::{Decoder, Message};
+use messages::Incoming;
+use retroglyph_core::backend::{
+    Clipboard, ClipboardError, Cursor, CursorStyle, DrawCell, EventSender, Input, Output,
+};
+use retroglyph_core::event::Event;
+use retroglyph_core::grid::{HasSize, Pos, Size};
+use retroglyph_terminal::session::{
+    self, SessionOptions, SocketInput, SocketServer, SocketSession,
+};
+use retroglyph_terminal::{ColorSupport, TerminalRenderer};
+use std::io::{self, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// How long a new session waits for the handshake and the browser's first size by default; see
+/// [`WebSocketOptions::handshake_timeout`].
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Builder-style configuration for a [`WebSocket`] session.
+///
+/// ```
+/// use std::time::Duration;
+/// use retroglyph_terminal::ColorSupport;
+/// use retroglyph_websocket::WebSocketOptions;
+///
+/// let options = WebSocketOptions::new()
+///     .handshake_timeout(Duration::from_secs(5))
+///     .color_support(ColorSupport::Indexed256);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebSocketOptions {
+    handshake_timeout: Duration,
+    color_support: ColorSupport,
+    default_size: Size,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        Self {
+            handshake_timeout: HANDSHAKE_TIMEOUT,
+            color_support: ColorSupport::Truecolor,
+            default_size: Size::new(80, 24),
+        }
+    }
+}
+
+impl WebSocketOptions {
+    /// Creates the default options: two seconds for the handshake, truecolor, 80x24 if the
+    /// browser doesn't report a size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long a new session waits for the WebSocket handshake and the browser's first
+    /// size message before its `App` starts ([`HANDSHAKE_TIMEOUT`] by default).
+    ///
+    /// A driver sends its size as soon as the connection opens, so the full wait only happens
+    /// on one that never does. A handshake that hasn't finished by then fails the session; a size
+    /// arriving later still applies.
+    #[must_use]
+    pub const fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    /// Renders every session with `color_support` ([`ColorSupport::Truecolor`] by default, which
+    /// xterm.js and the other browser terminals all handle).
+    #[must_use]
+    pub const fn color_support(mut self, color_support: ColorSupport) -> Self {
+        self.color_support = color_support;
+        self
+    }
+
+    /// Sets the size a session gets when its browser doesn't report one (80x24 by default).
+    #[must_use]
+    pub const fn default_size(mut self, width: u16, height: u16) -> Self {
+        self.default_size = Size::new(width, height);
+        self
+    }
+
+    /// Accepts the WebSocket handshake on `stream` and builds a [`WebSocket`] backend over it.
+    ///
+    /// Blocks until the browser has sent its size or
+    /// [`handshake_timeout`](Self::handshake_timeout) runs out, then switches the browser
+    /// terminal to its alternate screen and hides the cursor; dropping the backend undoes both.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket fails or the request on it isn't a WebSocket upgrade (which
+    /// is answered with an HTTP error first).
+    pub fn build(self, mut stream: TcpStream) -> io::Result<WebSocket> {
+        let deadline = Instant::now() + self.handshake_timeout;
+        let leftover = handshake::accept(&mut stream, self.handshake_timeout)?;
+        stream.set_nodelay(true)?;
+        let mut decoder = Decoder::default();
+        decoder.push(&leftover);
+        let mut renderer = TerminalRenderer::new(FrameSink {
+            stream: stream.try_clone()?,
+            pending: Vec::new(),
+            frame: Vec::new(),
+        });
+        renderer.set_color_support(self.color_support);
+        let mut socket = WebSocket {
+            renderer,
+            input: SocketInput::new(stream),
+            decoder,
+            size: self.default_size,
+            clipboard: None,
+            sized: false,
+        };
+        socket.receive_messages();
+        while !socket.sized && !socket.input.is_closed() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            session::read(&mut socket, Some(left));
+        }
+        socket.sized = true;
+        socket.send_output(b"\x1b[?1049h\x1b[?25l")?;
+        socket.renderer.clear_screen()?;
+        Ok(socket)
+    }
+}
+
+/// The renderer's writer: collects what it writes and sends each flush as one binary message.
+struct FrameSink {
+    stream: TcpStream,
+    /// Output written since the last flush.
+    pending: Vec<u8>,
+    /// Scratch space for the framed message, kept to reuse its allocation.
+    frame: Vec<u8>,
+}
+
+impl FrameSink {
+    /// Sends one message, after any output still pending so the two arrive in order.
+    fn send(&mut self, opcode: u8, payload: &[u8]) -> io::Result<()> {
+        self.flush()?;
+        self.frame.clear();
+        frame::encode(opcode, payload, &mut self.frame);
+        self.stream.write_all(&self.frame)
+    }
+}
+
+impl Write for FrameSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            self.frame.clear();
+            frame::encode(frame::BINARY, &self.pending, &mut self.frame);
+            self.pending.clear();
+            self.stream.write_all(&self.frame)?;
+        }
+        self.stream.flush()
+    }
+}
+
+/// A [`Backend`](retroglyph_core::backend::Backend) for one browser session: renders to the
+/// browser terminal over a WebSocket and reads its input back.
+///
+/// Usually built for you by [`WebSocketServer`]; build one directly with [`WebSocket::new`] or
+/// [`WebSocketOptions::build`] to drive a connection accepted some other way.
+pub struct WebSocket {
+    renderer: TerminalRenderer<FrameSink>,
+    /// Reads a second handle to the same socket.
+    input: SocketInput,
+    decoder: Decoder,
+    size: Size,
+    /// The text the driver last reported, or the app last copied.
+    clipboard: Option<String>,
+    /// The session has its size: later sizes arrive as [`Event::Resize`].
+    sized: bool,
+}
+
+impl WebSocket {
+    /// Accepts the handshake on `stream` using the default [`WebSocketOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket fails or the request on it isn't a WebSocket upgrade.
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        WebSocketOptions::default().build(stream)
+    }
+
+    /// The color support this session renders with; see [`WebSocketOptions::color_support`].
+    #[must_use]
+    pub const fn color_support(&self) -> ColorSupport {
+        self.renderer.color_support()
+    }
+
+    /// The address of the browser (or of the proxy in front of it).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket has been closed.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.input.stream().peer_addr()
+    }
+
+    /// Sends `bytes` as terminal output right away.
+    fn send_output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let sink = self.renderer.writer_mut();
+        sink.write_all(bytes)?;
+        sink.flush()
+    }
+
+    /// Handles every complete message decoded so far.
+    fn receive_messages(&mut self) {
+        while !self.input.is_closed() {
+            match self.decoder.next_message() {
+                Ok(None) => return,
+                Ok(Some(Message::Text(text))) => self.receive_text(&text),
+                Ok(Some(Message::Binary(_))) => {}
+                Ok(Some(Message::Ping(payload))) => {
+                    let _ = self.renderer.writer_mut().send(frame::PONG, &payload);
+                }
+                Ok(Some(Message::Close)) => self.hang_up(&[]),
+                // 1002: protocol error.
+                Err(_) => self.hang_up(&1002u16.to_be_bytes()),
+            }
+        }
+    }
+
+    fn receive_text(&mut self, text: &str) {
+        match messages::parse(text) {
+            Some(Incoming::Event(event)) => self.input.push(event),
+            Some(Incoming::Resize(width, height)) if self.sized => {
+                self.input.push(Event::Resize(width, height));
+            }
+            Some(Incoming::Resize(width, height)) => {
+                self.size = Size::new(width, height);
+                self.sized = true;
+            }
+            Some(Incoming::Clipboard(text)) => self.clipboard = Some(text),
+            None => {}
+        }
+    }
+
+    /// Answers (or starts) the closing handshake with a close frame carrying `status`, shuts the
+    /// connection down, and reports the browser gone.
+    fn hang_up(&mut self, status: &[u8]) {
+        let _ = self.renderer.writer_mut().send(frame::CLOSE, status);
+        let _ = self.input.stream().shutdown(Shutdown::Both);
+        self.input.close();
+    }
+}
+
+impl Drop for WebSocket {
+    fn drop(&mut self) {
+        // Leave the browser terminal the way it was before the session; it may well be gone.
+        let _ = self.send_output(b"\x1b[0m\x1b[?25h\x1b[?1049l");
+        if !self.input.is_closed() {
+            // 1000: normal closure.
+            let _ = self
+                .renderer
+                .writer_mut()
+                .send(frame::CLOSE, &1000u16.to_be_bytes());
+        }
+    }
+}
+
+impl Output for WebSocket {
+    type Error = io::Error;
+
+    fn draw_layers<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = DrawCell<'a>>,
+    {
+        let size = self.size;
+        let content =
+            content.filter(move |cell| cell.pos.x < size.width() && cell.pos.y < size.height());
+        self.renderer.draw_frame(content)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.renderer.end_frame()
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn resize(&mut self, size: Size) {
+        self.size = size;
+        // The browser terminal reflows its own screen on a resize; forget tracked cursor and
+        // color state so the next draw re-emits it all.
+        self.renderer.reset_state();
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.renderer.clear_screen()
+    }
+}
+
+impl SocketSession for WebSocket {
+    fn input(&self) -> &SocketInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut SocketInput {
+        &mut self.input
+    }
+
+    fn receive(&mut self, bytes: &[u8]) {
+        self.decoder.push(bytes);
+        self.receive_messages();
+    }
+}
+
+impl Input for WebSocket {
+    fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        session::poll_event(self, timeout)
+    }
+
+    fn push_event(&mut self, event: Event) {
+        self.input.push(event);
+    }
+
+    /// Takes whatever the browser has already sent, then parks until the socket has more or an
+    /// event is sent; see [`session::poll_next_event`].
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        session::poll_next_event(self, cx)
+    }
+
+    fn event_sender(&mut self) -> Option<EventSender> {
+        Some(self.input.event_sender())
+    }
+}
+
+impl Cursor for WebSocket {
+    fn set_cursor_visible(&mut self, visible: bool) {
+        let _ = self.renderer.set_cursor_visible(visible);
+    }
+
+    fn set_cursor_position(&mut self, position: Pos) {
+        let _ = self.renderer.move_cursor_to(position);
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) {
+        let _ = self.renderer.set_cursor_style(style);
+    }
+}
+
+/// The browser clipboard, relayed through the driver; see the crate docs.
+impl Clipboard for WebSocket {
+    /// Returns the text last reported by the driver or copied by the app, and fails if there is
+    /// neither.
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.clipboard
+            .clone()
+            .ok_or_else(|| ClipboardError::new("the browser clipboard has not been reported"))
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.clipboard = Some(text.to_owned());
+        self.renderer
+            .writer_mut()
+            .send(frame::TEXT, format!("c {text}").as_bytes())
+            .map_err(|err| ClipboardError::new(err.to_string()))
+    }
+}
+
+impl SessionOptions for WebSocketOptions {
+    type Session = WebSocket;
+
+    const PROTOCOL: &'static str = "websocket";
+
+    fn build(self, stream: TcpStream) -> io::Result<WebSocket> {
+        Self::build(self, stream)
+    }
+}
+
+/// Listens for browser connections and serves each one its own
+/// [`App`](retroglyph_core::app::App), accepting each one's handshake before its app starts.
+///
+/// See the crate docs for an example, and [`SocketServer`] for its methods.
+pub type WebSocketServer = SocketServer<WebSocketOptions>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retroglyph_core::app::{App, Flow, Frame, RunOptions};
+    use retroglyph_core::terminal::Terminal;
+    use retroglyph_terminal::session::Session;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    /// A connected `(server side, client side)` pair over loopback.
+    fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (server, client)
+    }
+
+    const UPGRADE: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+        Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+        Sec-WebSocket-Version: 13\r\n\r\n";
+
+    /// A masked client text frame.
+    fn text(message: &str) -> Vec<u8> {
+        let len = u8::try_from(message.len()).unwrap();
+        assert!(len <= 125);
+        let mut frame = vec![0x81, 0x80 | len, 0, 0, 0, 0];
+        frame.extend_from_slice(message.as_bytes());
+        frame
+    }
+
+    fn quick() -> WebSocketOptions {
+        WebSocketOptions::new().handshake_timeout(Duration::from_millis(200))
+    }
+
+    #[test]
+    fn options_default_to_the_handshake_timeout_truecolor_and_80x24() {
+        let options = WebSocketOptions::new();
+        assert_eq!(options.handshake_timeout, HANDSHAKE_TIMEOUT);
+        assert_eq!(options.color_support, ColorSupport::Truecolor);
+        assert_eq!(options.default_size, Size::new(80, 24));
+    }
+
+    #[test]
+    fn a_size_sent_with_the_upgrade_sizes_the_session() {
+        let (server, mut client) = socket_pair();
+        let mut request = UPGRADE.to_vec();
+        request.extend(text("r 100 30"));
+        client.write_all(&request).unwrap();
+        let socket = quick().build(server).unwrap();
+        assert_eq!(socket.size(), Size::new(100, 30));
+    }
+
+    #[test]
+    fn a_driver_that_never_sends_a_size_gets_the_default() {
+        let (server, mut client) = socket_pair();
+        client.write_all(UPGRADE).unwrap();
+        let socket = quick().default_size(40, 12).build(server).unwrap();
+        assert_eq!(socket.size(), Size::new(40, 12));
+    }
+
+    #[test]
+    fn a_plain_http_request_is_refused() {
+        let (server, mut client) = socket_pair();
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let err = quick().build(server).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+    }
+
+    #[test]
+    fn a_disconnect_is_one_close_event() {
+        let (server, mut client) = socket_pair();
+        client.write_all(UPGRADE).unwrap();
+        let mut socket = quick().build(server).unwrap();
+        drop(client);
+        assert_eq!(
+            socket.poll_event(Duration::from_secs(5)),
+            Some(Event::Close)
+        );
+        assert_eq!(socket.poll_event(Duration::ZERO), None);
+        // Polling again idles for the timeout rather than returning straight away.
+        let start = Instant::now();
+        assert_eq!(socket.poll_event(Duration::from_millis(20)), None);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn a_served_session_ends_even_if_the_app_ignores_close() {
+        struct IgnoresClose;
+
+        impl App<WebSocket> for IgnoresClose {
+            fn update(&mut self, term: &mut Terminal<WebSocket>, _frame: &Frame) -> Flow {
+                while term.poll(Duration::ZERO).is_some() {}
+                Flow::Idle
+            }
+        }
+
+        let (server, mut client) = socket_pair();
+        client.write_all(UPGRADE).unwrap();
+        let socket = quick().build(server).unwrap();
+        let (done, ended) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = retroglyph_core::app::run_with(
+                socket,
+                Session(IgnoresClose),
+                RunOptions::default(),
+            );
+            let _ = done.send(());
+        });
+        drop(client);
+        assert!(ended.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn sent_events_wake_a_waiting_poll() {
+        let (server, mut client) = socket_pair();
+        client.write_all(UPGRADE).unwrap();
+        let mut socket = quick().build(server).unwrap();
+        let sender = socket.event_sender().unwrap();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            sender.send(Event::FocusGained).unwrap();
+        });
+        assert_eq!(
+            socket.poll_event(Duration::from_secs(5)),
+            Some(Event::FocusGained)
+        );
+        thread.join().unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+//! The text messages a browser driver sends: input in the encodings `retroglyph-terminal-wasm`'s
+//! FFI already defines, and the terminal's size. See the crate docs for the format.
+
+// `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary
+// is intentional, so it's allowed crate-locally.
+#![allow(clippy::redundant_pub_crate)]
+
+use retroglyph_core::event::Event;
+use retroglyph_terminal_wasm::{decode_key_event, decode_mouse_event};
+
+/// One input message, decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Incoming {
+    /// Key, mouse, paste, or focus input.
+    Event(Event),
+    /// The browser terminal's size in cells.
+    Resize(u16, u16),
+    /// The browser clipboard's text, reported by the driver.
+    Clipboard(String),
+}
+
+/// Decodes one text message, or `None` if it isn't one this crate knows or doesn't decode to an
+/// event (an unknown key code, say), the same cases the in-browser FFI silently drops.
+pub(crate) fn parse(message: &str) -> Option<Incoming> {
+    let (kind, rest) = message.split_once(' ').unwrap_or((message, ""));
+    // Paste and clipboard text is taken verbatim, spaces and all.
+    match kind {
+        "p" => return Some(Incoming::Event(Event::Paste(rest.to_owned()))),
+        "c" => return Some(Incoming::Clipboard(rest.to_owned())),
+        _ => {}
+    }
+    let mut fields = rest.split(' ');
+    let mut next = || fields.next()?.parse::<u32>().ok();
+    let incoming = match kind {
+        "k" => {
+            let (code, mods) = (next()?, next()?);
+            Incoming::Event(Event::Key(decode_key_event(
+                code,
+                u8::try_from(mods).ok()?,
+            )?))
+        }
+        "m" => {
+            let mut small = || u16::try_from(next()?).ok();
+            let (x, y) = (small()?, small()?);
+            let (action, button, mods) = (small()?, small()?, small()?);
+            let byte = |value: u16| u8::try_from(value).ok();
+            Incoming::Event(Event::Mouse(decode_mouse_event(
+                x,
+                y,
+                byte(action)?,
+                byte(button)?,
+                byte(mods)?,
+            )?))
+        }
+        "r" => {
+            let mut dimension = || u16::try_from(next()?).ok().filter(|&d| d > 0);
+            Incoming::Resize(dimension()?, dimension()?)
+        }
+        "f" => match next()? {
+            0 => Incoming::Event(Event::FocusLost),
+            _ => Incoming::Event(Event::FocusGained),
+        },
+        _ => return None,
+    };
+    Some(incoming)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retroglyph_core::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use retroglyph_core::grid::Pos;
+    use retroglyph_terminal_wasm::{key_codes, mouse_actions, mouse_buttons};
+
+    #[test]
+    fn keys_and_mouse_use_the_ffi_encodings() {
+        assert_eq!(
+            parse(&format!("k {} 2", key_codes::LEFT)),
+            Some(Incoming::Event(Event::Key(KeyEvent::new(
+                KeyCode::Left,
+                KeyModifiers::CONTROL
+            ))))
+        );
+        assert_eq!(
+            parse(&format!(
+                "m 3 4 {} {} 0",
+                mouse_actions::DOWN,
+                mouse_buttons::RIGHT
+            )),
+            Some(Incoming::Event(Event::Mouse(MouseEvent::new(
+                MouseEventKind::Down(MouseButton::Right),
+                Pos { x: 3, y: 4 },
+                KeyModifiers::NONE
+            ))))
+        );
+    }
+
+    #[test]
+    fn paste_and_clipboard_text_keep_their_spaces() {
+        assert_eq!(
+            parse("p  two  spaces "),
+            Some(Incoming::Event(Event::Paste(" two  spaces ".to_owned())))
+        );
+        assert_eq!(
+            parse("c seed 42"),
+            Some(Incoming::Clipboard("seed 42".to_owned()))
+        );
+        assert_eq!(
+            parse("p"),
+            Some(Incoming::Event(Event::Paste(String::new())))
+        );
+    }
+
+    #[test]
+    fn resize_and_focus() {
+        assert_eq!(parse("r 120 40"), Some(Incoming::Resize(120, 40)));
+        assert_eq!(parse("r 0 40"), None);
+        assert_eq!(parse("f 1"), Some(Incoming::Event(Event::FocusGained)));
+        assert_eq!(parse("f 0"), Some(Incoming::Event(Event::FocusLost)));
+    }
+
+    #[test]
+    fn unknown_or_malformed_messages_are_dropped() {
+        assert_eq!(parse("x 1 2"), None);
+        assert_eq!(parse("k 55296 0"), None);
+        assert_eq!(parse("k 97 256"), None);
+        assert_eq!(parse("m 1 2"), None);
+        assert_eq!(parse("r 80"), None);
+        assert_eq!(parse(""), None);
+    }
+}
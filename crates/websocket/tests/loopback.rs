@@ -0,0 +1,271 @@
+//! End-to-end: a [`WebSocketServer`] on loopback, driven by a minimal WebSocket client that does
+//! what `js/websocket-pkg.js` does in a browser: reports its size, sends keys and mouse input,
+//! resizes, copies, and closes.
+
+use retroglyph_core::app::{App, Flow, Frame};
+use retroglyph_core::backend::Clipboard;
+use retroglyph_core::color::Style;
+use retroglyph_core::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use retroglyph_core::grid::{Pos, Size};
+use retroglyph_core::terminal::Terminal;
+use retroglyph_terminal_wasm::{key_codes, mouse_actions, mouse_buttons};
+use retroglyph_websocket::{WebSocket, WebSocketServer};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// What the session's app reports back to the test.
+#[derive(Debug, PartialEq)]
+enum Seen {
+    Started(Size),
+    Event(Event),
+}
+
+/// Reports everything it sees, draws how many keys it has seen so far, and copies `seed` when
+/// `c` is pressed.
+struct Scripted {
+    seen: mpsc::Sender<Seen>,
+    keys: usize,
+    drawn: bool,
+}
+
+impl App<WebSocket> for Scripted {
+    fn init(&mut self, term: &mut Terminal<WebSocket>) {
+        let _ = self.seen.send(Seen::Started(term.size()));
+    }
+
+    fn update(&mut self, term: &mut Terminal<WebSocket>, _frame: &Frame) -> Flow {
+        let mut changed = !self.drawn;
+        while let Some(event) = term.poll(Duration::ZERO) {
+            changed = true;
+            let close = event == Event::Close;
+            if let Event::Key(key) = &event {
+                self.keys += 1;
+                if key.code == KeyCode::Char('c') {
+                    term.backend_mut().set_clipboard_text("seed").unwrap();
+                }
+            }
+            let _ = self.seen.send(Seen::Event(event));
+            if close {
+                return Flow::Exit;
+            }
+        }
+        if !changed {
+            return Flow::Idle;
+        }
+        self.drawn = true;
+        term.surface()
+            .print((0, 0), &format!("keys={}", self.keys), Style::default());
+        Flow::Continue
+    }
+}
+
+/// The browser end: masks what it sends, and reads with a deadline so a broken server fails the
+/// test instead of hanging it.
+struct Client {
+    stream: TcpStream,
+    buffered: Vec<u8>,
+    /// Binary (ANSI) output received so far.
+    output: Vec<u8>,
+    /// Text messages received so far.
+    texts: Vec<String>,
+    closed: bool,
+}
+
+impl Client {
+    fn connect(addr: std::net::SocketAddr) -> Self {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(
+                b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                  Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .unwrap();
+        let mut client = Self {
+            stream,
+            buffered: Vec::new(),
+            output: Vec::new(),
+            texts: Vec::new(),
+            closed: false,
+        };
+        client.fill_until(|c| c.buffered.windows(4).any(|w| w == b"\r\n\r\n"));
+        let end = client
+            .buffered
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .unwrap()
+            + 4;
+        let head = String::from_utf8(client.buffered.drain(..end).collect()).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+        assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+        client
+    }
+
+    fn send_frame(&mut self, opcode: u8, payload: &[u8]) {
+        let mask = [0xa1, 0xb2, 0xc3, 0xd4];
+        let mut frame = vec![0x80 | opcode];
+        let len = u8::try_from(payload.len()).unwrap();
+        assert!(len <= 125);
+        frame.push(0x80 | len);
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+        self.stream.write_all(&frame).unwrap();
+    }
+
+    fn send(&mut self, message: &str) {
+        self.send_frame(0x1, message.as_bytes());
+    }
+
+    /// Reads until `done` holds, decoding frames as they complete.
+    fn fill_until(&mut self, done: impl Fn(&Self) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut buf = [0; 4096];
+        loop {
+            self.decode_frames();
+            if done(self) {
+                return;
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            assert!(!left.is_zero(), "timed out");
+            self.stream.set_read_timeout(Some(left)).unwrap();
+            let len = self.stream.read(&mut buf).unwrap();
+            assert_ne!(len, 0, "server hung up");
+            self.buffered.extend_from_slice(&buf[..len]);
+        }
+    }
+
+    /// Decodes the server's (unmasked, unfragmented) frames out of what has been read, once the
+    /// handshake response is out of the way.
+    fn decode_frames(&mut self) {
+        if self.buffered.starts_with(b"HTTP/") {
+            return;
+        }
+        loop {
+            let &[first, second, ..] = &self.buffered[..] else {
+                return;
+            };
+            let (len, at) = match second {
+                126 if self.buffered.len() >= 4 => (
+                    usize::from(u16::from_be_bytes([self.buffered[2], self.buffered[3]])),
+                    4,
+                ),
+                127 if self.buffered.len() >= 10 => {
+                    let mut len = [0; 8];
+                    len.copy_from_slice(&self.buffered[2..10]);
+                    (usize::try_from(u64::from_be_bytes(len)).unwrap(), 10)
+                }
+                126 | 127 => return,
+                len => (usize::from(len), 2),
+            };
+            if self.buffered.len() < at + len {
+                return;
+            }
+            let payload: Vec<u8> = self.buffered.drain(..at + len).skip(at).collect();
+            match first & 0x0f {
+                0x1 => self.texts.push(String::from_utf8(payload).unwrap()),
+                0x2 => self.output.extend(payload),
+                0x8 => self.closed = true,
+                _ => {}
+            }
+        }
+    }
+
+    fn read_output_until(&mut self, what: &[u8]) {
+        self.fill_until(|c| c.output.windows(what.len()).any(|w| w == what));
+    }
+}
+
+fn next(seen: &mpsc::Receiver<Seen>) -> Seen {
+    seen.recv_timeout(Duration::from_secs(10)).unwrap()
+}
+
+fn serve() -> (std::net::SocketAddr, mpsc::Receiver<Seen>) {
+    let server = WebSocketServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let (tx, seen) = mpsc::channel();
+    std::thread::spawn(move || {
+        server.serve(move || Scripted {
+            seen: tx.clone(),
+            keys: 0,
+            drawn: false,
+        })
+    });
+    (addr, seen)
+}
+
+#[test]
+fn a_browser_sizes_types_clicks_resizes_copies_and_closes() {
+    let (addr, seen) = serve();
+    let mut client = Client::connect(addr);
+    client.send("r 100 30");
+    assert_eq!(next(&seen), Seen::Started(Size::new(100, 30)));
+    client.read_output_until(b"keys=0");
+
+    client.send(&format!("k {} 0", u32::from('q')));
+    client.send(&format!("k {} 2", key_codes::UP));
+    assert_eq!(
+        next(&seen),
+        Seen::Event(Event::Key(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE
+        )))
+    );
+    assert_eq!(
+        next(&seen),
+        Seen::Event(Event::Key(KeyEvent::new(
+            KeyCode::Up,
+            KeyModifiers::CONTROL
+        )))
+    );
+    // Only the changed cell is redrawn: the count's digit, sixth column of the first row.
+    client.read_output_until(b"\x1b[1;6H2");
+
+    client.send(&format!(
+        "m 7 8 {} {} 1",
+        mouse_actions::DOWN,
+        mouse_buttons::LEFT
+    ));
+    assert_eq!(
+        next(&seen),
+        Seen::Event(Event::Mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            Pos { x: 7, y: 8 },
+            KeyModifiers::SHIFT
+        )))
+    );
+
+    client.send("r 120 40");
+    assert_eq!(next(&seen), Seen::Event(Event::Resize(120, 40)));
+
+    client.send("k 99 0");
+    assert!(matches!(next(&seen), Seen::Event(Event::Key(_))));
+    client.fill_until(|c| !c.texts.is_empty());
+    assert_eq!(client.texts, ["c seed"]);
+
+    // Ping is answered, then close: the server answers with its own close frame.
+    client.send_frame(0x9, b"hi");
+    client.send_frame(0x8, &1000u16.to_be_bytes());
+    assert_eq!(next(&seen), Seen::Event(Event::Close));
+    client.fill_until(|c| c.closed);
+}
+
+#[test]
+fn every_connection_gets_its_own_app() {
+    let (addr, seen) = serve();
+    let mut first = Client::connect(addr);
+    first.send("r 20 5");
+    let mut second = Client::connect(addr);
+    second.send("r 20 5");
+    for _ in 0..2 {
+        assert_eq!(next(&seen), Seen::Started(Size::new(20, 5)));
+    }
+
+    first.send(&format!("k {} 0", u32::from('a')));
+    first.read_output_until(b"\x1b[1;6H1");
+    second.read_output_until(b"keys=0");
+    assert!(!second.output.windows(7).any(|w| w == b"\x1b[1;6H1"));
+}
@@ -43,10 +43,15 @@ fn place(vertex_index: u32, instance_index: u32, cell: CellInput, offset_scale:
     let corner = corner_of(vertex_index);
     let col = f32(instance_index % u.cols);
     let row = f32(instance_index / u.cols);
+    // A double-size row (line size 1-3, see `instance::line_flags`) draws each cell two columns
+    // wide, and a double-height half (2 = top, 3 = bottom) stretches the glyph to twice the row's
+    // height and shows only its half.
+    let line = (cell.glyph_flags.y >> 2u) & 3u;
+    let stretch = vec2<f32>(select(1.0, 2.0, line != 0u), select(1.0, 2.0, line >= 2u));
     // dx/dy are in unscaled font pixels; `u.cell / u.glyph` is the integer render scale, so this
     // converts them to physical pixels.
-    let shift = vec2<f32>(cell.offset) * (u.cell / u.glyph) * offset_scale;
-    let px = (vec2<f32>(col, row) + corner) * u.cell + shift;
+    let shift = vec2<f32>(cell.offset) * (u.cell / u.glyph) * stretch * offset_scale;
+    let px = (vec2<f32>(col * stretch.x, row) + corner * vec2<f32>(stretch.x, 1.0)) * u.cell + shift;
 
     var out: CellVarying;
     // Pixel space (y-down, origin top-left) -> clip space (y-up). Flipping y here means the atlas
@@ -58,6 +63,9 @@ fn place(vertex_index: u32, instance_index: u32, cell: CellInput, offset_scale:
         1.0,
     );
     out.uv = corner;
+    if line >= 2u {
+        out.uv.y = corner.y * 0.5 + select(0.0, 0.5, line == 3u);
+    }
     out.slot = cell.glyph_flags.x;
     out.flags = cell.glyph_flags.y;
     out.fg = cell.fg;
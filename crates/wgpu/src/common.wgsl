@@ -20,9 +20,14 @@ struct Uniforms {
     // Glyph columns and rows packed per glyph-atlas array layer.
     atlas_cols: u32,
     atlas_rows: u32,
-    // Pads the block to 48 bytes so its size is a multiple of 16, as the uniform address space
-    // requires.
+    // Pads `crt` onto a 16-byte boundary, as the uniform address space requires of a `vec4`.
     pad: u32,
+    // CRT post-processing strengths: scanlines, glow, curvature, fringe. All zero when the effects
+    // are off; only `post.wgsl` reads them.
+    crt: vec4<f32>,
+    // The rectangle of the target the frame is scaled into: origin x, y, then width, height, in
+    // physical pixels. All zero when the frame isn't scaled; only `scale.wgsl` reads it.
+    viewport: vec4<f32>,
 }
 
 @group(0) @binding(0) var<uniform> u: Uniforms;
@@ -8,7 +8,10 @@
 
 use crate::WgpuRenderer;
 use retroglyph_window::atlas::{GlyphAtlas, MAX_SLOTS};
+use retroglyph_window::crt::CrtEffects;
 use retroglyph_window::font::FontChain;
+use retroglyph_window::palette::Palette;
+use retroglyph_window::scaling::ScalingMode;
 #[cfg(feature = "tilesets")]
 use retroglyph_window::tileset::TilesetOptions;
 use std::fmt;
@@ -108,6 +111,9 @@ pub struct WgpuBackendBuilder {
     cols: u16,
     rows: u16,
     scale: u16,
+    palette: Palette,
+    crt: CrtEffects,
+    scaling: ScalingMode,
 }
 
 impl Default for WgpuBackendBuilder {
@@ -128,6 +134,9 @@ impl WgpuBackendBuilder {
             cols: 80,
             rows: 25,
             scale: 1,
+            palette: Palette::DEFAULT,
+            crt: CrtEffects::NONE,
+            scaling: ScalingMode::Reflow,
         }
     }
 
@@ -184,6 +193,37 @@ impl WgpuBackendBuilder {
         self
     }
 
+    /// Sets the palette [`Color::Ansi`](retroglyph_core::color::Color::Ansi),
+    /// [`Color::Indexed`](retroglyph_core::color::Color::Indexed), and
+    /// [`Color::Default`](retroglyph_core::color::Color::Default) resolve through. Defaults to
+    /// [`Palette::DEFAULT`]; change it on the built renderer with
+    /// [`WgpuRenderer::set_palette`](WgpuRenderer::set_palette).
+    #[must_use]
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Sets the CRT post-processing effects (scanlines, glow, curvature, color fringing) drawn
+    /// over every frame in a fullscreen pass. Defaults to [`CrtEffects::NONE`], which skips the
+    /// pass; toggle them on the built renderer with
+    /// [`WgpuRenderer::set_crt`](WgpuRenderer::set_crt).
+    #[must_use]
+    pub const fn crt(mut self, effects: CrtEffects) -> Self {
+        self.crt = effects;
+        self
+    }
+
+    /// Sets how the frame is fitted into the window when the window is resized. Defaults to
+    /// [`ScalingMode::Reflow`], which resizes the grid instead; the other modes draw the frame
+    /// offscreen and scale it into the window with the mode's
+    /// [`ScaleFilter`](retroglyph_window::scaling::ScaleFilter).
+    #[must_use]
+    pub const fn scaling(mut self, mode: ScalingMode) -> Self {
+        self.scaling = mode;
+        self
+    }
+
     /// Registers a PNG sprite tileset. Glyphs a tileset maps override the bitmap font for those
     /// codepoints; register multiple and later ones win on codepoint collision. Build the options
     /// with [`TilesetOptions::builder`](retroglyph_window::tileset::TilesetOptions::builder).
@@ -236,7 +276,15 @@ impl WgpuBackendBuilder {
             return Err(WgpuBackendError::FontChainTooLarge);
         }
         #[cfg_attr(not(feature = "tilesets"), allow(unused_mut))]
-        let mut renderer = WgpuRenderer::new(glyphs, self.cols, self.rows, self.scale);
+        let mut renderer = WgpuRenderer::new(
+            glyphs,
+            self.cols,
+            self.rows,
+            self.scale,
+            self.palette,
+            self.crt,
+            self.scaling,
+        );
         #[cfg(feature = "tilesets")]
         {
             let cache = retroglyph_window::sprite_cache::SpriteCache::from_tilesets(&self.tilesets)
@@ -255,8 +303,8 @@ impl WgpuBackendBuilder {
     // `unnecessary_wraps`/`const` suggestions here.
     #[allow(clippy::unnecessary_wraps, clippy::missing_const_for_fn)]
     fn resolve_fonts(&self) -> Result<FontChain<'static>, WgpuBackendError> {
-        if let Some(fonts) = self.fonts {
-            return Ok(fonts);
+        if let Some(fonts) = &self.fonts {
+            return Ok(fonts.clone());
         }
         #[cfg(feature = "default-font")]
         {
@@ -289,6 +337,18 @@ impl retroglyph_window::presenter_builder::PresenterBuilder for WgpuBackendBuild
         self.font(fonts)
     }
 
+    fn palette(self, palette: Palette) -> Self {
+        self.palette(palette)
+    }
+
+    fn crt(self, effects: CrtEffects) -> Self {
+        self.crt(effects)
+    }
+
+    fn scaling(self, mode: ScalingMode) -> Self {
+        self.scaling(mode)
+    }
+
     #[cfg(feature = "tilesets")]
     fn tileset(self, opts: TilesetOptions) -> Self {
         self.tileset(opts)
@@ -255,6 +255,45 @@ fn assert_frames_match(frame: &Frame, software: &[u32]) {
     }
 }
 
+/// Asserts a readback frame matches `software` to within `tolerance` per channel, allowing at most
+/// `max_outliers` (a fraction of all pixels) to be off by more.
+fn assert_frames_close(frame: &Frame, software: &[u32], tolerance: u8, max_outliers: f64) {
+    assert_eq!(
+        software.len(),
+        (frame.width * frame.height) as usize,
+        "the two backends disagree on the surface size"
+    );
+    let mut outliers = Vec::new();
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let packed = software[(y * frame.width + x) as usize];
+            #[allow(clippy::cast_possible_truncation)]
+            let expected = [(packed >> 16) as u8, (packed >> 8) as u8, packed as u8];
+            let (r, g, b) = frame.rgb(x, y);
+            if [r, g, b]
+                .iter()
+                .zip(expected)
+                .any(|(got, want)| got.abs_diff(want) > tolerance)
+            {
+                outliers.push(format!("({x},{y}): gpu={:?} cpu={expected:?}", (r, g, b)));
+            }
+        }
+    }
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let allowed = (f64::from(frame.width * frame.height) * max_outliers) as usize;
+    assert!(
+        outliers.len() <= allowed,
+        "{} pixels differ from the software reference by more than {tolerance} (allowed \
+         {allowed}): {}",
+        outliers.len(),
+        outliers[..outliers.len().min(8)].join(", ")
+    );
+}
+
 /// A deterministic single-layer grid covering blanks, glyphs, colors, and sub-cell offsets.
 fn sample_grid(cols: u16, rows: u16) -> Vec<(Pos, Tile)> {
     let mut cells = Vec::new();
@@ -430,14 +469,15 @@ fn glyph_matches_font_coverage_fg_vs_bg() {
     let glyph = r.glyphs.fonts().resolve('A').expect("'A' is in CP437");
     let gw = u32::from(glyph.font().glyph_width());
     let gh = u32::from(glyph.font().glyph_height());
-    let rows: Vec<u8> = glyph.rows().to_vec();
+    // Decoded through the font, the same as the atlas builder, rather than re-deriving the row
+    // layout here.
+    let set: Vec<(u8, u8)> = glyph.font().glyph_pixels(glyph.index()).collect();
     let frame = render_to_frame(&mut r, device);
 
     for y in 0..gh {
-        let mask = rows[y as usize];
         for x in 0..gw {
-            // Bit 7 (MSB) is the leftmost pixel, matching the atlas builder.
-            let set = (mask >> (7 - x)) & 1 == 1;
+            #[allow(clippy::cast_possible_truncation)]
+            let set = set.contains(&(x as u8, y as u8));
             let expected = if set { RED } else { BLUE };
             assert_eq!(frame.rgb(x, y), expected, "glyph pixel ({x},{y})");
         }
@@ -484,6 +524,91 @@ fn matches_software_backend_with_layers_pixel_for_pixel() {
     assert_frames_match(&frame, cpu.pixels());
 }
 
+/// The CRT pass is the one place parity is approximate: `post.wgsl` and the CPU reference evaluate
+/// the same `f32` formula, but a GPU may fuse or reorder its multiply-adds, so a channel may round
+/// a step or two apart and a sample right on a pixel boundary may floor to its neighbour.
+#[test]
+fn crt_effects_match_the_software_reference_within_tolerance() {
+    use retroglyph_window::crt::CrtEffects;
+    let Some(device) = device_or_skip("crt_effects_match_the_software_reference") else {
+        return;
+    };
+
+    let (cols, rows, scale) = (8u16, 5u16, 2u16);
+    let cells = sample_grid(cols, rows);
+
+    let mut gpu = renderer(cols, rows, scale);
+    gpu.set_crt(CrtEffects::CLASSIC);
+    paint(&mut gpu, &cells);
+    let frame = render_to_frame(&mut gpu, device);
+
+    let mut cpu = software(cols, rows, scale);
+    cpu.set_crt(CrtEffects::CLASSIC);
+    paint(&mut cpu, &cells);
+    assert_ne!(
+        cpu.output_pixels(),
+        cpu.pixels(),
+        "the effects changed nothing"
+    );
+    assert_frames_close(&frame, cpu.output_pixels(), 2, 0.01);
+
+    // Toggled off at runtime, the pass is skipped and parity is exact again.
+    let Some(device) = device_or_skip("crt_effects_match_the_software_reference") else {
+        return;
+    };
+    gpu.set_crt(CrtEffects::NONE);
+    let frame = render_to_frame(&mut gpu, device);
+    assert_frames_match(&frame, cpu.pixels());
+}
+
+#[test]
+fn integer_scaling_doubles_the_frame_between_bars() {
+    use retroglyph_window::presenter::Presenter as _;
+    use retroglyph_window::scaling::ScalingMode;
+    let Some(device) = device_or_skip("integer_scaling_doubles_the_frame_between_bars") else {
+        return;
+    };
+
+    let (cols, rows) = (4u16, 3u16);
+    let cells = sample_grid(cols, rows);
+    let bars = (0x20, 0x40, 0x60);
+    let mut gpu = WgpuBackendBuilder::new()
+        .grid_size(cols, rows)
+        .scaling(ScalingMode::Integer { bars })
+        .build()
+        .expect("default-font builds a renderer");
+    paint(&mut gpu, &cells);
+    let (frame_w, frame_h) = gpu.geometry.surface_size(cols, rows);
+    // Room for 2x but not 3x on either axis, with an odd margin left over on each.
+    gpu.resize_surface(frame_w * 2 + 7, frame_h * 2 + 5);
+    let viewport = gpu
+        .geometry()
+        .viewport()
+        .expect("a scaling mode reports its viewport");
+    assert_eq!(viewport.size, (frame_w * 2, frame_h * 2));
+    let frame = render_to_frame(&mut gpu, device);
+
+    let mut cpu = software(cols, rows, 1);
+    paint(&mut cpu, &cells);
+    let pixels = cpu.pixels();
+
+    let (left, top) = viewport.origin;
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let inside = (left..left + viewport.size.0).contains(&x)
+                && (top..top + viewport.size.1).contains(&y);
+            #[allow(clippy::cast_possible_truncation)]
+            let expected = if inside {
+                let px = pixels[(((y - top) / 2) * frame_w + (x - left) / 2) as usize];
+                ((px >> 16) as u8, (px >> 8) as u8, px as u8)
+            } else {
+                bars
+            };
+            assert_eq!(frame.rgb(x, y), expected, "pixel ({x}, {y})");
+        }
+    }
+}
+
 /// A sub-cell offset must move only the glyph, never the cell's background fill, and the spill must
 /// reach a neighbour identically in every direction. Checking that against the CPU rasterizer at a
 /// scale above 1 is the strongest available statement of the shared spill contract: the shader
@@ -614,6 +739,96 @@ fn matches_software_backend_for_multicell_spans() {
     assert_frames_match(&frame, cpu.pixels());
 }
 
+/// Two 16x16 double-width glyphs: `'語'` fills the top half of its left cell and the bottom half
+/// of its right one, `'字'` a checkerboard of 2x2 blocks, so a half landing in the wrong cell (or
+/// the wrong way round) shows up as a pixel difference.
+static DOUBLE_WIDTH_DATA: [u8; 64] = {
+    let mut data = [0; 64];
+    let mut row = 0;
+    while row < 16 {
+        let (left, right) = if row < 8 { (0xFF, 0) } else { (0, 0xFF) };
+        data[row * 2] = left;
+        data[row * 2 + 1] = right;
+        let checker = if row % 4 < 2 {
+            0b1100_1100
+        } else {
+            0b0011_0011
+        };
+        data[32 + row * 2] = checker;
+        data[32 + row * 2 + 1] = checker;
+        row += 1;
+    }
+    data
+};
+
+/// The default font plus [`DOUBLE_WIDTH_DATA`] as its double-width font.
+fn double_width_fonts() -> retroglyph_window::font::FontChain<'static> {
+    use retroglyph_window::font::{BitmapFont, FontChain, unscii16};
+    const CHARSET: [char; 2] = ['語', '字'];
+    FontChain::from(unscii16::FONT).with_wide_font(BitmapFont::with_charset(
+        &DOUBLE_WIDTH_DATA,
+        16,
+        16,
+        2,
+        &CHARSET,
+    ))
+}
+
+/// A wide character's double-width glyph is drawn across its cell and its spacer, the same as
+/// the CPU rasterizer draws it: on layer 0, on a higher layer with a sub-cell offset that spills
+/// the right half past the spacer, and next to a wide character no font covers, which keeps the
+/// single-cell notdef path. The scene comes straight off a [`Grid`](retroglyph_core::grid::Grid),
+/// which is what writes the lead/spacer pairs.
+#[test]
+fn matches_software_backend_for_double_width_glyphs() {
+    use retroglyph_core::grid::Grid;
+
+    let Some(device) = device_or_skip("matches_software_backend_for_double_width_glyphs") else {
+        return;
+    };
+
+    let (cols, rows, scale) = (6u16, 2u16, 2u16);
+    let style = Style::new()
+        .fg(Color::rgb(GREEN.0, GREEN.1, GREEN.2))
+        .bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+    let mut grid = Grid::new(cols, rows);
+    grid.put_tile(0, (0, 0), Tile::new('語', style));
+    grid.put_tile(0, (2, 0), Tile::new('字', style));
+    grid.put_tile(0, (4, 0), Tile::new('あ', style));
+    grid.put_tile(1, (1, 1), Tile::new('語', style).with_offset(3, -2));
+    let scene: Vec<(u8, Pos, Tile)> = grid
+        .layers()
+        .map(|cell| (cell.layer, cell.pos, *cell.tile))
+        .collect();
+
+    let mut gpu = WgpuBackendBuilder::new()
+        .font(double_width_fonts())
+        .grid_size(cols, rows)
+        .scale(scale)
+        .build()
+        .expect("chain builds");
+    paint_layers(&mut gpu, &scene);
+    let frame = render_to_frame(&mut gpu, device);
+
+    let mut cpu = retroglyph_software::config::SoftwareBackendBuilder::new()
+        .font(double_width_fonts())
+        .grid_size(cols, rows)
+        .scale(scale)
+        .build()
+        .expect("chain builds")
+        .into_renderer()
+        .expect("headless software renderer");
+    paint_layers(&mut cpu, &scene);
+
+    assert_frames_match(&frame, cpu.pixels());
+    // And the glyph really spans both cells: '語''s top-left quadrant is lit, the spacer's is not,
+    // and the spacer's bottom half is.
+    let (cw, _) = gpu.geometry.cell_size();
+    assert_eq!(frame.rgb(0, 0), GREEN);
+    assert_eq!(frame.rgb(cw, 0), BLUE);
+    assert_eq!(frame.rgb(cw, 20), GREEN);
+}
+
 #[cfg(feature = "tilesets")]
 mod sprites {
     use super::{
@@ -717,7 +932,7 @@ mod sprites {
         let Some(device) = device_or_skip("mask_sprites_take_the_cell_foreground") else {
             return;
         };
-        // A mid-grey sheet times a mid-grey foreground is where integer and float rounding diverge.
+        // A mid-gray sheet times a mid-gray foreground is where integer and float rounding diverge.
         let png = sheet((0x80, 0x80, 0x80), 8);
         let (mut gpu, mut cpu) = pair(1, 1, 1, &png, 8, SheetColor::Mask);
 
@@ -738,4 +953,190 @@ mod sprites {
         let frame = render_to_frame(&mut gpu, device);
         assert_frames_match(&frame, cpu.pixels());
     }
+
+    /// Animated tileset entries draw the same frame on both backends at every timestamp: one
+    /// declared through `animate`, one through a metadata file, on a grid that never changes.
+    #[test]
+    fn animated_sprites_match_the_cpu_blit_at_every_timestamp() {
+        use core::time::Duration;
+        use retroglyph_window::animation::SpriteAnimation;
+        use retroglyph_window::presenter::Presenter as _;
+
+        // A 2-tile sheet: tile 0 red, tile 1 green.
+        let mut img = image::RgbaImage::new(16, 8);
+        for (x, _, px) in img.enumerate_pixels_mut() {
+            *px = if x < 8 {
+                image::Rgba([RED.0, RED.1, RED.2, 0xFF])
+            } else {
+                image::Rgba([GREEN.0, GREEN.1, GREEN.2, 0xFF])
+            };
+        }
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .expect("encode png");
+        let opts = || {
+            TilesetOptions::builder(png.clone())
+                .tile_size(8, 8)
+                .columns(2)
+                .codepage(Codepage::Custom(vec!['A', 'B']))
+                .animate(
+                    'A',
+                    SpriteAnimation::uniform([0, 1], Duration::from_millis(100)),
+                )
+                .animation_metadata("C pingpong 1:30 0:30 1:30")
+                .build()
+                .expect("valid animated tileset")
+        };
+        let mut gpu = WgpuBackendBuilder::new()
+            .grid_size(4, 1)
+            .scale(2)
+            .tileset(opts())
+            .build()
+            .expect("tileset builds");
+        let mut cpu = retroglyph_software::config::SoftwareBackendBuilder::new()
+            .grid_size(4, 1)
+            .scale(2)
+            .tileset(opts())
+            .build()
+            .expect("tileset builds")
+            .into_renderer()
+            .expect("headless software renderer");
+
+        let style = Style::new().bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+        let cells: Vec<(u8, Pos, Tile)> = ['A', 'B', 'C', '.']
+            .into_iter()
+            .zip(0..)
+            .map(|(ch, x)| (0, Pos::new(x, 0), Tile::new(ch, style)))
+            .collect();
+        for millis in [0, 40, 120, 250, 1_000] {
+            let Some(device) = device_or_skip("animated_sprites_match_the_cpu_blit") else {
+                return;
+            };
+            let time = Duration::from_millis(millis);
+            gpu.set_animation_time(time);
+            cpu.set_animation_time(time);
+            paint_layers(&mut gpu, &cells);
+            paint_layers(&mut cpu, &cells);
+            let frame = render_to_frame(&mut gpu, device);
+            assert_frames_match(&frame, cpu.pixels());
+        }
+    }
+    /// The same asymmetric sprite under every flip and turn, and scaled, matches the CPU blit,
+    /// which samples through `Transform::source_pixel`: the vertex shader's corner-to-texel
+    /// mapping must agree with it pixel for pixel.
+    #[test]
+    fn transformed_sprites_match_the_cpu_blit() {
+        use retroglyph_core::backend::{DrawCell, Output as _};
+        use retroglyph_core::tile::Transform;
+
+        // A different color in every pixel and a transparent corner, so a pixel landing in the
+        // wrong place changes the frame.
+        let mut img = image::RgbaImage::new(8, 8);
+        for (x, y, px) in img.enumerate_pixels_mut() {
+            #[allow(clippy::cast_possible_truncation)]
+            let rgba = if x + y < 3 {
+                [0, 0, 0, 0]
+            } else {
+                [(x * 32) as u8, (y * 32) as u8, 0x80, 0xFF]
+            };
+            *px = image::Rgba(rgba);
+        }
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .expect("encode png");
+        let (mut gpu, mut cpu) = pair(4, 2, 2, &png, 8, SheetColor::Art);
+
+        let style = Style::new().bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+        let cells: Vec<(Pos, Tile)> = (0..2)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let ch = if (x, y) == (0, 0) { '@' } else { ' ' };
+                (Pos::new(x, y), Tile::new(ch, style))
+            })
+            .collect();
+        let id = Transform::IDENTITY;
+        for transform in [
+            id.flipped_x(),
+            id.flipped_y(),
+            id.rotated(1),
+            id.rotated(2),
+            id.rotated(3).flipped_x(),
+            id.rotated(1).scaled(2),
+        ] {
+            let Some(device) = device_or_skip("transformed_sprites_match_the_cpu_blit") else {
+                return;
+            };
+            let stream = || {
+                cells
+                    .iter()
+                    .map(move |(p, t)| DrawCell::new(*p, t).with_transform(transform))
+            };
+            gpu.draw_layers(stream()).ok();
+            cpu.draw_layers(stream()).ok();
+            let frame = render_to_frame(&mut gpu, device);
+            assert_frames_match(&frame, cpu.pixels());
+        }
+    }
+
+    /// Palette swaps on an art sheet and a mask sheet, alone and under a tint, match the CPU
+    /// blit: the fragment shader's table search and luma must agree with `Remap::lookup`.
+    #[test]
+    fn remapped_sprites_match_the_cpu_blit() {
+        use retroglyph_core::backend::{DrawCell, Output as _};
+        use retroglyph_core::color::{Remap, Tint};
+
+        let mut img = image::RgbaImage::new(8, 8);
+        for (x, y, px) in img.enumerate_pixels_mut() {
+            #[allow(clippy::cast_possible_truncation)]
+            let rgba = if x + y < 3 {
+                [0, 0, 0, 0]
+            } else {
+                [(x * 32) as u8, (y * 32) as u8, 0x80, 0xFF]
+            };
+            *px = image::Rgba(rgba);
+        }
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .expect("encode png");
+
+        // Pixel (x, y) is `(x * 32, y * 32, 0x80)`: key two of them, the first twice to check
+        // the first entry wins.
+        let remaps = [
+            Remap::colors(&[
+                ((32, 96, 0x80), (255, 255, 0)),
+                ((96, 160, 0x80), (0, 255, 255)),
+                ((32, 96, 0x80), (1, 2, 3)),
+            ]),
+            Remap::ramp(&[(40, 20, 10), (110, 70, 40), (180, 140, 90), (240, 220, 180)]),
+            Remap::ramp(&[(255, 255, 255)]),
+        ];
+        let style = Style::new()
+            .fg(Color::rgb(200, 120, 40))
+            .bg(Color::rgb(BLUE.0, BLUE.1, BLUE.2));
+        let tile = Tile::new('@', style);
+        for sheet in [SheetColor::Art, SheetColor::Mask] {
+            let (mut gpu, mut cpu) = pair(1, 1, 1, &png, 8, sheet);
+            for remap in &remaps {
+                for tint in [Tint::None, Tint::mix(255, 255, 255, 128)] {
+                    let Some(device) = device_or_skip("remapped_sprites_match_the_cpu_blit") else {
+                        return;
+                    };
+                    let stream = || {
+                        std::iter::once(
+                            DrawCell::new(Pos::new(0, 0), &tile)
+                                .with_remap(Some(remap))
+                                .with_tint(tint),
+                        )
+                    };
+                    gpu.draw_layers(stream()).ok();
+                    cpu.draw_layers(stream()).ok();
+                    let frame = render_to_frame(&mut gpu, device);
+                    assert_frames_match(&frame, cpu.pixels());
+                }
+            }
+        }
+    }
 }
@@ -5,6 +5,7 @@
 #![allow(clippy::redundant_pub_crate)]
 
 use bytemuck::{Pod, Zeroable};
+use retroglyph_core::grid::LineSize;
 
 /// [`Cell::flags`] bit: paint this cell's opaque background.
 ///
@@ -18,6 +19,21 @@ pub(crate) const FLAG_HAS_BG: u16 = 1 << 0;
 /// cells don't erase the layer beneath.
 pub(crate) const FLAG_HAS_GLYPH: u16 = 1 << 1;
 
+/// [`Cell::flags`] bits 2-3 for a cell on a `line`-sized row: 0 normal, 1 double width, 2 the top
+/// half of a double-height line, 3 the bottom half.
+///
+/// The vertex shader widens such a cell's quad to two columns and, on a double-height half,
+/// samples only that half of the glyph.
+pub(crate) const fn line_flags(line: LineSize) -> u16 {
+    let kind = match line {
+        LineSize::Normal => 0,
+        LineSize::DoubleWidth => 1,
+        LineSize::DoubleHeightTop => 2,
+        LineSize::DoubleHeightBottom => 3,
+    };
+    kind << 2
+}
+
 /// Per-cell instance data, tightly packed to 16 bytes and uploaded straight to the GPU.
 ///
 /// There is no per-cell position: the vertex shader derives `(column, row)` from the instance
@@ -34,9 +50,10 @@ pub(crate) const FLAG_HAS_GLYPH: u16 = 1 << 1;
 pub(crate) struct Cell {
     /// Atlas slot (glyph id) for this cell.
     pub glyph: u16,
-    /// Compositing flags ([`FLAG_HAS_BG`] | [`FLAG_HAS_GLYPH`]).
+    /// Compositing flags ([`FLAG_HAS_BG`] | [`FLAG_HAS_GLYPH`]), plus the row's line size in bits
+    /// 2-3 (see [`line_flags`]).
     ///
-    /// A `u16` rather than the `u8` the two bits need, so it pairs with `glyph` into one
+    /// A `u16` rather than the `u8` the four bits need, so it pairs with `glyph` into one
     /// `Uint16x2` attribute instead of costing its own.
     pub flags: u16,
     /// Foreground RGB, uploaded as normalized `u8`. The fourth channel is padding, not alpha; the
@@ -87,8 +104,9 @@ impl Cell {
 
 #[cfg(test)]
 mod tests {
-    use super::{CELL_BYTES, CELL_STRIDE, Cell, FLAG_HAS_BG, FLAG_HAS_GLYPH};
+    use super::{CELL_BYTES, CELL_STRIDE, Cell, FLAG_HAS_BG, FLAG_HAS_GLYPH, line_flags};
     use core::mem::offset_of;
+    use retroglyph_core::grid::LineSize;
 
     #[test]
     fn layout_matches_the_vertex_attribute_offsets() {
@@ -125,6 +143,8 @@ mod tests {
     #[test]
     fn flags_are_distinct_bits() {
         assert_eq!(FLAG_HAS_BG & FLAG_HAS_GLYPH, 0);
+        let line = line_flags(LineSize::DoubleHeightBottom);
+        assert_eq!(line & (FLAG_HAS_BG | FLAG_HAS_GLYPH), 0);
     }
 
     #[test]
@@ -0,0 +1,60 @@
+// The CRT post-processing pass: one fullscreen triangle that reads the frame the layers composited
+// into and applies the per-pixel recipe `retroglyph_window::crt` specifies, step for step and in
+// the same operation order as `retroglyph-software`'s CPU reference, which `headless` compares it
+// against.
+
+// The composited frame. A one-layer array so it binds through the same layout as the atlases.
+@group(2) @binding(0) var scene: texture_2d_array<f32>;
+
+@vertex
+fn vs_post(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Vertices 0, 1, 2 land on (0, 0), (2, 0), (0, 2): a triangle whose [0, 1] corner is the screen.
+    let corner = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(corner * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// The frame pixel at `p`, clamped to the frame.
+fn fetch(p: vec2<i32>) -> vec3<f32> {
+    let size = vec2<i32>(u.screen);
+    return textureLoad(scene, clamp(p, vec2<i32>(0), size - 1), 0, 0).rgb;
+}
+
+@fragment
+fn fs_post(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+    let scanlines = u.crt.x;
+    let glow = u.crt.y;
+    let curvature = u.crt.z;
+    let fringe = u.crt.w;
+
+    // Curvature: warp the pixel center in [-1, 1] space; samples past the edge are black. The
+    // fragment position is already the top-left-origin pixel center.
+    let c = frag.xy / u.screen * 2.0 - 1.0;
+    let p = (c * (1.0 + curvature * (c.yx * c.yx)) + 1.0) * 0.5 * u.screen;
+    if !(p.x >= 0.0 && p.y >= 0.0 && p.x < u.screen.x && p.y < u.screen.y) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let s = vec2<i32>(floor(p));
+
+    // Fringe: red from the right, blue from the left.
+    var rgb = vec3<f32>(
+        fetch(vec2<i32>(i32(floor(p.x + fringe)), s.y)).r,
+        fetch(s).g,
+        fetch(vec2<i32>(i32(floor(p.x - fringe)), s.y)).b,
+    );
+
+    // Glow: the mean of the nine taps 2 pixels apart, added on top.
+    var sum = vec3<f32>(0.0);
+    for (var dy = -2; dy <= 2; dy += 2) {
+        for (var dx = -2; dx <= 2; dx += 2) {
+            sum += fetch(s + vec2<i32>(dx, dy));
+        }
+    }
+    rgb += glow * sum / 9.0;
+
+    // Scanlines: darken odd rows.
+    if (s.y & 1) == 1 {
+        rgb *= 1.0 - scanlines;
+    }
+
+    return vec4<f32>(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+}
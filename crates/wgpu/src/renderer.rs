@@ -40,6 +40,21 @@
 //! without dynamic offsets, so the two passes are two pipelines built from the same shader module.
 //! Switching pipelines inside a pass is cheap; the alternative would be a second pass, a second
 //! encoder, or a dynamic-offset uniform, all of which cost more than a pipeline bind.
+//!
+//! # The CRT pass
+//!
+//! With CRT effects on, the layers composite into an offscreen scene texture instead of the target,
+//! and a second pass draws one fullscreen triangle through `post.wgsl` that reads the scene back.
+//! The scene exists only while the effects are on, so a frame without them is the single pass
+//! above, unchanged.
+//!
+//! # The scaling pass
+//!
+//! Under a [`ScalingMode`](retroglyph_window::scaling::ScalingMode) that scales the frame, the
+//! layers composite into the scene too, at the grid's own size, and a last pass through
+//! `scale.wgsl` clears the target to the bar color and draws the frame into its viewport. With the
+//! CRT effects on as well, the CRT pass writes a second frame-sized texture for it to read: the
+//! effects work in frame pixels, so they can't be folded into the scaled draw.
 
 // `redundant_pub_crate` fires on `pub(crate)` items in this private module; the module boundary is
 // intentional, so it's allowed crate-locally.
@@ -50,6 +65,9 @@ use crate::instance::{CELL_STRIDE, Cell};
 use crate::shaders::{self, Shader};
 use bytemuck::{Pod, Zeroable};
 use retroglyph_window::atlas::{ATLAS_COLS, ATLAS_ROWS, AtlasData};
+use retroglyph_window::crt::CrtEffects;
+use retroglyph_window::geometry::Viewport;
+use retroglyph_window::scaling::ScaleFilter;
 #[cfg(feature = "tilesets")]
 use wgpu::util::DeviceExt as _;
 
@@ -58,9 +76,9 @@ use crate::sprite_set::{SPRITE_INSTANCE_BYTES, SPRITE_STRIDE, SpriteInstance, Sp
 
 /// The uniform block every pipeline reads, mirroring `common.wgsl`'s `Uniforms`.
 ///
-/// `#[repr(C)]` and padded to 48 bytes: WGSL's uniform address space requires a block's size to be
-/// a multiple of 16, and each `vec2` member to sit on an 8-byte boundary, which the field order
-/// below satisfies without any implicit padding.
+/// `#[repr(C)]` and 80 bytes: WGSL's uniform address space requires a block's size to be a
+/// multiple of 16, each `vec2` member to sit on an 8-byte boundary, and each `vec4` on a 16-byte
+/// one, which the field order below (with `_pad`) satisfies without any implicit padding.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
 pub(crate) struct Uniforms {
@@ -79,8 +97,12 @@ pub(crate) struct Uniforms {
     /// Glyph columns and rows packed per glyph-atlas array layer.
     atlas_cols: u32,
     atlas_rows: u32,
-    /// Pads the block to a multiple of 16 bytes.
+    /// Pads `crt` onto a 16-byte boundary.
     _pad: u32,
+    /// CRT post-processing strengths, in [`CrtEffects::to_array`] order; all zero when off.
+    crt: [f32; 4],
+    /// The scaled frame's viewport on the target: origin, then size. All zero when unscaled.
+    viewport: [f32; 4],
 }
 
 /// The vertex buffer layout for [`Cell`], as declared to the cell pipelines.
@@ -106,16 +128,23 @@ const fn cell_layout() -> wgpu::VertexBufferLayout<'static> {
 ///
 /// The two color stages are `Uint8x4`, not `Unorm8x4`: the fragment shader reproduces
 /// [`Tint::apply`](retroglyph_core::color::Tint::apply)'s `u8` arithmetic exactly, which needs the
-/// raw 0..255 channel values rather than normalized floats.
+/// raw 0..255 channel values rather than normalized floats. The remap table follows as four
+/// `Uint32x4`s of packed `0x00RRGGBB` colors, compared and unpacked as integers for the same reason.
 #[cfg(feature = "tilesets")]
 const fn sprite_layout() -> wgpu::VertexBufferLayout<'static> {
-    const ATTRS: [wgpu::VertexAttribute; 6] = wgpu::vertex_attr_array![
+    const ATTRS: [wgpu::VertexAttribute; 12] = wgpu::vertex_attr_array![
         0 => Uint16x2,
         1 => Uint16x2,
         2 => Sint16x2,
         3 => Uint16x2,
         4 => Uint8x4,
         5 => Uint8x4,
+        6 => Uint16x2,
+        7 => Uint16x2,
+        8 => Uint32x4,
+        9 => Uint32x4,
+        10 => Uint32x4,
+        11 => Uint32x4,
     ];
     wgpu::VertexBufferLayout {
         array_stride: SPRITE_STRIDE,
@@ -249,6 +278,22 @@ pub(crate) struct GpuResources {
     /// The sprite pipeline, atlas bind group, and instance buffer; `None` without a tileset.
     #[cfg(feature = "tilesets")]
     sprites: Option<SpriteGpu>,
+    /// The CRT post-processing pipeline.
+    post: wgpu::RenderPipeline,
+    /// The frame-scaling pipelines, one per [`ScaleFilter`].
+    scale_nearest: wgpu::RenderPipeline,
+    scale_linear: wgpu::RenderPipeline,
+    /// The layout the scene texture binds through (the atlases' layout).
+    atlas_layout: wgpu::BindGroupLayout,
+    /// The format the pipelines render into, and so the scene texture's too.
+    target_format: wgpu::TextureFormat,
+    /// The offscreen frame the layers composite into while CRT effects are on or the frame is
+    /// scaled; `None` otherwise. Sized by [`prepare_scene`](Self::prepare_scene).
+    scene: Option<SceneTarget>,
+    /// The CRT pass's output while the frame is both post-processed and scaled; `None` otherwise.
+    crt_out: Option<SceneTarget>,
+    /// Where the scaling pass draws the frame, or `None` to draw it 1:1.
+    placement: Option<Placement>,
     /// The uniform values last written, so a frame that changes nothing skips the write.
     last_uniforms: Uniforms,
 }
@@ -336,6 +381,9 @@ impl GpuResources {
             mapped_at_creation: false,
         });
 
+        let [post, scale_nearest, scale_linear] =
+            fullscreen_pipelines(device, &uniform_layout, &atlas_layout, target_format);
+
         Ok(Self {
             background,
             glyph,
@@ -346,6 +394,14 @@ impl GpuResources {
             cell_capacity: cell_capacity.max(1),
             #[cfg(feature = "tilesets")]
             sprites: None,
+            post,
+            scale_nearest,
+            scale_linear,
+            atlas_layout,
+            target_format,
+            scene: None,
+            crt_out: None,
+            placement: None,
             last_uniforms: Uniforms::default(),
         })
     }
@@ -440,7 +496,12 @@ impl GpuResources {
         glyph: (u32, u32),
         cols: u16,
         sprite_tex: (u32, u32),
+        crt: &CrtEffects,
+        viewport: Option<&Viewport>,
     ) {
+        let viewport = viewport.map_or([0.0; 4], |v| {
+            [v.origin.0, v.origin.1, v.size.0, v.size.1].map(|n| n as f32)
+        });
         let uniforms = Uniforms {
             screen: [screen.0 as f32, screen.1 as f32],
             cell: [cell.0 as f32, cell.1 as f32],
@@ -450,6 +511,8 @@ impl GpuResources {
             atlas_cols: ATLAS_COLS,
             atlas_rows: ATLAS_ROWS,
             _pad: 0,
+            crt: crt.to_array(),
+            viewport,
         };
         if uniforms == self.last_uniforms {
             return;
@@ -458,6 +521,60 @@ impl GpuResources {
         self.last_uniforms = uniforms;
     }
 
+    /// Creates, resizes, or drops the offscreen textures a `frame`-sized frame goes through: the
+    /// scene while the CRT effects are on (`crt`) or the frame is scaled (`placement`), and the CRT
+    /// pass's output while both are. Records `placement` for [`render`](Self::render).
+    pub(crate) fn prepare_scene(
+        &mut self,
+        device: &wgpu::Device,
+        frame: (u32, u32),
+        crt: bool,
+        placement: Option<Placement>,
+    ) {
+        let scaled = placement.is_some();
+        let scene = self.scene.take();
+        self.scene = self.frame_target(device, scene, (crt || scaled).then_some(frame));
+        let crt_out = self.crt_out.take();
+        self.crt_out = self.frame_target(device, crt_out, (crt && scaled).then_some(frame));
+        self.placement = placement;
+    }
+
+    /// `current` if it's already `size`, a new frame texture of `size` if it isn't, and `None`
+    /// without a `size`.
+    fn frame_target(
+        &self,
+        device: &wgpu::Device,
+        current: Option<SceneTarget>,
+        size: Option<(u32, u32)>,
+    ) -> Option<SceneTarget> {
+        let size = size?;
+        Some(match current {
+            Some(target) if target.size == size => target,
+            _ => self.create_frame_target(device, size),
+        })
+    }
+
+    /// Creates a `size` texture in the target format that passes can both render into and read.
+    fn create_frame_target(&self, device: &wgpu::Device, size: (u32, u32)) -> SceneTarget {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("retroglyph scene"),
+            size: wgpu::Extent3d {
+                width: size.0.max(1),
+                height: size.1.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.target_format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let group = atlas_bind_group(device, &self.atlas_layout, &texture);
+        SceneTarget { view, group, size }
+    }
+
     /// Uploads every layer's cells as one contiguous block, growing the buffer if the frame needs
     /// more room than the last one did.
     pub(crate) fn upload_cells(
@@ -510,6 +627,9 @@ impl GpuResources {
     ///
     /// Compositing layers in one pass is what makes an empty cell in a higher layer transparent:
     /// each pass `discard`s a cell whose matching flag is clear, so the layer beneath survives.
+    ///
+    /// While a scene is prepared, the layers go into it instead, and it reaches `view` through the
+    /// post-processing pipeline, the scaling one, or the first and then the second.
     pub(crate) fn render(
         &self,
         encoder: &mut wgpu::CommandEncoder,
@@ -517,10 +637,11 @@ impl GpuResources {
         layers: &[LayerRange],
         #[cfg(feature = "tilesets")] sprite_layers: &[LayerRange],
     ) {
+        let target = self.scene.as_ref().map_or(view, |scene| &scene.view);
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("retroglyph frame"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: target,
                 depth_slice: None,
                 resolve_target: None,
                 ops: wgpu::Operations {
@@ -565,9 +686,129 @@ impl GpuResources {
             #[cfg(not(feature = "tilesets"))]
             let _ = index;
         }
+        drop(pass);
+
+        let Some(scene) = &self.scene else {
+            return;
+        };
+        let Some(placement) = &self.placement else {
+            self.render_post(encoder, view, scene);
+            return;
+        };
+        let source = self.crt_out.as_ref().map_or(scene, |crt_out| {
+            self.render_post(encoder, &crt_out.view, scene);
+            crt_out
+        });
+        self.render_scaled(encoder, view, source, placement);
+    }
+
+    /// Draws `scene` into `view` through the CRT pipeline. The triangle covers every pixel, so the
+    /// pass doesn't need to clear first.
+    fn render_post(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        scene: &SceneTarget,
+    ) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("retroglyph crt"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_pipeline(&self.post);
+        pass.set_bind_group(0, &self.uniform_group, &[]);
+        // `post.wgsl` never reads the glyph atlas, but it shares the prelude that declares it, so
+        // the layout has group 1 and something must be bound there.
+        pass.set_bind_group(1, &self.glyph_atlas_group, &[]);
+        pass.set_bind_group(2, &scene.group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    /// Clears `view` to the placement's bar color and draws `source` into its viewport through the
+    /// scaling pipeline for its filter. The triangle covers the whole target; the scissor keeps it
+    /// inside the viewport, or the part of it on the target when the viewport hangs off an edge.
+    fn render_scaled(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        source: &SceneTarget,
+        placement: &Placement,
+    ) {
+        let (r, g, b) = placement.bars;
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("retroglyph scale"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: f64::from(r) / 255.0,
+                        g: f64::from(g) / 255.0,
+                        b: f64::from(b) / 255.0,
+                        a: 1.0,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        let Viewport { origin, size, .. } = placement.viewport;
+        let (target_w, target_h) = placement.target;
+        let (left, top) = (origin.0.min(target_w), origin.1.min(target_h));
+        let width = origin.0.saturating_add(size.0).min(target_w) - left;
+        let height = origin.1.saturating_add(size.1).min(target_h) - top;
+        if width == 0 || height == 0 {
+            return;
+        }
+        pass.set_scissor_rect(left, top, width, height);
+        pass.set_pipeline(match placement.filter {
+            ScaleFilter::Nearest => &self.scale_nearest,
+            ScaleFilter::Linear => &self.scale_linear,
+        });
+        pass.set_bind_group(0, &self.uniform_group, &[]);
+        // As with the CRT pass: unread, but declared by the shared prelude.
+        pass.set_bind_group(1, &self.glyph_atlas_group, &[]);
+        pass.set_bind_group(2, &source.group, &[]);
+        pass.draw(0..3, 0..1);
     }
 }
 
+/// Where and how the scaling pass draws a frame onto its target.
+pub(crate) struct Placement {
+    /// The target's size in pixels.
+    pub target: (u32, u32),
+    /// The rectangle of the target the frame is scaled into.
+    pub viewport: Viewport,
+    /// How the frame is resampled.
+    pub filter: ScaleFilter,
+    /// The color the rest of the target is cleared to.
+    pub bars: (u8, u8, u8),
+}
+
+/// An offscreen frame a pass renders into and a later pass reads: the scene the layers composite
+/// into, or the CRT pass's output on its way to the scaling pass.
+struct SceneTarget {
+    view: wgpu::TextureView,
+    group: wgpu::BindGroup,
+    /// The frame size the texture was created for.
+    size: (u32, u32),
+}
+
 /// The sprite pipeline, its atlas bind group, and its instance buffer.
 #[cfg(feature = "tilesets")]
 struct SpriteGpu {
@@ -656,6 +897,82 @@ fn cell_pipeline(
     })
 }
 
+/// Builds the pipelines that draw a whole frame: the CRT pass and the nearest and linear scaling
+/// passes, in that order.
+fn fullscreen_pipelines(
+    device: &wgpu::Device,
+    uniform_layout: &wgpu::BindGroupLayout,
+    atlas_layout: &wgpu::BindGroupLayout,
+    format: wgpu::TextureFormat,
+) -> [wgpu::RenderPipeline; 3] {
+    let post = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("retroglyph crt"),
+        source: wgpu::ShaderSource::Wgsl(shaders::source(Shader::Post).into()),
+    });
+    let scale = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("retroglyph scale"),
+        source: wgpu::ShaderSource::Wgsl(shaders::source(Shader::Scale).into()),
+    });
+    [
+        (&post, "vs_post", "fs_post"),
+        (&scale, "vs_scale", "fs_scale_nearest"),
+        (&scale, "vs_scale", "fs_scale_linear"),
+    ]
+    .map(|(module, vertex, fragment)| {
+        fullscreen_pipeline(
+            device,
+            uniform_layout,
+            atlas_layout,
+            module,
+            format,
+            (vertex, fragment),
+        )
+    })
+}
+
+/// Builds a pipeline that draws one fullscreen triangle from `module`'s `entries` (vertex, then
+/// fragment), with the frame it reads at group 2: the CRT pass and the two scaling passes. No
+/// vertex buffers, and no blending, since each overwrites every pixel it covers.
+fn fullscreen_pipeline(
+    device: &wgpu::Device,
+    uniform_layout: &wgpu::BindGroupLayout,
+    atlas_layout: &wgpu::BindGroupLayout,
+    module: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    (vertex_entry, fragment_entry): (&str, &str),
+) -> wgpu::RenderPipeline {
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(fragment_entry),
+        bind_group_layouts: &[Some(uniform_layout), Some(atlas_layout), Some(atlas_layout)],
+        immediate_size: 0,
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(fragment_entry),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
 /// Group 0: the shared uniform block, read by both shader stages.
 fn uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
     device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -833,7 +1150,7 @@ mod tests {
     fn uniform_block_is_a_multiple_of_sixteen_bytes() {
         // WGSL's uniform address space requires it, and `min_binding_size` above declares this
         // exact size to the validator.
-        assert_eq!(size_of::<Uniforms>(), 48);
+        assert_eq!(size_of::<Uniforms>(), 80);
     }
 
     #[test]
@@ -854,7 +1171,7 @@ mod tests {
         assert_eq!(layout.array_stride, SPRITE_STRIDE);
         assert_eq!(layout.step_mode, wgpu::VertexStepMode::Instance);
         let offsets: Vec<u64> = layout.attributes.iter().map(|a| a.offset).collect();
-        assert_eq!(offsets, vec![0, 4, 8, 12, 16, 20]);
+        assert_eq!(offsets, vec![0, 4, 8, 12, 16, 20, 24, 28, 32, 48, 64, 80]);
     }
 
     #[test]
@@ -0,0 +1,43 @@
+// The frame-scaling pass: one fullscreen triangle over the whole target, scissored to the
+// viewport the frame is scaled into, that reads the frame back at each pixel's position within it.
+// The bars around the viewport are the pass's clear color. Filtering is done by hand with
+// `textureLoad`, so the frame binds through the atlases' non-filtering layout.
+
+// The frame to scale: the composited scene, or the CRT pass's output. A one-layer array so it binds
+// through the same layout as the atlases.
+@group(2) @binding(0) var frame: texture_2d_array<f32>;
+
+@vertex
+fn vs_scale(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Vertices 0, 1, 2 land on (0, 0), (2, 0), (0, 2): a triangle whose [0, 1] corner is the screen.
+    let corner = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(corner * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// The frame pixel at `p`, clamped to the frame.
+fn texel(p: vec2<i32>) -> vec3<f32> {
+    let size = vec2<i32>(u.screen);
+    return textureLoad(frame, clamp(p, vec2<i32>(0), size - 1), 0, 0).rgb;
+}
+
+// Where the target pixel centered at `at` falls in the frame, in frame pixels. `u.screen` is the
+// frame's size here: the layers composited at it.
+fn frame_position(at: vec2<f32>) -> vec2<f32> {
+    return (at - u.viewport.xy) / u.viewport.zw * u.screen;
+}
+
+@fragment
+fn fs_scale_nearest(@builtin(position) at: vec4<f32>) -> @location(0) vec4<f32> {
+    return vec4<f32>(texel(vec2<i32>(floor(frame_position(at.xy)))), 1.0);
+}
+
+@fragment
+fn fs_scale_linear(@builtin(position) at: vec4<f32>) -> @location(0) vec4<f32> {
+    // Blend the four frame pixels whose centers surround the position.
+    let p = frame_position(at.xy) - 0.5;
+    let base = vec2<i32>(floor(p));
+    let t = p - floor(p);
+    let top = mix(texel(base), texel(base + vec2<i32>(1, 0)), t.x);
+    let bottom = mix(texel(base + vec2<i32>(0, 1)), texel(base + vec2<i32>(1, 1)), t.x);
+    return vec4<f32>(mix(top, bottom, t.y), 1.0);
+}
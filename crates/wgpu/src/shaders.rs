@@ -25,6 +25,12 @@ const CELLS: &str = include_str!("cells.wgsl");
 #[cfg(feature = "tilesets")]
 const SPRITES: &str = include_str!("sprites.wgsl");
 
+/// The CRT post-processing pass (`vs_post`/`fs_post`).
+const POST: &str = include_str!("post.wgsl");
+
+/// The frame-scaling pass (`vs_scale`/`fs_scale_nearest`/`fs_scale_linear`).
+const SCALE: &str = include_str!("scale.wgsl");
+
 /// Which shader module to build.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum Shader {
@@ -33,6 +39,10 @@ pub(crate) enum Shader {
     /// The sprite pipeline.
     #[cfg(feature = "tilesets")]
     Sprites,
+    /// The CRT post-processing pipeline.
+    Post,
+    /// The two frame-scaling pipelines.
+    Scale,
 }
 
 /// The complete WGSL source for `shader`: the shared prelude followed by that shader's body.
@@ -41,6 +51,8 @@ pub(crate) fn source(shader: Shader) -> String {
         Shader::Cells => CELLS,
         #[cfg(feature = "tilesets")]
         Shader::Sprites => SPRITES,
+        Shader::Post => POST,
+        Shader::Scale => SCALE,
     };
     let mut out = String::with_capacity(COMMON.len() + body.len() + 1);
     out.push_str(COMMON);
@@ -59,7 +71,7 @@ mod tests {
         // A body that declared its own `Uniforms` would compile and then read the wrong offsets;
         // the point of the prelude is that there is exactly one declaration.
         #[cfg_attr(not(feature = "tilesets"), allow(unused_mut))]
-        let mut shaders = vec![Shader::Cells];
+        let mut shaders = vec![Shader::Cells, Shader::Post, Shader::Scale];
         #[cfg(feature = "tilesets")]
         shaders.push(Shader::Sprites);
         for shader in shaders {
@@ -85,11 +97,13 @@ mod tests {
         // WGSL's uniform address space requires the block's size to be a multiple of 16; the Rust
         // struct is what gets written into the buffer, so the two must agree on that size or every
         // field past the first mismatch is read from the wrong offset.
-        assert_eq!(size_of::<Uniforms>(), 48);
+        assert_eq!(size_of::<Uniforms>(), 80);
         assert_eq!(size_of::<Uniforms>() % 16, 0);
-        // The trailing `pad` field exists only to reach that size; naming it here keeps a future
-        // edit that drops it from silently shrinking the block.
+        // The `pad` field exists only to put `crt` on its 16-byte boundary; naming it here keeps a
+        // future edit that drops it from silently shifting the block.
         assert!(COMMON.contains("pad: u32,"));
+        assert!(COMMON.contains("crt: vec4<f32>,"));
+        assert!(COMMON.contains("viewport: vec4<f32>,"));
     }
 
     #[test]
@@ -133,6 +147,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn post_applies_every_crt_effect() {
+        let src = source(Shader::Post);
+        for needle in [
+            "fn vs_post(",
+            "fn fs_post(",
+            "curvature * (c.yx * c.yx)",
+            "floor(p.x + fringe)",
+            "glow * sum / 9.0",
+            "rgb *= 1.0 - scanlines",
+        ] {
+            assert!(src.contains(needle), "post.wgsl is missing `{needle}`");
+        }
+    }
+
+    #[test]
+    fn scale_maps_the_viewport_onto_the_whole_frame() {
+        let src = source(Shader::Scale);
+        for needle in [
+            "fn vs_scale(",
+            "fn fs_scale_nearest(",
+            "fn fs_scale_linear(",
+            "(at - u.viewport.xy) / u.viewport.zw * u.screen",
+        ] {
+            assert!(src.contains(needle), "scale.wgsl is missing `{needle}`");
+        }
+    }
+
     #[cfg(feature = "tilesets")]
     #[test]
     fn the_sprite_stage_avoids_discard_too() {
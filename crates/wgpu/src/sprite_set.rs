@@ -16,13 +16,16 @@
 #![allow(clippy::redundant_pub_crate)]
 
 use bytemuck::{Pod, Zeroable};
-use retroglyph_core::color::Tint;
+use core::time::Duration;
+use retroglyph_core::color::{Remap, Tint};
+use retroglyph_core::tile::Transform;
+use retroglyph_window::animation::SpriteAnimations;
 use retroglyph_window::sprite_cache::{SpriteCache, SpriteTint};
 use retroglyph_window::tileset::{SheetColor, SpriteAlign};
 use std::collections::HashMap;
 
 /// Byte size of one [`SpriteInstance`], as a `usize` for slice and array arithmetic.
-pub(crate) const SPRITE_INSTANCE_BYTES: usize = 24;
+pub(crate) const SPRITE_INSTANCE_BYTES: usize = 96;
 
 /// Byte stride of one [`SpriteInstance`], which is also the vertex buffer's `array_stride`.
 pub(crate) const SPRITE_STRIDE: u64 = SPRITE_INSTANCE_BYTES as u64;
@@ -45,30 +48,33 @@ pub(crate) struct SpriteSlot {
 
 impl SpriteSlot {
     /// The offset, in unscaled pixels, from the anchor cell's top-left corner to this sprite's own
-    /// top-left pixel, for a span of `span_w` x `span_h` cells of `glyph_w` x `glyph_h`.
-    pub(crate) fn align_offset(
+    /// top-left pixel, for a span of `span_w` x `span_h` cells of `glyph_w` x `glyph_h`, with the
+    /// sprite drawn at its `transform`ed size.
+    pub(crate) const fn align_offset(
         self,
+        transform: Transform,
         span_w: u16,
         span_h: u16,
         glyph_w: u16,
         glyph_h: u16,
     ) -> (i16, i16) {
-        self.align.offset_in_span(
-            u32::from(self.w),
-            u32::from(self.h),
-            span_w,
-            span_h,
-            glyph_w,
-            glyph_h,
-        )
+        let (w, h) = self.size(transform);
+        self.align
+            .offset_in_span(w, h, span_w, span_h, glyph_w, glyph_h)
+    }
+
+    /// The size, in unscaled pixels, this sprite is drawn at under `transform`.
+    pub(crate) const fn size(self, transform: Transform) -> (u32, u32) {
+        transform.size(self.w as u32, self.h as u32)
     }
 }
 
 /// One sprite instance for the sprite pass, matching `sprites.wgsl`'s `SpriteInput` and the
 /// `VertexBufferLayout` in [`renderer`](crate::renderer).
 ///
-/// `#[repr(C)]`, 24 bytes, every attribute 4 bytes wide on a 4-byte offset. The `u16` pairs are
-/// grouped so each becomes one `Uint16x2`/`Sint16x2` attribute rather than costing its own.
+/// `#[repr(C)]`, 96 bytes, every attribute a multiple of 4 bytes wide on a 4-byte offset. The
+/// `u16` pairs are grouped so each becomes one `Uint16x2`/`Sint16x2` attribute rather than
+/// costing its own, and the remap table's eight colors a side travel as two `Uint32x4`s each.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
 pub(crate) struct SpriteInstance {
@@ -94,12 +100,33 @@ pub(crate) struct SpriteInstance {
     pub mask: [u8; 4],
     /// Cell stage: RGB color, with `a` carrying [`Tint::Mix`]'s amount.
     pub tint: [u8; 4],
+    /// The flip and quarter-turn bits of [`Transform`], laid out the same way (bit 0 flips X,
+    /// bit 1 flips Y, bits 2-3 are clockwise turns). A contract with `sprites.wgsl`.
+    pub transform: u16,
+    /// [`Transform::scale`].
+    pub scale: u16,
+    /// Which kind of [`Remap`] the remap stage is: 0 none, 1 exact colors, 2 ramp. A contract
+    /// with `sprites.wgsl`.
+    pub remap_op: u16,
+    /// How many of the entries below are in use.
+    pub remap_len: u16,
+    /// Each [`Remap::sources`] color packed `0x00RRGGBB`, the shape the shader packs the sampled
+    /// texel into to compare. Unused by a ramp.
+    pub remap_from: [u32; Remap::MAX_ENTRIES],
+    /// Each [`Remap::targets`] color, packed the same way.
+    pub remap_to: [u32; Remap::MAX_ENTRIES],
+}
+
+/// `rgb` packed `0x00RRGGBB`, the form the remap stage's colors take in [`SpriteInstance`].
+const fn pack_rgb((r, g, b): (u8, u8, u8)) -> u32 {
+    (r as u32) << 16 | (g as u32) << 8 | b as u32
 }
 
 impl SpriteInstance {
     /// A sprite drawn at `(col, row)`, offset by `(dx, dy)` unscaled pixels, recolored by
     /// `recolor`.
-    #[allow(clippy::too_many_arguments)]
+    // `remap_len` is at most `Remap::MAX_ENTRIES`.
+    #[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
     pub(crate) const fn new(
         col: u16,
         row: u16,
@@ -122,6 +149,25 @@ impl SpriteInstance {
             // authored instead of being recolored by a misread payload.
             _ => ([0, 0, 0, 0], 0),
         };
+        let remap = recolor.remap;
+        let remap_op = if remap.is_identity() {
+            0
+        } else if remap.is_ramp() {
+            2
+        } else {
+            1
+        };
+        let mut remap_from = [0; Remap::MAX_ENTRIES];
+        let mut remap_to = [0; Remap::MAX_ENTRIES];
+        let (sources, targets) = (remap.sources(), remap.targets());
+        let mut i = 0;
+        while i < targets.len() {
+            if i < sources.len() {
+                remap_from[i] = pack_rgb(sources[i]);
+            }
+            remap_to[i] = pack_rgb(targets[i]);
+            i += 1;
+        }
         Self {
             col,
             row,
@@ -133,7 +179,28 @@ impl SpriteInstance {
             tint_op,
             mask,
             tint,
+            transform: 0,
+            scale: 1,
+            remap_op,
+            remap_len: targets.len() as u16,
+            remap_from,
+            remap_to,
+        }
+    }
+
+    /// This instance drawn under `transform`. `w`/`h` stay the sprite's own size, which is what
+    /// the shader samples the atlas by; the quad it covers is sized from both.
+    pub(crate) const fn with_transform(mut self, transform: Transform) -> Self {
+        let mut bits = transform.quarter_turns() << 2;
+        if transform.is_flipped_x() {
+            bits |= 1;
+        }
+        if transform.is_flipped_y() {
+            bits |= 2;
         }
+        self.transform = bits as u16;
+        self.scale = transform.scale() as u16;
+        self
     }
 }
 
@@ -141,6 +208,8 @@ impl SpriteInstance {
 pub(crate) struct SpriteSet {
     /// `char` -> array layer.
     slots: HashMap<char, u16>,
+    /// The cache's animated codepoints, resolved to a frame's `char` before the `slots` lookup.
+    animations: SpriteAnimations,
     /// Per-layer sprite pixel size `(w, h)`.
     sizes: Vec<(u16, u16)>,
     /// Per-layer sprite placement within a span's cell box.
@@ -204,6 +273,7 @@ impl SpriteSet {
 
         Some(Self {
             slots,
+            animations: cache.animations().clone(),
             sizes,
             aligns,
             colors,
@@ -227,6 +297,12 @@ impl SpriteSet {
         })
     }
 
+    /// The atlas slot a cell holding `ch` shows at animation time `time`: its current frame's if
+    /// `ch` is animated, else [`slot`](Self::slot)'s. Matches `SpriteCache::get_at`.
+    pub(crate) fn slot_at(&self, ch: char, time: Duration) -> Option<SpriteSlot> {
+        self.slot(self.animations.glyph_at(ch, time))
+    }
+
     /// One layer's texture size in texels `(w, h)`.
     pub(crate) const fn tex_size(&self) -> (u32, u32) {
         (self.tex_w, self.tex_h)
@@ -247,7 +323,7 @@ impl SpriteSet {
 mod tests {
     use super::{SPRITE_INSTANCE_BYTES, SPRITE_STRIDE, SpriteInstance, SpriteTint};
     use core::mem::offset_of;
-    use retroglyph_core::color::Tint;
+    use retroglyph_core::color::{Remap, Tint};
 
     #[test]
     fn layout_matches_the_vertex_attribute_offsets() {
@@ -261,6 +337,39 @@ mod tests {
         assert_eq!(offset_of!(SpriteInstance, layer), 12);
         assert_eq!(offset_of!(SpriteInstance, mask), 16);
         assert_eq!(offset_of!(SpriteInstance, tint), 20);
+        assert_eq!(offset_of!(SpriteInstance, transform), 24);
+        assert_eq!(offset_of!(SpriteInstance, remap_op), 28);
+        assert_eq!(offset_of!(SpriteInstance, remap_from), 32);
+        assert_eq!(offset_of!(SpriteInstance, remap_to), 64);
+    }
+
+    #[test]
+    fn a_remap_encodes_its_kind_and_packed_entries() {
+        let swap = SpriteTint::default().with_remap(Some(&Remap::colors(&[(
+            (0x12, 0x34, 0x56),
+            (0xAB, 0xCD, 0xEF),
+        )])));
+        let i = inst(swap);
+        // `sprites.wgsl` branches on the kind and unpacks `0x00RRGGBB`.
+        assert_eq!((i.remap_op, i.remap_len), (1, 1));
+        assert_eq!((i.remap_from[0], i.remap_to[0]), (0x0012_3456, 0x00AB_CDEF));
+
+        let ramp = SpriteTint::default().with_remap(Some(&Remap::ramp(&[(1, 2, 3), (4, 5, 6)])));
+        let i = inst(ramp);
+        assert_eq!((i.remap_op, i.remap_len), (2, 2));
+        assert_eq!(i.remap_to[..2], [0x0001_0203, 0x0004_0506]);
+        assert_eq!(inst(SpriteTint::default()).remap_op, 0);
+    }
+
+    #[test]
+    fn a_transform_encodes_the_bits_the_shader_decodes() {
+        use retroglyph_core::tile::Transform;
+        let i = inst(SpriteTint::default());
+        assert_eq!((i.transform, i.scale), (0, 1));
+        let t = Transform::IDENTITY.flipped_y().rotated(3).scaled(2);
+        let i = i.with_transform(t);
+        // `sprites.wgsl` reads bit 0 as flip X, bit 1 as flip Y, and bits 2-3 as quarter turns.
+        assert_eq!((i.transform, i.scale), (0b1110, 2));
     }
 
     fn inst(recolor: SpriteTint) -> SpriteInstance {
@@ -280,6 +389,7 @@ mod tests {
         let recolor = SpriteTint {
             mask: Tint::multiply(10, 20, 30),
             tint: Tint::None,
+            ..SpriteTint::default()
         };
         assert_eq!(inst(recolor).mask, [10, 20, 30, 255]);
     }
@@ -289,10 +399,12 @@ mod tests {
         let mul = SpriteTint {
             mask: Tint::None,
             tint: Tint::multiply(1, 2, 3),
+            ..SpriteTint::default()
         };
         let mix = SpriteTint {
             mask: Tint::None,
             tint: Tint::mix(4, 5, 6, 7),
+            ..SpriteTint::default()
         };
         assert_eq!((inst(mul).tint_op, inst(mul).tint), (1, [1, 2, 3, 255]));
         assert_eq!((inst(mix).tint_op, inst(mix).tint), (2, [4, 5, 6, 7]));
@@ -305,6 +417,7 @@ mod tests {
         let none = SpriteTint {
             mask: Tint::None,
             tint: Tint::None,
+            ..SpriteTint::default()
         };
         assert_eq!(inst(none).tint_op, 0);
     }
@@ -24,9 +24,20 @@ struct SpriteInput {
     // `Uint8x4`: the sheet stage, raw 0..255. rgb is a multiply factor, a is 255 for a mask sheet
     // and 0 for an art sheet.
     @location(4) mask: vec4<u32>,
-    // `Uint8x4`: the cell stage, raw 0..255. rgb is the tint colour, a carries `Tint::Mix`'s
+    // `Uint8x4`: the cell stage, raw 0..255. rgb is the tint color, a carries `Tint::Mix`'s
     // amount.
     @location(5) tint: vec4<u32>,
+    // `Uint16x2`: x = flip X (bit 0), flip Y (bit 1), clockwise quarter turns (bits 2-3); y = the
+    // transform's integer scale.
+    @location(6) transform: vec2<u32>,
+    // `Uint16x2`: x = the remap's kind (0 none, 1 exact colors, 2 ramp), y = its entry count.
+    @location(7) remap: vec2<u32>,
+    // `Uint32x4` x2: the remap's source colors 0-3 and 4-7, packed `0x00RRGGBB`.
+    @location(8) remap_from0: vec4<u32>,
+    @location(9) remap_from1: vec4<u32>,
+    // `Uint32x4` x2: the remap's target colors, packed the same way.
+    @location(10) remap_to0: vec4<u32>,
+    @location(11) remap_to1: vec4<u32>,
 }
 
 struct SpriteVarying {
@@ -37,6 +48,11 @@ struct SpriteVarying {
     @location(2) @interpolate(flat) mask: vec4<u32>,
     @location(3) @interpolate(flat) tint: vec4<u32>,
     @location(4) @interpolate(flat) tint_op: u32,
+    @location(5) @interpolate(flat) remap: vec2<u32>,
+    @location(6) @interpolate(flat) remap_from0: vec4<u32>,
+    @location(7) @interpolate(flat) remap_from1: vec4<u32>,
+    @location(8) @interpolate(flat) remap_to0: vec4<u32>,
+    @location(9) @interpolate(flat) remap_to1: vec4<u32>,
 }
 
 @vertex
@@ -47,7 +63,14 @@ fn vs_sprite(
     let corner = corner_of(vertex_index);
     let scale = u.cell / u.glyph;
     let origin = vec2<f32>(sprite.cell) * u.cell + vec2<f32>(sprite.offset) * scale;
-    let px = origin + corner * (vec2<f32>(sprite.size) * scale);
+    // An odd number of quarter turns swaps the quad's sides; the transform's own scale multiplies
+    // the renderer's.
+    let turns = (sprite.transform.x >> 2u) & 3u;
+    var size = vec2<f32>(sprite.size);
+    if (turns & 1u) == 1u {
+        size = size.yx;
+    }
+    let px = origin + corner * (size * f32(sprite.transform.y) * scale);
 
     var out: SpriteVarying;
     out.clip = vec4<f32>(
@@ -58,11 +81,32 @@ fn vs_sprite(
     );
     // Each sprite sits at the top-left of its layer; the rest of the layer is transparent padding,
     // so the quad's [0,1] corners map onto the sprite's own fraction of the layer.
-    out.uv = corner * (vec2<f32>(sprite.size) / u.sprite_tex);
+    // The corner's place in the source sprite is `Transform::source_pixel` in normalized
+    // coordinates: the rotation is undone first, then the flips.
+    var source_uv = corner;
+    if turns == 1u {
+        source_uv = vec2<f32>(corner.y, 1.0 - corner.x);
+    } else if turns == 2u {
+        source_uv = vec2<f32>(1.0) - corner;
+    } else if turns == 3u {
+        source_uv = vec2<f32>(1.0 - corner.y, corner.x);
+    }
+    if (sprite.transform.x & 1u) != 0u {
+        source_uv.x = 1.0 - source_uv.x;
+    }
+    if (sprite.transform.x & 2u) != 0u {
+        source_uv.y = 1.0 - source_uv.y;
+    }
+    out.uv = source_uv * (vec2<f32>(sprite.size) / u.sprite_tex);
     out.layer = sprite.layer_op.x;
     out.tint_op = sprite.layer_op.y;
     out.mask = sprite.mask;
     out.tint = sprite.tint;
+    out.remap = sprite.remap;
+    out.remap_from0 = sprite.remap_from0;
+    out.remap_from1 = sprite.remap_from1;
+    out.remap_to0 = sprite.remap_to0;
+    out.remap_to1 = sprite.remap_to1;
     return out;
 }
 
@@ -72,7 +116,7 @@ fn multiply_u8(a: u32, b: u32) -> u32 {
 }
 
 // Mirrors `gem::channel::mix_u8`. `b - a` can be negative, so this runs signed, matching the
-// round-half-away-from-zero behaviour of the Rust reference.
+// round-half-away-from-zero behavior of the Rust reference.
 fn mix_u8(a: i32, b: i32, t: i32) -> i32 {
     let delta = (b - a) * t;
     var rounded: i32;
@@ -84,12 +128,25 @@ fn mix_u8(a: i32, b: i32, t: i32) -> i32 {
     return a + rounded;
 }
 
-// Samples the sprite, recolours it, and outputs straight-alpha RGBA. The caller enables
+// Entry `i` of a remap table split across two `vec4<u32>`s.
+fn remap_entry(lo: vec4<u32>, hi: vec4<u32>, i: u32) -> u32 {
+    if i < 4u {
+        return lo[i];
+    }
+    return hi[i - 4u];
+}
+
+fn unpack_rgb(c: u32) -> vec3<u32> {
+    return vec3<u32>((c >> 16u) & 255u, (c >> 8u) & 255u, c & 255u);
+}
+
+// Samples the sprite, recolors it, and outputs straight-alpha RGBA. The caller enables
 // source-over blending, so a sprite's transparent pixels let the layers below show through.
 //
-// The recolour is the two stages `retroglyph_window::sprite_cache::SpriteTint::apply` defines, in
-// that order: the sheet's own treatment (a multiply by the cell's foreground for a mask sheet),
-// then the cell's tint. Alpha is never touched by either, so which pixels are opaque, and
+// The recolor is the stages `retroglyph_window::sprite_cache::SpriteTint::apply` defines, in
+// that order: the cell's remap, or for a pixel it leaves alone the sheet's own treatment (a
+// multiply by the cell's foreground for a mask sheet), then the cell's tint. Alpha is never
+// touched by any of them, so which pixels are opaque, and
 // therefore the blending above, is identical tinted or not. The math is a bit-exact translation of
 // the two `u8` helpers `retroglyph_core::color::Tint::apply` calls rather than an approximation in
 // normalized float: the CPU rasterizer calls those functions directly, and `headless`'s parity
@@ -113,6 +170,23 @@ fn fs_sprite(in: SpriteVarying) -> @location(0) vec4<f32> {
         );
     }
 
+    // Ahead of stage 1, in effect: a pixel the remap maps replaces stage 1's result with its
+    // target. Kind 1 searches for an exact source color, first entry winning; kind 2 picks the
+    // ramp shade nearest the pixel's integer luma. Both mirror `Remap::lookup`.
+    if in.remap.x == 1u {
+        let key = (src_u8.r << 16u) | (src_u8.g << 8u) | src_u8.b;
+        for (var i = 0u; i < in.remap.y; i++) {
+            if remap_entry(in.remap_from0, in.remap_from1, i) == key {
+                rgb = unpack_rgb(remap_entry(in.remap_to0, in.remap_to1, i));
+                break;
+            }
+        }
+    } else if in.remap.x == 2u {
+        let gray = (77u * src_u8.r + 150u * src_u8.g + 29u * src_u8.b + 128u) >> 8u;
+        let index = (gray * (in.remap.y - 1u) + 127u) / 255u;
+        rgb = unpack_rgb(remap_entry(in.remap_to0, in.remap_to1, index));
+    }
+
     // Stage 2: the cell's tint. Op 0 (including any `Tint` variant added after this renderer was
     // written) leaves the artwork alone rather than guessing at a misread payload.
     if in.tint_op == 1u {
@@ -0,0 +1,429 @@
+//! Animated tileset entries: a codepoint that cycles through several of its sheet's tiles over time.
+//!
+//! A tileset declares each animation against the tile indices of its own sheet (row-major, the
+//! same numbering a [`Codepage`](crate::tileset::Codepage) maps), either in code with
+//! [`TilesetBuilder::animate`](crate::tileset::TilesetBuilder::animate) or in a metadata file read by
+//! [`TilesetBuilder::animation_metadata`](crate::tileset::TilesetBuilder::animation_metadata).
+//! [`SpriteCache::load`](crate::sprite_cache::SpriteCache::load) resolves those tiles to the
+//! codepoints they are stored under, and every pixel backend then substitutes the frame showing at
+//! its [`animation_time`](crate::presenter::Presenter::animation_time) before its sprite lookup,
+//! so a cell holding the animated codepoint changes artwork while the grid itself never does.
+//!
+//! Every animation reads the same clock, so two cells holding the same codepoint always show the
+//! same frame, and animations of equal length stay in step with each other.
+
+use crate::tileset::TilesetError;
+use core::time::Duration;
+use std::collections::BTreeMap;
+
+/// What an animation does once it has shown its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum LoopMode {
+    /// Start over from the first frame.
+    #[default]
+    Loop,
+    /// Play back down to the second frame, then forward again: `0 1 2 1 0 1 2 ...`. The end
+    /// frames are shown once per turn, not twice.
+    PingPong,
+    /// Hold the last frame forever.
+    Once,
+}
+
+/// One frame of a [`SpriteAnimation`]: a tile of the sheet, and how long it shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnimationFrame {
+    /// The frame's tile index in the sheet, row-major.
+    pub tile: usize,
+    /// How long the frame shows before the next one.
+    pub duration: Duration,
+}
+
+/// A frame list for one animated tileset entry.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use retroglyph_window::animation::{LoopMode, SpriteAnimation};
+///
+/// // Tiles 4, 5 and 6, a quarter second each, bouncing back and forth.
+/// let water = SpriteAnimation::uniform([4, 5, 6], Duration::from_millis(250))
+///     .mode(LoopMode::PingPong);
+/// assert_eq!(water.frame_at(Duration::from_millis(600)), 2);
+/// assert_eq!(water.frame_at(Duration::from_millis(800)), 1);
+/// assert_eq!(water.tile_at(Duration::from_millis(800)), Some(5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SpriteAnimation {
+    frames: Vec<AnimationFrame>,
+    /// When each frame stops showing, in nanoseconds from the start of a forward pass: the running
+    /// sum of the durations, kept alongside `frames` so [`frame_at`](Self::frame_at) can binary
+    /// search it on every draw without allocating.
+    ends: Vec<u128>,
+    mode: LoopMode,
+}
+
+impl SpriteAnimation {
+    /// An animation with no frames yet, which loops. Add frames with [`frame`](Self::frame).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            ends: Vec::new(),
+            mode: LoopMode::Loop,
+        }
+    }
+
+    /// A looping animation showing each of `tiles` for `duration`.
+    #[must_use]
+    pub fn uniform(tiles: impl IntoIterator<Item = usize>, duration: Duration) -> Self {
+        tiles.into_iter().fold(Self::new(), |animation, tile| {
+            animation.frame(tile, duration)
+        })
+    }
+
+    /// Appends a frame showing sheet tile `tile` for `duration`.
+    #[must_use]
+    pub fn frame(mut self, tile: usize, duration: Duration) -> Self {
+        let start = self.ends.last().copied().unwrap_or(0);
+        self.ends.push(start.saturating_add(duration.as_nanos()));
+        self.frames.push(AnimationFrame { tile, duration });
+        self
+    }
+
+    /// Sets what happens after the last frame. Defaults to [`LoopMode::Loop`].
+    #[must_use]
+    pub const fn mode(mut self, mode: LoopMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The frames, in order.
+    #[must_use]
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// What happens after the last frame.
+    #[must_use]
+    pub const fn loop_mode(&self) -> LoopMode {
+        self.mode
+    }
+
+    /// The index into [`frames`](Self::frames) showing `time` after the animation clock's start.
+    ///
+    /// A frame starts showing at the instant the previous one's duration runs out. An animation
+    /// whose frames all last zero time shows its first frame, and one without frames reports `0`.
+    #[must_use]
+    pub fn frame_at(&self, time: Duration) -> usize {
+        let count = self.ends.len();
+        let total = self.ends.last().copied().unwrap_or(0);
+        if count < 2 || total == 0 {
+            return 0;
+        }
+        let time = time.as_nanos();
+        match self.mode {
+            LoopMode::Loop => self.showing_at(time % total),
+            LoopMode::Once if time >= total => count - 1,
+            LoopMode::Once => self.showing_at(time),
+            LoopMode::PingPong => {
+                // The way back skips both end frames, so the turn is one forward pass plus the
+                // inner frames once more.
+                let (inner_start, inner_end) = (self.ends[0], self.ends[count - 2]);
+                let cycle = total + (inner_end - inner_start);
+                let at = time % cycle;
+                if at < total {
+                    self.showing_at(at)
+                } else {
+                    // Walking back through the inner frames is walking forward through them
+                    // from their end: the frame showing is the one spanning that mirrored time,
+                    // counting its end rather than its start as inside it.
+                    let mirrored = inner_end - (at - total);
+                    self.ends.partition_point(|&end| end < mirrored)
+                }
+            }
+        }
+    }
+
+    /// The index of the frame showing `at` nanoseconds into a forward pass, which is shorter than
+    /// the whole pass.
+    fn showing_at(&self, at: u128) -> usize {
+        self.ends.partition_point(|&end| end <= at)
+    }
+
+    /// The sheet tile showing `time` after the animation clock's start, or `None` without frames.
+    #[must_use]
+    pub fn tile_at(&self, time: Duration) -> Option<usize> {
+        self.frames.get(self.frame_at(time)).map(|frame| frame.tile)
+    }
+
+    /// Parses an animation metadata file into `(codepoint, animation)` entries, in file order.
+    ///
+    /// Each line declares one animated codepoint: the codepoint (a literal character, or `U+`
+    /// and its hex value), a [`LoopMode`] (`loop`, `pingpong`, or `once`), then one or more
+    /// `tile:millis` frames. Blank lines and everything after a `#` are ignored, so a literal `#`
+    /// is written `U+0023`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TilesetError::InvalidAnimation`] for a line missing its mode or frames, or with a
+    /// codepoint, mode, or frame that doesn't parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use retroglyph_window::animation::{LoopMode, SpriteAnimation};
+    ///
+    /// let text = "
+    /// ## codepoint  mode      frames (tile:millis)
+    /// ~            loop      0:200 1:200 2:200
+    /// U+E100       pingpong  8:100 9:100 10:300
+    /// ";
+    /// let entries = SpriteAnimation::parse_metadata(text)?;
+    /// assert_eq!(entries[0].0, '~');
+    /// assert_eq!(entries[1].0, '\u{E100}');
+    /// assert_eq!(entries[1].1.loop_mode(), LoopMode::PingPong);
+    /// assert_eq!(entries[1].1.frames()[2].duration, Duration::from_millis(300));
+    /// # Ok::<(), retroglyph_window::tileset::TilesetError>(())
+    /// ```
+    pub fn parse_metadata(text: &str) -> Result<Vec<(char, Self)>, TilesetError> {
+        let mut entries = Vec::new();
+        for (number, raw) in text.lines().enumerate() {
+            let line = number + 1;
+            let content = raw.split_once('#').map_or(raw, |(before, _)| before);
+            let mut fields = content.split_whitespace();
+            let Some(codepoint) = fields.next() else {
+                continue;
+            };
+            let codepoint = parse_codepoint(codepoint)
+                .ok_or_else(|| invalid(line, "the codepoint is not a character or U+hex value"))?;
+            let mode = match fields.next() {
+                Some("loop") => LoopMode::Loop,
+                Some("pingpong") => LoopMode::PingPong,
+                Some("once") => LoopMode::Once,
+                Some(_) => return Err(invalid(line, "the mode is not loop, pingpong, or once")),
+                None => return Err(invalid(line, "missing the mode and frames")),
+            };
+            let mut animation = Self::new().mode(mode);
+            for field in fields {
+                let (tile, millis) = field
+                    .split_once(':')
+                    .and_then(|(tile, millis)| Some((tile.parse().ok()?, millis.parse().ok()?)))
+                    .ok_or_else(|| invalid(line, "a frame is not tile:millis"))?;
+                animation = animation.frame(tile, Duration::from_millis(millis));
+            }
+            if animation.frames.is_empty() {
+                return Err(invalid(line, "missing the frames"));
+            }
+            entries.push((codepoint, animation));
+        }
+        Ok(entries)
+    }
+}
+
+/// A metadata codepoint: one literal character, or `U+` and a hex scalar value.
+fn parse_codepoint(field: &str) -> Option<char> {
+    if let Some(hex) = field
+        .strip_prefix("U+")
+        .or_else(|| field.strip_prefix("u+"))
+    {
+        return char::from_u32(u32::from_str_radix(hex, 16).ok()?);
+    }
+    let mut chars = field.chars();
+    let ch = chars.next()?;
+    chars.next().is_none().then_some(ch)
+}
+
+const fn invalid(line: usize, reason: &'static str) -> TilesetError {
+    TilesetError::InvalidAnimation { line, reason }
+}
+
+/// The animations a [`SpriteCache`](crate::sprite_cache::SpriteCache) holds, with each frame's
+/// tile resolved to the codepoint its sprite is stored under.
+///
+/// Built by [`SpriteCache::load`](crate::sprite_cache::SpriteCache::load); a backend only reads it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpriteAnimations {
+    /// Each animated codepoint's timing, and the codepoint of each of its frames' sprites.
+    entries: BTreeMap<char, (SpriteAnimation, Vec<char>)>,
+}
+
+impl SpriteAnimations {
+    /// No animations.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Whether no codepoint is animated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether `ch` is animated.
+    #[must_use]
+    pub fn contains(&self, ch: char) -> bool {
+        self.entries.contains_key(&ch)
+    }
+
+    /// Animates `ch` with `animation`, whose frames show the sprites stored under `glyphs`, one per
+    /// frame. Replaces any earlier animation of `ch`.
+    pub(crate) fn insert(&mut self, ch: char, animation: SpriteAnimation, glyphs: Vec<char>) {
+        debug_assert_eq!(animation.frames.len(), glyphs.len());
+        self.entries.insert(ch, (animation, glyphs));
+    }
+
+    /// The codepoint whose sprite `ch` shows at animation time `time`: the current frame's if `ch`
+    /// is animated, `ch` itself otherwise.
+    #[must_use]
+    pub fn glyph_at(&self, ch: char, time: Duration) -> char {
+        self.entries
+            .get(&ch)
+            .and_then(|(animation, glyphs)| glyphs.get(animation.frame_at(time)))
+            .copied()
+            .unwrap_or(ch)
+    }
+
+    /// Every animated codepoint and the index of the frame it shows at `time`, in codepoint order.
+    ///
+    /// A renderer that only repaints what changed compares this between frames to find the cells
+    /// an animation moved on in.
+    pub fn frames_at(&self, time: Duration) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.entries
+            .iter()
+            .map(move |(&ch, (animation, _))| (ch, animation.frame_at(time)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn loop_wraps_to_the_first_frame() {
+        let animation = SpriteAnimation::new()
+            .frame(0, ms(100))
+            .frame(1, ms(50))
+            .frame(2, ms(150));
+        let frames: Vec<usize> = [0, 99, 100, 149, 150, 299, 300, 410]
+            .map(|t| animation.frame_at(ms(t)))
+            .to_vec();
+        assert_eq!(frames, [0, 0, 1, 1, 2, 2, 0, 1]);
+    }
+
+    #[test]
+    fn once_holds_the_last_frame() {
+        let animation = SpriteAnimation::uniform([3, 4], ms(100)).mode(LoopMode::Once);
+        assert_eq!(animation.tile_at(ms(50)), Some(3));
+        assert_eq!(animation.tile_at(ms(150)), Some(4));
+        assert_eq!(animation.tile_at(ms(10_000)), Some(4));
+    }
+
+    #[test]
+    fn ping_pong_shows_each_end_frame_once_per_turn() {
+        let animation = SpriteAnimation::uniform([0, 1, 2, 3], ms(10)).mode(LoopMode::PingPong);
+        let frames: Vec<usize> = (0..12).map(|i| animation.frame_at(ms(i * 10))).collect();
+        assert_eq!(frames, [0, 1, 2, 3, 2, 1, 0, 1, 2, 3, 2, 1]);
+    }
+
+    #[test]
+    fn ping_pong_of_two_frames_alternates() {
+        let animation = SpriteAnimation::uniform([0, 1], ms(10)).mode(LoopMode::PingPong);
+        let frames: Vec<usize> = (0..4).map(|i| animation.frame_at(ms(i * 10))).collect();
+        assert_eq!(frames, [0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn ping_pong_never_shows_a_zero_length_frame_with_uneven_durations() {
+        let animation = SpriteAnimation::new()
+            .frame(0, ms(10))
+            .frame(1, ms(20))
+            .frame(2, Duration::ZERO)
+            .frame(3, ms(5))
+            .frame(4, ms(10))
+            .mode(LoopMode::PingPong);
+        // Forward over 45ms, then back through frames 3 and 1 (frame 2 takes no time) for 25ms.
+        let frames: Vec<usize> = [0, 9, 10, 29, 30, 34, 35, 44, 45, 49, 50, 69, 70]
+            .map(|t| animation.frame_at(ms(t)))
+            .to_vec();
+        assert_eq!(frames, [0, 0, 1, 1, 3, 3, 4, 4, 3, 3, 1, 1, 0]);
+    }
+
+    #[test]
+    fn degenerate_animations_show_their_first_frame() {
+        assert_eq!(SpriteAnimation::new().frame_at(ms(5)), 0);
+        assert_eq!(SpriteAnimation::new().tile_at(ms(5)), None);
+        let instant = SpriteAnimation::uniform([7, 8], Duration::ZERO);
+        assert_eq!(instant.tile_at(ms(5)), Some(7));
+    }
+
+    #[test]
+    fn metadata_parses_codepoints_modes_and_frames() {
+        let entries = SpriteAnimation::parse_metadata(
+            "# water\n\n~ loop 0:100 1:100 # comment\nU+E000 once 5:20\n",
+        )
+        .unwrap();
+        assert_eq!(
+            entries,
+            [
+                ('~', SpriteAnimation::uniform([0, 1], ms(100))),
+                (
+                    '\u{E000}',
+                    SpriteAnimation::new().frame(5, ms(20)).mode(LoopMode::Once)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_errors_name_the_line() {
+        for (text, reason) in [
+            (
+                "~ loop 0:100\nab loop 1:1",
+                "the codepoint is not a character or U+hex value",
+            ),
+            (
+                "\n~ bounce 0:100",
+                "the mode is not loop, pingpong, or once",
+            ),
+            ("~", "missing the mode and frames"),
+            ("~ loop", "missing the frames"),
+            ("~ loop 0-100", "a frame is not tile:millis"),
+        ] {
+            let line = text.lines().count();
+            assert_eq!(
+                SpriteAnimation::parse_metadata(text)
+                    .unwrap_err()
+                    .to_string(),
+                TilesetError::InvalidAnimation { line, reason }.to_string(),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn glyph_at_substitutes_only_animated_codepoints() {
+        let mut animations = SpriteAnimations::new();
+        animations.insert(
+            'w',
+            SpriteAnimation::uniform([0, 1], ms(100)),
+            vec!['\u{E000}', '\u{E001}'],
+        );
+        assert_eq!(animations.glyph_at('w', ms(0)), '\u{E000}');
+        assert_eq!(animations.glyph_at('w', ms(100)), '\u{E001}');
+        assert_eq!(animations.glyph_at('x', ms(100)), 'x');
+        assert_eq!(
+            animations.frames_at(ms(100)).collect::<Vec<_>>(),
+            [('w', 1)]
+        );
+    }
+}
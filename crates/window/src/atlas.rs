@@ -14,29 +14,34 @@
 //! inside that 256-layer minimum. A shader turns a slot back into its `(layer, column, row)`
 //! sub-rect.
 //!
-//! # Coverage, not colour
+//! A [double-width font](FontChain::with_wide_font)'s glyph takes two adjacent slots, its left
+//! half then its right half ([`GlyphAtlas::resolve_wide`]), so every slot is still one cell and
+//! the shader needs no notion of wide glyphs: a wide character's cell samples the left slot and
+//! its spacer cell the right one.
 //!
-//! [`AtlasData::coverage`] is one byte per texel: `0xFF` where the glyph's bit is set and `0` where
-//! it isn't, meant for a single-channel (`R8`) texture. A backend samples it with nearest filtering
-//! and blends the cell's foreground over its background by that coverage, so glyphs stay crisp at
-//! any integer scale and take the cell's colours like any other glyph.
+//! # Coverage, not colour
 //!
-//! Those two values are the only ones that ever appear, and that is load-bearing rather than
-//! incidental. Coverage used as an alpha is the usual place text rendering goes wrong on colour
-//! space: a rasterizer's partial coverage is a linear quantity, so interpolating between an
-//! sRGB-encoded foreground and background by it produces text that is too thin or too fat, and
-//! correcting for that is fiddly. The question does not arise for a bitmap font, because a
-//! blend factor of exactly 0 or exactly 1 selects one endpoint outright and every colour space
-//! agrees on the result.
+//! [`AtlasData::coverage`] is one byte per texel, meant for a single-channel (`R8`) texture. A
+//! backend samples it with nearest filtering and blends the cell's foreground over its background
+//! by that coverage, so glyphs stay crisp at any integer scale and take the cell's colours like
+//! any other glyph.
 //!
-//! Those two values being the only ones is an **invariant of this module**, not an accident of the
-//! current font sources, and every backend is entitled to rely on it. `coverage_is_strictly_binary`
-//! enforces it.
+//! For a 1-bit font the coverage is `0xFF` where the glyph's bit is set and `0` where it isn't,
+//! and those two values being the only ones is load-bearing rather than incidental. Coverage used
+//! as an alpha is the usual place text rendering goes wrong on colour space: a rasterizer's
+//! partial coverage is a linear quantity, so interpolating between an sRGB-encoded foreground and
+//! background by it produces text that is too thin or too fat, and correcting for that is fiddly.
+//! The question does not arise for a bitmap font, because a blend factor of exactly 0 or exactly
+//! 1 selects one endpoint outright and every colour space agrees on the result.
+//! `coverage_is_strictly_binary` enforces it.
 //!
-//! Anything that introduces partial coverage (an antialiased or grayscale-AA font source,
-//! multisampling, a non-integer render scale) reopens the colour-space question for all three
-//! backends simultaneously, and has to be a deliberate decision rather than a side effect. See
-//! `docs/references/core/color-space.md`.
+//! An [antialiased](BitmapFont::is_antialiased) font (a TrueType font rasterized with the
+//! `truetype` feature) is the one deliberate exception: its edge texels carry the rasterizer's
+//! partial coverage, and every backend blends them in sRGB-encoded space, the same as sprite
+//! alpha (see `docs/references/core/color-space.md`). That keeps the three backends agreeing with
+//! each other, but only to within one step per channel on those edge pixels, since GPU blend units
+//! round differently from the software backend's integer blend. Nothing else introduces partial
+//! coverage: multisampling and non-integer render scales stay out.
 //!
 //! # Examples
 //!
@@ -54,7 +59,7 @@
 //! # }
 //! ```
 
-use crate::font::{BitmapFont, FontChain};
+use crate::font::{BitmapFont, FontChain, ResolvedGlyph, WideHalf};
 
 /// Glyph columns packed into one array layer.
 pub const ATLAS_COLS: u32 = 16;
@@ -71,14 +76,14 @@ pub const SLOTS_PER_LAYER: u32 = ATLAS_COLS * ATLAS_ROWS;
 /// reject one, since [`GlyphAtlas::resolve`] has no slot to name them with.
 pub const MAX_SLOTS: u32 = u16::MAX as u32 + 1;
 
-/// The number of atlas slots `font` occupies: its glyph count, capped at the 256 a `u8` glyph
-/// index can address (see [`BitmapFont::rows`]).
+/// The number of atlas slots `font` occupies: its glyph count.
 ///
-/// A font that declares more glyphs than that has no way to name them, so the atlas doesn't
-/// reserve slots for them either.
+/// Every glyph a font declares is addressable through its `u16` glyph index (see
+/// [`BitmapFont::rows`]), so a font loaded with a large repertoire (e.g. a 512-glyph PSF) gets a
+/// slot for each one.
 #[must_use]
 pub fn addressable_glyphs(font: &BitmapFont) -> u32 {
-    u32::from(font.glyph_count()).min(256)
+    u32::from(font.glyph_count())
 }
 
 /// The packing of glyph cells into an array texture: a fixed [`ATLAS_COLS`]x[`ATLAS_ROWS`] grid of
@@ -128,8 +133,8 @@ impl AtlasGeometry {
 }
 
 /// Per-font slot bases and their total, the shared answer to "where does font `i`'s glyphs start,
-/// and how many slots does the whole chain need": font `i` starts at the sum of the addressable
-/// glyph counts of every font before it in chain order.
+/// and how many slots does the whole chain need": font `i` starts at the sum of the slots every
+/// font before it in chain order takes, one per addressable glyph, or two for a double-width font.
 struct SlotLayout {
     /// Flat atlas slot at which each font in the chain's glyphs start, indexed by the font's
     /// position in the chain.
@@ -143,13 +148,19 @@ struct SlotLayout {
 fn slot_layout(fonts: &FontChain<'_>) -> SlotLayout {
     let mut bases = Vec::with_capacity(fonts.font_count());
     let mut total = 0;
-    for font in fonts.fonts() {
+    for (font_index, font) in fonts.fonts().enumerate() {
         bases.push(total);
-        total += addressable_glyphs(font);
+        total += addressable_glyphs(font) * slots_per_glyph(fonts, font_index);
     }
     SlotLayout { bases, total }
 }
 
+/// The slots each glyph of the font at `font_index` takes: two halves for a double-width font, one
+/// otherwise.
+fn slots_per_glyph(fonts: &FontChain<'_>, font_index: usize) -> u32 {
+    if fonts.is_wide_font(font_index) { 2 } else { 1 }
+}
+
 /// The CPU-side coverage buffer for a whole atlas, grid-packed per [`AtlasGeometry`].
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -167,9 +178,10 @@ impl AtlasData {
     /// slot per glyph, so a static bitmap font needs no runtime rasterization.
     ///
     /// The fonts are laid out back to back in chain order, so a font's slots start at the sum of
-    /// the glyph counts before it, which is the same base [`GlyphAtlas::resolve`] adds to a
-    /// resolved glyph's own index. Every font in the chain is assumed to share `cell_size`; a
-    /// backend's builder checks that via [`FontChain::glyph_size`] before getting here.
+    /// the slots before it, which is the same base [`GlyphAtlas::resolve`] adds to a resolved
+    /// glyph's own index. Every font in the chain is assumed to share `cell_size` (a double-width
+    /// font, twice its width); a backend's builder checks that via [`FontChain::glyph_size`]
+    /// before getting here.
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
     pub fn build(fonts: &FontChain<'static>, cell_size: (u32, u32)) -> Self {
@@ -181,17 +193,27 @@ impl AtlasData {
         let tex_h = geometry.tex_h();
         let mut coverage = vec![0u8; (tex_w * tex_h * geometry.layers) as usize];
 
-        for (font, base) in fonts.fonts().zip(bases) {
+        for ((font_index, font), base) in fonts.fonts().enumerate().zip(bases) {
+            let slots = slots_per_glyph(fonts, font_index);
             for index in 0..addressable_glyphs(font) {
-                let (layer, gcol, grow) = AtlasGeometry::locate(base + index);
-                let (ox, oy) = (gcol * cell_w, grow * cell_h);
-                // `glyph_pixels` yields each set pixel `(x, y)` decoded MSB-first (the bit order
-                // lives in the font module, #164), so this stays width-agnostic.
-                for (x, y) in font.glyph_pixels(index as u8) {
-                    let px = ox + u32::from(x);
-                    let py = oy + u32::from(y);
-                    let idx = ((layer * tex_h + py) * tex_w + px) as usize;
-                    coverage[idx] = 0xFF;
+                for slot in 0..slots {
+                    let (layer, gcol, grow) = AtlasGeometry::locate(base + index * slots + slot);
+                    let (ox, oy) = (gcol * cell_w, grow * cell_h);
+                    // A double-width glyph's second slot holds its right half, starting `cell_w`
+                    // columns in. `glyph_coverage` yields each covered pixel `(x, y, coverage)`
+                    // already decoded (the row formats live in the font module, #164), so this
+                    // stays width- and format-agnostic.
+                    let first = slot * cell_w;
+                    for (x, y, value) in font.glyph_coverage(index as u16) {
+                        let x = u32::from(x);
+                        if !(first..first + cell_w).contains(&x) {
+                            continue;
+                        }
+                        let px = ox + x - first;
+                        let py = oy + u32::from(y);
+                        let idx = ((layer * tex_h + py) * tex_w + px) as usize;
+                        coverage[idx] = value;
+                    }
                 }
             }
         }
@@ -240,7 +262,8 @@ impl GlyphAtlas {
         }
     }
 
-    /// A resolved glyph's flat atlas slot: its font's base plus its own glyph index.
+    /// A resolved glyph's flat atlas slot: its font's base plus its own glyph index, or, for a
+    /// half of a double-width glyph, that half's slot of the glyph's pair.
     ///
     /// # Panics
     ///
@@ -248,8 +271,14 @@ impl GlyphAtlas {
     /// [`slot_count`](Self::slot_count) is within [`MAX_SLOTS`], which a backend's builder is
     /// expected to have checked.
     #[allow(clippy::cast_possible_truncation)]
-    fn slot_of(bases: &[u32], glyph: &crate::font::ResolvedGlyph) -> u16 {
-        (bases[glyph.font_index()] + u32::from(glyph.index())) as u16
+    fn slot_of(bases: &[u32], glyph: &ResolvedGlyph) -> u16 {
+        let base = bases[glyph.font_index()];
+        let index = u32::from(glyph.index());
+        (match glyph.half() {
+            None => base + index,
+            Some(WideHalf::Left) => base + index * 2,
+            Some(WideHalf::Right) => base + index * 2 + 1,
+        }) as u16
     }
 
     /// Glyph cell size in unscaled pixels.
@@ -300,6 +329,16 @@ impl GlyphAtlas {
         Some(Self::slot_of(&self.bases, &glyph))
     }
 
+    /// Resolves `ch` to the two atlas slots of its double-width glyph, `[left, right]`, or `None`
+    /// when no double-width font covers it (see [`FontChain::resolve_wide`]); the caller then
+    /// draws it in one cell through [`resolve`](Self::resolve).
+    #[must_use]
+    pub fn resolve_wide(&self, ch: char) -> Option<[u16; 2]> {
+        let left = self.fonts.resolve_wide(ch, WideHalf::Left)?;
+        let slot = Self::slot_of(&self.bases, &left);
+        Some([slot, slot + 1])
+    }
+
     /// Whether [`resolve`](Self::resolve) fell back to the substituted "not defined" glyph for
     /// `ch`, rather than a font actually covering it.
     ///
@@ -411,7 +450,7 @@ mod tests {
         assert!(atlas.is_notdef('あ'), "nothing in the chain covers 'あ'");
     }
 
-    /// Every coverage byte is exactly `0x00` or `0xFF`, never anything between.
+    /// Every coverage byte of a 1-bit font is exactly `0x00` or `0xFF`, never anything between.
     ///
     /// This is what lets every backend treat coverage as a blend factor without worrying about
     /// colour space: an alpha of exactly 0 or 1 selects one endpoint outright, so sRGB-encoded and
@@ -438,6 +477,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wide_glyph_rows_fill_the_whole_cell() {
+        // One 12x2 glyph (#164): row 0 is its leftmost and rightmost columns, which live in
+        // different bytes of the row; row 1 is empty.
+        static DATA: [u8; 4] = [0b0000_1000, 0b0000_0001, 0, 0];
+        const FONT: BitmapFont = BitmapFont::new(&DATA, 12, 2, 1);
+        let atlas = AtlasData::build(&FontChain::from(FONT), (12, 2));
+
+        let tex_w = atlas.geometry.tex_w() as usize;
+        assert_eq!(tex_w, 12 * ATLAS_COLS as usize);
+        let row0: Vec<usize> = (0..12).filter(|&x| atlas.coverage[x] == 0xFF).collect();
+        assert_eq!(row0, [0, 11]);
+        assert!(atlas.coverage[tex_w..tex_w + 12].iter().all(|&c| c == 0));
+    }
+
+    #[cfg(feature = "truetype")]
+    #[test]
+    fn antialiased_glyphs_keep_partial_coverage() {
+        use crate::testing::truetype_rect_font;
+        // One glyph 2.5 pixels wide at 10px: its third column is about half covered.
+        let bytes = truetype_rect_font(&[('A', [0, 0, 250, 400])]);
+        let font = BitmapFont::from_truetype(&bytes, 10.0).unwrap();
+        let atlas = AtlasData::build(&FontChain::from(font), (5, 10));
+
+        let tex_w = atlas.geometry.tex_w() as usize;
+        let row = &atlas.coverage[4 * tex_w..4 * tex_w + 5];
+        assert_eq!(row[..2], [0xFF, 0xFF]);
+        assert!(
+            (0x70..0x90).contains(&row[2]),
+            "edge coverage {:#x}",
+            row[2]
+        );
+        assert_eq!(row[3..], [0, 0]);
+    }
+
     #[cfg(feature = "default-font")]
     #[test]
     fn coverage_is_strictly_binary_for_the_bundled_font() {
@@ -464,6 +538,36 @@ mod tests {
         );
     }
 
+    /// A double-width font's glyph takes two adjacent slots, its left half then its right half.
+    #[test]
+    fn a_wide_font_takes_two_slots_per_glyph() {
+        static PRIMARY_DATA: [u8; 256 * 2] = [0; 256 * 2];
+        const PRIMARY: BitmapFont = BitmapFont::new(&PRIMARY_DATA, 8, 2, 256);
+        // Left half: top row set. Right half: bottom row set.
+        static WIDE_DATA: [u8; 4] = [0xFF, 0x00, 0x00, 0xFF];
+        const CHARSET: [char; 1] = ['語'];
+        let chain = FontChain::from(PRIMARY)
+            .with_wide_font(BitmapFont::with_charset(&WIDE_DATA, 16, 2, 1, &CHARSET));
+
+        let atlas = GlyphAtlas::new(chain, (8, 2));
+        assert_eq!(atlas.slot_count(), 258);
+        assert_eq!(atlas.resolve_wide('語'), Some([256, 257]));
+        assert_eq!(atlas.resolve_wide('A'), None);
+
+        let data = atlas.data();
+        let tex_w = data.geometry.tex_w();
+        let tex_h = data.geometry.tex_h();
+        let row = |slot: u32, y: u32| {
+            let (layer, gcol, grow) = AtlasGeometry::locate(slot);
+            let start = ((layer * tex_h + grow * 2 + y) * tex_w + gcol * 8) as usize;
+            data.coverage[start..start + 8].to_vec()
+        };
+        assert_eq!(row(256, 0), [0xFF; 8]);
+        assert_eq!(row(256, 1), [0; 8]);
+        assert_eq!(row(257, 0), [0; 8]);
+        assert_eq!(row(257, 1), [0xFF; 8]);
+    }
+
     #[cfg(feature = "default-font")]
     #[test]
     fn space_is_blank_and_full_block_is_solid_in_their_cells() {
@@ -498,14 +602,8 @@ mod tests {
     fn atlas_maps_char_to_font_index() {
         use crate::font::unscii16;
         let atlas = GlyphAtlas::new(FontChain::from(unscii16::FONT), (8, 16));
-        assert_eq!(
-            atlas.resolve('A'),
-            unscii16::FONT.glyph_index('A').map(u16::from)
-        );
-        assert_eq!(
-            atlas.space_slot(),
-            unscii16::FONT.glyph_index(' ').map(u16::from).unwrap()
-        );
+        assert_eq!(atlas.resolve('A'), unscii16::FONT.glyph_index('A'));
+        assert_eq!(atlas.space_slot(), unscii16::FONT.glyph_index(' ').unwrap());
         assert_eq!(atlas.cell_size(), (8, 16));
     }
 }
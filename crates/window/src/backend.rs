@@ -3,10 +3,12 @@
 
 use crate::presenter::Presenter;
 use retroglyph_core::backend::DrawCell;
-use retroglyph_core::backend::{Compositing, Cursor, Input, Output};
+use retroglyph_core::backend::{Clipboard, ClipboardError, Compositing, Cursor};
+use retroglyph_core::backend::{EventInbox, EventSender, Input, Output};
 use retroglyph_core::event::{Event, push_coalesced};
 use retroglyph_core::grid::Size;
 use std::collections::VecDeque;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 
 /// A [`Backend`](retroglyph_core::backend::Backend) built from a [`Presenter`] plus an input event queue.
@@ -115,6 +117,16 @@ use std::time::Duration;
 pub struct WindowBackend<P: Presenter> {
     presenter: P,
     events: VecDeque<Event>,
+    /// Events sent from other threads through [`Input::event_sender`], merged into `events` by
+    /// every [`poll_event`](Input::poll_event). Created on first use.
+    inbox: Option<EventInbox>,
+    /// Registered by [`Input::poll_next_event`] while `events` is empty, woken by the next
+    /// [`push_event`](Input::push_event).
+    waker: Option<Waker>,
+    /// The OS clipboard behind [`Clipboard`], opened on first use so a window that never copies
+    /// never connects to the platform's clipboard service.
+    #[cfg(not(target_arch = "wasm32"))]
+    clipboard: Option<crate::clipboard::SystemClipboard>,
 }
 
 impl<P: Presenter> WindowBackend<P> {
@@ -124,6 +136,10 @@ impl<P: Presenter> WindowBackend<P> {
         Self {
             presenter,
             events: VecDeque::new(),
+            inbox: None,
+            waker: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            clipboard: None,
         }
     }
 
@@ -143,6 +159,16 @@ impl<P: Presenter> WindowBackend<P> {
     pub fn into_presenter(self) -> P {
         self.presenter
     }
+
+    /// Installs the hook every [`EventSender::send`] calls after queueing its event: the winit
+    /// driver's way of waking an event loop parked in `ControlFlow::Wait`, which otherwise would
+    /// not poll this backend again until some unrelated window event arrived.
+    #[cfg(feature = "winit")]
+    pub(crate) fn set_send_hook(&mut self, hook: impl Fn() + Send + Sync + 'static) {
+        self.inbox
+            .get_or_insert_with(EventInbox::new)
+            .set_notify(hook);
+    }
 }
 
 impl<P: Presenter> Output for WindowBackend<P> {
@@ -187,6 +213,9 @@ impl<P: Presenter> Input for WindowBackend<P> {
     fn poll_event(&mut self, _timeout: Duration) -> Option<Event> {
         // Non-blocking by design: the caller's event loop drives frame
         // timing, so there is nothing to sleep on here.
+        while let Some(event) = self.inbox.as_ref().and_then(EventInbox::try_recv) {
+            push_coalesced(&mut self.events, event);
+        }
         self.events.pop_front()
     }
 
@@ -198,6 +227,34 @@ impl<P: Presenter> Input for WindowBackend<P> {
         // [`push_coalesced`] for the shared rule (also used by `retroglyph-terminal-wasm` and
         // `Headless`).
         push_coalesced(&mut self.events, event);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Waits on the queue [`push_event`](Input::push_event) fills and on the inbox: either one
+    /// wakes a parked task.
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        if let Some(event) = self.poll_event(Duration::ZERO) {
+            return Poll::Ready(event);
+        }
+        if let Some(event) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.poll_recv(cx.waker()))
+        {
+            return Poll::Ready(event);
+        }
+        self.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Hands out a sender whose events the next [`poll_event`](Input::poll_event) picks up.
+    ///
+    /// Under the winit driver each send also wakes the event loop and schedules a frame, so an
+    /// event-driven window idling in `ControlFlow::Wait` still reacts immediately.
+    fn event_sender(&mut self) -> Option<EventSender> {
+        Some(self.inbox.get_or_insert_with(EventInbox::new).sender())
     }
 }
 
@@ -205,6 +262,39 @@ impl<P: Presenter> Input for WindowBackend<P> {
 // bodies are exactly right here.
 impl<P: Presenter> Cursor for WindowBackend<P> {}
 
+/// The native OS clipboard, through [`SystemClipboard`](crate::clipboard::SystemClipboard).
+///
+/// On `wasm32` there is none (see `SystemClipboard`'s docs), and both methods keep the trait's
+/// failing defaults.
+#[cfg(not(target_arch = "wasm32"))]
+impl<P: Presenter> Clipboard for WindowBackend<P> {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.system_clipboard()?.clipboard_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.system_clipboard()?.set_clipboard_text(text)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<P: Presenter> Clipboard for WindowBackend<P> {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<P: Presenter> WindowBackend<P> {
+    /// The OS clipboard, opened now if this is the first use. A failed open is retried on the
+    /// next call, since a clipboard manager can start after the window does.
+    fn system_clipboard(
+        &mut self,
+    ) -> Result<&mut crate::clipboard::SystemClipboard, ClipboardError> {
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => crate::clipboard::SystemClipboard::new()?,
+        };
+        Ok(self.clipboard.insert(clipboard))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,6 +405,42 @@ mod tests {
         assert_eq!(backend.poll_event(Duration::ZERO), Some(moved(3)));
     }
 
+    #[test]
+    fn sent_events_reach_poll_event_after_pushed_ones() {
+        let mut backend = WindowBackend::new(NullPresenter);
+        let sender = backend
+            .event_sender()
+            .expect("WindowBackend supports senders");
+        backend.push_event(Event::Custom(1));
+        std::thread::spawn(move || sender.send(Event::Custom(2)))
+            .join()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(backend.poll_event(Duration::ZERO), Some(Event::Custom(1)));
+        assert_eq!(backend.poll_event(Duration::ZERO), Some(Event::Custom(2)));
+        assert_eq!(backend.poll_event(Duration::ZERO), None);
+    }
+
+    #[cfg(feature = "winit")]
+    #[test]
+    fn send_hook_runs_once_per_sent_event() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut backend = WindowBackend::new(NullPresenter);
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&wakes);
+        backend.set_send_hook(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let sender = backend
+            .event_sender()
+            .expect("WindowBackend supports senders");
+        sender.send(Event::Custom(1)).unwrap();
+        sender.send(Event::Custom(2)).unwrap();
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn satisfies_the_input_contract() {
         retroglyph_core::testing::conformance::assert_input_contract(|| {
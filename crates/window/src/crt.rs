@@ -0,0 +1,178 @@
+//! Retro CRT post-processing parameters the graphical backends share.
+//!
+//! A pixel backend composites the grid into a frame first and applies these effects to the whole
+//! frame afterwards, as a separate stage: `retroglyph-software` on the CPU, `retroglyph-gl` and
+//! `retroglyph-wgpu` in a fullscreen fragment pass. The three are checked against each other, so
+//! the effect is specified here once, per output pixel, and each backend implements exactly this:
+//!
+//! 1. **Curvature** (`k`): the pixel center `(x + 0.5, y + 0.5)` is mapped to `c` in `[-1, 1]²`,
+//!    warped to `(c.x * (1 + k * c.y²), c.y * (1 + k * c.x²))`, and mapped back to a sample
+//!    position `p` in pixels. A `p` outside the frame is black, which rounds the screen's corners.
+//! 2. **Color fringing** (`f`): red is read from the frame pixel at `(floor(p.x + f), floor(p.y))`,
+//!    green at `floor(p)`, and blue at `(floor(p.x - f), floor(p.y))`, each clamped to the frame.
+//! 3. **Phosphor glow** (`g`): `g` times the mean of the nine pixels `2` apart around `floor(p)`
+//!    (offsets `-2`, `0`, `2` on both axes, clamped to the frame) is added on top.
+//! 4. **Scanlines** (`s`): a sample on an odd row (`floor(p.y)` odd) is multiplied by `1 - s`.
+//!
+//! Channels are read as `value / 255`, and the result is clamped to `[0, 1]` and rounded back to
+//! a `u8`. Every effect at zero is the identity, and a backend skips the stage entirely then.
+
+/// How strongly each CRT effect applies. [`CrtEffects::NONE`] turns the whole stage off.
+///
+/// See the [module docs](crate::crt) for what each strength does to a pixel.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_window::crt::CrtEffects;
+///
+/// let effects = CrtEffects::NONE.with_scanlines(0.5).with_fringe(1.0);
+/// assert!(effects.is_enabled());
+/// assert_eq!(effects.glow(), 0.0);
+/// assert!(!CrtEffects::default().is_enabled());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrtEffects {
+    scanlines: f32,
+    glow: f32,
+    curvature: f32,
+    fringe: f32,
+}
+
+impl CrtEffects {
+    /// Every effect off: backends present the composited frame unchanged.
+    pub const NONE: Self = Self {
+        scanlines: 0.0,
+        glow: 0.0,
+        curvature: 0.0,
+        fringe: 0.0,
+    };
+
+    /// A moderate all-round preset: visible scanlines, a soft glow, a gentle bulge, and a one-pixel
+    /// fringe.
+    pub const CLASSIC: Self = Self {
+        scanlines: 0.35,
+        glow: 0.25,
+        curvature: 0.08,
+        fringe: 1.0,
+    };
+
+    /// Sets how much odd rows are darkened, from `0.0` (not at all) to `1.0` (black).
+    #[must_use]
+    pub const fn with_scanlines(mut self, strength: f32) -> Self {
+        self.scanlines = unit(strength);
+        self
+    }
+
+    /// Sets how much of the blurred neighborhood is added back on top of each pixel. `0.0` is
+    /// none; values above `1.0` are allowed and bloom heavily.
+    #[must_use]
+    pub const fn with_glow(mut self, strength: f32) -> Self {
+        self.glow = non_negative(strength);
+        self
+    }
+
+    /// Sets the barrel distortion. `0.0` is a flat screen; around `0.1` reads as a CRT's bulge.
+    #[must_use]
+    pub const fn with_curvature(mut self, strength: f32) -> Self {
+        self.curvature = non_negative(strength);
+        self
+    }
+
+    /// Sets how far, in physical pixels, the red and blue channels are pulled apart.
+    #[must_use]
+    pub const fn with_fringe(mut self, pixels: f32) -> Self {
+        self.fringe = non_negative(pixels);
+        self
+    }
+
+    /// The scanline strength, in `[0, 1]`.
+    #[must_use]
+    pub const fn scanlines(&self) -> f32 {
+        self.scanlines
+    }
+
+    /// The glow strength.
+    #[must_use]
+    pub const fn glow(&self) -> f32 {
+        self.glow
+    }
+
+    /// The curvature strength.
+    #[must_use]
+    pub const fn curvature(&self) -> f32 {
+        self.curvature
+    }
+
+    /// The color fringe offset, in physical pixels.
+    #[must_use]
+    pub const fn fringe(&self) -> f32 {
+        self.fringe
+    }
+
+    /// Whether any effect is on, i.e. whether a backend runs the post-processing stage at all.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.scanlines > 0.0 || self.glow > 0.0 || self.curvature > 0.0 || self.fringe > 0.0
+    }
+
+    /// The four strengths in the order the GPU shaders take them: scanlines, glow, curvature,
+    /// fringe.
+    #[must_use]
+    pub const fn to_array(&self) -> [f32; 4] {
+        [self.scanlines, self.glow, self.curvature, self.fringe]
+    }
+}
+
+// Every constructor sanitizes its strength, so no field is ever NaN and `==` is a total equivalence.
+impl Eq for CrtEffects {}
+
+impl Default for CrtEffects {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// `value` clamped to `[0, 1]`, with NaN treated as `0`.
+const fn unit(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// `value` with negatives, NaN, and infinity treated as `0`.
+const fn non_negative(value: f32) -> f32 {
+    if value.is_finite() && value > 0.0 {
+        value
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CrtEffects;
+
+    #[test]
+    fn strengths_are_sanitized_so_backends_never_see_nonsense() {
+        let effects = CrtEffects::NONE
+            .with_scanlines(3.0)
+            .with_glow(-1.0)
+            .with_curvature(f32::NAN)
+            .with_fringe(f32::INFINITY);
+        assert_eq!(effects, CrtEffects::NONE.with_scanlines(1.0));
+        assert_eq!(CrtEffects::NONE.with_scanlines(f32::NAN), CrtEffects::NONE);
+    }
+
+    #[test]
+    fn any_single_effect_enables_the_stage() {
+        assert!(!CrtEffects::NONE.is_enabled());
+        assert!(CrtEffects::NONE.with_scanlines(0.1).is_enabled());
+        assert!(CrtEffects::NONE.with_glow(0.1).is_enabled());
+        assert!(CrtEffects::NONE.with_curvature(0.1).is_enabled());
+        assert!(CrtEffects::NONE.with_fringe(0.1).is_enabled());
+        assert!(CrtEffects::CLASSIC.is_enabled());
+    }
+}
@@ -0,0 +1,821 @@
+//! Runtime loaders for font files: BDF, PSF1/PSF2, and (with the `png-fonts` feature) grid-of-glyphs
+//! PNG sheets. TrueType/OpenType outlines are rasterized by the sibling `truetype` module.
+//!
+//! Each loader decodes straight into [`BitmapFont`]'s own 1-bit row format, owning the result, so
+//! a loaded font is used exactly like a compiled-in one: on its own, or anywhere in a
+//! [`FontChain`](super::FontChain). Every loader gives the font an explicit repertoire, the same
+//! way [`BitmapFont::with_charset`] does, except a PSF font without a unicode table, which keeps
+//! the CP437 mapping its glyph order implies.
+
+use super::BitmapFont;
+use std::fmt;
+
+/// Errors from loading a font file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FontLoadError {
+    /// The data does not start with a PSF1 or PSF2 magic number.
+    NotPsf,
+    /// The data ends before its header says the font does.
+    Truncated,
+    /// A BDF file is malformed on the given 1-based line.
+    InvalidBdf {
+        /// The line the problem was found on.
+        line: usize,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The font's glyphs are zero-sized, or wider or taller than 255 pixels.
+    UnsupportedGlyphSize {
+        /// Glyph width in pixels.
+        width: u32,
+        /// Glyph height in pixels.
+        height: u32,
+    },
+    /// The font has more glyphs than a `u16` glyph index can address.
+    TooManyGlyphs(usize),
+    /// The font has no glyphs, or none mapped to a character.
+    NoGlyphs,
+    /// Image decode failed: the bytes are not a PNG the `image` crate can read.
+    #[cfg(feature = "png-fonts")]
+    ImageDecode(String),
+    /// The sheet has fewer glyph cells than the charset has characters.
+    #[cfg(feature = "png-fonts")]
+    SheetTooSmall {
+        /// Whole glyph cells in the sheet.
+        cells: usize,
+        /// Characters in the charset.
+        charset: usize,
+    },
+    /// The data is not a TrueType or OpenType font `ab_glyph` can parse.
+    #[cfg(feature = "truetype")]
+    InvalidTrueType,
+}
+
+impl fmt::Display for FontLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPsf => write!(f, "not a PSF1 or PSF2 font"),
+            Self::Truncated => write!(f, "font data ends before its declared glyphs do"),
+            Self::InvalidBdf { line, reason } => write!(f, "invalid BDF on line {line}: {reason}"),
+            Self::UnsupportedGlyphSize { width, height } => write!(
+                f,
+                "{width}x{height} glyphs are not supported; glyphs must be 1 to 255 pixels \
+                 wide and tall"
+            ),
+            Self::TooManyGlyphs(count) => write!(
+                f,
+                "{count} glyphs is more than the {} a font can address",
+                u16::MAX
+            ),
+            Self::NoGlyphs => write!(f, "the font has no glyphs mapped to a character"),
+            #[cfg(feature = "png-fonts")]
+            Self::ImageDecode(e) => write!(f, "image decode failed: {e}"),
+            #[cfg(feature = "png-fonts")]
+            Self::SheetTooSmall { cells, charset } => write!(
+                f,
+                "the sheet has {cells} glyph cells but the charset has {charset} characters"
+            ),
+            #[cfg(feature = "truetype")]
+            Self::InvalidTrueType => write!(f, "not a TrueType or OpenType font"),
+        }
+    }
+}
+
+impl std::error::Error for FontLoadError {}
+
+/// Checks a glyph size fits [`BitmapFont`]'s `u8` dimensions, returning it narrowed.
+pub(super) fn glyph_size(width: u32, height: u32) -> Result<(u8, u8), FontLoadError> {
+    match (u8::try_from(width), u8::try_from(height)) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(FontLoadError::UnsupportedGlyphSize { width, height }),
+    }
+}
+
+/// The bytes one glyph takes in [`BitmapFont`]'s row format: `height` rows of
+/// `ceil(width / 8)` bytes.
+fn glyph_bytes(width: u8, height: u8) -> usize {
+    usize::from(width).div_ceil(8) * usize::from(height)
+}
+
+/// Sets pixel `x` of a `width`-pixel `row` in [`BitmapFont::rows`]'s layout: bit
+/// `width - 1 - x` of the row read as a big-endian integer.
+fn set_pixel(row: &mut [u8], width: u8, x: u8) {
+    let bit = usize::from(width - 1 - x);
+    let last = row.len() - 1;
+    row[last - bit / 8] |= 1 << (bit % 8);
+}
+
+/// Checks a glyph count fits a `u16` glyph index.
+pub(super) fn glyph_count(count: usize) -> Result<u16, FontLoadError> {
+    match u16::try_from(count) {
+        Ok(0) => Err(FontLoadError::NoGlyphs),
+        Ok(count) => Ok(count),
+        Err(_) => Err(FontLoadError::TooManyGlyphs(count)),
+    }
+}
+
+impl BitmapFont {
+    /// Loads a PSF1 or PSF2 font, the Linux console's format (e.g. from `/usr/share/consolefonts`,
+    /// decompressed).
+    ///
+    /// A font with a unicode table maps each glyph to every codepoint the table lists for it
+    /// (multi-codepoint sequences are skipped). A font without one is assumed to be in CP437
+    /// order, like [`BitmapFont::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontLoadError::NotPsf`] if `bytes` is not PSF, [`FontLoadError::Truncated`] if it
+    /// ends early, and [`FontLoadError::UnsupportedGlyphSize`] for glyphs wider or taller than 255
+    /// pixels, or whose header's glyph size disagrees with their width and height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_window::font::BitmapFont;
+    ///
+    /// // A PSF1 header (magic, mode 0, 2-byte glyphs), then 256 glyphs of two rows each.
+    /// let mut psf = vec![0x36, 0x04, 0x00, 0x02];
+    /// psf.resize(4 + 256 * 2, 0);
+    /// psf[4 + usize::from(b'A') * 2] = 0xFF;
+    ///
+    /// let font = BitmapFont::from_psf(&psf)?;
+    /// assert_eq!((font.glyph_width(), font.glyph_height()), (8, 2));
+    /// let a = font.glyph_index('A').unwrap();
+    /// assert_eq!(font.rows(a), [0xFF, 0x00]);
+    /// # Ok::<(), retroglyph_window::font::FontLoadError>(())
+    /// ```
+    pub fn from_psf(bytes: &[u8]) -> Result<Self, FontLoadError> {
+        match bytes {
+            [0x36, 0x04, ..] => Self::from_psf1(bytes),
+            [0x72, 0xB5, 0x4A, 0x86, ..] => Self::from_psf2(bytes),
+            _ => Err(FontLoadError::NotPsf),
+        }
+    }
+
+    fn from_psf1(bytes: &[u8]) -> Result<Self, FontLoadError> {
+        const MODE_512: u8 = 0x01;
+        const MODE_HAS_TAB: u8 = 0x02;
+        const MODE_HAS_SEQ: u8 = 0x04;
+
+        let [_, _, mode, height, ..] = *bytes else {
+            return Err(FontLoadError::Truncated);
+        };
+        let count = if mode & MODE_512 == 0 { 256 } else { 512 };
+        let (width, height) = glyph_size(8, u32::from(height))?;
+        let glyphs_end = 4 + count * usize::from(height);
+        let data = bytes.get(4..glyphs_end).ok_or(FontLoadError::Truncated)?;
+
+        let map = if mode & (MODE_HAS_TAB | MODE_HAS_SEQ) == 0 {
+            None
+        } else {
+            // Per glyph: UCS-2 codepoints, then optional 0xFFFE-led sequences, then 0xFFFF.
+            let mut map = Vec::new();
+            let mut units = bytes[glyphs_end..]
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+            for glyph in 0..count {
+                let mut in_sequence = false;
+                loop {
+                    match units.next().ok_or(FontLoadError::Truncated)? {
+                        0xFFFF => break,
+                        0xFFFE => in_sequence = true,
+                        unit if !in_sequence => {
+                            if let Some(ch) = char::from_u32(u32::from(unit)) {
+                                map.push((ch, glyph_count(glyph + 1)? - 1));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Some(map)
+        };
+
+        let count = glyph_count(count)?;
+        Ok(Self::owned(data.to_vec(), width, height, count, map))
+    }
+
+    fn from_psf2(bytes: &[u8]) -> Result<Self, FontLoadError> {
+        const HAS_UNICODE_TABLE: u32 = 0x01;
+
+        let field = |i: usize| {
+            bytes
+                .get(i * 4..i * 4 + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+                .ok_or(FontLoadError::Truncated)
+        };
+        let (header_size, flags, count) = (field(2)?, field(3)?, field(4)?);
+        let (bytes_per_glyph, height, width) = (field(5)?, field(6)?, field(7)?);
+        #[allow(clippy::cast_possible_truncation)]
+        let (width, height) = glyph_size(width as u32, height as u32)?;
+        if bytes_per_glyph != glyph_bytes(width, height) {
+            // Rows are padded to whole bytes, so this only disagrees for a corrupt header.
+            return Err(FontLoadError::UnsupportedGlyphSize {
+                width: u32::from(width),
+                height: u32::from(height),
+            });
+        }
+        let glyph_total = glyph_count(count)?;
+        let glyphs_end = count
+            .checked_mul(bytes_per_glyph)
+            .and_then(|len| len.checked_add(header_size))
+            .ok_or(FontLoadError::Truncated)?;
+        let data = bytes
+            .get(header_size..glyphs_end)
+            .ok_or(FontLoadError::Truncated)?;
+        // PSF2 pads each row on the right, where `BitmapFont` keeps a row's pixels in its low
+        // bits: shift the padding over to the left end.
+        let data = if width % 8 == 0 {
+            data.to_vec()
+        } else {
+            let mut aligned = vec![0u8; data.len()];
+            let row_len = usize::from(width).div_ceil(8);
+            for (src, dst) in data
+                .chunks_exact(row_len)
+                .zip(aligned.chunks_exact_mut(row_len))
+            {
+                for x in 0..width {
+                    if src[usize::from(x / 8)] & (0x80 >> (x % 8)) != 0 {
+                        set_pixel(dst, width, x);
+                    }
+                }
+            }
+            aligned
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let map = if flags & HAS_UNICODE_TABLE as usize == 0 {
+            None
+        } else {
+            // Per glyph: UTF-8 codepoints, then optional 0xFE-led sequences, then 0xFF.
+            let mut map = Vec::new();
+            let mut table = bytes[glyphs_end..].split(|&b| b == 0xFF);
+            for glyph in 0..glyph_total {
+                let entry = table.next().ok_or(FontLoadError::Truncated)?;
+                let singles = entry.split(|&b| b == 0xFE).next().unwrap_or_default();
+                let text = String::from_utf8_lossy(singles);
+                map.extend(
+                    text.chars()
+                        .filter(|&ch| ch != char::REPLACEMENT_CHARACTER)
+                        .map(|ch| (ch, glyph)),
+                );
+            }
+            Some(map)
+        };
+
+        Ok(Self::owned(data, width, height, glyph_total, map))
+    }
+
+    /// Loads a BDF (Glyph Bitmap Distribution Format) font, as X11 and GNU Unifont ship.
+    ///
+    /// Each glyph's `ENCODING` is taken as its Unicode codepoint; glyphs without one (`-1`) are
+    /// skipped. Every glyph is placed in the cell the font's `FONTBOUNDINGBOX` describes, at the
+    /// offset its own `BBX` gives, so a font whose glyphs carry tight bounding boxes still lines
+    /// up on a shared baseline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontLoadError::InvalidBdf`] for a malformed file,
+    /// [`FontLoadError::UnsupportedGlyphSize`] for a bounding box wider or taller than 255 pixels,
+    /// and
+    /// [`FontLoadError::NoGlyphs`] if no glyph has an encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_window::font::BitmapFont;
+    ///
+    /// let bdf = "\
+    /// STARTFONT 2.1
+    /// FONTBOUNDINGBOX 4 3 0 -1
+    /// CHARS 1
+    /// STARTCHAR dot
+    /// ENCODING 46
+    /// BBX 1 1 1 0
+    /// BITMAP
+    /// 80
+    /// ENDCHAR
+    /// ENDFONT
+    /// ";
+    /// let font = BitmapFont::from_bdf(bdf.as_bytes())?;
+    /// assert_eq!((font.glyph_width(), font.glyph_height()), (4, 3));
+    /// let dot = font.glyph_index('.').unwrap();
+    /// // One pixel, one column in from the left, sitting on the baseline one row up from the
+    /// // bottom of the cell.
+    /// assert_eq!(font.glyph_pixels(dot).collect::<Vec<_>>(), [(1, 1)]);
+    /// # Ok::<(), retroglyph_window::font::FontLoadError>(())
+    /// ```
+    pub fn from_bdf(bytes: &[u8]) -> Result<Self, FontLoadError> {
+        let text = std::str::from_utf8(bytes).map_err(|e| FontLoadError::InvalidBdf {
+            line: bytes[..e.valid_up_to()].split(|&b| b == b'\n').count(),
+            reason: "not valid UTF-8",
+        })?;
+        Bdf::parse(text)?.into_font()
+    }
+}
+
+/// A bounding box: width, height, and the x/y offset of its lower-left corner from the origin.
+#[derive(Clone, Copy)]
+struct Bbx {
+    w: i32,
+    h: i32,
+    x: i32,
+    y: i32,
+    /// The line that declared it, for errors found only once it is placed in the cell.
+    line: usize,
+}
+
+/// The parts of a BDF file a [`BitmapFont`] needs.
+struct Bdf {
+    bounds: Bbx,
+    /// `(codepoint, bounding box, one row of bytes per bitmap row)` per glyph, in file order.
+    glyphs: Vec<(char, Bbx, Vec<Vec<u8>>)>,
+}
+
+impl Bdf {
+    fn parse(text: &str) -> Result<Self, FontLoadError> {
+        let mut bounds = None;
+        let mut glyphs = Vec::new();
+        // The glyph being read: its encoding (if any) and bounding box (if given).
+        let mut glyph: Option<(Option<char>, Option<Bbx>)> = None;
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        while let Some((line, content)) = lines.next() {
+            let invalid = |reason| FontLoadError::InvalidBdf { line, reason };
+            let mut words = content.split_whitespace();
+            match words.next() {
+                Some("FONTBOUNDINGBOX") => {
+                    bounds =
+                        Some(parse_bbx(words, line).ok_or_else(|| invalid("bad FONTBOUNDINGBOX"))?);
+                }
+                Some("STARTCHAR") => glyph = Some((None, None)),
+                Some("ENCODING") => {
+                    let (encoding, _) = glyph
+                        .as_mut()
+                        .ok_or_else(|| invalid("ENCODING outside a glyph"))?;
+                    let code: i64 = words
+                        .next()
+                        .and_then(|w| w.parse().ok())
+                        .ok_or_else(|| invalid("bad ENCODING"))?;
+                    *encoding = u32::try_from(code).ok().and_then(char::from_u32);
+                }
+                Some("BBX") => {
+                    let (_, bbx) = glyph
+                        .as_mut()
+                        .ok_or_else(|| invalid("BBX outside a glyph"))?;
+                    *bbx = Some(parse_bbx(words, line).ok_or_else(|| invalid("bad BBX"))?);
+                }
+                Some("BITMAP") => {
+                    let (encoding, bbx) = glyph
+                        .take()
+                        .ok_or_else(|| invalid("BITMAP outside a glyph"))?;
+                    let bbx = bbx
+                        .or(bounds)
+                        .ok_or_else(|| invalid("BITMAP before any bounding box"))?;
+                    let mut rows = Vec::new();
+                    loop {
+                        let (line, row) = lines.next().ok_or(FontLoadError::InvalidBdf {
+                            line,
+                            reason: "BITMAP without ENDCHAR",
+                        })?;
+                        let row = row.trim();
+                        if row == "ENDCHAR" {
+                            break;
+                        }
+                        rows.push(parse_hex_row(row).ok_or(FontLoadError::InvalidBdf {
+                            line,
+                            reason: "bad BITMAP row",
+                        })?);
+                    }
+                    if let Some(ch) = encoding {
+                        glyphs.push((ch, bbx, rows));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let bounds = bounds.ok_or(FontLoadError::InvalidBdf {
+            line: 1,
+            reason: "no FONTBOUNDINGBOX",
+        })?;
+        Ok(Self { bounds, glyphs })
+    }
+
+    fn into_font(self) -> Result<BitmapFont, FontLoadError> {
+        let Bbx { w, h, x, y, line } = self.bounds;
+        let (width, height) =
+            glyph_size(u32::try_from(w).unwrap_or(0), u32::try_from(h).unwrap_or(0))?;
+        let count = glyph_count(self.glyphs.len())?;
+        let row_len = usize::from(width).div_ceil(8);
+        let mut data = vec![0u8; self.glyphs.len() * glyph_bytes(width, height)];
+        let mut map = Vec::with_capacity(self.glyphs.len());
+        // The offsets below come straight from the file, so an extreme one is an error rather
+        // than an overflow.
+        let out_of_range = |line| FontLoadError::InvalidBdf {
+            line,
+            reason: "bounding box offset out of range",
+        };
+        let cell_top = h.checked_add(y).ok_or_else(|| out_of_range(line))?;
+
+        for ((ch, bbx, rows), (index, cell)) in self
+            .glyphs
+            .into_iter()
+            .zip((0..count).zip(data.chunks_exact_mut(glyph_bytes(width, height))))
+        {
+            map.push((ch, index));
+            // The glyph's top-left corner within the cell, counting rows down from the top.
+            let left = bbx.x.checked_sub(x).ok_or_else(|| out_of_range(bbx.line))?;
+            let top = bbx
+                .y
+                .checked_add(bbx.h)
+                .and_then(|glyph_top| cell_top.checked_sub(glyph_top))
+                .ok_or_else(|| out_of_range(bbx.line))?;
+            for (row, bits) in (top..).zip(rows.iter().take(usize::try_from(bbx.h).unwrap_or(0))) {
+                let Some(out) = usize::try_from(row)
+                    .ok()
+                    .and_then(|r| cell.chunks_exact_mut(row_len).nth(r))
+                else {
+                    continue;
+                };
+                for col in 0..bbx.w {
+                    let cx = left
+                        .checked_add(col)
+                        .ok_or_else(|| out_of_range(bbx.line))?;
+                    if cx >= i32::from(width) {
+                        break;
+                    }
+                    let byte = bits.get(usize::try_from(col / 8).unwrap_or(usize::MAX));
+                    let set = byte.is_some_and(|b| b & (0x80 >> (col % 8)) != 0);
+                    if let Ok(cx) = u8::try_from(cx)
+                        && set
+                    {
+                        set_pixel(out, width, cx);
+                    }
+                }
+            }
+        }
+
+        Ok(BitmapFont::owned(data, width, height, count, Some(map)))
+    }
+}
+
+/// Parses the four integers of a `BBX`/`FONTBOUNDINGBOX` line.
+fn parse_bbx<'a>(mut words: impl Iterator<Item = &'a str>, line: usize) -> Option<Bbx> {
+    let mut next = || words.next()?.parse::<i32>().ok();
+    let bbx = Bbx {
+        w: next()?,
+        h: next()?,
+        x: next()?,
+        y: next()?,
+        line,
+    };
+    (bbx.w >= 0 && bbx.h >= 0).then_some(bbx)
+}
+
+/// Parses one hex `BITMAP` row into its bytes.
+fn parse_hex_row(row: &str) -> Option<Vec<u8>> {
+    if !row.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..row.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(row.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(feature = "png-fonts")]
+impl BitmapFont {
+    /// Loads a PNG sheet of glyphs laid out on a grid of `glyph_width` x `glyph_height` cells,
+    /// left to right then top to bottom, with `charset[i]` naming the glyph in cell `i`.
+    ///
+    /// A pixel is set when it is both mostly opaque and bright (alpha and luminance at least
+    /// half), so white-on-black and white-on-transparent sheets both load as expected. Cells past
+    /// the end of `charset` are ignored; partial cells at the sheet's right or bottom edge are
+    /// never read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontLoadError::ImageDecode`] if `bytes` is not a readable PNG,
+    /// [`FontLoadError::UnsupportedGlyphSize`] for zero-sized cells,
+    /// [`FontLoadError::SheetTooSmall`] if `charset` names more glyphs than the sheet has cells,
+    /// and [`FontLoadError::NoGlyphs`] for an empty `charset`.
+    pub fn from_png_sheet(
+        bytes: &[u8],
+        glyph_width: u8,
+        glyph_height: u8,
+        charset: &[char],
+    ) -> Result<Self, FontLoadError> {
+        let (width, height) = glyph_size(u32::from(glyph_width), u32::from(glyph_height))?;
+        let count = glyph_count(charset.len())?;
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| FontLoadError::ImageDecode(e.to_string()))?
+            .into_rgba8();
+        let cols = image.width() / u32::from(width);
+        let cells = usize::try_from(cols * (image.height() / u32::from(height))).unwrap_or(0);
+        if cells < charset.len() {
+            return Err(FontLoadError::SheetTooSmall {
+                cells,
+                charset: charset.len(),
+            });
+        }
+
+        let row_len = usize::from(width).div_ceil(8);
+        let mut data = vec![0u8; charset.len() * glyph_bytes(width, height)];
+        for (index, cell) in
+            (0..u32::from(count)).zip(data.chunks_exact_mut(glyph_bytes(width, height)))
+        {
+            let (ox, oy) = (
+                (index % cols) * u32::from(width),
+                (index / cols) * u32::from(height),
+            );
+            for (y, row) in (0..u32::from(height)).zip(cell.chunks_exact_mut(row_len)) {
+                for x in 0..width {
+                    let [r, g, b, a] = image.get_pixel(ox + u32::from(x), oy + y).0;
+                    // Rec. 601 luma, in the same 0-255 range as the channels.
+                    let luma =
+                        (u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000;
+                    if a >= 0x80 && luma >= 0x80 {
+                        set_pixel(row, width, x);
+                    }
+                }
+            }
+        }
+
+        let map = charset.iter().copied().zip(0..count).collect();
+        Ok(Self::owned(data, width, height, count, Some(map)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FontLoadError;
+    use crate::font::{BitmapFont, FontChain};
+
+    /// A PSF2 font of `rows.len()` 8x2 glyphs, with `table` appended as its unicode table when
+    /// given.
+    fn psf2(rows: &[[u8; 2]], table: Option<&[u8]>) -> Vec<u8> {
+        let mut bytes = vec![0x72, 0xB5, 0x4A, 0x86];
+        let count = u32::try_from(rows.len()).unwrap();
+        let flags = u32::from(table.is_some());
+        for field in [0, 32, flags, count, 2, 2, 8] {
+            bytes.extend_from_slice(&u32::to_le_bytes(field));
+        }
+        bytes.extend(rows.iter().flatten());
+        bytes.extend_from_slice(table.unwrap_or_default());
+        bytes
+    }
+
+    #[test]
+    fn psf2_unicode_table_maps_every_listed_codepoint() {
+        // Glyph 0 answers for 'A' and 'Α' (Greek capital alpha); glyph 1 for 'é', plus a
+        // two-codepoint sequence ('e' + combining acute) that is skipped.
+        let table = "AΑ\u{FF}é\u{FE}e\u{301}\u{FF}";
+        let mut table_bytes = Vec::new();
+        for ch in table.chars() {
+            match ch {
+                '\u{FF}' => table_bytes.push(0xFF),
+                '\u{FE}' => table_bytes.push(0xFE),
+                _ => table_bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        let font =
+            BitmapFont::from_psf(&psf2(&[[0xF0, 0x0F], [0x18, 0x81]], Some(&table_bytes))).unwrap();
+
+        assert_eq!(font.glyph_count(), 2);
+        assert_eq!(font.glyph_index('A'), Some(0));
+        assert_eq!(font.glyph_index('Α'), Some(0));
+        assert_eq!(font.glyph_index('é'), Some(1));
+        assert_eq!(font.glyph_index('e'), None);
+        assert_eq!(font.rows(1), [0x18, 0x81]);
+    }
+
+    #[test]
+    fn psf2_without_a_table_uses_cp437() {
+        let rows = vec![[0u8; 2]; 256];
+        let font = BitmapFont::from_psf(&psf2(&rows, None)).unwrap();
+        assert_eq!(font.glyph_index('Ç'), Some(0x80));
+    }
+
+    #[test]
+    fn psf1_with_512_glyphs_and_a_table_addresses_past_256() {
+        let mut psf = vec![0x36, 0x04, 0x03, 0x01];
+        psf.extend((0..512).map(|i| u8::from(i == 300)));
+        for glyph in 0u16..512 {
+            if glyph == 300 {
+                psf.extend_from_slice(&0x263A_u16.to_le_bytes());
+            }
+            psf.extend_from_slice(&0xFFFF_u16.to_le_bytes());
+        }
+        let font = BitmapFont::from_psf(&psf).unwrap();
+        assert_eq!(font.glyph_count(), 512);
+        let smiley = font.glyph_index('☺').unwrap();
+        assert_eq!(smiley, 300);
+        assert_eq!(font.rows(smiley), [1]);
+    }
+
+    #[test]
+    fn psf2_wide_glyphs_move_row_padding_to_the_high_bits() {
+        // One 9x2 glyph. PSF2 pads rows on the right: row 0 is the leftmost and rightmost
+        // pixels, row 1 the rightmost alone.
+        let mut psf = psf2(&[[0b1000_0000, 0b1000_0000], [0, 0b1000_0000]], None);
+        psf[16..20].copy_from_slice(&1u32.to_le_bytes());
+        psf[20..24].copy_from_slice(&4u32.to_le_bytes());
+        psf[28..32].copy_from_slice(&9u32.to_le_bytes());
+
+        let font = BitmapFont::from_psf(&psf).unwrap();
+        assert_eq!((font.glyph_width(), font.glyph_count()), (9, 1));
+        assert_eq!(font.rows(0), [0b1, 0b1, 0, 0b1]);
+        assert_eq!(
+            font.glyph_pixels(0).collect::<Vec<_>>(),
+            [(0, 0), (8, 0), (8, 1)]
+        );
+    }
+
+    #[test]
+    fn psf_errors_are_typed() {
+        assert_eq!(BitmapFont::from_psf(b"nope"), Err(FontLoadError::NotPsf));
+        assert_eq!(
+            BitmapFont::from_psf(&[0x36, 0x04, 0x00, 0x10, 0x00]),
+            Err(FontLoadError::Truncated)
+        );
+        // A 9-pixel-wide glyph needs two bytes per row, so a header claiming one is corrupt.
+        let mut wide = psf2(&[[0, 0]], None);
+        wide[28..32].copy_from_slice(&9u32.to_le_bytes());
+        assert_eq!(
+            BitmapFont::from_psf(&wide),
+            Err(FontLoadError::UnsupportedGlyphSize {
+                width: 9,
+                height: 2
+            })
+        );
+    }
+
+    const BDF: &str = "\
+STARTFONT 2.1
+FONT -test-
+SIZE 4 75 75
+FONTBOUNDINGBOX 4 4 0 -1
+STARTPROPERTIES 1
+FONT_ASCENT 3
+ENDPROPERTIES
+CHARS 3
+STARTCHAR bar
+ENCODING 124
+BBX 1 4 2 -1
+BITMAP
+80
+80
+80
+80
+ENDCHAR
+STARTCHAR block
+ENCODING 9608
+BITMAP
+F0
+F0
+F0
+F0
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+BBX 4 4 0 -1
+BITMAP
+F0
+F0
+F0
+F0
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn bdf_places_glyphs_in_the_font_bounding_box() {
+        let font = BitmapFont::from_bdf(BDF.as_bytes()).unwrap();
+        assert_eq!((font.glyph_width(), font.glyph_height()), (4, 4));
+        // The unencoded glyph is dropped.
+        assert_eq!(font.glyph_count(), 2);
+
+        let bar = font.glyph_index('|').unwrap();
+        assert_eq!(font.rows(bar), [0b0010; 4]);
+        // A glyph without its own BBX inherits the font's.
+        let block = font.glyph_index('█').unwrap();
+        assert_eq!(font.rows(block), [0b1111; 4]);
+    }
+
+    #[test]
+    fn bdf_errors_report_the_line() {
+        let broken = BDF.replace("BBX 1 4 2 -1", "BBX one");
+        assert_eq!(
+            BitmapFont::from_bdf(broken.as_bytes()),
+            Err(FontLoadError::InvalidBdf {
+                line: 11,
+                reason: "bad BBX"
+            })
+        );
+        let wide = BDF.replace("FONTBOUNDINGBOX 4 4", "FONTBOUNDINGBOX 300 4");
+        assert!(matches!(
+            BitmapFont::from_bdf(wide.as_bytes()),
+            Err(FontLoadError::UnsupportedGlyphSize { width: 300, .. })
+        ));
+    }
+
+    #[test]
+    fn bdf_extreme_offsets_are_errors_not_overflows() {
+        let out_of_range = |line| {
+            Err(FontLoadError::InvalidBdf {
+                line,
+                reason: "bounding box offset out of range",
+            })
+        };
+        // Each of these overflows an i32 somewhere in placing the glyph in its cell.
+        let far_left = BDF
+            .replace("FONTBOUNDINGBOX 4 4 0 -1", "FONTBOUNDINGBOX 4 4 1 -1")
+            .replace("BBX 1 4 2 -1", "BBX 1 4 -2147483648 -1");
+        assert_eq!(BitmapFont::from_bdf(far_left.as_bytes()), out_of_range(11));
+        let far_right = BDF
+            .replace("FONTBOUNDINGBOX 4 4 0 -1", "FONTBOUNDINGBOX 4 4 -1 -1")
+            .replace("BBX 1 4 2 -1", "BBX 1 4 2147483647 -1");
+        assert_eq!(BitmapFont::from_bdf(far_right.as_bytes()), out_of_range(11));
+        let far_up = BDF.replace("BBX 1 4 2 -1", "BBX 1 4 2 2147483647");
+        assert_eq!(BitmapFont::from_bdf(far_up.as_bytes()), out_of_range(11));
+        // An extreme font bounding box is reported on its own line.
+        let font_box = BDF.replace(
+            "FONTBOUNDINGBOX 4 4 0 -1",
+            "FONTBOUNDINGBOX 4 4 0 2147483647",
+        );
+        assert_eq!(BitmapFont::from_bdf(font_box.as_bytes()), out_of_range(4));
+
+        // A huge glyph that starts off the cell's right edge is clipped, not walked pixel by
+        // pixel.
+        let huge = BDF.replace("BBX 1 4 2 -1", "BBX 2147483647 4 2147483647 -1");
+        let font = BitmapFont::from_bdf(huge.as_bytes()).unwrap();
+        assert_eq!(font.rows(font.glyph_index('|').unwrap()), [0; 4]);
+    }
+
+    #[test]
+    fn bdf_wide_glyphs_span_bytes() {
+        // A 12-pixel cell: the bar sits at column 10, in the row's second byte.
+        let wide = BDF
+            .replace("FONTBOUNDINGBOX 4 4", "FONTBOUNDINGBOX 12 4")
+            .replace("BBX 1 4 2 -1", "BBX 1 4 10 -1");
+        let font = BitmapFont::from_bdf(wide.as_bytes()).unwrap();
+        assert_eq!(font.bytes_per_row(), 2);
+
+        let bar = font.glyph_index('|').unwrap();
+        assert_eq!(font.rows(bar), [0b0000_0000, 0b0000_0010].repeat(4));
+        let block = font.glyph_index('█').unwrap();
+        assert_eq!(
+            font.glyph_pixels(block)
+                .filter(|&(_, y)| y == 0)
+                .collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn loaded_fonts_chain_with_static_fonts() {
+        static DATA: [u8; 256 * 4] = [0; 256 * 4];
+        let cp437 = BitmapFont::new(&DATA, 4, 4, 256);
+        let loaded = BitmapFont::from_bdf(BDF.as_bytes()).unwrap();
+
+        let chain = FontChain::from(loaded).with_fallback(cp437.clone());
+        assert_eq!(chain.glyph_size(), Some((4, 4)));
+        assert_eq!(chain.resolve('|').unwrap().font_index(), 0);
+        assert_eq!(*chain.resolve('A').unwrap().font(), cp437);
+    }
+
+    #[cfg(feature = "png-fonts")]
+    #[test]
+    fn png_sheet_reads_bright_opaque_pixels_per_cell() {
+        use image::ImageEncoder;
+
+        // Two 2x2 cells side by side: the first lit on its diagonal, the second lit only by a
+        // dim pixel and a transparent one, neither of which counts.
+        let white = [0xFF, 0xFF, 0xFF, 0xFF];
+        let dim = [0x40, 0x40, 0x40, 0xFF];
+        let clear = [0xFF, 0xFF, 0xFF, 0x00];
+        let black = [0, 0, 0, 0xFF];
+        let pixels: Vec<u8> = [white, black, dim, black, black, white, clear, black]
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut png = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png)
+            .write_image(&pixels, 4, 2, image::ExtendedColorType::Rgba8)
+            .unwrap();
+
+        let font = BitmapFont::from_png_sheet(&png, 2, 2, &['\\', ' ']).unwrap();
+        assert_eq!(font.rows(font.glyph_index('\\').unwrap()), [0b10, 0b01]);
+        assert_eq!(font.rows(font.glyph_index(' ').unwrap()), [0, 0]);
+
+        assert_eq!(
+            BitmapFont::from_png_sheet(&png, 2, 2, &['a', 'b', 'c']),
+            Err(FontLoadError::SheetTooSmall {
+                cells: 2,
+                charset: 3
+            })
+        );
+    }
+}
@@ -1,9 +1,20 @@
 //! Bitmap glyph fonts and CP437 mapping, shared by retroglyph's graphical backends.
 //!
-//! A [`BitmapFont`] holds a static 1-bit-per-pixel glyph table. Each glyph is stored as
-//! `glyph_height` bytes, one byte per row, MSB = leftmost pixel. For the standard 8-pixel-wide
-//! VGA format one byte covers all 8 pixels of a row; wider fonts would need two bytes per row,
-//! but that is not yet supported.
+//! A [`BitmapFont`] holds a 1-bit-per-pixel glyph table. Each glyph is stored as `glyph_height`
+//! rows of `ceil(glyph_width / 8)` bytes, MSB first. For the standard 8-pixel-wide VGA format
+//! one byte covers all 8 pixels of a row; a 9x16 VGA, 10x20, or 12x24 font takes two bytes per
+//! row (see [`BitmapFont::rows`] for the exact bit layout).
+//!
+//! The table is either `'static` data compiled into the binary ([`BitmapFont::new`],
+//! [`BitmapFont::with_charset`]) or owned data read at runtime from a BDF, PSF1/PSF2, or PNG
+//! sheet font file ([`BitmapFont::from_bdf`], [`BitmapFont::from_psf`], and, with the `png-fonts`
+//! feature, [`BitmapFont::from_png_sheet`]). Both kinds are cheap to clone and mix freely in a
+//! [`FontChain`].
+//!
+//! With the `truetype` feature, `BitmapFont::from_truetype` and `BitmapFont::from_truetype_cell`
+//! rasterize an outline font into an *antialiased* table instead: one coverage byte per pixel
+//! rather than one bit, so glyph edges blend with the background (see
+//! [`BitmapFont::is_antialiased`]).
 //!
 //! This module is the dependency-free glyph-source layer both `retroglyph-software` (CPU
 //! rasterizer) and `retroglyph-gl` (GPU atlas) build on, so their text output stays
@@ -13,48 +24,92 @@
 //! the embedded Unscii 16 font ([`unscii16::FONT`]); leave it off to supply your own via
 //! [`BitmapFont::new`].
 //!
-//! # Future work
-//!
-//! - **Expanded glyph cache:** For wider fonts (>8px), consider pre-computing expanded scanlines
-//!   to avoid per-frame bit extraction. Currently the bit extraction loop is not a bottleneck
-//!   for 8px-wide fonts at typical grid sizes, but wider fonts (10px, 16px) would benefit from
-//!   caching.
+//! A character a grid stores two columns wide (CJK, most emoji) draws from a double-width font
+//! added with [`FontChain::with_wide_font`]: [`FontChain::resolve_wide`] hands its left half to the
+//! character's own cell and its right half to the spacer cell beside it, so every backend still
+//! draws exactly one cell-sized piece of glyph per cell.
 //!
-//! - **Wider glyphs:** To support glyphs wider than 8px, change [`BitmapFont::rows`] to return
-//!   `ceil(glyph_width / 8)` bytes per row and update the consumers' bit extraction to index
-//!   across bytes. Tracked in retroglyph issue #164; deferred until a second, non-8px-wide font
-//!   is actually needed.
+//! A rasterizer that redraws glyphs every frame (the software backend) shouldn't re-extract
+//! bits across multi-byte rows each time: [`ScanlineCache`] expands each glyph it is asked for
+//! into runs of set pixels once ([`BitmapFont::glyph_spans`]) and hands back the same runs on
+//! every later draw.
+
+mod load;
+#[cfg(feature = "truetype")]
+mod truetype;
+
+pub use load::FontLoadError;
+
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::sync::Arc;
 
 // ── BitmapFont ─────────────────────────────────────────────────────────────
 
+/// A slice a font either borrows for the whole program or shares ownership of.
+///
+/// Compiled-in fonts stay `const`-constructible over `'static` data, while a font loaded at
+/// runtime owns its table without leaking it; cloning either kind never copies the data.
+#[derive(Debug, Clone)]
+enum Shared<T: 'static> {
+    Static(&'static [T]),
+    Owned(Arc<[T]>),
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Static(slice) => slice,
+            Self::Owned(slice) => slice,
+        }
+    }
+}
+
+/// How a [`BitmapFont`] maps a `char` to a glyph index.
+#[derive(Debug, Clone)]
+enum Charset {
+    /// The built-in CP437 table ([`BitmapFont::new`]).
+    Cp437,
+    /// `table[i]` is the `char` for glyph `i`, scanned linearly ([`BitmapFont::with_charset`]).
+    Table(&'static [char]),
+    /// `(char, glyph)` pairs sorted by `char`, binary searched. Loaded fonts use this: their
+    /// repertoires run to thousands of glyphs, and a PSF unicode table may map several
+    /// codepoints to one glyph.
+    Map(Arc<[(char, u16)]>),
+}
+
 /// A 1-bit-per-pixel bitmap glyph font.
 ///
-/// `Copy` because it is just a static reference plus a few small fields.
-#[derive(Debug, Clone, Copy)]
+/// Cheap to clone: the glyph table is either a `'static` reference or shared between clones.
+#[derive(Debug, Clone)]
 pub struct BitmapFont {
-    /// Glyph bitmap data: `glyph_count * glyph_height` bytes.
-    data: &'static [u8],
-    /// Width of each glyph in pixels (≤ 8 for single-byte rows).
+    /// Glyph bitmap data: `glyph_count * glyph_height * bytes_per_row()` bytes.
+    data: Shared<u8>,
+    /// Width of each glyph in pixels.
     glyph_width: u8,
-    /// Height of each glyph in pixels; also bytes per glyph.
+    /// Height of each glyph in pixels; also rows per glyph.
     glyph_height: u8,
+    /// Whether each pixel is a coverage byte rather than a bit (see [`rows`](Self::rows)).
+    antialiased: bool,
     /// Total number of glyphs stored in `data`.
     glyph_count: u16,
-    /// The `char` repertoire used by [`glyph_index`](Self::glyph_index), or `None` to use the
-    /// built-in CP437 mapping. Slot `i` in this table is glyph index `i`.
+    /// The `char` repertoire used by [`glyph_index`](Self::glyph_index).
     ///
-    /// A font built with [`with_charset`](Self::with_charset) declares its own repertoire
-    /// instead of being routed through the CP437 table every other font shares: this is what
-    /// lets a [`FontChain`] extend coverage past CP437 (e.g. quadrants, sextants, braille)
-    /// rather than every font in the chain answering the identical CP437 question.
-    charset: Option<&'static [char]>,
+    /// A font built with [`with_charset`](Self::with_charset) or loaded from a file declares its
+    /// own repertoire instead of being routed through the CP437 table every other font shares:
+    /// this is what lets a [`FontChain`] extend coverage past CP437 (e.g. quadrants, sextants,
+    /// braille) rather than every font in the chain answering the identical CP437 question.
+    charset: Charset,
 }
 
 impl BitmapFont {
     /// Constructs a bitmap font from a static byte slice, mapped through the built-in CP437
     /// `char` encoding.
     ///
-    /// `data` must contain exactly `glyph_count * glyph_height` bytes.
+    /// `data` must contain exactly `glyph_count * glyph_height * ceil(glyph_width / 8)` bytes
+    /// (see [`rows`](Self::rows) for the layout of each row).
     #[must_use]
     pub const fn new(
         data: &'static [u8],
@@ -63,11 +118,12 @@ impl BitmapFont {
         glyph_count: u16,
     ) -> Self {
         Self {
-            data,
+            data: Shared::Static(data),
             glyph_width,
             glyph_height,
+            antialiased: false,
             glyph_count,
-            charset: None,
+            charset: Charset::Cp437,
         }
     }
 
@@ -78,7 +134,7 @@ impl BitmapFont {
     /// This is how a font extends coverage past CP437: [`glyph_index`](Self::glyph_index) looks a
     /// `char` up in `charset` instead of the CP437 table, so a font built this way can answer for
     /// codepoints (quadrants, sextants, braille, ...) that CP437 has no mapping for at all.
-    /// `data` must contain exactly `glyph_count * glyph_height` bytes.
+    /// `data` is laid out exactly as for [`new`](Self::new).
     ///
     /// `charset` is scanned linearly, so it is meant for the focused repertoire a font actually
     /// declares (a few dozen block or marker glyphs), not for a second general-purpose encoding
@@ -92,71 +148,186 @@ impl BitmapFont {
         charset: &'static [char],
     ) -> Self {
         Self {
-            data,
+            data: Shared::Static(data),
+            glyph_width,
+            glyph_height,
+            antialiased: false,
+            glyph_count,
+            charset: Charset::Table(charset),
+        }
+    }
+
+    /// A font owning `data`, with `map` as its `(char, glyph index)` repertoire, or the CP437
+    /// table when `map` is `None`. The loaders' shared constructor.
+    ///
+    /// `map` is sorted here and, where a `char` appears more than once, its first entry wins.
+    fn owned(
+        data: Vec<u8>,
+        glyph_width: u8,
+        glyph_height: u8,
+        glyph_count: u16,
+        map: Option<Vec<(char, u16)>>,
+    ) -> Self {
+        let charset = map.map_or(Charset::Cp437, |mut map| {
+            map.sort_by_key(|&(ch, _)| ch);
+            map.dedup_by_key(|&mut (ch, _)| ch);
+            Charset::Map(map.into())
+        });
+        Self {
+            data: Shared::Owned(data.into()),
             glyph_width,
             glyph_height,
+            antialiased: false,
             glyph_count,
-            charset: Some(charset),
+            charset,
         }
     }
 
-    /// Returns the row bytes for glyph `index`.
+    /// Returns the row bytes for glyph `index`: `glyph_height` rows of
+    /// [`bytes_per_row`](Self::bytes_per_row) bytes each, top row first.
+    ///
+    /// A row's bytes read as one big-endian integer, and pixel `x` is bit `glyph_width - 1 - x`
+    /// of it: the leftmost pixel is the highest bit in use, and any padding bits are the
+    /// integer's unused high bits. For an 8-pixel-wide font that is simply one byte per row with
+    /// bit 7 (MSB) as the leftmost pixel; a 12-pixel row is two bytes, `0000_LLLL LLLL_LLLL`.
     ///
-    /// Each byte is one row; bit 7 (MSB) is the leftmost pixel.
+    /// An [antialiased](Self::is_antialiased) font instead stores one coverage byte per pixel,
+    /// left to right, `0` for none through `0xFF` for full.
     ///
     /// # Panics
     ///
-    /// Panics if `index as u16 >= self.glyph_count`.
+    /// Panics if `index >= self.glyph_count`.
     #[must_use]
-    pub fn rows(&self, index: u8) -> &[u8] {
+    pub fn rows(&self, index: u16) -> &[u8] {
         assert!(
-            u16::from(index) < self.glyph_count,
+            index < self.glyph_count,
             "glyph index {index} out of range ({})",
             self.glyph_count,
         );
-        let h = usize::from(self.glyph_height);
-        let start = usize::from(index) * h;
-        &self.data[start..start + h]
+        let len = usize::from(self.glyph_height) * self.bytes_per_row();
+        let start = usize::from(index) * len;
+        &self.data[start..start + len]
+    }
+
+    /// The number of bytes each glyph row takes in [`rows`](Self::rows): `ceil(glyph_width / 8)`,
+    /// or `glyph_width` for an [antialiased](Self::is_antialiased) font.
+    #[must_use]
+    pub const fn bytes_per_row(&self) -> usize {
+        if self.antialiased {
+            self.glyph_width as usize
+        } else {
+            (self.glyph_width as usize).div_ceil(8)
+        }
+    }
+
+    /// Whether this font stores a coverage byte per pixel rather than a bit, so its glyph edges
+    /// carry partial coverage (a font rasterized from TrueType outlines).
+    ///
+    /// A 1-bit font only ever reports coverage `0` or `0xFF`; see the [`atlas`](crate::atlas)
+    /// module for what partial coverage means for compositing.
+    #[must_use]
+    pub const fn is_antialiased(&self) -> bool {
+        self.antialiased
+    }
+
+    /// Coverage of pixel `x` of one of this font's rows: the stored byte for an antialiased
+    /// font, and `0xFF` or `0` for a bit of a 1-bit one.
+    fn coverage(&self, row: &[u8], x: u8) -> u8 {
+        if self.antialiased {
+            row[usize::from(x)]
+        } else if row_bit(row, self.glyph_width, x) {
+            0xFF
+        } else {
+            0
+        }
+    }
+
+    /// Iterates the covered pixels of glyph `index` as `(x, y, coverage)`, row-major from the
+    /// top: `x` in `0..glyph_width`, `y` in `0..glyph_height`, `coverage` in `1..=0xFF`. A 1-bit
+    /// font's set pixels all have coverage `0xFF`.
+    ///
+    /// This and [`glyph_spans`](Self::glyph_spans) are the only places the row formats are
+    /// decoded (pixel `x` of a 1-bit row is bit `glyph_width - 1 - x` of that row, see
+    /// [`rows`](Self::rows)), so consumers (the GL/wgpu atlas builder, the software rasterizer's
+    /// glyph blit) decode through them instead of each re-deriving the shift across a row's bytes
+    /// and risking disagreement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.glyph_count` (via [`rows`](Self::rows)).
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn glyph_coverage(&self, index: u16) -> impl Iterator<Item = (u8, u8, u8)> + '_ {
+        let width = self.glyph_width;
+        self.rows(index)
+            .chunks_exact(self.bytes_per_row())
+            .enumerate()
+            .flat_map(move |(y, row)| {
+                #[allow(clippy::cast_possible_truncation)]
+                let y = y as u8;
+                (0..width).filter_map(move |x| match self.coverage(row, x) {
+                    0 => None,
+                    coverage => Some((x, y, coverage)),
+                })
+            })
     }
 
     /// Iterates the set ("on") pixels of glyph `index` as `(x, y)` coordinates, row-major from the
-    /// top: `x` in `0..glyph_width`, `y` in `0..glyph_height`.
+    /// top: the pixels [`glyph_coverage`](Self::glyph_coverage) yields, without their coverage.
     ///
-    /// This is the single place the 1-bit format's MSB-first bit order lives (pixel `x` of a row
-    /// is bit `glyph_width - 1 - x` of that row's byte), so consumers (the GL atlas builder, the
-    /// software rasterizer's glyph blit) decode through it instead of each re-deriving the shift
-    /// and risking disagreement. It is also the one seam that has to change for wider-than-8px
-    /// glyphs (multi-byte rows, #164): today a row is a single byte (`glyph_width <= 8`), so its
-    /// bits are read straight out of that byte.
+    /// # Panics
     ///
-    /// A `glyph_width` above 8 is out of this format's contract (rows are one byte, so only bits
-    /// 0..8 exist); it is clamped to 8 here rather than shifting past the byte's width, so columns
-    /// 8.. of an oversized font are simply never yielded instead of panicking.
+    /// Panics if `index >= self.glyph_count` (via [`rows`](Self::rows)).
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn glyph_pixels(&self, index: u16) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.glyph_coverage(index).map(|(x, y, _)| (x, y))
+    }
+
+    /// Iterates the covered pixels of glyph `index` as horizontal runs, row-major from the top:
+    /// the same pixels as [`glyph_coverage`](Self::glyph_coverage), with each maximal run of
+    /// adjacent pixels of equal coverage in a row merged into one [`GlyphSpan`].
+    ///
+    /// A rasterizer fills a full-coverage run with one slice fill rather than one write per pixel;
+    /// see [`ScanlineCache`] for keeping the runs across frames.
     ///
     /// # Panics
     ///
-    /// Panics if `index as u16 >= self.glyph_count` (via [`rows`](Self::rows)).
+    /// Panics if `index >= self.glyph_count` (via [`rows`](Self::rows)).
     #[must_use = "iterators are lazy and do nothing unless consumed"]
-    pub fn glyph_pixels(&self, index: u8) -> impl Iterator<Item = (u8, u8)> + '_ {
-        let width = self.glyph_width.min(8);
+    pub fn glyph_spans(&self, index: u16) -> impl Iterator<Item = GlyphSpan> + '_ {
+        let width = self.glyph_width;
         self.rows(index)
-            .iter()
+            .chunks_exact(self.bytes_per_row())
             .enumerate()
-            .flat_map(move |(y, &row)| {
+            .flat_map(move |(y, row)| {
                 #[allow(clippy::cast_possible_truncation)]
                 let y = y as u8;
-                (0..width)
-                    .filter_map(move |x| ((row >> (width - 1 - x)) & 1 == 1).then_some((x, y)))
+                let mut x = 0;
+                core::iter::from_fn(move || {
+                    while x < width && self.coverage(row, x) == 0 {
+                        x += 1;
+                    }
+                    let start = x;
+                    let coverage = if x < width { self.coverage(row, x) } else { 0 };
+                    while x < width && self.coverage(row, x) == coverage {
+                        x += 1;
+                    }
+                    (x > start).then_some(GlyphSpan {
+                        x: start,
+                        y,
+                        len: x - start,
+                        coverage,
+                    })
+                })
             })
     }
 
-    /// The width of each glyph in pixels (≤ 8 for single-byte rows).
+    /// The width of each glyph in pixels.
     #[must_use]
     pub const fn glyph_width(&self) -> u8 {
         self.glyph_width
     }
 
-    /// The height of each glyph in pixels; also bytes per glyph.
+    /// The height of each glyph in pixels; also rows per glyph.
     #[must_use]
     pub const fn glyph_height(&self) -> u8 {
         self.glyph_height
@@ -174,10 +345,11 @@ impl BitmapFont {
     /// Maps a Unicode `char` to a glyph index in this font, or `None` if this font does not
     /// cover `ch`.
     ///
-    /// If this font was built with [`with_charset`](Self::with_charset), `ch` is looked up in
-    /// that explicit table; otherwise it goes through the built-in CP437 mapping. A miss is
-    /// either `ch` not being in this font's repertoire at all, or its mapped index falling
-    /// outside this font's `glyph_count` (e.g. a font built with fewer than 256 glyphs).
+    /// If this font was built with [`with_charset`](Self::with_charset) or loaded from a file
+    /// with its own repertoire, `ch` is looked up in that explicit table; otherwise it goes
+    /// through the built-in CP437 mapping. A miss is either `ch` not being in this font's
+    /// repertoire at all, or its mapped index falling outside this font's `glyph_count` (e.g. a
+    /// font built with fewer than 256 glyphs).
     ///
     /// A returned index is always `< glyph_count()`, so it is always a valid argument to
     /// [`rows`](Self::rows) and [`glyph_pixels`](Self::glyph_pixels).
@@ -186,28 +358,25 @@ impl BitmapFont {
     /// one's: a font cannot answer for a character it has no glyph for, and pretending otherwise
     /// is what hides a chain's later fonts from ever being consulted.
     #[must_use]
-    pub const fn glyph_index(&self, ch: char) -> Option<u8> {
-        if let Some(table) = self.charset {
-            let mut i = 0;
-            while i < table.len() {
-                if table[i] == ch && i < self.glyph_count as usize {
-                    #[allow(clippy::cast_possible_truncation)]
-                    return Some(i as u8);
-                }
-                i += 1;
-            }
-            return None;
-        }
-        match try_unicode_to_cp437(ch) {
-            Some(index) if (index as u16) < self.glyph_count => Some(index),
-            _ => None,
-        }
+    pub fn glyph_index(&self, ch: char) -> Option<u16> {
+        let index = match &self.charset {
+            Charset::Cp437 => try_unicode_to_cp437(ch).map(u16::from),
+            Charset::Table(table) => table
+                .iter()
+                .position(|&c| c == ch)
+                .and_then(|i| u16::try_from(i).ok()),
+            Charset::Map(map) => map
+                .binary_search_by_key(&ch, |&(c, _)| c)
+                .ok()
+                .map(|i| map[i].1),
+        };
+        index.filter(|&index| index < self.glyph_count)
     }
 }
 
-// Two `BitmapFont`s are equal when they point at the same static data and
-// share the same dimensions.  Comparing the full 4 KB slice on every draw
-// call would be wasteful, so we compare the data pointer instead.
+// Two `BitmapFont`s are equal when they point at the same data and share
+// the same dimensions.  Comparing the full 4 KB slice on every draw call
+// would be wasteful, so we compare the data pointer instead.
 impl PartialEq for BitmapFont {
     fn eq(&self, other: &Self) -> bool {
         core::ptr::eq(self.data.as_ptr(), other.data.as_ptr())
@@ -219,27 +388,60 @@ impl PartialEq for BitmapFont {
 
 impl Eq for BitmapFont {}
 
+/// Whether pixel `x` of a `width`-pixel `row` is set: bit `width - 1 - x` of the row read as a
+/// big-endian integer (see [`BitmapFont::rows`]).
+fn row_bit(row: &[u8], width: u8, x: u8) -> bool {
+    let bit = usize::from(width - 1 - x);
+    row[row.len() - 1 - bit / 8] >> (bit % 8) & 1 == 1
+}
+
+/// A horizontal run of equally covered pixels in a glyph: `len` pixels starting at `(x, y)`, in
+/// unscaled glyph pixels from the glyph's top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphSpan {
+    /// Column of the run's leftmost pixel.
+    pub x: u8,
+    /// Row of the run.
+    pub y: u8,
+    /// Number of pixels in the run (always at least one).
+    pub len: u8,
+    /// Coverage of every pixel in the run, `1..=0xFF`; always `0xFF` for a 1-bit font.
+    pub coverage: u8,
+}
+
 // ── Font chain ──────────────────────────────────────────────────────────────
 
+/// Which half of a double-width glyph one cell draws: the wide character's own cell takes the
+/// left half, and the spacer cell to its right the right half (see
+/// [`FontChain::with_wide_font`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WideHalf {
+    /// The left half, drawn in the wide character's own cell.
+    Left,
+    /// The right half, drawn in the spacer cell.
+    Right,
+}
+
 /// A glyph resolved from a [`FontChain`]: the glyph index plus the specific [`BitmapFont`] it
 /// came from, since each font in a chain owns its own bitmap data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ResolvedGlyph {
-    font: BitmapFont,
+pub struct ResolvedGlyph<'c> {
+    font: &'c BitmapFont,
     font_index: usize,
-    index: u8,
+    index: u16,
     notdef: bool,
+    half: Option<WideHalf>,
 }
 
-impl ResolvedGlyph {
+impl<'c> ResolvedGlyph<'c> {
     /// The font this glyph was resolved from.
     #[must_use]
-    pub const fn font(&self) -> BitmapFont {
+    pub const fn font(&self) -> &'c BitmapFont {
         self.font
     }
 
     /// The position of [`font`](Self::font) within the chain that resolved it: `0` is the
-    /// primary font, `1..` the fallbacks in order.
+    /// primary font, `1..` the fallbacks in order, then the double-width fonts.
     ///
     /// A GPU backend packs every font in the chain into one atlas and addresses a glyph by a flat
     /// slot, so it needs the font's position (a stable index into [`FontChain::fonts`]) rather
@@ -251,7 +453,7 @@ impl ResolvedGlyph {
 
     /// The glyph index within [`font`](Self::font), always `< font().glyph_count()`.
     #[must_use]
-    pub const fn index(&self) -> u8 {
+    pub const fn index(&self) -> u16 {
         self.index
     }
 
@@ -265,9 +467,58 @@ impl ResolvedGlyph {
 
     /// Returns the row bytes for this glyph; see [`BitmapFont::rows`].
     #[must_use]
-    pub fn rows(&self) -> &[u8] {
+    pub fn rows(&self) -> &'c [u8] {
         self.font.rows(self.index)
     }
+
+    /// The half of a double-width glyph this resolves to, or `None` for a glyph that fits one
+    /// cell (see [`FontChain::resolve_wide`]).
+    #[must_use]
+    pub const fn half(&self) -> Option<WideHalf> {
+        self.half
+    }
+
+    /// The glyph columns that land in this glyph's cell: all of them for a one-cell glyph, and
+    /// the left or right half for a [`half`](Self::half) of a double-width one.
+    #[must_use]
+    pub const fn cell_columns(&self) -> core::ops::Range<u8> {
+        let width = self.font.glyph_width;
+        match self.half {
+            None => 0..width,
+            Some(WideHalf::Left) => 0..width / 2,
+            Some(WideHalf::Right) => width / 2..width,
+        }
+    }
+
+    /// The covered pixels of this glyph that land in its cell, as
+    /// [`BitmapFont::glyph_coverage`] yields them but with `x` relative to the cell: the right
+    /// half of a double-width glyph starts back at `x == 0`.
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn cell_coverage(&self) -> impl Iterator<Item = (u8, u8, u8)> + 'c {
+        let columns = self.cell_columns();
+        let start = columns.start;
+        self.font
+            .glyph_coverage(self.index)
+            .filter(move |(x, _, _)| columns.contains(x))
+            .map(move |(x, y, coverage)| (x - start, y, coverage))
+    }
+
+    /// The runs of [`cell_coverage`](Self::cell_coverage): [`BitmapFont::glyph_spans`] clipped
+    /// to this glyph's cell, a run crossing the middle of a double-width glyph split between its
+    /// two halves.
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn cell_spans(&self) -> impl Iterator<Item = GlyphSpan> + 'c {
+        let columns = self.cell_columns();
+        self.font.glyph_spans(self.index).filter_map(move |span| {
+            let start = span.x.max(columns.start);
+            let end = (span.x + span.len).min(columns.end);
+            (start < end).then(|| GlyphSpan {
+                x: start - columns.start,
+                len: end - start,
+                ..span
+            })
+        })
+    }
 }
 
 /// The glyph source a backend draws from: a primary [`BitmapFont`] plus an ordered list of
@@ -316,10 +567,12 @@ impl ResolvedGlyph {
 /// assert_eq!(quadrant.font_index(), 1);
 /// assert!(!quadrant.is_notdef());
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FontChain<'a> {
     primary: BitmapFont,
-    fallbacks: &'a [BitmapFont],
+    fallbacks: Cow<'a, [BitmapFont]>,
+    /// Double-width fonts, consulted only by [`resolve_wide`](Self::resolve_wide).
+    wide: Cow<'a, [BitmapFont]>,
 }
 
 impl From<BitmapFont> for FontChain<'static> {
@@ -332,20 +585,93 @@ impl<'a> FontChain<'a> {
     /// Constructs a chain from a primary font and an ordered list of fallback fonts.
     #[must_use]
     pub const fn new(primary: BitmapFont, fallbacks: &'a [BitmapFont]) -> Self {
-        Self { primary, fallbacks }
+        Self {
+            primary,
+            fallbacks: Cow::Borrowed(fallbacks),
+            wide: Cow::Borrowed(&[]),
+        }
+    }
+
+    /// Appends `font` as the chain's last fallback.
+    ///
+    /// The way to chain fonts loaded at runtime, which have no `'static` slice to pass to
+    /// [`new`](Self::new):
+    ///
+    /// ```
+    /// use retroglyph_window::font::{BitmapFont, FontChain};
+    ///
+    /// # let bdf = "STARTFONT 2.1\nFONTBOUNDINGBOX 8 2 0 0\nCHARS 1\nSTARTCHAR A\n\
+    /// #     ENCODING 65\nBBX 8 2 0 0\nBITMAP\nFF\n81\nENDCHAR\nENDFONT\n";
+    /// let loaded = BitmapFont::from_bdf(bdf.as_bytes())?;
+    /// # static DATA: [u8; 256 * 2] = [0; 256 * 2];
+    /// # let cp437 = BitmapFont::new(&DATA, 8, 2, 256);
+    /// let chain = FontChain::from(loaded).with_fallback(cp437);
+    /// assert_eq!(chain.resolve('A').unwrap().font_index(), 0);
+    /// assert_eq!(chain.resolve('B').unwrap().font_index(), 1);
+    /// # Ok::<(), retroglyph_window::font::FontLoadError>(())
+    /// ```
+    #[must_use]
+    pub fn with_fallback(mut self, font: BitmapFont) -> Self {
+        self.fallbacks.to_mut().push(font);
+        self
+    }
+
+    /// Appends `font` as a double-width font: one whose glyphs are two cells wide and one tall,
+    /// for the characters a grid stores as a wide character plus its spacer (CJK ideographs,
+    /// most emoji).
+    ///
+    /// Double-width fonts answer only [`resolve_wide`](Self::resolve_wide), in the order they
+    /// were added; [`resolve`](Self::resolve) never reaches them, so a wide glyph is never
+    /// squeezed into one cell. A font like GNU Unifont, whose BDF mixes 8- and 16-pixel-wide
+    /// glyphs, loads as one 16-pixel-wide font that serves here as is: its narrow glyphs are
+    /// simply never asked for, since a grid only stores a two-column character as wide.
+    ///
+    /// ```
+    /// use retroglyph_window::font::{BitmapFont, FontChain, WideHalf};
+    ///
+    /// static NARROW: [u8; 256 * 2] = [0; 256 * 2];
+    /// static WIDE: [u8; 2 * 2] = [0xFF, 0x00, 0x00, 0xFF];
+    /// const WIDE_CHARSET: [char; 1] = ['語'];
+    ///
+    /// let chain = FontChain::from(BitmapFont::new(&NARROW, 8, 2, 256))
+    ///     .with_wide_font(BitmapFont::with_charset(&WIDE, 16, 2, 1, &WIDE_CHARSET));
+    /// assert_eq!(chain.glyph_size(), Some((8, 2)));
+    ///
+    /// let right = chain.resolve_wide('語', WideHalf::Right).unwrap();
+    /// assert_eq!(right.font_index(), 1);
+    /// // Its bottom row, shifted into the spacer cell's own columns.
+    /// assert_eq!(right.cell_coverage().count(), 8);
+    /// assert!(right.cell_coverage().all(|(x, y, _)| x < 8 && y == 1));
+    /// // The one-cell path never reaches a double-width font.
+    /// assert!(chain.resolve('語').unwrap().is_notdef());
+    /// ```
+    #[must_use]
+    pub fn with_wide_font(mut self, font: BitmapFont) -> Self {
+        self.wide.to_mut().push(font);
+        self
     }
 
-    /// The fonts in resolution order: the primary font first, then each fallback.
+    /// The fonts in resolution order: the primary font first, then each fallback, then each
+    /// double-width font.
     ///
     /// The position of a font in this iterator is its [`ResolvedGlyph::font_index`].
     pub fn fonts(&self) -> impl Iterator<Item = &BitmapFont> {
-        core::iter::once(&self.primary).chain(self.fallbacks.iter())
+        core::iter::once(&self.primary)
+            .chain(self.fallbacks.iter())
+            .chain(self.wide.iter())
     }
 
     /// The number of fonts in the chain (always at least one).
     #[must_use]
-    pub const fn font_count(&self) -> usize {
-        1 + self.fallbacks.len()
+    pub fn font_count(&self) -> usize {
+        1 + self.fallbacks.len() + self.wide.len()
+    }
+
+    /// Whether the font at `font_index` in [`fonts`](Self::fonts) is a double-width font (see
+    /// [`with_wide_font`](Self::with_wide_font)).
+    #[must_use]
+    pub fn is_wide_font(&self, font_index: usize) -> bool {
+        font_index > self.fallbacks.len() && font_index < self.font_count()
     }
 
     /// The glyph cell size (`(width, height)` in unscaled pixels) shared by every font in the
@@ -353,14 +679,21 @@ impl<'a> FontChain<'a> {
     ///
     /// A grid has one cell size, so a chain whose fonts don't agree on theirs has no single
     /// answer for how big a cell is; backends reject such a chain at build time rather than
-    /// picking one font's size and letting the others overflow or under-fill their cells.
+    /// picking one font's size and letting the others overflow or under-fill their cells. A
+    /// double-width font agrees when its glyphs are exactly two cells wide and one tall.
     #[must_use]
     pub fn glyph_size(&self) -> Option<(u8, u8)> {
         let size = (self.primary.glyph_width, self.primary.glyph_height);
-        self.fallbacks
+        let wide = (u16::from(size.0) * 2, u16::from(size.1));
+        (self
+            .fallbacks
             .iter()
             .all(|f| (f.glyph_width, f.glyph_height) == size)
-            .then_some(size)
+            && self
+                .wide
+                .iter()
+                .all(|f| (u16::from(f.glyph_width), u16::from(f.glyph_height)) == wide))
+        .then_some(size)
     }
 
     /// Resolves `ch` to a drawable glyph, trying the primary font first, then each fallback font
@@ -375,26 +708,106 @@ impl<'a> FontChain<'a> {
     /// A returned glyph is always in range for its font, so [`ResolvedGlyph::rows`] and
     /// [`BitmapFont::glyph_pixels`] cannot panic on it.
     #[must_use]
-    pub fn resolve(&self, ch: char) -> Option<ResolvedGlyph> {
+    pub fn resolve(&self, ch: char) -> Option<ResolvedGlyph<'_>> {
         self.lookup(ch, false).or_else(|| self.lookup(NOTDEF, true))
     }
 
-    /// The first font in the chain covering `ch`, tagged with `notdef`.
-    fn lookup(&self, ch: char, notdef: bool) -> Option<ResolvedGlyph> {
-        self.fonts()
+    /// Resolves one `half` of the double-width glyph for `ch` from the first
+    /// [double-width font](Self::with_wide_font) covering it, or `None` if none does.
+    ///
+    /// A backend asks this for a wide character's cell ([`WideHalf::Left`]) and its spacer
+    /// ([`WideHalf::Right`]). There is no substitute on a miss: the caller draws the character
+    /// through [`resolve`](Self::resolve) in its own cell instead, and leaves the spacer as it
+    /// is.
+    #[must_use]
+    pub fn resolve_wide(&self, ch: char, half: WideHalf) -> Option<ResolvedGlyph<'_>> {
+        let first = 1 + self.fallbacks.len();
+        self.wide
+            .iter()
+            .zip(first..)
+            .find_map(|(font, font_index)| {
+                font.glyph_index(ch).map(|index| ResolvedGlyph {
+                    font,
+                    font_index,
+                    index,
+                    notdef: false,
+                    half: Some(half),
+                })
+            })
+    }
+
+    /// The first one-cell font in the chain covering `ch`, tagged with `notdef`.
+    fn lookup(&self, ch: char, notdef: bool) -> Option<ResolvedGlyph<'_>> {
+        core::iter::once(&self.primary)
+            .chain(self.fallbacks.iter())
             .enumerate()
             .find_map(|(font_index, font)| {
                 font.glyph_index(ch).map(|index| (font_index, font, index))
             })
             .map(|(font_index, font, index)| ResolvedGlyph {
-                font: *font,
+                font,
                 font_index,
                 index,
                 notdef,
+                half: None,
             })
     }
 }
 
+// ── Scanline cache ──────────────────────────────────────────────────────────
+
+/// Glyphs of one [`FontChain`] expanded into [`GlyphSpan`]s on first use, so a rasterizer that
+/// redraws every frame pays for the bit extraction across a glyph's rows once.
+///
+/// Entries are keyed by a [`ResolvedGlyph`]'s font position, glyph index, and
+/// [half](ResolvedGlyph::half), so a cache is only
+/// meaningful for the one chain it was filled from: pair it with a chain for the chain's whole
+/// life, and start a fresh one if the chain changes.
+///
+/// ```
+/// use retroglyph_window::font::{BitmapFont, FontChain, GlyphSpan, ScanlineCache};
+///
+/// // 12x1 glyphs, blank except CP437 'A': pixels 0-3 and 8-11 set.
+/// static DATA: [u8; 66 * 2] = {
+///     let mut data = [0; 66 * 2];
+///     data[65 * 2] = 0b0000_1111;
+///     data[65 * 2 + 1] = 0b0000_1111;
+///     data
+/// };
+/// let chain = FontChain::from(BitmapFont::new(&DATA, 12, 1, 66));
+/// let mut cache = ScanlineCache::new();
+///
+/// let a = chain.resolve('A').unwrap();
+/// assert_eq!(
+///     cache.spans(&a),
+///     [
+///         GlyphSpan { x: 0, y: 0, len: 4, coverage: 0xFF },
+///         GlyphSpan { x: 8, y: 0, len: 4, coverage: 0xFF },
+///     ],
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScanlineCache {
+    glyphs: std::collections::HashMap<(usize, u16, Option<WideHalf>), Box<[GlyphSpan]>>,
+}
+
+impl ScanlineCache {
+    /// An empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The covered runs of `glyph` within its cell, expanded through
+    /// [`ResolvedGlyph::cell_spans`] the first time this glyph is asked for and served from the
+    /// cache after that.
+    pub fn spans(&mut self, glyph: &ResolvedGlyph<'_>) -> &[GlyphSpan] {
+        self.glyphs
+            .entry((glyph.font_index(), glyph.index(), glyph.half()))
+            .or_insert_with(|| glyph.cell_spans().collect())
+    }
+}
+
 // ── Default embedded font ──────────────────────────────────────────────────
 
 /// The Unscii 16 font, embedded when the `default-font` feature is enabled.
@@ -1415,7 +1828,7 @@ pub mod legacy_computing {
                 // The primary font's own CP437 coverage answers directly for the solid block;
                 // this chain never needs to fall back to `notdef` for it.
                 let full_block = chain.resolve('█').expect("CP437 coverage");
-                assert_eq!(full_block.font(), PRIMARY);
+                assert_eq!(full_block.font(), &PRIMARY);
                 assert!(!full_block.is_notdef());
             }
         }
@@ -1629,7 +2042,7 @@ const fn try_unicode_to_cp437(ch: char) -> Option<u8> {
 
 #[cfg(test)]
 mod tests {
-    use super::{BitmapFont, FontChain, try_unicode_to_cp437};
+    use super::{BitmapFont, FontChain, GlyphSpan, ScanlineCache, WideHalf, try_unicode_to_cp437};
 
     /// The four codepoints patched into `unscii16`'s `DATA` (see that module's doc comment)
     /// must actually be reachable through the char-to-glyph path, not just present at their
@@ -1693,9 +2106,10 @@ mod tests {
     fn chain_resolves_char_present_only_in_fallback_font() {
         // 'Ç' maps to CP437 index 0x80, which is out of range for `PRIMARY`
         // (glyph_count == 128) but present in `FALLBACK_FONT` (glyph_count == 256).
-        let chain = FontChain::new(PRIMARY, &[FALLBACK_FONT]);
+        let fallbacks = [FALLBACK_FONT];
+        let chain = FontChain::new(PRIMARY, &fallbacks);
         let resolved = chain.resolve('Ç').expect("covered by the fallback font");
-        assert_eq!(resolved.font(), FALLBACK_FONT);
+        assert_eq!(resolved.font(), &FALLBACK_FONT);
         assert_eq!(resolved.font_index(), 1);
         assert_eq!(resolved.index(), 0x80);
         assert!(!resolved.is_notdef());
@@ -1707,9 +2121,10 @@ mod tests {
         // chain miss and resolution must substitute the solid block. `PRIMARY` stops at glyph 128
         // and so doesn't have one, which is exactly the case a fixed 0xDB fallback index used to
         // resolve to an out-of-range glyph for.
-        let chain = FontChain::new(PRIMARY, &[FALLBACK_FONT]);
+        let fallbacks = [FALLBACK_FONT];
+        let chain = FontChain::new(PRIMARY, &fallbacks);
         let resolved = chain.resolve('あ').expect("solid block substitute");
-        assert_eq!(resolved.font(), FALLBACK_FONT);
+        assert_eq!(resolved.font(), &FALLBACK_FONT);
         assert_eq!(resolved.index(), 0xDB);
         assert!(resolved.is_notdef());
     }
@@ -1734,7 +2149,7 @@ mod tests {
         assert_eq!(chain.font_count(), 1);
         for ch in ['A', ' ', '█', '│', 'Ç', '☺'] {
             let resolved = chain.resolve(ch).expect("CP437 coverage");
-            assert_eq!(resolved.font(), FALLBACK_FONT);
+            assert_eq!(resolved.font(), &FALLBACK_FONT);
             assert_eq!(resolved.font_index(), 0);
             assert_eq!(resolved.index(), FALLBACK_FONT.glyph_index(ch).unwrap());
         }
@@ -1783,13 +2198,168 @@ mod tests {
     }
 
     #[test]
-    fn glyph_pixels_does_not_overflow_the_shift_for_width_above_8() {
-        // retroglyph#729: `row >> (width - 1 - x)` used to shift past the byte's width once
-        // `glyph_width` exceeded 8, which this 1-bit-per-row format never actually supports.
-        static DATA: [u8; 1] = [0b1111_1111];
-        let font = BitmapFont::new(&DATA, 12, 1, 1);
+    fn glyph_pixels_reads_multi_byte_rows_for_width_above_8() {
+        // A 12x2 glyph (#164), two bytes per row. Row 0: the leftmost and rightmost pixels, which
+        // sit in different bytes; row 1: the pixel either side of the byte boundary.
+        static DATA: [u8; 4] = [0b0000_1000, 0b0000_0001, 0b0000_0001, 0b1000_0000];
+        let font = BitmapFont::new(&DATA, 12, 2, 1);
+        assert_eq!(font.bytes_per_row(), 2);
+        assert_eq!(font.rows(0), DATA);
         let pixels: Vec<(u8, u8)> = font.glyph_pixels(0).collect();
-        assert_eq!(pixels, (0..8).map(|x| (x, 0)).collect::<Vec<_>>());
+        assert_eq!(pixels, [(0, 0), (11, 0), (3, 1), (4, 1)]);
+    }
+
+    #[test]
+    fn glyph_pixels_indexes_glyphs_by_their_multi_byte_size() {
+        // Two 9x1 glyphs: glyph 1 starts two bytes in, not one.
+        static DATA: [u8; 4] = [0b0000_0001, 0b0000_0000, 0b0000_0000, 0b0000_0001];
+        let font = BitmapFont::new(&DATA, 9, 1, 2);
+        assert_eq!(font.glyph_pixels(0).collect::<Vec<_>>(), [(0, 0)]);
+        assert_eq!(font.glyph_pixels(1).collect::<Vec<_>>(), [(8, 0)]);
+    }
+
+    #[test]
+    fn glyph_spans_merge_adjacent_pixels_into_runs() {
+        // A 10x2 glyph: row 0 is two runs split by a gap, row 1 is one run across the byte
+        // boundary ending on the last column.
+        static DATA: [u8; 4] = [0b0000_0011, 0b0011_1000, 0b0000_0000, 0b0011_1111];
+        let font = BitmapFont::new(&DATA, 10, 2, 1);
+        let spans: Vec<GlyphSpan> = font.glyph_spans(0).collect();
+        assert_eq!(
+            spans,
+            [
+                GlyphSpan {
+                    x: 0,
+                    y: 0,
+                    len: 2,
+                    coverage: 0xFF
+                },
+                GlyphSpan {
+                    x: 4,
+                    y: 0,
+                    len: 3,
+                    coverage: 0xFF
+                },
+                GlyphSpan {
+                    x: 4,
+                    y: 1,
+                    len: 6,
+                    coverage: 0xFF
+                },
+            ]
+        );
+        let from_spans: Vec<(u8, u8)> = spans
+            .iter()
+            .flat_map(|s| (s.x..s.x + s.len).map(move |x| (x, s.y)))
+            .collect();
+        assert_eq!(from_spans, font.glyph_pixels(0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn scanline_cache_keys_glyphs_by_their_font_in_the_chain() {
+        // The same glyph index in two fonts of one chain expands to each font's own runs.
+        static PRIMARY_DATA: [u8; 2] = [0b0000_0001, 0b1000_0000];
+        static FALLBACK_DATA: [u8; 2] = [0b0000_0000, 0b0000_0001];
+        const PRIMARY_CHARSET: [char; 1] = ['a'];
+        const FALLBACK_CHARSET: [char; 1] = ['b'];
+        let fallbacks = [BitmapFont::with_charset(
+            &FALLBACK_DATA,
+            9,
+            1,
+            1,
+            &FALLBACK_CHARSET,
+        )];
+        let chain = FontChain::new(
+            BitmapFont::with_charset(&PRIMARY_DATA, 9, 1, 1, &PRIMARY_CHARSET),
+            &fallbacks,
+        );
+        let mut cache = ScanlineCache::new();
+
+        let a = chain.resolve('a').unwrap();
+        let b = chain.resolve('b').unwrap();
+        assert_eq!((a.index(), b.index()), (0, 0));
+        assert_eq!(
+            cache.spans(&a),
+            [GlyphSpan {
+                x: 0,
+                y: 0,
+                len: 2,
+                coverage: 0xFF
+            }]
+        );
+        assert_eq!(
+            cache.spans(&b),
+            [GlyphSpan {
+                x: 8,
+                y: 0,
+                len: 1,
+                coverage: 0xFF
+            }]
+        );
+        assert_eq!(
+            cache.spans(&a),
+            [GlyphSpan {
+                x: 0,
+                y: 0,
+                len: 2,
+                coverage: 0xFF
+            }]
+        );
+    }
+
+    /// A double-width glyph splits at the cell boundary: each half is shifted to its own cell's
+    /// origin, and a run crossing the boundary is cut in two.
+    #[test]
+    fn wide_glyphs_split_into_cell_halves() {
+        static NARROW_DATA: [u8; 1] = [0];
+        // One row: column 0, which only the left half draws, and columns 6..10, two either side
+        // of the boundary at 8.
+        static WIDE_DATA: [u8; 2] = [0b1000_0011, 0b1100_0000];
+        const WIDE_CHARSET: [char; 1] = ['語'];
+        let chain = FontChain::from(BitmapFont::new(&NARROW_DATA, 8, 1, 1)).with_wide_font(
+            BitmapFont::with_charset(&WIDE_DATA, 16, 1, 1, &WIDE_CHARSET),
+        );
+        assert_eq!(chain.font_count(), 2);
+        assert!(chain.is_wide_font(1));
+        assert!(!chain.is_wide_font(0));
+
+        let left = chain.resolve_wide('語', WideHalf::Left).unwrap();
+        let right = chain.resolve_wide('語', WideHalf::Right).unwrap();
+        assert_eq!(
+            (left.half(), right.half()),
+            (Some(WideHalf::Left), Some(WideHalf::Right))
+        );
+        assert_eq!((left.cell_columns(), right.cell_columns()), (0..8, 8..16));
+        assert_eq!(
+            left.cell_coverage().collect::<Vec<_>>(),
+            [(0, 0, 0xFF), (6, 0, 0xFF), (7, 0, 0xFF)]
+        );
+        assert_eq!(
+            right.cell_coverage().collect::<Vec<_>>(),
+            [(0, 0, 0xFF), (1, 0, 0xFF)]
+        );
+
+        let mut cache = ScanlineCache::new();
+        let run = |x, len| GlyphSpan {
+            x,
+            y: 0,
+            len,
+            coverage: 0xFF,
+        };
+        assert_eq!(cache.spans(&left), [run(0, 1), run(6, 2)]);
+        assert_eq!(cache.spans(&right), [run(0, 2)]);
+
+        assert_eq!(chain.resolve_wide('A', WideHalf::Left), None);
+    }
+
+    #[test]
+    fn glyph_size_requires_wide_fonts_to_be_two_cells_wide() {
+        static DATA: [u8; 2] = [0; 2];
+        let narrow = BitmapFont::new(&DATA, 8, 1, 1);
+        let chain = FontChain::from(narrow.clone()).with_wide_font(narrow.clone());
+        assert_eq!(chain.glyph_size(), None);
+        let chain = FontChain::from(narrow).with_wide_font(BitmapFont::new(&DATA, 16, 1, 1));
+        assert_eq!(chain.glyph_size(), Some((8, 1)));
     }
 
     /// Reproduces retroglyph#507: a fallback font built with [`BitmapFont::with_charset`] can
@@ -1805,14 +2375,15 @@ mod tests {
             BitmapFont::with_charset(&BRAILLE_DATA, 8, 16, 1, &BRAILLE_CHARSET);
 
         let primary = FALLBACK_FONT; // full CP437 coverage, glyph_count == 256
-        let chain = FontChain::new(primary, &[BRAILLE_FONT]);
+        let fallbacks = [BRAILLE_FONT];
+        let chain = FontChain::new(primary.clone(), &fallbacks);
 
         let braille = chain.resolve('\u{2800}').expect("charset coverage");
-        assert_eq!(braille.font(), BRAILLE_FONT);
+        assert_eq!(braille.font(), &BRAILLE_FONT);
         assert_eq!(braille.index(), 0);
 
         let full_block = chain.resolve('\u{2588}').expect("CP437 coverage"); // '█', index 0xDB
-        assert_eq!(full_block.font(), primary);
+        assert_eq!(full_block.font(), &primary);
         assert_eq!(full_block.index(), 0xDB);
 
         assert_ne!(braille.index(), full_block.index());
@@ -0,0 +1,270 @@
+//! TrueType/OpenType outline fonts rasterized into an antialiased [`BitmapFont`] (the `truetype`
+//! feature).
+//!
+//! Every glyph the font maps a `char` to is drawn once, with [`ab_glyph`], into a fixed cell,
+//! keeping its partial edge coverage as one byte per pixel (see [`BitmapFont::rows`]). The result
+//! is an ordinary owned font: it sits in a [`FontChain`](super::FontChain) next to bitmap fonts,
+//! and the atlas builder and the software rasterizer draw it through the same coverage paths they
+//! draw those with. Rasterizing at the size a window actually needs is what keeps text crisp at a
+//! fractional DPI scale, where an integer-scaled bitmap font cannot land on the pixel grid.
+
+use super::BitmapFont;
+use super::load::{FontLoadError, glyph_count, glyph_size};
+use ab_glyph::{Font, FontRef, GlyphId, PxScale, ScaleFont, point};
+use std::collections::HashMap;
+
+/// Where an outline font's glyphs land inside its cells.
+struct Layout {
+    scale: PxScale,
+    width: u8,
+    height: u8,
+    /// Pen position of every glyph: its left edge and baseline, in pixels from the cell's
+    /// top-left corner.
+    origin: (f32, f32),
+}
+
+impl BitmapFont {
+    /// Rasterizes a TrueType or OpenType font, antialiased, at `px` pixels from ascent to descent.
+    ///
+    /// The cell metrics come from the font itself. The cell is as wide as the advance of `'0'`
+    /// (every glyph's advance, in a monospace font) and as tall as the line height including the
+    /// line gap, both rounded up to whole pixels; glyphs are drawn from the cell's left edge with
+    /// the baseline half the line gap below the ascent, and anything outside the cell is clipped.
+    ///
+    /// To chain the font with a bitmap font of a given cell size, rasterize it with
+    /// [`from_truetype_cell`](Self::from_truetype_cell) instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontLoadError::InvalidTrueType`] if `bytes` is not a font `ab_glyph` can parse,
+    /// [`FontLoadError::UnsupportedGlyphSize`] if the derived cell is empty or larger than 255
+    /// pixels either way, [`FontLoadError::NoGlyphs`] if the font maps no `char` to a glyph, and
+    /// [`FontLoadError::TooManyGlyphs`] if it has more glyphs than a `u16` can index.
+    pub fn from_truetype(bytes: &[u8], px: f32) -> Result<Self, FontLoadError> {
+        let font = FontRef::try_from_slice(bytes).map_err(|_| FontLoadError::InvalidTrueType)?;
+        let scale = PxScale::from(px);
+        let scaled = font.as_scaled(scale);
+        let advance = scaled.h_advance(font.glyph_id('0'));
+        let line = scaled.height() + scaled.line_gap();
+        let (width, height) = glyph_size(pixels(advance), pixels(line))?;
+        let layout = Layout {
+            scale,
+            width,
+            height,
+            origin: (0.0, (scaled.line_gap() / 2.0 + scaled.ascent()).round()),
+        };
+        rasterize(&font, &layout)
+    }
+
+    /// Rasterizes a TrueType or OpenType font, antialiased, into fixed `cell_width` x
+    /// `cell_height` cells.
+    ///
+    /// The font is scaled to the largest size at which both its line height and the advance of
+    /// `'0'` fit the cell, and centered in whatever space is left in the other direction. Every
+    /// font in a [`FontChain`](super::FontChain) draws into the same cell, so this is how an
+    /// outline font fills the gaps of a bitmap font (or the other way around): rasterize it at the
+    /// bitmap font's glyph size.
+    ///
+    /// # Errors
+    ///
+    /// As for [`from_truetype`](Self::from_truetype), with
+    /// [`FontLoadError::UnsupportedGlyphSize`] for a zero-sized cell.
+    pub fn from_truetype_cell(
+        bytes: &[u8],
+        cell_width: u8,
+        cell_height: u8,
+    ) -> Result<Self, FontLoadError> {
+        let (width, height) = glyph_size(u32::from(cell_width), u32::from(cell_height))?;
+        let font = FontRef::try_from_slice(bytes).map_err(|_| FontLoadError::InvalidTrueType)?;
+        // `PxScale` is in units of the font's ascent-to-descent height, so fitting the line (which
+        // adds the line gap) and the advance are both a ratio against that height.
+        let em = font.height_unscaled();
+        let line = em + font.line_gap_unscaled();
+        let advance = font.h_advance_unscaled(font.glyph_id('0'));
+        let px = (f32::from(height) * em / line).min(if advance > 0.0 {
+            f32::from(width) * em / advance
+        } else {
+            f32::INFINITY
+        });
+
+        let scale = PxScale::from(px);
+        let scaled = font.as_scaled(scale);
+        let spare_x = f32::from(width) - scaled.h_advance(font.glyph_id('0'));
+        let spare_y = f32::from(height) - (scaled.height() + scaled.line_gap());
+        let layout = Layout {
+            scale,
+            width,
+            height,
+            origin: (
+                (spare_x / 2.0).round(),
+                (spare_y / 2.0 + scaled.line_gap() / 2.0 + scaled.ascent()).round(),
+            ),
+        };
+        rasterize(&font, &layout)
+    }
+}
+
+/// Rounds a metric up to whole pixels; a negative or non-finite one becomes `0`, which
+/// [`glyph_size`] rejects.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn pixels(metric: f32) -> u32 {
+    metric.ceil() as u32
+}
+
+/// Draws every mapped glyph of `font` into its own cell, one coverage byte per pixel.
+///
+/// Glyphs are indexed in order of the lowest `char` mapped to them, so the same font file always
+/// produces the same glyph table; `char`s that share an outline share its index.
+fn rasterize(font: &FontRef<'_>, layout: &Layout) -> Result<BitmapFont, FontLoadError> {
+    let mut chars: Vec<(char, GlyphId)> = font
+        .codepoint_ids()
+        .map(|(id, ch)| (ch, id))
+        .filter(|&(_, id)| id.0 != 0)
+        .collect();
+    chars.sort_unstable_by_key(|&(ch, _)| ch);
+
+    let mut indices = HashMap::new();
+    let mut glyphs = Vec::new();
+    let mut map = Vec::with_capacity(chars.len());
+    for (ch, id) in chars {
+        let index = *indices.entry(id).or_insert_with(|| {
+            glyphs.push(id);
+            glyphs.len() - 1
+        });
+        map.push((ch, index));
+    }
+    let count = glyph_count(glyphs.len())?;
+    let map = map
+        .into_iter()
+        .map(|(ch, index)| (ch, u16::try_from(index).unwrap_or(u16::MAX)))
+        .collect();
+
+    let (width, height) = (usize::from(layout.width), usize::from(layout.height));
+    let mut data = vec![0u8; glyphs.len() * width * height];
+    for (id, cell) in glyphs
+        .into_iter()
+        .zip(data.chunks_exact_mut(width * height))
+    {
+        let glyph =
+            id.with_scale_and_position(layout.scale, point(layout.origin.0, layout.origin.1));
+        // Whitespace has no outline: its cell stays empty.
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        #[allow(clippy::cast_possible_truncation)]
+        let (left, top) = (bounds.min.x as i64, bounds.min.y as i64);
+        outline.draw(|x, y, coverage| {
+            let (Ok(x), Ok(y)) = (
+                usize::try_from(left + i64::from(x)),
+                usize::try_from(top + i64::from(y)),
+            ) else {
+                return;
+            };
+            if x < width && y < height {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let coverage = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                let pixel = &mut cell[y * width + x];
+                *pixel = (*pixel).max(coverage);
+            }
+        });
+    }
+
+    let mut font = BitmapFont::owned(data, layout.width, layout.height, count, Some(map));
+    font.antialiased = true;
+    Ok(font)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FontLoadError;
+    use crate::font::{BitmapFont, FontChain};
+    use crate::testing::truetype_rect_font;
+
+    /// `'█'` fills the whole em box; `'A'` is a 2.5 x 4 unit block on the baseline, so at 10px
+    /// (5x10 cells, baseline 8 rows down) its third column is half covered.
+    fn font() -> Vec<u8> {
+        truetype_rect_font(&[('█', [0, -200, 500, 800]), ('A', [0, 0, 250, 400])])
+    }
+
+    #[test]
+    fn cell_metrics_come_from_advance_and_line_height() {
+        let font = BitmapFont::from_truetype(&font(), 10.0).unwrap();
+        assert_eq!((font.glyph_width(), font.glyph_height()), (5, 10));
+        assert!(font.is_antialiased());
+        assert_eq!(font.bytes_per_row(), 5);
+    }
+
+    #[test]
+    fn edges_keep_partial_coverage() {
+        let font = BitmapFont::from_truetype(&font(), 10.0).unwrap();
+        let a = font.glyph_index('A').unwrap();
+        let covered: Vec<_> = font.glyph_coverage(a).collect();
+        for y in 4..8 {
+            assert!(covered.contains(&(0, y, 0xFF)));
+            assert!(covered.contains(&(1, y, 0xFF)));
+            let edge = covered
+                .iter()
+                .find(|&&(x, cy, _)| (x, cy) == (2, y))
+                .unwrap()
+                .2;
+            assert!((0x70..0x90).contains(&edge), "edge coverage {edge:#x}");
+        }
+        assert!(
+            covered
+                .iter()
+                .all(|&(x, y, _)| x < 3 && (4..8).contains(&y))
+        );
+
+        let block = font.glyph_index('█').unwrap();
+        assert!(font.rows(block).iter().all(|&c| c == 0xFF));
+    }
+
+    #[test]
+    fn fixed_cells_center_the_font() {
+        // Line height 10 fits 12 rows with 2 to spare, one above and one below; the advance fits.
+        let font = BitmapFont::from_truetype_cell(&font(), 5, 12).unwrap();
+        assert_eq!((font.glyph_width(), font.glyph_height()), (5, 12));
+        let block = font.glyph_index('█').unwrap();
+        let rows: Vec<_> = font.rows(block).chunks(5).map(|row| row[0]).collect();
+        assert_eq!(
+            rows,
+            [
+                0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn chains_with_bitmap_fonts() {
+        static BITMAP: [u8; 256 * 10] = [0xFF; 256 * 10];
+        let fallbacks = [BitmapFont::new(&BITMAP, 5, 10, 256)];
+        let chain = FontChain::new(
+            BitmapFont::from_truetype_cell(&font(), 5, 10).unwrap(),
+            &fallbacks,
+        );
+        assert_eq!(chain.resolve('A').unwrap().font_index(), 0);
+        // CP437 'é' is only in the bitmap fallback.
+        assert_eq!(chain.resolve('é').unwrap().font_index(), 1);
+    }
+
+    #[test]
+    fn errors_are_typed() {
+        assert_eq!(
+            BitmapFont::from_truetype(b"not a font", 10.0).unwrap_err(),
+            FontLoadError::InvalidTrueType
+        );
+        assert!(matches!(
+            BitmapFont::from_truetype(&font(), 0.0).unwrap_err(),
+            FontLoadError::UnsupportedGlyphSize { .. }
+        ));
+        assert!(matches!(
+            BitmapFont::from_truetype(&font(), 400.0).unwrap_err(),
+            FontLoadError::UnsupportedGlyphSize { .. }
+        ));
+        assert_eq!(
+            BitmapFont::from_truetype(&truetype_rect_font(&[]), 10.0).unwrap_err(),
+            FontLoadError::NoGlyphs
+        );
+    }
+}
@@ -18,19 +18,47 @@ pub struct CellGeometry {
     pub glyph_h: u16,
     /// Integer pixel scale: each glyph pixel becomes a `scale x scale` block of physical pixels.
     pub scale: u16,
+    /// See [`viewport`](Self::viewport).
+    viewport: Option<Viewport>,
 }
 
 impl CellGeometry {
-    /// A geometry for `glyph_w x glyph_h` glyphs drawn at integer `scale`.
+    /// A geometry for `glyph_w x glyph_h` glyphs drawn at integer `scale`, shown unscaled at the
+    /// window's origin.
     #[must_use]
     pub const fn new(glyph_w: u16, glyph_h: u16, scale: u16) -> Self {
         Self {
             glyph_w,
             glyph_h,
             scale,
+            viewport: None,
         }
     }
 
+    /// This geometry with the frame shown through `viewport`, so
+    /// [`pixel_to_cell`](Self::pixel_to_cell) maps window pixels back through it.
+    #[must_use]
+    pub const fn with_viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Where the frame is shown in the window, when a
+    /// [`ScalingMode`](crate::scaling::ScalingMode) scales or offsets it. `None` means window
+    /// pixels are frame pixels, as under [`ScalingMode::Reflow`](crate::scaling::ScalingMode::Reflow).
+    #[must_use]
+    pub const fn viewport(&self) -> Option<Viewport> {
+        self.viewport
+    }
+
+    /// Maps a window pixel position to the frame pixel position shown there, through the
+    /// [`viewport`](Self::viewport) if there is one.
+    #[must_use]
+    pub fn window_to_frame(&self, x: f64, y: f64) -> (f64, f64) {
+        self.viewport
+            .map_or((x, y), |viewport| viewport.window_to_frame(x, y))
+    }
+
     /// Cell size in physical pixels: `(glyph_w * scale, glyph_h * scale)`.
     ///
     /// The single embodiment of `Presenter::cell_size`'s "physical pixels, glyph x scale" contract.
@@ -51,19 +79,88 @@ impl CellGeometry {
         (cols as u32 * cell_w, rows as u32 * cell_h)
     }
 
-    /// Converts physical pixel coordinates to a grid cell [`Pos`], using this geometry's
-    /// [`cell_size`](Self::cell_size).
+    /// Converts physical window pixel coordinates to a grid cell [`Pos`], using this geometry's
+    /// [`cell_size`](Self::cell_size) after undoing any [`viewport`](Self::viewport) scaling and
+    /// offset.
     ///
-    /// Clamps to `u16::MAX` so out-of-bounds cursor positions (negative or extremely large)
-    /// don't panic: the caller is responsible for bounds-checking against the terminal size.
+    /// Returns `None` over a viewport's letterbox bars, where no cell is shown. Otherwise clamps
+    /// to `u16::MAX` so out-of-bounds cursor positions (negative or extremely large) don't
+    /// panic: the caller is responsible for bounds-checking against the terminal size.
     #[must_use]
-    pub fn pixel_to_cell(&self, x: f64, y: f64) -> Pos {
+    pub fn pixel_to_cell(&self, x: f64, y: f64) -> Option<Pos> {
+        let (x, y) = self.window_to_frame(x, y);
+        if let Some(viewport) = self.viewport
+            && !viewport.shows(x, y)
+        {
+            return None;
+        }
         let (cell_w, cell_h) = self.cell_size();
-        Pos {
+        Some(Pos {
             x: pixel_to_cell_axis(x, cell_w),
             y: pixel_to_cell_axis(y, cell_h),
+        })
+    }
+}
+
+/// Where a presenter's frame is shown in its window: a rectangle in window pixels the whole
+/// frame is scaled into.
+///
+/// Computed by [`ScalingMode::viewport`](crate::scaling::ScalingMode::viewport). The rectangle
+/// can extend past the window's right and bottom edges (an integer-scaled frame bigger than the
+/// window is cropped there), but never past its top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// The frame's own size in pixels: the grid's [`surface_size`](CellGeometry::surface_size).
+    pub frame: (u32, u32),
+    /// The top-left corner of the shown frame, in window pixels.
+    pub origin: (u32, u32),
+    /// The shown frame's size in window pixels.
+    pub size: (u32, u32),
+}
+
+impl Viewport {
+    /// A `frame`-sized frame shown 1:1 at the window's origin.
+    #[must_use]
+    pub const fn unscaled(frame: (u32, u32)) -> Self {
+        Self {
+            frame,
+            origin: (0, 0),
+            size: frame,
         }
     }
+
+    /// Whether window pixels are frame pixels: the frame is at the origin, at its own size.
+    #[must_use]
+    pub const fn is_unscaled(&self) -> bool {
+        self.origin.0 == 0
+            && self.origin.1 == 0
+            && self.size.0 == self.frame.0
+            && self.size.1 == self.frame.1
+    }
+
+    /// Maps a window pixel position to the frame pixel position shown there. Positions over a
+    /// letterbox bar map outside `[0, frame)`.
+    #[must_use]
+    pub fn window_to_frame(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            window_to_frame_axis(x, self.origin.0, self.size.0, self.frame.0),
+            window_to_frame_axis(y, self.origin.1, self.size.1, self.frame.1),
+        )
+    }
+
+    /// Whether the frame pixel position `(x, y)` is inside the frame, rather than past its edge
+    /// (over a letterbox bar, once mapped back to the window).
+    fn shows(&self, x: f64, y: f64) -> bool {
+        (0.0..f64::from(self.frame.0)).contains(&x) && (0.0..f64::from(self.frame.1)).contains(&y)
+    }
+}
+
+/// [`Viewport::window_to_frame`] along one axis. A zero-size viewport maps everything to `0`.
+fn window_to_frame_axis(px: f64, origin: u32, size: u32, frame: u32) -> f64 {
+    if size == 0 {
+        return 0.0;
+    }
+    (px - f64::from(origin)) * f64::from(frame) / f64::from(size)
 }
 
 /// Divides one clamped, non-negative pixel axis by a cell dimension, saturating to `u16::MAX`.
@@ -78,7 +175,7 @@ fn pixel_to_cell_axis(px: f64, cell: u32) -> u16 {
 
 #[cfg(test)]
 mod tests {
-    use super::CellGeometry;
+    use super::{CellGeometry, Viewport};
     use retroglyph_core::grid::Pos;
 
     #[test]
@@ -109,34 +206,40 @@ mod tests {
     fn pixel_to_cell_basic() {
         // 8×16 cells: pixel (20, 48) → col 2, row 3
         let geometry = CellGeometry::new(8, 16, 1);
-        assert_eq!(geometry.pixel_to_cell(20.0, 48.0), Pos { x: 2, y: 3 });
+        assert_eq!(geometry.pixel_to_cell(20.0, 48.0), Some(Pos { x: 2, y: 3 }));
     }
 
     #[test]
     fn pixel_to_cell_origin() {
         let geometry = CellGeometry::new(8, 16, 1);
-        assert_eq!(geometry.pixel_to_cell(0.0, 0.0), Pos { x: 0, y: 0 });
+        assert_eq!(geometry.pixel_to_cell(0.0, 0.0), Some(Pos { x: 0, y: 0 }));
     }
 
     #[test]
     fn pixel_to_cell_negative_coords_clamp_to_zero() {
         // Cursor briefly outside the window can produce negative physical coords.
         let geometry = CellGeometry::new(8, 16, 1);
-        assert_eq!(geometry.pixel_to_cell(-5.0, -10.0), Pos { x: 0, y: 0 });
+        assert_eq!(
+            geometry.pixel_to_cell(-5.0, -10.0),
+            Some(Pos { x: 0, y: 0 })
+        );
     }
 
     #[test]
     fn pixel_to_cell_zero_cell_size_returns_origin() {
         // Degenerate case: glyph size 0 (backend not yet initialised with a valid font).
         let geometry = CellGeometry::new(0, 0, 1);
-        assert_eq!(geometry.pixel_to_cell(100.0, 200.0), Pos { x: 0, y: 0 });
+        assert_eq!(
+            geometry.pixel_to_cell(100.0, 200.0),
+            Some(Pos { x: 0, y: 0 })
+        );
     }
 
     #[test]
     fn pixel_to_cell_accounts_for_scale() {
         // 8×16 glyphs at scale 2 → 16×32 cells: pixel (20, 48) → col 1, row 1.
         let geometry = CellGeometry::new(8, 16, 2);
-        assert_eq!(geometry.pixel_to_cell(20.0, 48.0), Pos { x: 1, y: 1 });
+        assert_eq!(geometry.pixel_to_cell(20.0, 48.0), Some(Pos { x: 1, y: 1 }));
     }
 
     #[test]
@@ -145,10 +248,61 @@ mod tests {
         let geometry = CellGeometry::new(1, 1, 1);
         assert_eq!(
             geometry.pixel_to_cell(f64::from(u32::MAX), f64::from(u32::MAX)),
-            Pos {
+            Some(Pos {
                 x: u16::MAX,
                 y: u16::MAX
-            }
+            })
+        );
+    }
+
+    // ── viewport ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn pixel_to_cell_undoes_a_letterboxed_viewport() {
+        // A 160x64 frame (20x4 cells of 8x16) doubled and centered in a 400x200 window: 40-pixel
+        // bars left and right, 36 above and below.
+        let geometry = CellGeometry::new(8, 16, 1).with_viewport(Viewport {
+            frame: (160, 64),
+            origin: (40, 36),
+            size: (320, 128),
+        });
+        assert_eq!(geometry.pixel_to_cell(40.0, 36.0), Some(Pos { x: 0, y: 0 }));
+        // Window (359, 163) is frame (159.5, 63.5): the last cell.
+        assert_eq!(
+            geometry.pixel_to_cell(359.0, 163.0),
+            Some(Pos { x: 19, y: 3 })
+        );
+        assert_eq!(geometry.pixel_to_cell(72.0, 68.0), Some(Pos { x: 2, y: 1 }));
+        // Over the bars: no cell.
+        assert_eq!(geometry.pixel_to_cell(10.0, 10.0), None);
+        assert_eq!(geometry.pixel_to_cell(39.0, 100.0), None);
+        assert_eq!(geometry.pixel_to_cell(100.0, 35.0), None);
+        assert_eq!(geometry.pixel_to_cell(360.0, 100.0), None);
+        assert_eq!(geometry.pixel_to_cell(100.0, 164.0), None);
+    }
+
+    #[test]
+    fn pixel_to_cell_undoes_a_stretched_viewport() {
+        // A 160x64 frame stretched to 480x96: 3x across, 1.5x down.
+        let geometry = CellGeometry::new(8, 16, 1).with_viewport(Viewport {
+            frame: (160, 64),
+            origin: (0, 0),
+            size: (480, 96),
+        });
+        assert_eq!(geometry.pixel_to_cell(47.0, 23.0), Some(Pos { x: 1, y: 0 }));
+        assert_eq!(geometry.pixel_to_cell(48.0, 24.0), Some(Pos { x: 2, y: 1 }));
+    }
+
+    #[test]
+    fn an_unscaled_viewport_maps_pixels_unchanged() {
+        let viewport = Viewport::unscaled((160, 64));
+        assert!(viewport.is_unscaled());
+        assert_eq!(viewport.window_to_frame(12.5, 7.0), (12.5, 7.0));
+        assert_eq!(
+            CellGeometry::new(8, 16, 1)
+                .with_viewport(viewport)
+                .pixel_to_cell(20.0, 48.0),
+            Some(Pos { x: 2, y: 3 })
         );
     }
 }
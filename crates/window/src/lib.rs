@@ -76,6 +76,16 @@
 //! font, so a consumer that only wants CP437 text shouldn't pay for it. Computed at compile time by
 //! a `const fn`, so this adds no font asset and no new dependency.
 //!
+//! ### `png-fonts`
+//!
+//! ⚪ Optional.
+//!
+//! Runtime loading of grid-of-glyphs PNG font sheets (`BitmapFont::from_png_sheet`).
+//!
+//! The BDF and PSF loaders are plain byte parsers and always available; only the PNG sheet loader
+//! needs an image decoder, so it alone sits behind this flag. Shares the `image` dependency with
+//! `tilesets`.
+//!
 //! ### `testing`
 //!
 //! ⚪ Optional.
@@ -83,7 +93,8 @@
 //! Testing helpers for asserting glyph coverage (`testing::assert_glyphs_covered`,
 //! `testing::uncovered_glyphs`), so a consumer can check a `FontChain` actually draws the
 //! characters it cares about rather than silently falling back to the substituted solid block
-//! (retroglyph#1292).
+//! (retroglyph#1292). With `truetype` on too, `testing::truetype_rect_font` builds a tiny outline
+//! font in memory for testing antialiased text without shipping a font file.
 //!
 //! ### `tilesets`
 //!
@@ -93,6 +104,17 @@
 //!
 //! Both graphical backends' own `tilesets` features forward to this one.
 //!
+//! ### `truetype`
+//!
+//! ⚪ Optional.
+//!
+//! Rasterizes TrueType/OpenType fonts into antialiased glyph tables
+//! (`BitmapFont::from_truetype`, `BitmapFont::from_truetype_cell`).
+//!
+//! Crisp text at fractional DPI scales, where an integer-scaled bitmap font can't land on the pixel
+//! grid. The result is an ordinary `BitmapFont`, so it chains with bitmap fonts in a `FontChain`
+//! and every backend's existing glyph path draws it. Adds `ab_glyph`.
+//!
 //! ### `winit`
 //!
 //! 🟢 Enabled by default.
@@ -127,11 +149,13 @@
 //! font atlas at a new pixel density); until one does, the grid renders at a fixed physical
 //! pixel size on every display, `HiDPI` or not.
 //!
-//! Window resize is clamped to whole cells: a physical size that isn't an exact multiple of
-//! `cell_size()` has its sub-cell remainder truncated, not centered or cleared, and the OS
-//! window is never resized to compensate: see
-//! [`Presenter::resize_surface`](presenter::Presenter::resize_surface)'s doc comment for the full
-//! contract, including the unpainted trailing strip this can leave on screen.
+//! Under the default [`ScalingMode::Reflow`](scaling::ScalingMode::Reflow), window resize is
+//! clamped to whole cells: a physical size that isn't an exact multiple of `cell_size()` has its
+//! sub-cell remainder truncated, not centered or cleared, and the OS window is never resized to
+//! compensate: see [`Presenter::resize_surface`](presenter::Presenter::resize_surface)'s doc
+//! comment for the full contract, including the unpainted trailing strip this can leave on screen.
+//! Every other [`ScalingMode`](scaling::ScalingMode) keeps the grid size and scales the frame into
+//! the whole window instead.
 //!
 //! # Threading model
 //!
@@ -167,6 +191,9 @@
 // every intra-doc link in this crate is fully qualified and this lint is off crate-wide.
 #![allow(rustdoc::redundant_explicit_links)]
 
+/// Animated tileset entries ([`SpriteAnimation`](animation::SpriteAnimation)).
+#[cfg(feature = "tilesets")]
+pub mod animation;
 pub mod atlas;
 // clippy::too_long_first_doc_paragraph is a known-noisy nursery lint (rust-lang/rust-clippy#13441):
 // it mis-attributes its span across this outer doc comment plus `backend`'s own inner module doc,
@@ -176,9 +203,12 @@ pub mod atlas;
 pub mod backend;
 // See the `too_long_first_doc_paragraph` comment above `backend`: same noisy-lint mis-attribution.
 #[allow(clippy::too_long_first_doc_paragraph)]
-/// System clipboard read/write ([`Clipboard`](clipboard::Clipboard),
-/// [`SystemClipboard`](clipboard::SystemClipboard) on native targets).
+/// System clipboard read/write ([`SystemClipboard`](clipboard::SystemClipboard), a
+/// [`Clipboard`](retroglyph_core::backend::Clipboard) on native targets).
 pub mod clipboard;
+/// Retro CRT post-processing parameters ([`CrtEffects`](crt::CrtEffects)) and the per-pixel effect
+/// every graphical backend implements.
+pub mod crt;
 // See the `too_long_first_doc_paragraph` comment above `backend`: same noisy-lint mis-attribution.
 #[allow(clippy::too_long_first_doc_paragraph)]
 /// Shared render-time glyph diagnostics ([`diagnostics::warn_notdef_glyph`]).
@@ -186,8 +216,10 @@ pub mod diagnostics;
 pub mod font;
 /// Shared cell/surface pixel geometry ([`CellGeometry`](geometry::CellGeometry)).
 pub mod geometry;
-/// Canonical default colors ([`DEFAULT_FG`](palette::DEFAULT_FG),
-/// [`DEFAULT_BG`](palette::DEFAULT_BG)) shared by the graphical backends.
+// See the `too_long_first_doc_paragraph` comment above `backend`: same noisy-lint mis-attribution.
+#[allow(clippy::too_long_first_doc_paragraph)]
+/// The [`Palette`](palette::Palette) graphical backends resolve colors through, and loaders for
+/// terminal color themes.
 pub mod palette;
 // See the `too_long_first_doc_paragraph` comment above `backend`: same noisy-lint mis-attribution.
 #[allow(clippy::too_long_first_doc_paragraph)]
@@ -197,13 +229,16 @@ pub mod presenter;
 #[allow(clippy::too_long_first_doc_paragraph)]
 /// The [`PresenterBuilder`](presenter_builder::PresenterBuilder) trait shared by the software/GL/wgpu backend builders.
 pub mod presenter_builder;
+/// How a windowed backend fits its frame into the window ([`ScalingMode`](scaling::ScalingMode)).
+pub mod scaling;
 #[cfg(feature = "tilesets")]
 pub mod sprite_cache;
 // See the `too_long_first_doc_paragraph` comment above `backend`: same noisy-lint mis-attribution.
 #[allow(clippy::too_long_first_doc_paragraph)]
 /// Testing helpers for asserting glyph coverage against a [`font::FontChain`]
-/// ([`testing::assert_glyphs_covered`], [`testing::uncovered_glyphs`]).
-#[cfg(feature = "testing")]
+/// ([`testing::assert_glyphs_covered`], [`testing::uncovered_glyphs`]) and, with `truetype`, for
+/// building an outline font in memory.
+#[cfg(any(test, feature = "testing"))]
 pub mod testing;
 #[cfg(feature = "tilesets")]
 pub mod tileset;
@@ -0,0 +1,888 @@
+//! Loaders for terminal color themes: Alacritty TOML, Xresources, iTerm2 `.itermcolors`, and
+//! base16 YAML.
+//!
+//! Each loader starts from [`Palette::DEFAULT`] and overrides whatever the theme sets, so a theme
+//! that only sets its ANSI colors keeps the default foreground/background, and keys a palette
+//! doesn't model (cursor, selection, dim colors) are ignored. They are hand-written line scanners
+//! rather than full TOML/YAML/plist parsers: these files are flat in practice, and a theme is
+//! read once at startup, so a parser dependency per format isn't worth carrying.
+
+use super::Palette;
+use retroglyph_core::color::Color;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Errors from loading a color theme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PaletteLoadError {
+    /// The theme is malformed on the given 1-based line.
+    Invalid {
+        /// The line the problem was found on.
+        line: usize,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The theme sets none of the colors a [`Palette`] holds.
+    NoColors,
+    /// A base16 scheme lacks `base0X`, the given one of its 16 colors.
+    MissingBase16(u8),
+}
+
+impl fmt::Display for PaletteLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { line, reason } => write!(f, "invalid theme on line {line}: {reason}"),
+            Self::NoColors => write!(
+                f,
+                "the theme sets no foreground, background, or ANSI colors"
+            ),
+            Self::MissingBase16(index) => {
+                write!(f, "the base16 scheme has no base{index:02X} color")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PaletteLoadError {}
+
+/// The ANSI color names Alacritty uses, in [`AnsiColor`](retroglyph_core::color::AnsiColor)
+/// discriminant order.
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Which `baseXX` color base16-shell gives each of the 16 ANSI colors.
+const BASE16_ANSI: [usize; 16] = [
+    0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05, 0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x07,
+];
+
+impl Palette {
+    /// Loads an [Alacritty](https://alacritty.org/config-alacritty.html) color theme: the
+    /// `[colors.primary]` foreground/background, the `[colors.normal]` and `[colors.bright]` ANSI
+    /// colors, and any `[[colors.indexed_colors]]` entries (which set a full 256-color table).
+    ///
+    /// Dotted keys and inline tables work the same as their section-header spellings. Every other
+    /// key is ignored, so a whole `alacritty.toml` loads as well as a theme file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteLoadError::Invalid`] for a line that is not a header or `key = value`, or
+    /// a color key whose value is not a `#rrggbb`/`0xrrggbb` string, and
+    /// [`PaletteLoadError::NoColors`] if the file sets no colors at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::color::Color;
+    /// use retroglyph_window::palette::Palette;
+    ///
+    /// let toml = "
+    /// [colors.primary]
+    /// background = '#002b36'
+    /// foreground = '#839496'
+    ///
+    /// [colors.normal]
+    /// red = '#dc322f'
+    /// ";
+    /// let palette = Palette::from_alacritty_toml(toml)?;
+    /// assert_eq!(palette.background(), (0x00, 0x2B, 0x36));
+    /// assert_eq!(palette.foreground_rgb(Color::RED), (0xDC, 0x32, 0x2F));
+    /// # Ok::<(), retroglyph_window::palette::PaletteLoadError>(())
+    /// ```
+    pub fn from_alacritty_toml(text: &str) -> Result<Self, PaletteLoadError> {
+        let mut theme = Theme::default();
+        let mut section = String::new();
+        // An open `[[colors.indexed_colors]]` entry: its header's line, and its pairs so far
+        // spelled as an inline table's, so it parses the same as one.
+        let mut entry: Option<(usize, String)> = None;
+        let mut lines = text.lines().enumerate();
+        while let Some((number, raw)) = lines.next() {
+            let line = number + 1;
+            let mut content = strip_toml_comment(raw).trim().to_owned();
+            // An array or inline table left open continues on the following lines.
+            while nesting(&content) > 0 {
+                let Some((_, next)) = lines.next() else {
+                    return Err(invalid(line, "unclosed array or inline table"));
+                };
+                content.push(' ');
+                content.push_str(strip_toml_comment(next).trim());
+            }
+            if content.is_empty() {
+                continue;
+            }
+            if content.starts_with('[') {
+                theme.finish_entry(entry.take())?;
+                let header = content
+                    .strip_prefix("[[")
+                    .and_then(|h| h.strip_suffix("]]"))
+                    .or_else(|| content.strip_prefix('[')?.strip_suffix(']'))
+                    .ok_or_else(|| invalid(line, "malformed table header"))?;
+                header.trim().clone_into(&mut section);
+                if content.starts_with("[[") && section == "colors.indexed_colors" {
+                    entry = Some((line, String::new()));
+                }
+                continue;
+            }
+            let (key, value) = content
+                .split_once('=')
+                .ok_or_else(|| invalid(line, "expected `key = value`"))?;
+            let key = join_key(&section, key.trim());
+            if let Some((_, pairs)) = &mut entry {
+                pairs.push_str(&content);
+                pairs.push(',');
+                continue;
+            }
+            theme.alacritty_entry(&key, value.trim(), line)?;
+        }
+        theme.finish_entry(entry)?;
+        theme.into_palette()
+    }
+
+    /// Loads an X resources color theme (`~/.Xresources`, or a theme written for it): the
+    /// `foreground`, `background`, and `color0`..`color255` resources, under any class or
+    /// instance prefix (`*color1`, `URxvt.color1`, `*.color1`).
+    ///
+    /// Colors may be `#rgb`, `#rrggbb`, or `rgb:r/g/b` with one to four hex digits per channel.
+    /// `#define NAME value` lines are expanded where a resource's value is exactly `NAME`, as in
+    /// base16's Xresources templates; other preprocessor lines and `!` comments are skipped.
+    /// Any `color16` or above sets a full 256-color table.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteLoadError::Invalid`] for a line that is not `resource: value`, or a color
+    /// resource whose value is not a color, and [`PaletteLoadError::NoColors`] if the file sets
+    /// no colors at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_core::color::Color;
+    /// use retroglyph_window::palette::Palette;
+    ///
+    /// let xresources = "
+    /// ! Gruvbox
+    /// #define red #cc241d
+    /// *.background: #282828
+    /// URxvt*color1: red
+    /// *color9: rgb:fb/49/34
+    /// ";
+    /// let palette = Palette::from_xresources(xresources)?;
+    /// assert_eq!(palette.background(), (0x28, 0x28, 0x28));
+    /// assert_eq!(palette.foreground_rgb(Color::RED), (0xCC, 0x24, 0x1D));
+    /// assert_eq!(palette.foreground_rgb(Color::BRIGHT_RED), (0xFB, 0x49, 0x34));
+    /// # Ok::<(), retroglyph_window::palette::PaletteLoadError>(())
+    /// ```
+    pub fn from_xresources(text: &str) -> Result<Self, PaletteLoadError> {
+        let mut theme = Theme::default();
+        let mut defines = HashMap::new();
+        for (number, raw) in text.lines().enumerate() {
+            let line = number + 1;
+            let content = raw.trim();
+            if let Some(define) = content.strip_prefix("#define") {
+                let mut words = define.split_whitespace();
+                if let (Some(name), Some(value)) = (words.next(), words.next()) {
+                    defines.insert(name, value);
+                }
+                continue;
+            }
+            if content.is_empty() || content.starts_with('!') || content.starts_with('#') {
+                continue;
+            }
+            let (resource, value) = content
+                .split_once(':')
+                .ok_or_else(|| invalid(line, "expected `resource: value`"))?;
+            let name = resource
+                .trim()
+                .rsplit(['.', '*'])
+                .next()
+                .unwrap_or_default();
+            let value = value.trim();
+            let value = defines.get(value).copied().unwrap_or(value);
+            match name {
+                "foreground" => theme.foreground = Some(parse_color(value, line)?),
+                "background" => theme.background = Some(parse_color(value, line)?),
+                _ => {
+                    if let Some(index) = name.strip_prefix("color").and_then(|n| n.parse().ok()) {
+                        theme.set_indexed(index, parse_color(value, line)?);
+                    }
+                }
+            }
+        }
+        theme.into_palette()
+    }
+
+    /// Loads an iTerm2 `.itermcolors` theme: its `Foreground Color`, `Background Color`, and
+    /// `Ansi 0 Color`..`Ansi 15 Color` entries.
+    ///
+    /// The file is an XML property list; each color is a dictionary of `Red`, `Green`, and
+    /// `Blue Component` reals from 0 to 1. The color space is not converted: components are read
+    /// as sRGB, which is what iTerm2 itself writes for themes exported from current versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteLoadError::Invalid`] for an unterminated tag, a component that is not a
+    /// number, or a color missing one of its three components, and [`PaletteLoadError::NoColors`]
+    /// if the file has none of those entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use retroglyph_window::palette::Palette;
+    ///
+    /// let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <plist version="1.0">
+    /// <dict>
+    ///     <key>Background Color</key>
+    ///     <dict>
+    ///         <key>Blue Component</key><real>0.2</real>
+    ///         <key>Green Component</key><real>0.2</real>
+    ///         <key>Red Component</key><real>0.2</real>
+    ///     </dict>
+    /// </dict>
+    /// </plist>"#;
+    /// let palette = Palette::from_itermcolors(plist)?;
+    /// assert_eq!(palette.background(), (51, 51, 51));
+    /// # Ok::<(), retroglyph_window::palette::PaletteLoadError>(())
+    /// ```
+    pub fn from_itermcolors(text: &str) -> Result<Self, PaletteLoadError> {
+        let mut theme = Theme::default();
+        let mut depth = 0usize;
+        // The top-level key naming the color dictionary being read, and its components so far.
+        let mut name: Option<&str> = None;
+        let mut component: Option<&str> = None;
+        let mut rgb: [Option<f32>; 3] = [None; 3];
+        let mut rest = text;
+        while let Some(start) = rest.find('<') {
+            let offset = text.len() - rest.len() + start;
+            let line = text[..offset].matches('\n').count() + 1;
+            let after = &rest[start + 1..];
+            // Comments, the XML declaration, and the DOCTYPE carry no colors.
+            if let Some(comment) = after.strip_prefix("!--") {
+                let end = comment
+                    .find("-->")
+                    .ok_or_else(|| invalid(line, "unterminated comment"))?;
+                rest = &comment[end + 3..];
+                continue;
+            }
+            let end = after
+                .find('>')
+                .ok_or_else(|| invalid(line, "unterminated tag"))?;
+            let tag = &after[..end];
+            rest = &after[end + 1..];
+            if tag.starts_with(['?', '!']) || tag.ends_with('/') {
+                continue;
+            }
+            match tag {
+                "dict" => {
+                    depth += 1;
+                    rgb = [None; 3];
+                }
+                "/dict" => {
+                    if depth == 2
+                        && let Some(name) = name.take()
+                    {
+                        let [Some(r), Some(g), Some(b)] = rgb else {
+                            return Err(invalid(
+                                line,
+                                "a color lacks a red, green or blue component",
+                            ));
+                        };
+                        theme.set_iterm_color(name, (unit_to_u8(r), unit_to_u8(g), unit_to_u8(b)));
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                "key" | "real" | "integer" | "string" => {
+                    let close = rest
+                        .find('<')
+                        .ok_or_else(|| invalid(line, "unterminated element"))?;
+                    let value = rest[..close].trim();
+                    rest = &rest[close..];
+                    match (tag, depth) {
+                        ("key", 1) => name = Some(value),
+                        ("key", _) => component = Some(value),
+                        ("real" | "integer", 2) => {
+                            let channel = match component.take() {
+                                Some("Red Component") => 0,
+                                Some("Green Component") => 1,
+                                Some("Blue Component") => 2,
+                                _ => continue,
+                            };
+                            let value = value
+                                .parse()
+                                .map_err(|_| invalid(line, "a color component is not a number"))?;
+                            rgb[channel] = Some(value);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        theme.into_palette()
+    }
+
+    /// Loads a [base16](https://github.com/tinted-theming/home) scheme, in either the classic
+    /// flat layout (`base00: "181818"`) or the newer one nesting the colors under `palette:`
+    /// (`base00: "#181818"`).
+    ///
+    /// The 16 `baseXX` colors map onto the palette the way base16-shell maps them onto a
+    /// terminal: `base05` on `base00` is the default foreground on background, and the ANSI colors
+    /// are `base00`, `08`, `0B`, `0A`, `0D`, `0E`, `0C`, `05`, then `03`, `08`, `0B`, `0A`, `0D`,
+    /// `0E`, `0C`, `07` for the bright ones. Other keys (`scheme`, `author`, base24's `base10`
+    /// and up) are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteLoadError::Invalid`] for a `baseXX` value that is not a hex color,
+    /// [`PaletteLoadError::MissingBase16`] if one of the 16 colors is missing, and
+    /// [`PaletteLoadError::NoColors`] if all of them are.
+    pub fn from_base16_yaml(text: &str) -> Result<Self, PaletteLoadError> {
+        let mut base = [None; 16];
+        for (number, raw) in text.lines().enumerate() {
+            let content = raw.trim();
+            let Some((key, value)) = content.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().trim_matches(['"', '\'']);
+            let Some(digit) = key
+                .strip_prefix("base0")
+                .filter(|digit| digit.len() == 1)
+                .and_then(|digit| usize::from_str_radix(digit, 16).ok())
+            else {
+                continue;
+            };
+            base[digit] = Some(parse_color(value, number + 1)?);
+        }
+        if base.iter().all(Option::is_none) {
+            return Err(PaletteLoadError::NoColors);
+        }
+        let mut colors = [(0, 0, 0); 16];
+        for (index, (color, slot)) in colors.iter_mut().zip(base).enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let missing = PaletteLoadError::MissingBase16(index as u8);
+            *color = slot.ok_or(missing)?;
+        }
+        Ok(Self::DEFAULT
+            .with_foreground(colors[0x05])
+            .with_background(colors[0x00])
+            .with_ansi_colors(BASE16_ANSI.map(|base| colors[base])))
+    }
+}
+
+/// The colors a theme file has set so far; anything left `None` keeps [`Palette::DEFAULT`]'s.
+#[derive(Default)]
+struct Theme {
+    foreground: Option<(u8, u8, u8)>,
+    background: Option<(u8, u8, u8)>,
+    /// Colors set by index: 0-15 are the ANSI colors, 16 and up need a full 256-color table.
+    indexed: HashMap<u8, (u8, u8, u8)>,
+}
+
+impl Theme {
+    fn set_indexed(&mut self, index: u8, rgb: (u8, u8, u8)) {
+        self.indexed.insert(index, rgb);
+    }
+
+    /// Applies one Alacritty `key = value` pair, `key` already qualified by its section.
+    fn alacritty_entry(
+        &mut self,
+        key: &str,
+        value: &str,
+        line: usize,
+    ) -> Result<(), PaletteLoadError> {
+        if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            for pair in split_top_level(inner) {
+                let (sub, value) = pair
+                    .split_once('=')
+                    .ok_or_else(|| invalid(line, "expected `key = value` in an inline table"))?;
+                self.alacritty_entry(&join_key(key, sub.trim()), value.trim(), line)?;
+            }
+            return Ok(());
+        }
+        match key {
+            "colors.primary.foreground" => self.foreground = Some(parse_color(value, line)?),
+            "colors.primary.background" => self.background = Some(parse_color(value, line)?),
+            "colors.indexed_colors" => {
+                let items = value
+                    .strip_prefix('[')
+                    .and_then(|v| v.strip_suffix(']'))
+                    .ok_or_else(|| invalid(line, "indexed_colors must be an array"))?;
+                for item in split_top_level(items) {
+                    let (index, rgb) = indexed_color(item, line)?;
+                    self.set_indexed(index, rgb);
+                }
+            }
+            _ => {
+                let ansi = key
+                    .strip_prefix("colors.normal.")
+                    .map(|name| (name, 0))
+                    .or_else(|| key.strip_prefix("colors.bright.").map(|name| (name, 8)));
+                if let Some((name, offset)) = ansi
+                    && let Some(index) = ANSI_NAMES.iter().position(|&n| n == name)
+                {
+                    #[allow(clippy::cast_possible_truncation)]
+                    self.set_indexed(index as u8 + offset, parse_color(value, line)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a finished `[[colors.indexed_colors]]` entry.
+    fn finish_entry(&mut self, entry: Option<(usize, String)>) -> Result<(), PaletteLoadError> {
+        if let Some((line, pairs)) = entry {
+            let (index, rgb) = indexed_color(&format!("{{{pairs}}}"), line)?;
+            self.set_indexed(index, rgb);
+        }
+        Ok(())
+    }
+
+    fn set_iterm_color(&mut self, name: &str, rgb: (u8, u8, u8)) {
+        match name {
+            "Foreground Color" => self.foreground = Some(rgb),
+            "Background Color" => self.background = Some(rgb),
+            _ => {
+                if let Some(index) = name
+                    .strip_prefix("Ansi ")
+                    .and_then(|n| n.strip_suffix(" Color"))
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&index| index < 16)
+                {
+                    self.set_indexed(index, rgb);
+                }
+            }
+        }
+    }
+
+    fn into_palette(self) -> Result<Palette, PaletteLoadError> {
+        if self.foreground.is_none() && self.background.is_none() && self.indexed.is_empty() {
+            return Err(PaletteLoadError::NoColors);
+        }
+        let mut palette = Palette::DEFAULT;
+        if let Some(rgb) = self.foreground {
+            palette = palette.with_foreground(rgb);
+        }
+        if let Some(rgb) = self.background {
+            palette = palette.with_background(rgb);
+        }
+        let mut ansi = *palette.ansi();
+        for (&index, &rgb) in &self.indexed {
+            if let Some(slot) = ansi.get_mut(usize::from(index)) {
+                *slot = rgb;
+            }
+        }
+        palette = palette.with_ansi_colors(ansi);
+        if self.indexed.keys().any(|&index| index >= 16) {
+            let mut table = [(0, 0, 0); 256];
+            for (index, slot) in (0..=u8::MAX).zip(&mut table) {
+                *slot = self
+                    .indexed
+                    .get(&index)
+                    .copied()
+                    .unwrap_or_else(|| palette.foreground_rgb(Color::Indexed(index)));
+            }
+            palette = palette.with_indexed(table);
+        }
+        Ok(palette)
+    }
+}
+
+const fn invalid(line: usize, reason: &'static str) -> PaletteLoadError {
+    PaletteLoadError::Invalid { line, reason }
+}
+
+/// Strips a quoted string's quotes, or cuts an unquoted value at its first whitespace (where a
+/// trailing comment would start).
+fn scalar(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote) {
+            return inner.split(quote).next().unwrap_or(inner);
+        }
+    }
+    value.split_whitespace().next().unwrap_or(value)
+}
+
+/// Parses a `#rgb`, `#rrggbb`, `0xrrggbb`, bare `rrggbb`, or X11 `rgb:r/g/b` color, quoted or
+/// not.
+fn parse_color(value: &str, line: usize) -> Result<(u8, u8, u8), PaletteLoadError> {
+    let value = scalar(value);
+    let rgb = value.strip_prefix("rgb:").map_or_else(
+        || {
+            let digits = value
+                .strip_prefix('#')
+                .or_else(|| value.strip_prefix("0x"))
+                .or_else(|| value.strip_prefix("0X"))
+                .unwrap_or(value);
+            hex_rgb(digits)
+        },
+        x11_rgb,
+    );
+    rgb.ok_or_else(|| invalid(line, "expected a color such as `#rrggbb`"))
+}
+
+fn hex_rgb(digits: &str) -> Option<(u8, u8, u8)> {
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    let [_, r, g, b] = value.to_be_bytes();
+    match digits.len() {
+        6 => Some((r, g, b)),
+        // `#rgb` repeats each digit: `#f80` is `#ff8800`.
+        3 => Some((g * 17, (b >> 4) * 17, (b & 0xF) * 17)),
+        _ => None,
+    }
+}
+
+/// Parses X11's `r/g/b`, each channel one to four hex digits scaled to its own range.
+fn x11_rgb(channels: &str) -> Option<(u8, u8, u8)> {
+    let mut parts = channels.split('/').map(|part| {
+        if part.is_empty() || part.len() > 4 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let value = u32::from_str_radix(part, 16).ok()?;
+        let max = (1u32 << (4 * part.len())) - 1;
+        u8::try_from((value * 255 + max / 2) / max).ok()
+    });
+    let rgb = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(rgb)
+}
+
+/// Parses one `{ index = N, color = '#rrggbb' }` item of Alacritty's `indexed_colors` array.
+fn indexed_color(item: &str, line: usize) -> Result<(u8, (u8, u8, u8)), PaletteLoadError> {
+    let pairs = item
+        .strip_prefix('{')
+        .and_then(|i| i.strip_suffix('}'))
+        .ok_or_else(|| invalid(line, "an indexed color must be an inline table"))?;
+    let (mut index, mut color) = (None, None);
+    for pair in split_top_level(pairs) {
+        match pair.split_once('=').map(|(key, value)| (key.trim(), value)) {
+            Some(("index", value)) => index = Some(parse_index(value, line)?),
+            Some(("color", value)) => color = Some(parse_color(value, line)?),
+            _ => {}
+        }
+    }
+    index
+        .zip(color)
+        .ok_or_else(|| invalid(line, "an indexed color needs an index and a color"))
+}
+
+fn parse_index(value: &str, line: usize) -> Result<u8, PaletteLoadError> {
+    scalar(value)
+        .parse()
+        .map_err(|_| invalid(line, "expected a color index from 0 to 255"))
+}
+
+/// Scales an iTerm2 color component from `0.0..=1.0` to a byte.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn unit_to_u8(component: f32) -> u8 {
+    (component.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Qualifies a TOML key by its enclosing table, stripping quotes from each dotted part.
+fn join_key(section: &str, key: &str) -> String {
+    let key = key
+        .split('.')
+        .map(|part| part.trim().trim_matches(['"', '\'']))
+        .collect::<Vec<_>>()
+        .join(".");
+    if section.is_empty() {
+        key
+    } else {
+        format!("{section}.{key}")
+    }
+}
+
+/// Cuts a TOML line at its comment, if any, leaving `#`s inside strings alone.
+fn strip_toml_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (at, ch) in line.char_indices() {
+        match (quote, ch) {
+            (None, '#') => return &line[..at],
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            _ => {}
+        }
+    }
+    line
+}
+
+/// How many arrays and inline tables `text` leaves open, ignoring brackets inside strings.
+fn nesting(text: &str) -> isize {
+    let mut quote = None;
+    let mut depth = 0;
+    for ch in text.chars() {
+        match (quote, ch) {
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Splits an array's items or an inline table's pairs at their top-level commas.
+fn split_top_level(text: &str) -> impl Iterator<Item = &str> {
+    let mut quote = None;
+    let mut depth = 0;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (at, ch) in text.char_indices() {
+        match (quote, ch) {
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            (None, ',') if depth == 0 => {
+                parts.push(&text[start..at]);
+                start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PaletteLoadError;
+    use crate::palette::Palette;
+    use retroglyph_core::color::{AnsiColor, Color};
+
+    #[test]
+    fn alacritty_reads_sections_dotted_keys_and_inline_tables() {
+        let toml = "
+            [general]
+            live_config_reload = true
+
+            [colors]
+            primary.foreground = \"0xfdf4c1\" # trailing comment
+            normal = { black = '#282828', red = '#cc241d' }
+
+            [colors.bright]
+            white = '#ffffff'
+            magenta = '#d3869b'
+
+            [colors.cursor]
+            text = '#000000'
+        ";
+        let palette = Palette::from_alacritty_toml(toml).unwrap();
+        assert_eq!(palette.foreground(), (0xFD, 0xF4, 0xC1));
+        assert_eq!(palette.background(), Palette::DEFAULT.background());
+        assert_eq!(palette.foreground_rgb(Color::BLACK), (0x28, 0x28, 0x28));
+        assert_eq!(palette.foreground_rgb(Color::RED), (0xCC, 0x24, 0x1D));
+        assert_eq!(
+            palette.foreground_rgb(Color::BRIGHT_MAGENTA),
+            (0xD3, 0x86, 0x9B)
+        );
+        assert_eq!(
+            palette.foreground_rgb(Color::BRIGHT_WHITE),
+            (0xFF, 0xFF, 0xFF)
+        );
+        assert_eq!(
+            palette.foreground_rgb(Color::GREEN),
+            AnsiColor::Green.to_rgb()
+        );
+        assert!(palette.indexed().is_none());
+    }
+
+    #[test]
+    fn alacritty_indexed_colors_set_a_full_table() {
+        let toml = "
+            [[colors.indexed_colors]]
+            index = 16
+            color = '#010203'
+
+            [[colors.indexed_colors]]
+            index = 17
+            color = '#040506'
+
+            [colors]
+            indexed_colors = [
+                { index = 255, color = '#070809' },
+            ]
+        ";
+        let palette = Palette::from_alacritty_toml(toml).unwrap();
+        assert_eq!(palette.foreground_rgb(Color::Indexed(16)), (1, 2, 3));
+        assert_eq!(palette.foreground_rgb(Color::Indexed(17)), (4, 5, 6));
+        assert_eq!(palette.foreground_rgb(Color::Indexed(255)), (7, 8, 9));
+        assert_eq!(
+            palette.foreground_rgb(Color::Indexed(100)),
+            Color::Indexed(100).resolve_rgb((0, 0, 0))
+        );
+        assert_eq!(
+            palette.foreground_rgb(Color::Indexed(1)),
+            AnsiColor::Red.to_rgb()
+        );
+    }
+
+    #[test]
+    fn alacritty_reports_the_line_of_a_bad_color() {
+        let toml = "[colors.normal]\nblack = '#000000'\nred = 'crimson'\n";
+        assert_eq!(
+            Palette::from_alacritty_toml(toml),
+            Err(PaletteLoadError::Invalid {
+                line: 3,
+                reason: "expected a color such as `#rrggbb`"
+            })
+        );
+        assert_eq!(
+            Palette::from_alacritty_toml("[window]\nopacity = 0.9\n"),
+            Err(PaletteLoadError::NoColors)
+        );
+    }
+
+    #[test]
+    fn xresources_reads_every_spelling_and_expands_defines() {
+        let xresources = "
+            ! comment
+            #define base0D #83a598
+            #ifdef COLOR
+            *foreground: #ebdbb2
+            URxvt.background:   #282828
+            *.color4: base0D
+            xterm*color12: #8af
+            *color200: rgb:0/8/f
+            *cursorColor: #ffffff
+        ";
+        let palette = Palette::from_xresources(xresources).unwrap();
+        assert_eq!(palette.foreground(), (0xEB, 0xDB, 0xB2));
+        assert_eq!(palette.background(), (0x28, 0x28, 0x28));
+        assert_eq!(palette.foreground_rgb(Color::BLUE), (0x83, 0xA5, 0x98));
+        assert_eq!(
+            palette.foreground_rgb(Color::BRIGHT_BLUE),
+            (0x88, 0xAA, 0xFF)
+        );
+        assert_eq!(
+            palette.foreground_rgb(Color::Indexed(200)),
+            (0x00, 0x88, 0xFF)
+        );
+        assert_eq!(
+            palette.foreground_rgb(Color::Indexed(4)),
+            (0x83, 0xA5, 0x98)
+        );
+    }
+
+    #[test]
+    fn xresources_scales_each_x11_channel_by_its_own_width() {
+        let palette = Palette::from_xresources("*background: rgb:f/80/ffff").unwrap();
+        assert_eq!(palette.background(), (0xFF, 0x80, 0xFF));
+        assert!(matches!(
+            Palette::from_xresources("*background: rgb:f/80"),
+            Err(PaletteLoadError::Invalid { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn itermcolors_reads_components_and_skips_unrelated_entries() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<!-- <key>Ansi 2 Color</key> -->
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Alpha Component</key>
+		<real>1</real>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Color Space</key>
+		<string>sRGB</string>
+		<key>Green Component</key>
+		<real>0.5</real>
+		<key>Red Component</key>
+		<integer>1</integer>
+	</dict>
+	<key>Cursor Color</key>
+	<dict>
+		<key>Blue Component</key><real>1</real>
+		<key>Green Component</key><real>1</real>
+		<key>Red Component</key><real>1</real>
+	</dict>
+	<key>Foreground Color</key>
+	<dict>
+		<key>Blue Component</key><real>0.8</real>
+		<key>Green Component</key><real>0.8</real>
+		<key>Red Component</key><real>0.8</real>
+	</dict>
+</dict>
+</plist>"#;
+        let palette = Palette::from_itermcolors(plist).unwrap();
+        assert_eq!(palette.foreground_rgb(Color::RED), (255, 128, 0));
+        assert_eq!(palette.foreground(), (204, 204, 204));
+        assert_eq!(
+            palette.foreground_rgb(Color::GREEN),
+            AnsiColor::Green.to_rgb()
+        );
+        assert_eq!(palette.background(), Palette::DEFAULT.background());
+    }
+
+    #[test]
+    fn itermcolors_rejects_a_color_missing_a_component() {
+        let plist = "<plist><dict>\n<key>Ansi 0 Color</key>\n<dict>\
+                     <key>Red Component</key><real>1</real>\n</dict></dict></plist>";
+        assert_eq!(
+            Palette::from_itermcolors(plist),
+            Err(PaletteLoadError::Invalid {
+                line: 4,
+                reason: "a color lacks a red, green or blue component"
+            })
+        );
+    }
+
+    /// A base16 scheme whose `baseXX` is `#XX0000`, so each ANSI color names its source.
+    fn base16(nested: bool) -> String {
+        use std::fmt::Write;
+
+        let mut yaml = String::from("scheme: \"Test\"\nauthor: \"Someone\"\n");
+        if nested {
+            yaml.push_str("palette:\n");
+        }
+        for index in 0..16 {
+            if nested {
+                writeln!(yaml, "  base{index:02X}: \"#{index:02x}0000\"").unwrap();
+            } else {
+                writeln!(yaml, "base{index:02X}: \"{index:02x}0000\" # comment").unwrap();
+            }
+        }
+        yaml
+    }
+
+    #[test]
+    fn base16_maps_onto_ansi_like_base16_shell() {
+        for nested in [false, true] {
+            let palette = Palette::from_base16_yaml(&base16(nested)).unwrap();
+            assert_eq!(palette.foreground(), (0x05, 0, 0));
+            assert_eq!(palette.background(), (0x00, 0, 0));
+            let sources: Vec<u8> = palette.ansi().iter().map(|rgb| rgb.0).collect();
+            assert_eq!(
+                sources,
+                [
+                    0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05, 0x03, 0x08, 0x0B, 0x0A, 0x0D,
+                    0x0E, 0x0C, 0x07
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn base16_requires_all_sixteen_colors() {
+        let yaml = base16(false).replace("base0C", "base10");
+        assert_eq!(
+            Palette::from_base16_yaml(&yaml),
+            Err(PaletteLoadError::MissingBase16(0x0C))
+        );
+        assert_eq!(
+            Palette::from_base16_yaml("scheme: \"Empty\"\n"),
+            Err(PaletteLoadError::NoColors)
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Colors the graphical backends resolve [`Color`](retroglyph_core::color::Color)s through.
+//!
+//! `retroglyph-core`'s [`Color::resolve_rgb`](retroglyph_core::color::Color::resolve_rgb) resolves
+//! palette colors through one fixed table (the classic xterm defaults), and has no opinion on what
+//! a [`Color::Default`](retroglyph_core::color::Color::Default) foreground/background should be:
+//! both are rendering-policy choices, not core color-model ones. A terminal backend never faces
+//! them, since it emits ANSI/indexed colors as-is and lets the terminal apply the user's theme. A
+//! pixel backend must produce real RGB itself, so it resolves through a
+//! [`Palette`](crate::palette::Palette) instead: the default foreground/background pair, the 16 ANSI
+//! colors, and optionally the whole 256-color table.
+//! [`Palette::DEFAULT`](crate::palette::Palette::DEFAULT) reproduces core's table, so a backend
+//! given no palette renders with core's colors; the `load` submodule's loaders read the themes
+//! terminals already ship (Alacritty, Xresources, iTerm2, base16), so a windowed build can look
+//! like the user's terminal.
+
+use retroglyph_core::color::{AnsiColor, Color};
+
+mod load;
+
+pub use load::PaletteLoadError;
+
+/// Foreground for [`Color::Default`](retroglyph_core::color::Color::Default): a light gray,
+/// matching a typical terminal's default text color.
+pub const DEFAULT_FG: (u8, u8, u8) = (0xD4, 0xD4, 0xD4);
+
+/// Background for [`Color::Default`](retroglyph_core::color::Color::Default): black.
+pub const DEFAULT_BG: (u8, u8, u8) = (0x00, 0x00, 0x00);
+
+/// Every color a pixel backend resolves a [`Color`] through.
+///
+/// - [`Color::Rgb`] passes through unchanged: a palette only remaps named colors.
+/// - [`Color::Ansi`] resolves through the palette's 16 [`ansi`](Self::ansi) colors.
+/// - [`Color::Indexed`] resolves through the full 256-color [`indexed`](Self::indexed) table when
+///   one is set. Without one, indices 0-15 resolve like the matching ANSI color and 16-255 through
+///   core's standard 6×6×6 cube and grayscale ramp, which is what terminals do too.
+/// - [`Color::Default`] resolves to the palette's [`foreground`](Self::foreground) or
+///   [`background`](Self::background), depending on which the color is used as.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_core::color::{AnsiColor, Color};
+/// use retroglyph_window::palette::Palette;
+///
+/// let palette = Palette::default()
+///     .with_background((0x00, 0x2B, 0x36))
+///     .with_ansi(AnsiColor::Red, (0xDC, 0x32, 0x2F));
+///
+/// assert_eq!(palette.foreground_rgb(Color::RED), (0xDC, 0x32, 0x2F));
+/// assert_eq!(palette.background_rgb(Color::Default), (0x00, 0x2B, 0x36));
+/// assert_eq!(palette.foreground_rgb(Color::rgb(1, 2, 3)), (1, 2, 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    foreground: (u8, u8, u8),
+    background: (u8, u8, u8),
+    ansi: [(u8, u8, u8); 16],
+    indexed: Option<Box<[(u8, u8, u8); 256]>>,
+}
+
+impl Palette {
+    /// [`DEFAULT_FG`] on [`DEFAULT_BG`], with core's xterm ANSI colors: what every pixel backend
+    /// renders with unless given another palette.
+    pub const DEFAULT: Self = Self {
+        foreground: DEFAULT_FG,
+        background: DEFAULT_BG,
+        ansi: xterm_ansi(),
+        indexed: None,
+    };
+
+    /// The IBM CGA's 16 RGBI colors, including its dark yellow adjusted to brown, with light gray
+    /// text on black like a DOS console.
+    pub const CGA: Self = Self {
+        foreground: (0xAA, 0xAA, 0xAA),
+        background: (0x00, 0x00, 0x00),
+        ansi: [
+            (0x00, 0x00, 0x00),
+            (0xAA, 0x00, 0x00),
+            (0x00, 0xAA, 0x00),
+            (0xAA, 0x55, 0x00),
+            (0x00, 0x00, 0xAA),
+            (0xAA, 0x00, 0xAA),
+            (0x00, 0xAA, 0xAA),
+            (0xAA, 0xAA, 0xAA),
+            (0x55, 0x55, 0x55),
+            (0xFF, 0x55, 0x55),
+            (0x55, 0xFF, 0x55),
+            (0xFF, 0xFF, 0x55),
+            (0x55, 0x55, 0xFF),
+            (0xFF, 0x55, 0xFF),
+            (0x55, 0xFF, 0xFF),
+            (0xFF, 0xFF, 0xFF),
+        ],
+        indexed: None,
+    };
+
+    /// Replaces the color [`Color::Default`] resolves to as a foreground.
+    #[must_use]
+    pub const fn with_foreground(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.foreground = rgb;
+        self
+    }
+
+    /// Replaces the color [`Color::Default`] resolves to as a background.
+    #[must_use]
+    pub const fn with_background(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.background = rgb;
+        self
+    }
+
+    /// Replaces one of the 16 ANSI colors.
+    #[must_use]
+    pub const fn with_ansi(mut self, color: AnsiColor, rgb: (u8, u8, u8)) -> Self {
+        self.ansi[color.to_index() as usize] = rgb;
+        self
+    }
+
+    /// Replaces all 16 ANSI colors, in [`AnsiColor`] discriminant order.
+    #[must_use]
+    pub const fn with_ansi_colors(mut self, ansi: [(u8, u8, u8); 16]) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Sets the full 256-color table [`Color::Indexed`] resolves through.
+    ///
+    /// The table replaces the palette's ANSI colors for `Indexed(0..16)` as well, so a theme can
+    /// give the two different readings of the same index (as xterm's `color0`..`color15` and its
+    /// bold colors can); [`Color::Ansi`] keeps resolving through [`ansi`](Self::ansi).
+    #[must_use]
+    pub fn with_indexed(mut self, table: [(u8, u8, u8); 256]) -> Self {
+        self.indexed = Some(Box::new(table));
+        self
+    }
+
+    /// The color [`Color::Default`] resolves to as a foreground.
+    #[must_use]
+    pub const fn foreground(&self) -> (u8, u8, u8) {
+        self.foreground
+    }
+
+    /// The color [`Color::Default`] resolves to as a background.
+    #[must_use]
+    pub const fn background(&self) -> (u8, u8, u8) {
+        self.background
+    }
+
+    /// The 16 ANSI colors, in [`AnsiColor`] discriminant order.
+    #[must_use]
+    pub const fn ansi(&self) -> &[(u8, u8, u8); 16] {
+        &self.ansi
+    }
+
+    /// The full 256-color table, if one was set with [`with_indexed`](Self::with_indexed).
+    #[must_use]
+    pub fn indexed(&self) -> Option<&[(u8, u8, u8); 256]> {
+        self.indexed.as_deref()
+    }
+
+    /// Resolves `color` to RGB through this palette, substituting `default` for
+    /// [`Color::Default`].
+    ///
+    /// Prefer [`foreground_rgb`](Self::foreground_rgb) or [`background_rgb`](Self::background_rgb),
+    /// which pick the matching default.
+    #[must_use]
+    pub fn resolve(&self, color: Color, default: (u8, u8, u8)) -> (u8, u8, u8) {
+        match color {
+            Color::Ansi(ansi) => self.ansi[usize::from(ansi.to_index())],
+            Color::Indexed(index) => match &self.indexed {
+                Some(table) => table[usize::from(index)],
+                None if index < 16 => self.ansi[usize::from(index)],
+                None => color.resolve_rgb(default),
+            },
+            _ => color.resolve_rgb(default),
+        }
+    }
+
+    /// Resolves `color` used as a foreground: [`Color::Default`] becomes
+    /// [`foreground`](Self::foreground).
+    #[must_use]
+    pub fn foreground_rgb(&self, color: Color) -> (u8, u8, u8) {
+        self.resolve(color, self.foreground)
+    }
+
+    /// Resolves `color` used as a background: [`Color::Default`] becomes
+    /// [`background`](Self::background).
+    #[must_use]
+    pub fn background_rgb(&self, color: Color) -> (u8, u8, u8) {
+        self.resolve(color, self.background)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Core's 16 ANSI colors, read through [`Color::resolve_rgb`] so [`Palette::DEFAULT`] can never
+/// disagree with it.
+const fn xterm_ansi() -> [(u8, u8, u8); 16] {
+    let mut ansi = [(0, 0, 0); 16];
+    let mut index = 0;
+    while index < 16 {
+        #[allow(clippy::cast_possible_truncation)]
+        let color = Color::Indexed(index as u8);
+        ansi[index] = color.resolve_rgb(DEFAULT_FG);
+        index += 1;
+    }
+    ansi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_palette_matches_core_for_every_color() {
+        let palette = Palette::DEFAULT;
+        for index in 0..=u8::MAX {
+            let color = Color::Indexed(index);
+            assert_eq!(palette.foreground_rgb(color), color.resolve_rgb(DEFAULT_FG));
+        }
+        for index in 0..16 {
+            let ansi = AnsiColor::try_from(index).expect("0..16 are valid ANSI indices");
+            assert_eq!(palette.foreground_rgb(Color::Ansi(ansi)), ansi.to_rgb());
+        }
+        assert_eq!(palette.foreground_rgb(Color::Default), DEFAULT_FG);
+        assert_eq!(palette.background_rgb(Color::Default), DEFAULT_BG);
+    }
+
+    #[test]
+    fn low_indexed_colors_follow_the_ansi_colors_until_a_table_is_set() {
+        let palette = Palette::DEFAULT.with_ansi(AnsiColor::Blue, (1, 2, 3));
+        assert_eq!(palette.foreground_rgb(Color::Indexed(4)), (1, 2, 3));
+        assert_eq!(
+            palette.foreground_rgb(Color::Indexed(196)),
+            Color::Indexed(196).resolve_rgb(DEFAULT_FG)
+        );
+
+        let palette = palette.with_indexed([(9, 9, 9); 256]);
+        assert_eq!(palette.foreground_rgb(Color::Indexed(4)), (9, 9, 9));
+        assert_eq!(palette.foreground_rgb(Color::Indexed(196)), (9, 9, 9));
+        assert_eq!(palette.foreground_rgb(Color::BLUE), (1, 2, 3));
+    }
+}
@@ -17,6 +17,7 @@
 //! [`resize_surface`](crate::presenter::Presenter::resize_surface), and the single-threaded execution model
 //! every `Presenter` implementation runs under.
 
+use core::time::Duration;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use retroglyph_core::backend::Output;
 use retroglyph_core::tile::Tile;
@@ -24,6 +25,7 @@ use std::fmt;
 use std::sync::Arc;
 
 use crate::geometry::CellGeometry;
+use crate::scaling::ScalingMode;
 
 /// A window/display handle pair, as one trait.
 ///
@@ -291,16 +293,23 @@ pub trait Presenter: Output {
     /// Resize the window surface to a new physical pixel size.
     ///
     /// Called on every window resize event with `width`/`height` already resolved by the
-    /// caller: for the `winit` driver (see `winit::run::WindowApp::resize_to`), that means
-    /// `cols * cell_w` x `rows * cell_h`, where `cols`/`rows` are the window's physical size
-    /// divided down to whole cells. Any sub-cell remainder is truncated, not centered or
-    /// cleared: when the window's physical size isn't an exact multiple of the cell size,
-    /// `width`/`height` here are the largest whole-cell-multiple that fits, which can be
-    /// smaller than the window's actual physical size. The OS window itself is never resized
-    /// to compensate, so a non-exact-multiple resize leaves a thin strip at the window's
-    /// trailing edge outside the surface: retroglyph does not paint or clear that strip;
-    /// whatever the OS/windowing backend leaves there remains visible until a subsequent
+    /// caller, according to [`scaling`](Self::scaling).
+    ///
+    /// Under [`ScalingMode::Reflow`], for the `winit` driver (see
+    /// `winit::run::WindowApp::resize_to`), that means `cols * cell_w` x `rows * cell_h`, where
+    /// `cols`/`rows` are the window's physical size divided down to whole cells. Any sub-cell
+    /// remainder is truncated, not centered or cleared: when the window's physical size isn't an
+    /// exact multiple of the cell size, `width`/`height` here are the largest whole-cell-multiple
+    /// that fits, which can be smaller than the window's actual physical size. The OS window
+    /// itself is never resized to compensate, so a non-exact-multiple resize leaves a thin strip
+    /// at the window's trailing edge outside the surface: retroglyph does not paint or clear that
+    /// strip; whatever the OS/windowing backend leaves there remains visible until a subsequent
     /// resize covers it.
+    ///
+    /// Under every other mode, the grid keeps its size and `width`/`height` are the window's
+    /// whole physical size (at least `1x1`). The presenter scales its frame into
+    /// [`ScalingMode::viewport`] of it, clears the rest to [`ScalingMode::bars`], and reports
+    /// that viewport through [`geometry`](Self::geometry).
     fn resize_surface(&mut self, width: u32, height: u32);
 
     /// Notify the presenter that the window's scale factor (DPI) changed.
@@ -337,10 +346,22 @@ pub trait Presenter: Output {
     ///
     /// Lets callers (e.g. `winit::run`'s cursor/mouse handlers) use
     /// [`CellGeometry::pixel_to_cell`] directly instead of re-deriving cell size from
-    /// [`cell_size`](Self::cell_size) themselves.
+    /// [`cell_size`](Self::cell_size) themselves. A presenter that scales its frame (any
+    /// [`scaling`](Self::scaling) but [`ScalingMode::Reflow`]) sets the geometry's
+    /// [`viewport`](CellGeometry::viewport) to where the frame currently is in the window, so that
+    /// mapping stays correct at every window size.
     #[must_use]
     fn geometry(&self) -> CellGeometry;
 
+    /// How this presenter fits its frame into the window, which decides what the event loop
+    /// passes to [`resize_surface`](Self::resize_surface).
+    ///
+    /// Defaults to [`ScalingMode::Reflow`]: the grid follows the window.
+    #[must_use]
+    fn scaling(&self) -> ScalingMode {
+        ScalingMode::Reflow
+    }
+
     /// Cell size in physical pixels `(width, height)`: [`geometry`](Self::geometry)'s
     /// [`cell_size`](CellGeometry::cell_size).
     ///
@@ -350,6 +371,28 @@ pub trait Presenter: Output {
     fn cell_size(&self) -> (u32, u32) {
         self.geometry().cell_size()
     }
+
+    /// The clock animated tileset sprites are drawn at.
+    ///
+    /// One clock for the whole presenter, so every cell showing the same animated codepoint is on
+    /// the same frame however long ago each was written. Defaults to zero for presenters that
+    /// draw no sprites.
+    #[must_use]
+    fn animation_time(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Sets the clock [`animation_time`](Self::animation_time) returns. The next present draws
+    /// every animated sprite at its frame for `time`, whether or not the grid changed.
+    ///
+    /// A no-op by default.
+    fn set_animation_time(&mut self, _time: Duration) {}
+
+    /// Advances [`animation_time`](Self::animation_time) by `delta`. The winit event loop calls
+    /// this with each [`Frame`](retroglyph_core::app::Frame)'s delta before the app's update.
+    fn advance_animations(&mut self, delta: Duration) {
+        self.set_animation_time(self.animation_time().saturating_add(delta));
+    }
 }
 
 /// The glyph a `Presenter` should paint art (a bitmap-font glyph or a tileset sprite) for, or
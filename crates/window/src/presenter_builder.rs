@@ -1,8 +1,11 @@
 //! [`PresenterBuilder`](crate::presenter_builder::PresenterBuilder), the shape shared by the
 //! software/GL/wgpu backend builders.
 
+use crate::crt::CrtEffects;
 use crate::font::FontChain;
+use crate::palette::Palette;
 use crate::presenter::Presenter;
+use crate::scaling::ScalingMode;
 #[cfg(feature = "tilesets")]
 use crate::tileset::TilesetOptions;
 
@@ -10,10 +13,10 @@ use crate::tileset::TilesetOptions;
 ///
 /// `SoftwareBackendBuilder`, `GlBackendBuilder`, and `WgpuBackendBuilder` are otherwise unrelated
 /// types: each crate defines its own builder because each backend owns its own renderer and error
-/// type, but the configuration surface (grid size, scale, fonts, tilesets) is identical across all
-/// three. This trait names that shared surface so a caller (a demo gallery, a test harness) can be
-/// generic over "a windowed backend's builder" instead of writing one near-identical function per
-/// backend crate (retroglyph#1192).
+/// type, but the configuration surface (grid size, scale, fonts, palette, CRT effects, scaling
+/// mode, tilesets) is identical across all three. This trait names that shared surface so a caller
+/// (a demo gallery, a test harness) can be generic over "a windowed backend's builder" instead of
+/// writing one near-identical function per backend crate (retroglyph#1192).
 ///
 /// A backend crate implements this by forwarding to its existing inherent methods; see
 /// `SoftwareBackendBuilder`, `GlBackendBuilder`, and `WgpuBackendBuilder` for the impls.
@@ -38,6 +41,36 @@ pub trait PresenterBuilder: Sized {
     #[must_use]
     fn font(self, fonts: impl Into<FontChain<'static>>) -> Self;
 
+    /// Sets the palette colors resolve through.
+    ///
+    /// The default ignores `palette` and keeps the backend's own colors, so a builder written
+    /// before palettes were configurable still implements this trait. Every builder in this
+    /// workspace overrides it.
+    #[must_use]
+    fn palette(self, _palette: Palette) -> Self {
+        self
+    }
+
+    /// Sets the CRT post-processing effects applied to every presented frame.
+    ///
+    /// The default ignores `effects` and presents frames unprocessed, so a builder written before
+    /// CRT effects existed still implements this trait. Every builder in this workspace overrides
+    /// it.
+    #[must_use]
+    fn crt(self, _effects: CrtEffects) -> Self {
+        self
+    }
+
+    /// Sets how the frame is fitted into the window when the window is resized.
+    ///
+    /// The default ignores `mode` and keeps reflowing the grid to the window
+    /// ([`ScalingMode::Reflow`]), so a builder written before scaling modes existed still
+    /// implements this trait. Every builder in this workspace overrides it.
+    #[must_use]
+    fn scaling(self, _mode: ScalingMode) -> Self {
+        self
+    }
+
     /// Registers a PNG sprite tileset. Available only with the `tilesets` feature.
     #[cfg(feature = "tilesets")]
     #[must_use]
@@ -0,0 +1,216 @@
+//! How a windowed backend fits its frame into a window whose size doesn't match it.
+//!
+//! A presenter renders its grid into a frame of exactly `cols * cell_w` x `rows * cell_h` pixels
+//! (see [`CellGeometry::surface_size`](crate::geometry::CellGeometry::surface_size)). When the
+//! window is resized, [`ScalingMode`](crate::scaling::ScalingMode) decides what gives: under
+//! [`Reflow`](crate::scaling::ScalingMode::Reflow) the grid follows the window, and under every
+//! other mode the grid keeps its size and the frame is scaled into a
+//! [`Viewport`](crate::geometry::Viewport) instead. `retroglyph-software`, `retroglyph-gl`, and
+//! `retroglyph-wgpu` all place the frame through
+//! [`ScalingMode::viewport`](crate::scaling::ScalingMode::viewport), and return that viewport from
+//! [`Presenter::geometry`](crate::presenter::Presenter::geometry) so
+//! [`CellGeometry::pixel_to_cell`](crate::geometry::CellGeometry::pixel_to_cell) maps the mouse
+//! back through it.
+
+use crate::geometry::Viewport;
+
+/// How a scaled frame is resampled. Only the GPU backends filter: `retroglyph-software` always
+/// scales nearest-neighbor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScaleFilter {
+    /// Each window pixel shows the nearest frame pixel: hard edges, uneven pixel widths at a
+    /// non-integer scale.
+    #[default]
+    Nearest,
+    /// Each window pixel blends the four nearest frame pixels: even, but soft.
+    Linear,
+}
+
+/// How a windowed backend fits its frame into the window.
+///
+/// Set with the backend builder's `scaling` method
+/// ([`PresenterBuilder::scaling`](crate::presenter_builder::PresenterBuilder::scaling)). Bars are
+/// `(r, g, b)`, like [`Palette`](crate::palette::Palette)'s colors.
+///
+/// # Examples
+///
+/// ```
+/// use retroglyph_window::geometry::Viewport;
+/// use retroglyph_window::scaling::ScalingMode;
+///
+/// // A 320x200 frame in a 1000x700 window: tripled, then centered.
+/// let mode = ScalingMode::Integer { bars: (0, 0, 0) };
+/// assert_eq!(
+///     mode.viewport((320, 200), (1000, 700)),
+///     Viewport { frame: (320, 200), origin: (20, 50), size: (960, 600) },
+/// );
+/// assert!(!mode.reflows());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScalingMode {
+    /// Resize the grid to as many whole cells as fit the window, and show the frame unscaled.
+    /// A sub-cell remainder is left as an unpainted strip at the right and bottom edges.
+    #[default]
+    Reflow,
+    /// Keep the grid size and scale the frame by the largest whole factor that fits (at least
+    /// `1`), centered between bars of color `bars`.
+    Integer {
+        /// The letterbox bars' color.
+        bars: (u8, u8, u8),
+    },
+    /// Keep the grid size and scale the frame as large as fits without distorting it, centered
+    /// between bars of color `bars`.
+    Fit {
+        /// How the scaled frame is resampled.
+        filter: ScaleFilter,
+        /// The letterbox bars' color.
+        bars: (u8, u8, u8),
+    },
+    /// Keep the grid size and scale the frame to cover the whole window, distorting it.
+    Stretch {
+        /// How the scaled frame is resampled.
+        filter: ScaleFilter,
+    },
+}
+
+impl ScalingMode {
+    /// Whether the grid is resized to follow the window, as opposed to the frame being scaled.
+    #[must_use]
+    pub const fn reflows(&self) -> bool {
+        matches!(self, Self::Reflow)
+    }
+
+    /// The color the window outside the viewport is cleared to. Black for the modes without bars,
+    /// which leave nothing of the window outside it uncovered.
+    #[must_use]
+    pub const fn bars(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Integer { bars } | Self::Fit { bars, .. } => *bars,
+            Self::Reflow | Self::Stretch { .. } => (0, 0, 0),
+        }
+    }
+
+    /// How the frame is resampled. [`ScaleFilter::Nearest`] for the modes that never scale by a
+    /// fraction.
+    #[must_use]
+    pub const fn filter(&self) -> ScaleFilter {
+        match self {
+            Self::Fit { filter, .. } | Self::Stretch { filter } => *filter,
+            Self::Reflow | Self::Integer { .. } => ScaleFilter::Nearest,
+        }
+    }
+
+    /// Where a `frame`-sized frame is shown in a `window`-sized window, both in physical pixels.
+    ///
+    /// [`Reflow`](Self::Reflow) and an empty frame or window always give
+    /// [`Viewport::unscaled`]. The scaling modes never shrink a viewport below one pixel on
+    /// either axis.
+    #[must_use]
+    pub fn viewport(&self, frame: (u32, u32), window: (u32, u32)) -> Viewport {
+        let (frame_w, frame_h) = frame;
+        let (window_w, window_h) = window;
+        if frame_w == 0 || frame_h == 0 || window_w == 0 || window_h == 0 {
+            return Viewport::unscaled(frame);
+        }
+        let size = match self {
+            Self::Reflow => return Viewport::unscaled(frame),
+            Self::Integer { .. } => {
+                let factor = (window_w / frame_w).min(window_h / frame_h).max(1);
+                (frame_w * factor, frame_h * factor)
+            }
+            Self::Fit { .. } => {
+                let factor = (f64::from(window_w) / f64::from(frame_w))
+                    .min(f64::from(window_h) / f64::from(frame_h));
+                (
+                    scaled_axis(frame_w, factor, window_w),
+                    scaled_axis(frame_h, factor, window_h),
+                )
+            }
+            Self::Stretch { .. } => window,
+        };
+        Viewport {
+            frame,
+            origin: (
+                window_w.saturating_sub(size.0) / 2,
+                window_h.saturating_sub(size.1) / 2,
+            ),
+            size,
+        }
+    }
+}
+
+/// `frame * factor`, rounded and kept within `[1, window]`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn scaled_axis(frame: u32, factor: f64, window: u32) -> u32 {
+    // The clamp keeps the cast in range: `window` is itself a `u32`.
+    (f64::from(frame) * factor)
+        .round()
+        .clamp(1.0, f64::from(window)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScaleFilter, ScalingMode};
+    use crate::geometry::Viewport;
+
+    const BLACK: (u8, u8, u8) = (0, 0, 0);
+
+    #[test]
+    fn reflow_shows_the_frame_unscaled() {
+        assert_eq!(
+            ScalingMode::Reflow.viewport((320, 200), (1000, 700)),
+            Viewport::unscaled((320, 200))
+        );
+    }
+
+    #[test]
+    fn integer_never_scales_below_one() {
+        // The window is smaller than the frame: shown 1:1 from the top-left, cropped.
+        let viewport = ScalingMode::Integer { bars: BLACK }.viewport((320, 200), (300, 150));
+        assert_eq!(viewport, Viewport::unscaled((320, 200)));
+    }
+
+    #[test]
+    fn fit_fills_the_limiting_axis_and_centers_the_other() {
+        let mode = ScalingMode::Fit {
+            filter: ScaleFilter::Linear,
+            bars: BLACK,
+        };
+        // 2.5x is the most the 500-pixel-tall window allows.
+        assert_eq!(
+            mode.viewport((320, 200), (1000, 500)),
+            Viewport {
+                frame: (320, 200),
+                origin: (100, 0),
+                size: (800, 500),
+            }
+        );
+    }
+
+    #[test]
+    fn stretch_covers_the_window() {
+        let mode = ScalingMode::Stretch {
+            filter: ScaleFilter::Nearest,
+        };
+        assert_eq!(
+            mode.viewport((320, 200), (1000, 500)),
+            Viewport {
+                frame: (320, 200),
+                origin: (0, 0),
+                size: (1000, 500),
+            }
+        );
+    }
+
+    #[test]
+    fn an_empty_window_leaves_the_frame_unscaled() {
+        let mode = ScalingMode::Stretch {
+            filter: ScaleFilter::Nearest,
+        };
+        assert_eq!(
+            mode.viewport((320, 200), (0, 0)),
+            Viewport::unscaled((320, 200))
+        );
+    }
+}
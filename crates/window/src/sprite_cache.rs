@@ -3,14 +3,18 @@
 //! The [`SpriteCache`] is built from [`TilesetOptions`]
 //! and provides O(1) lookup of decoded RGBA8 sprites by codepoint.
 
+use crate::animation::SpriteAnimations;
+use crate::palette::Palette;
 use crate::tileset::{SheetColor, SpriteAlign, TilesetError, TilesetOptions};
 // Only used by the source_over tests below (retroglyph#547): production code no longer has its
 // own source_over to exercise this type through, now that it delegates to the inherent
 // U8x4Rgba::source_over directly at its real call sites.
 #[cfg(test)]
 use alpha_blend::rgba::U8x4Rgba;
-use retroglyph_core::color::{Color, Tint};
+use core::time::Duration;
+use retroglyph_core::color::{Color, Remap, Tint};
 use retroglyph_core::dev_only;
+use retroglyph_core::tile::Transform;
 use std::collections::{BTreeMap, BTreeSet};
 
 /// A decoded, ready-to-blit sprite.
@@ -63,6 +67,22 @@ impl Sprite {
             glyph_h,
         )
     }
+
+    /// [`align_offset`](Self::align_offset) for this sprite drawn under `transform`: the box is
+    /// fitted around the flipped, turned, and scaled artwork rather than the sheet's.
+    #[must_use]
+    pub const fn transformed_align_offset(
+        &self,
+        transform: Transform,
+        span_w: u16,
+        span_h: u16,
+        glyph_w: u16,
+        glyph_h: u16,
+    ) -> (i16, i16) {
+        let (w, h) = transform.size(self.pixel_width, self.pixel_height);
+        self.align
+            .offset_in_span(w, h, span_w, span_h, glyph_w, glyph_h)
+    }
 }
 
 /// Cache of decoded sprites, keyed by Unicode codepoint.
@@ -82,6 +102,7 @@ impl Sprite {
 #[derive(Debug)]
 pub struct SpriteCache {
     sprites: BTreeMap<char, Sprite>,
+    animations: SpriteAnimations,
 }
 
 impl SpriteCache {
@@ -90,15 +111,33 @@ impl SpriteCache {
     pub const fn new() -> Self {
         Self {
             sprites: BTreeMap::new(),
+            animations: SpriteAnimations::new(),
         }
     }
 
     /// Returns the sprite for `ch`, if registered.
+    ///
+    /// Ignores animations: this is the sprite stored under `ch` itself. A renderer drawing a cell
+    /// uses [`get_at`](Self::get_at) instead.
     #[must_use]
     pub fn get(&self, ch: char) -> Option<&Sprite> {
         self.sprites.get(&ch)
     }
 
+    /// Returns the sprite a cell holding `ch` shows at animation time `time`: the current frame's
+    /// if `ch` is animated, else [`get`](Self::get)'s.
+    #[must_use]
+    pub fn get_at(&self, ch: char, time: Duration) -> Option<&Sprite> {
+        self.sprites.get(&self.animations.glyph_at(ch, time))
+    }
+
+    /// The animated codepoints every loaded tileset declared, with their frames resolved to the
+    /// codepoints their sprites are stored under.
+    #[must_use]
+    pub const fn animations(&self) -> &SpriteAnimations {
+        &self.animations
+    }
+
     /// Iterates every registered `(codepoint, sprite)` in codepoint order.
     ///
     /// Used by GPU backends to build a sprite atlas from the whole decoded set (the software
@@ -108,7 +147,8 @@ impl SpriteCache {
         self.sprites.iter().map(|(&ch, sprite)| (ch, sprite))
     }
 
-    /// Whether any sprite is registered.
+    /// Whether any sprite is registered. A cache with animations always has sprites: each frame is
+    /// one.
     #[must_use]
     pub fn is_empty(&self) -> bool {
         self.sprites.is_empty()
@@ -132,7 +172,10 @@ impl SpriteCache {
         Ok(cache)
     }
 
-    /// Loads a tileset, decoding the sprite sheet and inserting all sprites.
+    /// Loads a tileset, decoding the sprite sheet and inserting all sprites and animations.
+    ///
+    /// Each animation frame's tile is resolved through the tileset's codepage to the codepoint its
+    /// sprite is stored under, so the frames draw through the same lookup as any other sprite.
     ///
     /// On codepoint collision, the new sprite replaces the old one and a
     /// message is logged via `log::warn`. Unlike [`warn_sprite_needs_span`] and
@@ -150,7 +193,10 @@ impl SpriteCache {
     /// is 0, [`TilesetError::InvalidDimensions`] if the decoded image
     /// dimensions are not evenly divisible by the tile size, or
     /// [`TilesetError::TooManyColumns`] if `opts.columns` declares more columns
-    /// than the image actually has at `opts.tile_width`.
+    /// than the image actually has at `opts.tile_width`, and
+    /// [`TilesetError::AnimationFrameOutOfRange`] if an animation frame names a tile past the end
+    /// of the sheet or one the codepage leaves unmapped. Nothing is inserted when any of these
+    /// fail.
     #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
     pub fn load(&mut self, opts: &TilesetOptions) -> Result<(), TilesetError> {
         let img = image::load_from_memory(&opts.bytes)
@@ -185,6 +231,21 @@ impl SpriteCache {
         let rows = img_h / tile_h;
         let total_tiles = (columns * rows) as usize;
 
+        let mut animations = Vec::with_capacity(opts.animations.len());
+        for (ch, animation) in &opts.animations {
+            let glyphs = animation
+                .frames()
+                .iter()
+                .map(|frame| {
+                    (frame.tile < total_tiles)
+                        .then(|| opts.codepage.codepoint(frame.tile))
+                        .flatten()
+                        .ok_or(TilesetError::AnimationFrameOutOfRange(*ch, frame.tile))
+                })
+                .collect::<Result<Vec<char>, _>>()?;
+            animations.push((*ch, animation.clone(), glyphs));
+        }
+
         let raw = img.as_raw();
 
         for tile_idx in 0..total_tiles {
@@ -207,7 +268,7 @@ impl SpriteCache {
                     .copy_from_slice(&raw[src_start..src_start + (tile_w as usize * 4)]);
             }
 
-            // Apply transparent colour key if set.
+            // Apply transparent color key if set.
             if let Some((kr, kg, kb)) = opts.transparent_color {
                 for px in pixels.chunks_exact_mut(4) {
                     if px[0] == kr && px[1] == kg && px[2] == kb {
@@ -233,6 +294,9 @@ impl SpriteCache {
                 log::warn!("tileset codepoint collision: U+{cp:04X} '{codepoint}' overwritten");
             }
         }
+        for (ch, animation, glyphs) in animations {
+            self.animations.insert(ch, animation, glyphs);
+        }
         Ok(())
     }
 }
@@ -243,39 +307,48 @@ impl Default for SpriteCache {
     }
 }
 
-/// The complete recolouring one sprite goes through in one cell: the sheet's own treatment,
-/// then the cell's tint.
+/// The complete recoloring one sprite goes through in one cell: the cell's palette swap or, for
+/// a pixel it leaves alone, the sheet's own treatment; then the cell's tint.
 ///
 /// Two stages rather than one because they do not always fold together. A [`SheetColor::Mask`]
 /// sheet is a multiply by the cell's foreground, and a multiply composes with another multiply,
-/// but not with a [`Tint::Mix`]: "colour this mask red, then flash it half-way to white" is two
+/// but not with a [`Tint::Mix`]: "color this mask red, then flash it half-way to white" is two
 /// operations and cannot be written as one.
 ///
-/// Both pixel backends resolve through here, so a sprite recoloured on the software rasteriser
-/// and the same sprite recoloured in the GL fragment shader cannot disagree. The GL side uploads
-/// the two stages as instance attributes and mirrors [`apply`](Self::apply)'s order.
+/// A [`Remap`] sits in front of both because it is keyed on the artwork's own colors, so it has
+/// to see them before anything else changes them. A pixel it maps has been told its color
+/// outright, which is why the sheet's multiply is skipped for that pixel: a ramp over a
+/// [`SheetColor::Mask`] sheet paints the ramp's shades, not the shades darkened by `fg`. The tint
+/// still applies after, so a palette-swapped sprite flashes like any other.
+///
+/// Both pixel backends resolve through here, so a sprite recolored on the software rasteriser
+/// and the same sprite recolored in the GL fragment shader cannot disagree. The GL side uploads
+/// the stages as instance attributes and mirrors [`apply`](Self::apply)'s order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct SpriteTint {
-    /// The sheet's own treatment, applied first.
+    /// The sheet's own treatment, applied first to every pixel [`remap`](Self::remap) leaves alone.
     ///
-    /// [`Tint::Multiply`] by the cell's resolved foreground colour for a [`SheetColor::Mask`]
+    /// [`Tint::Multiply`] by the cell's resolved foreground color for a [`SheetColor::Mask`]
     /// sheet, [`Tint::None`] for [`SheetColor::Art`].
     pub mask: Tint,
     /// The cell's own tint, applied second.
     pub tint: Tint,
+    /// The cell's palette swap, looked up first. A pixel it maps skips [`mask`](Self::mask).
+    pub remap: Remap,
 }
 
 impl SpriteTint {
-    /// Resolves what a sprite from a `sheet`-coloured tileset should look like in a cell with
+    /// Resolves what a sprite from a `sheet`-colored tileset should look like in a cell with
     /// foreground `fg` and tint `tint`.
     ///
     /// Takes the sheet's declaration rather than the [`Sprite`] itself, because that is all the
     /// answer depends on: the GPU backend resolves against an atlas slot and never holds the
     /// pixels at draw time.
     ///
-    /// `default_fg` is the palette fallback for [`Color::Default`], which has no reading as a
+    /// `default_fg` is the fallback for [`Color::Default`], which has no reading as a
     /// modulation value on its own (see [`Tint`]); [`palette::DEFAULT_FG`](crate::palette) is
-    /// what both backends pass.
+    /// the built-in one. A backend with a configurable [`Palette`] resolves through
+    /// [`resolve_with_palette`](Self::resolve_with_palette) instead.
     #[must_use]
     pub const fn resolve(
         sheet: SheetColor,
@@ -283,27 +356,58 @@ impl SpriteTint {
         tint: Tint,
         default_fg: (u8, u8, u8),
     ) -> Self {
+        Self::with_foreground(sheet, fg.resolve_rgb(default_fg), tint)
+    }
+
+    /// [`resolve`](Self::resolve), with `fg` resolved through `palette`, the same as a glyph
+    /// drawn in that cell would be, including its foreground fallback for [`Color::Default`].
+    #[must_use]
+    pub fn resolve_with_palette(
+        sheet: SheetColor,
+        fg: Color,
+        tint: Tint,
+        palette: &Palette,
+    ) -> Self {
+        Self::with_foreground(sheet, palette.foreground_rgb(fg), tint)
+    }
+
+    /// The recoloring for a `sheet`-colored sprite in a cell whose foreground is `fg`.
+    const fn with_foreground(sheet: SheetColor, fg: (u8, u8, u8), tint: Tint) -> Self {
         let mask = match sheet {
             SheetColor::Art => Tint::None,
-            SheetColor::Mask => {
-                let (r, g, b) = fg.resolve_rgb(default_fg);
-                Tint::multiply(r, g, b)
-            }
+            SheetColor::Mask => Tint::multiply(fg.0, fg.1, fg.2),
         };
-        Self { mask, tint }
+        Self {
+            mask,
+            tint,
+            remap: Remap::NONE,
+        }
+    }
+
+    /// This recoloring with `remap` looked up ahead of the other stages; `None` is
+    /// [`Remap::NONE`], matching [`DrawCell::remap`](retroglyph_core::backend::DrawCell::remap).
+    #[must_use]
+    pub fn with_remap(mut self, remap: Option<&Remap>) -> Self {
+        self.remap = remap.copied().unwrap_or_default();
+        self
     }
 
     /// Whether this leaves every pixel exactly as authored, so a renderer can take its untinted
     /// path.
     #[must_use]
     pub const fn is_identity(&self) -> bool {
-        self.mask.is_identity() && self.tint.is_identity()
+        self.mask.is_identity() && self.tint.is_identity() && self.remap.is_identity()
     }
 
-    /// Applies both stages to one straight-alpha RGB triple, sheet treatment first.
+    /// Applies every stage to one straight-alpha RGB triple: the remap, or the sheet treatment
+    /// for a pixel the remap leaves alone, then the tint.
     #[must_use]
     pub const fn apply(&self, rgb: (u8, u8, u8)) -> (u8, u8, u8) {
-        self.tint.apply(self.mask.apply(rgb))
+        let rgb = match self.remap.lookup(rgb) {
+            Some(mapped) => mapped,
+            None => self.mask.apply(rgb),
+        };
+        self.tint.apply(rgb)
     }
 }
 
@@ -351,8 +455,8 @@ pub fn warn_sprite_needs_span(
 /// Warns, at most once per glyph, that `glyph` carries a tint but resolved to a bitmap font
 /// glyph rather than a sprite, so the tint was silently dropped.
 ///
-/// This is #537's exact trap: a font glyph is `fg`-coloured, so a cell that falls back to one
-/// still visibly changes colour when a tint is set, and it is easy to conclude the tint took
+/// This is #537's exact trap: a font glyph is `fg`-colored, so a cell that falls back to one
+/// still visibly changes color when a tint is set, and it is easy to conclude the tint took
 /// effect when in fact nothing read it. Both pixel backends call this from the branch that
 /// already knows the sprite cache missed for this glyph, so the diagnostic and the fix it names
 /// are identical on each.
@@ -389,6 +493,7 @@ pub fn warn_tint_needs_sprite(seen: &mut BTreeSet<char>, glyph: char, tint: Tint
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::animation::SpriteAnimation;
     use crate::tileset::{Codepage, SpriteAlign, TilesetOptions};
     use image::ImageEncoder;
 
@@ -556,6 +661,51 @@ mod tests {
         assert!(cache.get('C').is_none()); // tile index 2 unmapped
     }
 
+    #[test]
+    fn sprite_cache_get_at_follows_the_animation_frame() {
+        let png = make_test_png(8, 8, 3, 1); // tiles 0..3 -> 'A', 'B', 'C'
+        let ms = Duration::from_millis;
+        let opts = TilesetOptions::builder(png)
+            .tile_size(8, 8)
+            .start_codepoint('A')
+            .animate(
+                'A',
+                SpriteAnimation::new().frame(1, ms(100)).frame(2, ms(100)),
+            )
+            .build()
+            .unwrap();
+        let mut cache = SpriteCache::new();
+        cache.load(&opts).unwrap();
+        // Tile 1 is red 20, tile 2 red 40 (see `make_test_png`).
+        assert_eq!(cache.get_at('A', ms(0)).unwrap().pixels[0], 20);
+        assert_eq!(cache.get_at('A', ms(150)).unwrap().pixels[0], 40);
+        assert_eq!(cache.get_at('A', ms(200)).unwrap().pixels[0], 20);
+        // The still sprite under the animated codepoint is untouched, and others don't animate.
+        assert_eq!(cache.get('A').unwrap().pixels[0], 0);
+        assert_eq!(cache.get_at('C', ms(150)).unwrap().pixels[0], 40);
+        assert!(cache.animations().contains('A'));
+    }
+
+    #[test]
+    fn sprite_cache_rejects_an_animation_frame_past_the_sheet() {
+        let png = make_test_png(8, 8, 2, 1);
+        let opts = TilesetOptions::builder(png)
+            .tile_size(8, 8)
+            .start_codepoint('A')
+            .animate(
+                'A',
+                SpriteAnimation::uniform([0, 2], Duration::from_millis(50)),
+            )
+            .build()
+            .unwrap();
+        let mut cache = SpriteCache::new();
+        assert!(matches!(
+            cache.load(&opts),
+            Err(TilesetError::AnimationFrameOutOfRange('A', 2))
+        ));
+        assert!(cache.is_empty(), "a failed load inserts nothing");
+    }
+
     // ── Alignment inside a span's cell box ─────────────────────────────
 
     /// Loads a single-tile `tile_w` x `tile_h` sheet mapped to `'A'` with the given alignment.
@@ -650,24 +800,24 @@ mod tests {
         }
     }
 
-    const DEFAULT_FG: (u8, u8, u8) = (0xD4, 0xD4, 0xD4);
-
     #[test]
     fn art_sheet_ignores_fg_entirely() {
         let art = sprite_with(SheetColor::Art);
-        let resolved = SpriteTint::resolve(art.color, Color::RED, Tint::None, DEFAULT_FG);
+        let resolved =
+            SpriteTint::resolve_with_palette(art.color, Color::RED, Tint::None, &Palette::DEFAULT);
 
         assert_eq!(resolved.mask, Tint::None);
         assert!(resolved.is_identity());
-        // The whole point of #537: a full-colour sheet renders as authored, whatever fg says.
+        // The whole point of #537: a full-color sheet renders as authored, whatever fg says.
         assert_eq!(resolved.apply((10, 200, 30)), (10, 200, 30));
     }
 
     #[test]
-    fn mask_sheet_takes_its_colour_from_fg() {
+    fn mask_sheet_takes_its_color_from_fg() {
         let mask = sprite_with(SheetColor::Mask);
-        let (r, g, b) = Color::RED.resolve_rgb(DEFAULT_FG);
-        let resolved = SpriteTint::resolve(mask.color, Color::RED, Tint::None, DEFAULT_FG);
+        let (r, g, b) = Color::RED.resolve_rgb((0, 0, 0));
+        let resolved =
+            SpriteTint::resolve_with_palette(mask.color, Color::RED, Tint::None, &Palette::DEFAULT);
 
         assert_eq!(resolved.mask, Tint::multiply(r, g, b));
         // A white mask pixel takes the foreground exactly.
@@ -675,12 +825,16 @@ mod tests {
     }
 
     #[test]
-    fn mask_sheet_shades_a_grey_pixel_proportionally() {
+    fn mask_sheet_shades_a_gray_pixel_proportionally() {
         let mask = sprite_with(SheetColor::Mask);
-        let resolved =
-            SpriteTint::resolve(mask.color, Color::rgb(200, 100, 50), Tint::None, DEFAULT_FG);
+        let resolved = SpriteTint::resolve_with_palette(
+            mask.color,
+            Color::rgb(200, 100, 50),
+            Tint::None,
+            &Palette::DEFAULT,
+        );
 
-        // Half-grey artwork lands on a proportionally darker shade of the foreground, which is
+        // Half-gray artwork lands on a proportionally darker shade of the foreground, which is
         // how a libtcod/Dwarf Fortress style tileset is authored.
         let (r, _, _) = resolved.apply((128, 128, 128));
         assert!(r > 0 && r < 200, "expected a shade of the fg, got {r}");
@@ -689,21 +843,55 @@ mod tests {
     #[test]
     fn mask_sheet_resolves_default_fg_through_the_palette() {
         let mask = sprite_with(SheetColor::Mask);
-        let resolved = SpriteTint::resolve(mask.color, Color::Default, Tint::None, DEFAULT_FG);
+        let resolved = SpriteTint::resolve_with_palette(
+            mask.color,
+            Color::Default,
+            Tint::None,
+            &Palette::DEFAULT,
+        );
 
         // `Color::Default` has no reading as a modulation value on its own, so it goes through
         // the palette rather than being treated as white.
         assert_eq!(resolved.mask, Tint::multiply(0xD4, 0xD4, 0xD4));
     }
 
+    #[test]
+    fn resolve_falls_back_to_a_plain_default_fg() {
+        const RESOLVED: SpriteTint = SpriteTint::resolve(
+            SheetColor::Mask,
+            Color::Default,
+            Tint::None,
+            crate::palette::DEFAULT_FG,
+        );
+        assert_eq!(
+            RESOLVED,
+            SpriteTint::resolve_with_palette(
+                SheetColor::Mask,
+                Color::Default,
+                Tint::None,
+                &Palette::DEFAULT
+            )
+        );
+    }
+
+    #[test]
+    fn mask_sheet_resolves_an_ansi_fg_through_the_palette() {
+        let mask = sprite_with(SheetColor::Mask);
+        let palette = Palette::CGA;
+        let resolved =
+            SpriteTint::resolve_with_palette(mask.color, Color::YELLOW, Tint::None, &palette);
+
+        assert_eq!(resolved.mask, Tint::multiply(0xAA, 0x55, 0x00));
+    }
+
     #[test]
     fn the_cell_tint_applies_on_top_of_an_art_sheet() {
         let art = sprite_with(SheetColor::Art);
-        let resolved = SpriteTint::resolve(
+        let resolved = SpriteTint::resolve_with_palette(
             art.color,
             Color::RED,
             Tint::multiply(128, 128, 128),
-            DEFAULT_FG,
+            &Palette::DEFAULT,
         );
 
         assert!(!resolved.is_identity());
@@ -714,23 +902,78 @@ mod tests {
     fn both_stages_apply_in_order_on_a_mask_sheet() {
         let mask = sprite_with(SheetColor::Mask);
         let flash = Tint::mix(255, 255, 255, 255);
-        let resolved = SpriteTint::resolve(mask.color, Color::rgb(255, 0, 0), flash, DEFAULT_FG);
+        let resolved = SpriteTint::resolve_with_palette(
+            mask.color,
+            Color::rgb(255, 0, 0),
+            flash,
+            &Palette::DEFAULT,
+        );
 
         // Mask first would give red; the flash then takes it all the way to white. The other
         // order would give red, which is why the order is part of the contract.
         assert_eq!(resolved.apply((255, 255, 255)), (255, 255, 255));
     }
 
+    #[test]
+    fn a_ramp_over_a_mask_sheet_paints_its_shades_instead_of_fg() {
+        let mask = sprite_with(SheetColor::Mask);
+        let ramp = Remap::ramp(&[(10, 20, 30), (200, 100, 0)]);
+        let resolved =
+            SpriteTint::resolve_with_palette(mask.color, Color::RED, Tint::None, &Palette::DEFAULT)
+                .with_remap(Some(&ramp));
+
+        // The mapped pixel skips the multiply by red, which would have zeroed its blue channel.
+        assert_eq!(resolved.apply((0, 0, 0)), (10, 20, 30));
+        assert_eq!(resolved.apply((255, 255, 255)), (200, 100, 0));
+    }
+
+    #[test]
+    fn a_color_swap_leaves_unmatched_pixels_to_the_other_stages() {
+        let art = sprite_with(SheetColor::Art);
+        let team = Remap::colors(&[((255, 0, 0), (0, 0, 255))]);
+        let flash = Tint::mix(255, 255, 255, 255);
+        let resolved = SpriteTint::resolve_with_palette(
+            art.color,
+            Color::Default,
+            Tint::None,
+            &Palette::DEFAULT,
+        )
+        .with_remap(Some(&team));
+
+        assert!(!resolved.is_identity());
+        assert_eq!(resolved.apply((255, 0, 0)), (0, 0, 255));
+        assert_eq!(resolved.apply((0, 255, 0)), (0, 255, 0));
+        // The tint applies after the swap, to mapped and unmapped pixels alike.
+        let flashed = SpriteTint {
+            tint: flash,
+            ..resolved
+        };
+        assert_eq!(flashed.apply((255, 0, 0)), (255, 255, 255));
+        assert!(SpriteTint::default().with_remap(None).is_identity());
+    }
+
     #[test]
     fn an_untouched_art_cell_is_identity_so_renderers_can_skip_the_work() {
         let art = sprite_with(SheetColor::Art);
         assert!(
-            SpriteTint::resolve(art.color, Color::Default, Tint::None, DEFAULT_FG).is_identity()
+            SpriteTint::resolve_with_palette(
+                art.color,
+                Color::Default,
+                Tint::None,
+                &Palette::DEFAULT
+            )
+            .is_identity()
         );
-        // A mask sheet is never identity: its colour always comes from somewhere.
+        // A mask sheet is never identity: its color always comes from somewhere.
         let mask = sprite_with(SheetColor::Mask);
         assert!(
-            !SpriteTint::resolve(mask.color, Color::Default, Tint::None, DEFAULT_FG).is_identity()
+            !SpriteTint::resolve_with_palette(
+                mask.color,
+                Color::Default,
+                Tint::None,
+                &Palette::DEFAULT
+            )
+            .is_identity()
         );
     }
 
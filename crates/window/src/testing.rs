@@ -13,17 +13,18 @@
 //! whatever repertoire a consumer's own UI actually draws, without hand-rolling a
 //! resolve-and-check loop or scanning source text for character literals.
 
-use crate::font::FontChain;
+use crate::font::{FontChain, WideHalf};
 
-/// Whether `ch` renders as its own glyph in `chain`: covered by some font in the chain, not the
-/// substituted notdef fallback.
+/// Whether `ch` renders as its own glyph in `chain`: covered by some font in the chain (a
+/// double-width font included), not the substituted notdef fallback.
 ///
 /// A character no font in `chain` can draw at all (not even the fallback -- see
 /// [`FontChain::resolve`](crate::font::FontChain::resolve)) is also "not covered": it draws
 /// nothing, which is no more `ch`'s own glyph than the solid block is.
 #[must_use]
 pub fn is_glyph_covered(chain: &FontChain<'_>, ch: char) -> bool {
-    chain.resolve(ch).is_some_and(|glyph| !glyph.is_notdef())
+    chain.resolve_wide(ch, WideHalf::Left).is_some()
+        || chain.resolve(ch).is_some_and(|glyph| !glyph.is_notdef())
 }
 
 /// Every character in `chars` that does not render as itself in `chain`, in iteration order,
@@ -53,6 +54,113 @@ pub fn assert_glyphs_covered(chain: &FontChain<'_>, chars: impl IntoIterator<Ite
     );
 }
 
+/// A minimal TrueType font whose glyphs are axis-aligned rectangles, for testing the `truetype`
+/// loaders and the antialiased draw paths without shipping (or depending on the host having) a
+/// real font file.
+///
+/// Each `(ch, [x_min, y_min, x_max, y_max])` maps `ch` to one filled rectangle in font units. The
+/// font is 1000 units from ascent (800) to descent (-200) with no line gap, and every glyph
+/// advances 500 units: at [`BitmapFont::from_truetype`](crate::font::BitmapFont::from_truetype)
+/// `(bytes, 10.0)` that is a 5x10 cell with the baseline 8 rows down and one unit a hundredth of
+/// a pixel, so a rectangle edge that falls mid-pixel gives that pixel partial coverage.
+///
+/// # Panics
+///
+/// Panics if `glyphs` is too long for the font's 16-bit offsets (a few thousand rectangles).
+#[cfg(feature = "truetype")]
+#[must_use]
+pub fn truetype_rect_font(glyphs: &[(char, [i16; 4])]) -> Vec<u8> {
+    fn be16(out: &mut Vec<u8>, values: &[i32]) {
+        for &v in values {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            out.extend_from_slice(&(v as u16).to_be_bytes());
+        }
+    }
+
+    let mut sorted = glyphs.to_vec();
+    sorted.sort_by_key(|&(ch, _)| ch);
+    // Glyph 0 is the empty `.notdef`; glyph `i + 1` is `sorted[i]`.
+    let num_glyphs = i32::try_from(sorted.len() + 1).expect("too many glyphs");
+
+    // `glyf`: one contour of four on-curve points per rectangle, clockwise, coordinates as
+    // 16-bit deltas (flag 0x01 alone). 34 bytes each, so `loca` can use the short format.
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    be16(&mut loca, &[0, 0]);
+    for &(_, [x0, y0, x1, y1]) in &sorted {
+        let (x0, y0, x1, y1) = (i32::from(x0), i32::from(y0), i32::from(x1), i32::from(y1));
+        be16(&mut glyf, &[1, x0, y0, x1, y1, 3, 0]);
+        glyf.extend_from_slice(&[1; 4]);
+        be16(&mut glyf, &[x0, 0, x1 - x0, 0, y0, y1 - y0, 0, y0 - y1]);
+        be16(
+            &mut loca,
+            &[i32::try_from(glyf.len() / 2).expect("glyf too long")],
+        );
+    }
+
+    // `cmap`: a single format 12 (Unicode full repertoire) subtable, one group per char.
+    let mut cmap = Vec::new();
+    be16(&mut cmap, &[0, 1, 3, 10]);
+    cmap.extend_from_slice(&12u32.to_be_bytes());
+    be16(&mut cmap, &[12, 0]);
+    let groups = u32::try_from(sorted.len()).expect("too many glyphs");
+    for word in [16 + 12 * groups, 0, groups] {
+        cmap.extend_from_slice(&word.to_be_bytes());
+    }
+    for (gid, &(ch, _)) in (1u32..).zip(&sorted) {
+        for word in [u32::from(ch), u32::from(ch), gid] {
+            cmap.extend_from_slice(&word.to_be_bytes());
+        }
+    }
+
+    let mut head = Vec::new();
+    be16(&mut head, &[1, 0, 1, 0, 0, 0, 0x5F0F, 0x3CF5, 0, 1000]);
+    head.extend_from_slice(&[0; 16]);
+    be16(&mut head, &[0, -200, 500, 800, 0, 8, 2, 0, 0]);
+
+    let mut hhea = Vec::new();
+    be16(
+        &mut hhea,
+        &[1, 0, 800, -200, 0, 500, 0, 0, 500, 1, 0, 0, 0, 0, 0, 0, 0],
+    );
+    be16(&mut hhea, &[num_glyphs]);
+
+    let mut hmtx = Vec::new();
+    for _ in 0..num_glyphs {
+        be16(&mut hmtx, &[500, 0]);
+    }
+
+    let mut maxp = Vec::new();
+    be16(&mut maxp, &[0, 0x5000, num_glyphs]);
+
+    // Table records must be sorted by tag; checksums are left zero, which parsers don't verify.
+    let tables: [(&[u8; 4], Vec<u8>); 7] = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+    let mut font = Vec::new();
+    be16(&mut font, &[1, 0, 7, 64, 2, 48]);
+    let mut offset = 12 + 16 * tables.len();
+    for (tag, table) in &tables {
+        font.extend_from_slice(*tag);
+        font.extend_from_slice(&0u32.to_be_bytes());
+        for word in [offset, table.len()] {
+            font.extend_from_slice(&u32::try_from(word).expect("font too long").to_be_bytes());
+        }
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in tables {
+        font.extend_from_slice(&table);
+        font.resize(font.len().next_multiple_of(4), 0);
+    }
+    font
+}
+
 #[cfg(test)]
 mod tests {
     use super::{assert_glyphs_covered, is_glyph_covered, uncovered_glyphs};
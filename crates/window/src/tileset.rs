@@ -4,8 +4,10 @@
 //! that overlay or replace [`BitmapFont`](crate::font::BitmapFont) glyphs.
 //! A tileset is a sprite sheet PNG sliced into equally sized tiles, each
 //! mapped to a Unicode codepoint via a [`Codepage`]; [`SpriteCache`](crate::sprite_cache::SpriteCache)
-//! decodes and indexes those tiles for lookup by glyph at draw time.
+//! decodes and indexes those tiles for lookup by glyph at draw time. A tileset can also animate a
+//! codepoint through several of its tiles; see [`animation`](crate::animation).
 
+use crate::animation::SpriteAnimation;
 use core::fmt;
 
 /// What a tileset's pixels mean, which decides how its sprites respond to the cell's foreground
@@ -170,6 +172,19 @@ pub enum TilesetError {
     /// [`TilesetBuilder::columns`] declared more columns than the image actually has at the given
     /// `tile_width`: honoring it would read tile pixels from past the end of the decoded buffer.
     TooManyColumns(u16, u32),
+    /// An animation metadata file is malformed on the given 1-based line. See
+    /// [`SpriteAnimation::parse_metadata`].
+    InvalidAnimation {
+        /// The line the problem was found on.
+        line: usize,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The animation declared for this codepoint has no frames.
+    EmptyAnimation(char),
+    /// A frame of the animation declared for this codepoint names a tile index the sheet doesn't
+    /// have, or one its codepage leaves unmapped.
+    AnimationFrameOutOfRange(char, usize),
 }
 
 impl fmt::Display for TilesetError {
@@ -187,6 +202,14 @@ impl fmt::Display for TilesetError {
                 f,
                 "declared {declared} columns but the image only has {actual} at this tile_width"
             ),
+            Self::InvalidAnimation { line, reason } => {
+                write!(f, "invalid animation metadata on line {line}: {reason}")
+            }
+            Self::EmptyAnimation(ch) => write!(f, "the animation for {ch:?} has no frames"),
+            Self::AnimationFrameOutOfRange(ch, tile) => write!(
+                f,
+                "the animation for {ch:?} shows tile {tile}, which the sheet or its codepage doesn't have"
+            ),
         }
     }
 }
@@ -332,6 +355,10 @@ pub struct TilesetOptions {
     /// of an alpha channel.  Equivalent to bracket-lib's `with_font_bg()`
     /// or doryen-rs's top-left-pixel key colour auto-detection.
     pub transparent_color: Option<(u8, u8, u8)>,
+    /// Animated codepoints, each cycling through tiles of this sheet. A later entry for the same
+    /// codepoint replaces an earlier one, and an animation takes precedence over any sprite stored
+    /// under its codepoint.
+    pub animations: Vec<(char, SpriteAnimation)>,
 }
 
 impl TilesetOptions {
@@ -351,7 +378,9 @@ impl TilesetOptions {
                 align: SpriteAlign::TopLeft,
                 color: SheetColor::Art,
                 transparent_color: None,
+                animations: Vec::new(),
             },
+            metadata: None,
         }
     }
 }
@@ -407,8 +436,25 @@ impl TilesetOptions {
 ///     .build()
 ///     .unwrap();
 /// ```
+///
+/// Water animated through three tiles of its sheet, a fifth of a second each:
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use retroglyph_window::animation::SpriteAnimation;
+/// use retroglyph_window::tileset::TilesetOptions;
+///
+/// let png: Vec<u8> = std::fs::read("assets/terrain.png").unwrap();
+/// let opts = TilesetOptions::builder(png)
+///     .tile_size(16, 16)
+///     .animate('~', SpriteAnimation::uniform([32, 33, 34], Duration::from_millis(200)))
+///     .build()
+///     .unwrap();
+/// ```
 pub struct TilesetBuilder {
     opts: TilesetOptions,
+    /// Animation metadata text, parsed by [`build`](Self::build).
+    metadata: Option<String>,
 }
 
 impl TilesetBuilder {
@@ -482,13 +528,35 @@ impl TilesetBuilder {
         self
     }
 
+    /// Animates `ch` through the frames of `animation`, whose tiles are indices into this sheet.
+    ///
+    /// `ch` needn't be mapped by the codepage itself: a cell holding it shows whichever frame's
+    /// sprite is current, and a later animation of the same codepoint replaces this one. See
+    /// [`animation`](crate::animation).
+    #[must_use]
+    pub fn animate(mut self, ch: char, animation: SpriteAnimation) -> Self {
+        self.opts.animations.push((ch, animation));
+        self
+    }
+
+    /// Declares animations from a metadata file's text, in the format
+    /// [`SpriteAnimation::parse_metadata`] reads. Parsed by [`build`](Self::build); its entries
+    /// follow any [`animate`](Self::animate) calls, so they win on the same codepoint.
+    #[must_use]
+    pub fn animation_metadata(mut self, text: impl Into<String>) -> Self {
+        self.metadata = Some(text.into());
+        self
+    }
+
     /// Validates and builds [`TilesetOptions`].
     ///
     /// # Errors
     ///
-    /// Returns [`TilesetError::ZeroTileSize`] if tile dimensions are 0, or
-    /// [`TilesetError::EmptyCodepage`] if `Custom` codepage is empty.
-    pub fn build(self) -> Result<TilesetOptions, TilesetError> {
+    /// Returns [`TilesetError::ZeroTileSize`] if tile dimensions are 0,
+    /// [`TilesetError::EmptyCodepage`] if `Custom` codepage is empty,
+    /// [`TilesetError::InvalidAnimation`] if the animation metadata doesn't parse, or
+    /// [`TilesetError::EmptyAnimation`] if an animation has no frames.
+    pub fn build(mut self) -> Result<TilesetOptions, TilesetError> {
         if self.opts.tile_width == 0 || self.opts.tile_height == 0 {
             return Err(TilesetError::ZeroTileSize);
         }
@@ -497,6 +565,18 @@ impl TilesetBuilder {
         {
             return Err(TilesetError::EmptyCodepage);
         }
+        if let Some(text) = &self.metadata {
+            let entries = SpriteAnimation::parse_metadata(text)?;
+            self.opts.animations.extend(entries);
+        }
+        if let Some((ch, _)) = self
+            .opts
+            .animations
+            .iter()
+            .find(|(_, animation)| animation.frames().is_empty())
+        {
+            return Err(TilesetError::EmptyAnimation(*ch));
+        }
         Ok(self.opts)
     }
 }
@@ -538,6 +618,37 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn tileset_builder_appends_metadata_animations_after_animate_calls() {
+        use crate::animation::{LoopMode, SpriteAnimation};
+        use core::time::Duration;
+
+        let opts = TilesetOptions::builder(vec![])
+            .tile_size(16, 16)
+            .animation_metadata("~ pingpong 2:50 3:50")
+            .animate(
+                '~',
+                SpriteAnimation::uniform([0, 1], Duration::from_millis(100)),
+            )
+            .build()
+            .unwrap();
+        let modes: Vec<LoopMode> = opts
+            .animations
+            .iter()
+            .map(|(_, animation)| animation.loop_mode())
+            .collect();
+        assert_eq!(modes, [LoopMode::Loop, LoopMode::PingPong]);
+    }
+
+    #[test]
+    fn tileset_builder_rejects_an_animation_without_frames() {
+        let opts = TilesetOptions::builder(vec![])
+            .tile_size(16, 16)
+            .animate('~', SpriteAnimation::new())
+            .build();
+        assert!(matches!(opts, Err(TilesetError::EmptyAnimation('~'))));
+    }
+
     #[test]
     fn tileset_builder_defaults_to_top_left_alignment() {
         let opts = TilesetOptions::builder(vec![0u8; 64])
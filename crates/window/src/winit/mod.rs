@@ -17,8 +17,9 @@ mod web;
 
 pub use error::EventLoopError;
 pub use run::{
-    EventProxy, EventProxyClosed, WindowConfig, run_app, run_app_on, run_app_with_proxy,
-    run_app_with_typed_proxy, run_windowed, run_windowed_with_proxy, run_windowed_with_typed_proxy,
+    EventProxy, EventProxyClosed, WindowConfig, run_app, run_app_on, run_app_on_dyn,
+    run_app_with_proxy, run_app_with_typed_proxy, run_windowed, run_windowed_with_proxy,
+    run_windowed_with_typed_proxy,
 };
 pub use windowed::{Windowed, WindowedLaunchError};
 
@@ -15,12 +15,13 @@ use super::translate::{
 #[cfg(target_arch = "wasm32")]
 use super::web;
 use crate::backend::WindowBackend;
+use crate::geometry::CellGeometry;
 use crate::presenter::Presenter;
-use retroglyph_core::backend::Input;
+use retroglyph_core::backend::{DynBackend, Input};
 use retroglyph_core::event::{
     Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, PhysicalPos,
 };
-use retroglyph_core::grid::HasSize;
+use retroglyph_core::grid::{HasSize, Pos};
 use retroglyph_core::terminal::Terminal;
 use std::cell::Cell;
 use std::fmt;
@@ -48,7 +49,15 @@ use winit::window::{Window, WindowId};
 /// `T` defaults to `u64` (the payload [`Event::Custom`] itself carries), so existing code
 /// naming the bare `EventProxy` type (from before this type became generic) keeps compiling
 /// unchanged.
-pub struct EventProxy<T: Send + 'static = u64>(winit::event_loop::EventLoopProxy<T>);
+pub struct EventProxy<T: Send + 'static = u64>(winit::event_loop::EventLoopProxy<LoopMessage<T>>);
+
+/// What the event loop's user-event channel carries: an [`EventProxy`] payload, or a bare wake-up
+/// from an [`EventSender`](retroglyph_core::backend::EventSender) whose event is already queued
+/// on the [`WindowBackend`] and only needs a frame to be noticed.
+enum LoopMessage<T> {
+    User(T),
+    Wake,
+}
 
 // Hand-written rather than `#[derive(Clone, Debug)]`: a derive would add `T: Clone`/`T: Debug`
 // bounds to the impl, but `winit::event_loop::EventLoopProxy<T>` itself needs neither: cloning
@@ -81,8 +90,11 @@ impl<T: Send + 'static> EventProxy<T> {
     /// Returns [`EventProxyClosed`] if the event loop has already exited.
     pub fn send_event(&self, payload: T) -> Result<(), EventProxyClosed<T>> {
         self.0
-            .send_event(payload)
-            .map_err(|e| EventProxyClosed(e.0))
+            .send_event(LoopMessage::User(payload))
+            .map_err(|e| match e.0 {
+                LoopMessage::User(payload) => EventProxyClosed(payload),
+                LoopMessage::Wake => unreachable!("a `User` message comes back as itself"),
+            })
     }
 }
 
@@ -599,7 +611,7 @@ fn push_custom_event<P: Presenter>(id: u64, term: &mut Terminal<WindowBackend<P>
 /// [`Flow::Idle`](retroglyph_core::app::Flow::Idle).
 fn run_windowed_with_typed_proxy_and_exit_flag<T, P, F, O, D>(
     config: WindowConfig,
-    terminal: Terminal<WindowBackend<P>>,
+    mut terminal: Terminal<WindowBackend<P>>,
     app_loop: F,
     on_proxy: O,
     on_custom_event: D,
@@ -609,12 +621,17 @@ fn run_windowed_with_typed_proxy_and_exit_flag<T, P, F, O, D>(
 where
     T: Send + 'static,
     P: Presenter + 'static,
-    F: FnMut(&mut Terminal<WindowBackend<P>>) + 'static,
+    F: AppLoop<P> + 'static,
     O: FnOnce(EventProxy<T>),
     D: FnMut(T, &mut Terminal<WindowBackend<P>>) + 'static,
 {
-    let event_loop = EventLoop::<T>::with_user_event().build()?;
+    let event_loop = EventLoop::<LoopMessage<T>>::with_user_event().build()?;
     on_proxy(EventProxy(event_loop.create_proxy()));
+    let wake = event_loop.create_proxy();
+    terminal.backend_mut().set_send_hook(move || {
+        // Fails only once the loop has exited, when there is nothing left to wake.
+        let _ = wake.send_event(LoopMessage::Wake);
+    });
 
     // `Some(0)` has no finite pacing interval to express, so it falls back to uncapped rather
     // than computing `Duration::from_secs_f64(f64::INFINITY)` (which panics).
@@ -845,12 +862,16 @@ where
     run_windowed_with_typed_proxy_and_exit_flag(
         config,
         terminal,
-        move |term| {
+        move |term: &mut Terminal<WindowBackend<P>>| {
             let now = web_time::Instant::now();
             let delta = now.duration_since(last);
             last = now;
             let frame = retroglyph_core::app::Frame::new(delta, frame_count);
             frame_count = frame_count.wrapping_add(1);
+            // Animated sprites run on the frame clock, not on grid writes: a cell holding one
+            // changes pixels here with nothing in the grid changing, so an app has to keep
+            // returning `Flow::Continue` for them to play (an `Idle` frame skips the present).
+            term.backend_mut().presenter_mut().advance_animations(delta);
             match app.update(term, &frame) {
                 retroglyph_core::app::Flow::Exit => exit_requested_in_loop.set(true),
                 // Nothing changed: tell `handle_redraw_requested` to skip its automatic present
@@ -869,6 +890,76 @@ where
     )
 }
 
+/// Same as [`run_app_on`], but drives an app written against
+/// [`Box<dyn DynBackend>`](retroglyph_core::backend::DynBackend) instead of the concrete
+/// `WindowBackend<P>`.
+///
+/// This is what lets one `App` type run on whichever backend is picked at runtime. Each frame
+/// the terminal is boxed with [`Terminal::into_dyn`] for `init`/`update` and unboxed again with
+/// [`Terminal::downcast`] for the automatic present, so the app sees exactly the terminal it
+/// would see under [`run_app_on`]. An app that swaps in a different backend through
+/// [`Terminal::map_backend`] cannot be presented here any more: that is logged and the loop exits.
+///
+/// # Errors
+///
+/// Returns [`EventLoopError`] if the event loop cannot be
+/// created or fails while running.
+pub fn run_app_on_dyn<P, A>(
+    config: WindowConfig,
+    terminal: Terminal<WindowBackend<P>>,
+    mut app: A,
+) -> Result<(), EventLoopError>
+where
+    P: Presenter + 'static,
+    P::Error: Send + Sync,
+    A: retroglyph_core::app::App<Box<dyn DynBackend>> + 'static,
+{
+    let mut boxed = terminal.into_dyn();
+    app.init(&mut boxed);
+    let Ok(terminal) = boxed.downcast::<WindowBackend<P>>() else {
+        log::error!("app replaced its window backend during init; nothing left to present");
+        return Ok(());
+    };
+    let mut frame_count = 0u64;
+    let mut last = web_time::Instant::now();
+    let exit_requested = Rc::new(Cell::new(false));
+    let exit_requested_in_loop = exit_requested.clone();
+    let skip_present = Rc::new(Cell::new(false));
+    let skip_present_in_loop = skip_present.clone();
+    run_windowed_with_typed_proxy_and_exit_flag(
+        config,
+        terminal,
+        SlotLoop(move |slot: &mut Option<Terminal<WindowBackend<P>>>| {
+            let Some(mut term) = slot.take() else {
+                return;
+            };
+            let now = web_time::Instant::now();
+            let delta = now.duration_since(last);
+            last = now;
+            let frame = retroglyph_core::app::Frame::new(delta, frame_count);
+            frame_count = frame_count.wrapping_add(1);
+            // See `run_app_on_with_typed_proxy`.
+            term.backend_mut().presenter_mut().advance_animations(delta);
+            let mut boxed = term.into_dyn();
+            match app.update(&mut boxed, &frame) {
+                retroglyph_core::app::Flow::Exit => exit_requested_in_loop.set(true),
+                // See `run_app_on_with_typed_proxy` for both of these arms.
+                retroglyph_core::app::Flow::Idle => skip_present_in_loop.set(true),
+                _ => {}
+            }
+            *slot = boxed.downcast::<WindowBackend<P>>().ok();
+            if slot.is_none() {
+                log::error!("app replaced its window backend; nothing left to present");
+                exit_requested_in_loop.set(true);
+            }
+        }),
+        |_proxy: EventProxy| {},
+        push_custom_event,
+        exit_requested,
+        skip_present,
+    )
+}
+
 /// Initial window dimensions used before the first Resized event.
 struct InitWindowSize {
     width: u32,
@@ -943,6 +1034,41 @@ const fn button_mask(button: MouseButton) -> u8 {
     }
 }
 
+/// The cell under window pixel `(x, y)` and the frame pixel shown there, both mapped through
+/// `geometry`'s viewport. `None` over a letterbox bar, where there is no cell to report.
+fn pointer_at(geometry: CellGeometry, (x, y): (f64, f64)) -> Option<(Pos, PhysicalPos)> {
+    let pos = geometry.pixel_to_cell(x, y)?;
+    let (x, y) = geometry.window_to_frame(x, y);
+    Some((pos, translate_physical_pos(x, y)))
+}
+
+/// What [`WindowApp`] calls on every redraw.
+///
+/// Every closure-based driver passes a plain `FnMut(&mut Terminal<..>)` (the blanket impl below).
+/// [`run_app_on_dyn`] needs the terminal by value for the length of a frame, to box its backend
+/// and unbox it again afterwards, so it gets the whole slot through [`SlotLoop`] instead. A loop
+/// that leaves the slot empty must also have asked the event loop to exit.
+trait AppLoop<P: Presenter> {
+    fn run(&mut self, terminal: &mut Option<Terminal<WindowBackend<P>>>);
+}
+
+impl<P: Presenter, F: FnMut(&mut Terminal<WindowBackend<P>>)> AppLoop<P> for F {
+    fn run(&mut self, terminal: &mut Option<Terminal<WindowBackend<P>>>) {
+        if let Some(term) = terminal.as_mut() {
+            self(term);
+        }
+    }
+}
+
+/// An [`AppLoop`] that takes the terminal's whole slot rather than a borrow of the terminal.
+struct SlotLoop<G>(G);
+
+impl<P: Presenter, G: FnMut(&mut Option<Terminal<WindowBackend<P>>>)> AppLoop<P> for SlotLoop<G> {
+    fn run(&mut self, terminal: &mut Option<Terminal<WindowBackend<P>>>) {
+        (self.0)(terminal);
+    }
+}
+
 /// The winit `ApplicationHandler`: owns the window, the terminal, and the
 /// per-frame closure.
 ///
@@ -1323,10 +1449,10 @@ const fn system_theme_event(theme: winit::window::Theme) -> Event {
     }
 }
 
-impl<P, F, T, D> ApplicationHandler<T> for WindowApp<P, F, T, D>
+impl<P, F, T, D> ApplicationHandler<LoopMessage<T>> for WindowApp<P, F, T, D>
 where
     P: Presenter,
-    F: FnMut(&mut Terminal<WindowBackend<P>>) + 'static,
+    F: AppLoop<P> + 'static,
     T: 'static,
     D: FnMut(T, &mut Terminal<WindowBackend<P>>) + 'static,
 {
@@ -1355,8 +1481,13 @@ where
         }
     }
 
-    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: T) {
-        self.handle_user_event(event);
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, message: LoopMessage<T>) {
+        match message {
+            LoopMessage::User(event) => self.handle_user_event(event),
+            // The sent event already sits in the backend's inbox; `poll_event` picks it up on
+            // the frame this schedules.
+            LoopMessage::Wake => self.needs_redraw = true,
+        }
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
@@ -1412,7 +1543,7 @@ where
 impl<P, F, T, D> WindowApp<P, F, T, D>
 where
     P: Presenter,
-    F: FnMut(&mut Terminal<WindowBackend<P>>) + 'static,
+    F: AppLoop<P> + 'static,
     D: FnMut(T, &mut Terminal<WindowBackend<P>>) + 'static,
 {
     /// Ask winit for a `RedrawRequested`, if the window exists yet.
@@ -1525,12 +1656,16 @@ where
     /// independently of the consecutive-failure counter below, which is scoped to the surface
     /// present.
     fn handle_redraw_requested(&mut self) {
-        let Some(term) = self.terminal.as_mut() else {
+        let Some(present_count_before) = self.terminal.as_ref().map(Terminal::present_count) else {
             return;
         };
         self.skip_present.set(false);
-        let present_count_before = term.present_count();
-        (self.app_loop)(term);
+        self.app_loop.run(&mut self.terminal);
+        // A slot-based loop can leave the slot empty (see `AppLoop`); it has already asked the
+        // loop to exit, so there is nothing left to present.
+        let Some(term) = self.terminal.as_mut() else {
+            return;
+        };
         if !self.skip_present.get()
             && term.present_count() == present_count_before
             && let Err(e) = term.present()
@@ -1661,10 +1796,22 @@ where
     /// [`on_scale_factor_changed`](Self::on_scale_factor_changed): both need
     /// the same clamp-to-cell-grid math, just triggered by different winit
     /// events.
+    ///
+    /// A presenter whose [`ScalingMode`](crate::scaling::ScalingMode) doesn't
+    /// reflow keeps its grid: it's handed the whole window instead, and no
+    /// [`Event::Resize`] is pushed since the grid didn't change. Entering or
+    /// leaving fullscreen arrives here as an ordinary resize, so the
+    /// presenter's viewport (and with it mouse mapping) follows either way.
     fn resize_to(&mut self, size: winit::dpi::PhysicalSize<u32>) {
         let Some(term) = self.terminal.as_mut() else {
             return;
         };
+        if !term.backend().presenter().scaling().reflows() {
+            term.backend_mut()
+                .presenter_mut()
+                .resize_surface(size.width.max(1), size.height.max(1));
+            return;
+        }
         let (cell_w, cell_h) = term.backend().presenter().cell_size();
         // Clamp to at least one cell: a window smaller than one cell in
         // either dimension would otherwise divide down to 0 cols/rows,
@@ -1727,11 +1874,12 @@ where
         let scale = 1.0;
         let (x, y) = (position.x * scale, position.y * scale);
         self.cursor_px = (x, y);
-        let px = translate_physical_pos(x, y);
         let Some(term) = self.terminal.as_mut() else {
             return;
         };
-        let pos = term.backend().presenter().geometry().pixel_to_cell(x, y);
+        let Some((pos, px)) = pointer_at(term.backend().presenter().geometry(), (x, y)) else {
+            return;
+        };
         // Report a drag (rather than a plain move) while any button is held. Left takes
         // priority over Right over Middle when more than one is held at once: an arbitrary but
         // deterministic choice, matching the order the buttons are declared in `MouseButton`.
@@ -1761,15 +1909,8 @@ where
         let Some(btn) = translate_mouse_button(button) else {
             return;
         };
-        let px = self.cursor_physical_pos();
-        let Some(term) = self.terminal.as_mut() else {
-            return;
-        };
-        let pos = term
-            .backend()
-            .presenter()
-            .geometry()
-            .pixel_to_cell(self.cursor_px.0, self.cursor_px.1);
+        // Track the button even over a letterbox bar, where no event is reported, so a drag
+        // that ends there doesn't leave it held.
         let kind = if state.is_pressed() {
             self.held_buttons |= button_mask(btn);
             MouseEventKind::Down(btn)
@@ -1777,6 +1918,13 @@ where
             self.held_buttons &= !button_mask(btn);
             MouseEventKind::Up(btn)
         };
+        let Some(term) = self.terminal.as_mut() else {
+            return;
+        };
+        let Some((pos, px)) = pointer_at(term.backend().presenter().geometry(), self.cursor_px)
+        else {
+            return;
+        };
         term.backend_mut()
             .push_event(Event::Mouse(MouseEvent::with_pixel_position(
                 kind,
@@ -1787,15 +1935,13 @@ where
     }
 
     fn on_mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta) {
-        let px = self.cursor_physical_pos();
         let Some(term) = self.terminal.as_mut() else {
             return;
         };
-        let pos = term
-            .backend()
-            .presenter()
-            .geometry()
-            .pixel_to_cell(self.cursor_px.0, self.cursor_px.1);
+        let Some((pos, px)) = pointer_at(term.backend().presenter().geometry(), self.cursor_px)
+        else {
+            return;
+        };
         let (scroll_x, scroll_y) = match delta {
             winit::event::MouseScrollDelta::LineDelta(x, y) => (f64::from(x), f64::from(y)),
             winit::event::MouseScrollDelta::PixelDelta(p) => (p.x, p.y),
@@ -1861,11 +2007,6 @@ where
         }
     }
 
-    /// Convert the cached cursor pixel position to [`PhysicalPos`].
-    const fn cursor_physical_pos(&self) -> PhysicalPos {
-        translate_physical_pos(self.cursor_px.0, self.cursor_px.1)
-    }
-
     /// Push [`Event::FocusGained`]/[`Event::FocusLost`], and on loss, reset state that only makes
     /// sense while the window is focused.
     ///
@@ -1913,6 +2054,7 @@ where
 mod tests {
     use super::*;
     use crate::geometry::CellGeometry;
+    use crate::scaling::ScalingMode;
     use retroglyph_core::backend::DrawCell;
     use retroglyph_core::backend::Output;
     use retroglyph_core::event::{MouseButton, MouseEvent, MouseEventKind};
@@ -2112,6 +2254,12 @@ mod tests {
         /// can assert that `resize_to` keeps it in sync with the surface immediately, as a
         /// byproduct of the `Terminal::resize` call it now makes directly.
         size: Cell<Size>,
+        /// The mode reported by [`Presenter::scaling`]; [`ScalingMode::Reflow`] unless a test
+        /// opts into another.
+        scaling: ScalingMode,
+        /// The size last passed to [`Presenter::resize_surface`], which the viewport in
+        /// [`Presenter::geometry`] is placed in while `scaling` doesn't reflow.
+        window: Cell<Option<(u32, u32)>>,
     }
 
     impl Default for MockPresenter {
@@ -2119,6 +2267,8 @@ mod tests {
             Self {
                 last_scale_factor: Cell::new(None),
                 size: Cell::new(Size::new(10, 5)),
+                scaling: ScalingMode::Reflow,
+                window: Cell::new(None),
             }
         }
     }
@@ -2167,14 +2317,29 @@ mod tests {
             Ok(())
         }
 
-        fn resize_surface(&mut self, _width: u32, _height: u32) {}
+        fn resize_surface(&mut self, width: u32, height: u32) {
+            self.window.set(Some((width, height)));
+        }
 
         fn present(&mut self) -> Result<(), Self::SurfaceError> {
             Ok(())
         }
 
         fn geometry(&self) -> CellGeometry {
-            CellGeometry::new(8, 16, 1)
+            let geometry = CellGeometry::new(8, 16, 1);
+            if self.scaling.reflows() {
+                return geometry;
+            }
+            let size = self.size.get();
+            let frame = geometry.surface_size(size.width, size.height);
+            geometry.with_viewport(
+                self.scaling
+                    .viewport(frame, self.window.get().unwrap_or(frame)),
+            )
+        }
+
+        fn scaling(&self) -> ScalingMode {
+            self.scaling
         }
 
         fn scale_factor_changed(&mut self, scale_factor: f64) {
@@ -3208,6 +3373,72 @@ mod tests {
         assert!(app.exit_requested.get());
     }
 
+    type MockCustomHandler = fn(u64, &mut Terminal<WindowBackend<MockPresenter>>);
+
+    /// A [`WindowApp`] around any [`AppLoop`], for the [`SlotLoop`] tests below.
+    fn window_app_with<F: AppLoop<MockPresenter>>(
+        app_loop: F,
+    ) -> WindowApp<MockPresenter, F, u64, MockCustomHandler> {
+        WindowApp {
+            terminal: Some(Terminal::new(WindowBackend::new(MockPresenter::default()))),
+            app_loop,
+            on_custom_event: push_custom_event,
+            _user_event: PhantomData,
+            window: None,
+            title: String::new(),
+            init_size: InitWindowSize {
+                width: 80,
+                height: 80,
+            },
+            attrs: WindowAttrs::default(),
+            current_modifiers: KeyModifiers::NONE,
+            cursor_px: (0.0, 0.0),
+            active_touch: None,
+            held_buttons: 0,
+            frame_interval: None,
+            event_driven: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            next_frame: std::time::Instant::now(),
+            exit_requested: Rc::new(Cell::new(false)),
+            skip_present: Rc::new(Cell::new(false)),
+            needs_redraw: false,
+            consecutive_present_errors: 0,
+        }
+    }
+
+    #[test]
+    fn slot_loop_boxing_the_terminal_for_the_frame_still_gets_the_automatic_present() {
+        // The shape of `run_app_on_dyn`'s loop: box the backend, let the "app" run, unbox it.
+        let mut app = window_app_with(SlotLoop(
+            |slot: &mut Option<Terminal<WindowBackend<MockPresenter>>>| {
+                let mut boxed = slot.take().expect("terminal is in the slot").into_dyn();
+                boxed.requeue_events([Event::Custom(7)]);
+                *slot = boxed.downcast().ok();
+            },
+        ));
+
+        app.handle_window_event(WindowEvent::RedrawRequested);
+
+        let term = app.terminal.as_mut().expect("terminal was put back");
+        assert_eq!(term.present_count(), 1);
+        assert_eq!(term.poll(Duration::ZERO), Some(Event::Custom(7)));
+    }
+
+    #[test]
+    fn slot_loop_leaving_the_slot_empty_stops_later_events_from_touching_the_terminal() {
+        let mut app = window_app_with(SlotLoop(
+            |slot: &mut Option<Terminal<WindowBackend<MockPresenter>>>| {
+                slot.take();
+            },
+        ));
+
+        app.handle_window_event(WindowEvent::RedrawRequested);
+        app.handle_window_event(WindowEvent::RedrawRequested);
+        app.handle_window_event(WindowEvent::Focused(true));
+
+        assert!(app.terminal.is_none());
+    }
+
     #[test]
     fn theme_changed_pushes_mapped_system_theme_event() {
         let mut app = test_window_app();
@@ -3403,6 +3634,94 @@ mod tests {
         assert_eq!(app.terminal.as_ref().unwrap().size(), Size::new(11, 5));
     }
 
+    #[test]
+    fn a_letterboxing_presenter_keeps_its_grid_and_gets_the_whole_window() {
+        let mut app = test_window_app();
+        app.terminal
+            .as_mut()
+            .unwrap()
+            .backend_mut()
+            .presenter_mut()
+            .scaling = ScalingMode::Integer { bars: (0, 0, 0) };
+        app.resize_to(winit::dpi::PhysicalSize::new(333, 201));
+        let term = app.terminal.as_ref().unwrap();
+        assert_eq!(term.size(), Size::new(10, 5));
+        assert_eq!(term.backend().presenter().window.get(), Some((333, 201)));
+        assert_eq!(poll(&mut app), None, "the grid didn't change size");
+    }
+
+    #[test]
+    fn mouse_maps_through_the_letterbox_across_a_fullscreen_toggle() {
+        // A 10x5 grid of 8x16 cells is an 80x80 frame. Cell (3, 2) starts at frame pixel
+        // (24, 32); each case clicks one pixel inside it.
+        let mut app = test_window_app();
+        app.terminal
+            .as_mut()
+            .unwrap()
+            .backend_mut()
+            .presenter_mut()
+            .scaling = ScalingMode::Integer { bars: (0, 0, 0) };
+        // Windowed at 200x100: scale 1, centered at (60, 10). Fullscreen at 1920x1080: scale 13,
+        // centered at (440, 20). Then back to windowed. The pixel position is the frame pixel.
+        for (window, cursor, frame_px) in [
+            ((200, 100), (85.0, 43.0), PhysicalPos { x: 25, y: 33 }),
+            ((1920, 1080), (753.0, 437.0), PhysicalPos { x: 24, y: 32 }),
+            ((200, 100), (85.0, 43.0), PhysicalPos { x: 25, y: 33 }),
+        ] {
+            app.resize_to(winit::dpi::PhysicalSize::new(window.0, window.1));
+            app.handle_window_event(WindowEvent::CursorMoved {
+                device_id: winit::event::DeviceId::dummy(),
+                position: winit::dpi::PhysicalPosition::new(cursor.0, cursor.1),
+            });
+            let Some(Event::Mouse(mouse)) = poll(&mut app) else {
+                panic!("expected a mouse event at {window:?}");
+            };
+            assert_eq!(mouse.position, Pos { x: 3, y: 2 }, "at {window:?}");
+            assert_eq!(mouse.pixel_position, Some(frame_px), "at {window:?}");
+        }
+    }
+
+    #[test]
+    fn the_mouse_over_a_letterbox_bar_reports_nothing() {
+        // The 80x80 frame centered in a 200x100 window at scale 1: bars 60 pixels wide on the
+        // left and right, 10 high above and below.
+        let mut app = test_window_app();
+        app.terminal
+            .as_mut()
+            .unwrap()
+            .backend_mut()
+            .presenter_mut()
+            .scaling = ScalingMode::Integer { bars: (0, 0, 0) };
+        app.resize_to(winit::dpi::PhysicalSize::new(200, 100));
+        for cursor in [(30.0, 50.0), (100.0, 5.0), (170.0, 50.0), (100.0, 95.0)] {
+            app.handle_window_event(WindowEvent::CursorMoved {
+                device_id: winit::event::DeviceId::dummy(),
+                position: winit::dpi::PhysicalPosition::new(cursor.0, cursor.1),
+            });
+            app.handle_window_event(WindowEvent::MouseInput {
+                device_id: winit::event::DeviceId::dummy(),
+                state: winit::event::ElementState::Pressed,
+                button: winit::event::MouseButton::Left,
+            });
+            app.handle_window_event(WindowEvent::MouseInput {
+                device_id: winit::event::DeviceId::dummy(),
+                state: winit::event::ElementState::Released,
+                button: winit::event::MouseButton::Left,
+            });
+            assert_eq!(poll(&mut app), None, "at {cursor:?}");
+        }
+        // The release over the bar still let go of the button: back over the frame, a move is
+        // a move, not a drag.
+        app.handle_window_event(WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(85.0, 43.0),
+        });
+        let Some(Event::Mouse(mouse)) = poll(&mut app) else {
+            panic!("expected a mouse event over the frame");
+        };
+        assert_eq!(mouse.kind, MouseEventKind::Moved);
+    }
+
     #[test]
     fn resized_below_one_cell_clamps_surface_and_event_to_1x1() {
         // Regression test for #140: an 8x16-cell presenter resized to a
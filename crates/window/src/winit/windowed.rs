@@ -3,12 +3,13 @@
 use core::fmt;
 
 use retroglyph_core::app::{App, Launch, RunOptions};
+use retroglyph_core::backend::{DynBackend, Output};
 use retroglyph_core::terminal::Terminal;
 
 use crate::backend::WindowBackend;
 use crate::presenter_builder::PresenterBuilder;
 use crate::winit::EventLoopError;
-use crate::winit::run::{WindowConfig, run_app_on};
+use crate::winit::run::{WindowConfig, run_app_on, run_app_on_dyn};
 
 /// Pairs a windowed backend's [`PresenterBuilder`] with the window title [`Launch`] needs to
 /// open one.
@@ -60,6 +61,35 @@ impl<B: PresenterBuilder> Windowed<B> {
             title: title.into(),
         }
     }
+
+    /// Same as [`Launch::launch`], but for an app written against
+    /// [`Box<dyn DynBackend>`](DynBackend) rather than this builder's concrete backend, so the
+    /// same app value can be launched on whichever backend is chosen at runtime.
+    ///
+    /// See [`run_app_on_dyn`] for how the terminal is handed to the app.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Launch::launch`].
+    pub fn launch_dyn<A>(
+        self,
+        app: A,
+        options: RunOptions,
+    ) -> Result<(), WindowedLaunchError<B::Error>>
+    where
+        A: App<Box<dyn DynBackend>> + 'static,
+        B::Presenter: 'static,
+        <B::Presenter as Output>::Error: Send + Sync,
+    {
+        let presenter = self
+            .builder
+            .build_presenter()
+            .map_err(WindowedLaunchError::Build)?;
+        let config =
+            WindowConfig::fit(&presenter, self.title, None, true).with_run_options(options);
+        let terminal = Terminal::new(WindowBackend::new(presenter));
+        run_app_on_dyn(config, terminal, app).map_err(WindowedLaunchError::EventLoop)
+    }
 }
 
 /// The error [`Windowed`]'s [`Launch`] impl can fail with.
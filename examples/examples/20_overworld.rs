@@ -2398,10 +2398,8 @@ impl Overworld {
         for event in term.drain_events() {
             match event {
                 Event::Close => return false,
-                Event::Key(k) if k.is_down() => {
-                    if !self.handle_key(k.code, k.modifiers) {
-                        return false;
-                    }
+                Event::Key(k) if k.is_down() && !self.handle_key(k.code, k.modifiers) => {
+                    return false;
                 }
                 Event::Mouse(m) => self.handle_mouse(m.kind, m.position),
                 _ => {}
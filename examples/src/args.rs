@@ -10,6 +10,10 @@
 //! `portable-pty`'s `CommandBuilder::env` (not CLI args) for essentially every `svg_snapshot`
 //! test in the gallery, so removing env-var support outright would break that whole suite.
 //!
+//! `--backend <name>`/`RG_BACKEND` aren't parsed here: [`launch`](crate::launch) picks its backend
+//! through `retroglyph::BackendKind::detect`, which reads both itself, and this parser skips
+//! flags it doesn't know.
+//!
 //! Hand-rolled rather than a dependency (`lexopt`/`pico-args`): four flags, all with a single
 //! value, is small enough that a parsing crate would add more surface than it saves.
 
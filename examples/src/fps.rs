@@ -13,7 +13,7 @@
 #![allow(clippy::redundant_pub_crate)]
 
 #[cfg(feature = "crossterm")]
-use retroglyph::backend::{Compositing, Cursor, Input, Output};
+use retroglyph::backend::{Clipboard, ClipboardError, Compositing, Cursor, Input, Output};
 #[cfg(feature = "crossterm")]
 use retroglyph::event::Event;
 #[cfg(feature = "crossterm")]
@@ -23,6 +23,8 @@ use std::cell::Cell;
 #[cfg(feature = "crossterm")]
 use std::rc::Rc;
 #[cfg(feature = "crossterm")]
+use std::task::{Context, Poll, ready};
+#[cfg(feature = "crossterm")]
 use std::time::Duration;
 
 /// Whether the overlay starts visible, from `--fps` (see [`crate::args`]) or, if that flag
@@ -166,6 +168,16 @@ impl<B: Input> Input for ToggleFilter<B> {
     fn push_event(&mut self, event: Event) {
         self.inner.push_event(event);
     }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<Event> {
+        loop {
+            let event = ready!(self.inner.poll_next_event(cx));
+            if !crate::perf_overlay::is_toggle_key(&event) {
+                return Poll::Ready(event);
+            }
+            self.presses.set(self.presses.get().saturating_add(1));
+        }
+    }
 }
 
 #[cfg(feature = "crossterm")]
@@ -179,6 +191,17 @@ impl<B: Cursor> Cursor for ToggleFilter<B> {
     }
 }
 
+#[cfg(feature = "crossterm")]
+impl<B: Clipboard> Clipboard for ToggleFilter<B> {
+    fn clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.inner.clipboard_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.inner.set_clipboard_text(text)
+    }
+}
+
 /// The wasm-only floating toggle button: the browser counterpart to `PerfOverlayApp`'s built-in
 /// backtick/F1 key.
 ///
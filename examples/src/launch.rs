@@ -1,9 +1,10 @@
 //! The [`Example`] trait and `launch::<E>()` backend dispatch.
 //!
 //! Replaces the old `rg_run!`/`rg_run_software!` macros with plain generic
-//! functions: `launch::<E>()` picks a backend from the crate's enabled
-//! Cargo features (`software` > `crossterm` > headless-stdout fallback,
-//! mirroring the old macro's priority) and drives an [`Example`] on it.
+//! functions: `launch::<E>()` picks a backend among the crate's enabled
+//! Cargo features (at startup through `retroglyph::run_any`'s selection
+//! natively, at compile time on `wasm32`, headless stdout with none) and
+//! drives an [`Example`] on it.
 //! Nothing here is textually generated per example -- every example calls
 //! the exact same `launch::<Self>()`.
 //!
@@ -21,6 +22,16 @@
 ))]
 use crate::perf_overlay::PerfOverlayApp;
 use retroglyph::app::Frame;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(
+        feature = "crossterm",
+        feature = "software",
+        feature = "gl",
+        feature = "wgpu"
+    )
+))]
+use retroglyph::app::RunOptions;
 #[cfg(any(
     feature = "crossterm",
     feature = "software",
@@ -30,9 +41,11 @@ use retroglyph::app::Frame;
 use retroglyph::app::{App, Flow};
 use retroglyph::backend::Backend;
 #[cfg(feature = "crossterm")]
-use retroglyph::backend::Output;
+use retroglyph::backend::{DynBackend, Output};
 use retroglyph::terminal::Terminal;
 #[cfg(feature = "crossterm")]
+use std::cell::Cell;
+#[cfg(feature = "crossterm")]
 use std::rc::Rc;
 use std::time::Duration;
 
@@ -395,11 +408,17 @@ fn run_windowed<E: Example, B: retroglyph::PresenterBuilder>(
 /// fails to start.
 #[cfg(feature = "software")]
 pub fn run_software<E: Example>() {
-    run_software_with::<E>(E::configure(
+    run_software_with::<E>(software_builder::<E>());
+}
+
+/// [`run_software`]'s builder: 50x25 at `scale(2)`, then [`Example::configure`].
+#[cfg(feature = "software")]
+fn software_builder<E: Example>() -> retroglyph::software::config::SoftwareBackendBuilder {
+    E::configure(
         retroglyph::software::config::SoftwareBackendBuilder::new()
             .grid_size(50, 25)
             .scale(2),
-    ));
+    )
 }
 
 /// Runs `E` on the software backend using a caller-supplied, already-
@@ -442,14 +461,17 @@ pub fn run_software_with<E: Example>(
 /// Panics if the GL backend fails to initialize, or if the event loop fails to start.
 #[cfg(feature = "gl")]
 pub fn run_gl<E: Example>() {
-    run_windowed::<E, _>(
-        E::configure(
-            retroglyph::gl::config::GlBackendBuilder::new()
-                .grid_size(50, 25)
-                .scale(2),
-        ),
-        "gl",
-    );
+    run_windowed::<E, _>(gl_builder::<E>(), "gl");
+}
+
+/// [`run_gl`]'s builder: 50x25 at `scale(2)`, then [`Example::configure`].
+#[cfg(feature = "gl")]
+fn gl_builder<E: Example>() -> retroglyph::gl::config::GlBackendBuilder {
+    E::configure(
+        retroglyph::gl::config::GlBackendBuilder::new()
+            .grid_size(50, 25)
+            .scale(2),
+    )
 }
 
 // ── wgpu backend ────────────────────────────────────────────────────────────
@@ -475,14 +497,17 @@ pub fn run_gl<E: Example>() {
 /// Panics if the wgpu backend fails to initialize, or if the event loop fails to start.
 #[cfg(feature = "wgpu")]
 pub fn run_wgpu<E: Example>() {
-    run_windowed::<E, _>(
-        E::configure(
-            retroglyph::wgpu::config::WgpuBackendBuilder::new()
-                .grid_size(50, 25)
-                .scale(2),
-        ),
-        "wgpu",
-    );
+    run_windowed::<E, _>(wgpu_builder::<E>(), "wgpu");
+}
+
+/// [`run_wgpu`]'s builder: 50x25 at `scale(2)`, then [`Example::configure`].
+#[cfg(feature = "wgpu")]
+fn wgpu_builder<E: Example>() -> retroglyph::wgpu::config::WgpuBackendBuilder {
+    E::configure(
+        retroglyph::wgpu::config::WgpuBackendBuilder::new()
+            .grid_size(50, 25)
+            .scale(2),
+    )
 }
 
 // ── Crossterm backend ───────────────────────────────────────────────────────
@@ -500,38 +525,47 @@ pub fn run_wgpu<E: Example>() {
 /// running.
 #[cfg(feature = "crossterm")]
 pub fn run_crossterm<E: Example>() -> std::io::Result<()> {
-    // The backend has to be wrapped in a `ToggleFilter` before the `Terminal` sees it, or the
-    // overlay's toggle key races the example's own `drain_events` and gets swallowed -- see
-    // `ToggleFilter`'s docs for why crossterm specifically needs this and the windowed backends
-    // don't.
-    let presses = crate::fps::TogglePresses::default();
-    let filter = crate::fps::ToggleFilter::new(
-        retroglyph::crossterm::Crossterm::new()?,
-        Rc::clone(&presses),
-    );
-    let app = CrosstermToggleApp {
-        inner: perf_overlay_app(ExampleApp::<E>::new(), "crossterm"),
-        presses,
-    };
+    run_picked::<E>(retroglyph::BackendKind::Crossterm).map_err(|err| match err {
+        retroglyph::LaunchError::Crossterm(err) => err,
+        err => std::io::Error::other(err),
+    })
+}
 
-    match crate::args::parsed().record.clone() {
-        None => retroglyph::app::run_on(Terminal::new(filter), app),
-        Some(path) => {
-            let recorder = retroglyph_recorder::FrameRecorder::new(filter);
-            let handle = recorder.handle();
-            let size = recorder.inner().size();
-            // `run_on` takes `Terminal<B>` (and so this `FrameRecorder`) by value and never
-            // hands it back -- `handle`, taken before this call, is how the captured frames
-            // survive it. Runs to completion (propagating any error) before saving, so a
-            // recording is only written for a session that actually ran; see `save_cast` for why
-            // a save failure itself doesn't override that result.
-            let result = retroglyph::app::run_on(Terminal::new(recorder), app);
-            save_cast(&handle, size, &path);
-            result
-        }
+/// Wraps the crossterm backend for [`run_picked`]: in a [`ToggleFilter`](crate::fps::ToggleFilter)
+/// counting into `presses`, and, when `--record <path>` is passed, in a
+/// [`retroglyph_recorder::FrameRecorder`] whose handle and size land in `recording` for
+/// [`save_cast`] once `E` quits.
+///
+/// The filter has to wrap the backend before the `Terminal` sees it, or the overlay's toggle key
+/// races the example's own `drain_events` and gets swallowed -- see `ToggleFilter`'s docs for why
+/// crossterm specifically needs this and the windowed backends don't.
+#[cfg(feature = "crossterm")]
+fn wrap_crossterm(
+    backend: retroglyph::crossterm::Crossterm,
+    presses: crate::fps::TogglePresses,
+    recording: &Recording,
+) -> Box<dyn DynBackend> {
+    let filter = crate::fps::ToggleFilter::new(backend, presses);
+    if crate::args::parsed().record.is_none() {
+        return Box::new(filter);
     }
+    let recorder = retroglyph_recorder::FrameRecorder::new(filter);
+    recording.set(Some((recorder.handle(), recorder.inner().size())));
+    Box::new(recorder)
 }
 
+/// Where [`wrap_crossterm`] leaves a `--record` session's recorder handle and size: the backend
+/// moves into the driver and never comes back, so this is how the captured frames outlive it.
+#[cfg(feature = "crossterm")]
+type Recording = Rc<
+    Cell<
+        Option<(
+            retroglyph_recorder::FrameRecorderHandle,
+            retroglyph::grid::Size,
+        )>,
+    >,
+>;
+
 /// Writes `handle`'s captured frames to `path` as asciicast v3, via
 /// [`retroglyph_recorder::write_cast`]. Errors are logged to stderr rather than propagated: by
 /// the time this runs, `E` has already quit (successfully or not) and the process is about to
@@ -754,49 +788,128 @@ fn headless_frame_count() -> u32 {
 
 // ── Backend dispatch ─────────────────────────────────────────────────────────
 //
-// Mutually exclusive by construction: at most one of these `launch` items is compiled in for any
-// given feature set, mirroring the old rg_run! macro's priority, extended with `wgpu` as a third
-// windowed tier below `gl`: software > gl > wgpu > crossterm > wasm-headless > wasm-terminal >
-// headless stdout fallback. `wasm-headless`/`wasm-terminal` on non-wasm32 targets (e.g. `cargo
-// check --features wasm-headless` on a host) fall through to the headless-stdout arm, so every
-// feature combination stays host-checkable.
-
-/// Picks a backend from the crate's enabled Cargo features and runs `E` on
+// Natively, every backend feature the crate was built with is a candidate, and
+// `retroglyph::run_any`'s selection picks among them when the example starts: `--backend <name>`
+// or `RG_BACKEND`, else the first windowed backend (software > gl > wgpu) when there's a display,
+// else crossterm. On `wasm32` the page has already chosen its variant by the features it was
+// built with, so the same priority applies at compile time instead, followed by wasm-headless >
+// wasm-terminal. Every feature combination without a backend falls through to headless stdout,
+// including `wasm-headless`/`wasm-terminal` on a non-wasm32 target (e.g. `cargo check --features
+// wasm-headless` on a host), so every combination stays host-checkable.
+
+/// Runs `E` on `kind` through [`retroglyph::Backends`], the configurable form of
+/// [`retroglyph::run_any`], with the same per-backend setup [`run_software`]/[`run_gl`]/
+/// [`run_wgpu`]/[`run_crossterm`] document: the 50x25-at-2x grid and [`Example::configure`] for a
+/// windowed backend, paced at [`TARGET_FPS`], or the toggle filter and `--record` for crossterm.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(
+        feature = "crossterm",
+        feature = "software",
+        feature = "gl",
+        feature = "wgpu"
+    )
+))]
+fn run_picked<E: Example>(kind: retroglyph::BackendKind) -> Result<(), retroglyph::LaunchError> {
+    let backends = retroglyph::Backends::new(E::NAME).pick(kind);
+    #[cfg(feature = "software")]
+    let backends = backends.software(software_builder::<E>());
+    #[cfg(feature = "gl")]
+    let backends = backends.gl(gl_builder::<E>());
+    #[cfg(feature = "wgpu")]
+    let backends = backends.wgpu(wgpu_builder::<E>());
+
+    let options = RunOptions::default();
+    #[cfg(any(feature = "software", feature = "gl", feature = "wgpu"))]
+    let options = match TARGET_FPS {
+        Some(fps) if kind.is_windowed() => RunOptions::animated(fps),
+        _ => options,
+    };
+    let inner = perf_overlay_app(ExampleApp::<E>::new(), kind.name());
+
+    #[cfg(feature = "crossterm")]
+    {
+        let presses = crate::fps::TogglePresses::default();
+        let recording = Recording::default();
+        let backends = backends.map_crossterm({
+            let (presses, recording) = (Rc::clone(&presses), Rc::clone(&recording));
+            move |backend| wrap_crossterm(backend, presses, &recording)
+        });
+        // Harmless on a windowed backend: nothing counts presses there, so this only delegates.
+        let result = backends.launch(CrosstermToggleApp { inner, presses }, options);
+        // Runs to completion (propagating any error) before saving, so a recording is only
+        // written for a session that actually ran; see `save_cast` for why a save failure itself
+        // doesn't override that result.
+        if let (Some(path), Some((handle, size))) =
+            (crate::args::parsed().record.as_deref(), recording.take())
+        {
+            save_cast(&handle, size, path);
+        }
+        result
+    }
+    #[cfg(not(feature = "crossterm"))]
+    backends.launch(WasmToggleApp { inner }, options)
+}
+
+/// Picks a backend among the crate's enabled Cargo features and runs `E` on
 /// it. Call this (and nothing else) from every example's `main`.
 ///
+/// Natively the pick happens at startup, the same way [`retroglyph::run_any`] makes it: `--backend
+/// <name>` or `RG_BACKEND` names one (`crossterm`, `software`, `gl`, or `wgpu`), and otherwise a
+/// windowed backend wins when there's a display to open it on. A build with only one backend
+/// feature always runs on that one.
+///
 /// `--record <path>` (see [`crate::args`]) is honored by the crossterm and headless-stdout
-/// arms only, not this windowed (software) one: [`retroglyph_recorder::write_cast`] exports
-/// text/ANSI output, and this backend presents pixels, not `DrawCell` glyph diffs, so there is
-/// nothing meaningful for a `FrameRecorder` to capture here. Passing `--record` to a
-/// software/gl/wgpu build is silently ignored rather than an error, matching this crate's
-/// existing convention for a flag or env var a particular build doesn't apply to (see
-/// `time_scale`'s `wasm32` note for the same convention elsewhere).
-#[cfg(feature = "software")]
+/// backends only, not the windowed (software/gl/wgpu) ones: [`retroglyph_recorder::write_cast`]
+/// exports text/ANSI output, and those backends present pixels, not `DrawCell` glyph diffs, so
+/// there is nothing meaningful for a `FrameRecorder` to capture there. Passing `--record` to a
+/// windowed run is silently ignored rather than an error, matching this crate's existing
+/// convention for a flag or env var a particular build doesn't apply to (see `time_scale`'s
+/// `wasm32` note for the same convention elsewhere).
+///
+/// # Panics
+///
+/// Panics if `--backend`/`RG_BACKEND` names a backend this build didn't enable, or if the picked
+/// backend fails to start or fails while `E` is running.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(
+        feature = "crossterm",
+        feature = "software",
+        feature = "gl",
+        feature = "wgpu"
+    )
+))]
 pub fn launch<E: Example>() {
-    run_software::<E>();
+    let result = retroglyph::BackendKind::detect().and_then(run_picked::<E>);
+    if let Err(err) = result {
+        panic!("{err}");
+    }
 }
 
-/// See [`launch`]'s software-enabled overload. `gl` is the GPU windowed backend; `software` wins
-/// if both are somehow enabled.
-#[cfg(all(feature = "gl", not(feature = "software")))]
+/// See [`launch`]'s native overload. On `wasm32`, `software` wins over the other windowed
+/// backends.
+#[cfg(all(feature = "software", target_arch = "wasm32"))]
 pub fn launch<E: Example>() {
-    run_gl::<E>();
+    run_software::<E>();
 }
 
-/// See [`launch`]'s software-enabled overload. `wgpu` is the other GPU windowed backend; it loses
-/// to `software`/`gl` if either is also enabled, the same way `gl` loses to `software`.
-#[cfg(all(feature = "wgpu", not(any(feature = "software", feature = "gl"))))]
+/// See [`launch`]'s native overload. `gl` is the GPU windowed backend; `software` wins if both
+/// are somehow enabled.
+#[cfg(all(feature = "gl", not(feature = "software"), target_arch = "wasm32"))]
 pub fn launch<E: Example>() {
-    run_wgpu::<E>();
+    run_gl::<E>();
 }
 
-/// See [`launch`]'s software-enabled overload.
+/// See [`launch`]'s native overload. `wgpu` is the other GPU windowed backend; it loses to
+/// `software`/`gl` if either is also enabled, the same way `gl` loses to `software`.
 #[cfg(all(
-    feature = "crossterm",
-    not(any(feature = "software", feature = "gl", feature = "wgpu"))
+    feature = "wgpu",
+    not(any(feature = "software", feature = "gl")),
+    target_arch = "wasm32"
 ))]
 pub fn launch<E: Example>() {
-    run_crossterm::<E>().expect("crossterm backend failed");
+    run_wgpu::<E>();
 }
 
 /// No-op on `wasm32`: the real entry points for this backend are the